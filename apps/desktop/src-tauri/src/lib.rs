@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use log::LevelFilter;
 use mod_translator_core::job::runner::JobRunner;
-use tauri::{Manager, State};
+use tauri::{Manager, RunEvent, State};
 
 struct Shared(Arc<JobRunner>);
 
@@ -26,40 +26,6 @@ async fn cmd_cancel(state: State<'_, Shared>) -> Result<(), String> {
 
 pub fn run() {
     tauri::Builder::<tauri::Wry>::default()
-        .plugin(tauri_plugin_stronghold::Builder::new(|password| {
-            // 앱 고유 키로 Stronghold 암호화 (실제 배포시 더 강력한 키 필요)
-            // argon2 또는 더 안전한 해시를 사용하는 것이 권장됨
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            
-            let mut hasher = DefaultHasher::new();
-            password.hash(&mut hasher);
-            b"mod-translator-stronghold-key-v1".hash(&mut hasher);
-            let hash1 = hasher.finish();
-            
-            hasher = DefaultHasher::new();
-            hash1.hash(&mut hasher);
-            password.hash(&mut hasher);
-            let hash2 = hasher.finish();
-            
-            hasher = DefaultHasher::new();
-            hash2.hash(&mut hasher);
-            b"salt-key".hash(&mut hasher);
-            let hash3 = hasher.finish();
-            
-            hasher = DefaultHasher::new();
-            hash3.hash(&mut hasher);
-            password.hash(&mut hasher);
-            let hash4 = hasher.finish();
-            
-            // 4개의 u64 해시를 조합하여 32바이트 키 생성
-            let mut key = Vec::with_capacity(32);
-            key.extend_from_slice(&hash1.to_le_bytes());
-            key.extend_from_slice(&hash2.to_le_bytes());
-            key.extend_from_slice(&hash3.to_le_bytes());
-            key.extend_from_slice(&hash4.to_le_bytes());
-            key
-        }).build())
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -78,17 +44,76 @@ pub fn run() {
             cmd_cancel,
             mod_translator_core::detect_steam_path,
             mod_translator_core::scan_steam_library,
+            mod_translator_core::rescan_changed,
+            mod_translator_core::query_mods,
             mod_translator_core::list_mod_files,
             mod_translator_core::start_translation_job,
             mod_translator_core::cancel_translation_job,
+            mod_translator_core::rollback_translation_job,
+            mod_translator_core::list_recoverable_jobs,
+            mod_translator_core::discard_recoverable_job,
+            mod_translator_core::enqueue_translation_job,
+            mod_translator_core::reorder_queued_job,
+            mod_translator_core::set_job_priority,
+            mod_translator_core::set_max_concurrent_jobs,
+            mod_translator_core::list_jobs,
+            mod_translator_core::get_job_status,
+            mod_translator_core::get_job_history,
+            mod_translator_core::get_translation_stats,
+            mod_translator_core::get_provider_usage_metrics,
+            mod_translator_core::get_pending_segments,
+            mod_translator_core::approve_segment,
+            mod_translator_core::reject_segment,
+            mod_translator_core::edit_segment,
+            mod_translator_core::revalidate_file,
+            mod_translator_core::get_job_qc_report,
+            mod_translator_core::list_validation_failures,
+            mod_translator_core::retranslate_failed_segments,
+            mod_translator_core::accept_original,
+            mod_translator_core::inspect_protection,
             mod_translator_core::retry_translation_now,
             mod_translator_core::open_output_folder,
-            mod_translator_core::validate_api_key_and_list_models,
             mod_translator_core::get_validation_metrics,
             mod_translator_core::reset_validation_metrics,
             mod_translator_core::export_validation_metrics,
-            mod_translator_core::get_validation_log_file_path
+            mod_translator_core::get_validation_log_file_path,
+            mod_translator_core::get_usage_stats_enabled,
+            mod_translator_core::set_usage_stats_enabled,
+            mod_translator_core::export_usage_stats,
+            mod_translator_core::reset_usage_stats,
+            mod_translator_core::detect_mod_profiles,
+            mod_translator_core::set_mod_profile,
+            mod_translator_core::list_game_profiles,
+            mod_translator_core::list_extractor_plugins,
+            mod_translator_core::export_project,
+            mod_translator_core::import_project,
+            mod_translator_core::save_project,
+            mod_translator_core::load_project,
+            mod_translator_core::list_projects,
+            mod_translator_core::add_api_key,
+            mod_translator_core::remove_api_key,
+            mod_translator_core::list_api_keys,
+            mod_translator_core::test_api_key,
+            mod_translator_core::get_read_only_mode,
+            mod_translator_core::set_read_only_mode,
+            mod_translator_core::get_offline_mode,
+            mod_translator_core::set_offline_mode,
+            mod_translator_core::install_translation,
+            mod_translator_core::uninstall_translation,
+            mod_translator_core::generate_def_injected,
+            mod_translator_core::sync_stardew_i18n,
+            mod_translator_core::start_watch_mode,
+            mod_translator_core::stop_watch_mode,
+            mod_translator_core::set_watch_mod_enabled,
+            mod_translator_core::get_watch_status
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                // Let running jobs save their checkpoints before the runtime stops
+                let runner = app.state::<Shared>().0.clone();
+                tauri::async_runtime::block_on(runner.shutdown());
+            }
+        });
 }
@@ -52,7 +52,6 @@ fn main() {
             mod_translator_core::cancel_translation_job,
             mod_translator_core::retry_translation_now,
             mod_translator_core::open_output_folder,
-            mod_translator_core::validate_api_key_and_list_models,
             mod_translator_core::get_validation_metrics,
             mod_translator_core::reset_validation_metrics,
             mod_translator_core::export_validation_metrics,
@@ -21,7 +21,9 @@ import type {
   TranslationAttemptMetrics,
   TranslationBackoffStartedPayload,
   TranslationBackoffCancelledPayload,
+  TranslationModelFallbackPayload,
   TranslationRetryStartedPayload,
+  ValidatorConfig,
 } from "../types/core";
 import { useSettingsStore } from "./SettingsStore";
 
@@ -94,13 +96,13 @@ export interface TranslationJob {
   outputOverrideDir: string | null;
   status: JobState;
   providerId: ProviderId;
-  providerApiKey: string;
   modelId: string;
   progress: number;
   translatedCount: number;
   totalCount: number;
   latestFileName: string | null;
   latestFileSuccess: boolean | null;
+  latestFileProgress: number | null;
   cancelRequested: boolean;
   logs: JobLogEntry[];
   metrics: JobMetricEntry[];
@@ -332,6 +334,7 @@ const prepareJobForActivation = (job: TranslationJob): TranslationJob => ({
   totalCount: 0,
   latestFileName: null,
   latestFileSuccess: null,
+  latestFileProgress: null,
   cancelRequested: false,
   logs: [],
   metrics: [],
@@ -347,7 +350,6 @@ const prepareJobForActivation = (job: TranslationJob): TranslationJob => ({
 const createJob = (
   input: EnqueueJobInput,
   providerId: ProviderId,
-  providerApiKey: string,
   modelId: string,
 ): TranslationJob => ({
   id: createId(),
@@ -360,13 +362,13 @@ const createJob = (
   outputOverrideDir: null,
   status: "pending",
   providerId,
-  providerApiKey,
   modelId,
   progress: 0,
   translatedCount: 0,
   totalCount: 0,
   latestFileName: null,
   latestFileSuccess: null,
+  latestFileProgress: null,
   cancelRequested: false,
   logs: [],
   metrics: [],
@@ -405,6 +407,8 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     apiKeys,
     providerModels,
     autoTuneConcurrencyOn429,
+    validationMode,
+    validatorOptions,
   } = useSettingsStore();
   const [state, setState] = useState<JobStoreState>({
     currentJob: null,
@@ -412,16 +416,18 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     completedJobs: [],
   });
   const activeJobIdRef = useRef<string | null>(null);
+  const validatorConfig = useMemo<ValidatorConfig>(
+    () => ({ ...validatorOptions, validationMode }),
+    [validatorOptions, validationMode],
+  );
 
-  const providerApiKeyRef = useRef<string>("");
   const rateLimitHitCountRef = useRef(0);
   const lastRateLimitAtRef = useRef<number | null>(null);
   const lastAutoTuneAtRef = useRef<number | null>(null);
 
   useEffect(() => {
     activeJobIdRef.current = state.currentJob?.id ?? null;
-    providerApiKeyRef.current = state.currentJob?.providerApiKey ?? "";
-  }, [state.currentJob?.id, state.currentJob?.providerApiKey]);
+  }, [state.currentJob?.id]);
 
   useEffect(() => {
     if (!autoTuneConcurrencyOn429) {
@@ -535,6 +541,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
           totalCount: finalStats.totalCount,
           latestFileName: finalStats.fileName,
           latestFileSuccess: finalStats.fileSuccess,
+          latestFileProgress: null,
           cancelRequested: false,
           logs: finalLogEntry
             ? [...prev.currentJob.logs, finalLogEntry]
@@ -627,16 +634,18 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     const filesPayload = buildTranslationFilesPayload(activeJob, selectedFiles);
 
     await invoke("start_translation_job", {
-      jobId: activeJob.id,
-      provider: activeJob.providerId,
-      apiKey: activeJob.providerApiKey,
-      modelId,
-      sourceLang: activeJob.sourceLanguageGuess,
-      targetLang: activeJob.targetLanguage,
-      files: filesPayload,
-      outputOverrideDir: activeJob.outputOverrideDir,
-      resumeFromCheckpoint: true,
-      resetResumeState: false,
+      payload: {
+        jobId: activeJob.id,
+        provider: activeJob.providerId,
+        modelId,
+        sourceLang: activeJob.sourceLanguageGuess,
+        targetLang: activeJob.targetLanguage,
+        files: filesPayload,
+        outputOverrideDir: activeJob.outputOverrideDir,
+        validatorConfig,
+        resumeFromCheckpoint: true,
+        resetResumeState: false,
+      },
     });
 
     appendLog("이전 실패 지점부터 번역을 재개합니다.");
@@ -656,7 +665,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
         },
       };
     });
-  }, [appendLog, state.currentJob]);
+  }, [appendLog, state.currentJob, validatorConfig]);
 
   const restartCurrentJobFromStart = useCallback(async () => {
     if (!isTauri()) {
@@ -686,16 +695,18 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     const filesPayload = buildTranslationFilesPayload(activeJob, selectedFiles);
 
     await invoke("start_translation_job", {
-      jobId: activeJob.id,
-      provider: activeJob.providerId,
-      apiKey: activeJob.providerApiKey,
-      modelId,
-      sourceLang: activeJob.sourceLanguageGuess,
-      targetLang: activeJob.targetLanguage,
-      files: filesPayload,
-      outputOverrideDir: activeJob.outputOverrideDir,
-      resumeFromCheckpoint: false,
-      resetResumeState: true,
+      payload: {
+        jobId: activeJob.id,
+        provider: activeJob.providerId,
+        modelId,
+        sourceLang: activeJob.sourceLanguageGuess,
+        targetLang: activeJob.targetLanguage,
+        files: filesPayload,
+        outputOverrideDir: activeJob.outputOverrideDir,
+        validatorConfig,
+        resumeFromCheckpoint: false,
+        resetResumeState: true,
+      },
     });
 
     appendLog("파일 처음부터 번역을 다시 시작합니다.");
@@ -719,7 +730,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
         },
       };
     });
-  }, [appendLog, state.currentJob]);
+  }, [appendLog, state.currentJob, validatorConfig]);
 
   const enqueueJob = useCallback(
     (input: EnqueueJobInput): EnqueueJobResult => {
@@ -727,7 +738,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
 
       const providerId = activeProviderId;
       if (!providerId) {
-        const placeholderJob = createJob(input, "gemini", "", "");
+        const placeholderJob = createJob(input, "gemini", "");
         outcome = {
           job: placeholderJob,
           promoted: false,
@@ -736,11 +747,9 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
         return outcome;
       }
 
-      const apiKeyRaw = apiKeys[providerId] ?? "";
-      const apiKey = apiKeyRaw.trim();
-
-      if (!apiKey) {
-        const placeholderJob = createJob(input, providerId, "", "");
+      // 키는 코어가 번역기 ID로 찾으므로 저장되어 있는지만 확인
+      if (!apiKeys[providerId]) {
+        const placeholderJob = createJob(input, providerId, "");
         outcome = {
           job: placeholderJob,
           promoted: false,
@@ -751,7 +760,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
 
       const selectedModel = providerModels[providerId]?.trim() ?? "";
       if (!selectedModel) {
-        const placeholderJob = createJob(input, providerId, apiKey, "");
+        const placeholderJob = createJob(input, providerId, "");
         outcome = {
           job: placeholderJob,
           promoted: false,
@@ -760,7 +769,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
         return outcome;
       }
 
-      const baseJob = createJob(input, providerId, apiKey, selectedModel);
+      const baseJob = createJob(input, providerId, selectedModel);
 
       setState((prev) => {
         if (prev.currentJob && prev.currentJob.modId === input.modId) {
@@ -1111,16 +1120,18 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
       );
 
       await invoke("start_translation_job", {
-        jobId: activeJob.id,
-        provider: activeJob.providerId,
-        apiKey: activeJob.providerApiKey,
-        modelId,
-        sourceLang: sourceLanguage,
-        targetLang: targetLanguage,
-        files: filesPayload,
-        outputOverrideDir: activeJob.outputOverrideDir,
-        resumeFromCheckpoint: false,
-        resetResumeState: true,
+        payload: {
+          jobId: activeJob.id,
+          provider: activeJob.providerId,
+            modelId,
+          sourceLang: sourceLanguage,
+          targetLang: targetLanguage,
+          files: filesPayload,
+          outputOverrideDir: activeJob.outputOverrideDir,
+          validatorConfig,
+          resumeFromCheckpoint: false,
+          resetResumeState: true,
+        },
       });
 
       setState((prev) => {
@@ -1147,7 +1158,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
         };
       });
     },
-    [state.currentJob],
+    [state.currentJob, validatorConfig],
   );
 
   const requestCancelCurrentJob = useCallback(async () => {
@@ -1317,6 +1328,10 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
       const progress = clampProgress(Math.round(payload.progressPct ?? 0));
       const fileName = payload.fileName ?? null;
       const fileSuccess = payload.fileSuccess ?? null;
+      const fileProgress =
+        typeof payload.fileProgressPct === "number"
+          ? clampProgress(Math.round(payload.fileProgressPct))
+          : null;
       const translatedCount = payload.translatedCount ?? 0;
       const totalCount = payload.totalCount ?? 0;
       const lastWritten = payload.lastWritten ?? null;
@@ -1327,16 +1342,9 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
             return prev;
           }
 
-          const providerKey = prev.currentJob.providerApiKey.trim();
-          const containsSensitiveData =
-            !!providerKey &&
-            providerKey.length > 0 &&
-            trimmedLog.includes(providerKey);
-
-          const logs =
-            trimmedLog && !containsSensitiveData
-              ? [...prev.currentJob.logs, createLogEntry("info", trimmedLog)]
-              : prev.currentJob.logs;
+          const logs = trimmedLog
+            ? [...prev.currentJob.logs, createLogEntry("info", trimmedLog)]
+            : prev.currentJob.logs;
 
           const updatedFileErrors = applyFileErrorUpdates(
             prev.currentJob,
@@ -1384,6 +1392,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
               totalCount: nextTotalCount,
               latestFileName: fileName,
               latestFileSuccess: fileSuccess,
+              latestFileProgress: fileProgress,
               logs,
               fileErrors: updatedFileErrors,
               outputPath: nextOutputPath,
@@ -1416,13 +1425,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
               : status === "partial_success"
                 ? "일부 파일에서 오류가 발생했습니다."
                 : "작업이 중단되었습니다.";
-        const providerKey = providerApiKeyRef.current.trim();
-        const containsSensitiveData =
-          !!providerKey &&
-          providerKey.length > 0 &&
-          trimmedLog.includes(providerKey);
-        const sanitizedLog = containsSensitiveData ? "" : trimmedLog;
-        const text = sanitizedLog || fallbackText;
+        const text = trimmedLog || fallbackText;
         const finalLogEntry = text ? createLogEntry(level, text) : null;
         finalizeCurrentJob(
           status,
@@ -1491,6 +1494,30 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     [],
   );
 
+  const handleModelFallbackEvent = useCallback(
+    (payload: TranslationModelFallbackPayload) => {
+      setState((prev) => {
+        if (!prev.currentJob || prev.currentJob.id !== payload.jobId) {
+          return prev;
+        }
+
+        const logEntry = createLogEntry(
+          "warn",
+          `모델 '${payload.failedModel}'을(를) 찾을 수 없어 '${payload.fallbackModel}'(으)로 계속 번역합니다.`,
+        );
+
+        return {
+          ...prev,
+          currentJob: {
+            ...prev.currentJob,
+            logs: [...prev.currentJob.logs, logEntry],
+          },
+        };
+      });
+    },
+    [],
+  );
+
   const handleRetryStartedEvent = useCallback(
     (payload: TranslationRetryStartedPayload) => {
       setState((prev) => {
@@ -1629,6 +1656,25 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
           error,
         );
       }
+
+      try {
+        const unlistenModelFallback =
+          await listen<TranslationModelFallbackPayload>(
+            "translation-model-fallback",
+            (event) => {
+              if (disposed) {
+                return;
+              }
+              handleModelFallbackEvent(event.payload);
+            },
+          );
+        unlistenFns.push(unlistenModelFallback);
+      } catch (error) {
+        console.error(
+          "translation-model-fallback 이벤트 등록에 실패했습니다.",
+          error,
+        );
+      }
     };
 
     setup();
@@ -1648,6 +1694,7 @@ export function JobStoreProvider({ children }: { children: ReactNode }) {
     handleBackoffStartedEvent,
     handleBackoffCancelledEvent,
     handleRetryStartedEvent,
+    handleModelFallbackEvent,
   ]);
 
   const value = useMemo<JobStoreValue>(
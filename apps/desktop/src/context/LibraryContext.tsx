@@ -13,6 +13,7 @@ import type {
   LibraryEntry,
   LibraryScanDebug,
   LibraryScanResponse,
+  ModSourceKind,
   PolicyBanner,
   SteamPathResponse,
 } from '../types/core'
@@ -21,7 +22,7 @@ interface LibraryContextValue {
   policyBanner: PolicyBanner | null
   libraries: LibraryEntry[]
   isScanning: boolean
-  scanLibrary: (explicitPath?: string) => Promise<void>
+  scanLibrary: (explicitPath?: string, source?: ModSourceKind) => Promise<void>
   error: string | null
   steamPath: string | null
   detectSteamPath: () => Promise<string | null>
@@ -61,7 +62,7 @@ export function LibraryProvider({ children }: { children: ReactNode }) {
   }, [])
 
   const scanLibrary = useCallback(
-    async (explicitPath?: string) => {
+    async (explicitPath?: string, source?: ModSourceKind) => {
       if (!isTauri()) {
         setLibraries([])
         setPolicyBanner(null)
@@ -77,6 +78,7 @@ export function LibraryProvider({ children }: { children: ReactNode }) {
       try {
         const response = await invoke<LibraryScanResponse>('scan_steam_library', {
           explicit_path: explicitPath ?? null,
+          source: source ?? null,
         })
         setLibraries(response.libraries)
         setPolicyBanner(response.policy_banner)
@@ -19,11 +19,19 @@ import {
   type ProviderId,
   type ProviderRetryPolicy,
   normalizeRetryPolicy,
+  sanitizeValidatorOptions,
 } from "../storage/settingsStorage";
-import type { RetryPolicy, RetryableErrorCode } from "../types/core";
+import type {
+  ModelInfo,
+  RetryPolicy,
+  RetryableErrorCode,
+  StoredApiKey,
+  ValidatorOptions,
+} from "../types/core";
 import {
   loadApiKeys,
-  persistApiKeys,
+  removeApiKey,
+  saveApiKey,
   type ApiKeyMap,
 } from "../storage/apiKeyStorage";
 
@@ -51,13 +59,9 @@ interface SettingsStoreValue extends SettingsState {
   setActiveProvider: (provider: ProviderId) => void;
   setProviderModel: (provider: ProviderId, modelId: string) => void;
   updateApiKey: (provider: ProviderId, value: string | null) => void;
-  refreshProviderModels: (
-    provider: ProviderId,
-    apiKeyOverride?: string,
-  ) => Promise<void>;
+  refreshProviderModels: (provider: ProviderId) => Promise<void>;
   revalidateProviderKey: (
     provider: ProviderId,
-    apiKeyOverride?: string,
     modelOverride?: string,
   ) => Promise<KeyValidationState>;
   setConcurrency: (value: number) => void;
@@ -77,6 +81,7 @@ interface SettingsStoreValue extends SettingsState {
   setValidationMode: (
     mode: import("../storage/settingsStorage").ValidationMode,
   ) => void;
+  setValidatorOptions: (options: ValidatorOptions) => void;
   setProviderRetryPolicy: (
     provider: ProviderId,
     patch: Partial<ProviderRetryPolicy>,
@@ -93,6 +98,8 @@ interface ProviderModelDiscoveryState {
 interface ProviderValidationResponse {
   validationStatus: KeyValidationState;
   models: string[];
+  catalog?: ModelInfo[];
+  cachedAt?: string | null;
 }
 
 function createValidationResponse(
@@ -304,10 +311,11 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
   const runValidation = useCallback(
     async (
       provider: ProviderId,
-      apiKeyOverride?: string,
+      storedKey: StoredApiKey | undefined,
       modelOverride?: string,
     ): Promise<ProviderValidationResponse> => {
-      const trimmed = (apiKeyOverride ?? state.apiKeys[provider] ?? "").trim();
+      // 키는 코어가 번역기 ID로 찾으므로, 저장 시각으로 키가 바뀌었는지 구분
+      const keyVersion = storedKey?.addedAt ?? "";
       const modelHint = (
         modelOverride ??
         state.providerModels[provider] ??
@@ -316,13 +324,13 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
 
       const lock = validationLocks.current[provider];
       if (lock) {
-        if (lock.key === trimmed && lock.model === modelHint) {
+        if (lock.key === keyVersion && lock.model === modelHint) {
           return lock.promise;
         }
         await lock.promise;
       }
 
-      if (!trimmed) {
+      if (!storedKey) {
         setValidationInFlight((prev) => ({
           ...prev,
           [provider]: false,
@@ -338,10 +346,9 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
       const task = (async () => {
         try {
           const response = await invoke<ProviderValidationResponse>(
-            "validate_api_key_and_list_models",
+            "test_api_key",
             {
               provider,
-              apiKey: trimmed,
               modelHint: modelHint || undefined,
             },
           );
@@ -357,7 +364,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
 
       validationLocks.current[provider] = {
         promise: task,
-        key: trimmed,
+        key: keyVersion,
         model: modelHint,
       };
 
@@ -371,7 +378,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
 
       return result;
     },
-    [setValidationInFlight, state.apiKeys, state.providerModels],
+    [setValidationInFlight, state.providerModels],
   );
 
   useEffect(() => {
@@ -566,25 +573,21 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
   );
 
   const revalidateProviderKey = useCallback(
-    async (
-      provider: ProviderId,
-      apiKeyOverride?: string,
-      modelOverride?: string,
-    ) => {
+    async (provider: ProviderId, modelOverride?: string) => {
       const result = await runValidation(
         provider,
-        apiKeyOverride,
+        state.apiKeys[provider],
         modelOverride,
       );
       applyValidationResult(provider, result);
       return result.validationStatus;
     },
-    [applyValidationResult, runValidation],
+    [applyValidationResult, runValidation, state.apiKeys],
   );
 
   const refreshProviderModels = useCallback(
-    async (provider: ProviderId, apiKeyOverride?: string) => {
-      await revalidateProviderKey(provider, apiKeyOverride);
+    async (provider: ProviderId) => {
+      await revalidateProviderKey(provider);
     },
     [revalidateProviderKey],
   );
@@ -609,6 +612,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
         enableQualitySampling: state.enableQualitySampling,
         useServerHints: state.useServerHints,
         validationMode: state.validationMode,
+        validatorOptions: state.validatorOptions,
       });
     } catch (error) {
       console.error("Failed to persist settings", error);
@@ -631,6 +635,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
     state.enableQualitySampling,
     state.useServerHints,
     state.validationMode,
+    state.validatorOptions,
   ]);
 
   const setProviderEnabled = useCallback(
@@ -734,30 +739,38 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
   const updateApiKey = useCallback(
     async (provider: ProviderId, value: string | null) => {
       const trimmed = (value ?? "").trim();
-      const nextKeys: ApiKeyMap = { ...state.apiKeys };
-
-      if (trimmed) {
-        nextKeys[provider] = trimmed;
-      } else {
-        delete nextKeys[provider];
-      }
+      let stored: StoredApiKey | undefined;
 
       try {
-        await persistApiKeys(nextKeys);
-        setState((prev) => ({ ...prev, apiKeys: nextKeys }));
+        if (trimmed) {
+          stored = await saveApiKey(provider, trimmed);
+        } else {
+          await removeApiKey(provider);
+        }
       } catch (error) {
         throw error instanceof Error ? error : new Error(String(error));
       }
 
+      setState((prev) => {
+        const nextKeys: ApiKeyMap = { ...prev.apiKeys };
+        if (stored) {
+          nextKeys[provider] = stored;
+        } else {
+          delete nextKeys[provider];
+        }
+        return { ...prev, apiKeys: nextKeys };
+      });
+
       setProviderModelNotices((prev) => ({
         ...prev,
         [provider]: null,
       }));
 
       // 키 재검증
-      await revalidateProviderKey(provider, trimmed);
+      const result = await runValidation(provider, stored);
+      applyValidationResult(provider, result);
     },
-    [state.apiKeys, revalidateProviderKey, setProviderModelNotices],
+    [applyValidationResult, runValidation, setProviderModelNotices],
   );
 
   const setConcurrency = useCallback((value: number) => {
@@ -849,6 +862,13 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
     [],
   );
 
+  const setValidatorOptions = useCallback((options: ValidatorOptions) => {
+    setState((prev) => ({
+      ...prev,
+      validatorOptions: sanitizeValidatorOptions(options),
+    }));
+  }, []);
+
   const setProviderRetryPolicy = useCallback(
     (provider: ProviderId, patch: Partial<ProviderRetryPolicy>) => {
       setState((prev) => {
@@ -904,6 +924,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
       setEnableQualitySampling,
       setUseServerHints,
       setValidationMode,
+      setValidatorOptions,
       setProviderRetryPolicy,
     }),
     [
@@ -932,6 +953,7 @@ export function SettingsStoreProvider({ children }: { children: ReactNode }) {
       setEnableQualitySampling,
       setUseServerHints,
       setValidationMode,
+      setValidatorOptions,
       setProviderRetryPolicy,
     ],
   );
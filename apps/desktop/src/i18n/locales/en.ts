@@ -465,9 +465,10 @@ export const en: TranslationDictionary = {
       validationModes: {
         strict: "Strict",
         relaxed_xml: "Relaxed XML",
+        relaxed_xml_plus: "Relaxed XML+ (structure check)",
       },
       validationModeDesc:
-        "Relaxed mode: Ignore math/latex, validate only text within XML tag boundaries, enable auto-recovery (recommended)",
+        "Relaxed mode: Ignore math/latex, validate only text within XML tag boundaries, enable auto-recovery. Relaxed XML+ also keeps the tag, pipe and URL structure of the source (recommended)",
       dllResources: "Prioritize DLL resources (Mono.Cecil)",
       qualitySampling: "Perform quality sampling (5%)",
     },
@@ -462,9 +462,10 @@ export const ko: TranslationDictionary = {
       validationModes: {
         strict: "엄격 (Strict)",
         relaxed_xml: "느슨 (Relaxed XML)",
+        relaxed_xml_plus: "느슨 + 구조 검사 (Relaxed XML+)",
       },
       validationModeDesc:
-        "느슨 모드: 수식/라텍스 무시, XML 태그 경계 내 텍스트만 검증, 자동 복구 활성화 (권장)",
+        "느슨 모드: 수식/라텍스 무시, XML 태그 경계 내 텍스트만 검증, 자동 복구 활성화. 느슨 + 구조 검사는 원문의 태그, 파이프, URL 구조까지 유지합니다 (권장)",
       dllResources: "DLL 리소스 우선 처리 (Mono.Cecil)",
       qualitySampling: "품질 샘플링(5%) 수행",
     },
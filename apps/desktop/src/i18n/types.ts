@@ -417,6 +417,7 @@ export interface TranslationDictionary {
       validationModes: {
         strict: string;
         relaxed_xml: string;
+        relaxed_xml_plus: string;
       };
       validationModeDesc: string;
       dllResources: string;
@@ -1,9 +1,14 @@
-import type { RetryPolicy, RetryableErrorCode } from '../types/core'
+import type {
+  RetryPolicy,
+  RetryableErrorCode,
+  ValidatorMode,
+  ValidatorOptions,
+} from '../types/core'
 
 const STORAGE_KEY = 'mod_translator_settings_v1'
 
 export type ProviderId = 'gemini' | 'gpt' | 'claude' | 'grok'
-export type ValidationMode = 'strict' | 'relaxed_xml'
+export type ValidationMode = ValidatorMode
 
 export type ProviderModelMap = Record<ProviderId, string>
 export type ProviderModelListMap = Record<ProviderId, string[]>
@@ -129,6 +134,15 @@ export interface PersistedSettings {
   enableQualitySampling: boolean
   useServerHints: boolean
   validationMode: ValidationMode
+  validatorOptions: ValidatorOptions
+}
+
+export const DEFAULT_VALIDATOR_OPTIONS: ValidatorOptions = {
+  enableAutofix: true,
+  retryOnFail: true,
+  retryLimit: 1,
+  strictPairing: true,
+  preservePercentBinding: true,
 }
 
 export const DEFAULT_PERSISTED_SETTINGS: PersistedSettings = {
@@ -148,7 +162,8 @@ export const DEFAULT_PERSISTED_SETTINGS: PersistedSettings = {
   prioritizeDllResources: true,
   enableQualitySampling: true,
   useServerHints: true,
-  validationMode: 'relaxed_xml',
+  validationMode: 'relaxed_xml_plus',
+  validatorOptions: { ...DEFAULT_VALIDATOR_OPTIONS },
 }
 
 function isStorageAvailable() {
@@ -317,12 +332,28 @@ function sanitizeBoolean(value: unknown, fallback: boolean) {
 }
 
 function sanitizeValidationMode(value: unknown): ValidationMode {
-  if (value === 'strict' || value === 'relaxed_xml') {
+  if (value === 'strict' || value === 'relaxed_xml' || value === 'relaxed_xml_plus') {
     return value
   }
   return DEFAULT_PERSISTED_SETTINGS.validationMode
 }
 
+export function sanitizeValidatorOptions(value: unknown): ValidatorOptions {
+  const input =
+    value && typeof value === 'object' ? (value as Partial<ValidatorOptions>) : {}
+  const defaults = DEFAULT_VALIDATOR_OPTIONS
+  return {
+    enableAutofix: sanitizeBoolean(input.enableAutofix, defaults.enableAutofix),
+    retryOnFail: sanitizeBoolean(input.retryOnFail, defaults.retryOnFail),
+    retryLimit: Math.min(5, sanitizeNumber(input.retryLimit, defaults.retryLimit, 0)),
+    strictPairing: sanitizeBoolean(input.strictPairing, defaults.strictPairing),
+    preservePercentBinding: sanitizeBoolean(
+      input.preservePercentBinding,
+      defaults.preservePercentBinding,
+    ),
+  }
+}
+
 function sanitizeProviderRetryPolicyEntry(
   value: unknown,
   fallback: ProviderRetryPolicy,
@@ -439,6 +470,7 @@ export function loadPersistedSettings(): PersistedSettings {
         DEFAULT_PERSISTED_SETTINGS.useServerHints,
       ),
       validationMode: sanitizeValidationMode(parsed.validationMode),
+      validatorOptions: sanitizeValidatorOptions(parsed.validatorOptions),
     }
   } catch (error) {
     console.error('Failed to parse persisted settings, using defaults.', error)
@@ -29,8 +29,22 @@ export interface ModSummary {
   last_updated: FormattedTimestamp;
   policy: PolicyProfile;
   warnings: string[];
+  source: ModSourceKind;
+  /** 한국어 번역 적용 범위; 언어별 파일이 없으면 null */
+  translation_coverage: TranslationCoverage | null;
 }
 
+export interface TranslationCoverage {
+  target_language: string;
+  source_entries: number;
+  translated_entries: number;
+  /** 0–100 */
+  percent: number;
+}
+
+/** 모드를 찾을 위치: Steam 워크샵, 임의 폴더, CurseForge 인스턴스, Vortex/MO2 */
+export type ModSourceKind = "steam" | "folder" | "curseforge" | "nexus";
+
 export interface LibraryEntry {
   path: string;
   status: LibraryStatus;
@@ -85,6 +99,65 @@ export interface LibraryScanResponse {
   debug?: LibraryScanDebug;
 }
 
+/** `rescan_changed` 결과: 색인 이후 바뀐 워크샵 항목만 다시 읽은 라이브러리 */
+export interface RescanReport {
+  libraries: LibraryEntry[];
+  policy_banner: PolicyBanner;
+  /** 색인이 없어 전체 스캔을 실행함 */
+  full_scan: boolean;
+  added: string[];
+  updated: string[];
+  removed: string[];
+  elapsed_ms: number;
+}
+
+export type ModSortKey =
+  | "name"
+  | "game"
+  | "last_updated"
+  | "file_count"
+  | "translation_coverage";
+
+/** `query_mods` 조건. 지정하지 않은 조건은 모든 모드와 일치 */
+export interface ModQuery {
+  /** 이름/ID 검색어 (대소문자 무시) */
+  text?: string | null;
+  games?: string[];
+  /** 모두 설치되어 있어야 하는 언어 */
+  languages?: string[];
+  has_korean?: boolean | null;
+  min_files?: number | null;
+  max_files?: number | null;
+  /** Unix 초 */
+  updated_after?: number | null;
+  updated_before?: number | null;
+  sort?: ModSortKey;
+  descending?: boolean;
+  offset?: number;
+  limit?: number | null;
+}
+
+export interface IndexedMod extends ModSummary {
+  library: string;
+  file_count: number;
+  /** 모드 폴더의 마지막 변경 시각 (Unix 초) */
+  updated_at: number;
+}
+
+export interface ModQueryResult {
+  mods: IndexedMod[];
+  /** 페이지 적용 전 일치 항목 수 */
+  total: number;
+  offset: number;
+}
+
+/** `library-scan-progress` event payload emitted by `scan_steam_library` */
+export interface LibraryScanProgress {
+  library: string;
+  scanned_mods: number;
+  total_mods: number;
+}
+
 export interface SteamPathResponse {
   path: string | null;
   note: string;
@@ -96,6 +169,8 @@ export type JobState =
   | "completed"
   | "failed"
   | "canceled"
+  | "paused"
+  | "awaiting_approval"
   | "partial_success";
 
 export type ProviderId = "gemini" | "gpt" | "claude" | "grok";
@@ -137,21 +212,294 @@ export interface TranslationFileDescriptor {
   archiveEntryPath?: string;
 }
 
+export type MarkerScheme = "whiteSquare" | "doubleAngle" | "whiteCurly" | "ascii";
+
+/** 출력 인코딩: 원본 유지(기본값, 표현할 수 없으면 BOM 포함 UTF-8) 또는 UTF-8 고정 */
+export type OutputEncoding = "preserve" | "utf8" | "utf8Bom";
+
+export type ReasoningEffort = "low" | "medium" | "high";
+
+export interface ModelParams {
+  temperature?: number | null;
+  topP?: number | null;
+  maxOutputTokens?: number | null;
+  reasoningEffort?: ReasoningEffort | null;
+  thinkingBudgetTokens?: number | null;
+  /** {"translation": …} 형식의 구조화된 응답 요청 (지원하는 모델만, 기본값 사용) */
+  structuredOutput?: boolean | null;
+}
+
+/** 분당 요청/토큰 수 제한 (없으면 제한 없음). 같은 번역기·모델을 쓰는 작업이 함께 사용 */
+export interface RateLimitConfig {
+  requestsPerMinute?: number | null;
+  tokensPerMinute?: number | null;
+}
+
+/** 요청 시간 제한과 재시도 횟수 (없으면 기본값) */
+export interface RequestOptions {
+  /** 연결 시간 제한 (초, 기본값 10) */
+  connectTimeoutSecs?: number | null;
+  /** 요청 전체 시간 제한 (초, 기본값 120) */
+  requestTimeoutSecs?: number | null;
+  /** 세그먼트당 최대 시도 횟수 (첫 시도 포함, 기본값 3, 최대 10) */
+  maxRetryAttempts?: number | null;
+}
+
+export type ConsistencyMode = "report" | "harmonize";
+
+/** formal: 존댓말, casual: 반말, preserve: 원문 어조 유지 */
+export type TargetTone = "formal" | "casual" | "preserve";
+
+/** 참고 문맥: 없음, 파일 경로와 키, 파일 경로와 키와 앞뒤 문장 */
+export type ContextMode = "off" | "key" | "surrounding";
+
+/**
+ * 프롬프트 템플릿. `{{source_lang}}`, `{{target_lang}}`, `{{text}}`, `{{context}}`,
+ * `{{glossary}}`, `{{tone}}`, `{{game}}`, `{{game_context}}` 변수와
+ * `{{#if 변수}}...{{/if}}` 조건 블록을 지원하며, 비워 둔 부분은 내장 프롬프트를 사용
+ */
+export interface PromptTemplate {
+  system?: string | null;
+  user?: string | null;
+}
+
+/** 번역 프롬프트 설정 (번역기별 템플릿 > 공통 템플릿 > 내장 프롬프트) */
+export interface PromptConfig {
+  default?: PromptTemplate;
+  providers?: Partial<Record<ProviderId, PromptTemplate>>;
+  tone?: string | null;
+}
+
 export interface StartTranslationJobPayload {
   jobId: string;
   provider: ProviderId;
-  apiKey: string | null;
   modelId: string;
   files: TranslationFileDescriptor[];
   sourceLang: string | null;
-  targetLang: string | null;
+  /** 여러 언어를 주면 언어마다 `{jobId}-{언어}` 작업으로 차례로 번역 */
+  targetLang: string | string[] | null;
   outputOverrideDir?: string | null;
   resumeFromCheckpoint?: boolean;
   resetResumeState?: boolean;
+  maxCostUsd?: number | null;
+  maxTokens?: number | null;
+  modelParams?: ModelParams;
+  embedProvenance?: boolean;
+  strictMode?: boolean;
+  exportReview?: boolean;
+  approvalMode?: boolean;
+  glossary?: Record<string, string>;
+  translatorNotes?: boolean;
+  allowSourceOverwrite?: boolean;
+  markerScheme?: MarkerScheme | null;
+  outputEncoding?: OutputEncoding | null;
+  /** 바뀌지 않은 원문은 이전 번역을 재사용하고 새로 추가되거나 바뀐 문장만 번역 */
+  incremental?: boolean;
+  /** 프롬프트 템플릿 (게임 프로필의 프롬프트 설정이 우선) */
+  prompts?: PromptConfig;
+  /** 문장과 함께 참고 문맥으로 보낼 정보 (기본값 off) */
+  contextMode?: ContextMode;
+  /** surrounding 모드에서 앞뒤로 보낼 문장 수 (기본값 2, 최대 10) */
+  contextNeighbors?: number | null;
+  /** 번역 어조. 프롬프트에 넣고, 한국어 번역의 어조가 다르면 QC 경고를 남김 */
+  targetTone?: TargetTone | null;
+  /** 작업 후 일관성 검사 (report: 보고만, harmonize: 가장 많이 쓰인 번역으로 통일) */
+  consistencyPass?: ConsistencyMode | null;
+  rateLimit?: RateLimitConfig | null;
+  requestOptions?: RequestOptions | null;
+  /** 번역기 API 대신 요청을 보낼 주소 (같은 API를 제공하는 프록시 등) */
+  apiBaseUrl?: string | null;
+  /** 모델을 찾을 수 없을 때 대신 쓸 모델 (원래 모델 → 대체 모델, 빈 값이면 대체 안 함) */
+  modelFallbacks?: Record<string, string> | null;
+  /** 자리표시자 검증 방식. 빠진 항목은 기본값 사용 */
+  validatorConfig?: Partial<ValidatorConfig> | null;
+  /** 번역 후 세그먼트마다 0~100 신뢰도 점수를 매기는 품질 추정 */
+  qualityEstimation?: Partial<QualityEstimationConfig> | null;
+  /** 번역 결과의 욕설·비하 표현·거절 응답·지시문 주입 검사 (기본값 warn) */
+  outputFilter?: Partial<OutputFilterConfig> | null;
+  /** 원문이 이미 대상 언어로 쓰인 세그먼트는 번역하지 않고 그대로 둠 (기본값 false) */
+  skipAlreadyTranslated?: boolean;
+  /** 아랍어·히브리어 등 RTL 대상 언어에서 보호된 토큰을 감쌀 방향 제어 문자 (기본값 isolate) */
+  bidiMarks?: BidiMarks;
+  /** 번역할 파일의 글롭 (비어 있으면 모든 파일, 예: `lang/**`) */
+  includeGlobs?: string[];
+  /** 건너뛸 파일의 글롭 (예: `README.md`, `CHANGELOG*`, `docs/`). 포함 글롭보다 우선 */
+  excludeGlobs?: string[];
+  /** 추출은 하되 번역하지 않고 그대로 둘 항목 키와 정규식 (게임 프로필의 목록과 합침) */
+  skipList?: SkipList | null;
+}
+
+/** 번역 제외 목록 */
+export interface SkipList {
+  /** 그대로 둘 항목 키 (대소문자 구분) */
+  keys?: string[];
+  /** 항목 키나 줄 전체에 맞춰 볼 정규식 (예: `^Credits`) */
+  patterns?: string[];
+}
+
+/** isolate: LRI/PDI, mark: LRM, off: 넣지 않음 */
+export type BidiMarks = "isolate" | "mark" | "off";
+
+export interface QualityEstimationConfig {
+  enabled: boolean;
+  /** 역번역에 쓸 저렴한 모델 (없으면 역번역하지 않음) */
+  backTranslationModel: string | null;
+  /** 역번역할 세그먼트 비율 (0~1, 기본 0.1) */
+  sampleRate: number;
+  /** 이 점수보다 낮으면 QC 보고서에 신뢰도 낮음으로 표시 (기본 60) */
+  lowConfidenceThreshold: number;
+  /** 로컬 COMET 방식 채점 서버 주소 */
+  cometEndpoint: string | null;
+}
+
+/** block: 원문 유지, warn: QC 경고만, allow: 검사 안 함 */
+export type OutputFilterMode = "block" | "warn" | "allow";
+
+export interface OutputFilterConfig {
+  mode: OutputFilterMode;
+  /** 함께 막을 단어나 표현 (대소문자 무시, 부분 일치) */
+  blockedTerms: string[];
 }
 
 export type TranslationProgressState = JobState;
 
+export interface ReviewSegment {
+  filePath: string;
+  lineNumber: number;
+  source: string;
+  candidate: string;
+  recoverySteps: string[];
+}
+
+export interface TranslationReviewRequiredPayload {
+  jobId: string;
+  segments: ReviewSegment[];
+}
+
+export interface TranslationVariant {
+  translation: string;
+  /** `파일:행` */
+  locations: string[];
+}
+
+/** 일관성 검사 결과 ("translation-consistency-report" 이벤트) */
+export interface ConsistencyReport {
+  /** 여러 가지로 번역된 원문 (가장 많이 쓰인 번역이 먼저) */
+  sources: { source: string; variants: TranslationVariant[] }[];
+  /** 용어집 번역이 빠진 줄 */
+  terms: { term: string; expected: string; locations: string[] }[];
+  harmonized: number;
+}
+
+export interface TranslationConsistencyReportPayload {
+  jobId: string;
+  mode: ConsistencyMode;
+  report: ConsistencyReport;
+}
+
+export type WatchRootKind = "mod" | "workshop";
+
+export interface WatchRoot {
+  path: string;
+  kind: WatchRootKind;
+}
+
+export interface WatchStatus {
+  active: boolean;
+  roots: WatchRoot[];
+  /** 자동 번역에서 제외한 모드 경로 */
+  disabledMods: string[];
+}
+
+/** `translation-auto-started` 이벤트 */
+export interface TranslationAutoStartedPayload {
+  jobId: string;
+  modPath: string;
+  /** 작업을 시작하게 한 변경 파일 (모드 기준 상대 경로) */
+  changedFiles: string[];
+  fileCount: number;
+}
+
+export interface PendingSegment {
+  segmentId: number;
+  filePath: string;
+  lineNumber: number;
+  source: string;
+  translation: string;
+  usage?: string;
+}
+
+export type SegmentReviewStatus =
+  | "translated"
+  | "recovered"
+  | "rolled_back"
+  | "held_for_review"
+  | "failed"
+  | "untranslated"
+  | "invalid"
+  | "already_translated"
+  | "do_not_translate";
+
+export interface ReviewSummary {
+  total: number;
+  translated: number;
+  recovered: number;
+  rolledBack: number;
+  heldForReview: number;
+  failed: number;
+  untranslated: number;
+  invalid: number;
+  alreadyTranslated: number;
+  doNotTranslate: number;
+}
+
+export interface RevalidationIssue {
+  lineNumber: number;
+  text: string | null;
+  messages: string[];
+}
+
+export interface RevalidationResult {
+  outputFile: string;
+  reportFile: string;
+  changed: number;
+  passed: number;
+  failed: number;
+  formatError?: string;
+  issues: RevalidationIssue[];
+  summary: ReviewSummary;
+}
+
+export interface InspectedToken {
+  class: string;
+  value: string;
+  start: number;
+  end: number;
+  marker: string;
+}
+
+export interface ProtectionInspection {
+  profileId: string;
+  maskedText: string;
+  tokens: InspectedToken[];
+  likelyCode: boolean;
+  hasTranslatableText: boolean;
+}
+
+export type JobPriority = "low" | "normal" | "high";
+
+export interface JobStatusSnapshot {
+  jobId: string;
+  status: JobState;
+  queuePosition?: number;
+  priority?: JobPriority;
+  progressPct?: number;
+  translatedCount?: number;
+  totalCount?: number;
+  usage?: TranslationUsage;
+  updatedAt: string;
+}
+
 export interface TranslationFileErrorEntry {
   filePath: string;
   message: string;
@@ -177,6 +525,98 @@ export interface TranslationCheckpoint {
   totalCount: number;
 }
 
+export interface TranslationUsage {
+  promptTokens: number;
+  completionTokens: number;
+  totalTokens: number;
+  requestCount: number;
+  reportedRequestCount: number;
+  estimatedCostUsd?: number;
+}
+
+/** `get_job_history`가 돌려주는 끝난 작업 요약 */
+export interface JobHistoryEntry {
+  jobId: string;
+  status: "completed" | "partial_success" | "failed";
+  provider: ProviderId;
+  modelId: string;
+  sourceLang: string;
+  targetLang: string;
+  startedAt: string;
+  finishedAt: string;
+  durationMs: number;
+  files: number;
+  segments: ReviewSummary;
+  usage: TranslationUsage;
+  fileErrors: number;
+}
+
+export interface JobTotals {
+  jobs: number;
+  failedJobs: number;
+  files: number;
+  fileErrors: number;
+  durationMs: number;
+  segments: ReviewSummary;
+  promptTokens: number;
+  completionTokens: number;
+  estimatedCostUsd: number;
+  /** 번역한 세그먼트 중 복구, 원문 유지, 검토 보류, 요청 실패 비율 (0–1) */
+  issueRate: number;
+  costPerThousandSegmentsUsd?: number;
+}
+
+export interface ModelStats extends JobTotals {
+  provider: ProviderId;
+  modelId: string;
+}
+
+/** `get_translation_stats` 결과 */
+export interface TranslationStats {
+  lifetime: JobTotals;
+  byModel: ModelStats[];
+}
+
+/** `get_provider_usage_metrics` 결과: 앱을 시작한 뒤 제공자별 요청 통계 */
+export interface ProviderUsageMetrics {
+  provider: ProviderId;
+  /** 보낸 번역 요청 수 (실패 포함) */
+  requests: number;
+  /** 오류로 끝난 요청 수 */
+  failures: number;
+  /** 429(요청 한도 초과)로 끝난 요청 수 */
+  rateLimited: number;
+  /** 작업이 같은 세그먼트를 다시 요청한 횟수 */
+  retries: number;
+  /** 요청 하나의 평균 응답 시간; 요청이 없으면 없음 */
+  averageLatencyMs?: number;
+  promptTokens: number;
+  completionTokens: number;
+}
+
+export type RecoveryOption = "resume" | "rollback" | "discard";
+
+export interface RecoveredOutput {
+  relativePath: string;
+  absolutePath: string;
+  state: "intact" | "modified" | "missing";
+}
+
+/** 앱이 비정상 종료되어 끝나지 못한 작업 ("recoverable-jobs" 이벤트, `list_recoverable_jobs`) */
+export interface RecoverableJob {
+  jobId: string;
+  checkpoint: TranslationCheckpoint;
+  usage: TranslationUsage;
+  savedAt?: string;
+  filesInProgress: number;
+  outputs: RecoveredOutput[];
+  options: RecoveryOption[];
+}
+
+export interface RecoverableJobsEventPayload {
+  jobs: RecoverableJob[];
+}
+
 export interface TranslationProgressEventPayload {
   jobId: string;
   status: TranslationProgressState;
@@ -187,6 +627,8 @@ export interface TranslationProgressEventPayload {
   totalCount?: number;
   fileName?: string | null;
   fileSuccess?: boolean | null;
+  /** `fileName` 파일의 진행률 (문자 수 기준, 0–100) */
+  fileProgressPct?: number;
   fileErrors?: TranslationFileErrorEntry[];
   lastWritten?: {
     sourceRelativePath: string;
@@ -196,6 +638,7 @@ export interface TranslationProgressEventPayload {
   retry?: TranslationRetryInfo;
   resumeHint?: TranslationResumeHint;
   checkpoint?: TranslationCheckpoint | null;
+  usage?: TranslationUsage;
   metrics?: TranslationAttemptMetrics;
 }
 
@@ -213,12 +656,94 @@ export interface TranslationBackoffCancelledPayload {
   by: "user" | "logic";
 }
 
+export interface TranslationBudgetExceededPayload {
+  jobId: string;
+  limitKind: "tokens" | "cost";
+  limit: number;
+  used: number;
+  usage: TranslationUsage;
+  checkpoint: TranslationCheckpoint;
+}
+
+/** 작업 중 모델을 대체 모델로 바꿨을 때 ("translation-model-fallback" 이벤트) */
+export interface TranslationModelFallbackPayload {
+  jobId: string;
+  provider: ProviderId;
+  failedModel: string;
+  fallbackModel: string;
+}
+
+/** 포함/제외 글롭으로 건너뛴 파일 수 ("translation-file-filter" 이벤트) */
+export interface TranslationFileFilterPayload {
+  jobId: string;
+  totalFiles: number;
+  translatedFiles: number;
+  /** 제외 글롭별로 건너뛴 파일 수 (여러 글롭에 맞으면 첫 글롭에만 셈) */
+  excludedByPattern: Record<string, number>;
+  /** 포함 글롭에 맞지 않아 건너뛴 파일 수 */
+  notIncluded: number;
+}
+
 export interface TranslationRetryStartedPayload {
   jobId: string;
   attempt: number;
 }
 
-export type ArchiveType = "jar" | "zip";
+/** "translation-job-event" 이벤트로 오는 작업 진행 단계 */
+export type JobEvent =
+  | {
+      type: "started";
+      jobId: string;
+      totalFiles: number;
+      totalSegments: number;
+      resumedSegments: number;
+    }
+  | { type: "file_started"; jobId: string; filePath: string }
+  | {
+      type: "segment_translated";
+      jobId: string;
+      filePath: string;
+      lineNumber: number;
+      applied: boolean;
+      warnings?: string[];
+      translatedCount: number;
+      totalCount: number;
+    }
+  | {
+      type: "backoff";
+      jobId: string;
+      delayMs: number;
+      attempt: number;
+      maxAttempts: number;
+      reason: string;
+    }
+  | { type: "retry"; jobId: string; attempt: number }
+  | {
+      type: "file_written";
+      jobId: string;
+      sourceRelativePath: string;
+      outputRelativePath: string;
+      outputAbsolutePath: string;
+    }
+  | {
+      type: "completed";
+      jobId: string;
+      partialSuccess: boolean;
+      translatedCount: number;
+      totalCount: number;
+    }
+  | { type: "failed"; jobId: string; message?: string }
+  | { type: "canceled"; jobId: string; translatedCount: number };
+
+export type ArchiveType = "jar" | "zip" | "7z" | "rar";
+
+/** `archive-scan-progress` event payload emitted by `list_mod_files` */
+export interface ArchiveScanProgress {
+  archivePath: string;
+  scannedEntries: number;
+  totalEntries: number;
+  languageFiles: number;
+}
 
 export interface ModFileDescriptor {
   path: string;
@@ -242,7 +767,8 @@ export type ValidationErrorCode =
   | "PAIR_UNBALANCED"
   | "FORMAT_TOKEN_MISSING"
   | "XML_MALFORMED_AFTER_RESTORE"
-  | "RETRY_FAILED";
+  | "RETRY_FAILED"
+  | "PROFILE_RULE_VIOLATION";
 
 export type RecoveryStep =
   | "REINJECT_MISSING_PROTECTED"
@@ -281,12 +807,99 @@ export interface ValidationFailureReport {
   sourceLine: string;
   preprocessedSource: string;
   candidateLine: string;
+  /** 원문에서 보호된 토큰의 종류 (예: TAG, PRINTF) */
+  tokenTypes?: string[];
   autofix: AutofixResult;
   retry: RetryInfo;
   uiHint: UiHint;
 }
 
-export interface ValidatorConfig {
+export interface QcSegment {
+  filePath: string;
+  lineNumber: number;
+  source: string;
+  translation?: string;
+  status: SegmentReviewStatus;
+  messages?: string[];
+  /** 품질 추정 신뢰도 (0~100) */
+  confidence?: number;
+}
+
+export interface QcFileError {
+  filePath: string;
+  message: string;
+  code?: string;
+}
+
+export interface QcSummary {
+  totalSegments: number;
+  /** 자동 복구된 세그먼트를 포함한 번역 수 */
+  translated: number;
+  recovered: number;
+  warnings: number;
+  /** 품질 추정 점수가 기준보다 낮은 번역 수 */
+  lowConfidence: number;
+  rolledBack: number;
+  /** 검토 대기 또는 요청 실패로 원문을 유지한 세그먼트 수 */
+  keptOriginal: number;
+  validationFailures: number;
+  fileErrors: number;
+  /** 원문이 이미 대상 언어라 건너뛴 세그먼트 수 */
+  alreadyTranslated: number;
+  /** 번역 제외 목록에 있어 건너뛴 세그먼트 수 */
+  doNotTranslate: number;
+}
+
+/** `get_job_qc_report`가 돌려주는 작업별 QC 보고서 */
+export interface QcReport {
+  jobId: string;
+  sourceLang: string;
+  targetLang: string;
+  provider: string;
+  modelId: string;
+  generatedAt: string;
+  summary: QcSummary;
+  validationFailures: ValidationFailureReport[];
+  /** QC 경고와 함께 적용된 번역 */
+  warnings: QcSegment[];
+  /** 품질 추정 점수가 기준보다 낮은 번역 (낮은 순) */
+  lowConfidence: QcSegment[];
+  rolledBack: QcSegment[];
+  keptOriginal: QcSegment[];
+  fileErrors: QcFileError[];
+  alreadyTranslated: QcSegment[];
+  doNotTranslate: QcSegment[];
+}
+
+export type TriageResolution = "retranslated" | "accepted_original";
+
+/** `list_validation_failures`가 돌려주는, 원문을 유지한 세그먼트 */
+export interface FailedSegment {
+  /** `<작업 ID>:<번호>` 형식으로 작업 간에도 고유 */
+  segmentId: string;
+  fileIndex: number;
+  filePath: string;
+  lineNumber: number;
+  source: string;
+  prefix: string;
+  suffix: string;
+  status: SegmentReviewStatus;
+  messages?: string[];
+  failure?: ValidationFailureReport;
+  resolution?: TriageResolution;
+  translation?: string;
+}
+
+export interface RetranslationResult {
+  segmentId: string;
+  translation?: string;
+  error?: string;
+}
+
+export type ValidatorMode = "strict" | "relaxed_xml" | "relaxed_xml_plus";
+
+/** 검증 모드를 뺀 나머지 검증기 설정 */
+export interface ValidatorOptions {
   enableAutofix: boolean;
   retryOnFail: boolean;
   retryLimit: number;
@@ -294,6 +907,10 @@ export interface ValidatorConfig {
   preservePercentBinding: boolean;
 }
 
+export interface ValidatorConfig extends ValidatorOptions {
+  validationMode: ValidatorMode;
+}
+
 export interface ValidationMetrics {
   totalValidations: number;
   totalFailures: number;
@@ -304,4 +921,214 @@ export interface ValidationMetrics {
   byErrorCode: Record<string, number>;
 }
 
+export interface ProfileCandidate {
+  profileId: string;
+  name: string;
+  confidence: number;
+  detected: boolean;
+  signals: string[];
+}
+
+export interface ModProfileDetection {
+  candidates: ProfileCandidate[];
+  overrideProfileId: string | null;
+  activeProfileId: string | null;
+}
+
+export interface GameProfileDetectionRules {
+  folder_patterns: string[];
+  file_patterns: string[];
+  manifest_signatures: string[];
+}
+
+export interface GameProfile {
+  id: string;
+  name: string;
+  detector: GameProfileDetectionRules;
+  include_paths: string[];
+  exclude_paths: string[];
+  extra_placeholders: string[];
+  terminology: Record<string, string>;
+  validator_config: {
+    allowedTokenTypes: string[];
+    csvTargetColumns: number[];
+    forceFixedPatterns: string[];
+  };
+  prompt_config?: {
+    system?: string | null;
+    user?: string | null;
+    gameContext?: string | null;
+    tone?: string | null;
+  };
+  skip_list?: SkipList;
+  qc_rules?: {
+    maxVisualWidth?: number | null;
+    forbidLeadingPunctuation?: boolean | null;
+    normalizePunctuation?: boolean | null;
+  };
+  /** `before_send`/`after_receive` 훅이 있는 Rhai 스크립트 (사용자 프로필 폴더 기준) */
+  script?: string;
+}
+
+export interface ProfileLoadError {
+  file: string;
+  message: string;
+}
+
+export interface GameProfileListing {
+  profiles: GameProfile[];
+  userProfileIds: string[];
+  errors: ProfileLoadError[];
+  userProfileDir: string;
+}
+
+/** 외부 추출기 플러그인의 `plugin.toml`/`plugin.json` */
+export interface PluginManifest {
+  name: string;
+  command: string;
+  args: string[];
+  extensions: string[];
+  timeoutSecs?: number;
+  dir: string;
+}
+
+export interface PluginLoadError {
+  dir: string;
+  message: string;
+}
+
+export interface PluginListing {
+  plugins: PluginManifest[];
+  errors: PluginLoadError[];
+  pluginDir: string;
+}
+
+export interface MemoryEntry {
+  source: string;
+  target: string;
+  file: string;
+}
+
+export interface ExportedProject {
+  bundlePath: string;
+  fileCount: number;
+  memoryEntries: number;
+}
+
+export interface ImportedProject {
+  modName: string;
+  settings: unknown | null;
+  glossary: Record<string, string>;
+  translationMemory: MemoryEntry[];
+  profileOverride: string | null;
+  written: string[];
+  skipped: string[];
+}
+
+export interface ProjectMod {
+  modPath: string;
+  /** 선택한 파일 (비어 있으면 기본 선택 파일) */
+  files: TranslationFileDescriptor[];
+}
+
+export interface ProjectOutputOverrides {
+  outputOverrideDir?: string | null;
+  outputEncoding?: OutputEncoding;
+  allowSourceOverwrite?: boolean;
+  embedProvenance?: boolean;
+  exportReview?: boolean;
+}
+
+/** `save_project`/`load_project`로 저장하는 작업 설정 (API 키는 저장하지 않음) */
+export interface SavedProject {
+  name: string;
+  mods: ProjectMod[];
+  provider: string;
+  modelId: string;
+  sourceLang?: string | null;
+  targetLang?: string | null;
+  output?: ProjectOutputOverrides;
+  createdAt?: string | null;
+  updatedAt?: string | null;
+}
+
+/** `add_api_key`로 코어에 저장한 API 키 (키 자체는 코어 밖으로 나오지 않음) */
+export interface StoredApiKey {
+  provider: ProviderId;
+  /** 앞 4자와 끝 2자만 보이는 키 */
+  masked: string;
+  addedAt: string;
+}
+
+export type PricingTier = "budget" | "standard" | "premium" | "unknown";
+
+/** 모델 목록 항목과 모델별 특성 */
+export interface ModelInfo {
+  id: string;
+  /** 컨텍스트 창 크기 (토큰, 알 수 없으면 null) */
+  contextWindow: number | null;
+  /** 최대 출력 토큰 수 (알 수 없으면 null) */
+  maxOutputTokens: number | null;
+  pricingTier: PricingTier;
+  /** 모드 문자열 번역에 권장되는 모델인지 여부 */
+  recommendedForTranslation: boolean;
+}
+
+export interface ProjectSummary {
+  name: string;
+  modCount: number;
+  fileCount: number;
+  provider: string;
+  modelId: string;
+  targetLang: string | null;
+  updatedAt: string | null;
+}
+
+export interface InstallFileError {
+  path: string;
+  message: string;
+}
+
+export interface InstallReport {
+  targetDir: string;
+  installed: string[];
+  unchanged: string[];
+  errors: InstallFileError[];
+}
+
+export interface RollbackReport {
+  jobId: string;
+  restored: string[];
+  removed: string[];
+  kept: string[];
+  errors: InstallFileError[];
+}
+
+export interface UninstallReport {
+  targetDir: string;
+  restored: string[];
+  removed: string[];
+  kept: string[];
+  errors: InstallFileError[];
+}
+
+export interface DefInjectedFileError {
+  path: string;
+  message: string;
+}
+
+export interface DefInjectedReport {
+  created: string[];
+  entries: number;
+  skipped: string[];
+  errors: DefInjectedFileError[];
+}
+
+export interface StardewI18nReport {
+  defaultFile: string;
+  referencedKeys: number;
+  addedKeys: string[];
+  created: boolean;
+}
+
 // Legacy pipeline-related types removed in favor of streaming progress events.
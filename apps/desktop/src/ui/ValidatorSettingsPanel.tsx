@@ -1,9 +1,9 @@
 import { useState } from 'react'
-import type { ValidatorConfig } from '../types/core'
+import type { ValidatorOptions } from '../types/core'
 
 interface ValidatorSettingsPanelProps {
-  config: ValidatorConfig
-  onChange: (config: ValidatorConfig) => void
+  config: ValidatorOptions
+  onChange: (config: ValidatorOptions) => void
 }
 
 export function ValidatorSettingsPanel({
@@ -12,7 +12,7 @@ export function ValidatorSettingsPanel({
 }: ValidatorSettingsPanelProps) {
   const [localConfig, setLocalConfig] = useState(config)
 
-  const handleChange = (key: keyof ValidatorConfig, value: boolean | number) => {
+  const handleChange = (key: keyof ValidatorOptions, value: boolean | number) => {
     const newConfig = { ...localConfig, [key]: value }
     setLocalConfig(newConfig)
     onChange(newConfig)
@@ -34,6 +34,8 @@ const statusLabels: Record<JobState, string> = {
   completed: "완료됨",
   failed: "실패",
   canceled: "중단됨",
+  paused: "일시 중지됨",
+  awaiting_approval: "승인 대기",
   partial_success: "부분 성공",
 };
 
@@ -43,6 +45,8 @@ const statusTones: Record<JobState, ChipTone> = {
   completed: "primary",
   failed: "error",
   canceled: "warning",
+  paused: "warning",
+  awaiting_approval: "warning",
   partial_success: "warning",
 };
 
@@ -52,6 +56,8 @@ const progressClasses: Record<JobState, string> = {
   completed: "bg-emerald-500",
   failed: "bg-rose-500",
   canceled: "bg-slate-700",
+  paused: "bg-amber-600",
+  awaiting_approval: "bg-amber-600",
   partial_success: "bg-amber-500",
 };
 
@@ -644,6 +650,14 @@ function ProgressView() {
               언어: {sourceLanguageLabel} → {targetLanguageLabel}
             </p>
             {translatedSummary && <p>{translatedSummary}</p>}
+            {isRunning &&
+              currentJob.latestFileName &&
+              currentJob.latestFileProgress !== null && (
+                <p className="truncate" title={currentJob.latestFileName}>
+                  현재 파일: {currentJob.latestFileName} ·{" "}
+                  {currentJob.latestFileProgress}%
+                </p>
+              )}
             <p>선택된 파일 {selectedFilePaths.length}개</p>
           </div>
           {currentJobFailedFiles.length > 0 && (
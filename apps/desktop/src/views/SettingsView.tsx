@@ -2,13 +2,13 @@ import { useCallback, useEffect, useMemo, useState } from "react";
 import type { ChangeEvent } from "react";
 import { useTranslation } from "react-i18next";
 import { useLibraryContext } from "../context/LibraryContext";
-import { maskApiKey } from "../storage/apiKeyStorage";
 import {
   useSettingsStore,
   type KeyValidationState,
   type ProviderId,
 } from "../context/SettingsStore";
 import type { RetryPolicy, RetryableErrorCode } from "../types/core";
+import { ValidatorSettingsPanel } from "../ui/ValidatorSettingsPanel";
 
 const providers: Array<{ id: ProviderId; name: string; description: string }> =
   [
@@ -191,14 +191,13 @@ function SettingsView() {
     setUseServerHints,
     validationMode,
     setValidationMode,
+    validatorOptions,
+    setValidatorOptions,
     setProviderModel,
     setProviderRetryPolicy,
   } = useSettingsStore();
-  const activeProviderKey = activeProviderId
-    ? (apiKeys[activeProviderId] ?? "")
-    : "";
   const activeProviderKeyMissing =
-    Boolean(activeProviderId) && !activeProviderKey.trim();
+    Boolean(activeProviderId) && !apiKeys[activeProviderId];
   const activeProviderName = useMemo(
     () =>
       providers.find((provider) => provider.id === activeProviderId)?.name ??
@@ -308,7 +307,8 @@ function SettingsView() {
   const handleStartEditing = useCallback(
     (providerId: string) => {
       setEditingProvider(providerId);
-      setDraftApiKey(apiKeys[providerId] ?? "");
+      // 저장된 키는 코어 밖으로 나오지 않으므로 새 키를 입력받음
+      setDraftApiKey("");
       setApiKeyMessage("");
       setApiKeyError(null);
       setApiKeyStatus((prev) => {
@@ -317,7 +317,7 @@ function SettingsView() {
         return next;
       });
     },
-    [apiKeys],
+    [],
   );
 
   const handleApiKeyChange = useCallback((value: string) => {
@@ -432,7 +432,7 @@ function SettingsView() {
           )}
           <div className="mt-4 space-y-4">
             {providers.map((provider) => {
-              const storedValue = apiKeys[provider.id] ?? "";
+              const storedKey = apiKeys[provider.id];
               const isEditing = editingProvider === provider.id;
               const modelOptions = providerModelOptions[provider.id] ?? [];
               const verifiedList = verifiedModels?.[provider.id] ?? [];
@@ -544,7 +544,6 @@ function SettingsView() {
                             if (!checking) {
                               void revalidateProviderKey(
                                 provider.id,
-                                undefined,
                                 nextValue,
                               );
                             }
@@ -613,7 +612,6 @@ function SettingsView() {
                           if (checking) return;
                           await revalidateProviderKey(
                             provider.id,
-                            undefined,
                             selectedModel,
                           );
                         }}
@@ -658,8 +656,8 @@ function SettingsView() {
                         ) : (
                           <>
                             <p className="text-xs text-slate-400 sm:text-sm">
-                              {storedValue
-                                ? `저장된 키: ${maskApiKey(storedValue)}`
+                              {storedKey
+                                ? `저장된 키: ${storedKey.masked}`
                                 : "저장된 키가 없습니다."}
                             </p>
                             <div className="flex items-center gap-3">
@@ -671,9 +669,9 @@ function SettingsView() {
                                   }
                                   className="inline-flex items-center justify-center rounded-xl border border-slate-700 px-4 py-2 text-sm font-semibold text-slate-200 transition hover:border-brand-500 hover:text-brand-200"
                                 >
-                                  {storedValue ? "수정" : "추가"}
+                                  {storedKey ? "수정" : "추가"}
                                 </button>
-                                {storedValue && (
+                                {storedKey && (
                                   <button
                                     type="button"
                                     onClick={() =>
@@ -1136,7 +1134,11 @@ function SettingsView() {
                 value={validationMode}
                 onChange={(event) => {
                   const value = event.target.value;
-                  if (value === "strict" || value === "relaxed_xml") {
+                  if (
+                    value === "strict" ||
+                    value === "relaxed_xml" ||
+                    value === "relaxed_xml_plus"
+                  ) {
                     setValidationMode(value);
                   }
                 }}
@@ -1148,6 +1150,9 @@ function SettingsView() {
                 <option value="relaxed_xml">
                   {t("settings.rules.validationModes.relaxed_xml")}
                 </option>
+                <option value="relaxed_xml_plus">
+                  {t("settings.rules.validationModes.relaxed_xml_plus")}
+                </option>
               </select>
             </label>
             <p className="text-xs text-slate-400">
@@ -1177,6 +1182,13 @@ function SettingsView() {
             </label>
           </div>
         </section>
+
+        <section className="rounded-2xl border border-slate-800/60 bg-slate-900/60 p-6 shadow-inner shadow-black/30">
+          <ValidatorSettingsPanel
+            config={validatorOptions}
+            onChange={setValidatorOptions}
+          />
+        </section>
       </form>
     </div>
   );
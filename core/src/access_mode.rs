@@ -0,0 +1,155 @@
+/// Read-only mode for shared machines.
+///
+/// When enabled, every command that writes to mod directories or archives
+/// (starting or queueing jobs, approving segments, re-validating outputs,
+/// importing project bundles) fails with a clear error. Scanning, previews,
+/// token inspection, cost estimation and report viewing keep working, so
+/// reviewers and first-time users can explore without touching their mods.
+///
+/// Offline mode is kept in the same file. While it is on, no request is sent
+/// to a translation provider: model lists come from the saved catalog,
+/// library scans are answered from the library index, and translation jobs
+/// fail at their preflight check instead of timing out segment by segment.
+use crate::paths::app_data_dir;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const ACCESS_MODE_FILE_NAME: &str = "access-mode.json";
+
+/// On-disk file: the read-only and offline flags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccessModeFile {
+    #[serde(default)]
+    read_only: bool,
+    #[serde(default)]
+    offline: bool,
+}
+
+impl AccessModeFile {
+    fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|error| {
+            warn!("ignoring unreadable access mode {}: {}", path.display(), error);
+            Self::default()
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create access mode dir: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize access mode: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write access mode: {}", e))
+    }
+}
+
+static ACCESS_MODE: Lazy<Mutex<AccessModeFile>> =
+    Lazy::new(|| Mutex::new(AccessModeFile::load(&get_access_mode_path())));
+
+/// Location of the access mode file.
+pub fn get_access_mode_path() -> PathBuf {
+    app_data_dir().join(ACCESS_MODE_FILE_NAME)
+}
+
+pub fn is_read_only() -> bool {
+    ACCESS_MODE
+        .lock()
+        .map(|guard| guard.read_only)
+        .unwrap_or(false)
+}
+
+pub fn is_offline() -> bool {
+    ACCESS_MODE
+        .lock()
+        .map(|guard| guard.offline)
+        .unwrap_or(false)
+}
+
+fn read_only_error(action: &str) -> String {
+    format!(
+        "읽기 전용 모드에서는 {} 작업을 할 수 없습니다. 설정에서 읽기 전용 모드를 해제하세요.",
+        action
+    )
+}
+
+/// Fails with a user-facing error when read-only mode is on. `action`
+/// names the blocked operation, e.g. "번역 시작".
+pub fn ensure_writable(action: &str) -> Result<(), String> {
+    if is_read_only() {
+        return Err(read_only_error(action));
+    }
+    Ok(())
+}
+
+/// Tauri command to check whether read-only mode is enabled
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn get_read_only_mode() -> Result<bool, String> {
+    ACCESS_MODE
+        .lock()
+        .map(|guard| guard.read_only)
+        .map_err(|_| "access mode lock poisoned".to_string())
+}
+
+/// Tauri command to turn read-only mode on or off
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn set_read_only_mode(enabled: bool) -> Result<(), String> {
+    let mut guard = ACCESS_MODE
+        .lock()
+        .map_err(|_| "access mode lock poisoned".to_string())?;
+    guard.read_only = enabled;
+    guard.save(&get_access_mode_path())
+}
+
+/// Tauri command to check whether offline mode is enabled
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn get_offline_mode() -> Result<bool, String> {
+    ACCESS_MODE
+        .lock()
+        .map(|guard| guard.offline)
+        .map_err(|_| "access mode lock poisoned".to_string())
+}
+
+/// Tauri command to turn offline mode on or off
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn set_offline_mode(enabled: bool) -> Result<(), String> {
+    let mut guard = ACCESS_MODE
+        .lock()
+        .map_err(|_| "access mode lock poisoned".to_string())?;
+    guard.offline = enabled;
+    guard.save(&get_access_mode_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_mode_defaults_to_writable_and_roundtrips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(ACCESS_MODE_FILE_NAME);
+        let mut file = AccessModeFile::load(&path);
+        assert!(!file.read_only);
+
+        file.read_only = true;
+        file.save(&path).unwrap();
+        assert!(AccessModeFile::load(&path).read_only);
+        assert!(!AccessModeFile::load(&path).offline);
+
+        fs::write(&path, r#"{"offline": true}"#).unwrap();
+        let file = AccessModeFile::load(&path);
+        assert!(file.offline && !file.read_only);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(!AccessModeFile::load(&path).read_only);
+        assert!(read_only_error("번역 시작").contains("번역 시작"));
+    }
+}
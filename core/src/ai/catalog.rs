@@ -0,0 +1,553 @@
+/// Model catalog: cached model lists and per-model capabilities.
+///
+/// Listing models takes a network round trip per provider, and the settings
+/// screen asks for the list every time a key is entered or the screen is
+/// opened. Lists are kept for [`CATALOG_TTL`] per provider and API key. Each
+/// model is annotated with its context window, a pricing tier and whether it
+/// suits string translation, so the UI can guide the choice of model and
+/// requests that cannot fit the context window are rejected before they are
+/// sent.
+///
+/// Every fetched list is also saved to `model-catalog.json` in the app data
+/// folder. The saved list is what offline mode answers with, and what the
+/// settings screen keeps showing when a refresh cannot reach the provider.
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::pricing::pricing_for;
+use super::ProviderId;
+use crate::paths::app_data_dir;
+
+/// How long a fetched model list is reused
+pub const CATALOG_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const CATALOG_FILE_NAME: &str = "model-catalog.json";
+
+static CATALOGS: Lazy<Mutex<CatalogCache>> = Lazy::new(|| Mutex::new(CatalogCache::default()));
+
+/// Token limits of a model family and whether it is a good default for
+/// translating mod strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    pub context_window: u32,
+    pub max_output_tokens: u32,
+    pub recommended: bool,
+}
+
+const fn caps(context_window: u32, max_output_tokens: u32, recommended: bool) -> ModelCapabilities {
+    ModelCapabilities {
+        context_window,
+        max_output_tokens,
+        recommended,
+    }
+}
+
+/// Model id prefixes and their capabilities. As with the pricing table, more
+/// specific prefixes must come first because the first match wins.
+const CAPABILITY_TABLE: &[(ProviderId, &str, ModelCapabilities)] = &[
+    (ProviderId::Gpt, "gpt-4o-mini", caps(128_000, 16_384, true)),
+    (ProviderId::Gpt, "gpt-4o", caps(128_000, 16_384, true)),
+    (ProviderId::Gpt, "gpt-4.1-nano", caps(1_047_576, 32_768, false)),
+    (ProviderId::Gpt, "gpt-4.1", caps(1_047_576, 32_768, true)),
+    (ProviderId::Gpt, "gpt-5", caps(400_000, 128_000, false)),
+    (ProviderId::Gpt, "o1", caps(200_000, 100_000, false)),
+    (ProviderId::Gpt, "o3", caps(200_000, 100_000, false)),
+    (ProviderId::Gpt, "o4-mini", caps(200_000, 100_000, false)),
+    (ProviderId::Gpt, "gpt-3.5-turbo", caps(16_385, 4_096, false)),
+    (ProviderId::Claude, "claude-3-haiku", caps(200_000, 4_096, false)),
+    (ProviderId::Claude, "claude-3-opus", caps(200_000, 4_096, false)),
+    (ProviderId::Claude, "claude-3-5", caps(200_000, 8_192, true)),
+    (ProviderId::Claude, "claude-3-7", caps(200_000, 64_000, true)),
+    (ProviderId::Claude, "claude-opus", caps(200_000, 32_000, false)),
+    (ProviderId::Claude, "claude-sonnet", caps(200_000, 64_000, true)),
+    (ProviderId::Claude, "claude-haiku", caps(200_000, 64_000, true)),
+    (ProviderId::Gemini, "gemini-1.5-pro", caps(2_097_152, 8_192, false)),
+    (ProviderId::Gemini, "gemini-1.5-flash", caps(1_048_576, 8_192, false)),
+    (ProviderId::Gemini, "gemini-2.0-flash", caps(1_048_576, 8_192, true)),
+    (ProviderId::Gemini, "gemini-2.5", caps(1_048_576, 65_536, true)),
+    (ProviderId::Gemini, "gemma", caps(32_768, 8_192, false)),
+    (ProviderId::Grok, "grok-2", caps(131_072, 8_192, false)),
+    (ProviderId::Grok, "grok-3-mini", caps(131_072, 16_384, false)),
+    (ProviderId::Grok, "grok-3", caps(131_072, 16_384, true)),
+    (ProviderId::Grok, "grok-4-fast", caps(2_000_000, 30_000, true)),
+    (ProviderId::Grok, "grok-4", caps(256_000, 16_384, false)),
+];
+
+/// Looks up the capabilities of a model. Returns `None` for unknown models,
+/// which are never rejected for their size.
+pub fn capabilities_for(provider: ProviderId, model_id: &str) -> Option<ModelCapabilities> {
+    let normalized = normalize_model_id(model_id);
+    CAPABILITY_TABLE
+        .iter()
+        .find(|(candidate, prefix, _)| *candidate == provider && normalized.starts_with(prefix))
+        .map(|(_, _, capabilities)| *capabilities)
+}
+
+/// Context window of a model in tokens, if known
+pub fn context_window_for(provider: ProviderId, model_id: &str) -> Option<u32> {
+    capabilities_for(provider, model_id).map(|capabilities| capabilities.context_window)
+}
+
+fn normalize_model_id(model_id: &str) -> String {
+    model_id
+        .trim()
+        .trim_start_matches("models/")
+        .to_ascii_lowercase()
+}
+
+/// Rough cost class of a model, from its input list price
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PricingTier {
+    /// Up to $0.50 per million input tokens
+    Budget,
+    /// Up to $3 per million input tokens
+    Standard,
+    Premium,
+    Unknown,
+}
+
+impl PricingTier {
+    pub fn for_model(provider: ProviderId, model_id: &str) -> Self {
+        match pricing_for(provider, model_id) {
+            Some(pricing) if pricing.input_per_million <= 0.5 => PricingTier::Budget,
+            Some(pricing) if pricing.input_per_million <= 3.0 => PricingTier::Standard,
+            Some(_) => PricingTier::Premium,
+            None => PricingTier::Unknown,
+        }
+    }
+}
+
+/// A model offered by a provider, as shown in the model picker
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelInfo {
+    pub id: String,
+    /// 컨텍스트 창 크기 (토큰, 알 수 없으면 없음)
+    pub context_window: Option<u32>,
+    /// 최대 출력 토큰 수 (알 수 없으면 없음)
+    pub max_output_tokens: Option<u32>,
+    pub pricing_tier: PricingTier,
+    /// 모드 문자열 번역에 권장되는 모델인지 여부
+    pub recommended_for_translation: bool,
+}
+
+impl ModelInfo {
+    pub fn describe(provider: ProviderId, model_id: &str) -> Self {
+        let capabilities = capabilities_for(provider, model_id);
+        Self {
+            id: model_id.trim().to_string(),
+            context_window: capabilities.map(|capabilities| capabilities.context_window),
+            max_output_tokens: capabilities.map(|capabilities| capabilities.max_output_tokens),
+            pricing_tier: PricingTier::for_model(provider, model_id),
+            recommended_for_translation: capabilities
+                .is_some_and(|capabilities| capabilities.recommended),
+        }
+    }
+}
+
+/// Built-in replacements for retired or renamed models, used when a job has
+/// no configured fallback for the model it lost. Matched by prefix like the
+/// capability table.
+const DEFAULT_FALLBACKS: &[(ProviderId, &str, &str)] = &[
+    (ProviderId::Gemini, "gemini-1.5-flash", "gemini-2.0-flash"),
+    (ProviderId::Gemini, "gemini-1.5-pro", "gemini-2.5-pro"),
+    (ProviderId::Gemini, "gemini-2.0-flash-lite", "gemini-2.5-flash-lite"),
+    (ProviderId::Gemini, "gemini-2.0-flash", "gemini-2.5-flash"),
+    (ProviderId::Gpt, "gpt-3.5-turbo", "gpt-4o-mini"),
+    (ProviderId::Gpt, "gpt-4-turbo", "gpt-4o"),
+    (ProviderId::Claude, "claude-3-haiku", "claude-3-5-haiku-latest"),
+    (ProviderId::Claude, "claude-3-5-sonnet", "claude-sonnet-4-5"),
+    (ProviderId::Claude, "claude-3-opus", "claude-opus-4-1"),
+    (ProviderId::Grok, "grok-2", "grok-3"),
+    (ProviderId::Grok, "grok-beta", "grok-3"),
+];
+
+/// Model to switch to after `failed_model` was reported missing.
+///
+/// The job's `configured` fallback for the model comes first, then the
+/// built-in replacement; an empty configured value turns fallback off for
+/// that model. With a cached `catalog` only listed models are picked, and
+/// when neither candidate is listed a recommended model of the same pricing
+/// tier (or any recommended model) is used. Models in `tried` are skipped so
+/// a job never cycles between missing models.
+pub fn fallback_model(
+    provider: ProviderId,
+    failed_model: &str,
+    configured: &HashMap<String, String>,
+    catalog: Option<&[ModelInfo]>,
+    tried: &[String],
+) -> Option<String> {
+    let failed = normalize_model_id(failed_model);
+    let configured = configured
+        .iter()
+        .find(|(model, _)| normalize_model_id(model) == failed)
+        .map(|(_, fallback)| fallback.trim());
+    if configured == Some("") {
+        return None;
+    }
+    let built_in = DEFAULT_FALLBACKS
+        .iter()
+        .find(|(candidate, prefix, _)| *candidate == provider && failed.starts_with(prefix))
+        .map(|(_, _, fallback)| *fallback);
+    let is_new = |model: &str| {
+        let normalized = normalize_model_id(model);
+        normalized != failed
+            && !tried
+                .iter()
+                .any(|entry| normalize_model_id(entry) == normalized)
+    };
+    let mut candidates = configured
+        .into_iter()
+        .chain(built_in)
+        .filter(|model| is_new(model));
+
+    let Some(catalog) = catalog else {
+        return candidates.next().map(str::to_string);
+    };
+    let listed = |model: &str| {
+        catalog
+            .iter()
+            .find(|entry| normalize_model_id(&entry.id) == normalize_model_id(model))
+    };
+    if let Some(entry) = candidates.find_map(listed) {
+        return Some(entry.id.clone());
+    }
+    let tier = PricingTier::for_model(provider, failed_model);
+    let recommended = || {
+        catalog
+            .iter()
+            .filter(|entry| entry.recommended_for_translation && is_new(&entry.id))
+    };
+    recommended()
+        .find(|entry| entry.pricing_tier == tier)
+        .or_else(|| recommended().next())
+        .map(|entry| entry.id.clone())
+}
+
+#[derive(Debug)]
+struct CachedCatalog {
+    /// Hash of the API key the list was fetched with; other keys may see
+    /// other models
+    key_fingerprint: String,
+    fetched_at: Instant,
+    models: Vec<ModelInfo>,
+}
+
+/// Model lists per provider, from the last successful key validation
+#[derive(Debug, Default)]
+pub struct CatalogCache {
+    entries: HashMap<ProviderId, CachedCatalog>,
+}
+
+impl CatalogCache {
+    /// Annotates and stores the models `api_key` can use at `provider`
+    pub fn store(
+        &mut self,
+        provider: ProviderId,
+        api_key: &str,
+        model_ids: &[String],
+        now: Instant,
+    ) -> Vec<ModelInfo> {
+        let models: Vec<ModelInfo> = model_ids
+            .iter()
+            .map(|model_id| ModelInfo::describe(provider, model_id))
+            .collect();
+        self.entries.insert(
+            provider,
+            CachedCatalog {
+                key_fingerprint: fingerprint(api_key),
+                fetched_at: now,
+                models: models.clone(),
+            },
+        );
+        models
+    }
+
+    /// Models fetched with `api_key` less than [`CATALOG_TTL`] ago; any key
+    /// when `api_key` is `None`
+    pub fn get(
+        &self,
+        provider: ProviderId,
+        api_key: Option<&str>,
+        now: Instant,
+    ) -> Option<Vec<ModelInfo>> {
+        let entry = self.entries.get(&provider)?;
+        if now.saturating_duration_since(entry.fetched_at) >= CATALOG_TTL {
+            return None;
+        }
+        if api_key.is_some_and(|key| fingerprint(key) != entry.key_fingerprint) {
+            return None;
+        }
+        Some(entry.models.clone())
+    }
+
+    pub fn invalidate(&mut self, provider: ProviderId) {
+        self.entries.remove(&provider);
+    }
+}
+
+/// Short SHA-256 of the key, stable across runs so saved lists can be
+/// matched to it without storing the key itself
+fn fingerprint(api_key: &str) -> String {
+    let digest = Sha256::digest(api_key.trim().as_bytes());
+    hex::encode(&digest[..8])
+}
+
+/// A model list as saved on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedCatalog {
+    key_fingerprint: String,
+    fetched_at: DateTime<Utc>,
+    models: Vec<String>,
+}
+
+/// On-disk file: the last list fetched for each provider
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedCatalogs {
+    #[serde(default)]
+    providers: HashMap<ProviderId, SavedCatalog>,
+}
+
+impl SavedCatalogs {
+    fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|error| {
+            warn!(
+                "ignoring unreadable model catalog {}: {}",
+                path.display(),
+                error
+            );
+            Self::default()
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create model catalog dir: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize model catalog: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write model catalog: {}", e))
+    }
+
+    fn get(
+        &self,
+        provider: ProviderId,
+        api_key: Option<&str>,
+    ) -> Option<(Vec<ModelInfo>, DateTime<Utc>)> {
+        let saved = self.providers.get(&provider)?;
+        if api_key.is_some_and(|key| fingerprint(key) != saved.key_fingerprint) {
+            return None;
+        }
+        let models = saved
+            .models
+            .iter()
+            .map(|model_id| ModelInfo::describe(provider, model_id))
+            .collect();
+        Some((models, saved.fetched_at))
+    }
+}
+
+/// Location of the saved model lists
+pub fn get_catalog_path() -> PathBuf {
+    app_data_dir().join(CATALOG_FILE_NAME)
+}
+
+/// Caches the models listed for `api_key`, saves them to disk and returns
+/// them annotated
+pub fn store_models(provider: ProviderId, api_key: &str, model_ids: &[String]) -> Vec<ModelInfo> {
+    let path = get_catalog_path();
+    let mut saved = SavedCatalogs::load(&path);
+    saved.providers.insert(
+        provider,
+        SavedCatalog {
+            key_fingerprint: fingerprint(api_key),
+            fetched_at: Utc::now(),
+            models: model_ids.to_vec(),
+        },
+    );
+    if let Err(error) = saved.save(&path) {
+        warn!("failed to save model catalog: {}", error);
+    }
+
+    match CATALOGS.lock() {
+        Ok(mut cache) => cache.store(provider, api_key, model_ids, Instant::now()),
+        Err(_) => model_ids
+            .iter()
+            .map(|model_id| ModelInfo::describe(provider, model_id))
+            .collect(),
+    }
+}
+
+/// The list of `provider` saved on disk for `api_key` (or any key when
+/// `None`), whatever its age, with the time it was fetched
+pub fn saved_models(
+    provider: ProviderId,
+    api_key: Option<&str>,
+) -> Option<(Vec<ModelInfo>, DateTime<Utc>)> {
+    SavedCatalogs::load(&get_catalog_path()).get(provider, api_key)
+}
+
+/// Cached models of `provider` listed for `api_key`, or for whichever key
+/// was validated last when `api_key` is `None`
+pub fn cached_models(provider: ProviderId, api_key: Option<&str>) -> Option<Vec<ModelInfo>> {
+    let Ok(cache) = CATALOGS.lock() else {
+        return None;
+    };
+    cache.get(provider, api_key, Instant::now())
+}
+
+/// Drops the cached and saved list of `provider`, e.g. after its key was
+/// rejected
+pub fn invalidate(provider: ProviderId) {
+    let path = get_catalog_path();
+    let mut saved = SavedCatalogs::load(&path);
+    if saved.providers.remove(&provider).is_some() {
+        if let Err(error) = saved.save(&path) {
+            warn!("failed to save model catalog: {}", error);
+        }
+    }
+
+    let Ok(mut cache) = CATALOGS.lock() else {
+        return;
+    };
+    cache.invalidate(provider);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_are_annotated_from_the_tables() {
+        let flash = ModelInfo::describe(ProviderId::Gemini, "models/gemini-2.5-flash");
+        assert_eq!(flash.context_window, Some(1_048_576));
+        assert_eq!(flash.pricing_tier, PricingTier::Budget);
+        assert!(flash.recommended_for_translation);
+
+        let opus = ModelInfo::describe(ProviderId::Claude, "claude-3-opus-20240229");
+        assert_eq!(opus.pricing_tier, PricingTier::Premium);
+        assert!(!opus.recommended_for_translation);
+
+        let unknown = ModelInfo::describe(ProviderId::Grok, "grok-beta");
+        assert_eq!(unknown.context_window, None);
+        assert_eq!(unknown.pricing_tier, PricingTier::Unknown);
+        assert_eq!(
+            context_window_for(ProviderId::Gpt, "gpt-3.5-turbo-0125"),
+            Some(16_385)
+        );
+    }
+
+    #[test]
+    fn cached_lists_expire_and_are_bound_to_the_key() {
+        let mut cache = CatalogCache::default();
+        let now = Instant::now();
+        let models = vec!["gpt-4o-mini".to_string(), "gpt-4o".to_string()];
+        cache.store(ProviderId::Gpt, "sk-first", &models, now);
+
+        let hit = cache.get(ProviderId::Gpt, Some("sk-first"), now).unwrap();
+        assert_eq!(hit.len(), 2);
+        assert!(cache.get(ProviderId::Gpt, Some("sk-second"), now).is_none());
+        assert!(cache.get(ProviderId::Gpt, None, now).is_some());
+        assert!(cache.get(ProviderId::Claude, None, now).is_none());
+        assert!(cache
+            .get(ProviderId::Gpt, Some("sk-first"), now + CATALOG_TTL)
+            .is_none());
+
+        cache.invalidate(ProviderId::Gpt);
+        assert!(cache.get(ProviderId::Gpt, None, now).is_none());
+    }
+
+    #[test]
+    fn saved_lists_survive_a_restart_and_are_bound_to_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(CATALOG_FILE_NAME);
+        assert!(SavedCatalogs::load(&path).providers.is_empty());
+
+        let fetched_at = Utc::now();
+        let mut saved = SavedCatalogs::default();
+        saved.providers.insert(
+            ProviderId::Gemini,
+            SavedCatalog {
+                key_fingerprint: fingerprint("AIza-first "),
+                fetched_at,
+                models: vec!["gemini-2.5-flash".to_string()],
+            },
+        );
+        saved.save(&path).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("AIza"));
+
+        let loaded = SavedCatalogs::load(&path);
+        let (models, at) = loaded.get(ProviderId::Gemini, Some("AIza-first")).unwrap();
+        assert_eq!(models[0].context_window, Some(1_048_576));
+        assert_eq!(at, fetched_at);
+        assert!(loaded
+            .get(ProviderId::Gemini, Some("AIza-second"))
+            .is_none());
+        assert!(loaded.get(ProviderId::Gemini, None).is_some());
+        assert!(loaded.get(ProviderId::Gpt, None).is_none());
+
+        fs::write(&path, "[]").unwrap();
+        assert!(SavedCatalogs::load(&path).providers.is_empty());
+    }
+
+    #[test]
+    fn fallback_prefers_configured_then_listed_models() {
+        let no_config = HashMap::new();
+        assert_eq!(
+            fallback_model(
+                ProviderId::Gemini,
+                "gemini-1.5-flash-002",
+                &no_config,
+                None,
+                &[]
+            ),
+            Some("gemini-2.0-flash".to_string())
+        );
+
+        let mut configured = HashMap::new();
+        configured.insert(
+            "gemini-1.5-flash-002".to_string(),
+            "gemini-2.5-flash".to_string(),
+        );
+        let catalog: Vec<ModelInfo> = ["gemini-2.5-flash", "gemini-2.5-flash-lite"]
+            .iter()
+            .map(|model| ModelInfo::describe(ProviderId::Gemini, model))
+            .collect();
+        let pick = |configured: &HashMap<String, String>, tried: &[String]| {
+            fallback_model(
+                ProviderId::Gemini,
+                "gemini-1.5-flash-002",
+                configured,
+                Some(&catalog),
+                tried,
+            )
+        };
+        assert_eq!(pick(&configured, &[]), Some("gemini-2.5-flash".to_string()));
+        // Neither candidate is usable: a recommended listed model is picked
+        assert_eq!(
+            pick(&configured, &["gemini-2.5-flash".to_string()]),
+            Some("gemini-2.5-flash-lite".to_string())
+        );
+
+        configured.insert("gemini-1.5-flash-002".to_string(), String::new());
+        assert_eq!(pick(&configured, &[]), None);
+        assert_eq!(
+            fallback_model(ProviderId::Grok, "grok-4", &no_config, None, &[]),
+            None
+        );
+    }
+}
@@ -0,0 +1,227 @@
+/// Provider-wide circuit breaker.
+///
+/// Per-segment backoff does not stop the other segments and jobs from
+/// hitting a provider that is rejecting everything. After
+/// [`FAILURE_THRESHOLD`] consecutive rate limit or server errors from a
+/// provider, every new request to it waits out a cooldown. Requests resume on
+/// their own afterwards as probes; a failed probe opens the breaker again with
+/// a longer cooldown, one success closes it. Outcomes of requests sent before
+/// the breaker last opened say nothing about the provider since, so they are
+/// ignored.
+use log::warn;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{ProviderId, TranslationError};
+
+/// Consecutive 429/5xx responses that open the breaker
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// Pause after the breaker first opens; doubled on every reopening
+pub const BASE_COOLDOWN: Duration = Duration::from_secs(15);
+
+pub const MAX_COOLDOWN: Duration = Duration::from_secs(120);
+
+static BREAKERS: Lazy<Mutex<HashMap<ProviderId, CircuitBreaker>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    consecutive_failures: u32,
+    /// Times the breaker opened since the last success; while non-zero and
+    /// the cooldown is over, requests are half-open probes
+    trips: u32,
+    /// When the breaker last opened
+    opened_at: Option<Instant>,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Closes the breaker, unless the request was sent before it opened
+    pub fn record_success(&mut self, started: Instant) {
+        if self.is_stale(started) {
+            return;
+        }
+        *self = Self::default();
+    }
+
+    /// Counts a 429/5xx response of a request sent at `started`. Returns the
+    /// cooldown when this failure opens the breaker; `hint` is the server's
+    /// retry delay, which the cooldown never undercuts.
+    pub fn record_failure(
+        &mut self,
+        hint: Option<Duration>,
+        started: Instant,
+        now: Instant,
+    ) -> Option<Duration> {
+        if self.remaining(now).is_some() || self.is_stale(started) {
+            return None;
+        }
+        // A failed half-open probe reopens the breaker at once
+        if self.trips == 0 {
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+            if self.consecutive_failures < FAILURE_THRESHOLD {
+                return None;
+            }
+        }
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(1 << self.trips.min(8))
+            .min(MAX_COOLDOWN)
+            .max(hint.unwrap_or_default());
+        self.opened_at = Some(now);
+        self.open_until = Some(now + cooldown);
+        self.trips = self.trips.saturating_add(1);
+        Some(cooldown)
+    }
+
+    /// Whether a request sent at `started` was already in flight when the
+    /// breaker last opened
+    fn is_stale(&self, started: Instant) -> bool {
+        self.opened_at.is_some_and(|opened_at| started < opened_at)
+    }
+
+    /// Time left before requests may be sent again
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.open_until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+/// Waits while the breaker of `provider` is open
+pub async fn wait_until_closed(provider: ProviderId) {
+    loop {
+        let remaining = {
+            let Ok(breakers) = BREAKERS.lock() else {
+                return;
+            };
+            breakers
+                .get(&provider)
+                .and_then(|breaker| breaker.remaining(Instant::now()))
+        };
+        match remaining {
+            Some(remaining) => tokio::time::sleep(remaining).await,
+            None => return,
+        }
+    }
+}
+
+/// Updates the breaker of `provider` with the outcome of a request sent at
+/// `started`. Only rate limits and server errors count as failures; other
+/// errors leave the breaker alone.
+pub fn record_outcome<T>(
+    provider: ProviderId,
+    started: Instant,
+    result: &Result<T, TranslationError>,
+) {
+    let Ok(mut breakers) = BREAKERS.lock() else {
+        return;
+    };
+    let breaker = breakers.entry(provider).or_default();
+    match result {
+        Ok(_) => breaker.record_success(started),
+        Err(
+            error @ (TranslationError::RateLimited { .. }
+            | TranslationError::ServerTransient { .. }),
+        ) => {
+            let hint = error.retry_hint().map(|hint| hint.clamped_delay());
+            if let Some(cooldown) = breaker.record_failure(hint, started, Instant::now()) {
+                warn!(
+                    "{} returned {} consecutive rate limit/server errors; pausing requests for {}s",
+                    provider,
+                    FAILURE_THRESHOLD,
+                    cooldown.as_secs()
+                );
+            }
+        }
+        Err(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaker_opens_after_threshold_and_reopens_on_failed_probe() {
+        let mut breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        for _ in 1..FAILURE_THRESHOLD {
+            assert_eq!(breaker.record_failure(None, now, now), None);
+        }
+        assert_eq!(breaker.record_failure(None, now, now), Some(BASE_COOLDOWN));
+        assert_eq!(breaker.remaining(now), Some(BASE_COOLDOWN));
+        assert_eq!(breaker.remaining(now + BASE_COOLDOWN), None);
+
+        // The first request after the cooldown fails again: longer pause
+        let later = now + BASE_COOLDOWN;
+        assert_eq!(
+            breaker.record_failure(None, later, later),
+            Some(BASE_COOLDOWN * 2)
+        );
+
+        let probe = later + BASE_COOLDOWN * 2;
+        breaker.record_success(probe);
+        assert_eq!(breaker.remaining(probe), None);
+        assert_eq!(breaker.record_failure(None, probe, probe), None);
+    }
+
+    #[test]
+    fn failures_of_requests_in_flight_do_not_reopen_the_breaker() {
+        let mut breaker = CircuitBreaker::default();
+        let sent = Instant::now();
+        let opened = sent + Duration::from_millis(200);
+        for _ in 1..FAILURE_THRESHOLD {
+            breaker.record_failure(None, sent, opened);
+        }
+        assert_eq!(
+            breaker.record_failure(None, sent, opened),
+            Some(BASE_COOLDOWN)
+        );
+
+        // Twenty concurrent requests sent alongside the ones that opened the
+        // breaker fail while it is open and after the cooldown
+        for step in 0..20 {
+            let now = opened + Duration::from_secs(step);
+            assert_eq!(breaker.record_failure(None, sent, now), None);
+        }
+        let reopened = opened + BASE_COOLDOWN;
+        assert_eq!(breaker.record_failure(None, sent, reopened), None);
+        assert_eq!(breaker.remaining(reopened), None);
+        // nor does a late success of one of them close it
+        breaker.record_success(sent);
+        assert_eq!(breaker.trips, 1);
+
+        // Probes released together after the cooldown escalate it once
+        let probes = reopened;
+        let failed_at = probes + Duration::from_millis(300);
+        assert_eq!(
+            breaker.record_failure(None, probes, failed_at),
+            Some(BASE_COOLDOWN * 2)
+        );
+        for _ in 0..10 {
+            assert_eq!(breaker.record_failure(None, probes, failed_at), None);
+        }
+        let after = failed_at + BASE_COOLDOWN * 2;
+        assert_eq!(breaker.record_failure(None, probes, after), None);
+        assert_eq!(breaker.remaining(failed_at), Some(BASE_COOLDOWN * 2));
+    }
+
+    #[test]
+    fn cooldown_respects_server_hint_and_cap() {
+        let mut breaker = CircuitBreaker::default();
+        let mut now = Instant::now();
+        for _ in 1..FAILURE_THRESHOLD {
+            breaker.record_failure(None, now, now);
+        }
+        let hint = Duration::from_secs(40);
+        assert_eq!(breaker.record_failure(Some(hint), now, now), Some(hint));
+        for _ in 0..10 {
+            now += MAX_COOLDOWN;
+            breaker.record_failure(None, now, now);
+        }
+        assert_eq!(breaker.remaining(now), Some(MAX_COOLDOWN));
+    }
+}
@@ -0,0 +1,183 @@
+/// Per-provider request counters.
+///
+/// Every translation request records its latency, outcome and token usage
+/// here, and the job loop records each retry, so users can see why jobs are
+/// slow: a provider answering with 429s, high latency or many retries.
+/// Counters live in memory and start over when the app restarts.
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{ProviderId, TranslationError, TranslationOutput};
+
+static METRICS: Lazy<Mutex<HashMap<ProviderId, ProviderCounters>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Default)]
+struct ProviderCounters {
+    requests: u64,
+    failures: u64,
+    rate_limited: u64,
+    retries: u64,
+    total_latency: Duration,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+impl ProviderCounters {
+    fn record_request(
+        &mut self,
+        latency: Duration,
+        result: &Result<TranslationOutput, TranslationError>,
+    ) {
+        self.requests = self.requests.saturating_add(1);
+        self.total_latency = self.total_latency.saturating_add(latency);
+        match result {
+            Ok(output) => {
+                if let Some(usage) = output.usage {
+                    self.prompt_tokens = self.prompt_tokens.saturating_add(usage.prompt_tokens);
+                    self.completion_tokens = self
+                        .completion_tokens
+                        .saturating_add(usage.completion_tokens);
+                }
+            }
+            Err(error) => {
+                self.failures = self.failures.saturating_add(1);
+                if matches!(error, TranslationError::RateLimited { .. }) {
+                    self.rate_limited = self.rate_limited.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self, provider: ProviderId) -> ProviderUsageMetrics {
+        let average_latency_ms =
+            (self.requests > 0).then(|| self.total_latency.as_millis() as u64 / self.requests);
+        ProviderUsageMetrics {
+            provider,
+            requests: self.requests,
+            failures: self.failures,
+            rate_limited: self.rate_limited,
+            retries: self.retries,
+            average_latency_ms,
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+        }
+    }
+}
+
+/// Counters of one provider since the app started
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUsageMetrics {
+    pub provider: ProviderId,
+    /// 보낸 번역 요청 수 (실패 포함)
+    pub requests: u64,
+    /// 오류로 끝난 요청 수
+    pub failures: u64,
+    /// 429(요청 한도 초과)로 끝난 요청 수
+    pub rate_limited: u64,
+    /// 작업이 같은 세그먼트를 다시 요청한 횟수
+    pub retries: u64,
+    /// 요청 하나의 평균 응답 시간; 요청이 없으면 없음
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_latency_ms: Option<u64>,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+/// Records a request to `provider` that took `latency`
+pub fn record_request(
+    provider: ProviderId,
+    latency: Duration,
+    result: &Result<TranslationOutput, TranslationError>,
+) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        metrics
+            .entry(provider)
+            .or_default()
+            .record_request(latency, result);
+    }
+}
+
+/// Records that a segment is sent to `provider` again
+pub fn record_retry(provider: ProviderId) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        let counters = metrics.entry(provider).or_default();
+        counters.retries = counters.retries.saturating_add(1);
+    }
+}
+
+/// Counters of every provider used since the app started, by provider name
+pub fn provider_usage_metrics() -> Vec<ProviderUsageMetrics> {
+    let Ok(metrics) = METRICS.lock() else {
+        return Vec::new();
+    };
+    let mut snapshot: Vec<ProviderUsageMetrics> = metrics
+        .iter()
+        .map(|(provider, counters)| counters.snapshot(*provider))
+        .collect();
+    snapshot.sort_by_key(|metrics| metrics.provider.label());
+    snapshot
+}
+
+/// Tauri command returning the request counters of each provider
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_provider_usage_metrics() -> Vec<ProviderUsageMetrics> {
+    provider_usage_metrics()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::TokenUsage;
+
+    #[test]
+    fn counts_requests_failures_and_tokens() {
+        let mut counters = ProviderCounters::default();
+        assert_eq!(
+            counters.snapshot(ProviderId::Claude).average_latency_ms,
+            None
+        );
+
+        counters.record_request(
+            Duration::from_millis(300),
+            &Ok(TranslationOutput::new(
+                "안녕".into(),
+                Some(TokenUsage::new(120, 30)),
+            )),
+        );
+        counters.record_request(
+            Duration::from_millis(100),
+            &Err(TranslationError::RateLimited {
+                provider: ProviderId::Claude,
+                message: "slow down".into(),
+                retry_hint: None,
+            }),
+        );
+        counters.record_request(
+            Duration::from_millis(200),
+            &Err(TranslationError::NetworkTransient {
+                provider: ProviderId::Claude,
+                message: "reset".into(),
+            }),
+        );
+        counters.retries = 2;
+
+        assert_eq!(
+            counters.snapshot(ProviderId::Claude),
+            ProviderUsageMetrics {
+                provider: ProviderId::Claude,
+                requests: 3,
+                failures: 2,
+                rate_limited: 1,
+                retries: 2,
+                average_latency_ms: Some(200),
+                prompt_tokens: 120,
+                completion_tokens: 30,
+            }
+        );
+    }
+}
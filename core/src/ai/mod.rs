@@ -1,5 +1,12 @@
+pub mod catalog;
+pub mod circuit;
 pub mod hints;
+pub mod metrics;
+pub mod pricing;
+pub mod prompt;
+pub mod rate_limit;
 pub mod retry;
+pub mod structured;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -7,13 +14,24 @@ use reqwest::{header::HeaderMap, Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Instant;
 use thiserror::Error;
 
-use crate::protector::{ProtectedFragment, ProtectorError};
+use crate::document::{
+    join_document, needs_document_mode, overlap_context, split_document_protected,
+    validate_document_structure, DOCUMENT_CHUNK_MAX_CHARS, DOCUMENT_OVERLAP_CHARS,
+};
+use crate::llm_guards::sanitize_response;
+use crate::protector::{ProtectedFragment, Protector, ProtectorError};
 
+use self::catalog::{capabilities_for, context_window_for};
 use self::hints::{
     parse_gemini_error_hints, parse_retry_after_header, GeminiErrorHints, RetryHint,
 };
+use self::prompt::{
+    default_system_prompt, default_user_prompt, render, PromptTemplate, PromptVars,
+};
+use self::rate_limit::{estimate_request_tokens, limiter_for, RateLimitConfig};
 
 static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(\{\d+\}|%\d*\$?s|%\d*\$?d|\$\{[^}]+\}|\\n|\\r|\\t)")
@@ -58,8 +76,22 @@ pub enum TranslationError {
         status: Option<StatusCode>,
         retry_hint: Option<RetryHint>,
     },
+    #[error(
+        "{provider} request for {model_id} needs ~{estimated_tokens} tokens, \
+         over its {context_window}-token context window"
+    )]
+    ContextOverflow {
+        provider: ProviderId,
+        model_id: String,
+        estimated_tokens: u64,
+        context_window: u32,
+    },
+    #[error("{provider} request not sent: offline mode is on")]
+    OfflineMode { provider: ProviderId },
     #[error("placeholder mismatch: {0:?}")]
     PlaceholderMismatch(Vec<String>),
+    #[error("document structure mismatch: {0}")]
+    StructureMismatch(String),
     #[error("{provider} io error: {message}")]
     IoError {
         provider: ProviderId,
@@ -85,6 +117,14 @@ impl ProviderId {
             ProviderId::Grok => "Grok",
         }
     }
+
+    /// Highest temperature the provider's API accepts
+    pub fn max_temperature(&self) -> f32 {
+        match self {
+            ProviderId::Claude => 1.0,
+            ProviderId::Gemini | ProviderId::Gpt | ProviderId::Grok => 2.0,
+        }
+    }
 }
 
 impl fmt::Display for ProviderId {
@@ -107,6 +147,215 @@ impl TryFrom<&str> for ProviderId {
     }
 }
 
+/// Token usage reported by a provider for a single request.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens.saturating_add(self.completion_tokens)
+    }
+}
+
+/// Translated text along with the usage the provider reported for the request.
+#[derive(Debug, Clone)]
+pub struct TranslationOutput {
+    pub text: String,
+    pub usage: Option<TokenUsage>,
+}
+
+impl TranslationOutput {
+    fn new(text: String, usage: Option<TokenUsage>) -> Self {
+        Self { text, usage }
+    }
+}
+
+/// Sampling temperature used when the caller does not override it.
+pub const DEFAULT_TEMPERATURE: f32 = 0.2;
+
+/// Output token limit sent to providers that require one (Claude).
+pub const DEFAULT_MAX_OUTPUT_TOKENS: u32 = 1024;
+
+/// Translated characters one output token is assumed to cover. Kept low
+/// because Korean, Chinese and Japanese output takes about a token for every
+/// one or two characters.
+const OUTPUT_CHARS_PER_TOKEN: usize = 2;
+
+const PING_TEXT: &str = "Hello";
+
+/// Reasoning effort hint for models that support it (OpenAI o-series, Grok mini).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+        }
+    }
+}
+
+/// Generation parameters forwarded to the provider request builders.
+///
+/// Every field is optional; unset fields fall back to the provider defaults
+/// this crate has always used (temperature 0.2, 1024 output tokens for Claude).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ModelParams {
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub max_output_tokens: Option<u32>,
+    /// Used by OpenAI reasoning models and Grok mini models.
+    #[serde(default)]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Thinking token budget for Claude extended thinking and Gemini 2.5.
+    #[serde(default)]
+    pub thinking_budget_tokens: Option<u32>,
+    /// Ask for `{"translation": "..."}` replies where the provider supports
+    /// it. On unless set to false.
+    #[serde(default)]
+    pub structured_output: Option<bool>,
+}
+
+impl ModelParams {
+    /// Drops or clamps values outside the ranges `provider` accepts.
+    pub fn sanitized(&self, provider: ProviderId) -> Self {
+        Self {
+            temperature: self
+                .temperature
+                .filter(|value| value.is_finite())
+                .map(|value| value.clamp(0.0, provider.max_temperature())),
+            top_p: self
+                .top_p
+                .filter(|value| value.is_finite() && *value > 0.0)
+                .map(|value| value.min(1.0)),
+            max_output_tokens: self.max_output_tokens.filter(|value| *value > 0),
+            reasoning_effort: self.reasoning_effort,
+            thinking_budget_tokens: self.thinking_budget_tokens,
+            structured_output: self.structured_output,
+        }
+    }
+}
+
+/// Provider, credentials, languages, and generation parameters shared by every
+/// request of a translation job.
+#[derive(Debug, Clone)]
+pub struct TranslationSettings {
+    pub provider: ProviderId,
+    pub api_key: String,
+    pub model_id: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub params: ModelParams,
+    /// Overrides of the built-in prompts
+    pub prompt: PromptTemplate,
+    pub prompt_vars: PromptVars,
+    /// Limits shared with every other request to the same provider and model
+    pub rate_limit: RateLimitConfig,
+    /// API root used instead of the provider's own, e.g. a proxy that speaks
+    /// the provider's API
+    pub base_url: Option<String>,
+}
+
+impl TranslationSettings {
+    /// `path` under `base_url`, or under the provider's `default_root`
+    fn endpoint(&self, default_root: &str, path: &str) -> String {
+        let root = self
+            .base_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(default_root);
+        format!("{}/{}", root.trim_end_matches('/'), path)
+    }
+
+    /// System and user prompts for translating `input`, with `context` as
+    /// read-only surrounding text
+    pub fn render_prompts(&self, input: &str, context: Option<&str>) -> (Option<String>, String) {
+        let context = context.map(str::trim).filter(|value| !value.is_empty());
+        let lookup = |name: &str| -> Option<&str> {
+            match name {
+                "source_lang" => Some(self.source_lang.as_str()),
+                "target_lang" => Some(self.target_lang.as_str()),
+                "text" => Some(input),
+                "context" => context,
+                "glossary" => self.prompt_vars.glossary.as_deref(),
+                "tone" => self.prompt_vars.tone.as_deref(),
+                "game" => self.prompt_vars.game.as_deref(),
+                "game_context" => self.prompt_vars.game_context.as_deref(),
+                _ => None,
+            }
+        };
+        let system = self
+            .prompt
+            .system
+            .as_deref()
+            .or_else(|| default_system_prompt(self.provider))
+            .map(|template| render(template, lookup))
+            .filter(|system| !system.trim().is_empty());
+        let user = self
+            .prompt
+            .user
+            .as_deref()
+            .unwrap_or_else(|| default_user_prompt(self.provider));
+        (system, render(user, lookup))
+    }
+
+    /// Longest text whose translation fits the model's output limit, in
+    /// characters; `None` when the limit is unknown
+    pub fn output_limit_chars(&self) -> Option<usize> {
+        self.params
+            .max_output_tokens
+            .filter(|tokens| *tokens > 0)
+            .or_else(|| (self.provider == ProviderId::Claude).then_some(DEFAULT_MAX_OUTPUT_TOKENS))
+            .or_else(|| {
+                capabilities_for(self.provider, &self.model_id)
+                    .map(|capabilities| capabilities.max_output_tokens)
+            })
+            .map(|tokens| tokens as usize * OUTPUT_CHARS_PER_TOKEN)
+    }
+
+    /// Size of the chunks [`translate_document`] sends, in characters
+    pub fn document_chunk_chars(&self) -> usize {
+        self.output_limit_chars()
+            .map_or(DOCUMENT_CHUNK_MAX_CHARS, |limit| {
+                limit.min(DOCUMENT_CHUNK_MAX_CHARS)
+            })
+    }
+
+    /// Whether `text` is too long for one request and goes through
+    /// [`translate_document`] instead
+    pub fn needs_document_mode(&self, text: &str) -> bool {
+        needs_document_mode(text)
+            || self
+                .output_limit_chars()
+                .is_some_and(|limit| text.chars().count() > limit)
+    }
+}
+
 impl TranslationError {
     pub fn retry_hint(&self) -> Option<&RetryHint> {
         match self {
@@ -124,6 +373,17 @@ impl TranslationError {
             _ => None,
         }
     }
+
+    /// Errors that will not go away by retrying: the key or model is unusable.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            TranslationError::Unauthorized { .. }
+                | TranslationError::Forbidden { .. }
+                | TranslationError::ModelNotFound { .. }
+                | TranslationError::OfflineMode { .. }
+        )
+    }
 }
 
 fn map_translation_http_error(
@@ -208,109 +468,165 @@ fn map_translation_http_error(
 
 pub async fn translate_text(
     client: &Client,
-    provider: ProviderId,
-    api_key: &str,
-    model_id: &str,
+    settings: &TranslationSettings,
+    fragment: &ProtectedFragment,
+) -> Result<TranslationOutput, TranslationError> {
+    translate_text_with_context(client, settings, fragment, None).await
+}
+
+/// Sends a one-word translation request to check that the key and model are
+/// usable before a job starts.
+pub async fn ping_provider(
+    client: &Client,
+    settings: &TranslationSettings,
+) -> Result<(), TranslationError> {
+    let fragment = Protector::protect(PING_TEXT);
+    translate_text(client, settings, &fragment)
+        .await
+        .map(|_| ())
+}
+
+/// Translates `fragment`, passing `context` to the model as read-only
+/// surrounding text that must not appear in the output.
+pub async fn translate_text_with_context(
+    client: &Client,
+    settings: &TranslationSettings,
     fragment: &ProtectedFragment,
-    source_lang: &str,
-    target_lang: &str,
-) -> Result<String, TranslationError> {
+    context: Option<&str>,
+) -> Result<TranslationOutput, TranslationError> {
     let normalized_original = fragment.original().trim();
     if normalized_original.is_empty() {
-        return Ok(String::new());
+        return Ok(TranslationOutput::new(String::new(), None));
     }
 
     let masked_input = fragment.masked_text();
     let normalized_masked = masked_input.trim();
     if normalized_masked.is_empty() {
-        return Ok(fragment.original().to_string());
+        return Ok(TranslationOutput::new(
+            fragment.original().to_string(),
+            None,
+        ));
     }
 
-    let translated_masked = match provider {
-        ProviderId::Gemini => {
-            translate_with_gemini(
-                client,
-                api_key,
-                model_id,
-                normalized_masked,
-                source_lang,
-                target_lang,
-            )
-            .await?
+    let estimated_tokens = estimate_request_tokens(normalized_masked, context);
+    if let Some(context_window) = context_window_for(settings.provider, &settings.model_id) {
+        if estimated_tokens > u64::from(context_window) {
+            return Err(TranslationError::ContextOverflow {
+                provider: settings.provider,
+                model_id: settings.model_id.clone(),
+                estimated_tokens,
+                context_window,
+            });
         }
-        ProviderId::Gpt => {
-            translate_with_gpt(
-                client,
-                api_key,
-                model_id,
-                normalized_masked,
-                source_lang,
-                target_lang,
-            )
-            .await?
+    }
+
+    if crate::access_mode::is_offline() {
+        return Err(TranslationError::OfflineMode {
+            provider: settings.provider,
+        });
+    }
+
+    circuit::wait_until_closed(settings.provider).await;
+    let limiter = limiter_for(settings.provider, &settings.model_id, &settings.rate_limit);
+    if let Some(limiter) = &limiter {
+        limiter.acquire(estimated_tokens).await;
+    }
+
+    let started = Instant::now();
+    let result = match settings.provider {
+        ProviderId::Gemini => {
+            translate_with_gemini(client, settings, normalized_masked, context).await
         }
+        ProviderId::Gpt => translate_with_gpt(client, settings, normalized_masked, context).await,
         ProviderId::Claude => {
-            translate_with_claude(
-                client,
-                api_key,
-                model_id,
-                normalized_masked,
-                source_lang,
-                target_lang,
-            )
-            .await?
-        }
-        ProviderId::Grok => {
-            translate_with_grok(
-                client,
-                api_key,
-                model_id,
-                normalized_masked,
-                source_lang,
-                target_lang,
-            )
-            .await?
+            translate_with_claude(client, settings, normalized_masked, context).await
         }
+        ProviderId::Grok => translate_with_grok(client, settings, normalized_masked, context).await,
     };
+    circuit::record_outcome(settings.provider, started, &result);
+    metrics::record_request(settings.provider, started.elapsed(), &result);
+    let response = result?;
 
-    let restored = fragment
-        .restore(&translated_masked)
-        .map_err(|error| match error {
-            ProtectorError::MissingTokens(tokens) | ProtectorError::UnexpectedTokens(tokens) => {
-                TranslationError::PlaceholderMismatch(tokens)
-            }
-        })?;
+    if let (Some(limiter), Some(usage)) = (&limiter, response.usage) {
+        limiter.record_usage(estimated_tokens, usage.total_tokens());
+    }
+
+    let text = sanitize_response(&response.text, normalized_masked);
+    let restored = fragment.restore(&text).map_err(|error| match error {
+        ProtectorError::MissingTokens(tokens) | ProtectorError::UnexpectedTokens(tokens) => {
+            TranslationError::PlaceholderMismatch(tokens)
+        }
+    })?;
 
     ensure_placeholder_integrity(fragment.original(), &restored)?;
-    Ok(restored.trim().to_string())
+    Ok(TranslationOutput::new(
+        restored.trim().to_string(),
+        response.usage,
+    ))
+}
+
+/// Translates a very long segment in document mode: the text is split into
+/// paragraph-aligned chunks that fit one request, each chunk is translated
+/// with the tail of the previous chunk as context, and the result is rejoined
+/// and checked for the same line-break structure as the source.
+///
+/// `fragment` is the whole segment as masked for a single request; chunks
+/// are never cut inside one of its tokens and are masked again with
+/// `protect`, so they keep the segment's token classes and markers.
+pub async fn translate_document(
+    client: &Client,
+    settings: &TranslationSettings,
+    fragment: &ProtectedFragment,
+    protect: impl Fn(&str) -> ProtectedFragment,
+) -> Result<TranslationOutput, TranslationError> {
+    let text = fragment.original();
+    let spans: Vec<(usize, usize)> = fragment
+        .token_map()
+        .tokens
+        .iter()
+        .map(|token| token.span)
+        .collect();
+    let chunks = split_document_protected(text, settings.document_chunk_chars(), &spans);
+    let mut translated = Vec::with_capacity(chunks.len());
+    let mut usage: Option<TokenUsage> = None;
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        let fragment = protect(&chunk.text);
+        let context = overlap_context(&chunks, index, DOCUMENT_OVERLAP_CHARS);
+        let output =
+            translate_text_with_context(client, settings, &fragment, context.as_deref()).await?;
+        if let Some(chunk_usage) = output.usage {
+            let total = usage.get_or_insert_with(TokenUsage::default);
+            total.prompt_tokens = total
+                .prompt_tokens
+                .saturating_add(chunk_usage.prompt_tokens);
+            total.completion_tokens = total
+                .completion_tokens
+                .saturating_add(chunk_usage.completion_tokens);
+        }
+        translated.push(output.text);
+    }
+
+    let joined = join_document(&chunks, &translated);
+    validate_document_structure(text, &joined)
+        .map_err(|error| TranslationError::StructureMismatch(error.to_string()))?;
+    Ok(TranslationOutput::new(joined, usage))
 }
 
 async fn translate_with_gemini(
     client: &Client,
-    api_key: &str,
-    model_id: &str,
+    settings: &TranslationSettings,
     input: &str,
-    source_lang: &str,
-    target_lang: &str,
-) -> Result<String, TranslationError> {
-    let prompt = format!(
-        r#"Translate the following text from {source_lang} to {target_lang}.
-
-CRITICAL RULES (MUST FOLLOW):
-1. Preserve ALL placeholders exactly: {{0}}, {{1}}, %1$s, %d, ${{variable}}, etc.
-2. DO NOT translate or modify:
-   - Numbers and numeric values (keep "100", "3.14" as-is)
-   - Mathematical expressions (keep "x^2", "10-20" as-is)  
-   - Units and measurements (keep "10kg", "5m", "20%" as-is)
-   - Code tokens: __TOKEN_0__, __PROTECT_1__, etc.
-   - File paths, URLs, variable names
-   - Formatting codes: \n, \r, \t, color codes like <color=#FF0000>
-3. Only translate natural language text
-4. Return ONLY the translated text, no explanations
-
-Text to translate:
-{input}"#
-    );
+    context: Option<&str>,
+) -> Result<TranslationOutput, TranslationError> {
+    let TranslationSettings {
+        api_key,
+        model_id,
+        params,
+        ..
+    } = settings;
+    let params = params.sanitized(ProviderId::Gemini);
+    let (system, mut prompt) = settings.render_prompts(input, context);
 
     let trimmed_model = model_id.trim();
     if trimmed_model.is_empty() {
@@ -319,26 +635,37 @@ Text to translate:
             message: "Gemini 모델이 지정되지 않았습니다.".into(),
         });
     }
+    let structured = structured::is_enabled(ProviderId::Gemini, trimmed_model, &params);
+    if structured {
+        prompt = structured::with_instruction(ProviderId::Gemini, prompt);
+    }
     let normalized_model = if trimmed_model.starts_with("models/") {
         trimmed_model.to_string()
     } else {
         format!("models/{trimmed_model}")
     };
 
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/{normalized_model}:generateContent?key={api_key}"
+    let url = settings.endpoint(
+        "https://generativelanguage.googleapis.com/v1beta",
+        &format!("{normalized_model}:generateContent?key={api_key}"),
     );
-    let response = client
-        .post(url)
-        .json(&serde_json::json!({
-            "contents": [{ "parts": [{ "text": prompt }] }]
-        }))
-        .send()
-        .await
-        .map_err(|err| TranslationError::NetworkTransient {
+    let mut body = serde_json::json!({
+        "contents": [{ "parts": [{ "text": prompt }] }]
+    });
+    if let Some(system) = system {
+        body["systemInstruction"] = serde_json::json!({ "parts": [{ "text": system }] });
+    }
+    apply_gemini_params(&mut body, &params);
+    if structured {
+        structured::apply(&mut body, ProviderId::Gemini);
+    }
+
+    let response = client.post(url).json(&body).send().await.map_err(|err| {
+        TranslationError::NetworkTransient {
             provider: ProviderId::Gemini,
             message: err.to_string(),
-        })?;
+        }
+    })?;
 
     let status = response.status();
     let headers = response.headers().clone();
@@ -368,6 +695,7 @@ Text to translate:
             message: err.to_string(),
             retry_hint: None,
         })?;
+    let usage = parsed.usage_metadata.map(TokenUsage::from);
     let text = parsed
         .candidates
         .and_then(|candidates| candidates.into_iter().next())
@@ -380,30 +708,29 @@ Text to translate:
             message: "Gemini 응답에서 결과를 찾지 못했습니다.".into(),
             retry_hint: None,
         })?;
+    let text = if structured {
+        structured::parse_translation(&text)
+    } else {
+        text
+    };
 
-    Ok(text)
+    Ok(TranslationOutput::new(text, usage))
 }
 
 async fn translate_with_gpt(
     client: &Client,
-    api_key: &str,
-    model_id: &str,
+    settings: &TranslationSettings,
     input: &str,
-    source_lang: &str,
-    target_lang: &str,
-) -> Result<String, TranslationError> {
-    let prompt = format!(
-        r#"Translate the following text from {source_lang} to {target_lang}.
-
-CRITICAL RULES:
-1. Preserve ALL placeholders: {{0}}, %1$s, ${{var}}, etc.
-2. DO NOT translate numbers, math expressions, units, code tokens
-3. Keep formatting codes (\n, \t, color tags) unchanged
-4. Return ONLY the translated text
-
-Text:
-{input}"#
-    );
+    context: Option<&str>,
+) -> Result<TranslationOutput, TranslationError> {
+    let TranslationSettings {
+        api_key,
+        model_id,
+        params,
+        ..
+    } = settings;
+    let params = params.sanitized(ProviderId::Gpt);
+    let (system, mut prompt) = settings.render_prompts(input, context);
 
     let trimmed_model = model_id.trim();
     if trimmed_model.is_empty() {
@@ -412,24 +739,24 @@ Text:
             message: "OpenAI 모델이 지정되지 않았습니다.".into(),
         });
     }
+    let structured = structured::is_enabled(ProviderId::Gpt, trimmed_model, &params);
+    if structured {
+        prompt = structured::with_instruction(ProviderId::Gpt, prompt);
+    }
+
+    let mut body = serde_json::json!({
+        "model": trimmed_model,
+        "messages": chat_messages(system, prompt)
+    });
+    apply_openai_params(&mut body, &params, trimmed_model);
+    if structured {
+        structured::apply(&mut body, ProviderId::Gpt);
+    }
 
     let response = client
-        .post("https://api.openai.com/v1/chat/completions")
+        .post(settings.endpoint("https://api.openai.com/v1", "chat/completions"))
         .bearer_auth(api_key)
-        .json(&serde_json::json!({
-            "model": trimmed_model,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": "You are a professional game localization translator. CRITICAL: Preserve ALL placeholders ({0}, %s, ${var}), numbers, math expressions, units, and formatting codes EXACTLY as they appear. Only translate natural language text. Never modify code tokens like __TOKEN_0__ or __PROTECT_1__."
-                },
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "temperature": 0.2
-        }))
+        .json(&body)
         .send()
         .await
         .map_err(|err| TranslationError::NetworkTransient {
@@ -465,6 +792,7 @@ Text:
             message: err.to_string(),
             retry_hint: None,
         })?;
+    let usage = parsed.usage.map(TokenUsage::from);
     let text = parsed
         .choices
         .into_iter()
@@ -475,30 +803,29 @@ Text:
             message: "GPT 응답에서 결과를 찾지 못했습니다.".into(),
             retry_hint: None,
         })?;
+    let text = if structured {
+        structured::parse_translation(&text)
+    } else {
+        text
+    };
 
-    Ok(text)
+    Ok(TranslationOutput::new(text, usage))
 }
 
 async fn translate_with_claude(
     client: &Client,
-    api_key: &str,
-    model_id: &str,
+    settings: &TranslationSettings,
     input: &str,
-    source_lang: &str,
-    target_lang: &str,
-) -> Result<String, TranslationError> {
-    let prompt = format!(
-        r#"Translate the following text from {source_lang} to {target_lang}.
-
-CRITICAL RULES:
-1. Preserve ALL placeholders: {{0}}, %1$s, ${{var}}, etc.
-2. DO NOT translate numbers, math expressions, units, code tokens
-3. Keep formatting codes (\n, \t, color tags) unchanged
-4. Return ONLY the translated text
-
-Text:
-{input}"#
-    );
+    context: Option<&str>,
+) -> Result<TranslationOutput, TranslationError> {
+    let TranslationSettings {
+        api_key,
+        model_id,
+        params,
+        ..
+    } = settings;
+    let params = params.sanitized(ProviderId::Claude);
+    let (system, mut prompt) = settings.render_prompts(input, context);
 
     let trimmed_model = model_id.trim();
     if trimmed_model.is_empty() {
@@ -507,23 +834,33 @@ Text:
             message: "Claude 모델이 지정되지 않았습니다.".into(),
         });
     }
+    let structured = structured::is_enabled(ProviderId::Claude, trimmed_model, &params);
+    if structured {
+        prompt = structured::with_instruction(ProviderId::Claude, prompt);
+    }
+
+    let mut body = serde_json::json!({
+        "model": trimmed_model,
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt
+            }
+        ]
+    });
+    if let Some(system) = system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    apply_claude_params(&mut body, &params);
+    if structured {
+        structured::apply(&mut body, ProviderId::Claude);
+    }
 
     let response = client
-        .post("https://api.anthropic.com/v1/messages")
+        .post(settings.endpoint("https://api.anthropic.com/v1", "messages"))
         .header("x-api-key", api_key)
         .header("anthropic-version", "2023-06-01")
-        .json(&serde_json::json!({
-            "model": trimmed_model,
-            "max_tokens": 1024,
-            "system": "You are a professional game localization translator. CRITICAL: Preserve ALL placeholders ({0}, %s, ${var}), numbers, math expressions, units, and formatting codes EXACTLY as they appear. Only translate natural language text. Never modify code tokens like __TOKEN_0__ or __PROTECT_1__.",
-            "messages": [
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "temperature": 0.2
-        }))
+        .json(&body)
         .send()
         .await
         .map_err(|err| TranslationError::NetworkTransient {
@@ -559,11 +896,22 @@ Text:
             message: err.to_string(),
             retry_hint: None,
         })?;
-    let text = parsed
-        .content
-        .unwrap_or_default()
-        .into_iter()
-        .find_map(|block| block.text)
+    let usage = parsed.usage.map(TokenUsage::from);
+    let blocks = parsed.content.unwrap_or_default();
+    let tool_text = blocks
+        .iter()
+        .filter_map(|block| block.input.as_ref())
+        .find_map(structured::translation_from_value);
+    let text = tool_text
+        .or_else(|| {
+            blocks.into_iter().find_map(|block| block.text).map(|text| {
+                if structured {
+                    structured::parse_translation(&text)
+                } else {
+                    text
+                }
+            })
+        })
         .ok_or_else(|| TranslationError::ServerTransient {
             provider: ProviderId::Claude,
             status: Some(status),
@@ -571,29 +919,23 @@ Text:
             retry_hint: None,
         })?;
 
-    Ok(text)
+    Ok(TranslationOutput::new(text, usage))
 }
 
 async fn translate_with_grok(
     client: &Client,
-    api_key: &str,
-    model_id: &str,
+    settings: &TranslationSettings,
     input: &str,
-    source_lang: &str,
-    target_lang: &str,
-) -> Result<String, TranslationError> {
-    let prompt = format!(
-        r#"Translate the following text from {source_lang} to {target_lang}.
-
-CRITICAL RULES:
-1. Preserve ALL placeholders: {{0}}, %1$s, ${{var}}, etc.
-2. DO NOT translate numbers, math expressions, units, code tokens
-3. Keep formatting codes (\n, \t, color tags) unchanged
-4. Return ONLY the translated text
-
-Text:
-{input}"#
-    );
+    context: Option<&str>,
+) -> Result<TranslationOutput, TranslationError> {
+    let TranslationSettings {
+        api_key,
+        model_id,
+        params,
+        ..
+    } = settings;
+    let params = params.sanitized(ProviderId::Grok);
+    let (system, mut prompt) = settings.render_prompts(input, context);
 
     let trimmed_model = model_id.trim();
     if trimmed_model.is_empty() {
@@ -602,24 +944,24 @@ Text:
             message: "Grok 모델이 지정되지 않았습니다.".into(),
         });
     }
+    let structured = structured::is_enabled(ProviderId::Grok, trimmed_model, &params);
+    if structured {
+        prompt = structured::with_instruction(ProviderId::Grok, prompt);
+    }
+
+    let mut body = serde_json::json!({
+        "model": trimmed_model,
+        "messages": chat_messages(system, prompt)
+    });
+    apply_grok_params(&mut body, &params, trimmed_model);
+    if structured {
+        structured::apply(&mut body, ProviderId::Grok);
+    }
 
     let response = client
-        .post("https://api.x.ai/v1/chat/completions")
+        .post(settings.endpoint("https://api.x.ai/v1", "chat/completions"))
         .bearer_auth(api_key)
-        .json(&serde_json::json!({
-            "model": trimmed_model,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": "You are a professional game localization translator. CRITICAL: Preserve ALL placeholders ({0}, %s, ${var}), numbers, math expressions, units, and formatting codes EXACTLY as they appear. Only translate natural language text. Never modify code tokens like __TOKEN_0__ or __PROTECT_1__."
-                },
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "temperature": 0.2
-        }))
+        .json(&body)
         .send()
         .await
         .map_err(|err| TranslationError::NetworkTransient {
@@ -655,6 +997,7 @@ Text:
             message: err.to_string(),
             retry_hint: None,
         })?;
+    let usage = parsed.usage.map(TokenUsage::from);
     let text = parsed
         .choices
         .into_iter()
@@ -665,13 +1008,142 @@ Text:
             message: "Grok 응답에서 결과를 찾지 못했습니다.".into(),
             retry_hint: None,
         })?;
+    let text = if structured {
+        structured::parse_translation(&text)
+    } else {
+        text
+    };
+
+    Ok(TranslationOutput::new(text, usage))
+}
+
+/// OpenAI-style chat messages: the optional system prompt, then the request
+fn chat_messages(system: Option<String>, prompt: String) -> serde_json::Value {
+    let mut messages = Vec::with_capacity(2);
+    if let Some(system) = system {
+        messages.push(serde_json::json!({ "role": "system", "content": system }));
+    }
+    messages.push(serde_json::json!({ "role": "user", "content": prompt }));
+    serde_json::Value::Array(messages)
+}
+
+fn insert_param<T: Serialize>(body: &mut serde_json::Value, key: &str, value: Option<T>) {
+    if let (Some(map), Some(value)) = (body.as_object_mut(), value) {
+        if let Ok(value) = serde_json::to_value(value) {
+            map.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// OpenAI reasoning models reject sampling parameters and use
+/// `reasoning_effort` instead.
+fn is_openai_reasoning_model(model_id: &str) -> bool {
+    let lowered = model_id.to_ascii_lowercase();
+    ["o1", "o3", "o4", "gpt-5"]
+        .iter()
+        .any(|prefix| lowered.starts_with(prefix))
+}
+
+fn apply_gemini_params(body: &mut serde_json::Value, params: &ModelParams) {
+    let mut config = serde_json::json!({});
+    insert_param(&mut config, "temperature", params.temperature);
+    insert_param(&mut config, "topP", params.top_p);
+    insert_param(&mut config, "maxOutputTokens", params.max_output_tokens);
+    if let Some(budget) = params.thinking_budget_tokens {
+        insert_param(
+            &mut config,
+            "thinkingConfig",
+            Some(serde_json::json!({ "thinkingBudget": budget })),
+        );
+    }
+    if config.as_object().is_some_and(|map| !map.is_empty()) {
+        insert_param(body, "generationConfig", Some(config));
+    }
+}
+
+fn apply_openai_params(body: &mut serde_json::Value, params: &ModelParams, model_id: &str) {
+    if is_openai_reasoning_model(model_id) {
+        insert_param(
+            body,
+            "reasoning_effort",
+            params.reasoning_effort.map(|effort| effort.as_str()),
+        );
+    } else {
+        insert_param(
+            body,
+            "temperature",
+            Some(params.temperature.unwrap_or(DEFAULT_TEMPERATURE)),
+        );
+        insert_param(body, "top_p", params.top_p);
+    }
+    insert_param(body, "max_completion_tokens", params.max_output_tokens);
+}
 
-    Ok(text)
+fn apply_claude_params(body: &mut serde_json::Value, params: &ModelParams) {
+    let max_tokens = params
+        .max_output_tokens
+        .unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS);
+    match params.thinking_budget_tokens {
+        Some(budget) => {
+            // Extended thinking requires max_tokens above the budget and
+            // does not allow custom sampling parameters.
+            let max_tokens = max_tokens.max(budget.saturating_add(DEFAULT_MAX_OUTPUT_TOKENS));
+            insert_param(body, "max_tokens", Some(max_tokens));
+            insert_param(
+                body,
+                "thinking",
+                Some(serde_json::json!({ "type": "enabled", "budget_tokens": budget })),
+            );
+        }
+        None => {
+            insert_param(body, "max_tokens", Some(max_tokens));
+            insert_param(
+                body,
+                "temperature",
+                Some(params.temperature.unwrap_or(DEFAULT_TEMPERATURE)),
+            );
+            insert_param(body, "top_p", params.top_p);
+        }
+    }
+}
+
+fn apply_grok_params(body: &mut serde_json::Value, params: &ModelParams, model_id: &str) {
+    insert_param(
+        body,
+        "temperature",
+        Some(params.temperature.unwrap_or(DEFAULT_TEMPERATURE)),
+    );
+    insert_param(body, "top_p", params.top_p);
+    insert_param(body, "max_tokens", params.max_output_tokens);
+    if model_id.to_ascii_lowercase().contains("-mini") {
+        insert_param(
+            body,
+            "reasoning_effort",
+            params.reasoning_effort.map(|effort| effort.as_str()),
+        );
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct GeminiResponse {
     candidates: Option<Vec<GeminiCandidate>>,
+    #[serde(default, rename = "usageMetadata")]
+    usage_metadata: Option<GeminiUsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+}
+
+impl From<GeminiUsageMetadata> for TokenUsage {
+    fn from(value: GeminiUsageMetadata) -> Self {
+        TokenUsage::new(value.prompt_token_count, value.candidates_token_count)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -692,6 +1164,22 @@ struct GeminiPart {
 #[derive(Debug, Deserialize)]
 struct OpenAiResponse {
     choices: Vec<OpenAiChoice>,
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
+
+impl From<OpenAiUsage> for TokenUsage {
+    fn from(value: OpenAiUsage) -> Self {
+        TokenUsage::new(value.prompt_tokens, value.completion_tokens)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -707,12 +1195,31 @@ struct OpenAiMessage {
 #[derive(Debug, Deserialize)]
 struct AnthropicResponse {
     content: Option<Vec<AnthropicContentBlock>>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+impl From<AnthropicUsage> for TokenUsage {
+    fn from(value: AnthropicUsage) -> Self {
+        TokenUsage::new(value.input_tokens, value.output_tokens)
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct AnthropicContentBlock {
     #[serde(default)]
     text: Option<String>,
+    /// Arguments of a `tool_use` block
+    #[serde(default)]
+    input: Option<serde_json::Value>,
 }
 
 fn collect_placeholders(input: &str) -> Vec<String> {
@@ -758,3 +1265,171 @@ fn ensure_placeholder_integrity(original: &str, translated: &str) -> Result<(),
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_key_and_model_errors_are_fatal() {
+        let unauthorized = TranslationError::Unauthorized {
+            provider: ProviderId::Gpt,
+            message: "invalid key".into(),
+        };
+        let rate_limited = TranslationError::RateLimited {
+            provider: ProviderId::Gpt,
+            message: "slow down".into(),
+            retry_hint: None,
+        };
+        assert!(unauthorized.is_fatal());
+        assert!(!rate_limited.is_fatal());
+    }
+
+    #[test]
+    fn parses_openai_usage() {
+        let body = r#"{"choices":[{"message":{"content":"안녕"}}],"usage":{"prompt_tokens":12,"completion_tokens":5,"total_tokens":17}}"#;
+        let parsed: OpenAiResponse = serde_json::from_str(body).unwrap();
+        let usage = parsed.usage.map(TokenUsage::from).unwrap();
+        assert_eq!(usage, TokenUsage::new(12, 5));
+        assert_eq!(usage.total_tokens(), 17);
+    }
+
+    #[test]
+    fn parses_gemini_and_anthropic_usage() {
+        let gemini =
+            r#"{"candidates":[],"usageMetadata":{"promptTokenCount":8,"candidatesTokenCount":3}}"#;
+        let parsed: GeminiResponse = serde_json::from_str(gemini).unwrap();
+        assert_eq!(
+            parsed.usage_metadata.map(TokenUsage::from),
+            Some(TokenUsage::new(8, 3))
+        );
+
+        let claude = r#"{"content":[{"text":"hi"}],"usage":{"input_tokens":20,"output_tokens":4}}"#;
+        let parsed: AnthropicResponse = serde_json::from_str(claude).unwrap();
+        assert_eq!(
+            parsed.usage.map(TokenUsage::from),
+            Some(TokenUsage::new(20, 4))
+        );
+    }
+
+    #[test]
+    fn parses_anthropic_tool_use_blocks() {
+        let claude = r#"{"content":[{"type":"tool_use","name":"submit_translation","input":{"translation":"안녕"}}]}"#;
+        let parsed: AnthropicResponse = serde_json::from_str(claude).unwrap();
+        let blocks = parsed.content.unwrap();
+        assert_eq!(
+            blocks[0]
+                .input
+                .as_ref()
+                .and_then(structured::translation_from_value),
+            Some("안녕".to_string())
+        );
+    }
+
+    #[test]
+    fn default_params_keep_legacy_temperature() {
+        let mut body = serde_json::json!({});
+        apply_openai_params(&mut body, &ModelParams::default(), "gpt-4o-mini");
+        assert_eq!(body["temperature"], serde_json::json!(DEFAULT_TEMPERATURE));
+        assert!(body.get("max_completion_tokens").is_none());
+
+        let mut body = serde_json::json!({});
+        apply_claude_params(&mut body, &ModelParams::default());
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_OUTPUT_TOKENS);
+
+        let mut body = serde_json::json!({});
+        apply_gemini_params(&mut body, &ModelParams::default());
+        assert!(body.get("generationConfig").is_none());
+    }
+
+    #[test]
+    fn reasoning_models_skip_sampling_params() {
+        let params = ModelParams {
+            temperature: Some(0.7),
+            reasoning_effort: Some(ReasoningEffort::High),
+            ..ModelParams::default()
+        };
+        let mut body = serde_json::json!({});
+        apply_openai_params(&mut body, &params, "o3-mini");
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["reasoning_effort"], "high");
+    }
+
+    #[test]
+    fn claude_thinking_raises_max_tokens() {
+        let params = ModelParams {
+            temperature: Some(0.5),
+            max_output_tokens: Some(2000),
+            thinking_budget_tokens: Some(4096),
+            ..ModelParams::default()
+        };
+        let mut body = serde_json::json!({});
+        apply_claude_params(&mut body, &params.sanitized(ProviderId::Claude));
+        assert_eq!(body["thinking"]["budget_tokens"], 4096);
+        assert!(body["max_tokens"].as_u64().unwrap() > 4096);
+        assert!(body.get("temperature").is_none());
+    }
+
+    #[test]
+    fn sanitized_params_clamp_ranges() {
+        let params = ModelParams {
+            temperature: Some(5.0),
+            top_p: Some(0.0),
+            max_output_tokens: Some(0),
+            ..ModelParams::default()
+        }
+        .sanitized(ProviderId::Gpt);
+        assert_eq!(params.temperature, Some(2.0));
+        assert_eq!(params.top_p, None);
+        assert_eq!(params.max_output_tokens, None);
+
+        let params = ModelParams {
+            temperature: Some(1.5),
+            ..ModelParams::default()
+        };
+        assert_eq!(params.sanitized(ProviderId::Gemini).temperature, Some(1.5));
+        assert_eq!(params.sanitized(ProviderId::Claude).temperature, Some(1.0));
+    }
+
+    #[test]
+    fn missing_usage_is_tolerated() {
+        let body = r#"{"choices":[{"message":{"content":"ok"}}]}"#;
+        let parsed: OpenAiResponse = serde_json::from_str(body).unwrap();
+        assert!(parsed.usage.is_none());
+    }
+
+    #[test]
+    fn document_mode_follows_the_output_limit() {
+        let settings = |provider: ProviderId, model_id: &str, max_output_tokens: Option<u32>| {
+            TranslationSettings {
+                provider,
+                api_key: String::new(),
+                model_id: model_id.to_string(),
+                source_lang: "en".into(),
+                target_lang: "ko".into(),
+                params: ModelParams {
+                    max_output_tokens,
+                    ..ModelParams::default()
+                },
+                prompt: PromptTemplate::default(),
+                prompt_vars: PromptVars::default(),
+                rate_limit: RateLimitConfig::default(),
+                base_url: None,
+            }
+        };
+        let text = "a".repeat(3_000);
+
+        // Claude answers with 1024 tokens unless told otherwise
+        let claude = settings(ProviderId::Claude, "claude-sonnet-4-5", None);
+        assert!(claude.needs_document_mode(&text));
+        assert_eq!(claude.document_chunk_chars(), DOCUMENT_CHUNK_MAX_CHARS);
+        let small = settings(ProviderId::Claude, "claude-sonnet-4-5", Some(500));
+        assert_eq!(small.document_chunk_chars(), 1_000);
+
+        let gemini = settings(ProviderId::Gemini, "gemini-2.5-flash", None);
+        assert!(!gemini.needs_document_mode(&text));
+        let unknown = settings(ProviderId::Grok, "grok-beta", None);
+        assert!(!unknown.needs_document_mode(&text));
+        assert!(unknown.needs_document_mode(&"a".repeat(5_000)));
+    }
+}
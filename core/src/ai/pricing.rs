@@ -0,0 +1,100 @@
+use super::{ProviderId, TokenUsage};
+
+/// Published list price for a model family, in USD per one million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    const fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    /// Estimates the cost of the given usage in USD.
+    pub fn cost_usd(&self, usage: &TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.input_per_million
+            + usage.completion_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Model id prefixes and their prices. More specific prefixes must come first
+/// because the first match wins.
+const PRICING_TABLE: &[(ProviderId, &str, ModelPricing)] = &[
+    (ProviderId::Gpt, "gpt-4o-mini", ModelPricing::new(0.15, 0.60)),
+    (ProviderId::Gpt, "gpt-4o", ModelPricing::new(2.50, 10.00)),
+    (ProviderId::Gpt, "gpt-4.1-nano", ModelPricing::new(0.10, 0.40)),
+    (ProviderId::Gpt, "gpt-4.1-mini", ModelPricing::new(0.40, 1.60)),
+    (ProviderId::Gpt, "gpt-4.1", ModelPricing::new(2.00, 8.00)),
+    (ProviderId::Gpt, "o4-mini", ModelPricing::new(1.10, 4.40)),
+    (ProviderId::Gpt, "o3-mini", ModelPricing::new(1.10, 4.40)),
+    (ProviderId::Gpt, "o3", ModelPricing::new(2.00, 8.00)),
+    (ProviderId::Gpt, "gpt-3.5-turbo", ModelPricing::new(0.50, 1.50)),
+    (ProviderId::Claude, "claude-3-haiku", ModelPricing::new(0.25, 1.25)),
+    (ProviderId::Claude, "claude-3-5-haiku", ModelPricing::new(0.80, 4.00)),
+    (ProviderId::Claude, "claude-haiku", ModelPricing::new(0.80, 4.00)),
+    (ProviderId::Claude, "claude-3-opus", ModelPricing::new(15.00, 75.00)),
+    (ProviderId::Claude, "claude-opus", ModelPricing::new(15.00, 75.00)),
+    (ProviderId::Claude, "claude-3", ModelPricing::new(3.00, 15.00)),
+    (ProviderId::Claude, "claude-sonnet", ModelPricing::new(3.00, 15.00)),
+    (ProviderId::Gemini, "gemini-1.5-flash", ModelPricing::new(0.075, 0.30)),
+    (ProviderId::Gemini, "gemini-1.5-pro", ModelPricing::new(1.25, 5.00)),
+    (ProviderId::Gemini, "gemini-2.0-flash-lite", ModelPricing::new(0.075, 0.30)),
+    (ProviderId::Gemini, "gemini-2.0-flash", ModelPricing::new(0.10, 0.40)),
+    (ProviderId::Gemini, "gemini-2.5-flash-lite", ModelPricing::new(0.10, 0.40)),
+    (ProviderId::Gemini, "gemini-2.5-flash", ModelPricing::new(0.30, 2.50)),
+    (ProviderId::Gemini, "gemini-2.5-pro", ModelPricing::new(1.25, 10.00)),
+    (ProviderId::Grok, "grok-3-mini", ModelPricing::new(0.30, 0.50)),
+    (ProviderId::Grok, "grok-3", ModelPricing::new(3.00, 15.00)),
+    (ProviderId::Grok, "grok-4", ModelPricing::new(3.00, 15.00)),
+];
+
+/// Looks up list pricing for a model. Returns `None` for unknown models so
+/// callers can decide how to treat unpriced usage.
+pub fn pricing_for(provider: ProviderId, model_id: &str) -> Option<ModelPricing> {
+    let normalized = model_id
+        .trim()
+        .trim_start_matches("models/")
+        .to_ascii_lowercase();
+    PRICING_TABLE
+        .iter()
+        .find(|(candidate, prefix, _)| *candidate == provider && normalized.starts_with(prefix))
+        .map(|(_, _, pricing)| *pricing)
+}
+
+/// Estimates the USD cost of `usage` for the given model, if it is priced.
+pub fn estimate_cost_usd(provider: ProviderId, model_id: &str, usage: &TokenUsage) -> Option<f64> {
+    pricing_for(provider, model_id).map(|pricing| pricing.cost_usd(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specific_prefix_wins() {
+        let mini = pricing_for(ProviderId::Gpt, "gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!(mini, ModelPricing::new(0.15, 0.60));
+        let full = pricing_for(ProviderId::Gpt, "gpt-4o-2024-08-06").unwrap();
+        assert_eq!(full, ModelPricing::new(2.50, 10.00));
+    }
+
+    #[test]
+    fn gemini_resource_names_are_normalized() {
+        assert!(pricing_for(ProviderId::Gemini, "models/gemini-2.0-flash").is_some());
+        assert!(pricing_for(ProviderId::Gemini, "gpt-4o").is_none());
+    }
+
+    #[test]
+    fn estimates_cost_from_usage() {
+        let usage = TokenUsage::new(1_000_000, 500_000);
+        let cost = estimate_cost_usd(ProviderId::Claude, "claude-sonnet-4-20250514", &usage).unwrap();
+        assert!((cost - 10.5).abs() < 1e-9);
+        assert!(estimate_cost_usd(ProviderId::Grok, "unknown-model", &usage).is_none());
+    }
+}
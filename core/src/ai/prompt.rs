@@ -0,0 +1,348 @@
+/// Prompt templates for translation requests.
+///
+/// Templates use a small Handlebars-style syntax: `{{name}}` inserts a
+/// variable and `{{#if name}}...{{/if}}` keeps its body only when the
+/// variable is not empty. The built-in templates are the prompts each provider
+/// has always been sent; `TranslatorConfig` overrides them for every provider
+/// or for one, and a game profile overrides them for its mods.
+use super::ProviderId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Variables a template may use
+pub const PROMPT_VARIABLES: &[&str] = &[
+    "source_lang",
+    "target_lang",
+    "text",
+    "context",
+    "glossary",
+    "tone",
+    "game",
+    "game_context",
+];
+
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a professional game localization translator. CRITICAL: Preserve ALL placeholders ({0}, %s, ${var}), numbers, math expressions, units, and formatting codes EXACTLY as they appear. Only translate natural language text. Never modify code tokens like __TOKEN_0__ or __PROTECT_1__.";
+
+const GEMINI_USER_PROMPT: &str = r#"Translate the following text from {{source_lang}} to {{target_lang}}.
+
+CRITICAL RULES (MUST FOLLOW):
+1. Preserve ALL placeholders exactly: {0}, {1}, %1$s, %d, ${variable}, etc.
+2. DO NOT translate or modify:
+   - Numbers and numeric values (keep "100", "3.14" as-is)
+   - Mathematical expressions (keep "x^2", "10-20" as-is)
+   - Units and measurements (keep "10kg", "5m", "20%" as-is)
+   - Code tokens: __TOKEN_0__, __PROTECT_1__, etc.
+   - File paths, URLs, variable names
+   - Formatting codes: \n, \r, \t, color codes like <color=#FF0000>
+3. Only translate natural language text
+4. Return ONLY the translated text, no explanations
+
+{{#if game}}The text comes from a {{game}} mod.{{#if game_context}} {{game_context}}{{/if}}
+
+{{/if}}{{#if tone}}Tone: {{tone}}
+
+{{/if}}{{#if glossary}}Use these translations for the following terms:
+{{glossary}}
+
+{{/if}}{{#if context}}Preceding text, for context only (do NOT translate it or include it in your answer):
+{{context}}
+
+{{/if}}Text to translate:
+{{text}}"#;
+
+const CHAT_USER_PROMPT: &str = r#"Translate the following text from {{source_lang}} to {{target_lang}}.
+
+CRITICAL RULES:
+1. Preserve ALL placeholders: {0}, %1$s, ${var}, etc.
+2. DO NOT translate numbers, math expressions, units, code tokens
+3. Keep formatting codes (\n, \t, color tags) unchanged
+4. Return ONLY the translated text
+
+{{#if game}}The text comes from a {{game}} mod.{{#if game_context}} {{game_context}}{{/if}}
+
+{{/if}}{{#if tone}}Tone: {{tone}}
+
+{{/if}}{{#if glossary}}Use these translations for the following terms:
+{{glossary}}
+
+{{/if}}{{#if context}}Preceding text, for context only (do NOT translate it or include it in your answer):
+{{context}}
+
+{{/if}}Text:
+{{text}}"#;
+
+/// System and user prompt templates; unset parts fall back to the next
+/// layer and finally to the built-in prompt
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplate {
+    /// 시스템 프롬프트 (Gemini는 systemInstruction으로 전송)
+    #[serde(default)]
+    pub system: Option<String>,
+    /// 번역 요청 프롬프트, `{{text}}`에 번역할 문장이 들어감
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+impl PromptTemplate {
+    /// The template with the parts set in `other` replaced
+    pub fn overlay(&self, other: &PromptTemplate) -> PromptTemplate {
+        PromptTemplate {
+            system: other.system.clone().or_else(|| self.system.clone()),
+            user: other.user.clone().or_else(|| self.user.clone()),
+        }
+    }
+
+    /// Checks both parts for unknown variables and unbalanced `{{#if}}`s
+    pub fn validate(&self) -> Result<(), String> {
+        for template in [&self.system, &self.user].into_iter().flatten() {
+            validate_template(template)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prompt settings of `TranslatorConfig`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptConfig {
+    /// 모든 번역기에 적용되는 템플릿
+    #[serde(default)]
+    pub default: PromptTemplate,
+    /// 번역기별 템플릿 (기본 템플릿보다 우선)
+    #[serde(default)]
+    pub providers: HashMap<ProviderId, PromptTemplate>,
+    /// `{{tone}}` 변수 값 (예: "formal, polite")
+    #[serde(default)]
+    pub tone: Option<String>,
+}
+
+impl PromptConfig {
+    /// Template for `provider`: its own parts over the shared ones
+    pub fn template_for(&self, provider: ProviderId) -> PromptTemplate {
+        match self.providers.get(&provider) {
+            Some(template) => self.default.overlay(template),
+            None => self.default.clone(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.default.validate()?;
+        for (provider, template) in &self.providers {
+            template
+                .validate()
+                .map_err(|error| format!("{} 프롬프트: {}", provider, error))?;
+        }
+        Ok(())
+    }
+}
+
+/// Values of the job- and mod-level template variables. The languages, the
+/// text and its context are filled in per request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromptVars {
+    pub glossary: Option<String>,
+    pub tone: Option<String>,
+    pub game: Option<String>,
+    pub game_context: Option<String>,
+}
+
+impl PromptVars {
+    /// Glossary terms as `source → target` lines, sorted by source term
+    pub fn glossary_lines(terms: &HashMap<String, String>) -> Option<String> {
+        let mut lines: Vec<String> = terms
+            .iter()
+            .filter(|(source, target)| !source.trim().is_empty() && !target.trim().is_empty())
+            .map(|(source, target)| format!("{} → {}", source.trim(), target.trim()))
+            .collect();
+        lines.sort();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+/// Built-in system prompt; Gemini requests never had one
+pub fn default_system_prompt(provider: ProviderId) -> Option<&'static str> {
+    match provider {
+        ProviderId::Gemini => None,
+        ProviderId::Gpt | ProviderId::Claude | ProviderId::Grok => Some(DEFAULT_SYSTEM_PROMPT),
+    }
+}
+
+/// Built-in user prompt
+pub fn default_user_prompt(provider: ProviderId) -> &'static str {
+    match provider {
+        ProviderId::Gemini => GEMINI_USER_PROMPT,
+        ProviderId::Gpt | ProviderId::Claude | ProviderId::Grok => CHAT_USER_PROMPT,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+    If(&'a str),
+    EndIf,
+}
+
+fn tokenize(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let tag = rest[start + 2..start + 2 + length].trim();
+        tokens.push(if tag == "/if" {
+            Token::EndIf
+        } else if let Some(name) = tag.strip_prefix("#if ") {
+            Token::If(name.trim())
+        } else {
+            Token::Variable(tag)
+        });
+        rest = &rest[start + 2 + length + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// Renders `template` with the values returned by `lookup`. Unknown variables
+/// render as nothing; a stray `{{/if}}` is ignored.
+pub fn render<'v>(template: &str, lookup: impl Fn(&str) -> Option<&'v str>) -> String {
+    let mut output = String::with_capacity(template.len());
+    // Whether each open `{{#if}}` (and everything outside them) is rendered
+    let mut active = vec![true];
+    for token in tokenize(template) {
+        let rendering = active.last().copied().unwrap_or(true);
+        match token {
+            Token::Text(text) if rendering => output.push_str(text),
+            Token::Variable(name) if rendering => output.push_str(lookup(name).unwrap_or("")),
+            Token::If(name) => {
+                let present = lookup(name).is_some_and(|value| !value.trim().is_empty());
+                active.push(rendering && present);
+            }
+            Token::EndIf if active.len() > 1 => {
+                active.pop();
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+/// Rejects unknown variables and unbalanced `{{#if}}` blocks
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let mut depth = 0usize;
+    for token in tokenize(template) {
+        match token {
+            Token::Variable(name) | Token::If(name) if !PROMPT_VARIABLES.contains(&name) => {
+                return Err(format!("알 수 없는 프롬프트 변수입니다: {{{{{}}}}}", name));
+            }
+            Token::If(_) => depth += 1,
+            Token::EndIf if depth == 0 => {
+                return Err("짝이 맞지 않는 {{/if}}가 있습니다.".into());
+            }
+            Token::EndIf => depth -= 1,
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        return Err("닫히지 않은 {{#if}}가 있습니다.".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars<'a>(pairs: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<&'a str> {
+        move |name| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        }
+    }
+
+    #[test]
+    fn renders_variables_and_conditional_blocks() {
+        let template = "{{source_lang}}->{{ target_lang }}{{#if tone}} ({{tone}}){{/if}}: {{text}}";
+        let pairs = [("source_lang", "en"), ("target_lang", "ko"), ("text", "Hi")];
+        assert_eq!(render(template, vars(&pairs)), "en->ko: Hi");
+
+        let pairs = [("target_lang", "ko"), ("tone", "casual"), ("text", "Hi")];
+        assert_eq!(render(template, vars(&pairs)), "->ko (casual): Hi");
+
+        // Nested blocks stay hidden inside a hidden block, literal braces pass through
+        let nested = "{{#if game}}[{{#if tone}}{{tone}}{{/if}}]{{/if}}{0} ${var}";
+        assert_eq!(render(nested, vars(&[("tone", "formal")])), "{0} ${var}");
+    }
+
+    #[test]
+    fn built_in_prompts_are_valid_and_skip_empty_sections() {
+        for provider in [
+            ProviderId::Gemini,
+            ProviderId::Gpt,
+            ProviderId::Claude,
+            ProviderId::Grok,
+        ] {
+            validate_template(default_user_prompt(provider)).unwrap();
+        }
+        let prompt = render(
+            CHAT_USER_PROMPT,
+            vars(&[
+                ("source_lang", "en"),
+                ("target_lang", "ko"),
+                ("text", "Hello"),
+            ]),
+        );
+        assert!(prompt.ends_with("unchanged\n4. Return ONLY the translated text\n\nText:\nHello"));
+        assert!(prompt.contains("{0}, %1$s, ${var}"));
+    }
+
+    #[test]
+    fn validation_rejects_unknown_variables_and_unbalanced_blocks() {
+        assert!(validate_template("{{text}} {{#if glossary}}{{glossary}}{{/if}}").is_ok());
+        assert!(validate_template("{{txt}}").is_err());
+        assert!(validate_template("{{#if tone}}{{tone}}").is_err());
+        assert!(validate_template("{{text}}{{/if}}").is_err());
+    }
+
+    #[test]
+    fn provider_templates_overlay_the_shared_one() {
+        let mut config = PromptConfig {
+            default: PromptTemplate {
+                system: Some("shared system".into()),
+                user: Some("shared {{text}}".into()),
+            },
+            ..Default::default()
+        };
+        config.providers.insert(
+            ProviderId::Claude,
+            PromptTemplate {
+                system: None,
+                user: Some("claude {{text}}".into()),
+            },
+        );
+
+        let claude = config.template_for(ProviderId::Claude);
+        assert_eq!(claude.system.as_deref(), Some("shared system"));
+        assert_eq!(claude.user.as_deref(), Some("claude {{text}}"));
+        assert_eq!(config.template_for(ProviderId::Gpt), config.default);
+
+        config.providers.insert(
+            ProviderId::Gpt,
+            PromptTemplate {
+                system: Some("{{#if tone}}".into()),
+                user: None,
+            },
+        );
+        assert!(config.validate().is_err());
+    }
+}
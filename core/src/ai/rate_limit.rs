@@ -0,0 +1,246 @@
+/// Request and token rate limits per provider and model.
+///
+/// Free tiers allow only a few requests or tokens per minute, and the
+/// concurrent segments of one or more jobs easily go over that, burning the
+/// retry budget on 429 responses. Every request first takes from token
+/// buckets shared by all jobs using the same provider and model, waiting
+/// until they have refilled enough.
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::ProviderId;
+
+/// Rough characters per token used to size requests before they are sent
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens of the instructions around the text in a request
+const PROMPT_OVERHEAD_TOKENS: u64 = 200;
+
+type LimiterKey = (ProviderId, String);
+
+static RATE_LIMITERS: Lazy<Mutex<HashMap<LimiterKey, Arc<RateLimiter>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct RateLimitConfig {
+    /// 분당 요청 수 (없으면 제한 없음)
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+    /// 분당 토큰 수 (입력 + 출력, 없으면 제한 없음)
+    #[serde(default)]
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimitConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.requests_per_minute.unwrap_or(0) == 0 && self.tokens_per_minute.unwrap_or(0) == 0
+    }
+}
+
+/// Bucket holding up to a minute's allowance and refilling continuously
+#[derive(Debug)]
+struct TokenBucket {
+    per_minute: f64,
+    available: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_minute: Option<u32>, now: Instant) -> Option<Self> {
+        let per_minute = f64::from(per_minute.filter(|value| *value > 0)?);
+        Some(Self {
+            per_minute,
+            available: per_minute,
+            updated: now,
+        })
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.available = (self.available + elapsed * self.per_minute / 60.0).min(self.per_minute);
+        self.updated = now;
+    }
+
+    /// Time until `amount` can be taken. Amounts above the capacity only
+    /// wait for a full bucket, otherwise they could never be sent.
+    fn wait_for(&self, amount: f64) -> Duration {
+        let missing = amount.min(self.per_minute) - self.available;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing * 60.0 / self.per_minute)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    config: RateLimitConfig,
+    requests: Option<TokenBucket>,
+    tokens: Option<TokenBucket>,
+}
+
+impl LimiterState {
+    fn new(config: RateLimitConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            config,
+            requests: TokenBucket::new(config.requests_per_minute, now),
+            tokens: TokenBucket::new(config.tokens_per_minute, now),
+        }
+    }
+}
+
+/// Limiter shared by every request to one provider and model
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            state: Mutex::new(LimiterState::new(config)),
+        }
+    }
+
+    /// Switches to `config`, starting from full buckets when the limits change
+    fn configure(&self, config: RateLimitConfig) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.config != config {
+            *state = LimiterState::new(config);
+        }
+    }
+
+    /// Time until a request of `tokens` tokens may be sent; when it is zero
+    /// the request has been taken from the buckets
+    fn try_acquire(&self, tokens: u64) -> Duration {
+        let Ok(mut state) = self.state.lock() else {
+            return Duration::ZERO;
+        };
+        let now = Instant::now();
+        let tokens = tokens as f64;
+        let mut wait = Duration::ZERO;
+        if let Some(bucket) = state.requests.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(1.0));
+        }
+        if let Some(bucket) = state.tokens.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(tokens));
+        }
+        if wait.is_zero() {
+            if let Some(bucket) = state.requests.as_mut() {
+                bucket.available -= 1.0;
+            }
+            if let Some(bucket) = state.tokens.as_mut() {
+                bucket.available -= tokens;
+            }
+        }
+        wait
+    }
+
+    /// Waits until a request estimated at `tokens` tokens may be sent
+    pub async fn acquire(&self, tokens: u64) {
+        loop {
+            let wait = self.try_acquire(tokens);
+            if wait.is_zero() {
+                return;
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Corrects the token bucket by the difference between the estimate a
+    /// request was admitted with and the usage the provider reported
+    pub fn record_usage(&self, estimated: u64, actual: u64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if let Some(bucket) = state.tokens.as_mut() {
+            bucket.available =
+                (bucket.available + estimated as f64 - actual as f64).min(bucket.per_minute);
+        }
+    }
+}
+
+/// Limiter shared by every request to `provider` and `model_id`, switched
+/// to `config`. `None` when `config` sets no limits.
+pub fn limiter_for(
+    provider: ProviderId,
+    model_id: &str,
+    config: &RateLimitConfig,
+) -> Option<Arc<RateLimiter>> {
+    if config.is_unlimited() {
+        return None;
+    }
+    let Ok(mut limiters) = RATE_LIMITERS.lock() else {
+        return None;
+    };
+    let limiter = limiters
+        .entry((provider, model_id.trim().to_string()))
+        .or_insert_with(|| Arc::new(RateLimiter::new(*config)))
+        .clone();
+    limiter.configure(*config);
+    Some(limiter)
+}
+
+/// Estimated tokens of a request translating `input` with `context`: the
+/// prompt, the text and a reply about as long as the text
+pub fn estimate_request_tokens(input: &str, context: Option<&str>) -> u64 {
+    let estimate = |text: &str| text.chars().count().div_ceil(CHARS_PER_TOKEN) as u64;
+    PROMPT_OVERHEAD_TOKENS + estimate(input) * 2 + context.map(estimate).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_wait_once_the_minute_allowance_is_used() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_minute: Some(2),
+            tokens_per_minute: None,
+        });
+        assert!(limiter.try_acquire(100).is_zero());
+        assert!(limiter.try_acquire(100).is_zero());
+        let wait = limiter.try_acquire(100);
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn token_limits_use_reported_usage() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_minute: None,
+            tokens_per_minute: Some(1000),
+        });
+        assert!(limiter.try_acquire(800).is_zero());
+        assert!(!limiter.try_acquire(800).is_zero());
+        // The request used far fewer tokens than estimated
+        limiter.record_usage(800, 200);
+        assert!(limiter.try_acquire(700).is_zero());
+        // Oversized requests only wait for a full bucket
+        assert!(limiter.try_acquire(5000) <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn limiters_are_shared_per_provider_and_model() {
+        let config = RateLimitConfig {
+            requests_per_minute: Some(10),
+            tokens_per_minute: None,
+        };
+        let first = limiter_for(ProviderId::Gemini, "gemini-test-shared", &config).unwrap();
+        let second = limiter_for(ProviderId::Gemini, " gemini-test-shared ", &config).unwrap();
+        let other = limiter_for(ProviderId::Gpt, "gemini-test-shared", &config).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert!(limiter_for(ProviderId::Gemini, "x", &RateLimitConfig::default()).is_none());
+    }
+}
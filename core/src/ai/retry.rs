@@ -1,4 +1,6 @@
 use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::time::{Duration, SystemTime};
 
 /// Policy parameters for retry decisions.
@@ -154,6 +156,26 @@ fn compute_exponential_backoff(
     Duration::from_millis(capped_ms as u64)
 }
 
+/// Spreads `delay` over its upper half ("equal jitter") so segments and jobs
+/// that failed together do not retry in lockstep. `sample` is in `[0, 1)`.
+pub fn apply_jitter(delay: Duration, sample: f64) -> Duration {
+    let half = delay / 2;
+    half + half.mul_f64(sample.clamp(0.0, 1.0))
+}
+
+/// Random sample in `[0, 1)` for [`apply_jitter`], taken from the randomly
+/// keyed standard library hasher.
+pub fn jitter_sample() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
 /// Parses the value of an HTTP `Retry-After` header.
 ///
 /// Returns `None` when parsing fails.
@@ -256,6 +278,19 @@ mod tests {
         assert!(!third.used_hint);
     }
 
+    #[test]
+    fn jitter_stays_in_upper_half() {
+        let delay = Duration::from_secs(8);
+        assert_eq!(apply_jitter(delay, 0.0), Duration::from_secs(4));
+        assert_eq!(apply_jitter(delay, 1.0), delay);
+        for _ in 0..100 {
+            let sample = jitter_sample();
+            assert!((0.0..1.0).contains(&sample));
+            let jittered = apply_jitter(delay, sample);
+            assert!(jittered >= Duration::from_secs(4) && jittered <= delay);
+        }
+    }
+
     #[test]
     fn respects_max_delay_cap() {
         let policy = RetryPolicy {
@@ -0,0 +1,209 @@
+/// Structured translation output.
+///
+/// Models sometimes wrap a translation in chatter ("Here is the
+/// translation:"), which then ends up in the merged file. Where the provider
+/// can constrain its reply, requests ask for `{"translation": "..."}`: JSON
+/// mode for OpenAI and Grok, a forced tool call for Claude and a response
+/// schema for Gemini. Replies are parsed leniently and fall back to the plain
+/// text when they are not the expected object.
+use serde_json::{json, Value};
+
+use crate::llm_guards::strip_code_fence;
+
+use super::{ModelParams, ProviderId};
+
+/// Field holding the translated text in structured replies
+pub const TRANSLATION_FIELD: &str = "translation";
+
+/// Tool Claude is made to call with the translation
+pub const CLAUDE_TOOL_NAME: &str = "submit_translation";
+
+/// Appended to the user prompt for JSON replies. OpenAI JSON mode also
+/// rejects requests whose messages do not mention JSON.
+pub const JSON_OUTPUT_INSTRUCTION: &str = "Respond with only a JSON object of the form \
+{\"translation\": \"<translated text>\"} and nothing else.";
+
+/// Whether structured output is requested from `provider` for `model_id`.
+/// On by default; models without support for it always get plain text.
+pub fn is_enabled(provider: ProviderId, model_id: &str, params: &ModelParams) -> bool {
+    if params.structured_output == Some(false) {
+        return false;
+    }
+    let lowered = model_id.trim().to_ascii_lowercase();
+    let lowered = lowered.strip_prefix("models/").unwrap_or(&lowered);
+    match provider {
+        // Gemma models served by the Gemini API have no JSON mode
+        ProviderId::Gemini => !lowered.starts_with("gemma"),
+        // The first o1 releases reject `response_format`
+        ProviderId::Gpt => !["o1-preview", "o1-mini"]
+            .iter()
+            .any(|prefix| lowered.starts_with(prefix)),
+        // Extended thinking does not allow forcing a tool call
+        ProviderId::Claude => params.thinking_budget_tokens.is_none(),
+        ProviderId::Grok => true,
+    }
+}
+
+/// `prompt` with the JSON reply instruction for providers answering in JSON
+pub fn with_instruction(provider: ProviderId, mut prompt: String) -> String {
+    if provider != ProviderId::Claude {
+        prompt.push_str("\n\n");
+        prompt.push_str(JSON_OUTPUT_INSTRUCTION);
+    }
+    prompt
+}
+
+/// JSON schema of the structured reply
+pub fn translation_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            TRANSLATION_FIELD: {
+                "type": "string",
+                "description": "The translated text"
+            }
+        },
+        "required": [TRANSLATION_FIELD]
+    })
+}
+
+/// Adds the structured output options of `provider` to a request body
+pub fn apply(body: &mut Value, provider: ProviderId) {
+    let Some(map) = body.as_object_mut() else {
+        return;
+    };
+    match provider {
+        ProviderId::Gpt | ProviderId::Grok => {
+            map.insert("response_format".into(), json!({ "type": "json_object" }));
+        }
+        ProviderId::Claude => {
+            map.insert(
+                "tools".into(),
+                json!([{
+                    "name": CLAUDE_TOOL_NAME,
+                    "description": "Submit the translated text.",
+                    "input_schema": translation_schema()
+                }]),
+            );
+            map.insert(
+                "tool_choice".into(),
+                json!({ "type": "tool", "name": CLAUDE_TOOL_NAME }),
+            );
+        }
+        ProviderId::Gemini => {
+            let config = map.entry("generationConfig").or_insert_with(|| json!({}));
+            if let Some(config) = config.as_object_mut() {
+                config.insert("responseMimeType".into(), json!("application/json"));
+                config.insert(
+                    "responseSchema".into(),
+                    json!({
+                        "type": "OBJECT",
+                        "properties": { TRANSLATION_FIELD: { "type": "STRING" } },
+                        "required": [TRANSLATION_FIELD]
+                    }),
+                );
+            }
+        }
+    }
+}
+
+/// The translation field of a structured reply object
+pub fn translation_from_value(value: &Value) -> Option<String> {
+    value
+        .get(TRANSLATION_FIELD)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Translation in a reply that was asked to be structured. Code fences and
+/// text around the object are tolerated; anything else is returned as is.
+pub fn parse_translation(text: &str) -> String {
+    let trimmed = strip_code_fence(text.trim());
+    let object = serde_json::from_str::<Value>(trimmed).ok().or_else(|| {
+        let start = trimmed.find('{')?;
+        let end = trimmed.rfind('}')?;
+        (start < end)
+            .then(|| serde_json::from_str::<Value>(&trimmed[start..=end]).ok())
+            .flatten()
+    });
+    object
+        .as_ref()
+        .and_then(translation_from_value)
+        .unwrap_or_else(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_are_parsed_leniently() {
+        assert_eq!(parse_translation(r#"{"translation": "안녕"}"#), "안녕");
+        assert_eq!(
+            parse_translation("```json\n{\"translation\": \"검 {0}\"}\n```"),
+            "검 {0}"
+        );
+        assert_eq!(
+            parse_translation("Here you go:\n{\"translation\": \"줄1\\n줄2\"}"),
+            "줄1\n줄2"
+        );
+        // Not the expected object: the reply is kept
+        assert_eq!(parse_translation("그냥 텍스트"), "그냥 텍스트");
+        assert_eq!(
+            parse_translation(r#"{"text": "안녕"}"#),
+            r#"{"text": "안녕"}"#
+        );
+    }
+
+    #[test]
+    fn requests_are_shaped_per_provider() {
+        let mut body = json!({ "generationConfig": { "temperature": 0.2 } });
+        apply(&mut body, ProviderId::Gemini);
+        assert_eq!(body["generationConfig"]["temperature"], 0.2);
+        assert_eq!(
+            body["generationConfig"]["responseMimeType"],
+            "application/json"
+        );
+
+        let mut body = json!({});
+        apply(&mut body, ProviderId::Claude);
+        assert_eq!(body["tool_choice"]["name"], CLAUDE_TOOL_NAME);
+
+        let mut body = json!({});
+        apply(&mut body, ProviderId::Gpt);
+        assert_eq!(body["response_format"]["type"], "json_object");
+        assert!(with_instruction(ProviderId::Gpt, "Translate".into()).contains("JSON"));
+        assert_eq!(
+            with_instruction(ProviderId::Claude, "Translate".into()),
+            "Translate"
+        );
+    }
+
+    #[test]
+    fn unsupported_models_get_plain_text() {
+        let params = ModelParams::default();
+        assert!(is_enabled(ProviderId::Gemini, "gemini-2.5-flash", &params));
+        assert!(!is_enabled(
+            ProviderId::Gemini,
+            "models/gemma-3-27b-it",
+            &params
+        ));
+        assert!(!is_enabled(ProviderId::Gpt, "o1-mini", &params));
+
+        let thinking = ModelParams {
+            thinking_budget_tokens: Some(2048),
+            ..ModelParams::default()
+        };
+        assert!(!is_enabled(
+            ProviderId::Claude,
+            "claude-sonnet-4-5",
+            &thinking
+        ));
+
+        let off = ModelParams {
+            structured_output: Some(false),
+            ..ModelParams::default()
+        };
+        assert!(!is_enabled(ProviderId::Grok, "grok-4", &off));
+    }
+}
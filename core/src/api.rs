@@ -0,0 +1,69 @@
+//! Stable facade for embedding `mod_translator_core`.
+//!
+//! Everything re-exported here follows semantic versioning: items are only
+//! removed or changed incompatibly in a major release, and new fields on
+//! serialized payloads are always optional. Payload, event and settings
+//! structs are `#[non_exhaustive]`, so build them with `Default` or their
+//! `new` constructors and set fields afterwards. Other public modules of this
+//! crate are implementation details of the desktop app and may change in any
+//! release; they are hidden from the docs and collected under [`unstable`]
+//! when the `unstable` feature is enabled.
+//!
+//! ```
+//! use mod_translator_core::api::{FileFormat, GameProfile, PlaceholderValidator};
+//! ```
+
+// Translation jobs: payloads, progress events and the job queue.
+pub use crate::job::queue::{JobPriority, JobQueue, QueueError, DEFAULT_MAX_CONCURRENT_JOBS};
+pub use crate::jobs::{
+    ContextMode, JobStatusSnapshot, PendingSegment, ReviewSegment, StartTranslationJobPayload,
+    TranslationCheckpoint, TranslationFileInput, TranslationProgressEventPayload, TranslationUsage,
+};
+
+// Providers and model settings.
+pub use crate::ai::{
+    ModelParams, ProviderId, ReasoningEffort, TokenUsage, TranslationError, DEFAULT_MAX_OUTPUT_TOKENS,
+    DEFAULT_TEMPERATURE,
+};
+pub use crate::ai::rate_limit::RateLimitConfig;
+
+// Configuration.
+pub use crate::config::{
+    IgnoreOptions, RequestOptions, TranslatorConfig, UiOptions, ValidatorOptions};
+
+// Game profiles.
+pub use crate::profiles::{
+    list_profiles, DetectionRules, FormatRule, GameProfile, TokenSubstitution, ValidatorProfileConfig,
+};
+
+// File formats.
+pub use crate::formats::{
+    get_handler, FailedEntry, FileFormat, FormatError, FormatHandler, TranslatableEntry,
+    TranslatedEntry, TranslationResult,
+};
+
+// Placeholder validation.
+pub use crate::placeholder_validator::{
+    AutofixResult, PlaceholderValidator, RecoveryStep, Segment, ValidationErrorCode,
+    ValidationFailureReport, ValidationMode, ValidationSuccess, ValidatorConfig,
+};
+
+// Review reports.
+pub use crate::review::{
+    FileReviewReport, ReviewMetadata, ReviewSummary, SegmentReview, SegmentReviewStatus,
+};
+
+/// Implementation modules without stability guarantees. Enable the
+/// `unstable` feature to use them through this path.
+#[cfg(feature = "unstable")]
+pub mod unstable {
+    pub use crate::ai::{hints, pricing, retry};
+    pub use crate::job::adaptive;
+    #[cfg(feature = "tauri")]
+    pub use crate::job::runner;
+    pub use crate::{
+        archive, backup, document, encoding, format_validator, incremental, llm_guards, math_units,
+        paths, pipeline, policy, protector, provenance, quality, scanner, scanners, text_extractor,
+        tone_analyzer, translate, validation_logger, validator,
+    };
+}
@@ -1,18 +1,24 @@
-//! ZIP/JAR 아카이브 파일 처리 모듈
+//! ZIP/JAR/7z/RAR 아카이브 파일 처리 모듈
 //!
 //! 마인크래프트 모드(.jar) 및 일반 ZIP 아카이브 내부의
 //! 언어 파일을 읽고, 번역 후 재패키징하는 기능을 제공합니다.
+//! 7z와 RAR은 읽기만 지원하며, 번역 결과는 원본 옆의 ZIP 파일에 저장합니다.
+//! RAR은 설치된 7-Zip 실행 파일로 읽습니다.
 
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{self, Read, Write};
+use std::io::{self, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use sevenz_rust::{Password, SevenZReader};
 use zip::read::ZipArchive;
 use zip::write::{FileOptions, ZipWriter};
 use zip::CompressionMethod;
 
+use crate::backup::write_atomic_with;
+use crate::encoding::FileMetadata;
+
 /// 아카이브 처리 결과 에러 타입
 #[derive(Debug, thiserror::Error)]
 pub enum ArchiveError {
@@ -22,6 +28,12 @@ pub enum ArchiveError {
     #[error("ZIP error: {0}")]
     Zip(#[from] zip::result::ZipError),
 
+    #[error("7z error: {0}")]
+    SevenZip(#[from] sevenz_rust::Error),
+
+    #[error("RAR error: {0}")]
+    Rar(String),
+
     #[error("Archive not found: {0}")]
     NotFound(String),
 
@@ -54,7 +66,7 @@ pub struct ArchiveScanResult {
     pub archive_path: PathBuf,
     /// 발견된 언어 파일들
     pub language_files: Vec<ArchiveEntry>,
-    /// 아카이브 타입 (jar, zip)
+    /// 아카이브 타입 (jar, zip, 7z, rar)
     pub archive_type: ArchiveType,
     /// 총 엔트리 수
     pub total_entries: usize,
@@ -66,6 +78,9 @@ pub struct ArchiveScanResult {
 pub enum ArchiveType {
     Jar,
     Zip,
+    #[serde(rename = "7z")]
+    SevenZip,
+    Rar,
 }
 
 impl ArchiveType {
@@ -73,6 +88,8 @@ impl ArchiveType {
         match ext.to_lowercase().as_str() {
             "jar" => Some(Self::Jar),
             "zip" => Some(Self::Zip),
+            "7z" => Some(Self::SevenZip),
+            "rar" => Some(Self::Rar),
             _ => None,
         }
     }
@@ -81,16 +98,20 @@ impl ArchiveType {
         match self {
             Self::Jar => "jar",
             Self::Zip => "zip",
+            Self::SevenZip => "7z",
+            Self::Rar => "rar",
         }
     }
+
+    /// 원본 아카이브를 직접 수정할 수 있는지 (7z/RAR은 읽기 전용)
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Self::Jar | Self::Zip)
+    }
 }
 
 /// 아카이브 파일인지 확인
 pub fn is_archive_file(path: &Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| matches!(ext.to_lowercase().as_str(), "jar" | "zip"))
-        .unwrap_or(false)
+    detect_archive_type(path).is_some()
 }
 
 /// 아카이브 타입 감지
@@ -153,8 +174,35 @@ fn is_language_file(entry_path: &str) -> bool {
     false
 }
 
+/// 진행 상황 콜백을 호출하는 간격 (엔트리 수)
+const PROGRESS_INTERVAL: usize = 500;
+
+/// 아카이브 스캔 진행 상황
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveScanProgress {
+    pub archive_path: PathBuf,
+    /// 지금까지 확인한 엔트리 수
+    pub scanned_entries: usize,
+    pub total_entries: usize,
+    /// 지금까지 발견한 언어 파일 수
+    pub language_files: usize,
+}
+
 /// 아카이브 내부 스캔
 pub fn scan_archive(archive_path: &Path) -> ArchiveResult<ArchiveScanResult> {
+    scan_archive_with_progress(archive_path, |_| {})
+}
+
+/// 아카이브 내부 스캔 (진행 상황 보고)
+///
+/// ZIP/JAR은 중앙 디렉터리의 엔트리 이름만 보고 언어 파일이 아닌 엔트리를
+/// 건너뛰므로, 수 GB 모드팩도 엔트리를 열거나 압축을 풀지 않고 스캔합니다.
+/// `on_progress`는 `PROGRESS_INTERVAL`개마다, 그리고 마지막에 한 번 호출됩니다.
+pub fn scan_archive_with_progress(
+    archive_path: &Path,
+    mut on_progress: impl FnMut(&ArchiveScanProgress),
+) -> ArchiveResult<ArchiveScanResult> {
     if !archive_path.exists() {
         return Err(ArchiveError::NotFound(archive_path.display().to_string()));
     }
@@ -162,64 +210,267 @@ pub fn scan_archive(archive_path: &Path) -> ArchiveResult<ArchiveScanResult> {
     let archive_type = detect_archive_type(archive_path)
         .ok_or_else(|| ArchiveError::InvalidFormat(archive_path.display().to_string()))?;
 
-    let file = File::open(archive_path)?;
-    let mut archive = ZipArchive::new(file)?;
-
-    let total_entries = archive.len();
+    let mut progress = ArchiveScanProgress {
+        archive_path: archive_path.to_path_buf(),
+        scanned_entries: 0,
+        total_entries: 0,
+        language_files: 0,
+    };
     let mut language_files = Vec::new();
-
-    for i in 0..total_entries {
-        let entry = archive.by_index(i)?;
-        let entry_path = entry.name().to_string();
-        
-        if entry.is_dir() {
-            continue;
+    let mut next_report = PROGRESS_INTERVAL;
+
+    match archive_type {
+        ArchiveType::Jar | ArchiveType::Zip => {
+            let mut archive = open_zip(archive_path)?;
+            progress.total_entries = archive.len();
+            for i in 0..archive.len() {
+                let is_candidate = archive
+                    .name_for_index(i)
+                    .is_some_and(|name| !name.ends_with('/') && is_language_file(name));
+                if is_candidate {
+                    // 압축 해제기를 만들지 않고 크기 정보만 읽음
+                    let entry = archive.by_index_raw(i)?;
+                    language_files.push(ArchiveEntry {
+                        path: entry.name().to_string(),
+                        size: entry.size(),
+                        compressed_size: entry.compressed_size(),
+                        is_dir: false,
+                    });
+                }
+                report_progress(
+                    &mut progress,
+                    i + 1,
+                    language_files.len(),
+                    &mut next_report,
+                    &mut on_progress,
+                );
+            }
         }
-
-        if is_language_file(&entry_path) {
-            language_files.push(ArchiveEntry {
-                path: entry_path,
-                size: entry.size(),
-                compressed_size: entry.compressed_size(),
-                is_dir: false,
-            });
+        ArchiveType::SevenZip | ArchiveType::Rar => {
+            let entries = list_entries(archive_path, archive_type)?;
+            progress.total_entries = entries.len();
+            for (i, entry) in entries.into_iter().enumerate() {
+                if !entry.is_dir && is_language_file(&entry.path) {
+                    language_files.push(entry);
+                }
+                report_progress(
+                    &mut progress,
+                    i + 1,
+                    language_files.len(),
+                    &mut next_report,
+                    &mut on_progress,
+                );
+            }
         }
     }
+    if progress.total_entries == 0 {
+        on_progress(&progress);
+    }
 
     Ok(ArchiveScanResult {
         archive_path: archive_path.to_path_buf(),
         language_files,
         archive_type,
-        total_entries,
+        total_entries: progress.total_entries,
     })
 }
 
+fn report_progress(
+    progress: &mut ArchiveScanProgress,
+    scanned_entries: usize,
+    language_files: usize,
+    next_report: &mut usize,
+    on_progress: &mut impl FnMut(&ArchiveScanProgress),
+) {
+    progress.scanned_entries = scanned_entries;
+    progress.language_files = language_files;
+    if scanned_entries >= *next_report || scanned_entries == progress.total_entries {
+        on_progress(progress);
+        *next_report = scanned_entries + PROGRESS_INTERVAL;
+    }
+}
+
+/// 버퍼를 거쳐 ZIP 아카이브 열기 (중앙 디렉터리를 작은 단위로 여러 번 읽음)
+fn open_zip(archive_path: &Path) -> ArchiveResult<ZipArchive<BufReader<File>>> {
+    Ok(ZipArchive::new(BufReader::new(File::open(archive_path)?))?)
+}
+
+/// 아카이브의 모든 엔트리 목록
+fn list_entries(archive_path: &Path, archive_type: ArchiveType) -> ArchiveResult<Vec<ArchiveEntry>> {
+    match archive_type {
+        ArchiveType::Jar | ArchiveType::Zip => {
+            let mut archive = open_zip(archive_path)?;
+            let mut entries = Vec::with_capacity(archive.len());
+            for i in 0..archive.len() {
+                let entry = archive.by_index_raw(i)?;
+                entries.push(ArchiveEntry {
+                    path: entry.name().to_string(),
+                    size: entry.size(),
+                    compressed_size: entry.compressed_size(),
+                    is_dir: entry.is_dir(),
+                });
+            }
+            Ok(entries)
+        }
+        ArchiveType::SevenZip => {
+            let reader = SevenZReader::open(archive_path, Password::empty())?;
+            Ok(reader
+                .archive()
+                .files
+                .iter()
+                .map(|entry| ArchiveEntry {
+                    path: entry.name().replace('\\', "/"),
+                    size: entry.size(),
+                    compressed_size: entry.compressed_size,
+                    is_dir: entry.is_directory(),
+                })
+                .collect())
+        }
+        ArchiveType::Rar => {
+            let listing = run_seven_zip(&["l", "-slt", "-ba"], archive_path, None)?;
+            Ok(parse_slt_listing(&String::from_utf8_lossy(&listing)))
+        }
+    }
+}
+
 /// 아카이브에서 특정 파일 내용 읽기
 pub fn read_archive_entry(archive_path: &Path, entry_path: &str) -> ArchiveResult<Vec<u8>> {
-    let file = File::open(archive_path)?;
-    let mut archive = ZipArchive::new(file)?;
+    let archive_type = detect_archive_type(archive_path)
+        .ok_or_else(|| ArchiveError::InvalidFormat(archive_path.display().to_string()))?;
+
+    match archive_type {
+        ArchiveType::Jar | ArchiveType::Zip => {
+            let mut archive = open_zip(archive_path)?;
+
+            let mut entry = archive.by_name(entry_path)
+                .map_err(|_| ArchiveError::EntryNotFound(entry_path.to_string()))?;
+
+            let mut contents = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut contents)?;
+
+            Ok(contents)
+        }
+        ArchiveType::SevenZip => {
+            // 솔리드 압축은 앞쪽 엔트리부터 순서대로 풀어야 하므로 전체를 순회
+            let mut reader = SevenZReader::open(archive_path, Password::empty())?;
+            let mut found = None;
+            reader.for_each_entries(|entry, data| {
+                if entry.is_directory() || entry.name().replace('\\', "/") != entry_path {
+                    return Ok(true);
+                }
+                let mut contents = Vec::with_capacity(entry.size() as usize);
+                data.read_to_end(&mut contents)?;
+                found = Some(contents);
+                Ok(false)
+            })?;
+            found.ok_or_else(|| ArchiveError::EntryNotFound(entry_path.to_string()))
+        }
+        ArchiveType::Rar => {
+            let exists = list_entries(archive_path, archive_type)?
+                .iter()
+                .any(|entry| !entry.is_dir && entry.path == entry_path);
+            if !exists {
+                return Err(ArchiveError::EntryNotFound(entry_path.to_string()));
+            }
+            run_seven_zip(&["e", "-so"], archive_path, Some(entry_path))
+        }
+    }
+}
+
+/// 7-Zip 실행 파일 후보 (RAR 읽기용, 7za는 RAR을 지원하지 않음)
+fn seven_zip_candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from("7z"), PathBuf::from("7zz")];
+    if cfg!(target_os = "windows") {
+        for base in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Some(dir) = std::env::var_os(base) {
+                candidates.push(PathBuf::from(dir).join("7-Zip").join("7z.exe"));
+            }
+        }
+    }
+    candidates
+}
 
-    let mut entry = archive.by_name(entry_path)
-        .map_err(|_| ArchiveError::EntryNotFound(entry_path.to_string()))?;
+/// 7-Zip을 실행하고 표준 출력을 반환
+fn run_seven_zip(args: &[&str], archive_path: &Path, entry_path: Option<&str>) -> ArchiveResult<Vec<u8>> {
+    for program in seven_zip_candidates() {
+        let mut command = std::process::Command::new(&program);
+        command.args(args).arg(archive_path);
+        if let Some(entry) = entry_path {
+            command.arg(entry);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
 
-    let mut contents = Vec::with_capacity(entry.size() as usize);
-    entry.read_to_end(&mut contents)?;
+        let output = match command.output() {
+            Ok(output) => output,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        if !output.status.success() {
+            return Err(ArchiveError::Rar(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        return Ok(output.stdout);
+    }
+    Err(ArchiveError::Rar(
+        "RAR 아카이브를 읽으려면 7-Zip을 설치해야 합니다".to_string(),
+    ))
+}
 
-    Ok(contents)
+/// `7z l -slt -ba` 출력을 엔트리 목록으로 변환
+fn parse_slt_listing(listing: &str) -> Vec<ArchiveEntry> {
+    let mut entries: Vec<ArchiveEntry> = Vec::new();
+    for line in listing.lines() {
+        let Some((key, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        if key == "Path" {
+            entries.push(ArchiveEntry {
+                path: value.replace('\\', "/"),
+                size: 0,
+                compressed_size: 0,
+                is_dir: false,
+            });
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+        match key {
+            "Size" => entry.size = value.parse().unwrap_or(0),
+            "Packed Size" => entry.compressed_size = value.parse().unwrap_or(0),
+            "Folder" => entry.is_dir = value == "+",
+            "Attributes" => entry.is_dir |= value.starts_with('D'),
+            _ => {}
+        }
+    }
+    entries
 }
 
 /// 아카이브에서 특정 파일 내용을 문자열로 읽기
 pub fn read_archive_entry_string(archive_path: &Path, entry_path: &str) -> ArchiveResult<String> {
+    read_archive_entry_text(archive_path, entry_path).map(|(content, _)| content)
+}
+
+/// 아카이브에서 특정 파일 내용을 읽고 인코딩, 줄바꿈, BOM 정보도 함께 반환
+pub fn read_archive_entry_text(
+    archive_path: &Path,
+    entry_path: &str,
+) -> ArchiveResult<(String, FileMetadata)> {
     let bytes = read_archive_entry(archive_path, entry_path)?;
     
-    // UTF-8 BOM 제거
-    let content = if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        String::from_utf8_lossy(&bytes[3..]).to_string()
-    } else {
-        String::from_utf8_lossy(&bytes).to_string()
+    // BOM과 레거시 인코딩(CP949, Shift-JIS 등)을 감지해 디코딩
+    let decoded = match FileMetadata::decode_bytes(&bytes) {
+        Ok(decoded) => decoded,
+        Err(_) => (String::from_utf8_lossy(&bytes).to_string(), FileMetadata::detect(&bytes)),
     };
     
-    Ok(content)
+    Ok(decoded)
 }
 
 /// 아카이브 수정 옵션
@@ -273,7 +524,8 @@ impl ArchiveModification {
 /// 아카이브 수정 및 새 파일로 저장
 /// 
 /// 원본 아카이브를 읽어서 수정사항을 적용한 후 새 파일로 저장합니다.
-/// 원본 파일은 변경되지 않습니다.
+/// 임시 파일에 작성한 뒤 교체하므로 `output_path`가 원본과 같아도 되며,
+/// 중간에 실패해도 기존 파일은 그대로 남습니다.
 pub fn modify_archive(
     source_path: &Path,
     output_path: &Path,
@@ -281,18 +533,26 @@ pub fn modify_archive(
 ) -> ArchiveResult<()> {
     if modifications.is_empty() {
         // 수정사항이 없으면 단순 복사
-        fs::copy(source_path, output_path)?;
+        if source_path != output_path {
+            write_atomic_with(output_path, |file| {
+                io::copy(&mut File::open(source_path)?, file).map(|_| ())
+            })?;
+        }
         return Ok(());
     }
 
-    let source_file = File::open(source_path)?;
-    let mut source_archive = ZipArchive::new(source_file)?;
+    // 임시 파일에 작성 후 최종 위치로 이동 (원본은 교체 전에 닫힘)
+    write_atomic_with(output_path, |output_file| {
+        let source_file = File::open(source_path)?;
+        write_modified_archive(ZipArchive::new(source_file)?, output_file, modifications)
+    })
+}
 
-    // 임시 파일에 작성 후 최종 위치로 이동
-    let output_dir = output_path.parent().unwrap_or(Path::new("."));
-    fs::create_dir_all(output_dir)?;
-    
-    let output_file = File::create(output_path)?;
+fn write_modified_archive(
+    mut source_archive: ZipArchive<File>,
+    output_file: &mut File,
+    modifications: &ArchiveModification,
+) -> ArchiveResult<()> {
     let mut writer = ZipWriter::new(output_file);
 
     // 압축 옵션 설정
@@ -342,20 +602,38 @@ pub fn modify_archive(
     Ok(())
 }
 
+/// 아카이브 번역 저장 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveUpdate {
+    /// 번역이 기록된 파일 (7z/RAR은 옆의 ZIP)
+    pub written_path: PathBuf,
+    /// 수정 전 파일의 백업 (백업하지 않았거나 새로 만든 경우 None)
+    pub backup_path: Option<PathBuf>,
+}
+
 /// 아카이브 내부에 번역된 언어 파일을 추가/업데이트하고 원본 백업 후 덮어쓰기
 pub fn update_archive_with_translations(
     archive_path: &Path,
     translations: HashMap<String, String>,
     backup_dir: Option<&Path>,
-) -> ArchiveResult<PathBuf> {
-    // 백업 생성
+) -> ArchiveResult<ArchiveUpdate> {
+    let archive_type = detect_archive_type(archive_path)
+        .ok_or_else(|| ArchiveError::InvalidFormat(archive_path.display().to_string()))?;
+    if !archive_type.is_writable() {
+        return write_sibling_zip(archive_path, translations, backup_dir);
+    }
+
+    // 백업 생성 (작업마다 따로 남도록 시각을 붙임)
+    let mut backup_path = None;
     if let Some(backup_base) = backup_dir {
         let file_name = archive_path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("archive");
-        let backup_path = backup_base.join(format!("{}.backup", file_name));
+        let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+        let path = backup_base.join(format!("{}.{}.backup", file_name, timestamp));
         fs::create_dir_all(backup_base)?;
-        fs::copy(archive_path, &backup_path)?;
+        fs::copy(archive_path, &path)?;
+        backup_path = Some(path);
     }
 
     // 수정사항 준비
@@ -374,14 +652,55 @@ pub fn update_archive_with_translations(
         }
     }
 
-    // 임시 파일에 수정된 아카이브 생성
-    let temp_path = archive_path.with_extension("tmp");
-    modify_archive(archive_path, &temp_path, &modifications)?;
+    // 임시 파일에 수정된 아카이브를 만든 뒤 원본과 교체
+    modify_archive(archive_path, archive_path, &modifications)?;
+
+    Ok(ArchiveUpdate {
+        written_path: archive_path.to_path_buf(),
+        backup_path,
+    })
+}
+
+/// 수정할 수 없는 아카이브(7z/RAR)의 번역을 저장할 ZIP 경로
+///
+/// 예: Mods/MyMod.7z -> Mods/MyMod.translated.zip
+pub fn sibling_zip_path(archive_path: &Path) -> PathBuf {
+    let stem = archive_path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("archive");
+    archive_path.with_file_name(format!("{}.translated.zip", stem))
+}
 
-    // 원본을 임시 파일로 교체
-    fs::rename(&temp_path, archive_path)?;
+/// 번역된 파일만 담은 ZIP을 원본 옆에 저장 (이미 있으면 백업 후 갱신)
+fn write_sibling_zip(
+    archive_path: &Path,
+    translations: HashMap<String, String>,
+    backup_dir: Option<&Path>,
+) -> ArchiveResult<ArchiveUpdate> {
+    let zip_path = sibling_zip_path(archive_path);
+    if zip_path.exists() {
+        return update_archive_with_translations(&zip_path, translations, backup_dir);
+    }
 
-    Ok(archive_path.to_path_buf())
+    let mut entries: Vec<(String, String)> = translations.into_iter().collect();
+    entries.sort();
+
+    write_atomic_with(&zip_path, |file| -> ArchiveResult<()> {
+        let mut writer = ZipWriter::new(file);
+        let options = FileOptions::<()>::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+        for (path, content) in entries {
+            writer.start_file(path, options)?;
+            writer.write_all(content.as_bytes())?;
+        }
+        writer.finish()?;
+        Ok(())
+    })?;
+    Ok(ArchiveUpdate {
+        written_path: zip_path,
+        backup_path: None,
+    })
 }
 
 /// 마인크래프트 모드 JAR에서 언어 파일 경로 생성
@@ -415,6 +734,8 @@ mod tests {
         assert!(is_archive_file(Path::new("archive.zip")));
         assert!(is_archive_file(Path::new("test.ZIP")));
         assert!(is_archive_file(Path::new("mod.JAR")));
+        assert!(is_archive_file(Path::new("mod.7z")));
+        assert!(is_archive_file(Path::new("mod.RAR")));
         assert!(!is_archive_file(Path::new("file.txt")));
         assert!(!is_archive_file(Path::new("file.json")));
     }
@@ -423,7 +744,111 @@ mod tests {
     fn test_archive_type_detection() {
         assert_eq!(detect_archive_type(Path::new("mod.jar")), Some(ArchiveType::Jar));
         assert_eq!(detect_archive_type(Path::new("archive.zip")), Some(ArchiveType::Zip));
+        assert_eq!(detect_archive_type(Path::new("mod.7z")), Some(ArchiveType::SevenZip));
+        assert_eq!(detect_archive_type(Path::new("mod.rar")), Some(ArchiveType::Rar));
         assert_eq!(detect_archive_type(Path::new("file.txt")), None);
+        assert!(!ArchiveType::SevenZip.is_writable());
+        assert!(!ArchiveType::Rar.is_writable());
+        assert_eq!(serde_json::to_string(&ArchiveType::SevenZip).unwrap(), "\"7z\"");
+    }
+
+    #[test]
+    fn test_seven_zip_reads_and_writes_sibling_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("MyMod.7z");
+        let mut writer = sevenz_rust::SevenZWriter::create(&archive_path).unwrap();
+        for (name, content) in [
+            ("About/About.xml", "<ModMetaData/>"),
+            ("Languages/English/Keyed/Misc.xml", "<LanguageData><A>Hello</A></LanguageData>"),
+        ] {
+            let mut entry = sevenz_rust::SevenZArchiveEntry::new();
+            entry.name = name.to_string();
+            entry.has_stream = true;
+            writer.push_archive_entry(entry, Some(content.as_bytes())).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let scan = scan_archive(&archive_path).unwrap();
+        assert_eq!(scan.archive_type, ArchiveType::SevenZip);
+        assert_eq!(scan.total_entries, 2);
+        let paths: Vec<&str> = scan.language_files.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["Languages/English/Keyed/Misc.xml"]);
+        assert_eq!(
+            read_archive_entry_string(&archive_path, "Languages/English/Keyed/Misc.xml").unwrap(),
+            "<LanguageData><A>Hello</A></LanguageData>"
+        );
+        assert!(matches!(
+            read_archive_entry(&archive_path, "missing.xml"),
+            Err(ArchiveError::EntryNotFound(_))
+        ));
+
+        let keyed = "Languages/Korean/Keyed/Misc.xml".to_string();
+        let written = update_archive_with_translations(
+            &archive_path,
+            HashMap::from([(keyed.clone(), "<LanguageData><A>안녕</A></LanguageData>".to_string())]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            written,
+            ArchiveUpdate {
+                written_path: dir.path().join("MyMod.translated.zip"),
+                backup_path: None,
+            }
+        );
+        let update = update_archive_with_translations(
+            &archive_path,
+            HashMap::from([(keyed.clone(), "<LanguageData><A>안녕하세요</A></LanguageData>".to_string())]),
+            Some(&dir.path().join(".backup")),
+        )
+        .unwrap();
+        let written = update.written_path;
+        assert!(update.backup_path.unwrap().starts_with(dir.path().join(".backup")));
+        assert_eq!(
+            read_archive_entry_string(&written, &keyed).unwrap(),
+            "<LanguageData><A>안녕하세요</A></LanguageData>"
+        );
+        assert_eq!(scan_archive(&written).unwrap().total_entries, 1);
+    }
+
+    #[test]
+    fn test_scan_skips_non_language_entries_and_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("pack.jar");
+        let mut writer = ZipWriter::new(File::create(&archive_path).unwrap());
+        let options = FileOptions::<()>::default();
+        writer.add_directory("assets/demo/lang/", options).unwrap();
+        writer.start_file("assets/demo/lang/en_us.json", options).unwrap();
+        writer.write_all(b"{}").unwrap();
+        for i in 0..PROGRESS_INTERVAL {
+            writer.start_file(format!("assets/demo/textures/{}.png", i), options).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut reports = Vec::new();
+        let scan = scan_archive_with_progress(&archive_path, |progress| {
+            reports.push((progress.scanned_entries, progress.language_files));
+        })
+        .unwrap();
+
+        let total = PROGRESS_INTERVAL + 2;
+        assert_eq!(scan.total_entries, total);
+        assert_eq!(scan.language_files.len(), 1);
+        assert_eq!(scan.language_files[0].size, 2);
+        assert_eq!(reports, vec![(PROGRESS_INTERVAL, 1), (total, 1)]);
+    }
+
+    #[test]
+    fn test_parse_slt_listing() {
+        let listing = "Path = Languages\\English\r\nFolder = +\r\nSize = 0\r\n\r\n\
+            Path = Languages\\English\\Keyed.xml\r\nFolder = -\r\nSize = 120\r\nPacked Size = 64\r\n";
+        let entries = parse_slt_listing(listing);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].is_dir);
+        assert_eq!(entries[1].path, "Languages/English/Keyed.xml");
+        assert_eq!(entries[1].size, 120);
+        assert_eq!(entries[1].compressed_size, 64);
+        assert!(!entries[1].is_dir);
     }
 
     #[test]
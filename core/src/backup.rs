@@ -1,6 +1,7 @@
+use crate::file_lock::{is_sharing_violation, retry_while_locked};
 use chrono::Local;
 use serde::Serialize;
-use std::fs::{self, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
@@ -18,6 +19,18 @@ pub enum BackupError {
     Io(#[from] io::Error),
     #[error("백업 파일을 생성하지 못했습니다: {0}")]
     BackupCreate(String),
+    #[error("백업 파일을 생성하지 못했습니다: {0}")]
+    BackupCopy(io::Error),
+}
+
+impl BackupError {
+    /// Whether the write failed because another process holds the file open
+    pub fn is_file_locked(&self) -> bool {
+        match self {
+            BackupError::Io(err) | BackupError::BackupCopy(err) => is_sharing_violation(err),
+            BackupError::BackupCreate(_) => false,
+        }
+    }
 }
 
 pub fn backup_and_swap(target: &Path, contents: &[u8]) -> Result<BackupOutcome, BackupError> {
@@ -42,45 +55,82 @@ pub fn backup_and_swap(target: &Path, contents: &[u8]) -> Result<BackupOutcome,
             candidate.set_extension(format!("bak.{timestamp}"));
         }
 
-        fs::copy(target, &candidate).map_err(|err| BackupError::BackupCreate(err.to_string()))?;
+        retry_while_locked(|| fs::copy(target, &candidate)).map_err(BackupError::BackupCopy)?;
         Some(candidate)
     } else {
         None
     };
 
+    write_atomic(target, contents)?;
+
+    Ok(BackupOutcome {
+        backup_path,
+        temporary_path: build_temp_path(target),
+        final_path: target.to_path_buf(),
+    })
+}
+
+/// Replaces `target` with `contents` so that a crash leaves either the old
+/// file or the new one, never a truncated mix: the bytes go to a sibling
+/// temporary file that is synced to disk and then renamed over `target`.
+pub fn write_atomic(target: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic_with(target, |file| file.write_all(contents))
+}
+
+/// Like [`write_atomic`], but `write` streams into the temporary file.
+/// Nothing is renamed when `write` fails.
+pub fn write_atomic_with<E, F>(target: &Path, write: F) -> Result<(), E>
+where
+    E: From<io::Error>,
+    F: FnOnce(&mut File) -> Result<(), E>,
+{
+    if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
     let temp_path = build_temp_path(target);
-    let mut file = OpenOptions::new()
+    let written = OpenOptions::new()
         .create(true)
         .write(true)
         .truncate(true)
-        .open(&temp_path)?;
-    file.write_all(contents)?;
-    file.sync_all()?;
-    drop(file);
+        .open(&temp_path)
+        .map_err(E::from)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()?;
+            Ok(())
+        })
+        .and_then(|()| replace_with(&temp_path, target).map_err(E::from));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    written
+}
 
+fn replace_with(temp_path: &Path, target: &Path) -> io::Result<()> {
+    // A running game or Steam may hold the target open for a moment
     #[cfg(target_os = "windows")]
     {
         use std::io::ErrorKind;
-        if let Err(err) = fs::rename(&temp_path, target) {
-            if err.kind() == ErrorKind::AlreadyExists {
-                fs::remove_file(target)?;
-                fs::rename(&temp_path, target)?;
-            } else {
-                return Err(BackupError::Io(err));
+        if let Err(err) = retry_while_locked(|| fs::rename(temp_path, target)) {
+            if err.kind() != ErrorKind::AlreadyExists {
+                return Err(err);
             }
+            retry_while_locked(|| fs::remove_file(target))?;
+            fs::rename(temp_path, target)?;
         }
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        fs::rename(&temp_path, target)?;
+        retry_while_locked(|| fs::rename(temp_path, target))?;
+        // Persist the rename itself; not every filesystem supports this
+        if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let _ = File::open(parent).and_then(|dir| dir.sync_all());
+        }
     }
 
-    Ok(BackupOutcome {
-        backup_path,
-        temporary_path: temp_path,
-        final_path: target.to_path_buf(),
-    })
+    Ok(())
 }
 
 fn build_temp_path(target: &Path) -> PathBuf {
@@ -119,4 +169,26 @@ mod tests {
         file.read_to_string(&mut contents).unwrap();
         assert_eq!(contents, "translated");
     }
+
+    #[test]
+    fn failed_atomic_write_keeps_the_original() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("en_us.json");
+        fs::write(&target, b"{\"a\": \"b\"}").unwrap();
+
+        let result: io::Result<()> = write_atomic_with(&target, |file| {
+            file.write_all(b"{\"a\": ")?;
+            Err(io::Error::other("crashed mid-write"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read(&target).unwrap(), b"{\"a\": \"b\"}");
+        assert!(!build_temp_path(&target).exists());
+
+        write_atomic(&dir.path().join("nested/ko_kr.json"), b"{}").unwrap();
+        assert_eq!(
+            fs::read(dir.path().join("nested/ko_kr.json")).unwrap(),
+            b"{}"
+        );
+    }
 }
@@ -0,0 +1,255 @@
+/// Bidirectional text handling for right-to-left target languages.
+///
+/// Placeholders, tags and numbers are left-to-right runs. Inside Arabic or
+/// Hebrew text the Unicode bidi algorithm moves them around, so `{0}` can end
+/// up on the wrong side of its word and `<color>…</color>` around the wrong
+/// phrase in game. Jobs translating into an RTL language wrap every protected
+/// token in directional controls and check that paired markup still opens
+/// before it closes in logical order.
+use crate::protector::TokenClass;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// LEFT-TO-RIGHT ISOLATE
+const LRI: char = '\u{2066}';
+/// POP DIRECTIONAL ISOLATE
+const PDI: char = '\u{2069}';
+/// LEFT-TO-RIGHT MARK
+const LRM: char = '\u{200E}';
+
+/// Languages written right to left (ISO 639-1 and common 639-3 codes)
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ku", "ps", "sd", "ug", "ur", "yi",
+];
+
+/// Directional controls put around protected tokens in RTL output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BidiMarks {
+    /// LRI … PDI isolates (Unicode 6.3; most current text engines)
+    #[default]
+    Isolate,
+    /// LRM on both sides, for engines that do not know isolates
+    Mark,
+    /// Leave the tokens as they are
+    Off,
+}
+
+impl BidiMarks {
+    fn controls(self) -> Option<(char, char)> {
+        match self {
+            Self::Isolate => Some((LRI, PDI)),
+            Self::Mark => Some((LRM, LRM)),
+            Self::Off => None,
+        }
+    }
+}
+
+/// Whether `code` (e.g. `ar`, `he-IL`, `fa_IR`) names a right-to-left language
+pub fn is_rtl_language(code: &str) -> bool {
+    let base = code
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(code)
+        .trim()
+        .to_ascii_lowercase();
+    RTL_LANGUAGES.contains(&base.as_str())
+}
+
+/// Whether a token class is markup that opens and closes (`<b>…</b>`,
+/// `[color=red]…[/color]`, `{i}…{/i}`)
+pub fn is_markup(kind: TokenClass) -> bool {
+    matches!(
+        kind,
+        TokenClass::Tag
+            | TokenClass::BbCode
+            | TokenClass::RimworldColor
+            | TokenClass::RichText
+            | TokenClass::RenpyTag
+            | TokenClass::FactorioColor
+    )
+}
+
+/// Whether a token class is worth isolating; escapes and entities are not
+/// displayed as themselves
+pub fn needs_isolation(kind: TokenClass) -> bool {
+    !matches!(
+        kind,
+        TokenClass::Escape
+            | TokenClass::Entity
+            | TokenClass::EscapedBrace
+            | TokenClass::EscapedPercent
+    )
+}
+
+/// Wraps each occurrence of `tokens` in `text` with the controls of `marks`.
+/// Longer tokens win where tokens overlap, and tokens that are already
+/// wrapped are left alone, so applying this twice changes nothing.
+pub fn isolate_tokens(text: &str, tokens: &[&str], marks: BidiMarks) -> String {
+    let Some((open, close)) = marks.controls() else {
+        return text.to_string();
+    };
+    let mut sorted: Vec<&str> = tokens
+        .iter()
+        .copied()
+        .filter(|token| !token.is_empty())
+        .collect();
+    sorted.sort_by_key(|token| std::cmp::Reverse(token.len()));
+    sorted.dedup();
+
+    let mut occupied = vec![false; text.len()];
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for token in sorted {
+        for (start, _) in text.match_indices(token) {
+            let end = start + token.len();
+            if occupied[start..end].iter().any(|taken| *taken) {
+                continue;
+            }
+            occupied[start..end]
+                .iter_mut()
+                .for_each(|taken| *taken = true);
+            spans.push((start, end));
+        }
+    }
+    spans.sort_unstable();
+
+    let mut output = String::with_capacity(text.len() + spans.len() * 6);
+    let mut cursor = 0;
+    for (start, end) in spans {
+        output.push_str(&text[cursor..start]);
+        let wrapped = text[..start].ends_with(open) && text[end..].starts_with(close);
+        if !wrapped {
+            output.push(open);
+        }
+        output.push_str(&text[start..end]);
+        if !wrapped {
+            output.push(close);
+        }
+        cursor = end;
+    }
+    output.push_str(&text[cursor..]);
+    output
+}
+
+/// Markup names whose closing token comes before any opening token in the
+/// translation but not in the source, as when a model writes RTL text in
+/// visual order (`</b>word<b>`)
+pub fn misordered_markup(source: &str, translation: &str, markup: &[&str]) -> Vec<String> {
+    let in_source = unopened_closings(source, markup);
+    unopened_closings(translation, markup)
+        .difference(&in_source)
+        .cloned()
+        .collect()
+}
+
+fn unopened_closings(text: &str, markup: &[&str]) -> BTreeSet<String> {
+    let mut occurrences: Vec<(usize, &str)> = Vec::new();
+    for token in markup.iter().filter(|token| !token.is_empty()) {
+        occurrences.extend(text.match_indices(token).map(|(start, _)| (start, *token)));
+    }
+    occurrences.sort_unstable();
+    occurrences.dedup();
+
+    let mut open: HashMap<String, usize> = HashMap::new();
+    let mut unopened = BTreeSet::new();
+    for (_, token) in occurrences {
+        match markup_name(token) {
+            Some((name, false)) => *open.entry(name).or_default() += 1,
+            Some((name, true)) => match open.get_mut(&name) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => {
+                    unopened.insert(name);
+                }
+            },
+            None => {}
+        }
+    }
+    unopened
+}
+
+/// Lowercased name of a markup token and whether it closes; `None` for
+/// self-closing tags
+fn markup_name(token: &str) -> Option<(String, bool)> {
+    let inner = token
+        .strip_prefix('<')
+        .and_then(|rest| rest.strip_suffix('>'))
+        .or_else(|| {
+            token
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+        })
+        .or_else(|| {
+            token
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+        })?;
+    if inner.ends_with('/') {
+        return None;
+    }
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, inner),
+    };
+    let name: String = inner
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '=')
+        .collect::<String>()
+        .to_lowercase();
+    (!name.is_empty()).then_some((name, closing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_rtl_languages() {
+        assert!(is_rtl_language("ar"));
+        assert!(is_rtl_language("he-IL"));
+        assert!(is_rtl_language("fa_IR"));
+        assert!(!is_rtl_language("ko"));
+        assert!(!is_rtl_language("arn"));
+    }
+
+    #[test]
+    fn isolates_each_token_once() {
+        let text = "لديك {0} من <color=red>الذهب</color>";
+        let tokens = ["{0}", "<color=red>", "</color>"];
+        let isolated = isolate_tokens(text, &tokens, BidiMarks::Isolate);
+        assert_eq!(
+            isolated,
+            "لديك \u{2066}{0}\u{2069} من \u{2066}<color=red>\u{2069}الذهب\u{2066}</color>\u{2069}"
+        );
+        assert_eq!(
+            isolate_tokens(&isolated, &tokens, BidiMarks::Isolate),
+            isolated
+        );
+        assert_eq!(
+            isolate_tokens("%1$s و %s", &["%1$s", "%s"], BidiMarks::Mark),
+            "\u{200E}%1$s\u{200E} و \u{200E}%s\u{200E}"
+        );
+        assert_eq!(isolate_tokens(text, &tokens, BidiMarks::Off), text);
+    }
+
+    #[test]
+    fn reports_markup_closed_before_it_opens() {
+        let markup = ["<b>", "</b>", "[color=red]", "[/color]"];
+        let source = "Deal <b>{0}</b> [color=red]fire[/color] damage";
+        assert!(misordered_markup(
+            source,
+            "أحدث <b>{0}</b> ضرر [color=red]نار[/color]",
+            &markup
+        )
+        .is_empty());
+        assert_eq!(
+            misordered_markup(
+                source,
+                "أحدث </b>{0}<b> ضرر [color=red]نار[/color]",
+                &markup
+            ),
+            ["b"]
+        );
+        // A line that only closes a tag opened on an earlier line
+        assert!(misordered_markup("done</b>", "تم</b>", &markup).is_empty());
+    }
+}
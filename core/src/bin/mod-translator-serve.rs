@@ -0,0 +1,73 @@
+//! Runs the localhost control server.
+//!
+//! `mod-translator-serve [--addr 127.0.0.1:7420] [--token <token>]`; the
+//! token can also come from `MOD_TRANSLATOR_TOKEN`. Without one, a token is
+//! generated and printed.
+//!
+//! Build it without the default `tauri` feature so it does not link the
+//! webview libraries: `cargo build -p mod_translator_core
+//! --no-default-features --features serve --bin mod-translator-serve`.
+use mod_translator_core::server::{serve_listener, ServeOptions};
+use std::process::ExitCode;
+
+fn parse_options() -> Result<ServeOptions, String> {
+    let mut options = ServeOptions {
+        token: std::env::var("MOD_TRANSLATOR_TOKEN").ok(),
+        ..ServeOptions::default()
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{arg} 뒤에 값이 필요합니다."))
+        };
+        match arg.as_str() {
+            "--addr" => {
+                let addr = value()?;
+                options.addr = addr
+                    .parse()
+                    .map_err(|e| format!("주소가 올바르지 않습니다 ({addr}): {e}"))?;
+            }
+            "--token" => options.token = Some(value()?),
+            _ => return Err(format!("알 수 없는 인자입니다: {arg}")),
+        }
+    }
+    Ok(options)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let options = match parse_options() {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("사용법: mod-translator-serve [--addr 127.0.0.1:7420] [--token <토큰>]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let listener = match options.bind().await {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let token = options.access_token();
+    if let Ok(addr) = listener.local_addr() {
+        eprintln!("http://{addr} 에서 요청을 기다립니다.");
+    }
+    if options
+        .token
+        .as_deref()
+        .is_none_or(|given| given.trim().is_empty())
+    {
+        eprintln!("접근 토큰: {token}");
+    }
+    match serve_listener(listener, token).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -282,6 +282,8 @@ mod codex_spec_tests {
             
             // Game-specific
             "__1__", "__ENTITY__iron-ore__", "[img=item/iron]",
+            "__plural_for_parameter__1__{1=item|rest=items}__",
+            "[Root.GetName]", "$VARIABLE$", "£energy£",
             
             // ICU (simplified - full ICU has nested braces)
             "{count, plural, one {# item} other {# items}}",
@@ -1,10 +1,15 @@
 /// Configuration for the translation system
+use crate::ai::prompt::{PromptConfig, PromptTemplate};
+use crate::ai::{rate_limit::RateLimitConfig, ModelParams, ProviderId};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ValidatorOptions {
     pub enable_autofix: bool,
     pub retry_on_fail: bool,
@@ -31,6 +36,7 @@ impl Default for ValidatorOptions {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct UiOptions {
     pub show_failed_line: bool,
     pub allow_clipboard_copy: bool,
@@ -50,6 +56,7 @@ impl Default for UiOptions {
 /// 번역 제외 패턴 설정
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct IgnoreOptions {
     /// 사용자 정의 무시 패턴 (gitignore 형식)
     #[serde(default)]
@@ -197,12 +204,126 @@ pub fn matches_ignore_pattern(path: &str, pattern: &str) -> bool {
     path_lower.contains(&pattern_lower)
 }
 
+/// 경로가 글롭과 일치하는지 확인 (대소문자 무시)
+///
+/// `*`는 폴더 구분자를 넘지 않는 임의의 문자열, `**`는 여러 폴더, `?`는 한 글자입니다.
+/// 구분자가 없는 글롭(`README.md`, `CHANGELOG*`)은 어느 폴더에서든 파일 이름과 비교하고,
+/// 구분자가 있는 글롭(`lang/**/*.json`)은 모드 루트 기준 경로 전체와 비교합니다.
+/// `docs/`처럼 `/`로 끝나는 글롭은 그 이름의 폴더 아래 모든 파일입니다.
+pub fn matches_glob(path: &str, glob: &str) -> bool {
+    let path: Vec<char> = path
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_lowercase()
+        .chars()
+        .collect();
+    let glob = glob.trim().replace('\\', "/").to_lowercase();
+    let glob = glob.trim_start_matches("./").trim_start_matches('/');
+    if glob.is_empty() {
+        return false;
+    }
+
+    let pattern = match glob.strip_suffix('/') {
+        Some(dir) if !dir.contains('/') => format!("**/{dir}/**"),
+        Some(dir) => format!("{dir}/**"),
+        None if !glob.contains('/') => format!("**/{glob}"),
+        None => glob.to_string(),
+    };
+    let pattern: Vec<char> = pattern.chars().collect();
+    glob_match(&pattern, &path)
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no folder at all
+            if let ['/', after @ ..] = rest {
+                if glob_match(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|start| glob_match(rest, &path[start..]))
+        }
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&end| end == 0 || path[end - 1] != '/')
+            .any(|start| glob_match(rest, &path[start..])),
+        ['?', rest @ ..] => match path {
+            [first, tail @ ..] if *first != '/' => glob_match(rest, tail),
+            _ => false,
+        },
+        [expected, rest @ ..] => match path {
+            [first, tail @ ..] if first == expected => glob_match(rest, tail),
+            _ => false,
+        },
+    }
+}
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+pub const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 3;
+/// Upper bound of `maxRetryAttempts`, so a typo cannot retry a segment forever
+pub const MAX_RETRY_ATTEMPTS_LIMIT: u32 = 10;
+
+/// 요청 시간 제한과 재시도 횟수 (없으면 기본값)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct RequestOptions {
+    /// 연결 시간 제한 (초, 기본값 10)
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// 요청 전체 시간 제한 (초, 기본값 120)
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
+    /// 세그먼트당 최대 시도 횟수 (첫 시도 포함, 기본값 3, 최대 10)
+    #[serde(default)]
+    pub max_retry_attempts: Option<u32>,
+}
+
+impl RequestOptions {
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.connect_timeout_secs
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS),
+        )
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.request_timeout_secs
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS),
+        )
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_retry_attempts
+            .unwrap_or(DEFAULT_MAX_RETRY_ATTEMPTS)
+            .clamp(1, MAX_RETRY_ATTEMPTS_LIMIT)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct TranslatorConfig {
     pub validator: ValidatorOptions,
     pub ui: UiOptions,
     #[serde(default)]
     pub ignore: IgnoreOptions,
+    /// 번역기별 모델 파라미터 (temperature, top_p 등)
+    #[serde(default)]
+    pub model_params: HashMap<ProviderId, ModelParams>,
+    /// 번역 프롬프트 템플릿 (기본값은 내장 프롬프트)
+    #[serde(default)]
+    pub prompts: PromptConfig,
+    /// 번역기별 분당 요청/토큰 수 제한 (없으면 제한 없음)
+    #[serde(default)]
+    pub rate_limits: HashMap<ProviderId, RateLimitConfig>,
+    /// 요청 시간 제한과 재시도 횟수
+    #[serde(default)]
+    pub requests: RequestOptions,
 }
 
 impl Default for TranslatorConfig {
@@ -211,11 +332,30 @@ impl Default for TranslatorConfig {
             validator: ValidatorOptions::default(),
             ui: UiOptions::default(),
             ignore: IgnoreOptions::default(),
+            model_params: HashMap::new(),
+            prompts: PromptConfig::default(),
+            rate_limits: HashMap::new(),
+            requests: RequestOptions::default(),
         }
     }
 }
 
 impl TranslatorConfig {
+    /// 지정한 번역기의 모델 파라미터 (없으면 기본값)
+    pub fn model_params_for(&self, provider: ProviderId) -> ModelParams {
+        self.model_params.get(&provider).cloned().unwrap_or_default()
+    }
+
+    /// 지정한 번역기의 프롬프트 템플릿 (번역기별 템플릿 > 공통 템플릿)
+    pub fn prompt_template_for(&self, provider: ProviderId) -> PromptTemplate {
+        self.prompts.template_for(provider)
+    }
+
+    /// 지정한 번역기의 분당 요청/토큰 수 제한 (없으면 제한 없음)
+    pub fn rate_limit_for(&self, provider: ProviderId) -> RateLimitConfig {
+        self.rate_limits.get(&provider).copied().unwrap_or_default()
+    }
+
     /// Load configuration from YAML file
     pub fn from_yaml_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
         let content = fs::read_to_string(path)
@@ -259,6 +399,23 @@ mod tests {
         assert!(config.ui.show_failed_line);
     }
 
+    #[test]
+    fn test_request_options_defaults_and_limits() {
+        let options = RequestOptions::default();
+        assert_eq!(options.connect_timeout(), Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS));
+        assert_eq!(options.request_timeout(), Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS));
+        assert_eq!(options.max_attempts(), DEFAULT_MAX_RETRY_ATTEMPTS);
+
+        let options = RequestOptions {
+            connect_timeout_secs: Some(0),
+            request_timeout_secs: Some(300),
+            max_retry_attempts: Some(99),
+        };
+        assert_eq!(options.connect_timeout(), Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS));
+        assert_eq!(options.request_timeout(), Duration::from_secs(300));
+        assert_eq!(options.max_attempts(), MAX_RETRY_ATTEMPTS_LIMIT);
+    }
+
     #[test]
     fn test_json_serialization() {
         let config = TranslatorConfig::default();
@@ -309,6 +466,30 @@ README*
         assert!(matches_ignore_pattern("Readme.md", "README*"));
     }
     
+    #[test]
+    fn test_matches_glob() {
+        // Globs without a separator match the file name in any folder
+        assert!(matches_glob("README.md", "readme.md"));
+        assert!(matches_glob("About/Changelog.txt", "CHANGELOG*"));
+        assert!(!matches_glob("About/OldChangelog.txt", "CHANGELOG*"));
+
+        // Globs with a separator are anchored at the mod root
+        assert!(matches_glob("lang/en_us.json", "lang/*.json"));
+        assert!(!matches_glob("lang/sub/en_us.json", "lang/*.json"));
+        assert!(matches_glob("lang/sub/en_us.json", "lang/**/*.json"));
+        assert!(matches_glob(r"lang\en_us.json", "lang/**/*.json"));
+        assert!(!matches_glob("assets/lang/en_us.json", "lang/**"));
+        assert!(matches_glob("assets/lang/en_us.json", "**/lang/*.json"));
+        assert!(matches_glob("lang/a.cfg", "lang/?.cfg"));
+
+        // Trailing slash: everything under a folder of that name
+        assert!(matches_glob("docs/guide.md", "docs/"));
+        assert!(matches_glob("Mod/Docs/guide.md", "docs/"));
+        assert!(!matches_glob("documents/guide.md", "docs/"));
+        assert!(matches_glob("Mod/docs/guide.md", "Mod/docs/"));
+        assert!(!matches_glob("docs/guide.md", ""));
+    }
+
     #[test]
     fn test_default_ignore_patterns() {
         let patterns = default_ignore_patterns();
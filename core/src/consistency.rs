@@ -0,0 +1,318 @@
+/// Whole-job consistency pass.
+///
+/// Segments are translated one at a time, so a long mod can end up with the
+/// same source text translated differently in different files, or with a
+/// glossary term rendered three different ways. After a job translates every
+/// segment this pass groups the translations by source value and checks the
+/// glossary terms, then reports the differences or, in harmonize mode,
+/// rewrites repeated sources to their most common translation.
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+// `<Key attr="…">value</Key>`
+static XML_VALUE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^<[A-Za-z_][^>]*>(.*)</[^>]+>$").expect("valid xml value regex"));
+// `"key": "value",`
+static JSON_VALUE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^"(?:[^"\\]|\\.)+"\s*:\s*"((?:[^"\\]|\\.)*)",?$"#)
+        .expect("valid json value regex")
+});
+// `key=value` and `key: value`
+static ASSIGNMENT_VALUE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9_][\w.\-\[\]]*\s*[=:]\s*(.*)$").expect("valid assignment value regex")
+});
+
+/// Translations of one source with the locations using each
+type Variants<'a> = Vec<(&'a str, Vec<String>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsistencyMode {
+    /// Only report inconsistencies
+    Report,
+    /// Rewrite repeated sources to their most common translation, then report
+    Harmonize,
+}
+
+/// A translated segment of the job
+#[derive(Debug, Clone, Copy)]
+pub struct TranslatedEntry<'a> {
+    pub file: &'a str,
+    pub line_number: usize,
+    /// Trimmed source line
+    pub source: &'a str,
+    /// Trimmed translated line
+    pub translation: &'a str,
+    /// Glossary of the segment's mod (source term -> target term)
+    pub glossary: &'a HashMap<String, String>,
+}
+
+impl TranslatedEntry<'_> {
+    fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line_number)
+    }
+}
+
+/// One way a repeated source was translated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationVariant {
+    pub translation: String,
+    /// `file:line`
+    pub locations: Vec<String>,
+}
+
+/// A source value translated more than one way
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceVariants {
+    pub source: String,
+    /// Most common translation first
+    pub variants: Vec<TranslationVariant>,
+}
+
+impl SourceVariants {
+    /// Translation used when harmonizing: the most common one
+    pub fn preferred(&self) -> &str {
+        &self.variants[0].translation
+    }
+}
+
+/// A glossary term whose target is missing from translations of lines that
+/// contain it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TermMismatch {
+    pub term: String,
+    pub expected: String,
+    /// `file:line`
+    pub locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyReport {
+    pub sources: Vec<SourceVariants>,
+    pub terms: Vec<TermMismatch>,
+    /// Segments rewritten in harmonize mode
+    pub harmonized: usize,
+}
+
+impl ConsistencyReport {
+    pub fn is_clean(&self) -> bool {
+        self.sources.is_empty() && self.terms.is_empty()
+    }
+}
+
+/// Byte range of the translatable value in a trimmed line: the element text
+/// of an XML line, the string of a JSON pair or the right side of an
+/// assignment. Other lines are all value.
+pub fn entry_value_range(line: &str) -> Range<usize> {
+    [&*XML_VALUE_RE, &*JSON_VALUE_RE, &*ASSIGNMENT_VALUE_RE]
+        .iter()
+        .find_map(|re| re.captures(line))
+        .and_then(|captures| captures.get(1))
+        .map(|value| value.range())
+        .unwrap_or(0..line.len())
+}
+
+pub fn entry_value(line: &str) -> &str {
+    line[entry_value_range(line)].trim()
+}
+
+/// Repeated sources with more than one translation, and glossary terms whose
+/// targets are missing from the translations of lines containing them
+pub fn check_consistency(entries: &[TranslatedEntry]) -> ConsistencyReport {
+    // source -> translation -> locations, in first-seen order
+    let mut by_source: Vec<(&str, Variants)> = Vec::new();
+    let mut source_index: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        let source = entry_value(entry.source);
+        if !source.chars().any(char::is_alphabetic) {
+            continue;
+        }
+        let translation = entry_value(entry.translation);
+        let index = *source_index.entry(source).or_insert_with(|| {
+            by_source.push((source, Vec::new()));
+            by_source.len() - 1
+        });
+        let variants = &mut by_source[index].1;
+        match variants
+            .iter_mut()
+            .find(|(existing, _)| *existing == translation)
+        {
+            Some((_, locations)) => locations.push(entry.location()),
+            None => variants.push((translation, vec![entry.location()])),
+        }
+    }
+
+    let sources = by_source
+        .into_iter()
+        .filter(|(_, variants)| variants.len() > 1)
+        .map(|(source, mut variants)| {
+            // Stable sort keeps the first-seen translation ahead on ties
+            variants.sort_by_key(|(_, locations)| Reverse(locations.len()));
+            SourceVariants {
+                source: source.to_string(),
+                variants: variants
+                    .into_iter()
+                    .map(|(translation, locations)| TranslationVariant {
+                        translation: translation.to_string(),
+                        locations,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let mut terms: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for entry in entries {
+        let source = entry_value(entry.source);
+        let translation = entry_value(entry.translation).to_lowercase();
+        for (term, expected) in entry.glossary {
+            let (term, expected) = (term.trim(), expected.trim());
+            if term.is_empty() || expected.is_empty() || !contains_term(source, term) {
+                continue;
+            }
+            if !translation.contains(&expected.to_lowercase()) {
+                terms
+                    .entry((term.to_string(), expected.to_string()))
+                    .or_default()
+                    .push(entry.location());
+            }
+        }
+    }
+
+    ConsistencyReport {
+        sources,
+        terms: terms
+            .into_iter()
+            .map(|((term, expected), locations)| TermMismatch {
+                term,
+                expected,
+                locations,
+            })
+            .collect(),
+        harmonized: 0,
+    }
+}
+
+/// New translated lines for entries whose repeated source was translated
+/// differently from the preferred translation, as `(entry index, line)`
+pub fn harmonize(entries: &[TranslatedEntry], report: &ConsistencyReport) -> Vec<(usize, String)> {
+    let preferred: HashMap<&str, &str> = report
+        .sources
+        .iter()
+        .map(|variants| (variants.source.as_str(), variants.preferred()))
+        .collect();
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let target = preferred.get(entry_value(entry.source))?;
+            if entry_value(entry.translation) == *target {
+                return None;
+            }
+            let mut line = entry.translation.to_string();
+            line.replace_range(entry_value_range(entry.translation), target);
+            Some((index, line))
+        })
+        .collect()
+}
+
+/// Whether `text` contains `term` as a whole word, ignoring case
+fn contains_term(text: &str, term: &str) -> bool {
+    let text = text.to_lowercase();
+    let term = term.to_lowercase();
+    text.match_indices(&term).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + term.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry<'a>(
+        line_number: usize,
+        source: &'a str,
+        translation: &'a str,
+        glossary: &'a HashMap<String, String>,
+    ) -> TranslatedEntry<'a> {
+        TranslatedEntry {
+            file: "Keyed/Items.xml",
+            line_number,
+            source,
+            translation,
+            glossary,
+        }
+    }
+
+    #[test]
+    fn values_are_found_in_common_line_shapes() {
+        assert_eq!(entry_value("<Sword.label>Sword</Sword.label>"), "Sword");
+        assert_eq!(entry_value(r#""item.sword": "Sword","#), "Sword");
+        assert_eq!(entry_value("item.sword = Sword"), "Sword");
+        assert_eq!(entry_value("Just a sentence."), "Just a sentence.");
+    }
+
+    #[test]
+    fn repeated_sources_are_reported_and_harmonized() {
+        let glossary = HashMap::new();
+        let entries = [
+            entry(1, "<A>Plasteel</A>", "<A>플라스틸</A>", &glossary),
+            entry(2, "<B>Plasteel</B>", "<B>플라스강</B>", &glossary),
+            entry(3, r#""c": "Plasteel","#, r#""c": "플라스틸","#, &glossary),
+            entry(4, "<D>Steel</D>", "<D>강철</D>", &glossary),
+        ];
+
+        let report = check_consistency(&entries);
+        assert_eq!(report.sources.len(), 1);
+        assert_eq!(report.sources[0].source, "Plasteel");
+        assert_eq!(report.sources[0].preferred(), "플라스틸");
+        assert_eq!(
+            report.sources[0].variants[1].locations,
+            vec!["Keyed/Items.xml:2"]
+        );
+
+        let changes = harmonize(&entries, &report);
+        assert_eq!(changes, vec![(1, "<B>플라스틸</B>".to_string())]);
+    }
+
+    #[test]
+    fn glossary_terms_missing_from_translations_are_reported() {
+        let glossary = HashMap::from([("Component".to_string(), "부품".to_string())]);
+        let entries = [
+            entry(
+                1,
+                "<A>Needs a component.</A>",
+                "<A>부품이 필요합니다.</A>",
+                &glossary,
+            ),
+            entry(
+                2,
+                "<B>Component crate</B>",
+                "<B>컴포넌트 상자</B>",
+                &glossary,
+            ),
+            entry(3, "<C>Components</C>", "<C>구성품</C>", &glossary),
+        ];
+
+        let report = check_consistency(&entries);
+        assert!(report.sources.is_empty());
+        assert_eq!(
+            report.terms,
+            vec![TermMismatch {
+                term: "Component".into(),
+                expected: "부품".into(),
+                locations: vec!["Keyed/Items.xml:2".into()],
+            }]
+        );
+    }
+}
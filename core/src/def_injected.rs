@@ -0,0 +1,467 @@
+/// RimWorld DefInjected skeleton generation.
+///
+/// RimWorld translates Def text through `Languages/<Language>/DefInjected/`
+/// files keyed by `defName` and field path. Many mods ship only `Defs/`, so
+/// there is nothing for the translation job to pick up. This scans each
+/// `Defs/` folder without a sibling `Languages/` folder, extracts `label`,
+/// `description` and `rulesStrings` fields, and writes English DefInjected
+/// files plus an empty Keyed file. The job then translates them like any
+/// other `Languages/English/` file.
+use crate::access_mode::ensure_writable;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Language the generated skeletons are written in
+const SOURCE_LANGUAGE_FOLDER: &str = "English";
+const KEYED_SKELETON_NAME: &str = "Keyed.xml";
+
+/// Def fields holding a single translatable string
+const TEXT_FIELDS: [&str; 2] = ["label", "description"];
+/// Def fields holding a list of translatable `<li>` strings
+const LIST_FIELDS: [&str; 1] = ["rulesStrings"];
+
+// Comments, processing instructions, CDATA, doctype, and start/end tags
+static TOKEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?s)<!--.*?-->|<\?.*?\?>|<!\[CDATA\[(.*?)\]\]>|<!DOCTYPE[^>]*>|<(/?)([A-Za-z_][\w.\-:]*)((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>"#,
+    )
+    .expect("valid xml token regex")
+});
+
+static ABSTRACT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\bAbstract\s*=\s*["']true["']"#).expect("valid abstract attr regex")
+});
+
+/// Result of generating skeletons for one mod
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefInjectedReport {
+    /// Files written, relative to the mod root
+    pub created: Vec<String>,
+    /// Injection entries across all written files
+    pub entries: usize,
+    /// `Languages` folders that already existed and were left alone
+    pub skipped: Vec<String>,
+    pub errors: Vec<DefInjectedFileError>,
+}
+
+/// A Defs file that could not be read or a skeleton that could not be written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefInjectedFileError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Value of one DefInjected entry, kept as raw XML text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InjectionValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+/// One DefInjected entry, e.g. `Steel.label`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injection {
+    /// Def type the entry belongs to, e.g. `ThingDef`
+    pub def_type: String,
+    /// `defName` followed by the field path
+    pub key: String,
+    pub value: InjectionValue,
+}
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: String,
+    text: String,
+    children: Vec<Element>,
+}
+
+/// Builds a lenient element tree: unbalanced end tags close the innermost
+/// element, and unclosed elements are closed at the end of the input.
+fn parse_elements(xml: &str) -> Vec<Element> {
+    let mut stack = vec![Element::default()];
+    let mut last = 0;
+    for cap in TOKEN_RE.captures_iter(xml) {
+        let whole = cap.get(0).expect("whole match");
+        if let Some(current) = stack.last_mut() {
+            current.text.push_str(&xml[last..whole.start()]);
+        }
+        last = whole.end();
+
+        if let Some(cdata) = cap.get(1) {
+            if let Some(current) = stack.last_mut() {
+                current.text.push_str(&escape_text(cdata.as_str()));
+            }
+            continue;
+        }
+        let Some(name) = cap.get(3) else {
+            continue;
+        };
+        if &cap[2] == "/" {
+            if stack.len() > 1 {
+                close_innermost(&mut stack);
+            }
+            continue;
+        }
+        let element = Element {
+            name: name.as_str().to_string(),
+            attributes: cap[4].to_string(),
+            ..Element::default()
+        };
+        if &cap[5] == "/" {
+            if let Some(current) = stack.last_mut() {
+                current.children.push(element);
+            }
+        } else {
+            stack.push(element);
+        }
+    }
+    while stack.len() > 1 {
+        close_innermost(&mut stack);
+    }
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn close_innermost(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(element);
+        }
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Extracts the DefInjected entries of one Defs XML file. Abstract defs and
+/// defs without a `defName` are skipped; list items are addressed by index.
+pub fn collect_injections(defs_xml: &str) -> Vec<Injection> {
+    let mut injections = Vec::new();
+    for root in parse_elements(defs_xml) {
+        for def in &root.children {
+            if ABSTRACT_RE.is_match(&def.attributes) {
+                continue;
+            }
+            let def_name = def
+                .children
+                .iter()
+                .find(|child| child.name == "defName")
+                .map(|child| child.text.trim())
+                .unwrap_or_default();
+            if def_name.is_empty() {
+                continue;
+            }
+            collect_fields(def, &def.name, def_name, &mut injections);
+        }
+    }
+    injections
+}
+
+fn collect_fields(element: &Element, def_type: &str, path: &str, out: &mut Vec<Injection>) {
+    let mut list_index = 0;
+    for child in &element.children {
+        let segment = if child.name == "li" {
+            list_index += 1;
+            (list_index - 1).to_string()
+        } else {
+            child.name.clone()
+        };
+        let key = format!("{}.{}", path, segment);
+
+        let value = if LIST_FIELDS.contains(&child.name.as_str()) {
+            let items: Vec<String> = child
+                .children
+                .iter()
+                .filter(|item| item.name == "li")
+                .map(|item| item.text.trim().to_string())
+                .filter(|text| !text.is_empty())
+                .collect();
+            (!items.is_empty()).then_some(InjectionValue::List(items))
+        } else if TEXT_FIELDS.contains(&child.name.as_str()) && child.children.is_empty() {
+            let text = child.text.trim();
+            (!text.is_empty()).then(|| InjectionValue::Text(text.to_string()))
+        } else {
+            collect_fields(child, def_type, &key, out);
+            None
+        };
+
+        if let Some(value) = value {
+            out.push(Injection {
+                def_type: def_type.to_string(),
+                key,
+                value,
+            });
+        }
+    }
+}
+
+/// `LanguageData` document for a set of entries
+fn render_language_data(injections: &[Injection]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<LanguageData>\n");
+    for injection in injections {
+        match &injection.value {
+            InjectionValue::Text(text) => {
+                xml.push_str(&format!("  <{0}>{1}</{0}>\n", injection.key, text));
+            }
+            InjectionValue::List(items) => {
+                xml.push_str(&format!("  <{}>\n", injection.key));
+                for item in items {
+                    xml.push_str(&format!("    <li>{}</li>\n", item));
+                }
+                xml.push_str(&format!("  </{}>\n", injection.key));
+            }
+        }
+    }
+    xml.push_str("</LanguageData>\n");
+    xml
+}
+
+const KEYED_SKELETON: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<LanguageData>\n  <!-- Keyed strings used by the mod's code go here -->\n</LanguageData>\n";
+
+/// `Defs` folders at the mod root and one level down (`1.5/Defs`, `Common/Defs`)
+fn defs_folders(mod_root: &Path) -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+    let root_defs = mod_root.join("Defs");
+    if root_defs.is_dir() {
+        folders.push(root_defs);
+    }
+    if let Ok(entries) = fs::read_dir(mod_root) {
+        let mut nested: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path().join("Defs"))
+            .filter(|path| path.is_dir())
+            .collect();
+        nested.sort();
+        folders.extend(nested);
+    }
+    folders
+}
+
+fn xml_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            xml_files(&path, out);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+        {
+            out.push(path);
+        }
+    }
+}
+
+fn relative_to(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| path.to_string_lossy().to_string())
+}
+
+/// Writes English DefInjected and Keyed skeletons next to every `Defs`
+/// folder of the mod that has no `Languages` folder yet. Entries from Defs
+/// files with the same name are merged into one DefInjected file per type.
+pub fn generate_skeletons(mod_root: &Path) -> DefInjectedReport {
+    let mut report = DefInjectedReport::default();
+    for defs_dir in defs_folders(mod_root) {
+        let base = defs_dir.parent().unwrap_or(mod_root);
+        let languages = base.join("Languages");
+        if languages.exists() {
+            report.skipped.push(relative_to(mod_root, &languages));
+            continue;
+        }
+
+        let mut sources = Vec::new();
+        xml_files(&defs_dir, &mut sources);
+        let mut files: BTreeMap<(String, String), Vec<Injection>> = BTreeMap::new();
+        for source in sources {
+            let contents = match fs::read_to_string(&source) {
+                Ok(contents) => contents,
+                Err(error) => {
+                    report.errors.push(DefInjectedFileError {
+                        path: relative_to(mod_root, &source),
+                        message: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let file_name = source
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            for injection in collect_injections(contents.trim_start_matches('\u{feff}')) {
+                files
+                    .entry((injection.def_type.clone(), file_name.clone()))
+                    .or_default()
+                    .push(injection);
+            }
+        }
+        if files.is_empty() {
+            continue;
+        }
+
+        let language_dir = languages.join(SOURCE_LANGUAGE_FOLDER);
+        let mut outputs: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|((def_type, file_name), injections)| {
+                report.entries += injections.len();
+                let path = language_dir
+                    .join("DefInjected")
+                    .join(def_type)
+                    .join(file_name);
+                (path, render_language_data(injections))
+            })
+            .collect();
+        outputs.push((
+            language_dir.join("Keyed").join(KEYED_SKELETON_NAME),
+            KEYED_SKELETON.to_string(),
+        ));
+
+        for (path, contents) in outputs {
+            let written = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&path, contents));
+            match written {
+                Ok(()) => report.created.push(relative_to(mod_root, &path)),
+                Err(error) => report.errors.push(DefInjectedFileError {
+                    path: relative_to(mod_root, &path),
+                    message: error.to_string(),
+                }),
+            }
+        }
+    }
+    report
+}
+
+/// Tauri command to generate DefInjected and Keyed skeletons for a RimWorld
+/// mod that ships only Defs
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn generate_def_injected(modDirectory: String) -> Result<DefInjectedReport, String> {
+    ensure_writable("DefInjected 생성")?;
+    let root = PathBuf::from(&modDirectory);
+    if !root.is_dir() {
+        return Err("모드 디렉터리를 찾을 수 없습니다.".into());
+    }
+    if defs_folders(&root).is_empty() {
+        return Err(format!("Defs 폴더를 찾을 수 없습니다: {}", modDirectory));
+    }
+    Ok(generate_skeletons(&root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Defs>
+  <!-- <ThingDef><defName>Commented</defName><label>no</label></ThingDef> -->
+  <ThingDef Name="BaseMetal" Abstract="True">
+    <label>ignored</label>
+  </ThingDef>
+  <ThingDef ParentName="BaseMetal">
+    <defName>Steel</defName>
+    <label>steel</label>
+    <description>A metal &amp; alloy.</description>
+    <comps>
+      <li Class="CompProperties_Power">
+        <label>power</label>
+      </li>
+      <li><description><![CDATA[<b>bold</b>]]></description></li>
+    </comps>
+    <graphicData><texPath>Things/Steel</texPath></graphicData>
+  </ThingDef>
+  <RulePackDef>
+    <defName>NamerSteel</defName>
+    <rulePack>
+      <rulesStrings>
+        <li>r_name->[adj] steel</li>
+        <li>adj->shiny</li>
+      </rulesStrings>
+    </rulePack>
+  </RulePackDef>
+</Defs>"#;
+
+    #[test]
+    fn collects_labels_descriptions_and_rule_strings() {
+        let injections = collect_injections(DEFS);
+        let keys: Vec<(&str, &str)> = injections
+            .iter()
+            .map(|i| (i.def_type.as_str(), i.key.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("ThingDef", "Steel.label"),
+                ("ThingDef", "Steel.description"),
+                ("ThingDef", "Steel.comps.0.label"),
+                ("ThingDef", "Steel.comps.1.description"),
+                ("RulePackDef", "NamerSteel.rulePack.rulesStrings"),
+            ]
+        );
+        assert_eq!(
+            injections[1].value,
+            InjectionValue::Text("A metal &amp; alloy.".to_string())
+        );
+        assert_eq!(
+            injections[3].value,
+            InjectionValue::Text("&lt;b&gt;bold&lt;/b&gt;".to_string())
+        );
+        assert_eq!(
+            injections[4].value,
+            InjectionValue::List(vec![
+                "r_name->[adj] steel".to_string(),
+                "adj->shiny".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn writes_skeletons_only_where_languages_are_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("1.5/Defs/Items")).unwrap();
+        fs::write(root.join("1.5/Defs/Items/Metals.xml"), DEFS).unwrap();
+        fs::create_dir_all(root.join("Defs")).unwrap();
+        fs::write(root.join("Defs/Old.xml"), DEFS).unwrap();
+        fs::create_dir_all(root.join("Languages/English/Keyed")).unwrap();
+
+        let report = generate_skeletons(root);
+        assert_eq!(report.skipped, vec!["Languages"]);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.entries, 5);
+        assert_eq!(
+            report.created,
+            vec![
+                "1.5/Languages/English/DefInjected/RulePackDef/Metals.xml",
+                "1.5/Languages/English/DefInjected/ThingDef/Metals.xml",
+                "1.5/Languages/English/Keyed/Keyed.xml",
+            ]
+        );
+
+        let things =
+            fs::read_to_string(root.join("1.5/Languages/English/DefInjected/ThingDef/Metals.xml"))
+                .unwrap();
+        assert!(things.contains("  <Steel.label>steel</Steel.label>\n"));
+        let rules = fs::read_to_string(
+            root.join("1.5/Languages/English/DefInjected/RulePackDef/Metals.xml"),
+        )
+        .unwrap();
+        assert!(rules
+            .contains("  <NamerSteel.rulePack.rulesStrings>\n    <li>r_name->[adj] steel</li>\n"));
+        assert!(!root.join("Languages/English/DefInjected").exists());
+    }
+}
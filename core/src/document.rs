@@ -0,0 +1,332 @@
+/// Document mode for extremely long single segments (books, lore dumps).
+///
+/// Instead of sending one huge prompt that providers silently truncate, the
+/// text is split on paragraph boundaries into bounded chunks. Each chunk keeps
+/// the exact separator that followed it so the translated document can be
+/// rejoined with the original layout, and the rejoined output is checked for
+/// the same paragraph structure as the source. Cuts never fall inside a
+/// protected token, so every chunk can be masked and restored on its own.
+use thiserror::Error;
+
+/// Segments longer than this (in characters) are translated in document mode.
+pub const DOCUMENT_MODE_THRESHOLD_CHARS: usize = 4_000;
+
+/// Upper bound for a single chunk sent to a provider.
+pub const DOCUMENT_CHUNK_MAX_CHARS: usize = 2_000;
+
+/// Amount of preceding source text sent along with a chunk as context.
+pub const DOCUMENT_OVERLAP_CHARS: usize = 300;
+
+/// Paragraph separators in priority order. Escaped forms are what most mod
+/// formats (XML values, JSON strings, .properties) store on a single line.
+const PARAGRAPH_SEPARATORS: &[&str] = &["\r\n\r\n", "\n\n", "\\n\\n", "\r\n", "\n", "\\n"];
+
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?', '。', '！', '？'];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DocumentError {
+    #[error("paragraph structure changed: expected {expected} line breaks, found {actual}")]
+    LineBreakMismatch { expected: usize, actual: usize },
+}
+
+/// A piece of a long document. `prefix` and `suffix` hold whitespace and
+/// separators that must be preserved verbatim around the translated text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentChunk {
+    pub prefix: String,
+    pub text: String,
+    pub suffix: String,
+}
+
+/// Returns true when a segment should be translated in document mode.
+pub fn needs_document_mode(text: &str) -> bool {
+    text.chars().count() > DOCUMENT_MODE_THRESHOLD_CHARS
+}
+
+/// Splits `text` into chunks of at most `max_chars` characters, preferring
+/// paragraph boundaries, then sentence boundaries, then whitespace.
+pub fn split_document(text: &str, max_chars: usize) -> Vec<DocumentChunk> {
+    split_document_protected(text, max_chars, &[])
+}
+
+/// Like [`split_document`], without cutting inside the `protected` byte
+/// spans of `text`. A chunk only exceeds `max_chars` when a single token is
+/// longer than the room left for it.
+pub fn split_document_protected(
+    text: &str,
+    max_chars: usize,
+    protected: &[(usize, usize)],
+) -> Vec<DocumentChunk> {
+    let max_chars = max_chars.max(1);
+    let mut pieces: Vec<(String, String)> = Vec::new();
+    for (offset, body, separator) in split_paragraphs(text, protected) {
+        if body.chars().count() <= max_chars {
+            pieces.push((body.to_string(), separator.to_string()));
+            continue;
+        }
+        let parts = split_oversized(body, offset, max_chars, protected);
+        let last = parts.len().saturating_sub(1);
+        for (index, part) in parts.into_iter().enumerate() {
+            let separator = if index == last { separator } else { "" };
+            pieces.push((part.to_string(), separator.to_string()));
+        }
+    }
+
+    // Greedily merge neighbouring pieces while they fit in one chunk.
+    let mut merged: Vec<(String, String)> = Vec::new();
+    for (body, separator) in pieces {
+        if let Some((last_body, last_separator)) = merged.last_mut() {
+            let combined = last_body.chars().count()
+                + last_separator.chars().count()
+                + body.chars().count();
+            if combined <= max_chars {
+                last_body.push_str(last_separator);
+                last_body.push_str(&body);
+                *last_separator = separator;
+                continue;
+            }
+        }
+        merged.push((body, separator));
+    }
+
+    merged
+        .into_iter()
+        .map(|(body, separator)| {
+            let trimmed_start = body.trim_start();
+            let prefix = body[..body.len() - trimmed_start.len()].to_string();
+            let trimmed = trimmed_start.trim_end();
+            let trailing = &trimmed_start[trimmed.len()..];
+            DocumentChunk {
+                prefix,
+                text: trimmed.to_string(),
+                suffix: format!("{trailing}{separator}"),
+            }
+        })
+        .collect()
+}
+
+/// Source text immediately preceding chunk `index`, used as overlap context.
+pub fn overlap_context(chunks: &[DocumentChunk], index: usize, max_chars: usize) -> Option<String> {
+    if index == 0 || max_chars == 0 {
+        return None;
+    }
+    let previous = &chunks.get(index - 1)?.text;
+    let total = previous.chars().count();
+    let tail: String = previous.chars().skip(total.saturating_sub(max_chars)).collect();
+    let trimmed = tail.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Rejoins translated chunk bodies with the original separators.
+pub fn join_document(chunks: &[DocumentChunk], translated: &[String]) -> String {
+    let mut output = String::new();
+    for (chunk, text) in chunks.iter().zip(translated) {
+        output.push_str(&chunk.prefix);
+        output.push_str(text);
+        output.push_str(&chunk.suffix);
+    }
+    output
+}
+
+/// Verifies the translated document kept the source's line-break structure.
+pub fn validate_document_structure(original: &str, translated: &str) -> Result<(), DocumentError> {
+    let expected = count_line_breaks(original);
+    let actual = count_line_breaks(translated);
+    if expected != actual {
+        return Err(DocumentError::LineBreakMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+fn count_line_breaks(text: &str) -> usize {
+    text.matches('\n').count() + text.matches("\\n").count()
+}
+
+/// The protected span that a cut at byte `index` would break apart
+fn enclosing_span(protected: &[(usize, usize)], index: usize) -> Option<(usize, usize)> {
+    protected
+        .iter()
+        .copied()
+        .find(|(start, end)| *start < index && index < *end)
+}
+
+/// Splits text into (offset, paragraph, separator) triples using the
+/// strongest separator present outside the protected spans.
+fn split_paragraphs<'a>(
+    text: &'a str,
+    protected: &[(usize, usize)],
+) -> Vec<(usize, &'a str, &'a str)> {
+    let outside_tokens = |separator: &str, position: usize| {
+        enclosing_span(protected, position).is_none()
+            && enclosing_span(protected, position + separator.len()).is_none()
+    };
+    let Some(separator) = PARAGRAPH_SEPARATORS.iter().find(|separator| {
+        text.match_indices(**separator)
+            .any(|(position, _)| outside_tokens(separator, position))
+    }) else {
+        return vec![(0, text, "")];
+    };
+
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut search = 0;
+    while let Some(found) = text[search..].find(separator) {
+        let position = search + found;
+        if !outside_tokens(separator, position) {
+            search = position + separator.len();
+            continue;
+        }
+        // Absorb repeated separators so blank lines stay attached to the break.
+        let mut end = position + separator.len();
+        while text[end..].starts_with(separator) && outside_tokens(separator, end) {
+            end += separator.len();
+        }
+        result.push((start, &text[start..position], &text[position..end]));
+        start = end;
+        search = end;
+    }
+    result.push((start, &text[start..], ""));
+    result
+}
+
+/// Splits a paragraph longer than `max_chars` at sentence boundaries, falling
+/// back to the last whitespace (or a hard cut) inside the window. `offset`
+/// is the paragraph's position in the text the `protected` spans refer to.
+fn split_oversized<'a>(
+    text: &'a str,
+    offset: usize,
+    max_chars: usize,
+    protected: &[(usize, usize)],
+) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    let mut consumed = 0;
+    while rest.chars().count() > max_chars {
+        let base = offset + consumed;
+        let allowed = |index: &usize| enclosing_span(protected, base + index).is_none();
+        let window_end = rest
+            .char_indices()
+            .nth(max_chars)
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        let window = &rest[..window_end];
+        let cut = window
+            .char_indices()
+            .filter(|(_, c)| SENTENCE_TERMINATORS.contains(c))
+            .map(|(index, c)| index + c.len_utf8())
+            .filter(allowed)
+            .rfind(|index| *index < window_end)
+            .or_else(|| {
+                window
+                    .char_indices()
+                    .filter(|(_, c)| c.is_whitespace())
+                    .map(|(index, _)| index)
+                    .filter(allowed)
+                    .rfind(|index| *index > 0)
+            })
+            .unwrap_or_else(|| match enclosing_span(protected, base + window_end) {
+                // Cut before the token, or after it when it starts the window
+                Some((start, _)) if start > base => start - base,
+                Some((_, end)) => (end - base).min(rest.len()),
+                None => window_end,
+            });
+        parts.push(&rest[..cut]);
+        rest = &rest[cut..];
+        consumed += cut;
+    }
+    if !rest.is_empty() {
+        parts.push(rest);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejoin_identity(text: &str, max_chars: usize) -> String {
+        let chunks = split_document(text, max_chars);
+        let bodies: Vec<String> = chunks.iter().map(|chunk| chunk.text.clone()).collect();
+        join_document(&chunks, &bodies)
+    }
+
+    #[test]
+    fn short_text_is_single_chunk() {
+        let chunks = split_document("Just one paragraph.", 100);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "Just one paragraph.");
+        assert!(!needs_document_mode("short"));
+    }
+
+    #[test]
+    fn splits_on_escaped_paragraphs_and_roundtrips() {
+        let text = "First chapter text.\\n\\nSecond chapter text.\\n\\nThird chapter.";
+        let chunks = split_document(text, 25);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1].text, "Second chapter text.");
+        assert_eq!(chunks[0].suffix, "\\n\\n");
+        assert_eq!(rejoin_identity(text, 25), text);
+    }
+
+    #[test]
+    fn merges_small_paragraphs_up_to_limit() {
+        let text = "A.\n\nB.\n\nC.";
+        let chunks = split_document(text, 100);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, text);
+    }
+
+    #[test]
+    fn oversized_paragraph_splits_on_sentences() {
+        let text = "One sentence here. Another sentence here. A third one here.";
+        let chunks = split_document(text, 25);
+        assert!(chunks.len() >= 3);
+        assert!(chunks.iter().all(|chunk| chunk.text.chars().count() <= 25));
+        assert_eq!(rejoin_identity(text, 25), text);
+    }
+
+    #[test]
+    fn cuts_stay_outside_protected_tokens() {
+        // The only sentence end and space in the window sit inside the tag
+        let text = "Read <img src=\"a.png\"> now. Then go on.";
+        let tag = (5, 22);
+        let chunks = split_document_protected(text, 12, &[tag]);
+        assert!(chunks.iter().any(|chunk| chunk.text == "Read"));
+        assert!(chunks
+            .iter()
+            .any(|chunk| chunk.text.contains("<img src=\"a.png\">")));
+        let bodies: Vec<String> = chunks.iter().map(|chunk| chunk.text.clone()).collect();
+        assert_eq!(join_document(&chunks, &bodies), text);
+
+        // A line break escape inside a protected block is not a paragraph break
+        let text = "{n, plural,\\n one{x}} Tail text.\\nNext paragraph.";
+        let block = (0, 21);
+        let chunks = split_document_protected(text, 40, &[block]);
+        assert_eq!(chunks[0].text, "{n, plural,\\n one{x}} Tail text.");
+        assert_eq!(chunks[0].suffix, "\\n");
+    }
+
+    #[test]
+    fn overlap_uses_previous_chunk_tail() {
+        let text = "Alpha paragraph.\n\nBeta paragraph.";
+        let chunks = split_document(text, 20);
+        assert_eq!(overlap_context(&chunks, 0, 10), None);
+        assert_eq!(overlap_context(&chunks, 1, 10).as_deref(), Some("paragraph."));
+    }
+
+    #[test]
+    fn detects_structure_changes() {
+        assert!(validate_document_structure("a\\nb", "x\\ny").is_ok());
+        assert_eq!(
+            validate_document_structure("a\\n\\nb", "x y"),
+            Err(DocumentError::LineBreakMismatch {
+                expected: 2,
+                actual: 0
+            })
+        );
+    }
+}
@@ -1,14 +1,69 @@
 /// Encoding and newline preservation
+///
+/// Older mods often ship CP949, Shift-JIS or Windows-1252 text. Files are
+/// decoded by BOM, then UTF-8 validation, then a chardetng guess, and written
+/// back in the same encoding when the translation fits in it.
+use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
 use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum Encoding {
     Utf8,
     Utf8Bom,
     Utf16Le,
     Utf16Be,
+    /// Byte-preserving fallback for single-byte text with no better guess
     Latin1,
+    /// Korean (EUC-KR superset)
+    Cp949,
+    ShiftJis,
+    Gbk,
+    Big5,
+    Windows1252,
+}
+
+impl Encoding {
+    /// The encoding_rs codec for legacy multi-byte and code page encodings
+    fn legacy_codec(self) -> Option<&'static encoding_rs::Encoding> {
+        match self {
+            Encoding::Cp949 => Some(encoding_rs::EUC_KR),
+            Encoding::ShiftJis => Some(encoding_rs::SHIFT_JIS),
+            Encoding::Gbk => Some(encoding_rs::GBK),
+            Encoding::Big5 => Some(encoding_rs::BIG5),
+            Encoding::Windows1252 => Some(encoding_rs::WINDOWS_1252),
+            _ => None,
+        }
+    }
+
+    fn from_codec(codec: &'static encoding_rs::Encoding) -> Self {
+        if codec == encoding_rs::EUC_KR {
+            Encoding::Cp949
+        } else if codec == encoding_rs::SHIFT_JIS {
+            Encoding::ShiftJis
+        } else if codec == encoding_rs::GBK || codec == encoding_rs::GB18030 {
+            Encoding::Gbk
+        } else if codec == encoding_rs::BIG5 {
+            Encoding::Big5
+        } else if codec == encoding_rs::WINDOWS_1252 {
+            Encoding::Windows1252
+        } else {
+            Encoding::Latin1
+        }
+    }
+}
+
+/// Encoding used when writing translated files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputEncoding {
+    /// Same encoding as the source; UTF-8 with BOM when the translation
+    /// cannot be represented in it
+    #[default]
+    Preserve,
+    Utf8,
+    Utf8Bom,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,8 +111,10 @@ impl FileMetadata {
             return (Encoding::Utf8, false);
         }
         
-        // Fallback to Latin1
-        (Encoding::Latin1, false)
+        // Guess the legacy encoding from byte statistics
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(content, true);
+        (Encoding::from_codec(detector.guess(None, false)), false)
     }
     
     fn detect_newline(content: &[u8]) -> Newline {
@@ -78,8 +135,13 @@ impl FileMetadata {
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes)?;
         
-        let metadata = Self::detect(&bytes);
-        let text = Self::decode(&bytes, metadata.encoding)?;
+        Self::decode_bytes(&bytes)
+    }
+    
+    /// Decode raw file bytes with encoding detection
+    pub fn decode_bytes(bytes: &[u8]) -> Result<(String, FileMetadata), std::io::Error> {
+        let metadata = Self::detect(bytes);
+        let text = Self::decode(bytes, metadata.encoding)?;
         
         Ok((text, metadata))
     }
@@ -99,6 +161,16 @@ impl FileMetadata {
     }
     
     fn decode(bytes: &[u8], encoding: Encoding) -> Result<String, std::io::Error> {
+        if let Some(codec) = encoding.legacy_codec() {
+            let (text, had_errors) = codec.decode_without_bom_handling(bytes);
+            if had_errors {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid {} text", codec.name()),
+                ));
+            }
+            return Ok(text.into_owned());
+        }
         match encoding {
             Encoding::Utf8 => {
                 String::from_utf8(bytes.to_vec())
@@ -132,10 +204,26 @@ impl FileMetadata {
                 // All bytes 0-255 are valid as Latin1 and map to Unicode code points
                 Ok(bytes.iter().map(|&b| char::from(b)).collect())
             }
+            _ => unreachable!("legacy encodings are decoded above"),
         }
     }
     
-    fn encode(text: &str, encoding: Encoding) -> Result<Vec<u8>, std::io::Error> {
+    /// Encode `text`, failing when a character has no representation in
+    /// `encoding`
+    pub fn encode(text: &str, encoding: Encoding) -> Result<Vec<u8>, std::io::Error> {
+        let unmappable = |name: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("text cannot be represented in {}", name),
+            )
+        };
+        if let Some(codec) = encoding.legacy_codec() {
+            let (bytes, _, had_errors) = codec.encode(text);
+            if had_errors {
+                return Err(unmappable(codec.name()));
+            }
+            return Ok(bytes.into_owned());
+        }
         match encoding {
             Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
             Encoding::Utf8Bom => {
@@ -157,13 +245,31 @@ impl FileMetadata {
                 }
                 Ok(bytes)
             }
-            Encoding::Latin1 => {
-                // Convert back to Latin1 (lossy)
-                Ok(text
-                    .chars()
-                    .map(|c| if (c as u32) < 256 { c as u8 } else { b'?' })
-                    .collect())
-            }
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c as u32).map_err(|_| unmappable("ISO-8859-1")))
+                .collect(),
+            _ => unreachable!("legacy encodings are encoded above"),
+        }
+    }
+    
+    /// Bytes for a translated file written from a source with this metadata.
+    /// Newlines follow the source; the encoding follows `output`. Returns the
+    /// encoding actually used, which is UTF-8 with BOM when `Preserve` could
+    /// not represent the translation.
+    pub fn encode_output(&self, text: &str, output: OutputEncoding) -> (Vec<u8>, Encoding) {
+        let text = Self::normalize_newlines(text, self.newline);
+        let encoding = match output {
+            OutputEncoding::Preserve => self.encoding,
+            OutputEncoding::Utf8 => Encoding::Utf8,
+            OutputEncoding::Utf8Bom => Encoding::Utf8Bom,
+        };
+        match Self::encode(&text, encoding) {
+            Ok(bytes) => (bytes, encoding),
+            Err(_) => (
+                Self::encode(&text, Encoding::Utf8Bom).unwrap_or_default(),
+                Encoding::Utf8Bom,
+            ),
         }
     }
     
@@ -215,6 +321,54 @@ mod tests {
         assert_eq!(metadata.newline, Newline::Lf);
     }
     
+    #[test]
+    fn detects_and_decodes_legacy_encodings() {
+        let korean = "아이템=강철 검\n설명=튼튼한 강철로 만든 검입니다.\n";
+        let (bytes, _, _) = encoding_rs::EUC_KR.encode(korean);
+        let (text, metadata) = FileMetadata::decode_bytes(&bytes).unwrap();
+        assert_eq!(metadata.encoding, Encoding::Cp949);
+        assert_eq!(text, korean);
+
+        let japanese = "アイテム=鋼の剣\n説明=丈夫な鋼で作られた剣です。\n";
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(japanese);
+        let (text, metadata) = FileMetadata::decode_bytes(&bytes).unwrap();
+        assert_eq!(metadata.encoding, Encoding::ShiftJis);
+        assert_eq!(text, japanese);
+
+        let french = "item=Épée en acier trempé\r\ndescription=Forgée à la main.\r\n";
+        let (bytes, _, _) = encoding_rs::WINDOWS_1252.encode(french);
+        let (text, metadata) = FileMetadata::decode_bytes(&bytes).unwrap();
+        assert_eq!(metadata.encoding, Encoding::Windows1252);
+        assert_eq!(metadata.newline, Newline::Crlf);
+        assert_eq!(text, french);
+    }
+
+    #[test]
+    fn output_keeps_the_source_encoding_when_it_fits() {
+        let cp949 = FileMetadata {
+            encoding: Encoding::Cp949,
+            newline: Newline::Crlf,
+            has_bom: false,
+        };
+        let (bytes, encoding) = cp949.encode_output("이름=검\n", OutputEncoding::Preserve);
+        assert_eq!(encoding, Encoding::Cp949);
+        assert_eq!(encoding_rs::EUC_KR.decode_without_bom_handling(&bytes).0, "이름=검\r\n");
+
+        let (bytes, encoding) = cp949.encode_output("이름=검\n", OutputEncoding::Utf8);
+        assert_eq!(encoding, Encoding::Utf8);
+        assert_eq!(bytes, "이름=검\r\n".as_bytes());
+
+        let cp1252 = FileMetadata {
+            encoding: Encoding::Windows1252,
+            newline: Newline::Lf,
+            has_bom: false,
+        };
+        let (bytes, encoding) = cp1252.encode_output("name=강철 검", OutputEncoding::Preserve);
+        assert_eq!(encoding, Encoding::Utf8Bom);
+        assert_eq!(&bytes[..3], &[0xEF, 0xBB, 0xBF]);
+        assert!(FileMetadata::encode("강", Encoding::Latin1).is_err());
+    }
+    
     #[test]
     fn roundtrip_with_bom() {
         let temp = NamedTempFile::new().unwrap();
@@ -0,0 +1,186 @@
+/// Handling for mod files locked by a running game or Steam.
+///
+/// Windows refuses to replace a file another process holds open, so writes
+/// fail while the game is running or Steam is validating the workshop item.
+/// Writes retry with backoff on sharing violations; if the lock outlives the
+/// retries, the error tells the user which program to close instead of
+/// reporting a bare I/O failure.
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Waits between attempts while a file stays locked
+const LOCK_RETRY_DELAYS_MS: [u64; 4] = [200, 500, 1000, 2000];
+
+/// Processes that commonly hold mod files open, with a display name
+const KNOWN_LOCK_HOLDERS: [(&str, &str); 9] = [
+    ("steam.exe", "Steam"),
+    ("steamwebhelper.exe", "Steam"),
+    ("rimworldwin64.exe", "RimWorld"),
+    ("factorio.exe", "Factorio"),
+    ("stardew valley.exe", "Stardew Valley"),
+    ("javaw.exe", "Minecraft"),
+    ("ck3.exe", "Crusader Kings III"),
+    ("eu4.exe", "Europa Universalis IV"),
+    ("stellaris.exe", "Stellaris"),
+];
+
+/// Whether the error means another process holds the file open
+pub fn is_sharing_violation(error: &io::Error) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        // ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION
+        matches!(error.raw_os_error(), Some(32) | Some(33))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        // EBUSY, ETXTBSY
+        matches!(error.raw_os_error(), Some(16) | Some(26))
+    }
+}
+
+/// Runs `operation`, retrying with backoff while it fails on a locked file.
+pub fn retry_while_locked<T>(mut operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    retry_with_delays(&mut operation, &LOCK_RETRY_DELAYS_MS)
+}
+
+fn retry_with_delays<T>(
+    operation: &mut impl FnMut() -> io::Result<T>,
+    delays_ms: &[u64],
+) -> io::Result<T> {
+    let mut delays = delays_ms.iter();
+    loop {
+        match operation() {
+            Err(error) if is_sharing_violation(&error) => match delays.next() {
+                Some(delay) => thread::sleep(Duration::from_millis(*delay)),
+                None => return Err(error),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Names of running programs that are likely to hold mod files open.
+/// Only available on Windows; empty elsewhere or when the lookup fails.
+pub fn running_lock_holders() -> Vec<String> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+        let Ok(output) = std::process::Command::new("tasklist")
+            .args(["/FO", "CSV", "/NH"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+        else {
+            return Vec::new();
+        };
+        lock_holders_in_task_list(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Picks known lock holders out of `tasklist /FO CSV /NH` output
+pub fn lock_holders_in_task_list(task_list: &str) -> Vec<String> {
+    let mut holders: Vec<String> = Vec::new();
+    for line in task_list.lines() {
+        let image = line
+            .split(',')
+            .next()
+            .unwrap_or_default()
+            .trim_matches('"')
+            .to_ascii_lowercase();
+        let known = KNOWN_LOCK_HOLDERS
+            .iter()
+            .find(|(process, _)| *process == image);
+        if let Some((_, name)) = known {
+            if !holders.iter().any(|holder| holder == name) {
+                holders.push(name.to_string());
+            }
+        }
+    }
+    holders
+}
+
+/// User-facing message for a file that stayed locked through every retry
+pub fn locked_file_message(path: &Path, holders: &[String]) -> String {
+    let mut message = format!(
+        "{} 파일을 다른 프로그램이 사용 중이라 저장하지 못했습니다. 게임이나 Steam을 종료한 뒤 다시 시도하세요.",
+        path.to_string_lossy()
+    );
+    if !holders.is_empty() {
+        message.push_str(&format!(" (실행 중: {})", holders.join(", ")));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "windows")]
+    const LOCKED_CODE: i32 = 32;
+    #[cfg(not(target_os = "windows"))]
+    const LOCKED_CODE: i32 = 16;
+
+    #[test]
+    fn retries_only_sharing_violations() {
+        let mut attempts = 0;
+        let result = retry_with_delays(
+            &mut || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err(io::Error::from_raw_os_error(LOCKED_CODE))
+                } else {
+                    Ok(attempts)
+                }
+            },
+            &[0, 0, 0],
+        );
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: io::Result<()> = retry_with_delays(
+            &mut || {
+                attempts += 1;
+                Err(io::Error::from_raw_os_error(LOCKED_CODE))
+            },
+            &[0, 0],
+        );
+        assert!(is_sharing_violation(&result.unwrap_err()));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result: io::Result<()> = retry_with_delays(
+            &mut || {
+                attempts += 1;
+                Err(io::Error::new(io::ErrorKind::NotFound, "missing"))
+            },
+            &[0, 0],
+        );
+        assert!(!is_sharing_violation(&result.unwrap_err()));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn names_known_programs_from_the_task_list() {
+        let task_list = "\"System Idle Process\",\"0\",\"Services\",\"0\",\"8 K\"\n\
+            \"steam.exe\",\"1200\",\"Console\",\"1\",\"60,000 K\"\n\
+            \"steamwebhelper.exe\",\"1300\",\"Console\",\"1\",\"90,000 K\"\n\
+            \"RimWorldWin64.exe\",\"4400\",\"Console\",\"1\",\"900,000 K\"\n";
+        assert_eq!(
+            lock_holders_in_task_list(task_list),
+            vec!["Steam", "RimWorld"]
+        );
+
+        let message = locked_file_message(Path::new("Mod/Keyed.xml"), &["Steam".to_string()]);
+        assert!(message.contains("Mod/Keyed.xml"));
+        assert!(message.ends_with("(실행 중: Steam)"));
+    }
+}
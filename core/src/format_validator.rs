@@ -7,7 +7,13 @@
 /// - PO: valid gettext format
 /// - ICU: balanced MessageFormat blocks
 /// - CFG/INI: valid key=value structure
+/// - TOML: valid strings, tables and key = value lines
+/// - Ren'Py: closed string literals
+/// - mcfunction/SNBT: closed strings and balanced brackets
+/// - Paradox: language header and KEY:0 "value" entries
+/// - Unity .asset: every YAML document parses
 
+use serde::Deserialize;
 use serde_json;
 use serde_yaml;
 use thiserror::Error;
@@ -43,6 +49,24 @@ pub enum FormatValidationError {
     
     #[error("Lua format error: {0}")]
     LuaError(String),
+    
+    #[error("TOML format error: {0}")]
+    TomlError(String),
+    
+    #[error("Ren'Py script error: {0}")]
+    RenPyError(String),
+    
+    #[error("mcfunction command error: {0}")]
+    McFunctionError(String),
+    
+    #[error("SNBT format error: {0}")]
+    SnbtError(String),
+    
+    #[error("Paradox localisation error: {0}")]
+    ParadoxError(String),
+    
+    #[error("Unity asset error: {0}")]
+    UnityAssetError(String),
 }
 
 /// Validates JSON format
@@ -416,6 +440,213 @@ pub fn validate_lua(content: &str) -> Result<(), FormatValidationError> {
     Ok(())
 }
 
+/// Validates TOML format - checks strings, tables and key = value lines
+pub fn validate_toml(content: &str) -> Result<(), FormatValidationError> {
+    crate::formats::toml::check_syntax(content)
+        .map_err(|e| FormatValidationError::TomlError(e.to_string()))
+}
+
+/// Validates a Ren'Py script - every string literal is closed, quoted
+/// strings on the line they start on and triple-quoted ones anywhere after
+pub fn validate_renpy(content: &str) -> Result<(), FormatValidationError> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut line = 1;
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let ch = chars[i];
+        match ch {
+            '\n' => line += 1,
+            '#' => {
+                // Comment, skip to end of line
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '"' | '\'' | '`' => {
+                let start_line = line;
+                let is_quote = |at: usize| chars.get(at) == Some(&ch);
+                let triple = is_quote(i + 1) && is_quote(i + 2);
+                // A quote at `at` ends the string when the rest of a triple follows
+                let closes = |at: usize| !triple || (is_quote(at + 1) && is_quote(at + 2));
+                i += if triple { 3 } else { 1 };
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(FormatValidationError::RenPyError(format!(
+                                "string opened on line {start_line} is never closed"
+                            )));
+                        }
+                        Some('\\') => {
+                            if chars.get(i + 1) == Some(&'\n') {
+                                line += 1;
+                            }
+                            i += 2;
+                            continue;
+                        }
+                        Some('\n') if !triple => {
+                            return Err(FormatValidationError::RenPyError(format!(
+                                "string on line {start_line} is not closed"
+                            )));
+                        }
+                        Some('\n') => line += 1,
+                        Some(&c) if c == ch && closes(i) => {
+                            i += if triple { 3 } else { 1 };
+                            break;
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    
+    Ok(())
+}
+
+/// Validates a Paradox localisation file - a language header followed by
+/// `KEY:0 "value"` entries and comments
+pub fn validate_paradox(content: &str) -> Result<(), FormatValidationError> {
+    crate::formats::paradox::check_syntax(content)
+        .map_err(|e| FormatValidationError::ParadoxError(e.to_string()))
+}
+
+/// Validates a Unity text asset - every `--- !u!` document parses as YAML.
+/// Unity declares the `!u!` tag handle once but uses it on every document
+/// header, and marks prefab instances `stripped`, so the headers are reduced
+/// to a bare `---` first.
+pub fn validate_unity_asset(content: &str) -> Result<(), FormatValidationError> {
+    let content = content
+        .lines()
+        .map(|line| {
+            if line.starts_with("--- ") {
+                "---"
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    
+    for document in serde_yaml::Deserializer::from_str(&content) {
+        serde_yaml::Value::deserialize(document)
+            .map_err(|e| FormatValidationError::UnityAssetError(e.to_string()))?;
+    }
+    
+    Ok(())
+}
+
+/// Validates an SNBT file - strings are closed and `{}`/`[]` are balanced
+pub fn validate_snbt(content: &str) -> Result<(), FormatValidationError> {
+    check_nbt_balance(content, 1).map_err(FormatValidationError::SnbtError)
+}
+
+/// Validates an mcfunction file - on every command, the NBT and JSON text
+/// after the first `{` or `[` has closed strings and balanced brackets.
+/// Text before that (`say it's done`) is free-form and not checked.
+pub fn validate_mcfunction(content: &str) -> Result<(), FormatValidationError> {
+    for (index, line) in content.lines().enumerate() {
+        let command = line.trim_start();
+        if command.starts_with('#') {
+            continue;
+        }
+        if let Some(start) = command.find(['{', '[']) {
+            check_nbt_balance(&command[start..], index + 1)
+                .map_err(FormatValidationError::McFunctionError)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Checks that quoted strings are closed and brackets are balanced in NBT
+/// or JSON text starting on line `first_line`
+fn check_nbt_balance(content: &str, first_line: usize) -> Result<(), String> {
+    let mut open: Vec<(char, usize)> = Vec::new();
+    let mut string: Option<(char, usize)> = None;
+    let mut escaped = false;
+    let mut line = first_line;
+    
+    for ch in content.chars() {
+        if let Some((quote, _)) = string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == quote {
+                string = None;
+            }
+        } else {
+            match ch {
+                '"' | '\'' => string = Some((ch, line)),
+                '{' | '[' => open.push((ch, line)),
+                '}' | ']' => {
+                    let expected = if ch == '}' { '{' } else { '[' };
+                    match open.pop() {
+                        Some((opener, _)) if opener == expected => {}
+                        Some((opener, opened)) => {
+                            return Err(format!(
+                                "'{ch}' on line {line} closes '{opener}' from line {opened}"
+                            ));
+                        }
+                        None => return Err(format!("unmatched '{ch}' on line {line}")),
+                    }
+                }
+                _ => {}
+            }
+        }
+        if ch == '\n' {
+            line += 1;
+        }
+    }
+    
+    if let Some((_, opened)) = string {
+        return Err(format!("string opened on line {opened} is never closed"));
+    }
+    if let Some((opener, opened)) = open.pop() {
+        return Err(format!("'{opener}' on line {opened} is never closed"));
+    }
+    Ok(())
+}
+
+/// Validates a whole file with the checker for its format.
+/// Formats without a structural checker always pass.
+pub fn validate_for_format(
+    format: crate::formats::FileFormat,
+    content: &str,
+) -> Result<(), FormatValidationError> {
+    use crate::formats::FileFormat;
+
+    match format {
+        FileFormat::Json | FileFormat::BetterQuesting => validate_json(content),
+        FileFormat::Xml | FileFormat::Resx => validate_xml(content),
+        FileFormat::Yaml => validate_yaml(content),
+        FileFormat::Po => validate_po(content),
+        FileFormat::Ini | FileFormat::Cfg => validate_ini(content),
+        FileFormat::Csv => validate_csv(content),
+        FileFormat::Markdown => validate_markdown(content),
+        FileFormat::Properties => validate_properties(content),
+        FileFormat::Lua => validate_lua(content),
+        FileFormat::Toml => validate_toml(content),
+        FileFormat::RenPy => validate_renpy(content),
+        FileFormat::McFunction => validate_mcfunction(content),
+        FileFormat::Snbt | FileFormat::FtbQuests => validate_snbt(content),
+        FileFormat::Paradox => validate_paradox(content),
+        FileFormat::UnityAsset => validate_unity_asset(content),
+        // Binary files are checked by their handler reading them back
+        FileFormat::Txt
+        | FileFormat::Bethesda
+        | FileFormat::Mo
+        | FileFormat::UnityBundle
+        | FileFormat::Unknown => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,4 +793,80 @@ msgid "World"
         let content = "local msg = \"Hello"; // missing closing quote
         assert!(validate_lua(content).is_err());
     }
+    
+    #[test]
+    fn test_validate_renpy_valid() {
+        let content = "label start:\n    e \"It's \\\"fine\\\".\" # don't\n    $ note = '''two\nlines'''\n    return\n";
+        assert!(validate_renpy(content).is_ok());
+    }
+    
+    #[test]
+    fn test_validate_renpy_unclosed() {
+        let content = "label start:\n    e \"Hello\n    return\n"; // missing closing quote
+        assert!(validate_renpy(content).is_err());
+        assert!(validate_renpy("$ text = '''never closed\n").is_err());
+    }
+    
+    #[test]
+    fn test_validate_mcfunction_valid() {
+        let content = r#"# it's a comment with {
+say it's done
+tellraw @a[tag=won] {"text":"Win [1/2]","color":"gold"}
+give @p diamond_sword{display:{Name:'{"text":"Sword"}'}} 1
+"#;
+        assert!(validate_mcfunction(content).is_ok());
+    }
+    
+    #[test]
+    fn test_validate_mcfunction_unbalanced() {
+        assert!(validate_mcfunction("tellraw @a {\"text\":\"Hi\"\n").is_err());
+        assert!(validate_mcfunction("tellraw @a {\"text\":\"Hi}\n").is_err());
+    }
+    
+    #[test]
+    fn test_validate_snbt_valid() {
+        let content = "{\n\ttitle: \"Chapter {1}\"\n\tdescription: [\"It's \\\"here\\\"\"]\n}\n";
+        assert!(validate_snbt(content).is_ok());
+    }
+    
+    #[test]
+    fn test_validate_snbt_unbalanced() {
+        assert!(validate_snbt("{\n\ttitle: \"Chapter\"\n").is_err());
+        assert!(validate_snbt("{ list: [1, 2} ]").is_err());
+    }
+    
+    #[test]
+    fn test_validate_paradox_valid() {
+        let content = "\u{feff}l_english:\n # Events\n event.1.t:0 \"The \"Great\" Feast\"\n event.1.d: \"$NAME$ arrives\" # popup\n";
+        assert!(validate_paradox(content).is_ok());
+    }
+    
+    #[test]
+    fn test_validate_paradox_invalid() {
+        assert!(validate_paradox(" event.1.t:0 \"No header\"\n").is_err());
+        assert!(validate_paradox("l_english:\n event.1.t:0 \"Unclosed\n").is_err());
+    }
+    
+    #[test]
+    fn test_validate_unity_asset_valid() {
+        let content = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1001 &100100000 stripped
+Prefab:
+  m_ObjectHideFlags: 1
+--- !u!114 &11400000
+MonoBehaviour:
+  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  entries:
+  - key: menu_quit
+    value: "Quit to \"desktop\"?"
+"#;
+        assert!(validate_unity_asset(content).is_ok());
+    }
+    
+    #[test]
+    fn test_validate_unity_asset_invalid() {
+        let content = "%YAML 1.1\n--- !u!114 &1\nMonoBehaviour:\n  value: \"Unclosed\n  other: 1\n";
+        assert!(validate_unity_asset(content).is_err());
+    }
 }
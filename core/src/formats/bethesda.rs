@@ -0,0 +1,571 @@
+/// Bethesda string tables (Skyrim, Fallout 4, Starfield)
+/// Localized plugins keep their text in three binary tables next to the
+/// plugin: `.strings` (names), `.dlstrings` (descriptions, books) and
+/// `.ilstrings` (dialogue). Each starts with an entry count and data size,
+/// followed by a directory of `(string id, offset)` pairs and the string data.
+/// `.strings` data is NUL-terminated; the other two prefix every string with
+/// its length. Tables are binary, so [`BethesdaHandler`] only implements the
+/// byte methods of [`FormatHandler`] and reads the table kind from the path.
+use super::{
+    binary_only, FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const HEADER_LEN: usize = 8;
+const DIRECTORY_ENTRY_LEN: usize = 8;
+
+// Windows-1252 characters for bytes 0x80..=0x9F; unassigned bytes keep
+// their Latin-1 code point like browsers do.
+const CP1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+// Languages whose tables the games ship in Windows-1252
+const WINDOWS_1252_LANGUAGES: &[&str] = &["en", "fr", "de", "it", "es", "pt"];
+
+// Language suffixes used in table file names: Skyrim_English.STRINGS
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("en", "english"),
+    ("fr", "french"),
+    ("de", "german"),
+    ("it", "italian"),
+    ("es", "spanish"),
+    ("pl", "polish"),
+    ("ru", "russian"),
+    ("cs", "czech"),
+    ("ja", "japanese"),
+    ("ko", "korean"),
+    ("zh", "chinese"),
+    ("pt", "ptbr"),
+    ("tr", "turkish"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringTableKind {
+    /// `.strings`: NUL-terminated names and short labels
+    Strings,
+    /// `.dlstrings`: length-prefixed descriptions and book text
+    DlStrings,
+    /// `.ilstrings`: length-prefixed dialogue lines
+    IlStrings,
+}
+
+impl StringTableKind {
+    /// Detect the table kind from the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "strings" => Some(Self::Strings),
+            "dlstrings" => Some(Self::DlStrings),
+            "ilstrings" => Some(Self::IlStrings),
+            _ => None,
+        }
+    }
+
+    fn is_length_prefixed(self) -> bool {
+        !matches!(self, Self::Strings)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringEncoding {
+    Utf8,
+    /// Legacy Skyrim and Fallout tables for Western languages
+    Windows1252,
+}
+
+impl StringEncoding {
+    /// Encoding of a table localized into `target_lang`. Windows-1252 tables
+    /// stay Windows-1252 for the Western European languages it covers; every
+    /// other language (Korean, Japanese, Chinese, Russian, ...) is written as
+    /// UTF-8, which is what the games read for those languages.
+    pub fn for_target_language(self, target_lang: &str) -> Self {
+        let primary = primary_language(target_lang);
+        if self == Self::Windows1252 && WINDOWS_1252_LANGUAGES.contains(&primary.as_str()) {
+            Self::Windows1252
+        } else {
+            Self::Utf8
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringEntry {
+    pub id: u32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringTable {
+    pub kind: StringTableKind,
+    /// Encoding used by [`StringTable::to_bytes`]; detected when parsing
+    pub encoding: StringEncoding,
+    pub entries: Vec<StringEntry>,
+}
+
+impl StringTable {
+    /// Parse a binary string table. The encoding is UTF-8 when every string
+    /// is valid UTF-8 and Windows-1252 otherwise.
+    pub fn parse(bytes: &[u8], kind: StringTableKind) -> Result<Self, FormatError> {
+        let count = read_u32(bytes, 0)? as usize;
+        let data_size = read_u32(bytes, 4)? as usize;
+        let data_start = count
+            .checked_mul(DIRECTORY_ENTRY_LEN)
+            .and_then(|len| len.checked_add(HEADER_LEN))
+            .filter(|start| *start <= bytes.len())
+            .ok_or_else(|| {
+                parse_error(format!("directory of {count} entries exceeds file size"))
+            })?;
+        let data = bytes
+            .get(data_start..data_start + data_size)
+            .ok_or_else(|| {
+                parse_error(format!(
+                    "string data of {data_size} bytes exceeds file size"
+                ))
+            })?;
+
+        let mut raw_entries = Vec::with_capacity(count);
+        for index in 0..count {
+            let entry_start = HEADER_LEN + index * DIRECTORY_ENTRY_LEN;
+            let id = read_u32(bytes, entry_start)?;
+            let offset = read_u32(bytes, entry_start + 4)? as usize;
+            raw_entries.push((id, read_string(data, offset, kind)?));
+        }
+
+        let encoding = if raw_entries
+            .iter()
+            .all(|(_, raw)| std::str::from_utf8(raw).is_ok())
+        {
+            StringEncoding::Utf8
+        } else {
+            StringEncoding::Windows1252
+        };
+        let entries = raw_entries
+            .into_iter()
+            .map(|(id, raw)| StringEntry {
+                id,
+                text: decode(raw, encoding),
+            })
+            .collect();
+
+        Ok(Self {
+            kind,
+            encoding,
+            entries,
+        })
+    }
+
+    /// Serialize the table. Identical strings share one data slot, as in the
+    /// tables shipped with the games.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mut directory = Vec::with_capacity(self.entries.len() * DIRECTORY_ENTRY_LEN);
+        let mut data = Vec::new();
+        let mut offsets: HashMap<&str, u32> = HashMap::new();
+
+        for entry in &self.entries {
+            let offset = match offsets.get(entry.text.as_str()) {
+                Some(offset) => *offset,
+                None => {
+                    let offset = to_u32(data.len(), "string data")?;
+                    let encoded = encode(&entry.text, self.encoding).map_err(|c| {
+                        FormatError::EncodingError(format!(
+                            "string {:#010X} contains '{c}', which Windows-1252 cannot encode",
+                            entry.id
+                        ))
+                    })?;
+                    if self.kind.is_length_prefixed() {
+                        let len = to_u32(encoded.len() + 1, "string")?;
+                        data.extend_from_slice(&len.to_le_bytes());
+                    }
+                    data.extend_from_slice(&encoded);
+                    data.push(0);
+                    offsets.insert(entry.text.as_str(), offset);
+                    offset
+                }
+            };
+            directory.extend_from_slice(&entry.id.to_le_bytes());
+            directory.extend_from_slice(&offset.to_le_bytes());
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + directory.len() + data.len());
+        bytes.extend_from_slice(&to_u32(self.entries.len(), "entry count")?.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(data.len(), "string data")?.to_le_bytes());
+        bytes.extend_from_slice(&directory);
+        bytes.extend_from_slice(&data);
+        Ok(bytes)
+    }
+
+    /// Non-empty strings as translatable entries keyed by their hex id
+    pub fn extract(&self) -> Vec<TranslatableEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.text.trim().is_empty())
+            .map(|entry| {
+                let mut metadata = HashMap::new();
+                metadata.insert("string_id".to_string(), entry.id.to_string());
+                TranslatableEntry {
+                    key: entry_key(entry.id),
+                    source: entry.text.clone(),
+                    context: Some(format!("string id {}", entry_key(entry.id))),
+                    metadata,
+                }
+            })
+            .collect()
+    }
+
+    /// Replace strings with their translations into `target_lang`;
+    /// untranslated ids keep the original. The table switches to the encoding
+    /// [`StringEncoding::for_target_language`] picks, so a Windows-1252 table
+    /// localized into Korean is written as UTF-8.
+    pub fn apply(&mut self, translations: &TranslationResult, target_lang: &str) {
+        self.encoding = self.encoding.for_target_language(target_lang);
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+        for entry in &mut self.entries {
+            if let Some(target) = translation_map.get(entry_key(entry.id).as_str()) {
+                entry.text = target.to_string();
+            }
+        }
+    }
+}
+
+pub struct BethesdaHandler;
+
+impl BethesdaHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BethesdaHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for BethesdaHandler {
+    fn extract(&self, _content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Err(binary_only(FileFormat::Bethesda))
+    }
+
+    fn merge(
+        &self,
+        _original: &str,
+        _translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        Err(binary_only(FileFormat::Bethesda))
+    }
+
+    fn extract_bytes(
+        &self,
+        path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Ok(StringTable::parse(content, table_kind(path)?)?.extract())
+    }
+
+    fn merge_bytes(
+        &self,
+        path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        let mut table = StringTable::parse(original, table_kind(path)?)?;
+        table.apply(translations, target_lang);
+        table.to_bytes()
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Bethesda
+    }
+}
+
+fn table_kind(path: &Path) -> Result<StringTableKind, FormatError> {
+    StringTableKind::from_path(path).ok_or_else(|| {
+        FormatError::UnsupportedFormat(format!("{} is not a string table", path.display()))
+    })
+}
+
+/// Output path for a localized table: `Plugin_English.STRINGS` becomes
+/// `Plugin_Korean.STRINGS` for `ko`. Returns `None` when the file name has no
+/// known language suffix or the target language has no Bethesda name.
+pub fn localized_table_path(path: &Path, target_lang: &str) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_str()?;
+    let extension = path.extension()?.to_str()?;
+    let (plugin, current) = stem.rsplit_once('_')?;
+    let current_lower = current.to_ascii_lowercase();
+    if !LANGUAGE_NAMES
+        .iter()
+        .any(|(_, name)| *name == current_lower)
+    {
+        return None;
+    }
+    let language = language_name(target_lang)?;
+    // Keep the casing style of the original suffix
+    let language = if current.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = language.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())?
+    } else {
+        language.to_string()
+    };
+    Some(path.with_file_name(format!("{plugin}_{language}.{extension}")))
+}
+
+fn language_name(lang: &str) -> Option<&'static str> {
+    let primary = primary_language(lang);
+    LANGUAGE_NAMES
+        .iter()
+        .find(|(code, _)| *code == primary)
+        .map(|(_, name)| *name)
+}
+
+/// `ko` for `ko-KR`, `pt` for `pt_BR`
+fn primary_language(lang: &str) -> String {
+    lang.split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn entry_key(id: u32) -> String {
+    format!("{id:08X}")
+}
+
+fn parse_error(message: String) -> FormatError {
+    FormatError::ParseError(message)
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Result<u32, FormatError> {
+    bytes
+        .get(at..at + 4)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or_else(|| parse_error(format!("unexpected end of table at byte {at}")))
+}
+
+fn to_u32(value: usize, what: &str) -> Result<u32, FormatError> {
+    u32::try_from(value).map_err(|_| {
+        FormatError::SerializationError(format!("{what} is too large for a string table"))
+    })
+}
+
+fn read_string(data: &[u8], offset: usize, kind: StringTableKind) -> Result<&[u8], FormatError> {
+    if kind.is_length_prefixed() {
+        let len = read_u32(data, offset)? as usize;
+        let raw = data
+            .get(offset + 4..offset + 4 + len)
+            .ok_or_else(|| parse_error(format!("string at offset {offset} runs past the data")))?;
+        Ok(raw.strip_suffix(&[0]).unwrap_or(raw))
+    } else {
+        let rest = data
+            .get(offset..)
+            .ok_or_else(|| parse_error(format!("string offset {offset} is outside the data")))?;
+        let end = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| parse_error(format!("string at offset {offset} is not terminated")))?;
+        Ok(&rest[..end])
+    }
+}
+
+fn decode(raw: &[u8], encoding: StringEncoding) -> String {
+    match encoding {
+        StringEncoding::Utf8 => String::from_utf8_lossy(raw).into_owned(),
+        StringEncoding::Windows1252 => raw
+            .iter()
+            .map(|byte| match byte {
+                0x80..=0x9F => CP1252_HIGH[(byte - 0x80) as usize],
+                _ => char::from(*byte),
+            })
+            .collect(),
+    }
+}
+
+/// Encodes `text`, returning the first character the encoding cannot represent
+fn encode(text: &str, encoding: StringEncoding) -> Result<Vec<u8>, char> {
+    match encoding {
+        StringEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+        StringEncoding::Windows1252 => text
+            .chars()
+            .map(|c| match c as u32 {
+                code @ (0x00..=0x7F | 0xA0..=0xFF) => Ok(code as u8),
+                _ => CP1252_HIGH
+                    .iter()
+                    .position(|high| *high == c)
+                    .map(|index| 0x80 + index as u8)
+                    .ok_or(c),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    fn table(
+        kind: StringTableKind,
+        encoding: StringEncoding,
+        texts: &[(u32, &str)],
+    ) -> StringTable {
+        StringTable {
+            kind,
+            encoding,
+            entries: texts
+                .iter()
+                .map(|(id, text)| StringEntry {
+                    id: *id,
+                    text: text.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_nul_terminated_strings() {
+        // 2 entries, ids 1 and 2, both pointing into "Iron Sword\0Shield\0"
+        let mut bytes = vec![2, 0, 0, 0, 18, 0, 0, 0];
+        bytes.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[2, 0, 0, 0, 11, 0, 0, 0]);
+        bytes.extend_from_slice(b"Iron Sword\0Shield\0");
+
+        let parsed = StringTable::parse(&bytes, StringTableKind::Strings).unwrap();
+        assert_eq!(parsed.encoding, StringEncoding::Utf8);
+        assert_eq!(
+            parsed,
+            table(
+                StringTableKind::Strings,
+                StringEncoding::Utf8,
+                &[(1, "Iron Sword"), (2, "Shield")]
+            )
+        );
+        assert_eq!(parsed.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn round_trips_length_prefixed_tables_and_shares_duplicates() {
+        for kind in [StringTableKind::DlStrings, StringTableKind::IlStrings] {
+            let original = table(
+                kind,
+                StringEncoding::Utf8,
+                &[
+                    (0x10, "A worn journal."),
+                    (0x20, "정착민"),
+                    (0x30, "A worn journal."),
+                ],
+            );
+            let bytes = original.to_bytes().unwrap();
+            // Header + 3 directory entries + two unique strings with length prefixes
+            assert_eq!(bytes.len(), 8 + 3 * 8 + (4 + 16) + (4 + 10));
+            assert_eq!(StringTable::parse(&bytes, kind).unwrap(), original);
+        }
+    }
+
+    #[test]
+    fn decodes_and_encodes_windows_1252() {
+        let mut bytes = vec![1, 0, 0, 0, 8, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0];
+        bytes.extend_from_slice(&[0x93, b'C', b'a', b'f', 0xE9, 0x94, 0x85, 0]);
+
+        let mut parsed = StringTable::parse(&bytes, StringTableKind::Strings).unwrap();
+        assert_eq!(parsed.encoding, StringEncoding::Windows1252);
+        assert_eq!(parsed.entries[0].text, "“Café”…");
+        assert_eq!(parsed.to_bytes().unwrap(), bytes);
+
+        parsed.entries[0].text = "카페".to_string();
+        assert!(matches!(
+            parsed.to_bytes(),
+            Err(FormatError::EncodingError(_))
+        ));
+    }
+
+    #[test]
+    fn localized_tables_use_an_encoding_the_target_language_fits() {
+        let mut bytes = vec![1, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0];
+        bytes.extend_from_slice(&[b'C', b'a', b'f', 0xE9, 0x85, 0]);
+        let translations = |target: &str| TranslationResult {
+            translated: vec![TranslatedEntry {
+                key: "00000007".into(),
+                source: "Café…".into(),
+                target: target.into(),
+            }],
+            failed: vec![],
+        };
+
+        let mut korean = StringTable::parse(&bytes, StringTableKind::Strings).unwrap();
+        korean.apply(&translations("카페…"), "ko-KR");
+        assert_eq!(korean.encoding, StringEncoding::Utf8);
+        let written = korean.to_bytes().unwrap();
+        let reparsed = StringTable::parse(&written, StringTableKind::Strings).unwrap();
+        assert_eq!(reparsed.entries[0].text, "카페…");
+
+        let mut french = StringTable::parse(&bytes, StringTableKind::Strings).unwrap();
+        french.apply(&translations("Café crème…"), "fr");
+        assert_eq!(french.encoding, StringEncoding::Windows1252);
+        assert_eq!(french.to_bytes().unwrap().last(), Some(&0));
+        assert_eq!(
+            StringEncoding::Utf8.for_target_language("fr"),
+            StringEncoding::Utf8
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_tables() {
+        let bytes = [3, 0, 0, 0, 100, 0, 0, 0, 1, 0, 0, 0];
+        assert!(StringTable::parse(&bytes, StringTableKind::Strings).is_err());
+    }
+
+    #[test]
+    fn applies_translations_and_names_localized_tables() {
+        let mut strings = table(
+            StringTableKind::Strings,
+            StringEncoding::Utf8,
+            &[(0xAB, "Iron Sword"), (0xAC, "")],
+        );
+        let entries = strings.extract();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "000000AB");
+
+        strings.apply(
+            &TranslationResult {
+                translated: vec![TranslatedEntry {
+                    key: "000000AB".into(),
+                    source: "Iron Sword".into(),
+                    target: "철제 검".into(),
+                }],
+                failed: vec![],
+            },
+            "ko",
+        );
+        assert_eq!(strings.entries[0].text, "철제 검");
+
+        assert_eq!(
+            localized_table_path(Path::new("Strings/MyMod_English.STRINGS"), "ko"),
+            Some(PathBuf::from("Strings/MyMod_Korean.STRINGS"))
+        );
+        assert_eq!(
+            localized_table_path(Path::new("mymod_english.dlstrings"), "ja-JP"),
+            Some(PathBuf::from("mymod_japanese.dlstrings"))
+        );
+        assert_eq!(localized_table_path(Path::new("MyMod.strings"), "ko"), None);
+        assert_eq!(
+            localized_table_path(Path::new("My_Mod.strings"), "ko"),
+            None
+        );
+        assert_eq!(
+            StringTableKind::from_path(Path::new("a_english.ILSTRINGS")),
+            Some(StringTableKind::IlStrings)
+        );
+    }
+}
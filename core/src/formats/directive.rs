@@ -0,0 +1,94 @@
+/// In-file translator directives
+///
+/// Mod authors can steer translation from inside their files with comments
+/// that start with `mt:`, in whichever comment syntax the format uses:
+///
+/// - `<!-- mt:skip -->`, `# mt:skip`: leave the next entry untranslated
+/// - `// mt:lock` … `// mt:unlock`: leave every entry in between untranslated
+/// - `-- mt:glossary Iron Ore=철광석, Sword=검`: fixed renderings for the file
+///
+/// Directive lines are comments, so they are written back unchanged.
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// `<!-- … -->` and `/* … */` around the whole line, or a line comment
+static COMMENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:<!--(?P<xml>.*?)-->|/\*(?P<block>.*?)\*/|(?://|#|--|;)(?P<line>.*))$")
+        .expect("valid directive comment regex")
+});
+
+static DIRECTIVE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*mt:(?P<name>[a-z]+)\b\s*(?P<args>.*?)\s*$")
+        .expect("valid directive regex")
+});
+
+/// A directive comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Directive {
+    /// Leave the next entry as it is
+    Skip,
+    /// Leave entries as they are until [`Directive::Unlock`]
+    Lock,
+    Unlock,
+    /// Source term and the rendering it must get, for the whole file
+    Glossary(Vec<(String, String)>),
+}
+
+/// Parses a trimmed line. Returns `None` for anything that is not a comment
+/// holding a known `mt:` directive.
+pub fn parse_directive(line: &str) -> Option<Directive> {
+    let comment = COMMENT_RE.captures(line.trim())?;
+    let body = ["xml", "block", "line"]
+        .iter()
+        .find_map(|group| comment.name(group))?
+        .as_str();
+    let directive = DIRECTIVE_RE.captures(body)?;
+    match directive["name"].to_ascii_lowercase().as_str() {
+        "skip" => Some(Directive::Skip),
+        "lock" => Some(Directive::Lock),
+        "unlock" => Some(Directive::Unlock),
+        "glossary" => {
+            let terms: Vec<(String, String)> = directive["args"]
+                .split(',')
+                .filter_map(|pair| {
+                    let (term, rendering) = pair.split_once('=')?;
+                    let (term, rendering) = (term.trim(), rendering.trim());
+                    (!term.is_empty() && !rendering.is_empty())
+                        .then(|| (term.to_string(), rendering.to_string()))
+                })
+                .collect();
+            (!terms.is_empty()).then_some(Directive::Glossary(terms))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_directives_in_each_comment_syntax() {
+        assert_eq!(parse_directive("<!-- mt:skip -->"), Some(Directive::Skip));
+        assert_eq!(parse_directive("# mt:lock"), Some(Directive::Lock));
+        assert_eq!(parse_directive("; MT:Unlock"), Some(Directive::Unlock));
+        assert_eq!(parse_directive("/* mt:skip */"), Some(Directive::Skip));
+        assert_eq!(
+            parse_directive("// mt:glossary term=Foo"),
+            Some(Directive::Glossary(vec![("term".into(), "Foo".into())]))
+        );
+        assert_eq!(
+            parse_directive("-- mt:glossary Iron Ore = 철광석, Sword=검, broken"),
+            Some(Directive::Glossary(vec![
+                ("Iron Ore".into(), "철광석".into()),
+                ("Sword".into(), "검".into()),
+            ]))
+        );
+
+        assert_eq!(parse_directive("# mt:glossary"), None);
+        assert_eq!(parse_directive("# mt:translate"), None);
+        assert_eq!(parse_directive("# see mt:skip below"), None);
+        assert_eq!(parse_directive("<label>mt:skip</label>"), None);
+        assert_eq!(parse_directive("<!-- EN: Shown on the title screen -->"), None);
+    }
+}
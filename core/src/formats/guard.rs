@@ -0,0 +1,352 @@
+/// Panic isolation for format handlers.
+///
+/// A bug in one handler must not take down the job that called it.
+/// [`GuardedHandler`] runs `extract`/`merge` under `catch_unwind`, turns a
+/// panic into [`FormatError::HandlerPanic`], appends the details to the
+/// handler panic log for bug reports and hands the file to
+/// [`LineFallbackHandler`], which translates it line by line. Binary files
+/// have no lines to fall back to, so `extract_bytes`/`merge_bytes` return
+/// the panic as an error.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use crate::paths::app_data_dir;
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Details of a handler panic, one JSON line in the panic log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandlerPanicReport {
+    pub format: FileFormat,
+    pub operation: String,
+    pub message: String,
+    pub input_bytes: usize,
+    pub occurred_at: String,
+}
+
+/// Log file that collects handler panics
+pub fn handler_panic_log_path() -> PathBuf {
+    app_data_dir().join("logs").join("handler-panics.jsonl")
+}
+
+/// Wraps a handler so its panics fall back to line-based handling.
+///
+/// Use one guard per file: once `extract` has fallen back, `merge` uses the
+/// fallback too so the line keys it handed out still match.
+pub struct GuardedHandler {
+    inner: Box<dyn FormatHandler>,
+    fell_back: AtomicBool,
+    panics: Mutex<Vec<HandlerPanicReport>>,
+    log_path: Option<PathBuf>,
+}
+
+impl GuardedHandler {
+    pub fn new(inner: Box<dyn FormatHandler>) -> Self {
+        Self {
+            inner,
+            fell_back: AtomicBool::new(false),
+            panics: Mutex::new(Vec::new()),
+            log_path: Some(handler_panic_log_path()),
+        }
+    }
+
+    /// Writes panic reports to `path` instead of the default log, or
+    /// nowhere when `None`
+    pub fn with_log_path(mut self, path: Option<PathBuf>) -> Self {
+        self.log_path = path;
+        self
+    }
+
+    /// Whether the file is being handled by the line-based fallback
+    pub fn used_fallback(&self) -> bool {
+        self.fell_back.load(Ordering::SeqCst)
+    }
+
+    /// Panics caught so far
+    pub fn panics(&self) -> Vec<HandlerPanicReport> {
+        self.panics
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// Runs `operation`, converting a panic into `FormatError::HandlerPanic`
+    fn guarded<T>(
+        &self,
+        operation: &'static str,
+        input_bytes: usize,
+        run: impl FnOnce() -> Result<T, FormatError>,
+    ) -> Result<T, FormatError> {
+        match panic::catch_unwind(AssertUnwindSafe(run)) {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                self.record(HandlerPanicReport {
+                    format: self.inner.format(),
+                    operation: operation.to_string(),
+                    message: message.clone(),
+                    input_bytes,
+                    occurred_at: chrono::Utc::now().to_rfc3339(),
+                });
+                Err(FormatError::HandlerPanic { operation, message })
+            }
+        }
+    }
+
+    fn record(&self, report: HandlerPanicReport) {
+        error!(
+            "{:?} handler panicked during {}: {}",
+            report.format, report.operation, report.message
+        );
+        if let Some(path) = &self.log_path {
+            if let Err(err) = append_report(path, &report) {
+                error!("failed to record handler panic: {}", err);
+            }
+        }
+        if let Ok(mut panics) = self.panics.lock() {
+            panics.push(report);
+        }
+    }
+}
+
+fn append_report(path: &Path, report: &HandlerPanicReport) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(report)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+impl FormatHandler for GuardedHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        if self.used_fallback() {
+            return LineFallbackHandler.extract(content);
+        }
+        match self.guarded("extract", content.len(), || self.inner.extract(content)) {
+            Err(FormatError::HandlerPanic { .. }) => {
+                self.fell_back.store(true, Ordering::SeqCst);
+                LineFallbackHandler.extract(content)
+            }
+            result => result,
+        }
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        if self.used_fallback() {
+            return LineFallbackHandler.merge(original, translations);
+        }
+        match self.guarded("merge", original.len(), || {
+            self.inner.merge(original, translations)
+        }) {
+            Err(FormatError::HandlerPanic { .. }) => {
+                self.fell_back.store(true, Ordering::SeqCst);
+                LineFallbackHandler.merge(original, translations)
+            }
+            result => result,
+        }
+    }
+
+    fn extract_bytes(
+        &self,
+        path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        self.guarded("extract", content.len(), || {
+            self.inner.extract_bytes(path, content)
+        })
+    }
+
+    fn merge_bytes(
+        &self,
+        path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        self.guarded("merge", original.len(), || {
+            self.inner
+                .merge_bytes(path, original, translations, target_lang)
+        })
+    }
+
+    fn format(&self) -> FileFormat {
+        self.inner.format()
+    }
+}
+
+/// Treats every non-empty line as one entry, like the translation job does.
+/// `merge` matches lines by source text, so it also applies translations
+/// that were extracted by another handler.
+pub struct LineFallbackHandler;
+
+impl FormatHandler for LineFallbackHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| TranslatableEntry {
+                key: format!("line:{}", index + 1),
+                source: line.trim().to_string(),
+                context: Some(format!("line {}", index + 1)),
+                metadata: HashMap::new(),
+            })
+            .collect())
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let targets: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.source.trim(), entry.target.as_str()))
+            .collect();
+
+        let mut merged: Vec<String> = Vec::new();
+        for line in original.lines() {
+            let trimmed = line.trim();
+            match targets.get(trimmed) {
+                Some(target) if !trimmed.is_empty() => {
+                    let start = line.find(trimmed).unwrap_or(0);
+                    let end = start + trimmed.len();
+                    merged.push(format!("{}{}{}", &line[..start], target, &line[end..]));
+                }
+                _ => merged.push(line.to_string()),
+            }
+        }
+
+        let line_ending = if original.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut output = merged.join(line_ending);
+        if original.ends_with('\n') {
+            output.push_str(line_ending);
+        }
+        Ok(output)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Txt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    struct BrokenHandler {
+        extract_panics: bool,
+    }
+
+    impl FormatHandler for BrokenHandler {
+        fn extract(&self, _content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+            if self.extract_panics {
+                panic!("index out of bounds");
+            }
+            Ok(vec![TranslatableEntry {
+                key: "greeting".into(),
+                source: "Hello".into(),
+                context: None,
+                metadata: HashMap::new(),
+            }])
+        }
+
+        fn merge(
+            &self,
+            _original: &str,
+            _translations: &TranslationResult,
+        ) -> Result<String, FormatError> {
+            panic!("{}", String::from("merge bug"));
+        }
+
+        fn format(&self) -> FileFormat {
+            FileFormat::Lua
+        }
+    }
+
+    fn translated(source: &str, target: &str) -> TranslationResult {
+        TranslationResult {
+            translated: vec![TranslatedEntry {
+                key: "any".into(),
+                source: source.into(),
+                target: target.into(),
+            }],
+            failed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn panicking_extract_falls_back_to_lines_and_is_logged() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("logs").join("panics.jsonl");
+        let guard = GuardedHandler::new(Box::new(BrokenHandler {
+            extract_panics: true,
+        }))
+        .with_log_path(Some(log.clone()));
+
+        let content = "  Hello\n\nBye\n";
+        let entries = guard.extract(content).unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["line:1", "line:3"]);
+        assert!(guard.used_fallback());
+
+        let merged = guard.merge(content, &translated("Hello", "안녕")).unwrap();
+        assert_eq!(merged, "  안녕\n\nBye\n");
+
+        let panics = guard.panics();
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].operation, "extract");
+        assert_eq!(panics[0].message, "index out of bounds");
+        assert_eq!(panics[0].format, FileFormat::Lua);
+
+        let logged: HandlerPanicReport =
+            serde_json::from_str(std::fs::read_to_string(&log).unwrap().trim()).unwrap();
+        assert_eq!(logged, panics[0]);
+    }
+
+    #[test]
+    fn panicking_merge_still_applies_translations() {
+        let guard = GuardedHandler::new(Box::new(BrokenHandler {
+            extract_panics: false,
+        }))
+        .with_log_path(None);
+
+        assert_eq!(guard.extract("x = Hello").unwrap()[0].key, "greeting");
+        assert!(!guard.used_fallback());
+
+        let merged = guard
+            .merge("Hello\nWorld", &translated("Hello", "안녕"))
+            .unwrap();
+        assert_eq!(merged, "안녕\nWorld");
+        assert_eq!(guard.panics()[0].message, "merge bug");
+    }
+
+    #[test]
+    fn line_fallback_keeps_crlf_line_endings() {
+        let merged = LineFallbackHandler
+            .merge("Hello\r\n  World\r\n", &translated("World", "세계"))
+            .unwrap();
+        assert_eq!(merged, "Hello\r\n  세계\r\n");
+    }
+}
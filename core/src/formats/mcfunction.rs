@@ -0,0 +1,391 @@
+/// Minecraft text component handler for `.mcfunction` and `.snbt` files
+/// Extracts the `"text"` values of JSON text components in commands such as
+/// `tellraw`, `title` and `bossbar`, including components nested in SNBT
+/// strings (`Name:"{\"text\":\"Sword\"}"`) and SNBT-style components
+/// (`{text:'Hello'}`). FTB Quests chapters have their own handler in
+/// `quests`. Selectors, NBT keys and every other component field are left
+/// untouched; selectors inside text are protected by the MCSEL token class.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ops::Range;
+
+// `"text":` with any level of escaping, e.g. `\"text\":` inside an SNBT string
+static JSON_TEXT_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(\\*)"text(\\*)"\s*:\s*"#).expect("valid JSON text key regex"));
+
+// SNBT component key: `{text:'Hello'}`
+static SNBT_TEXT_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[{,\s])text\s*:\s*").expect("valid SNBT text key regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    /// JSON string, possibly nested in `levels` SNBT strings
+    Json,
+    /// SNBT string, possibly nested in `levels` SNBT strings
+    Snbt,
+}
+
+#[derive(Debug)]
+struct TextValue {
+    key: String,
+    value: String,
+    /// Byte range of the encoded content, without delimiters
+    range: Range<usize>,
+    encoding: Encoding,
+    quote: char,
+    /// Number of enclosing SNBT strings (backslashes before each delimiter)
+    levels: usize,
+    context: String,
+}
+
+pub struct McFunctionHandler {
+    format: FileFormat,
+}
+
+impl McFunctionHandler {
+    /// Handler for `.mcfunction` command files
+    pub fn new() -> Self {
+        Self {
+            format: FileFormat::McFunction,
+        }
+    }
+
+    /// Handler for `.snbt` files
+    pub fn snbt() -> Self {
+        Self {
+            format: FileFormat::Snbt,
+        }
+    }
+
+    fn scan(&self, content: &str) -> Vec<TextValue> {
+        let mut values = Vec::new();
+        for found in JSON_TEXT_KEY_RE.captures_iter(content) {
+            let (Some(before), Some(after)) = (found.get(1), found.get(2)) else {
+                continue;
+            };
+            if before.len() != after.len() {
+                continue;
+            }
+            let start = found.get(0).expect("match").end();
+            values.extend(read_string(content, start, before.len(), Encoding::Json));
+        }
+        for found in SNBT_TEXT_KEY_RE.find_iter(content) {
+            values.extend(read_string(content, found.end(), 0, Encoding::Snbt));
+        }
+
+        values.sort_by_key(|value| value.range.start);
+        let mut last_end = 0;
+        values.retain(|value| {
+            let keep = value.range.start >= last_end;
+            if keep {
+                last_end = value.range.end;
+            }
+            keep
+        });
+
+        let is_function = self.format == FileFormat::McFunction;
+        values.retain_mut(|value| {
+            let line = line_at(content, value.range.start);
+            if is_function && line.trim_start().starts_with('#') {
+                return false;
+            }
+            value.context = line
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            value.key = position_key(content, value.range.start);
+            true
+        });
+        values
+    }
+}
+
+impl Default for McFunctionHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for McFunctionHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Ok(self
+            .scan(content)
+            .into_iter()
+            .filter(|value| value.value.chars().any(|c| c.is_alphabetic()))
+            .map(|value| TranslatableEntry {
+                key: value.key,
+                source: value.value,
+                context: Some(value.context),
+                metadata: HashMap::from([("type".to_string(), "text_component".to_string())]),
+            })
+            .collect())
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+        for value in self.scan(original) {
+            let Some(target) = translation_map.get(value.key.as_str()) else {
+                continue;
+            };
+            result.push_str(&original[last..value.range.start]);
+            result.push_str(&encode(target, value.encoding, value.quote, value.levels));
+            last = value.range.end;
+        }
+        result.push_str(&original[last..]);
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        self.format
+    }
+}
+
+/// Reads the string literal at `start`, delimited by `levels` backslashes
+/// and a quote. Returns `None` when no string starts there.
+fn read_string(
+    content: &str,
+    start: usize,
+    levels: usize,
+    encoding: Encoding,
+) -> Option<TextValue> {
+    let rest = &content[start..];
+    let after_escapes = rest.get(levels..)?;
+    if !rest.as_bytes()[..levels].iter().all(|b| *b == b'\\') {
+        return None;
+    }
+    let quote = after_escapes.chars().next()?;
+    let allowed = match encoding {
+        Encoding::Json => quote == '"',
+        Encoding::Snbt => quote == '"' || quote == '\'',
+    };
+    if !allowed {
+        return None;
+    }
+
+    let content_start = start + levels + 1;
+    let bytes = content.as_bytes();
+    let mut pos = content_start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => {
+                let run = bytes[pos..].iter().take_while(|b| **b == b'\\').count();
+                let next = bytes.get(pos + run).copied();
+                // Each level doubles the backslashes of the levels inside it, so
+                // the delimiter is the quote whose run is 2^levels - 1 modulo 2^(levels + 1)
+                let period = 1usize << (levels + 1);
+                if next == Some(quote as u8) && run % period == period / 2 - 1 {
+                    let end = pos + run - levels;
+                    return Some(text_value(
+                        content,
+                        content_start..end,
+                        encoding,
+                        quote,
+                        levels,
+                    ));
+                }
+                // An escaped character, at this level or a deeper one
+                pos += run + usize::from(next.is_some());
+            }
+            b'\n' => return None,
+            b if b == quote as u8 => {
+                if levels == 0 {
+                    return Some(text_value(
+                        content,
+                        content_start..pos,
+                        encoding,
+                        quote,
+                        levels,
+                    ));
+                }
+                // A bare quote ends the enclosing string: malformed
+                return None;
+            }
+            _ => pos += 1,
+        }
+    }
+    None
+}
+
+/// SNBT quoted string at `start`: its decoded value, the byte range of its
+/// content and its quote character
+pub fn read_snbt_string(content: &str, start: usize) -> Option<(String, Range<usize>, char)> {
+    read_string(content, start, 0, Encoding::Snbt)
+        .map(|value| (value.value, value.range, value.quote))
+}
+
+fn text_value(
+    content: &str,
+    range: Range<usize>,
+    encoding: Encoding,
+    quote: char,
+    levels: usize,
+) -> TextValue {
+    let mut value = content[range.clone()].to_string();
+    for _ in 0..levels {
+        value = unescape_snbt(&value);
+    }
+    let value = match encoding {
+        Encoding::Json => unescape_json(&value),
+        Encoding::Snbt => unescape_snbt(&value),
+    };
+    TextValue {
+        key: String::new(),
+        value,
+        range,
+        encoding,
+        quote,
+        levels,
+        context: String::new(),
+    }
+}
+
+fn encode(text: &str, encoding: Encoding, quote: char, levels: usize) -> String {
+    let mut encoded = match encoding {
+        Encoding::Json => escape_json(text),
+        Encoding::Snbt => escape_snbt(text, quote),
+    };
+    // Enclosing SNBT strings are always double quoted
+    for _ in 0..levels {
+        encoded = escape_snbt(&encoded, '"');
+    }
+    encoded
+}
+
+/// Removes one layer of SNBT escaping: `\\`, `\"` and `\'`
+fn unescape_snbt(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(next @ ('\\' | '"' | '\'')) => result.push(next),
+            Some(next) => {
+                result.push('\\');
+                result.push(next);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Escapes `text` for an SNBT string delimited by `quote`
+pub fn escape_snbt(text: &str, quote: char) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\\' || c == quote {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn unescape_json(text: &str) -> String {
+    serde_json::from_str::<String>(&format!("\"{}\"", text)).unwrap_or_else(|_| text.to_string())
+}
+
+fn escape_json(text: &str) -> String {
+    let quoted = serde_json::to_string(text).unwrap_or_else(|_| format!("\"{}\"", text));
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn line_at(content: &str, offset: usize) -> &str {
+    let start = content[..offset].rfind('\n').map_or(0, |pos| pos + 1);
+    let end = content[offset..]
+        .find('\n')
+        .map_or(content.len(), |pos| offset + pos);
+    &content[start..end]
+}
+
+/// `line:column` of a byte offset, used as a stable entry key
+pub fn position_key(content: &str, offset: usize) -> String {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    let column = content[line_start..offset].chars().count() + 1;
+    format!("{}:{}", line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const FUNCTION: &str = r#"# {"text":"comment, not shown"}
+tellraw @a ["",{"text":"Welcome, ","color":"gold"},{"selector":"@p"},{"text":"say \"hi\""}]
+title @p subtitle {text:'Round two'}
+give @p diamond_sword{display:{Name:"{\"text\":\"Blade of \\\"Dawn\\\"\"}"}} 1
+scoreboard players set @s timer 20
+"#;
+
+    fn translate(handler: &McFunctionHandler, content: &str, targets: &[(&str, &str)]) -> String {
+        let entries = handler.extract(content).unwrap();
+        let translations = TranslationResult {
+            translated: targets
+                .iter()
+                .map(|(source, target)| {
+                    let entry = entries.iter().find(|e| e.source == *source).unwrap();
+                    TranslatedEntry {
+                        key: entry.key.clone(),
+                        source: entry.source.clone(),
+                        target: target.to_string(),
+                    }
+                })
+                .collect(),
+            failed: vec![],
+        };
+        handler.merge(content, &translations).unwrap()
+    }
+
+    #[test]
+    fn extracts_text_components_at_any_nesting() {
+        let entries = McFunctionHandler::new().extract(FUNCTION).unwrap();
+        let sources: Vec<&str> = entries.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(
+            sources,
+            vec!["Welcome, ", "say \"hi\"", "Round two", "Blade of \"Dawn\""]
+        );
+        assert_eq!(entries[0].context.as_deref(), Some("tellraw"));
+        assert_eq!(entries[0].key, "2:25");
+    }
+
+    #[test]
+    fn merge_re_escapes_for_each_level() {
+        let handler = McFunctionHandler::new();
+        let merged = translate(
+            &handler,
+            FUNCTION,
+            &[
+                ("say \"hi\"", "\"안녕\"이라고 말해"),
+                ("Round two", "2라운드 '시작'"),
+                ("Blade of \"Dawn\"", "\"새벽\"의 검"),
+            ],
+        );
+        assert!(merged.contains(r#"{"text":"\"안녕\"이라고 말해"}]"#));
+        assert!(merged.contains(r"{text:'2라운드 \'시작\''}"));
+        assert!(merged.contains(r#"Name:"{\"text\":\"\\\"새벽\\\"의 검\"}""#));
+        assert!(merged.contains(r#"{"selector":"@p"}"#));
+
+        let reparsed = handler.extract(&merged).unwrap();
+        assert!(reparsed.iter().any(|e| e.source == "\"새벽\"의 검"));
+        assert!(reparsed.iter().any(|e| e.source == "2라운드 '시작'"));
+    }
+}
@@ -0,0 +1,588 @@
+/// Gettext `.mo` binary catalogs
+/// A 28-byte header (magic, revision, message count, offsets of the original
+/// and translation tables, hash table size and offset) is followed by two
+/// tables of `(length, offset)` pairs pointing at NUL-terminated strings.
+/// Contexts are joined to the msgid with `\x04`, plural msgids and plural
+/// translations are separated by `\0`. Both byte orders are read and written
+/// back as found. Output is sorted by msgid and has no hash table, so gettext
+/// looks messages up by binary search.
+/// Catalogs are binary, so [`MoHandler`] only implements the byte methods
+/// of [`FormatHandler`].
+use super::{
+    binary_only, FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+const MAGIC: u32 = 0x9504_12de;
+const HEADER_LEN: usize = 28;
+const CONTEXT_SEPARATOR: char = '\u{4}';
+
+// Plural-Forms headers for common target languages
+const PLURAL_FORMS: &[(&str, &str)] = &[
+    ("ko", "nplurals=1; plural=0;"),
+    ("ja", "nplurals=1; plural=0;"),
+    ("zh", "nplurals=1; plural=0;"),
+    ("vi", "nplurals=1; plural=0;"),
+    ("th", "nplurals=1; plural=0;"),
+    ("en", "nplurals=2; plural=(n != 1);"),
+    ("de", "nplurals=2; plural=(n != 1);"),
+    ("es", "nplurals=2; plural=(n != 1);"),
+    ("it", "nplurals=2; plural=(n != 1);"),
+    ("nl", "nplurals=2; plural=(n != 1);"),
+    ("sv", "nplurals=2; plural=(n != 1);"),
+    ("pt", "nplurals=2; plural=(n != 1);"),
+    ("tr", "nplurals=2; plural=(n != 1);"),
+    ("fr", "nplurals=2; plural=(n > 1);"),
+    (
+        "ru",
+        "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);",
+    ),
+    (
+        "uk",
+        "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);",
+    ),
+    (
+        "pl",
+        "nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    fn read(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            ByteOrder::Little => u32::from_le_bytes(bytes),
+            ByteOrder::Big => u32::from_be_bytes(bytes),
+        }
+    }
+
+    fn write(self, value: u32) -> [u8; 4] {
+        match self {
+            ByteOrder::Little => value.to_le_bytes(),
+            ByteOrder::Big => value.to_be_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoMessage {
+    pub context: Option<String>,
+    /// Empty for the catalog header
+    pub msgid: String,
+    pub msgid_plural: Option<String>,
+    /// One string for singular messages, one per plural form otherwise
+    pub translations: Vec<String>,
+}
+
+impl MoMessage {
+    fn key(&self) -> String {
+        match &self.context {
+            Some(context) => format!("{context}{CONTEXT_SEPARATOR}{}", self.msgid),
+            None => self.msgid.clone(),
+        }
+    }
+
+    fn original_bytes(&self) -> Vec<u8> {
+        let mut original = self.key().into_bytes();
+        if let Some(plural) = &self.msgid_plural {
+            original.push(0);
+            original.extend_from_slice(plural.as_bytes());
+        }
+        original
+    }
+
+    fn is_header(&self) -> bool {
+        self.context.is_none() && self.msgid.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoCatalog {
+    pub byte_order: ByteOrder,
+    pub revision: u32,
+    pub messages: Vec<MoMessage>,
+}
+
+impl MoCatalog {
+    pub fn parse(bytes: &[u8]) -> Result<Self, FormatError> {
+        let magic = bytes
+            .get(..4)
+            .map(|slice| [slice[0], slice[1], slice[2], slice[3]])
+            .ok_or_else(|| parse_error("file is shorter than the .mo header".to_string()))?;
+        let byte_order = if u32::from_le_bytes(magic) == MAGIC {
+            ByteOrder::Little
+        } else if u32::from_be_bytes(magic) == MAGIC {
+            ByteOrder::Big
+        } else {
+            return Err(parse_error(
+                "not a gettext .mo file (bad magic number)".to_string(),
+            ));
+        };
+
+        let read = |at: usize| read_u32(bytes, at, byte_order);
+        let revision = read(4)?;
+        if revision >> 16 != 0 {
+            return Err(parse_error(format!(
+                "unsupported .mo major revision {}",
+                revision >> 16
+            )));
+        }
+        let count = read(8)? as usize;
+        let originals_at = read(12)? as usize;
+        let translations_at = read(16)? as usize;
+
+        let mut messages = Vec::with_capacity(count.min(bytes.len() / 16));
+        for index in 0..count {
+            let original = read_string(bytes, originals_at + index * 8, byte_order)?;
+            let translation = read_string(bytes, translations_at + index * 8, byte_order)?;
+
+            let (context, ids) = match original.split_once(CONTEXT_SEPARATOR) {
+                Some((context, ids)) => (Some(context.to_string()), ids),
+                None => (None, original),
+            };
+            let (msgid, msgid_plural) = match ids.split_once('\0') {
+                Some((msgid, plural)) => (msgid.to_string(), Some(plural.to_string())),
+                None => (ids.to_string(), None),
+            };
+            let translations = if msgid_plural.is_some() {
+                translation.split('\0').map(str::to_string).collect()
+            } else {
+                vec![translation.to_string()]
+            };
+
+            messages.push(MoMessage {
+                context,
+                msgid,
+                msgid_plural,
+                translations,
+            });
+        }
+
+        Ok(Self {
+            byte_order,
+            revision,
+            messages,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mut messages: Vec<(Vec<u8>, Vec<u8>)> = self
+            .messages
+            .iter()
+            .map(|message| {
+                (
+                    message.original_bytes(),
+                    message.translations.join("\0").into_bytes(),
+                )
+            })
+            .collect();
+        // gettext binary-searches the original table
+        messages.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let count = messages.len();
+        let originals_at = HEADER_LEN;
+        let translations_at = originals_at + count * 8;
+        let strings_at = translations_at + count * 8;
+
+        let order = self.byte_order;
+        let mut original_table = Vec::with_capacity(count * 8);
+        let mut translation_table = Vec::with_capacity(count * 8);
+        let mut strings = Vec::new();
+        for (original, translation) in &messages {
+            for (bytes, table) in [
+                (original, &mut original_table),
+                (translation, &mut translation_table),
+            ] {
+                let offset = to_u32(strings_at + strings.len())?;
+                table.extend_from_slice(&order.write(to_u32(bytes.len())?));
+                table.extend_from_slice(&order.write(offset));
+                strings.extend_from_slice(bytes);
+                strings.push(0);
+            }
+        }
+
+        let mut out = Vec::with_capacity(strings_at + strings.len());
+        for value in [
+            MAGIC,
+            self.revision,
+            to_u32(count)?,
+            to_u32(originals_at)?,
+            to_u32(translations_at)?,
+            0,
+            to_u32(strings_at)?,
+        ] {
+            out.extend_from_slice(&order.write(value));
+        }
+        out.extend_from_slice(&original_table);
+        out.extend_from_slice(&translation_table);
+        out.extend_from_slice(&strings);
+        Ok(out)
+    }
+
+    /// The catalog header (`Project-Id-Version: ...`), stored under the empty msgid
+    pub fn header(&self) -> Option<&str> {
+        self.messages
+            .iter()
+            .find(|message| message.is_header())
+            .and_then(|message| message.translations.first())
+            .map(String::as_str)
+    }
+
+    /// Number of plural forms declared by the header's `Plural-Forms` field
+    pub fn plural_count(&self) -> Option<usize> {
+        let header = self.header()?;
+        let forms = header_field(header, "Plural-Forms")?;
+        forms
+            .split(';')
+            .filter_map(|part| part.trim().strip_prefix("nplurals="))
+            .find_map(|count| count.trim().parse().ok())
+    }
+
+    /// Sets or replaces a header field, creating the header if needed
+    pub fn set_header_field(&mut self, name: &str, value: &str) {
+        let index = match self.messages.iter().position(MoMessage::is_header) {
+            Some(index) => index,
+            None => {
+                self.messages.insert(
+                    0,
+                    MoMessage {
+                        context: None,
+                        msgid: String::new(),
+                        msgid_plural: None,
+                        translations: vec![String::new()],
+                    },
+                );
+                0
+            }
+        };
+        let header = &mut self.messages[index].translations[0];
+        let prefix = format!("{name}:");
+        let mut lines: Vec<String> = header
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        let field = format!("{name}: {value}");
+        match lines.iter_mut().find(|line| line.starts_with(&prefix)) {
+            Some(line) => *line = field,
+            None => lines.push(field),
+        }
+        *header = lines.join("\n") + "\n";
+    }
+
+    /// Translatable entries; plural messages yield `key[0]` for the singular
+    /// msgid and `key[1]` for the plural msgid
+    pub fn extract(&self) -> Vec<TranslatableEntry> {
+        let mut entries = Vec::new();
+        for message in self.messages.iter().filter(|message| !message.is_header()) {
+            let key = message.key();
+            let mut metadata = HashMap::new();
+            if let Some(context) = &message.context {
+                metadata.insert("msgctxt".to_string(), context.clone());
+            }
+            let context = message.context.clone();
+
+            match &message.msgid_plural {
+                None => entries.push(TranslatableEntry {
+                    key,
+                    source: message.msgid.clone(),
+                    context,
+                    metadata,
+                }),
+                Some(plural) => {
+                    for (index, source) in [&message.msgid, plural].into_iter().enumerate() {
+                        let mut metadata = metadata.clone();
+                        let form = if index == 0 { "singular" } else { "plural" };
+                        metadata.insert("plural_form".to_string(), form.to_string());
+                        entries.push(TranslatableEntry {
+                            key: format!("{key}[{index}]"),
+                            source: source.clone(),
+                            context: context.clone(),
+                            metadata,
+                        });
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /// Writes translations into the catalog for `target_lang`. The header's
+    /// `Language` and, for known languages, `Plural-Forms` are updated, and
+    /// plural messages get one string per target plural form: the singular
+    /// translation first when the language has more than one form, the plural
+    /// translation for every other form.
+    pub fn apply(&mut self, translations: &TranslationResult, target_lang: &str) {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        self.set_header_field("Language", &target_lang.replace('-', "_"));
+        if let Some(forms) = plural_forms_for(target_lang) {
+            self.set_header_field("Plural-Forms", forms);
+        }
+        let plural_count = self.plural_count().unwrap_or(2).max(1);
+
+        for message in self
+            .messages
+            .iter_mut()
+            .filter(|message| !message.is_header())
+        {
+            let key = message.key();
+            if message.msgid_plural.is_none() {
+                if let Some(target) = translation_map.get(key.as_str()) {
+                    message.translations = vec![target.to_string()];
+                }
+                continue;
+            }
+
+            let singular = translation_map.get(format!("{key}[0]").as_str()).copied();
+            let plural = translation_map.get(format!("{key}[1]").as_str()).copied();
+            let Some(plural) = plural.or(singular) else {
+                continue;
+            };
+            let singular = singular.unwrap_or(plural);
+            message.translations = (0..plural_count)
+                .map(|form| {
+                    if form == 0 && plural_count > 1 {
+                        singular.to_string()
+                    } else {
+                        plural.to_string()
+                    }
+                })
+                .collect();
+        }
+    }
+}
+
+pub struct MoHandler;
+
+impl MoHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MoHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for MoHandler {
+    fn extract(&self, _content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Err(binary_only(FileFormat::Mo))
+    }
+
+    fn merge(
+        &self,
+        _original: &str,
+        _translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        Err(binary_only(FileFormat::Mo))
+    }
+
+    fn extract_bytes(
+        &self,
+        _path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Ok(MoCatalog::parse(content)?.extract())
+    }
+
+    fn merge_bytes(
+        &self,
+        _path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        let mut catalog = MoCatalog::parse(original)?;
+        catalog.apply(translations, target_lang);
+        catalog.to_bytes()
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Mo
+    }
+}
+
+/// `Plural-Forms` header value for a language, if known
+pub fn plural_forms_for(lang: &str) -> Option<&'static str> {
+    let primary = lang.split(['-', '_']).next()?.to_ascii_lowercase();
+    PLURAL_FORMS
+        .iter()
+        .find(|(code, _)| *code == primary)
+        .map(|(_, forms)| *forms)
+}
+
+fn header_field<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header.lines().find_map(|line| {
+        let (field, value) = line.split_once(':')?;
+        field
+            .trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim())
+    })
+}
+
+fn parse_error(message: String) -> FormatError {
+    FormatError::ParseError(message)
+}
+
+fn read_u32(bytes: &[u8], at: usize, order: ByteOrder) -> Result<u32, FormatError> {
+    bytes
+        .get(at..at + 4)
+        .map(|slice| order.read([slice[0], slice[1], slice[2], slice[3]]))
+        .ok_or_else(|| parse_error(format!("unexpected end of .mo file at byte {at}")))
+}
+
+/// Reads the string described by the `(length, offset)` pair at `entry_at`
+fn read_string(bytes: &[u8], entry_at: usize, order: ByteOrder) -> Result<&str, FormatError> {
+    let length = read_u32(bytes, entry_at, order)? as usize;
+    let offset = read_u32(bytes, entry_at + 4, order)? as usize;
+    let raw = offset
+        .checked_add(length)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| parse_error(format!("string at offset {offset} runs past the file")))?;
+    std::str::from_utf8(raw).map_err(|error| {
+        FormatError::EncodingError(format!("string at offset {offset} is not UTF-8: {error}"))
+    })
+}
+
+fn to_u32(value: usize) -> Result<u32, FormatError> {
+    u32::try_from(value).map_err(|_| {
+        FormatError::SerializationError("catalog is too large for the .mo format".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    fn sample(byte_order: ByteOrder) -> MoCatalog {
+        MoCatalog {
+            byte_order,
+            revision: 0,
+            messages: vec![
+                MoMessage {
+                    context: None,
+                    msgid: String::new(),
+                    msgid_plural: None,
+                    translations: vec![
+                        "Content-Type: text/plain; charset=UTF-8\nPlural-Forms: nplurals=2; plural=(n != 1);\n"
+                            .to_string(),
+                    ],
+                },
+                MoMessage {
+                    context: Some("menu".to_string()),
+                    msgid: "Open".to_string(),
+                    msgid_plural: None,
+                    translations: vec!["Open".to_string()],
+                },
+                MoMessage {
+                    context: None,
+                    msgid: "%d item".to_string(),
+                    msgid_plural: Some("%d items".to_string()),
+                    translations: vec!["%d item".to_string(), "%d items".to_string()],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_both_byte_orders() {
+        for order in [ByteOrder::Little, ByteOrder::Big] {
+            let catalog = sample(order);
+            let bytes = catalog.to_bytes().unwrap();
+            let parsed = MoCatalog::parse(&bytes).unwrap();
+            assert_eq!(parsed.byte_order, order);
+            // Written sorted by msgid: header, "%d item", "menu\x04Open"
+            assert_eq!(parsed.messages[0], catalog.messages[0]);
+            assert_eq!(parsed.messages[1], catalog.messages[2]);
+            assert_eq!(parsed.messages[2], catalog.messages[1]);
+            assert_eq!(parsed.plural_count(), Some(2));
+        }
+        let little = sample(ByteOrder::Little).to_bytes().unwrap();
+        assert_eq!(&little[..4], &[0xde, 0x12, 0x04, 0x95]);
+    }
+
+    #[test]
+    fn extracts_contexts_and_plural_forms() {
+        let entries = sample(ByteOrder::Little).extract();
+        let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["menu\u{4}Open", "%d item[0]", "%d item[1]"]);
+        assert_eq!(
+            entries[0].metadata.get("msgctxt").map(String::as_str),
+            Some("menu")
+        );
+        assert_eq!(entries[2].source, "%d items");
+        assert_eq!(
+            entries[2].metadata.get("plural_form").map(String::as_str),
+            Some("plural")
+        );
+    }
+
+    #[test]
+    fn applies_translations_with_target_plural_forms() {
+        let translated = |key: &str, target: &str| TranslatedEntry {
+            key: key.into(),
+            source: String::new(),
+            target: target.into(),
+        };
+        let translations = TranslationResult {
+            translated: vec![
+                translated("menu\u{4}Open", "열기"),
+                translated("%d item[0]", "아이템 %d개"),
+                translated("%d item[1]", "아이템 %d개"),
+            ],
+            failed: vec![],
+        };
+
+        let mut korean = sample(ByteOrder::Little);
+        korean.apply(&translations, "ko-KR");
+        assert_eq!(korean.plural_count(), Some(1));
+        assert!(korean.header().unwrap().contains("Language: ko_KR\n"));
+        assert!(korean
+            .header()
+            .unwrap()
+            .starts_with("Content-Type: text/plain; charset=UTF-8\n"));
+        assert_eq!(korean.messages[1].translations, vec!["열기"]);
+        assert_eq!(korean.messages[2].translations, vec!["아이템 %d개"]);
+
+        let mut russian = sample(ByteOrder::Little);
+        russian.apply(
+            &TranslationResult {
+                translated: vec![
+                    translated("%d item[0]", "%d предмет"),
+                    translated("%d item[1]", "%d предметов"),
+                ],
+                failed: vec![],
+            },
+            "ru",
+        );
+        assert_eq!(
+            russian.messages[2].translations,
+            vec!["%d предмет", "%d предметов", "%d предметов"]
+        );
+        let reparsed = MoCatalog::parse(&russian.to_bytes().unwrap()).unwrap();
+        assert_eq!(reparsed.plural_count(), Some(3));
+    }
+
+    #[test]
+    fn rejects_non_mo_data() {
+        assert!(MoCatalog::parse(b"msgid \"\"\nmsgstr \"\"\n").is_err());
+        let mut truncated = sample(ByteOrder::Little).to_bytes().unwrap();
+        truncated.truncate(40);
+        assert!(MoCatalog::parse(&truncated).is_err());
+    }
+}
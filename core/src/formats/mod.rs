@@ -9,7 +9,21 @@ pub mod csv;
 pub mod properties;
 pub mod lua;
 pub mod txt;
+pub mod unity_asset;
+#[cfg(feature = "unity-bundles")]
+pub mod unity_bundle;
 pub mod markdown;
+pub mod paradox;
+pub mod resx;
+pub mod renpy;
+pub mod bethesda;
+pub mod mo;
+pub mod toml;
+pub mod mcfunction;
+pub mod quests;
+pub mod guard;
+pub mod directive;
+pub mod plugin;
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -31,6 +45,12 @@ pub enum FormatError {
     
     #[error("Encoding error: {0}")]
     EncodingError(String),
+    
+    #[error("Handler panicked during {operation}: {message}")]
+    HandlerPanic {
+        operation: &'static str,
+        message: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -47,6 +67,18 @@ pub enum FileFormat {
     Lua,
     Txt,
     Markdown,
+    Resx,
+    Paradox,
+    UnityAsset,
+    RenPy,
+    Toml,
+    McFunction,
+    Snbt,
+    FtbQuests,
+    BetterQuesting,
+    Bethesda,
+    Mo,
+    UnityBundle,
     Unknown,
 }
 
@@ -65,16 +97,81 @@ impl FileFormat {
             "lua" => Self::Lua,
             "txt" => Self::Txt,
             "md" | "markdown" => Self::Markdown,
+            "resx" | "resw" => Self::Resx,
+            "asset" => Self::UnityAsset,
+            "rpy" => Self::RenPy,
+            "toml" => Self::Toml,
+            "mcfunction" => Self::McFunction,
+            "snbt" => Self::Snbt,
+            "strings" | "dlstrings" | "ilstrings" => Self::Bethesda,
+            "mo" => Self::Mo,
+            #[cfg(feature = "unity-bundles")]
+            "unity3d" | "assetbundle" => Self::UnityBundle,
             _ => Self::Unknown,
         }
     }
     
     /// Detect format from path
     pub fn from_path(path: &Path) -> Self {
-        path.extension()
+        let format = path.extension()
             .and_then(|ext| ext.to_str())
             .map(Self::from_extension)
-            .unwrap_or(Self::Unknown)
+            .unwrap_or(Self::Unknown);
+        
+        // Paradox localisation shares the .yml extension with YAML
+        let is_paradox = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(paradox::ParadoxHandler::is_localisation_file_name);
+        if format == Self::Yaml && is_paradox {
+            return Self::Paradox;
+        }
+        
+        // Quest books use their own handler so only quest text is translated
+        if format == Self::Snbt && quests::QuestHandler::is_ftb_quests_path(path) {
+            return Self::FtbQuests;
+        }
+        if format == Self::Json && quests::QuestHandler::is_better_questing_path(path) {
+            return Self::BetterQuesting;
+        }
+        format
+    }
+    
+    /// Whether translation jobs translate the format entry by entry through
+    /// its handler, so only the text it extracts reaches the model and the
+    /// output is its merge. The other formats are translated line by line.
+    pub fn translates_entries(self) -> bool {
+        matches!(
+            self,
+            Self::RenPy
+                | Self::Toml
+                | Self::McFunction
+                | Self::Snbt
+                | Self::Resx
+                | Self::Paradox
+                | Self::UnityAsset
+                | Self::FtbQuests
+                | Self::BetterQuesting
+                | Self::Bethesda
+                | Self::Mo
+                | Self::UnityBundle
+        )
+    }
+    
+    /// Whether files of this format are binary, so jobs read and write their
+    /// raw bytes through [`FormatHandler::extract_bytes`] and
+    /// [`FormatHandler::merge_bytes`] instead of decoding them as text
+    pub fn is_binary(self) -> bool {
+        matches!(self, Self::Bethesda | Self::Mo | Self::UnityBundle)
+    }
+    
+    /// Refine a path-based guess using the file content, for Paradox files
+    /// that do not follow the `_l_<language>.yml` naming convention
+    pub fn refine_with_content(self, content: &str) -> Self {
+        if self == Self::Yaml && paradox::ParadoxHandler::is_localisation_content(content) {
+            Self::Paradox
+        } else {
+            self
+        }
     }
 }
 
@@ -131,12 +228,51 @@ pub trait FormatHandler: Send + Sync {
         translations: &TranslationResult,
     ) -> Result<String, FormatError>;
     
+    /// Extract translatable entries from the raw bytes of the file at
+    /// `path`. Text formats read the bytes as UTF-8.
+    fn extract_bytes(
+        &self,
+        _path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        self.extract(utf8(content)?)
+    }
+    
+    /// Merge translations into the raw bytes of the file at `path`,
+    /// returning the bytes of the file translated into `target_lang`
+    fn merge_bytes(
+        &self,
+        _path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        _target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        self.merge(utf8(original)?, translations)
+            .map(String::into_bytes)
+    }
+    
     /// Get the format this handler supports
     fn format(&self) -> FileFormat;
 }
 
-/// Get appropriate handler for a file
+fn utf8(bytes: &[u8]) -> Result<&str, FormatError> {
+    std::str::from_utf8(bytes).map_err(|e| FormatError::EncodingError(e.to_string()))
+}
+
+/// Error returned by the text methods of handlers for binary formats
+fn binary_only(format: FileFormat) -> FormatError {
+    FormatError::UnsupportedFormat(format!("{:?} files are binary; read them as bytes", format))
+}
+
+/// Get appropriate handler for a file. Handlers are wrapped in a
+/// [`guard::GuardedHandler`], so a panicking handler degrades to line-based
+/// handling instead of aborting the caller.
 pub fn get_handler(format: FileFormat) -> Option<Box<dyn FormatHandler>> {
+    unguarded_handler(format)
+        .map(|handler| Box::new(guard::GuardedHandler::new(handler)) as Box<dyn FormatHandler>)
+}
+
+fn unguarded_handler(format: FileFormat) -> Option<Box<dyn FormatHandler>> {
     match format {
         FileFormat::Xml => Some(Box::new(xml::XmlHandler::new())),
         FileFormat::Json => Some(Box::new(json::JsonHandler::new())),
@@ -148,6 +284,21 @@ pub fn get_handler(format: FileFormat) -> Option<Box<dyn FormatHandler>> {
         FileFormat::Lua => Some(Box::new(lua::LuaHandler::new())),
         FileFormat::Markdown => Some(Box::new(markdown::MarkdownHandler::new())),
         FileFormat::Txt => Some(Box::new(txt::TxtHandler::new())),
+        FileFormat::Resx => Some(Box::new(resx::ResxHandler::new())),
+        FileFormat::Paradox => Some(Box::new(paradox::ParadoxHandler::new())),
+        FileFormat::UnityAsset => Some(Box::new(unity_asset::UnityAssetHandler::new())),
+        FileFormat::RenPy => Some(Box::new(renpy::RenpyHandler::new())),
+        FileFormat::Toml => Some(Box::new(toml::TomlHandler::new())),
+        FileFormat::McFunction => Some(Box::new(mcfunction::McFunctionHandler::new())),
+        FileFormat::Snbt => Some(Box::new(mcfunction::McFunctionHandler::snbt())),
+        FileFormat::FtbQuests => Some(Box::new(quests::QuestHandler::ftb_quests())),
+        FileFormat::BetterQuesting => Some(Box::new(quests::QuestHandler::better_questing())),
+        FileFormat::Bethesda => Some(Box::new(bethesda::BethesdaHandler::new())),
+        FileFormat::Mo => Some(Box::new(mo::MoHandler::new())),
+        #[cfg(feature = "unity-bundles")]
+        FileFormat::UnityBundle => Some(Box::new(unity_bundle::UnityBundleHandler::new())),
+        #[cfg(not(feature = "unity-bundles"))]
+        FileFormat::UnityBundle => None,
         FileFormat::Unknown => None,
     }
 }
@@ -0,0 +1,211 @@
+/// Paradox localisation format handler (CK3, EU4, Stellaris, HOI4, ...)
+/// Files look like YAML but are not: a `l_english:` header followed by
+/// ` KEY:0 "value"` lines, where the number is an optional version.
+/// Keys, versions, comments and the header are preserved; only the quoted
+/// value is translated.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+// Language header: l_english:, l_simp_chinese:
+static HEADER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*l_[a-z_]+:\s*(?:#.*)?$").expect("valid header regex")
+});
+
+// Entry line: indentation, key, optional version, quoted value, trailing comment.
+// The value runs to the last quote because Paradox does not escape inner quotes.
+static ENTRY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(\s*)([A-Za-z0-9_.\-']+):(\d*)(\s*)"(.*)"(\s*(?:#.*)?)$"#)
+        .expect("valid entry regex")
+});
+
+// Paradox file names end in the language: events_l_english.yml
+static FILE_NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)_l_[a-z_]+\.yml$").expect("valid file name regex")
+});
+
+pub struct ParadoxHandler;
+
+impl ParadoxHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check whether a file name follows the `*_l_<language>.yml` convention
+    pub fn is_localisation_file_name(name: &str) -> bool {
+        FILE_NAME_RE.is_match(name)
+    }
+
+    /// Check whether content starts with a Paradox language header
+    pub fn is_localisation_content(content: &str) -> bool {
+        content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .is_some_and(|line| HEADER_RE.is_match(line))
+    }
+}
+
+impl Default for ParadoxHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for ParadoxHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let content = content.trim_start_matches('\u{feff}');
+        if !Self::is_localisation_content(content) {
+            return Err(FormatError::ParseError(
+                "missing Paradox language header (e.g. l_english:)".to_string(),
+            ));
+        }
+
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let Some(cap) = ENTRY_RE.captures(line) else {
+                continue;
+            };
+            let value = &cap[5];
+            if value.trim().is_empty() {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            metadata.insert("line".to_string(), (index + 1).to_string());
+            if !cap[3].is_empty() {
+                metadata.insert("version".to_string(), cap[3].to_string());
+            }
+
+            entries.push(TranslatableEntry {
+                key: cap[2].to_string(),
+                source: value.to_string(),
+                context: Some(format!("line {}", index + 1)),
+                metadata,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(original.len());
+        for line in original.split_inclusive('\n') {
+            let body = line.trim_end_matches(['\r', '\n']);
+            let ending = &line[body.len()..];
+
+            let rebuilt = ENTRY_RE.captures(body).and_then(|cap| {
+                let target = translation_map.get(&cap[2])?;
+                // Paradox values are single-line; keep newlines as \n escapes
+                let target = target.replace("\r\n", "\\n").replace('\n', "\\n");
+                Some(format!(
+                    "{}{}:{}{}\"{}\"{}",
+                    &cap[1], &cap[2], &cap[3], &cap[4], target, &cap[6]
+                ))
+            });
+
+            match rebuilt {
+                Some(rebuilt) => result.push_str(&rebuilt),
+                None => result.push_str(body),
+            }
+            result.push_str(ending);
+        }
+
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Paradox
+    }
+}
+
+/// Checks that `content` has a language header and that every other line
+/// is a comment or a `KEY:0 "value"` entry
+pub fn check_syntax(content: &str) -> Result<(), FormatError> {
+    let content = content.trim_start_matches('\u{feff}');
+    if !ParadoxHandler::is_localisation_content(content) {
+        return Err(FormatError::ParseError(
+            "missing Paradox language header (e.g. l_english:)".to_string(),
+        ));
+    }
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !HEADER_RE.is_match(line) && !ENTRY_RE.is_match(line) {
+            return Err(FormatError::ParseError(format!(
+                "line {} is not a KEY:0 \"value\" entry",
+                index + 1
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const SAMPLE: &str = "\u{feff}l_english:\n # Events\n event.1.t:0 \"The §YGreat§! Feast\"\n event.1.d: \"$NAME$ invites [Root.GetName]\" # shown in popup\n empty_key:0 \"\"\n";
+
+    #[test]
+    fn extracts_quoted_values_with_versions() {
+        let entries = ParadoxHandler::new().extract(SAMPLE).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].key, "event.1.t");
+        assert_eq!(entries[0].source, "The §YGreat§! Feast");
+        assert_eq!(entries[0].metadata.get("version").map(String::as_str), Some("0"));
+        assert_eq!(entries[1].source, "$NAME$ invites [Root.GetName]");
+        assert!(!entries[1].metadata.contains_key("version"));
+    }
+
+    #[test]
+    fn merge_keeps_header_versions_and_comments() {
+        let translations = TranslationResult {
+            translated: vec![
+                TranslatedEntry {
+                    key: "event.1.t".into(),
+                    source: "The §YGreat§! Feast".into(),
+                    target: "§Y위대한§! 연회".into(),
+                },
+                TranslatedEntry {
+                    key: "event.1.d".into(),
+                    source: "$NAME$ invites [Root.GetName]".into(),
+                    target: "$NAME$이(가) [Root.GetName]을(를)\n초대합니다".into(),
+                },
+            ],
+            failed: vec![],
+        };
+
+        let merged = ParadoxHandler::new().merge(SAMPLE, &translations).unwrap();
+        assert_eq!(
+            merged,
+            "\u{feff}l_english:\n # Events\n event.1.t:0 \"§Y위대한§! 연회\"\n event.1.d: \"$NAME$이(가) [Root.GetName]을(를)\\n초대합니다\" # shown in popup\n empty_key:0 \"\"\n"
+        );
+    }
+
+    #[test]
+    fn detects_paradox_files() {
+        assert!(ParadoxHandler::is_localisation_file_name("my_events_l_english.yml"));
+        assert!(!ParadoxHandler::is_localisation_file_name("config.yml"));
+        assert!(ParadoxHandler::is_localisation_content("# comment\nl_german:\n"));
+        assert!(!ParadoxHandler::is_localisation_content("title: Hello\n"));
+        assert!(ParadoxHandler::new().extract("title: \"Hello\"\n").is_err());
+    }
+}
@@ -0,0 +1,709 @@
+/// Extractor plugins for formats core does not know
+///
+/// A plugin is a program core runs as a child process, so third parties can
+/// support proprietary formats (Unity bundles, a game's own binary tables)
+/// without changing core. Each plugin has its own folder in the plugin
+/// directory with a `plugin.toml` or `plugin.json` manifest:
+///
+/// ```toml
+/// name = "unity-bundles"
+/// command = "bin/unity-bundles"   # relative to the plugin folder, or on PATH
+/// args = ["--stdio"]
+/// extensions = ["assetbundle", "unity3d"]
+/// ```
+///
+/// Core talks JSON-RPC 2.0 to it, one message per line on stdin/stdout.
+/// File bytes travel base64 encoded:
+///
+/// - `initialize` `{protocolVersion}` → `{name, protocolVersion}`
+/// - `extract` `{path, content}` → `{entries: [TranslatableEntry]}`
+/// - `merge` `{path, content, translations: TranslationResult}` → `{content}`
+/// - `shutdown`, a notification sent before core closes stdin
+///
+/// Anything the plugin writes to stderr goes to the log. A plugin that
+/// exits or does not answer in time is started again on the next request.
+///
+/// The library scan and translation jobs look up [`plugin_for_path`] before
+/// the built-in handlers, so a plugin also takes over extensions core knows.
+/// Plugins read files as bytes, like the binary formats.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use crate::paths::app_data_dir;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Version of the request/response shapes above
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+const PLUGINS_DIR_NAME: &str = "plugins";
+const MANIFEST_FILE_NAMES: [&str; 2] = ["plugin.toml", "plugin.json"];
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long a plugin gets to exit after `shutdown` before it is killed
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+
+/// A plugin's `plugin.toml` / `plugin.json`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub name: String,
+    /// 실행할 프로그램 (플러그인 폴더 기준 경로 또는 PATH에 있는 이름)
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 플러그인이 맡는 파일 확장자 (점 없이, 대소문자 무시)
+    pub extensions: Vec<String>,
+    /// 요청 하나에 응답을 기다릴 시간 (초, 기본 60)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// 매니페스트가 있는 폴더 (불러올 때 채움)
+    #[serde(default, skip_deserializing)]
+    pub dir: PathBuf,
+}
+
+impl PluginManifest {
+    fn timeout(&self) -> Duration {
+        self.timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    /// The command, resolved against the plugin folder when it is there
+    fn program(&self) -> PathBuf {
+        let local = self.dir.join(&self.command);
+        if local.is_file() {
+            local
+        } else {
+            PathBuf::from(&self.command)
+        }
+    }
+
+    pub fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|claimed| claimed.eq_ignore_ascii_case(ext))
+            })
+    }
+}
+
+/// A plugin folder that could not be used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginLoadError {
+    pub dir: String,
+    pub message: String,
+}
+
+/// Installed plugins, as returned by `list_extractor_plugins`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginListing {
+    pub plugins: Vec<PluginManifest>,
+    pub errors: Vec<PluginLoadError>,
+    pub plugin_dir: String,
+}
+
+/// Directory holding one folder per plugin
+pub fn plugins_dir() -> PathBuf {
+    app_data_dir().join(PLUGINS_DIR_NAME)
+}
+
+/// Reads the manifest of every plugin folder in `dir`, in folder name order.
+/// Folders without a manifest are ignored; invalid manifests, names already
+/// taken and extensions an earlier plugin claimed are reported.
+pub fn load_plugins(dir: &Path) -> (Vec<PluginManifest>, Vec<PluginLoadError>) {
+    let mut plugins: Vec<PluginManifest> = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (plugins, errors);
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    for plugin_dir in dirs {
+        let Some(manifest_path) = MANIFEST_FILE_NAMES
+            .iter()
+            .map(|name| plugin_dir.join(name))
+            .find(|path| path.is_file())
+        else {
+            continue;
+        };
+        let result = parse_manifest(&manifest_path).and_then(|mut manifest| {
+            if plugins.iter().any(|loaded| loaded.name == manifest.name) {
+                return Err(format!(
+                    "이미 사용 중인 플러그인 이름입니다: {}",
+                    manifest.name
+                ));
+            }
+            if let Some((extension, owner)) = manifest.extensions.iter().find_map(|extension| {
+                plugins
+                    .iter()
+                    .find(|loaded| loaded.extensions.contains(extension))
+                    .map(|owner| (extension, &owner.name))
+            }) {
+                return Err(format!(
+                    "확장자 {}는 이미 {} 플러그인이 맡고 있습니다",
+                    extension, owner
+                ));
+            }
+            manifest.dir = plugin_dir.clone();
+            Ok(manifest)
+        });
+        match result {
+            Ok(manifest) => plugins.push(manifest),
+            Err(message) => errors.push(PluginLoadError {
+                dir: plugin_dir.to_string_lossy().to_string(),
+                message,
+            }),
+        }
+    }
+    (plugins, errors)
+}
+
+fn parse_manifest(path: &Path) -> Result<PluginManifest, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut manifest: PluginManifest = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&contents).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())?
+    };
+    manifest.name = manifest.name.trim().to_string();
+    if manifest.name.is_empty() {
+        return Err("플러그인 이름이 비어 있습니다".to_string());
+    }
+    if manifest.command.trim().is_empty() {
+        return Err("실행할 프로그램(command)이 비어 있습니다".to_string());
+    }
+    manifest.extensions = manifest
+        .extensions
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    if manifest.extensions.is_empty() {
+        return Err("플러그인이 맡을 확장자(extensions)가 없습니다".to_string());
+    }
+    Ok(manifest)
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    id: Option<u64>,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    #[serde(default)]
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct InitializeResult {
+    #[serde(rename = "protocolVersion")]
+    protocol_version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractResult {
+    entries: Vec<TranslatableEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeResult {
+    content: String,
+}
+
+/// A running plugin process
+struct PluginProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    responses: Receiver<String>,
+    next_id: u64,
+}
+
+impl PluginProcess {
+    fn spawn(manifest: &PluginManifest) -> Result<Self, FormatError> {
+        let mut child = Command::new(manifest.program())
+            .args(&manifest.args)
+            .current_dir(&manifest.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                FormatError::UnsupportedFormat(format!(
+                    "failed to start plugin {}: {}",
+                    manifest.name, e
+                ))
+            })?;
+
+        let (sender, responses) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            let name = manifest.name.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    warn!("plugin {}: {}", name, line);
+                }
+            });
+        }
+
+        let mut process = Self {
+            stdin: child.stdin.take(),
+            child,
+            responses,
+            next_id: 1,
+        };
+        let result = process.call(
+            manifest,
+            "initialize",
+            json!({ "protocolVersion": PLUGIN_PROTOCOL_VERSION }),
+        )?;
+        let initialized: InitializeResult = parse_result(manifest, "initialize", result)?;
+        if initialized.protocol_version != PLUGIN_PROTOCOL_VERSION {
+            return Err(FormatError::UnsupportedFormat(format!(
+                "plugin {} speaks protocol version {}, expected {}",
+                manifest.name, initialized.protocol_version, PLUGIN_PROTOCOL_VERSION
+            )));
+        }
+        Ok(process)
+    }
+
+    fn call(
+        &mut self,
+        manifest: &PluginManifest,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, FormatError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        self.write_line(&request)?;
+
+        let deadline = Instant::now() + manifest.timeout();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.responses.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(plugin_error(
+                        manifest,
+                        method,
+                        format!("no answer within {:?}", manifest.timeout()),
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(plugin_error(manifest, method, "plugin exited"))
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response: RpcResponse = serde_json::from_str(&line).map_err(|e| {
+                plugin_error(manifest, method, format!("invalid response {line:?}: {e}"))
+            })?;
+            if response.id != Some(id) {
+                warn!(
+                    "plugin {} answered request {:?} while {} was waiting",
+                    manifest.name, response.id, id
+                );
+                continue;
+            }
+            if let Some(error) = response.error {
+                return Err(plugin_error(
+                    manifest,
+                    method,
+                    format!("{} (code {})", error.message, error.code),
+                ));
+            }
+            return Ok(response.result.unwrap_or(Value::Null));
+        }
+    }
+
+    fn write_line(&mut self, message: &Value) -> Result<(), FormatError> {
+        let stdin = self
+            .stdin
+            .as_mut()
+            .ok_or_else(|| FormatError::IoError(std::io::ErrorKind::BrokenPipe.into()))?;
+        let mut line = message.to_string();
+        line.push('\n');
+        stdin.write_all(line.as_bytes())?;
+        stdin.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        let _ = self.write_line(&json!({ "jsonrpc": "2.0", "method": "shutdown" }));
+        drop(self.stdin.take());
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        while Instant::now() < deadline {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn plugin_error(manifest: &PluginManifest, method: &str, message: impl AsRef<str>) -> FormatError {
+    FormatError::ParseError(format!(
+        "plugin {} failed to {}: {}",
+        manifest.name,
+        method,
+        message.as_ref()
+    ))
+}
+
+fn parse_result<T: serde::de::DeserializeOwned>(
+    manifest: &PluginManifest,
+    method: &str,
+    result: Value,
+) -> Result<T, FormatError> {
+    serde_json::from_value(result)
+        .map_err(|e| plugin_error(manifest, method, format!("unexpected result: {e}")))
+}
+
+/// An installed plugin. The process starts on the first request and is
+/// reused until it fails.
+pub struct ExtractorPlugin {
+    manifest: PluginManifest,
+    process: Mutex<Option<PluginProcess>>,
+}
+
+impl ExtractorPlugin {
+    pub fn new(manifest: PluginManifest) -> Self {
+        Self {
+            manifest,
+            process: Mutex::new(None),
+        }
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    /// Entries to translate in the file at `path` holding `content`
+    pub fn extract(
+        &self,
+        path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let result = self.request(
+            "extract",
+            json!({ "path": path.to_string_lossy(), "content": BASE64.encode(content) }),
+        )?;
+        let extracted: ExtractResult = parse_result(&self.manifest, "extract", result)?;
+        Ok(extracted.entries)
+    }
+
+    /// `original` with the translations applied
+    pub fn merge(
+        &self,
+        path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+    ) -> Result<Vec<u8>, FormatError> {
+        let result = self.request(
+            "merge",
+            json!({
+                "path": path.to_string_lossy(),
+                "content": BASE64.encode(original),
+                "translations": translations,
+            }),
+        )?;
+        let merged: MergeResult = parse_result(&self.manifest, "merge", result)?;
+        BASE64
+            .decode(merged.content.as_bytes())
+            .map_err(|e| plugin_error(&self.manifest, "merge", format!("invalid base64: {e}")))
+    }
+
+    fn bytes_only(&self) -> FormatError {
+        FormatError::UnsupportedFormat(format!(
+            "plugin {} reads files as bytes",
+            self.manifest.name
+        ))
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value, FormatError> {
+        let mut process = self
+            .process
+            .lock()
+            .map_err(|_| plugin_error(&self.manifest, method, "plugin lock poisoned"))?;
+        if process.is_none() {
+            *process = Some(PluginProcess::spawn(&self.manifest)?);
+        }
+        let result = process
+            .as_mut()
+            .map(|running| running.call(&self.manifest, method, params))
+            .unwrap_or(Ok(Value::Null));
+        if result.is_err() {
+            // Start a fresh process next time rather than reading stale answers
+            *process = None;
+        }
+        result
+    }
+}
+
+/// Plugins only implement the byte methods, since they are given the path
+/// of the file along with its content
+impl FormatHandler for ExtractorPlugin {
+    fn extract(&self, _content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Err(self.bytes_only())
+    }
+
+    fn merge(
+        &self,
+        _original: &str,
+        _translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        Err(self.bytes_only())
+    }
+
+    fn extract_bytes(
+        &self,
+        path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        ExtractorPlugin::extract(self, path, content)
+    }
+
+    fn merge_bytes(
+        &self,
+        path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        _target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        ExtractorPlugin::merge(self, path, original, translations)
+    }
+
+    /// Plugins handle formats core does not know
+    fn format(&self) -> FileFormat {
+        FileFormat::Unknown
+    }
+}
+
+static PLUGINS: Lazy<Mutex<Vec<Arc<ExtractorPlugin>>>> = Lazy::new(|| {
+    let (manifests, errors) = load_plugins(&plugins_dir());
+    for error in errors {
+        warn!("skipping plugin {}: {}", error.dir, error.message);
+    }
+    Mutex::new(
+        manifests
+            .into_iter()
+            .map(ExtractorPlugin::new)
+            .map(Arc::new)
+            .collect(),
+    )
+});
+
+/// The installed plugin that handles `path`, by extension
+pub fn plugin_for_path(path: &Path) -> Option<Arc<ExtractorPlugin>> {
+    PLUGINS
+        .lock()
+        .ok()?
+        .iter()
+        .find(|plugin| plugin.manifest.handles(path))
+        .cloned()
+}
+
+/// Adds a plugin to the registry without installing it in the plugin
+/// directory
+#[cfg(test)]
+pub(crate) fn register_plugin(manifest: PluginManifest) {
+    PLUGINS
+        .lock()
+        .unwrap()
+        .push(Arc::new(ExtractorPlugin::new(manifest)));
+}
+
+/// Tauri command to list installed extractor plugins, re-reading the plugin
+/// directory. Plugins that were already running keep their process.
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn list_extractor_plugins() -> Result<PluginListing, String> {
+    let dir = plugins_dir();
+    let (manifests, errors) = load_plugins(&dir);
+    let mut plugins = PLUGINS
+        .lock()
+        .map_err(|_| "plugin registry lock poisoned".to_string())?;
+    let mut running: HashMap<PluginManifest, Arc<ExtractorPlugin>> = plugins
+        .drain(..)
+        .map(|plugin| (plugin.manifest.clone(), plugin))
+        .collect();
+    *plugins = manifests
+        .iter()
+        .map(|manifest| {
+            running
+                .remove(manifest)
+                .unwrap_or_else(|| Arc::new(ExtractorPlugin::new(manifest.clone())))
+        })
+        .collect();
+    Ok(PluginListing {
+        plugins: manifests,
+        errors,
+        plugin_dir: dir.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Registers a plugin written in sh for files with `extension`. It
+    /// extracts one entry, `title` → `Hello`, and merges by writing the
+    /// first translation as the whole file.
+    #[cfg(unix)]
+    pub(crate) fn register_title_plugin(dir: &Path, extension: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = r#"#!/bin/sh
+while read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"initialize"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"name\":\"title\",\"protocolVersion\":1}}" ;;
+    *'"extract"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"entries\":[{\"key\":\"title\",\"source\":\"Hello\",\"context\":null}]}}" ;;
+    *'"merge"'*)
+      target=$(printf '%s' "$line" | sed -n 's/.*"target":"\([^"]*\)".*/\1/p')
+      content=$(printf '%s' "$target" | base64 | tr -d '\n')
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"content\":\"$content\"}}" ;;
+    *'"shutdown"'*) exit 0 ;;
+  esac
+done
+"#;
+        let program = dir.join("plugin.sh");
+        fs::write(&program, script).unwrap();
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+        register_plugin(PluginManifest {
+            name: format!("title-{extension}"),
+            command: "plugin.sh".into(),
+            args: Vec::new(),
+            extensions: vec![extension.into()],
+            timeout_secs: Some(10),
+            dir: dir.to_path_buf(),
+        });
+    }
+
+    fn write_manifest(root: &Path, folder: &str, manifest: &str) {
+        let dir = root.join(folder);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("plugin.toml"), manifest).unwrap();
+    }
+
+    #[test]
+    fn loads_manifests_and_reports_conflicts() {
+        let root = tempfile::tempdir().unwrap();
+        write_manifest(
+            root.path(),
+            "a-bundles",
+            "name = \"bundles\"\ncommand = \"bundles\"\nextensions = [\".AssetBundle\", \"unity3d\"]\n",
+        );
+        write_manifest(
+            root.path(),
+            "b-other",
+            "name = \"other\"\ncommand = \"other\"\nextensions = [\"unity3d\"]\n",
+        );
+        write_manifest(root.path(), "c-broken", "name = \"broken\"\n");
+        fs::create_dir_all(root.path().join("d-no-manifest")).unwrap();
+
+        let (plugins, errors) = load_plugins(root.path());
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].extensions, ["assetbundle", "unity3d"]);
+        assert_eq!(plugins[0].dir, root.path().join("a-bundles"));
+        assert!(plugins[0].handles(Path::new("Mods/ui.ASSETBUNDLE")));
+        assert!(!plugins[0].handles(Path::new("Mods/ui.xml")));
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].message.contains("bundles"));
+        assert!(errors[1].dir.ends_with("c-broken"));
+    }
+
+    /// A plugin written in sh that upper-cases on merge and knows one entry
+    #[cfg(unix)]
+    #[test]
+    fn talks_json_rpc_to_a_plugin_process() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("echo");
+        fs::create_dir_all(&dir).unwrap();
+        let script = r#"#!/bin/sh
+while read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"initialize"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"name\":\"echo\",\"protocolVersion\":1}}" ;;
+    *'"extract"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"entries\":[{\"key\":\"title\",\"source\":\"Hello\",\"context\":null}]}}" ;;
+    *'"merge"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"content\":\"7JWI64WV\"}}" ;;
+    *'"shutdown"'*) exit 0 ;;
+    *) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"error\":{\"code\":-32601,\"message\":\"unknown method\"}}" ;;
+  esac
+done
+"#;
+        let program = dir.join("plugin.sh");
+        fs::write(&program, script).unwrap();
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let plugin = ExtractorPlugin::new(PluginManifest {
+            name: "echo".into(),
+            command: "plugin.sh".into(),
+            args: Vec::new(),
+            extensions: vec!["bin".into()],
+            timeout_secs: Some(10),
+            dir,
+        });
+        let entries = plugin
+            .extract(Path::new("ui.bin"), &[0, 159, 146, 150])
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "title");
+        assert_eq!(entries[0].source, "Hello");
+
+        let translations = TranslationResult {
+            translated: Vec::new(),
+            failed: Vec::new(),
+        };
+        let merged = plugin
+            .merge(Path::new("ui.bin"), b"Hello", &translations)
+            .unwrap();
+        assert_eq!(String::from_utf8(merged).unwrap(), "안녕");
+
+        let error = plugin.request("frobnicate", json!({})).unwrap_err();
+        assert!(error.to_string().contains("unknown method"));
+        // The failed request restarts the process for the next one
+        assert_eq!(plugin.extract(Path::new("ui.bin"), b"").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,364 @@
+/// Quest book handler for FTB Quests and BetterQuesting
+/// Only player-facing quest text is translated: `title`, `subtitle` and
+/// `description` in FTB Quests `.snbt` chapters, and the `name` (title) and
+/// `desc` (description) properties in BetterQuesting `DefaultQuests.json`.
+/// FTB chapters keep every other byte; description lines holding images or
+/// JSON components (`{image:...}`, `{"text":...}`) are left alone.
+/// BetterQuesting files are re-serialized like other JSON files.
+use super::mcfunction::{escape_snbt, position_key, read_snbt_string};
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+
+// FTB Quests fields holding player-facing text
+static FTB_FIELD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[{,\s])(title|subtitle|description)\s*:\s*").expect("valid FTB field regex")
+});
+
+// BetterQuesting property -> the quest field it holds
+const BQ_FIELDS: &[(&str, &str)] = &[("name", "title"), ("desc", "description")];
+
+struct QuestText {
+    field: String,
+    value: String,
+    range: Range<usize>,
+    quote: char,
+}
+
+pub struct QuestHandler {
+    format: FileFormat,
+}
+
+impl QuestHandler {
+    /// Handler for FTB Quests chapter files (`config/ftbquests/quests/**/*.snbt`)
+    pub fn ftb_quests() -> Self {
+        Self {
+            format: FileFormat::FtbQuests,
+        }
+    }
+
+    /// Handler for BetterQuesting databases (`DefaultQuests.json`)
+    pub fn better_questing() -> Self {
+        Self {
+            format: FileFormat::BetterQuesting,
+        }
+    }
+
+    /// FTB Quests keeps its chapters under an `ftbquests` folder
+    pub fn is_ftb_quests_path(path: &Path) -> bool {
+        has_component(path, "ftbquests")
+    }
+
+    /// `DefaultQuests.json`, or JSON under a `betterquesting` folder
+    pub fn is_better_questing_path(path: &Path) -> bool {
+        let is_default_quests = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.eq_ignore_ascii_case("DefaultQuests.json"));
+        is_default_quests || has_component(path, "betterquesting")
+    }
+}
+
+impl FormatHandler for QuestHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let entry = |key: String, field: &str, source: String| TranslatableEntry {
+            key,
+            source,
+            context: Some(format!("quest {}", field)),
+            metadata: HashMap::from([("field".to_string(), field.to_string())]),
+        };
+
+        if self.format == FileFormat::BetterQuesting {
+            let mut root = parse_json(content)?;
+            let mut entries = Vec::new();
+            visit_better_questing(&mut root, "", false, &mut |path, field, text| {
+                if is_quest_text(text) {
+                    entries.push(entry(path.to_string(), field, text.clone()));
+                }
+            });
+            return Ok(entries);
+        }
+
+        Ok(scan_ftb(content)
+            .into_iter()
+            .filter(|text| is_quest_text(&text.value))
+            .map(|text| {
+                entry(
+                    position_key(content, text.range.start),
+                    &text.field,
+                    text.value,
+                )
+            })
+            .collect())
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        if self.format == FileFormat::BetterQuesting {
+            let mut root = parse_json(original)?;
+            visit_better_questing(&mut root, "", false, &mut |path, _, text| {
+                if let Some(target) = translation_map.get(path) {
+                    *text = target.to_string();
+                }
+            });
+            return serde_json::to_string_pretty(&root).map_err(|e| {
+                FormatError::SerializationError(format!("JSON serialize error: {}", e))
+            });
+        }
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+        for text in scan_ftb(original) {
+            let key = position_key(original, text.range.start);
+            let Some(target) = translation_map.get(key.as_str()) else {
+                continue;
+            };
+            result.push_str(&original[last..text.range.start]);
+            result.push_str(&escape_snbt(target, text.quote));
+            last = text.range.end;
+        }
+        result.push_str(&original[last..]);
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        self.format
+    }
+}
+
+fn has_component(path: &Path, name: &str) -> bool {
+    path.components().any(|part| {
+        part.as_os_str()
+            .to_string_lossy()
+            .eq_ignore_ascii_case(name)
+    })
+}
+
+/// Quest text, not an image, JSON component or blank separator line
+fn is_quest_text(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    !trimmed.starts_with(['{', '[']) && text.chars().any(|c| c.is_alphabetic())
+}
+
+/// Every `title`/`subtitle`/`description` string, in file order
+fn scan_ftb(content: &str) -> Vec<QuestText> {
+    let mut texts = Vec::new();
+    for found in FTB_FIELD_RE.captures_iter(content) {
+        let field = found[1].to_string();
+        let start = found.get(0).expect("match").end();
+        if !content[start..].starts_with('[') {
+            if let Some((value, range, quote)) = read_snbt_string(content, start) {
+                texts.push(QuestText {
+                    field,
+                    value,
+                    range,
+                    quote,
+                });
+            }
+            continue;
+        }
+        // Description lists: items are separated by newlines or commas
+        let mut pos = start + 1;
+        loop {
+            pos += content[pos..]
+                .find(|c: char| !c.is_whitespace() && c != ',')
+                .unwrap_or(content.len() - pos);
+            let Some((value, range, quote)) = read_snbt_string(content, pos) else {
+                break;
+            };
+            pos = range.end + 1;
+            texts.push(QuestText {
+                field: field.clone(),
+                value,
+                range,
+                quote,
+            });
+        }
+    }
+    texts
+}
+
+fn parse_json(content: &str) -> Result<Value, FormatError> {
+    serde_json::from_str(content)
+        .map_err(|e| FormatError::ParseError(format!("JSON parse error: {}", e)))
+}
+
+/// Calls `visit` with the path, quest field and text of every `name`/`desc`
+/// inside a `betterquesting` properties object. Keys carry NBT type suffixes
+/// (`name:8`), which are ignored when matching.
+fn visit_better_questing(
+    value: &mut Value,
+    path: &str,
+    in_properties: bool,
+    visit: &mut dyn FnMut(&str, &str, &mut String),
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let child_path = format!("{}/{}", path, key);
+                let name = key.split(':').next().unwrap_or_default();
+                if let (true, Value::String(text)) = (in_properties, &mut *child) {
+                    if let Some((_, field)) = BQ_FIELDS.iter().find(|(bq, _)| *bq == name) {
+                        visit(&child_path, field, text);
+                    }
+                    continue;
+                }
+                visit_better_questing(child, &child_path, name == "betterquesting", visit);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                visit_better_questing(item, &format!("{}/{}", path, index), false, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    fn translate_all(handler: &QuestHandler, content: &str, targets: &[(&str, &str)]) -> String {
+        let entries = handler.extract(content).unwrap();
+        let translations = TranslationResult {
+            translated: targets
+                .iter()
+                .map(|(source, target)| {
+                    let entry = entries.iter().find(|e| e.source == *source).unwrap();
+                    TranslatedEntry {
+                        key: entry.key.clone(),
+                        source: entry.source.clone(),
+                        target: target.to_string(),
+                    }
+                })
+                .collect(),
+            failed: vec![],
+        };
+        handler.merge(content, &translations).unwrap()
+    }
+
+    #[test]
+    fn ftb_chapters_translate_only_quest_text() {
+        let chapter = r#"{
+	id: "1A2B3C4D"
+	filename: "getting_started"
+	title: "Getting Started"
+	quests: [{
+		title: "Wood"
+		subtitle: "Punch a \"tree\""
+		description: [
+			"Collect &alogs&r to begin."
+			""
+			"{image:ftbquests:textures/logo.png width:50 height:50}"
+		]
+		tasks: [{ id: "5E6F", type: "item", item: "minecraft:oak_log", title: "Oak" }]
+	}]
+}"#;
+        let handler = QuestHandler::ftb_quests();
+        let entries = handler.extract(chapter).unwrap();
+        let sources: Vec<&str> = entries.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(
+            sources,
+            vec![
+                "Getting Started",
+                "Wood",
+                "Punch a \"tree\"",
+                "Collect &alogs&r to begin.",
+                "Oak"
+            ]
+        );
+        assert_eq!(entries[3].context.as_deref(), Some("quest description"));
+
+        let merged = translate_all(
+            &handler,
+            chapter,
+            &[
+                ("Punch a \"tree\"", "\"나무\"를 때리세요"),
+                ("Wood", "나무"),
+            ],
+        );
+        assert!(merged.contains(r#"subtitle: "\"나무\"를 때리세요""#));
+        assert!(merged.contains("\t\ttitle: \"나무\"\n"));
+        assert!(merged.contains(r#"filename: "getting_started""#));
+        assert!(merged.contains("{image:ftbquests:textures/logo.png width:50 height:50}"));
+    }
+
+    #[test]
+    fn better_questing_reads_name_and_desc_properties() {
+        let database = r#"{
+  "format:8": "2.0.0",
+  "questDatabase:9": {
+    "0:10": {
+      "questID:3": 0,
+      "properties:10": {
+        "betterquesting:10": {
+          "name:8": "Into the Mines",
+          "desc:8": "Find some iron ore.",
+          "icon:10": { "id:8": "minecraft:iron_ore" }
+        }
+      }
+    }
+  },
+  "name:8": "not a quest field"
+}"#;
+        let handler = QuestHandler::better_questing();
+        let entries = handler.extract(database).unwrap();
+        let mut pairs: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.source.as_str()))
+            .collect();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                (
+                    "/questDatabase:9/0:10/properties:10/betterquesting:10/desc:8",
+                    "Find some iron ore."
+                ),
+                (
+                    "/questDatabase:9/0:10/properties:10/betterquesting:10/name:8",
+                    "Into the Mines"
+                ),
+            ]
+        );
+
+        let merged = translate_all(&handler, database, &[("Into the Mines", "광산 속으로")]);
+        let reparsed: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(
+            reparsed["questDatabase:9"]["0:10"]["properties:10"]["betterquesting:10"]["name:8"],
+            "광산 속으로"
+        );
+        assert_eq!(reparsed["name:8"], "not a quest field");
+    }
+
+    #[test]
+    fn detects_quest_paths() {
+        assert!(QuestHandler::is_ftb_quests_path(Path::new(
+            "config/ftbquests/quests/chapters/intro.snbt"
+        )));
+        assert!(QuestHandler::is_better_questing_path(Path::new(
+            "config/betterquesting/DefaultQuests.json"
+        )));
+        assert!(QuestHandler::is_better_questing_path(Path::new(
+            "DefaultQuests.json"
+        )));
+        assert!(!QuestHandler::is_better_questing_path(Path::new(
+            "assets/lang/en_us.json"
+        )));
+    }
+}
@@ -0,0 +1,492 @@
+/// Ren'Py script (`.rpy`) format handler
+/// Extracts dialogue, narration, menu choices, `new "..."` lines of string
+/// translation blocks and `_("...")` / `__("...")` translatables. Labels,
+/// statements such as `jump`/`show`/`play`, `$` lines and python, screen,
+/// style and transform blocks are left alone apart from their `_()` calls.
+/// Interpolation like `[player_name]` stays inside the extracted text and is
+/// protected by the RPYVAR token class.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use std::collections::HashMap;
+use std::ops::Range;
+
+// Statements whose strings are file names, expressions or identifiers
+static STATEMENT_KEYWORDS: &[&str] = &[
+    "label",
+    "jump",
+    "call",
+    "return",
+    "pass",
+    "show",
+    "hide",
+    "scene",
+    "with",
+    "at",
+    "play",
+    "queue",
+    "stop",
+    "voice",
+    "pause",
+    "window",
+    "nvl",
+    "camera",
+    "image",
+    "define",
+    "default",
+    "init",
+    "python",
+    "screen",
+    "style",
+    "transform",
+    "translate",
+    "menu",
+    "if",
+    "elif",
+    "else",
+    "while",
+    "for",
+    "old",
+    "layeredimage",
+    "testcase",
+];
+
+// Statements that open a block of python or screen/ATL code
+static CODE_BLOCK_KEYWORDS: &[&str] = &[
+    "init",
+    "python",
+    "screen",
+    "style",
+    "transform",
+    "image",
+    "layeredimage",
+];
+
+// Functions that mark a string literal as translatable
+static TRANSLATE_FUNCTIONS: &[&str] = &["_", "__"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StringKind {
+    Dialogue,
+    Menu,
+    /// `new "..."` inside a `translate <language> strings:` block
+    StringTranslation,
+    /// `_("...")` or `__("...")`
+    Translatable,
+}
+
+impl StringKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            StringKind::Dialogue => "dialogue",
+            StringKind::Menu => "menu",
+            StringKind::StringTranslation => "string",
+            StringKind::Translatable => "translatable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// Single-line string literal; `range` covers the text between the quotes
+    Str {
+        range: Range<usize>,
+        quote: char,
+    },
+    Punct(char),
+}
+
+/// A translatable string literal in the script
+struct RenpyString {
+    key: String,
+    kind: StringKind,
+    speaker: Option<String>,
+    line: usize,
+    /// Byte range of the literal's contents in the file
+    range: Range<usize>,
+    quote: char,
+}
+
+pub struct RenpyHandler;
+
+impl RenpyHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RenpyHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for RenpyHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let mut entries = Vec::new();
+        for string in scan(content) {
+            let source = unescape(&content[string.range.clone()], string.quote);
+            if !has_visible_text(&source) {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            metadata.insert("line".to_string(), string.line.to_string());
+            metadata.insert("kind".to_string(), string.kind.as_str().to_string());
+            let context = match &string.speaker {
+                Some(speaker) => {
+                    metadata.insert("speaker".to_string(), speaker.clone());
+                    format!("{speaker}, line {}", string.line)
+                }
+                None => format!("line {}", string.line),
+            };
+
+            entries.push(TranslatableEntry {
+                key: string.key,
+                source,
+                context: Some(context),
+                metadata,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+        for string in scan(original) {
+            let Some(target) = translation_map.get(string.key.as_str()) else {
+                continue;
+            };
+            result.push_str(&original[last..string.range.start]);
+            result.push_str(&escape(target, string.quote));
+            last = string.range.end;
+        }
+        result.push_str(&original[last..]);
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::RenPy
+    }
+}
+
+/// Finds every translatable string literal, in file order
+fn scan(content: &str) -> Vec<RenpyString> {
+    let mut strings = Vec::new();
+    let mut code_block_indent: Option<usize> = None;
+    let mut offset = 0;
+
+    for (index, raw_line) in content.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += raw_line.len();
+
+        let body = raw_line.trim_end_matches(['\r', '\n']);
+        let bom = body.len() - body.trim_start_matches('\u{feff}').len();
+        let body = &body[bom..];
+        let trimmed = body.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = body.len() - trimmed.len();
+        let base = line_start + bom;
+        let line = index + 1;
+
+        if code_block_indent.is_some_and(|block| indent <= block) {
+            code_block_indent = None;
+        }
+        let in_code = code_block_indent.is_some();
+
+        let tokens = tokenize(body);
+        if !in_code && opens_code_block(&tokens) {
+            code_block_indent = Some(indent);
+        }
+
+        let mut push =
+            |kind: StringKind, speaker: Option<String>, range: &Range<usize>, quote: char| {
+                strings.push(RenpyString {
+                    key: format!("{}:{}", line, range.start),
+                    kind,
+                    speaker,
+                    line,
+                    range: base + range.start..base + range.end,
+                    quote,
+                });
+            };
+
+        if !in_code {
+            if let Some((kind, speaker, range, quote)) = script_string(body, &tokens) {
+                push(kind, speaker, &range, quote);
+            }
+        }
+
+        for window in tokens.windows(3) {
+            if let [Token::Word(function), Token::Punct('('), Token::Str { range, quote }] = window
+            {
+                if TRANSLATE_FUNCTIONS.contains(&function.as_str()) {
+                    push(StringKind::Translatable, None, range, *quote);
+                }
+            }
+        }
+    }
+
+    strings
+}
+
+/// Dialogue, narration, menu choice or `new` string on a script line
+fn script_string(
+    body: &str,
+    tokens: &[Token],
+) -> Option<(StringKind, Option<String>, Range<usize>, char)> {
+    match tokens {
+        [Token::Word(word), Token::Str { range, quote }, ..] if word == "new" => {
+            return Some((StringKind::StringTranslation, None, range.clone(), *quote));
+        }
+        [Token::Word(word), ..] if STATEMENT_KEYWORDS.contains(&word.as_str()) => return None,
+        // "Eileen" "Hello there."
+        [Token::Str { range: who, .. }, Token::Str { range, quote }, ..] => {
+            return Some((
+                StringKind::Dialogue,
+                Some(body[who.clone()].to_string()),
+                range.clone(),
+                *quote,
+            ));
+        }
+        _ => {}
+    }
+
+    // Speaker and image attributes: e happy @ vhappy "..."
+    let prefix = tokens
+        .iter()
+        .take_while(|token| matches!(token, Token::Word(_) | Token::Punct('@')))
+        .count();
+    let Some(Token::Str { range, quote }) = tokens.get(prefix) else {
+        return None;
+    };
+    let speaker = match tokens.first() {
+        Some(Token::Word(word)) if prefix > 0 => Some(word.clone()),
+        _ => None,
+    };
+    let kind = if speaker.is_none() && tokens.last() == Some(&Token::Punct(':')) {
+        StringKind::Menu
+    } else {
+        StringKind::Dialogue
+    };
+    Some((kind, speaker, range.clone(), *quote))
+}
+
+fn opens_code_block(tokens: &[Token]) -> bool {
+    matches!(tokens.first(), Some(Token::Word(word)) if CODE_BLOCK_KEYWORDS.contains(&word.as_str()))
+        && tokens.last() == Some(&Token::Punct(':'))
+}
+
+/// Splits one line into words, single-line string literals and punctuation,
+/// stopping at a comment. Triple-quoted and unterminated strings end the
+/// line since their text cannot be located on it.
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '#' {
+            break;
+        }
+        if c == '"' || c == '\'' {
+            let triple: String = [c; 3].iter().collect();
+            if line[start..].starts_with(&triple) {
+                break;
+            }
+            let mut end = None;
+            while let Some((position, next)) = chars.next() {
+                if next == '\\' {
+                    chars.next();
+                } else if next == c {
+                    end = Some(position);
+                    break;
+                }
+            }
+            let Some(end) = end else {
+                break;
+            };
+            tokens.push(Token::Str {
+                range: start + 1..end,
+                quote: c,
+            });
+            continue;
+        }
+        if is_word_char(c) {
+            let mut end = start + c.len_utf8();
+            while let Some(&(position, next)) = chars.peek() {
+                if !is_word_char(next) {
+                    break;
+                }
+                end = position + next.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Word(line[start..end].to_string()));
+            continue;
+        }
+        tokens.push(Token::Punct(c));
+    }
+
+    tokens
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// Text outside interpolations and text tags contains something to translate.
+/// `[[` and `{{` are escaped literal brackets.
+fn has_visible_text(text: &str) -> bool {
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '[' | '{' if chars.peek() == Some(&c) => {
+                chars.next();
+            }
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && c.is_alphabetic() => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn unescape(raw: &str, quote: char) -> String {
+    raw.replace(&format!("\\{quote}"), &quote.to_string())
+}
+
+fn escape(text: &str, quote: char) -> String {
+    let text = text.replace("\r\n", "\\n").replace('\n', "\\n");
+    let mut escaped = String::with_capacity(text.len());
+    let mut previous = None;
+    for c in text.chars() {
+        if c == quote && previous != Some('\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        previous = Some(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const SAMPLE: &str = r##"define e = Character(_("Eileen"), color="#c8ffc8")
+image bg room = "bg/room.png"
+
+init python:
+    greeting = _("Welcome back")
+    path = "audio/theme.ogg"
+
+label start:
+    scene bg room
+    play music "audio/theme.ogg"
+    "It was a quiet morning."
+    e happy "Hello, [player_name]! Say \"hi\"."
+    menu:
+        "Go left":
+            jump left
+        "Stay" if courage > 2:
+            $ renpy.notify(_("You stay."))
+    # e "A comment"
+    return
+"##;
+
+    fn sources(entries: &[TranslatableEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.source.as_str()).collect()
+    }
+
+    #[test]
+    fn extracts_dialogue_menus_and_translatables_only() {
+        let entries = RenpyHandler::new().extract(SAMPLE).unwrap();
+        assert_eq!(
+            sources(&entries),
+            vec![
+                "Eileen",
+                "Welcome back",
+                "It was a quiet morning.",
+                "Hello, [player_name]! Say \"hi\".",
+                "Go left",
+                "Stay",
+                "You stay.",
+            ]
+        );
+        assert_eq!(
+            entries[3].metadata.get("speaker").map(String::as_str),
+            Some("e")
+        );
+        assert_eq!(
+            entries[3].metadata.get("kind").map(String::as_str),
+            Some("dialogue")
+        );
+        assert_eq!(
+            entries[4].metadata.get("kind").map(String::as_str),
+            Some("menu")
+        );
+        assert_eq!(
+            entries[6].metadata.get("kind").map(String::as_str),
+            Some("translatable")
+        );
+    }
+
+    #[test]
+    fn merge_escapes_quotes_and_keeps_code() {
+        let handler = RenpyHandler::new();
+        let entries = handler.extract(SAMPLE).unwrap();
+        let translations = TranslationResult {
+            translated: vec![
+                TranslatedEntry {
+                    key: entries[3].key.clone(),
+                    source: entries[3].source.clone(),
+                    target: "안녕, [player_name]! \"안녕\"이라고\n말해.".into(),
+                },
+                TranslatedEntry {
+                    key: entries[4].key.clone(),
+                    source: entries[4].source.clone(),
+                    target: "왼쪽으로".into(),
+                },
+            ],
+            failed: vec![],
+        };
+
+        let merged = handler.merge(SAMPLE, &translations).unwrap();
+        assert!(merged.contains(r#"    e happy "안녕, [player_name]! \"안녕\"이라고\n말해.""#));
+        assert!(merged.contains("        \"왼쪽으로\":\n            jump left\n"));
+        assert!(merged.contains("play music \"audio/theme.ogg\""));
+        assert_eq!(merged.lines().count(), SAMPLE.lines().count());
+    }
+
+    #[test]
+    fn handles_string_translation_blocks_and_named_speakers() {
+        let script = "translate korean strings:\n    old \"Start\"\n    new \"Start\"\n\nlabel a:\n    \"Narrator\" \"[[Not] an interpolation.\"\n    e \"{b}[score]{/b}\"\n";
+        let entries = RenpyHandler::new().extract(script).unwrap();
+        assert_eq!(sources(&entries), vec!["Start", "[[Not] an interpolation."]);
+        assert_eq!(
+            entries[0].metadata.get("kind").map(String::as_str),
+            Some("string")
+        );
+        assert_eq!(
+            entries[1].metadata.get("speaker").map(String::as_str),
+            Some("Narrator")
+        );
+    }
+}
@@ -0,0 +1,294 @@
+/// RESX/RESW (.NET resource) format handler
+/// Only the `<value>` of string `<data>` entries is translated; the schema,
+/// `<resheader>` entries, comments and `xml:space` attributes stay untouched.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ops::Range;
+
+// Opening <data ...> tag (self-closing tags are filtered out afterwards)
+static DATA_OPEN_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<data\b[^>]*>").expect("valid data tag regex"));
+
+static DATA_CLOSE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"</data\s*>").expect("valid data close regex"));
+
+static VALUE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<value\b([^>]*)>(.*?)</value\s*>").expect("valid value regex"));
+
+static COMMENT_ELEMENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<comment\b[^>]*>(.*?)</comment\s*>").expect("valid comment element regex")
+});
+
+// Matches XML comments (should be skipped)
+static COMMENT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<!--[\s\S]*?-->").expect("valid comment regex"));
+
+static NAME_ATTR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bname\s*=\s*"([^"]*)""#).expect("valid name attr regex"));
+
+// Non-string resources (images, serialized objects) carry a type or mimetype
+static TYPED_ATTR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(type|mimetype)\s*=").expect("valid type attr regex"));
+
+static PRESERVE_SPACE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bxml:space\s*=\s*"preserve""#).expect("valid xml:space regex"));
+
+/// A `<value>` found in the file, with the byte range of its inner text
+struct ResxValue {
+    name: String,
+    inner: Range<usize>,
+    preserve_space: bool,
+    comment: Option<String>,
+}
+
+pub struct ResxHandler;
+
+impl ResxHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Collect translatable `<value>` elements in document order
+    fn scan(content: &str) -> Vec<ResxValue> {
+        let comments: Vec<Range<usize>> =
+            COMMENT_RE.find_iter(content).map(|m| m.range()).collect();
+        let mut values = Vec::new();
+        let mut search_from = 0;
+
+        while let Some(open) = DATA_OPEN_RE.find_at(content, search_from) {
+            search_from = open.end();
+            let tag = open.as_str();
+            if tag.ends_with("/>") || comments.iter().any(|range| range.contains(&open.start())) {
+                continue;
+            }
+            let Some(close) = DATA_CLOSE_RE.find_at(content, open.end()) else {
+                break;
+            };
+            search_from = close.end();
+
+            if TYPED_ATTR_RE.is_match(tag) {
+                continue;
+            }
+            let Some(name) = NAME_ATTR_RE.captures(tag).map(|cap| unescape_xml(&cap[1])) else {
+                continue;
+            };
+            // WinForms designer metadata such as ">>button1.Type" or "$this.Icon"
+            if name.starts_with(">>") || name.starts_with('$') {
+                continue;
+            }
+
+            let body = &content[open.end()..close.start()];
+            let Some(value) = VALUE_RE.captures(body) else {
+                continue;
+            };
+            let inner = value.get(2).expect("value group");
+            if inner.as_str().contains("<![CDATA[") {
+                continue;
+            }
+
+            values.push(ResxValue {
+                name,
+                inner: open.end() + inner.start()..open.end() + inner.end(),
+                preserve_space: PRESERVE_SPACE_RE.is_match(tag)
+                    || PRESERVE_SPACE_RE.is_match(&value[1]),
+                comment: COMMENT_ELEMENT_RE
+                    .captures(body)
+                    .map(|cap| unescape_xml(cap[1].trim()))
+                    .filter(|text| !text.is_empty()),
+            });
+        }
+
+        values
+    }
+
+    /// Text handed to the translator: whitespace is trimmed unless preserved
+    fn source_text(raw: &str, preserve_space: bool) -> String {
+        if preserve_space {
+            unescape_xml(raw)
+        } else {
+            unescape_xml(raw.trim())
+        }
+    }
+}
+
+impl Default for ResxHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for ResxHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let mut entries = Vec::new();
+
+        for value in Self::scan(content) {
+            let source = Self::source_text(&content[value.inner.clone()], value.preserve_space);
+            if source.trim().is_empty() {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            metadata.insert("type".to_string(), "resx_value".to_string());
+            if value.preserve_space {
+                metadata.insert("xml_space".to_string(), "preserve".to_string());
+            }
+            if let Some(comment) = &value.comment {
+                metadata.insert("comment".to_string(), comment.clone());
+            }
+
+            entries.push(TranslatableEntry {
+                key: value.name.clone(),
+                source,
+                context: Some(
+                    value
+                        .comment
+                        .unwrap_or_else(|| format!("<data name=\"{}\">", value.name)),
+                ),
+                metadata,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+
+        for value in Self::scan(original) {
+            let Some(&target) = translation_map.get(value.name.as_str()) else {
+                continue;
+            };
+            let raw = &original[value.inner.clone()];
+            result.push_str(&original[last..value.inner.start]);
+            if value.preserve_space {
+                result.push_str(&escape_xml(target));
+            } else {
+                // Keep the indentation around the text as it was
+                let leading = &raw[..raw.len() - raw.trim_start().len()];
+                let trailing = &raw[raw.trim_end().len()..];
+                result.push_str(leading);
+                result.push_str(&escape_xml(target.trim()));
+                result.push_str(trailing);
+            }
+            last = value.inner.end;
+        }
+
+        result.push_str(&original[last..]);
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Resx
+    }
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <xsd:schema id="root" xmlns="" xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+    <xsd:element name="root" msdata:IsDataSet="true" />
+  </xsd:schema>
+  <resheader name="resmimetype">
+    <value>text/microsoft-resx</value>
+  </resheader>
+  <!-- <data name="Disabled"><value>Old text</value></data> -->
+  <data name="Greeting" xml:space="preserve">
+    <value>Hello, {0} &amp; friends!</value>
+    <comment>Shown on the title screen</comment>
+  </data>
+  <data name="Padded">
+    <value>
+      Press start
+    </value>
+  </data>
+  <data name="Logo" type="System.Drawing.Bitmap, System.Drawing" mimetype="application/x-microsoft.net.object.bytearray.base64">
+    <value>iVBORw0KGgo=</value>
+  </data>
+  <data name="&gt;&gt;button1.Name" xml:space="preserve">
+    <value>button1</value>
+  </data>
+  <data name="Empty" />
+</root>
+"#;
+
+    fn translated(key: &str, source: &str, target: &str) -> TranslatedEntry {
+        TranslatedEntry {
+            key: key.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn extracts_only_string_data_values() {
+        let entries = ResxHandler::new().extract(SAMPLE).unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["Greeting", "Padded"]);
+
+        assert_eq!(entries[0].source, "Hello, {0} & friends!");
+        assert_eq!(
+            entries[0].context.as_deref(),
+            Some("Shown on the title screen")
+        );
+        assert_eq!(
+            entries[0].metadata.get("xml_space").map(String::as_str),
+            Some("preserve")
+        );
+        assert_eq!(entries[1].source, "Press start");
+    }
+
+    #[test]
+    fn merge_preserves_schema_comments_and_layout() {
+        let handler = ResxHandler::new();
+        let translations = TranslationResult {
+            translated: vec![
+                translated(
+                    "Greeting",
+                    "Hello, {0} & friends!",
+                    "안녕하세요, {0} & 친구들!",
+                ),
+                translated("Padded", "Press start", "시작 버튼을 누르세요"),
+            ],
+            failed: vec![],
+        };
+
+        let merged = handler.merge(SAMPLE, &translations).unwrap();
+        let expected = SAMPLE
+            .replace("Hello, {0} &amp; friends!", "안녕하세요, {0} &amp; 친구들!")
+            .replace("      Press start\n", "      시작 버튼을 누르세요\n");
+        assert_eq!(merged, expected);
+        assert!(merged.contains("<!-- <data name=\"Disabled\"><value>Old text</value></data> -->"));
+        assert!(merged.contains("<value>text/microsoft-resx</value>"));
+    }
+}
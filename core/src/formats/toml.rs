@@ -0,0 +1,640 @@
+/// TOML format handler
+/// Extracts string values only. Keys, tables, comments and non-string
+/// values are copied through byte for byte; merge rewrites just the string
+/// literals it has translations for, keeping their quoting style where the
+/// translation allows it.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    /// `"..."`
+    Basic,
+    /// `'...'`
+    Literal,
+    /// `"""..."""`
+    MultiBasic,
+    /// `'''...'''`
+    MultiLiteral,
+}
+
+impl Quote {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Basic => "basic",
+            Self::Literal => "literal",
+            Self::MultiBasic => "multiline_basic",
+            Self::MultiLiteral => "multiline_literal",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TomlString {
+    /// Dotted key path, with `[n]` for array items, e.g. `mods[0].description`
+    key: String,
+    value: String,
+    /// Byte range of the literal including its quotes
+    range: Range<usize>,
+    quote: Quote,
+    /// Multi-line literal whose content starts on the next line
+    leading_newline: bool,
+}
+
+pub struct TomlHandler;
+
+impl TomlHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TomlHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for TomlHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        let entries = Parser::new(content)
+            .parse()?
+            .into_iter()
+            .filter(|string| is_translatable(&string.value))
+            .map(|string| TranslatableEntry {
+                context: Some(string.key.clone()),
+                metadata: HashMap::from([("quote".to_string(), string.quote.as_str().to_string())]),
+                key: string.key,
+                source: string.value,
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+        for string in Parser::new(original).parse()? {
+            let Some(target) = translation_map.get(string.key.as_str()) else {
+                continue;
+            };
+            result.push_str(&original[last..string.range.start]);
+            result.push_str(&render(target, string.quote, string.leading_newline));
+            last = string.range.end;
+        }
+        result.push_str(&original[last..]);
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::Toml
+    }
+}
+
+/// Checks that `content` parses as TOML as far as this handler reads it
+pub fn check_syntax(content: &str) -> Result<(), FormatError> {
+    Parser::new(content).parse().map(|_| ())
+}
+
+/// Skips ids, paths and URLs that configs keep next to UI strings
+fn is_translatable(value: &str) -> bool {
+    let trimmed = value.trim();
+    if !trimmed.chars().any(|c| c.is_alphabetic()) {
+        return false;
+    }
+    if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+        return false;
+    }
+    // A single token with namespace or path separators: `minecraft:stone`, `gui/icon.png`
+    let single_token = !trimmed.contains(char::is_whitespace);
+    !(single_token && trimmed.contains([':', '/', '\\', '_']))
+}
+
+/// Writes `text` as a literal of the original style, switching to a basic
+/// string when the literal style cannot hold the text.
+fn render(text: &str, quote: Quote, leading_newline: bool) -> String {
+    let newline = if leading_newline { "\n" } else { "" };
+    match quote {
+        Quote::Literal if !text.contains(['\'', '\n', '\r']) => format!("'{}'", text),
+        Quote::MultiLiteral if !text.contains("'''") => format!("'''{}{}'''", newline, text),
+        Quote::Basic | Quote::Literal => format!("\"{}\"", escape(text, false)),
+        Quote::MultiBasic | Quote::MultiLiteral => {
+            format!("\"\"\"{}{}\"\"\"", newline, escape(text, true))
+        }
+    }
+}
+
+fn escape(text: &str, multiline: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' if multiline => escaped.push('\n'),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    strings: Vec<TomlString>,
+    /// Next index for each `[[array.of.tables]]`
+    table_arrays: HashMap<String, usize>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            strings: Vec::new(),
+            table_arrays: HashMap::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<TomlString>, FormatError> {
+        let mut table = String::new();
+        if self.src.starts_with('\u{feff}') {
+            self.pos = '\u{feff}'.len_utf8();
+        }
+
+        while self.pos < self.src.len() {
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some('\n') | Some('\r') | Some('#') => {}
+                Some('[') => table = self.parse_table_header()?,
+                Some(_) => {
+                    let key = self.parse_key()?;
+                    self.skip_whitespace();
+                    if !self.eat('=') {
+                        return Err(self.error("expected '=' after key"));
+                    }
+                    self.skip_whitespace();
+                    let path = join_key(&table, &key);
+                    self.parse_value(&path)?;
+                }
+            }
+            self.finish_line()?;
+        }
+        Ok(self.strings)
+    }
+
+    fn parse_table_header(&mut self) -> Result<String, FormatError> {
+        let is_array = self.src[self.pos..].starts_with("[[");
+        self.pos += if is_array { 2 } else { 1 };
+        self.skip_whitespace();
+        let key = self.parse_key()?;
+        self.skip_whitespace();
+        let close = if is_array { "]]" } else { "]" };
+        if !self.src[self.pos..].starts_with(close) {
+            return Err(self.error("unterminated table header"));
+        }
+        self.pos += close.len();
+
+        let name = self.resolve_table(&join_key("", &key));
+        if !is_array {
+            return Ok(name);
+        }
+        let index = self.table_arrays.entry(name.clone()).or_insert(0);
+        let path = format!("{}[{}]", name, index);
+        *index += 1;
+        Ok(path)
+    }
+
+    /// Places sub-tables of an array of tables under its latest element:
+    /// `[quests.reward]` after the second `[[quests]]` is `quests[1].reward`
+    fn resolve_table(&self, name: &str) -> String {
+        let parent = self
+            .table_arrays
+            .iter()
+            .filter(|(array, _)| name.starts_with(&format!("{}.", array)))
+            .max_by_key(|(array, _)| array.len());
+        match parent {
+            Some((array, next)) => format!("{}[{}]{}", array, next - 1, &name[array.len()..]),
+            None => name.to_string(),
+        }
+    }
+
+    /// Dotted key: bare, `"basic"` or `'literal'` parts
+    fn parse_key(&mut self) -> Result<Vec<String>, FormatError> {
+        let mut parts = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let part = match self.peek() {
+                Some('"') | Some('\'') => {
+                    let (value, quote) = self.parse_string()?;
+                    if matches!(quote, Quote::MultiBasic | Quote::MultiLiteral) {
+                        return Err(self.error("multi-line strings cannot be keys"));
+                    }
+                    value
+                }
+                _ => {
+                    let start = self.pos;
+                    while let Some(c) = self.peek() {
+                        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                            self.pos += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    if start == self.pos {
+                        return Err(self.error("expected a key"));
+                    }
+                    self.src[start..self.pos].to_string()
+                }
+            };
+            parts.push(part);
+            self.skip_whitespace();
+            if !self.eat('.') {
+                return Ok(parts);
+            }
+        }
+    }
+
+    fn parse_value(&mut self, path: &str) -> Result<(), FormatError> {
+        match self.peek() {
+            Some('"') | Some('\'') => {
+                let start = self.pos;
+                let leading_newline = {
+                    let rest = &self.src[self.pos..];
+                    (rest.starts_with("\"\"\"") || rest.starts_with("'''"))
+                        && (rest[3..].starts_with('\n') || rest[3..].starts_with("\r\n"))
+                };
+                let (value, quote) = self.parse_string()?;
+                self.strings.push(TomlString {
+                    key: path.to_string(),
+                    value,
+                    range: start..self.pos,
+                    quote,
+                    leading_newline,
+                });
+            }
+            Some('[') => {
+                self.pos += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_blank();
+                    if self.eat(']') {
+                        break;
+                    }
+                    self.parse_value(&format!("{}[{}]", path, index))?;
+                    index += 1;
+                    self.skip_blank();
+                    if self.eat(',') {
+                        continue;
+                    }
+                    if self.eat(']') {
+                        break;
+                    }
+                    return Err(self.error("expected ',' or ']' in array"));
+                }
+            }
+            Some('{') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.eat('}') {
+                    return Ok(());
+                }
+                loop {
+                    let key = self.parse_key()?;
+                    self.skip_whitespace();
+                    if !self.eat('=') {
+                        return Err(self.error("expected '=' in inline table"));
+                    }
+                    self.skip_whitespace();
+                    self.parse_value(&join_key(path, &key))?;
+                    self.skip_whitespace();
+                    if self.eat(',') {
+                        continue;
+                    }
+                    if self.eat('}') {
+                        break;
+                    }
+                    return Err(self.error("expected ',' or '}' in inline table"));
+                }
+            }
+            Some(_) => {
+                // Numbers, booleans and dates
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    if matches!(c, ',' | ']' | '}' | '#' | '\n' | '\r') {
+                        break;
+                    }
+                    self.pos += c.len_utf8();
+                }
+                if self.src[start..self.pos].trim().is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+            }
+            None => return Err(self.error("expected a value")),
+        }
+        Ok(())
+    }
+
+    /// Parses a string literal of any style and returns its decoded value
+    fn parse_string(&mut self) -> Result<(String, Quote), FormatError> {
+        let start = self.pos;
+        let rest = &self.src[self.pos..];
+        let (quote, delimiter) = if rest.starts_with("\"\"\"") {
+            (Quote::MultiBasic, "\"\"\"")
+        } else if rest.starts_with("'''") {
+            (Quote::MultiLiteral, "'''")
+        } else if rest.starts_with('"') {
+            (Quote::Basic, "\"")
+        } else {
+            (Quote::Literal, "'")
+        };
+        self.pos += delimiter.len();
+
+        let multiline = matches!(quote, Quote::MultiBasic | Quote::MultiLiteral);
+        if multiline {
+            // A newline right after the opening delimiter is trimmed
+            if self.src[self.pos..].starts_with("\r\n") {
+                self.pos += 2;
+            } else if self.src[self.pos..].starts_with('\n') {
+                self.pos += 1;
+            }
+        }
+
+        let mut value = String::new();
+        loop {
+            let rest = &self.src[self.pos..];
+            if rest.starts_with(delimiter) {
+                self.pos += delimiter.len();
+                // Up to two quotes may sit right before the closing delimiter
+                if multiline {
+                    let quote_char = delimiter.as_bytes()[0];
+                    let mut extra = 0;
+                    while extra < 2 && self.src.as_bytes().get(self.pos) == Some(&quote_char) {
+                        value.push(quote_char as char);
+                        self.pos += 1;
+                        extra += 1;
+                    }
+                }
+                return Ok((value, quote));
+            }
+            let Some(c) = rest.chars().next() else {
+                return Err(self.error_at(start, "unterminated string"));
+            };
+            if !multiline && (c == '\n' || c == '\r') {
+                return Err(self.error_at(start, "unterminated string"));
+            }
+            if c == '\\' && matches!(quote, Quote::Basic | Quote::MultiBasic) {
+                self.pos += 1;
+                self.parse_escape(&mut value, multiline)?;
+                continue;
+            }
+            value.push(c);
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn parse_escape(&mut self, value: &mut String, multiline: bool) -> Result<(), FormatError> {
+        let Some(c) = self.peek() else {
+            return Err(self.error("unterminated escape"));
+        };
+        self.pos += c.len_utf8();
+        match c {
+            'b' => value.push('\u{8}'),
+            't' => value.push('\t'),
+            'n' => value.push('\n'),
+            'f' => value.push('\u{c}'),
+            'r' => value.push('\r'),
+            'e' => value.push('\u{1b}'),
+            '"' => value.push('"'),
+            '\\' => value.push('\\'),
+            'u' | 'U' => {
+                let len = if c == 'u' { 4 } else { 8 };
+                let hex = self
+                    .src
+                    .get(self.pos..self.pos + len)
+                    .ok_or_else(|| self.error("truncated unicode escape"))?;
+                let decoded = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape"))?;
+                value.push(decoded);
+                self.pos += len;
+            }
+            // Line-ending backslash: trim the newline and following whitespace
+            ' ' | '\t' | '\r' | '\n' if multiline => {
+                self.pos -= c.len_utf8();
+                while let Some(c) = self.peek() {
+                    if c.is_whitespace() {
+                        self.pos += c.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            _ => return Err(self.error("invalid escape sequence")),
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ') | Some('\t')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Whitespace, newlines and comments, as allowed inside arrays
+    fn skip_blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ') | Some('\t') | Some('\r') | Some('\n') => self.pos += 1,
+                Some('#') => self.skip_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Allows trailing whitespace and a comment, then consumes the newline
+    fn finish_line(&mut self) -> Result<(), FormatError> {
+        self.skip_whitespace();
+        if self.peek() == Some('#') {
+            self.skip_comment();
+        }
+        self.eat('\r');
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(_) => Err(self.error("unexpected content after value")),
+        }
+    }
+
+    fn error(&self, message: &str) -> FormatError {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: &str) -> FormatError {
+        let line = self.src[..pos.min(self.src.len())].matches('\n').count() + 1;
+        FormatError::ParseError(format!("TOML line {}: {}", line, message))
+    }
+}
+
+fn join_key(prefix: &str, parts: &[String]) -> String {
+    let joined = parts.join(".");
+    if prefix.is_empty() {
+        joined
+    } else {
+        format!("{}.{}", prefix, joined)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const SAMPLE: &str = r#"# Client settings
+title = "Welcome back"   # shown on the main menu
+mod_id = "examplemod"
+icon = 'textures/gui/icon.png'
+max_players = 8
+
+[ui.buttons]
+start = "Start game"
+quit = 'Quit to "desktop"'
+tips = [
+    "Press F to pay respects",  # classic
+    "minecraft:stone",
+]
+"tooltip.long" = """
+First line
+second line"""
+
+[[quests]]
+name = { label = "Find the key", id = "quest_key" }
+
+[[quests]]
+name = { label = "Open the door" }
+
+[quests.reward]
+text = "A rusty key"
+"#;
+
+    fn translate(entries: &[TranslatableEntry], pairs: &[(&str, &str)]) -> TranslationResult {
+        TranslationResult {
+            translated: pairs
+                .iter()
+                .map(|(key, target)| {
+                    let entry = entries.iter().find(|e| e.key == *key).unwrap();
+                    TranslatedEntry {
+                        key: entry.key.clone(),
+                        source: entry.source.clone(),
+                        target: target.to_string(),
+                    }
+                })
+                .collect(),
+            failed: vec![],
+        }
+    }
+
+    #[test]
+    fn extracts_string_values_with_key_paths() {
+        let entries = TomlHandler::new().extract(SAMPLE).unwrap();
+        let pairs: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.source.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("title", "Welcome back"),
+                ("mod_id", "examplemod"),
+                ("ui.buttons.start", "Start game"),
+                ("ui.buttons.quit", "Quit to \"desktop\""),
+                ("ui.buttons.tips[0]", "Press F to pay respects"),
+                ("ui.buttons.tooltip.long", "First line\nsecond line"),
+                ("quests[0].name.label", "Find the key"),
+                ("quests[1].name.label", "Open the door"),
+                ("quests[1].reward.text", "A rusty key"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_comments_and_structure() {
+        let handler = TomlHandler::new();
+        let entries = handler.extract(SAMPLE).unwrap();
+        let translations = translate(
+            &entries,
+            &[
+                ("title", "다시 오신 것을 환영합니다"),
+                ("ui.buttons.quit", "'바탕 화면'으로 나가기"),
+                ("ui.buttons.tips[0]", "F를 눌러 \"조의\"를 표하세요"),
+                ("ui.buttons.tooltip.long", "첫 줄\n둘째 줄"),
+                ("quests[1].name.label", "문 열기"),
+            ],
+        );
+        let merged = handler.merge(SAMPLE, &translations).unwrap();
+
+        assert!(merged.contains("title = \"다시 오신 것을 환영합니다\"   # shown on the main menu"));
+        assert!(merged.contains("quit = \"'바탕 화면'으로 나가기\""));
+        assert!(merged.contains("\"F를 눌러 \\\"조의\\\"를 표하세요\",  # classic"));
+        assert!(merged.contains("\"tooltip.long\" = \"\"\"\n첫 줄\n둘째 줄\"\"\""));
+        assert!(merged.contains("name = { label = \"문 열기\" }"));
+        assert!(merged.contains("name = { label = \"Find the key\", id = \"quest_key\" }"));
+        assert!(merged.starts_with("# Client settings\n"));
+
+        let reparsed = handler.extract(&merged).unwrap();
+        let quit = reparsed
+            .iter()
+            .find(|e| e.key == "ui.buttons.quit")
+            .unwrap();
+        assert_eq!(quit.source, "'바탕 화면'으로 나가기");
+    }
+
+    #[test]
+    fn reports_line_of_syntax_errors() {
+        let error = TomlHandler::new()
+            .extract("ok = \"fine\"\nbroken = \"no end\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+}
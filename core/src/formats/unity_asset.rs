@@ -0,0 +1,455 @@
+/// Unity `.asset` (text-serialized YAML) format handler
+/// Extracts string fields from MonoBehaviour text tables while leaving Unity
+/// internals (`m_*` fields, `{fileID: ..., guid: ...}` references, GUIDs and
+/// numbers) untouched. Values are written back in the quoting style they had.
+use super::{FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+// Field line: indentation (with optional "- "), field name, value
+static FIELD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\s*(?:-\s+)?)([A-Za-z_][A-Za-z0-9_]*):(?:\s+(.*))?$").expect("valid field regex")
+});
+
+// Sequence item holding a bare string: "  - Some text"
+static ITEM_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*-\s+)(.+)$").expect("valid sequence item regex"));
+
+static GUID_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9a-fA-F]{32}$").expect("valid guid regex"));
+
+// Identifiers and asset paths: item_sword, UI.Menu.Title, Assets/Icons/sword.png
+static ID_OR_PATH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9_\-]+(?:[./][A-Za-z0-9_\-]+)+$|^[A-Za-z0-9]+(?:_[A-Za-z0-9]+)+$")
+        .expect("valid id regex")
+});
+
+// Field names that hold references or lookup keys rather than display text
+static SKIP_FIELDS: &[&str] = &[
+    "guid",
+    "fileID",
+    "type",
+    "key",
+    "id",
+    "Id",
+    "ID",
+    "serializedVersion",
+    "tableCollectionName",
+    "sharedTableData",
+    "locale",
+    "code",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteStyle {
+    Plain,
+    Single,
+    Double,
+}
+
+/// A string scalar in the file, possibly spanning several lines
+struct UnityField {
+    key: String,
+    /// Everything before the value on the first line, e.g. "    value: "
+    prefix: String,
+    first_line: usize,
+    /// Exclusive end line of the scalar
+    end_line: usize,
+    style: QuoteStyle,
+    text: String,
+}
+
+pub struct UnityAssetHandler;
+
+impl UnityAssetHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check whether the content is a text-serialized Unity asset
+    pub fn is_unity_yaml(content: &str) -> bool {
+        content.trim_start_matches('\u{feff}').starts_with("%YAML") || content.contains("--- !u!")
+    }
+
+    fn scan(lines: &[&str]) -> Vec<UnityField> {
+        let mut fields = Vec::new();
+        let mut index = 0;
+
+        while index < lines.len() {
+            let line = lines[index];
+            let (prefix, name, value) = if let Some(cap) = FIELD_RE.captures(line) {
+                let value = cap.get(3).map(|m| m.as_str()).unwrap_or_default();
+                let prefix_len = line.len() - value.len();
+                (&line[..prefix_len], Some(cap[2].to_string()), value)
+            } else if let Some(cap) = ITEM_RE.captures(line) {
+                let value = cap.get(2).map(|m| m.as_str()).unwrap_or_default();
+                (&line[..line.len() - value.len()], None, value)
+            } else {
+                index += 1;
+                continue;
+            };
+
+            let indent = leading_spaces(line);
+            let (style, raw, end_line) = collect_scalar(lines, index, value, indent);
+            let skip = name.as_deref().is_some_and(|name| !is_text_field(name));
+
+            if let Some(raw) = raw.filter(|_| !skip) {
+                let text = match style {
+                    QuoteStyle::Plain => raw,
+                    QuoteStyle::Single => raw.replace("''", "'"),
+                    QuoteStyle::Double => unescape_double(&raw),
+                };
+                fields.push(UnityField {
+                    key: format!("{}@{}", name.as_deref().unwrap_or("item"), index + 1),
+                    prefix: prefix.to_string(),
+                    first_line: index,
+                    end_line,
+                    style,
+                    text,
+                });
+            }
+            index = end_line.max(index + 1);
+        }
+
+        fields
+    }
+
+    /// Check if a value is display text rather than an id, path or number
+    pub(super) fn is_translatable_text(text: &str) -> bool {
+        let trimmed = text.trim();
+        !trimmed.is_empty()
+            && trimmed.chars().any(|c| c.is_alphabetic())
+            && !GUID_RE.is_match(trimmed)
+            && !ID_OR_PATH_RE.is_match(trimmed)
+    }
+
+    fn render_value(style: QuoteStyle, text: &str) -> String {
+        match style {
+            QuoteStyle::Plain if is_plain_safe(text) => text.to_string(),
+            QuoteStyle::Single if !text.contains('\n') => format!("'{}'", text.replace('\'', "''")),
+            _ => format!("\"{}\"", escape_double(text)),
+        }
+    }
+}
+
+impl Default for UnityAssetHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for UnityAssetHandler {
+    fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        if !Self::is_unity_yaml(content) {
+            return Err(FormatError::ParseError(
+                "not a text-serialized Unity asset".to_string(),
+            ));
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        let entries = Self::scan(&lines)
+            .into_iter()
+            .filter(|field| Self::is_translatable_text(&field.text))
+            .map(|field| {
+                let mut metadata = HashMap::new();
+                metadata.insert("line".to_string(), (field.first_line + 1).to_string());
+                TranslatableEntry {
+                    context: Some(format!("line {}", field.first_line + 1)),
+                    key: field.key,
+                    source: field.text,
+                    metadata,
+                }
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn merge(
+        &self,
+        original: &str,
+        translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        let translation_map: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+
+        let lines: Vec<&str> = original.lines().collect();
+        let line_ending = if original.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut replacements: HashMap<usize, (usize, String)> = HashMap::new();
+        for field in Self::scan(&lines) {
+            if let Some(target) = translation_map.get(field.key.as_str()) {
+                let rendered = format!(
+                    "{}{}",
+                    field.prefix,
+                    Self::render_value(field.style, target)
+                );
+                replacements.insert(field.first_line, (field.end_line, rendered));
+            }
+        }
+
+        let mut output: Vec<String> = Vec::with_capacity(lines.len());
+        let mut index = 0;
+        while index < lines.len() {
+            match replacements.remove(&index) {
+                Some((end_line, rendered)) => {
+                    output.push(rendered);
+                    index = end_line;
+                }
+                None => {
+                    output.push(lines[index].to_string());
+                    index += 1;
+                }
+            }
+        }
+
+        let mut result = output.join(line_ending);
+        if original.ends_with('\n') {
+            result.push_str(line_ending);
+        }
+        Ok(result)
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::UnityAsset
+    }
+}
+
+/// Check whether a field by this name can hold display text
+pub(super) fn is_text_field(name: &str) -> bool {
+    !name.starts_with("m_") && !SKIP_FIELDS.contains(&name)
+}
+
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Reads the scalar starting at `lines[start]`. Returns the quote style, the
+/// raw scalar (`None` for mappings, flow collections and block scalars) and
+/// the exclusive end line.
+fn collect_scalar(
+    lines: &[&str],
+    start: usize,
+    value: &str,
+    indent: usize,
+) -> (QuoteStyle, Option<String>, usize) {
+    let value = value.trim_end();
+    if value.is_empty() || value.starts_with(['{', '[', '|', '>', '&', '*', '!']) {
+        return (QuoteStyle::Plain, None, start + 1);
+    }
+
+    let style = match value.chars().next() {
+        Some('"') => QuoteStyle::Double,
+        Some('\'') => QuoteStyle::Single,
+        _ => QuoteStyle::Plain,
+    };
+
+    let mut parts = vec![value.to_string()];
+    let mut end = start + 1;
+    match style {
+        QuoteStyle::Plain => {
+            // Plain scalars continue on more-indented lines that are not fields
+            while end < lines.len() {
+                let next = lines[end];
+                if next.trim().is_empty()
+                    || leading_spaces(next) <= indent
+                    || FIELD_RE.is_match(next)
+                    || ITEM_RE.is_match(next)
+                {
+                    break;
+                }
+                parts.push(next.trim().to_string());
+                end += 1;
+            }
+            (style, Some(parts.join(" ")), end)
+        }
+        QuoteStyle::Single | QuoteStyle::Double => {
+            let quote = if style == QuoteStyle::Double {
+                '"'
+            } else {
+                '\''
+            };
+            let mut joined = value[1..].to_string();
+            while !closes_quote(&joined, quote) {
+                let Some(next) = lines.get(end) else {
+                    return (style, None, end);
+                };
+                // Folded line breaks become spaces, blank lines become newlines
+                if next.trim().is_empty() {
+                    joined.push('\n');
+                } else if joined.ends_with('\n') {
+                    joined.push_str(next.trim());
+                } else {
+                    joined.push(' ');
+                    joined.push_str(next.trim());
+                }
+                end += 1;
+            }
+            let inner = joined.trim_end();
+            (style, Some(inner[..inner.len() - 1].to_string()), end)
+        }
+    }
+}
+
+/// Whether `text` (the part after the opening quote) ends with a closing quote
+fn closes_quote(text: &str, quote: char) -> bool {
+    let trimmed = text.trim_end();
+    if !trimmed.ends_with(quote) {
+        return false;
+    }
+    if quote == '\'' {
+        // '' is an escaped quote; an odd run of trailing quotes closes
+        let run = trimmed.chars().rev().take_while(|&c| c == '\'').count();
+        return run % 2 == 1;
+    }
+    let body = &trimmed[..trimmed.len() - 1];
+    let backslashes = body.chars().rev().take_while(|&c| c == '\\').count();
+    backslashes % 2 == 0
+}
+
+fn unescape_double(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => result.push(decoded),
+                    None => {
+                        result.push_str("\\u");
+                        result.push_str(&code);
+                    }
+                }
+            }
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+fn escape_double(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+/// Whether `text` can be written as a plain YAML scalar without quoting
+fn is_plain_safe(text: &str) -> bool {
+    let Some(first) = text.chars().next() else {
+        return false;
+    };
+    !"-?:,[]{}#&*!|>'\"%@`".contains(first)
+        && text == text.trim()
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.contains(['\n', '\t', '\\'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    const SAMPLE: &str = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!114 &11400000
+MonoBehaviour:
+  m_ObjectHideFlags: 0
+  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_Name: LocalizationTable
+  entries:
+  - key: menu_start
+    value: Start game
+    fontSize: 24
+  - key: menu_quit
+    value: "Quit to \"desktop\"?\nProgress is saved."
+  - key: long_text
+    value: "This description is long enough that Unity wraps
+      it onto a second line."
+  tooltipIcon: Assets/UI/Icons/tooltip.png
+  labels:
+  - 'It''s dangerous'
+  - item_sword
+"#;
+
+    fn sources(entries: &[TranslatableEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.source.as_str()).collect()
+    }
+
+    #[test]
+    fn extracts_strings_but_not_unity_internals() {
+        let entries = UnityAssetHandler::new().extract(SAMPLE).unwrap();
+        assert_eq!(
+            sources(&entries),
+            [
+                "Start game",
+                "Quit to \"desktop\"?\nProgress is saved.",
+                "This description is long enough that Unity wraps it onto a second line.",
+                "It's dangerous",
+            ]
+        );
+        assert_eq!(entries[0].key, "value@10");
+    }
+
+    #[test]
+    fn merge_keeps_quoting_style_and_references() {
+        let handler = UnityAssetHandler::new();
+        let translated = |key: &str, target: &str| TranslatedEntry {
+            key: key.to_string(),
+            source: String::new(),
+            target: target.to_string(),
+        };
+        let translations = TranslationResult {
+            translated: vec![
+                translated("value@10", "게임 시작"),
+                translated(
+                    "value@13",
+                    "\"바탕화면\"으로 나갈까요?\n진행 상황은 저장됩니다.",
+                ),
+                translated("value@15", "긴 설명입니다."),
+                translated("item@19", "위험해요: 조심"),
+            ],
+            failed: vec![],
+        };
+
+        let merged = handler.merge(SAMPLE, &translations).unwrap();
+        assert!(merged.contains("    value: 게임 시작\n    fontSize: 24\n"));
+        assert!(
+            merged.contains(r#"    value: "\"바탕화면\"으로 나갈까요?\n진행 상황은 저장됩니다.""#)
+        );
+        assert!(merged.contains("    value: \"긴 설명입니다.\"\n  tooltipIcon:"));
+        assert!(merged.contains("  - '위험해요: 조심'\n  - item_sword\n"));
+        assert!(merged.contains("guid: 0123456789abcdef0123456789abcdef"));
+        assert!(!merged.contains("second line"));
+    }
+
+    #[test]
+    fn rejects_non_unity_content() {
+        assert!(UnityAssetHandler::new().extract("title: Hello\n").is_err());
+    }
+}
@@ -0,0 +1,1600 @@
+/// Unity AssetBundles (`.assetbundle`, `.unity3d`) and serialized asset files
+/// A UnityFS bundle is a big-endian header, a block/directory table and the
+/// data of its files, split into blocks compressed with LZ4 or LZMA. The
+/// files are mostly SerializedFiles: a table of object types (with their
+/// field layout, the type tree) and the objects themselves. Text is taken
+/// from `TextAsset.m_Script` and from the string fields of MonoBehaviours
+/// that look like display text, with the same field rules as
+/// [`unity_asset`](super::unity_asset).
+/// Bundles are binary, so [`UnityBundleHandler`] only implements the byte
+/// methods of [`FormatHandler`]. Written bundles keep the Unity version of
+/// the original and store their blocks LZ4 compressed.
+use super::unity_asset::{is_text_field, UnityAssetHandler};
+use super::{
+    binary_only, FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult,
+};
+use log::warn;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::Path;
+
+const BUNDLE_SIGNATURE: &str = "UnityFS";
+
+// Bundle flags
+const COMPRESSION_MASK: u32 = 0x3F;
+const BLOCKS_AND_DIRECTORY_COMBINED: u32 = 0x40;
+const BLOCKS_INFO_AT_END: u32 = 0x80;
+const BLOCK_INFO_NEEDS_PADDING: u32 = 0x200;
+
+const COMPRESSION_NONE: u32 = 0;
+const COMPRESSION_LZMA: u32 = 1;
+const COMPRESSION_LZ4: u32 = 2;
+const COMPRESSION_LZ4HC: u32 = 3;
+
+// Most a compressed block can expand, used to reject corrupt sizes before
+// allocating: LZ4 tops out near 255:1, LZMA near 7000:1.
+const MAX_LZ4_RATIO: usize = 256;
+const MAX_LZMA_RATIO: usize = 8192;
+
+const NODE_SERIALIZED_FILE: u32 = 0x4;
+/// Uncompressed size of the blocks written back, as in Unity's own bundles
+const BLOCK_SIZE: usize = 128 * 1024;
+
+/// SerializedFile format versions read here: Unity 5.5 to Unity 6
+const MIN_SERIALIZED_VERSION: u32 = 17;
+const MAX_SERIALIZED_VERSION: u32 = 22;
+
+const CLASS_TEXT_ASSET: i32 = 49;
+const CLASS_MONO_BEHAVIOUR: i32 = 114;
+
+// Type tree node flags
+const TYPE_FLAG_ARRAY: u8 = 0x1;
+const META_FLAG_ALIGN: i32 = 0x4000;
+
+/// Strings type trees refer to by offset with the high bit set, joined with
+/// NULs in Unity's order
+const COMMON_STRINGS: &str = "AABB\0AnimationClip\0AnimationCurve\0AnimationState\0Array\0Base\0\
+BitField\0bitset\0bool\0char\0ColorRGBA\0Component\0data\0deque\0double\0dynamic_array\0\
+FastPropertyName\0first\0float\0Font\0GameObject\0Generic Mono\0GradientNEW\0GUID\0GUIStyle\0int\0\
+list\0long long\0map\0Matrix4x4f\0MdFour\0MonoBehaviour\0MonoScript\0m_ByteSize\0m_Curve\0\
+m_EditorClassIdentifier\0m_EditorHideFlags\0m_Enabled\0m_ExtensionPtr\0m_GameObject\0m_Index\0\
+m_IsArray\0m_IsStatic\0m_MetaFlag\0m_Name\0m_ObjectHideFlags\0m_PrefabInternal\0\
+m_PrefabParentObject\0m_Script\0m_StaticEditorFlags\0m_Type\0m_Version\0Object\0pair\0\
+PPtr<Component>\0PPtr<GameObject>\0PPtr<Material>\0PPtr<MonoBehaviour>\0PPtr<MonoScript>\0\
+PPtr<Object>\0PPtr<Prefab>\0PPtr<Sprite>\0PPtr<TextAsset>\0PPtr<Texture>\0PPtr<Texture2D>\0\
+PPtr<Transform>\0Prefab\0Quaternionf\0Rectf\0RectInt\0RectOffset\0second\0set\0short\0size\0\
+SInt16\0SInt32\0SInt64\0SInt8\0staticvector\0string\0TextAsset\0TextMesh\0Texture\0Texture2D\0\
+Transform\0TypelessData\0UInt16\0UInt32\0UInt64\0UInt8\0unsigned int\0unsigned long long\0\
+unsigned short\0vector\0Vector2f\0Vector3f\0Vector4f\0m_ScriptingClassIdentifier\0Gradient\0\
+Type*\0int2_storage\0int3_storage\0BoundsInt\0m_CorrespondingSourceObject\0m_PrefabInstance\0\
+m_PrefabAsset\0FileSize\0Hash128\0";
+
+/// Check whether the bytes start like a UnityFS bundle
+pub fn is_asset_bundle(bytes: &[u8]) -> bool {
+    bytes.starts_with(BUNDLE_SIGNATURE.as_bytes()) && bytes.get(BUNDLE_SIGNATURE.len()) == Some(&0)
+}
+
+/// A file inside a bundle
+#[derive(Debug, Clone)]
+pub struct BundleFile {
+    pub path: String,
+    pub flags: u32,
+    pub content: BundleContent,
+}
+
+#[derive(Debug, Clone)]
+pub enum BundleContent {
+    Serialized(SerializedFile),
+    /// Resources (`.resS`, `.resource`) and files that could not be read
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetBundle {
+    pub version: u32,
+    pub unity_version: String,
+    pub unity_revision: String,
+    pub files: Vec<BundleFile>,
+}
+
+impl AssetBundle {
+    pub fn parse(bytes: &[u8]) -> Result<Self, FormatError> {
+        let mut reader = Reader::new(bytes, false);
+        let signature = reader.cstring()?;
+        if signature != BUNDLE_SIGNATURE {
+            return Err(FormatError::UnsupportedFormat(format!(
+                "{signature} bundles are not supported, only UnityFS"
+            )));
+        }
+        let version = reader.u32()?;
+        let unity_version = reader.cstring()?;
+        let unity_revision = reader.cstring()?;
+        let _size = reader.i64()?;
+        let compressed_info_size = reader.u32()? as usize;
+        let info_size = reader.u32()? as usize;
+        let flags = reader.u32()?;
+        if version >= 7 {
+            reader.align(16)?;
+        }
+
+        let compressed_info = if flags & BLOCKS_INFO_AT_END != 0 {
+            let start = bytes
+                .len()
+                .checked_sub(compressed_info_size)
+                .ok_or_else(|| parse_error("block table runs past the bundle".into()))?;
+            &bytes[start..]
+        } else {
+            reader.take(compressed_info_size)?
+        };
+        let info = decompress(compressed_info, flags & COMPRESSION_MASK, info_size)?;
+        if flags & BLOCK_INFO_NEEDS_PADDING != 0 {
+            reader.align(16)?;
+        }
+
+        let mut info_reader = Reader::new(&info, false);
+        info_reader.take(16)?; // hash of the uncompressed data
+        let block_count = info_reader.count()?;
+        let mut data = Vec::new();
+        for _ in 0..block_count {
+            let size = info_reader.u32()? as usize;
+            let compressed_size = info_reader.u32()? as usize;
+            let block_flags = info_reader.u16()? as u32;
+            let block = reader.take(compressed_size)?;
+            data.extend(decompress(block, block_flags & COMPRESSION_MASK, size)?);
+        }
+
+        let file_count = info_reader.count()?;
+        let mut files = Vec::with_capacity(file_count);
+        for _ in 0..file_count {
+            let offset = usize::try_from(info_reader.i64()?).ok();
+            let size = usize::try_from(info_reader.i64()?).ok();
+            let file_flags = info_reader.u32()?;
+            let path = info_reader.cstring()?;
+            let raw = offset
+                .zip(size)
+                .and_then(|(offset, size)| data.get(offset..offset.checked_add(size)?))
+                .ok_or_else(|| parse_error(format!("{path} runs past the bundle data")))?;
+            let content = if file_flags & NODE_SERIALIZED_FILE != 0 {
+                match SerializedFile::parse(raw) {
+                    Ok(file) => BundleContent::Serialized(file),
+                    Err(error) => {
+                        warn!("keeping {} in the bundle as is: {}", path, error);
+                        BundleContent::Raw(raw.to_vec())
+                    }
+                }
+            } else {
+                BundleContent::Raw(raw.to_vec())
+            };
+            files.push(BundleFile {
+                path,
+                flags: file_flags,
+                content,
+            });
+        }
+
+        Ok(Self {
+            version,
+            unity_version,
+            unity_revision,
+            files,
+        })
+    }
+
+    /// Serialize the bundle with an uncompressed block table and LZ4 blocks
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for file in &self.files {
+            let offset = data.len();
+            match &file.content {
+                BundleContent::Serialized(serialized) => data.extend(serialized.to_bytes()?),
+                BundleContent::Raw(raw) => data.extend_from_slice(raw),
+            }
+            directory.extend_from_slice(&(offset as i64).to_be_bytes());
+            directory.extend_from_slice(&((data.len() - offset) as i64).to_be_bytes());
+            directory.extend_from_slice(&file.flags.to_be_bytes());
+            directory.extend_from_slice(file.path.as_bytes());
+            directory.push(0);
+        }
+
+        let mut blocks = Vec::new();
+        let mut info = vec![0u8; 16];
+        let chunks: Vec<&[u8]> = data.chunks(BLOCK_SIZE).collect();
+        info.extend_from_slice(&to_i32(chunks.len(), "block count")?.to_be_bytes());
+        for chunk in chunks {
+            let compressed = lz4_flex::block::compress(chunk);
+            let (stored, compression) = if compressed.len() < chunk.len() {
+                (compressed.as_slice(), COMPRESSION_LZ4)
+            } else {
+                (chunk, COMPRESSION_NONE)
+            };
+            info.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+            info.extend_from_slice(&(stored.len() as u32).to_be_bytes());
+            info.extend_from_slice(&(compression as u16).to_be_bytes());
+            blocks.extend_from_slice(stored);
+        }
+        info.extend_from_slice(&to_i32(self.files.len(), "file count")?.to_be_bytes());
+        info.extend_from_slice(&directory);
+
+        let mut bytes = Vec::with_capacity(info.len() + blocks.len() + 64);
+        bytes.extend_from_slice(BUNDLE_SIGNATURE.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&self.version.to_be_bytes());
+        bytes.extend_from_slice(self.unity_version.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(self.unity_revision.as_bytes());
+        bytes.push(0);
+        let size_at = bytes.len();
+        bytes.extend_from_slice(&0i64.to_be_bytes());
+        let info_size = to_u32(info.len(), "block table")?;
+        bytes.extend_from_slice(&info_size.to_be_bytes());
+        bytes.extend_from_slice(&info_size.to_be_bytes());
+        bytes.extend_from_slice(&BLOCKS_AND_DIRECTORY_COMBINED.to_be_bytes());
+        if self.version >= 7 {
+            pad(&mut bytes, 16);
+        }
+        bytes.extend_from_slice(&info);
+        bytes.extend_from_slice(&blocks);
+        let size = (bytes.len() as i64).to_be_bytes();
+        bytes[size_at..size_at + 8].copy_from_slice(&size);
+        Ok(bytes)
+    }
+
+    /// Text of every serialized file, keyed `<file>:<path id>:<field>`
+    pub fn extract(&self) -> Vec<TranslatableEntry> {
+        let mut entries = Vec::new();
+        for file in &self.files {
+            if let BundleContent::Serialized(serialized) = &file.content {
+                entries.extend(serialized.extract().into_iter().map(|mut entry| {
+                    entry.key = format!("{}:{}", file.path, entry.key);
+                    entry
+                        .metadata
+                        .insert("bundle_file".to_string(), file.path.clone());
+                    entry
+                }));
+            }
+        }
+        entries
+    }
+
+    /// Replace text with its translations; untranslated keys keep the original
+    pub fn apply(&mut self, translations: &TranslationResult) -> Result<(), FormatError> {
+        for file in &mut self.files {
+            let BundleContent::Serialized(serialized) = &mut file.content else {
+                continue;
+            };
+            let prefix = format!("{}:", file.path);
+            let targets: HashMap<&str, &str> = translations
+                .translated
+                .iter()
+                .filter_map(|entry| {
+                    let key = entry.key.strip_prefix(&prefix)?;
+                    Some((key, entry.target.as_str()))
+                })
+                .collect();
+            serialized.apply_targets(&targets)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct UnityBundleHandler;
+
+impl UnityBundleHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for UnityBundleHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatHandler for UnityBundleHandler {
+    fn extract(&self, _content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Err(binary_only(FileFormat::UnityBundle))
+    }
+
+    fn merge(
+        &self,
+        _original: &str,
+        _translations: &TranslationResult,
+    ) -> Result<String, FormatError> {
+        Err(binary_only(FileFormat::UnityBundle))
+    }
+
+    fn extract_bytes(
+        &self,
+        _path: &Path,
+        content: &[u8],
+    ) -> Result<Vec<TranslatableEntry>, FormatError> {
+        Ok(AssetBundle::parse(content)?.extract())
+    }
+
+    fn merge_bytes(
+        &self,
+        _path: &Path,
+        original: &[u8],
+        translations: &TranslationResult,
+        _target_lang: &str,
+    ) -> Result<Vec<u8>, FormatError> {
+        let mut bundle = AssetBundle::parse(original)?;
+        bundle.apply(translations)?;
+        bundle.to_bytes()
+    }
+
+    fn format(&self) -> FileFormat {
+        FileFormat::UnityBundle
+    }
+}
+
+/// Field layout of one object type
+#[derive(Debug, Clone)]
+struct TypeNode {
+    type_name: String,
+    name: String,
+    byte_size: i32,
+    flags: u8,
+    meta_flag: i32,
+    children: Vec<TypeNode>,
+}
+
+impl TypeNode {
+    fn leaf(type_name: &str, name: &str, byte_size: i32, meta_flag: i32) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            name: name.to_string(),
+            byte_size,
+            flags: 0,
+            meta_flag,
+            children: Vec::new(),
+        }
+    }
+
+    /// `TextAsset` as written by Unity 2017 and later, for files built
+    /// without type trees. Fields after `m_Script` are copied as they are.
+    fn text_asset() -> Self {
+        let string = |name: &str| {
+            let mut node = Self::leaf("string", name, -1, META_FLAG_ALIGN);
+            node.children
+                .push(Self::leaf("Array", "Array", -1, META_FLAG_ALIGN));
+            node
+        };
+        let mut root = Self::leaf("TextAsset", "Base", -1, 0);
+        root.children = vec![string("m_Name"), string("m_Script")];
+        root
+    }
+
+    fn is_array(&self) -> bool {
+        self.flags & TYPE_FLAG_ARRAY != 0
+    }
+
+    fn aligns(&self) -> bool {
+        self.meta_flag & META_FLAG_ALIGN != 0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SerializedType {
+    class_id: i32,
+    tree: Option<TypeNode>,
+}
+
+#[derive(Debug, Clone)]
+struct ObjectInfo {
+    path_id: i64,
+    /// Start of the data, relative to the data offset
+    start: usize,
+    size: usize,
+    type_index: usize,
+    /// Where the object's start and size are stored in the metadata
+    start_field_at: usize,
+    size_field_at: usize,
+}
+
+/// A SerializedFile: a `.assets` file or a `CAB-…` file inside a bundle
+#[derive(Debug, Clone)]
+pub struct SerializedFile {
+    bytes: Vec<u8>,
+    version: u32,
+    little_endian: bool,
+    data_offset: usize,
+    types: Vec<SerializedType>,
+    objects: Vec<ObjectInfo>,
+    /// Object data rewritten by `apply`, by object index
+    rewritten: HashMap<usize, Vec<u8>>,
+}
+
+/// A string field found in an object
+struct FoundString {
+    field: String,
+    text: String,
+}
+
+impl SerializedFile {
+    pub fn parse(bytes: &[u8]) -> Result<Self, FormatError> {
+        let mut header = Reader::new(bytes, false);
+        header.u32()?; // metadata size
+        header.u32()?; // file size
+        let version = header.u32()?;
+        let mut data_offset = header.u32()? as u64;
+        if !(MIN_SERIALIZED_VERSION..=MAX_SERIALIZED_VERSION).contains(&version) {
+            return Err(FormatError::UnsupportedFormat(format!(
+                "serialized file version {version}"
+            )));
+        }
+        let little_endian = header.u8()? == 0;
+        header.take(3)?;
+        if version >= 22 {
+            header.u32()?; // metadata size
+            header.i64()?; // file size
+            data_offset = header.i64()? as u64;
+            header.i64()?;
+        }
+        let data_offset = usize::try_from(data_offset)
+            .ok()
+            .filter(|offset| *offset <= bytes.len())
+            .ok_or_else(|| parse_error(format!("data offset {data_offset} is past the file")))?;
+
+        let mut reader = Reader::new(bytes, little_endian);
+        reader.pos = header.pos;
+        reader.cstring()?; // Unity version
+        reader.i32()?; // target platform
+        let has_type_trees = reader.u8()? != 0;
+        let type_count = reader.count()?;
+        let mut types = Vec::with_capacity(type_count);
+        for _ in 0..type_count {
+            types.push(read_type(&mut reader, version, has_type_trees)?);
+        }
+
+        let object_count = reader.count()?;
+        let mut objects = Vec::with_capacity(object_count);
+        for _ in 0..object_count {
+            reader.align(4)?;
+            let path_id = reader.i64()?;
+            let start_field_at = reader.pos;
+            let start = if version >= 22 {
+                reader.i64()? as u64
+            } else {
+                reader.u32()? as u64
+            };
+            let size_field_at = reader.pos;
+            let size = reader.u32()? as usize;
+            let type_index = reader.i32()?;
+            let type_index = usize::try_from(type_index)
+                .ok()
+                .filter(|index| *index < types.len())
+                .ok_or_else(|| parse_error(format!("object {path_id} has no type")))?;
+            let start = usize::try_from(start)
+                .ok()
+                .filter(|start| {
+                    data_offset
+                        .checked_add(*start)
+                        .and_then(|begin| begin.checked_add(size))
+                        .is_some_and(|end| end <= bytes.len())
+                })
+                .ok_or_else(|| parse_error(format!("object {path_id} runs past the file")))?;
+            objects.push(ObjectInfo {
+                path_id,
+                start,
+                size,
+                type_index,
+                start_field_at,
+                size_field_at,
+            });
+        }
+
+        Ok(Self {
+            bytes: bytes.to_vec(),
+            version,
+            little_endian,
+            data_offset,
+            types,
+            objects,
+            rewritten: HashMap::new(),
+        })
+    }
+
+    /// Serialize the file; objects are laid out again, 8-byte aligned, in
+    /// their original order
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let mut bytes = self.bytes[..self.data_offset].to_vec();
+        let mut order: Vec<usize> = (0..self.objects.len()).collect();
+        order.sort_by_key(|index| self.objects[*index].start);
+        let mut placed = vec![(0usize, 0usize); self.objects.len()];
+        for index in order {
+            let written = bytes.len() - self.data_offset;
+            bytes.resize(self.data_offset + written.div_ceil(8) * 8, 0);
+            let data = self.object_data(index);
+            placed[index] = (bytes.len() - self.data_offset, data.len());
+            bytes.extend_from_slice(data);
+        }
+
+        for (object, (start, size)) in self.objects.iter().zip(placed) {
+            let size = to_u32(size, "object")?;
+            if self.version >= 22 {
+                let start = self.encode_i64(start as i64);
+                bytes[object.start_field_at..object.start_field_at + 8].copy_from_slice(&start);
+            } else {
+                let start = self.encode_u32(to_u32(start, "serialized file")?);
+                bytes[object.start_field_at..object.start_field_at + 4].copy_from_slice(&start);
+            }
+            let size = self.encode_u32(size);
+            bytes[object.size_field_at..object.size_field_at + 4].copy_from_slice(&size);
+        }
+
+        // Header fields are always big-endian
+        if self.version >= 22 {
+            let size = (bytes.len() as i64).to_be_bytes();
+            bytes[24..32].copy_from_slice(&size);
+        } else {
+            let size = to_u32(bytes.len(), "serialized file")?.to_be_bytes();
+            bytes[4..8].copy_from_slice(&size);
+        }
+        Ok(bytes)
+    }
+
+    /// `TextAsset` contents and MonoBehaviour display strings, keyed
+    /// `<path id>:<field>`
+    pub fn extract(&self) -> Vec<TranslatableEntry> {
+        let mut entries = Vec::new();
+        for (index, object) in self.objects.iter().enumerate() {
+            let Some((class_id, found)) = self.read_strings(index) else {
+                continue;
+            };
+            let object_name = found
+                .iter()
+                .find(|string| string.field == "m_Name")
+                .map(|string| string.text.clone())
+                .unwrap_or_default();
+            let class_name = if class_id == CLASS_TEXT_ASSET {
+                "TextAsset"
+            } else {
+                "MonoBehaviour"
+            };
+            for string in found {
+                let translatable = if class_id == CLASS_TEXT_ASSET {
+                    string.field == "m_Script" && !string.text.trim().is_empty()
+                } else {
+                    is_text_field(field_name(&string.field))
+                        && UnityAssetHandler::is_translatable_text(&string.text)
+                };
+                if !translatable {
+                    continue;
+                }
+                let mut metadata = HashMap::new();
+                metadata.insert("class".to_string(), class_name.to_string());
+                metadata.insert("path_id".to_string(), object.path_id.to_string());
+                metadata.insert("field".to_string(), string.field.clone());
+                if !object_name.is_empty() {
+                    metadata.insert("object_name".to_string(), object_name.clone());
+                }
+                entries.push(TranslatableEntry {
+                    key: format!("{}:{}", object.path_id, string.field),
+                    context: Some(format!("{class_name} {object_name} {}", string.field)),
+                    source: string.text,
+                    metadata,
+                });
+            }
+        }
+        entries
+    }
+
+    /// Replace text with its translations; untranslated keys keep the original
+    pub fn apply(&mut self, translations: &TranslationResult) -> Result<(), FormatError> {
+        let targets: HashMap<&str, &str> = translations
+            .translated
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.target.as_str()))
+            .collect();
+        self.apply_targets(&targets)
+    }
+
+    fn apply_targets(&mut self, targets: &HashMap<&str, &str>) -> Result<(), FormatError> {
+        let mut by_object: HashMap<i64, HashMap<String, String>> = HashMap::new();
+        for (key, target) in targets {
+            let Some((path_id, field)) = key.split_once(':') else {
+                continue;
+            };
+            if let Ok(path_id) = path_id.parse::<i64>() {
+                by_object
+                    .entry(path_id)
+                    .or_default()
+                    .insert(field.to_string(), target.to_string());
+            }
+        }
+        for index in 0..self.objects.len() {
+            let Some(replacements) = by_object.get(&self.objects[index].path_id) else {
+                continue;
+            };
+            let Some(tree) = self.tree_for(index) else {
+                continue;
+            };
+            let mut walker = Walker::new(self.object_data(index), self.little_endian, true);
+            walker.replacements = Some(replacements);
+            walker.walk(tree, "")?;
+            let data = walker.finish();
+            self.rewritten.insert(index, data);
+        }
+        Ok(())
+    }
+
+    fn object_data(&self, index: usize) -> &[u8] {
+        match self.rewritten.get(&index) {
+            Some(data) => data,
+            None => {
+                let object = &self.objects[index];
+                let start = self.data_offset + object.start;
+                &self.bytes[start..start + object.size]
+            }
+        }
+    }
+
+    /// Field layout of a text-bearing object; `None` for other classes and
+    /// for MonoBehaviours stored without a type tree
+    fn tree_for(&self, index: usize) -> Option<&TypeNode> {
+        let object_type = &self.types[self.objects[index].type_index];
+        match (object_type.class_id, &object_type.tree) {
+            (CLASS_TEXT_ASSET | CLASS_MONO_BEHAVIOUR, Some(tree)) => Some(tree),
+            (CLASS_TEXT_ASSET, None) => Some(&TEXT_ASSET_TREE),
+            _ => None,
+        }
+    }
+
+    fn read_strings(&self, index: usize) -> Option<(i32, Vec<FoundString>)> {
+        let tree = self.tree_for(index)?;
+        let mut walker = Walker::new(self.object_data(index), self.little_endian, false);
+        if let Err(error) = walker.walk(tree, "") {
+            warn!(
+                "skipping object {} of a serialized file: {}",
+                self.objects[index].path_id, error
+            );
+            return None;
+        }
+        let class_id = self.types[self.objects[index].type_index].class_id;
+        Some((class_id, walker.found))
+    }
+
+    fn encode_u32(&self, value: u32) -> [u8; 4] {
+        if self.little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    }
+
+    fn encode_i64(&self, value: i64) -> [u8; 8] {
+        if self.little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    }
+}
+
+static TEXT_ASSET_TREE: Lazy<TypeNode> = Lazy::new(TypeNode::text_asset);
+
+fn read_type(
+    reader: &mut Reader<'_>,
+    version: u32,
+    has_type_trees: bool,
+) -> Result<SerializedType, FormatError> {
+    let class_id = reader.i32()?;
+    reader.u8()?; // stripped
+    reader.i16()?; // script type index
+    if class_id == CLASS_MONO_BEHAVIOUR {
+        reader.take(16)?; // script hash
+    }
+    reader.take(16)?; // type hash
+    if !has_type_trees {
+        return Ok(SerializedType {
+            class_id,
+            tree: None,
+        });
+    }
+
+    let node_count = reader.count()?;
+    let strings_size = reader.count()?;
+    let mut flat = Vec::with_capacity(node_count);
+    for _ in 0..node_count {
+        reader.u16()?; // version
+        let level = reader.u8()?;
+        let flags = reader.u8()?;
+        let type_offset = reader.u32()?;
+        let name_offset = reader.u32()?;
+        let byte_size = reader.i32()?;
+        reader.i32()?; // index
+        let meta_flag = reader.i32()?;
+        if version >= 19 {
+            reader.take(8)?; // referenced type hash
+        }
+        flat.push((level, flags, type_offset, name_offset, byte_size, meta_flag));
+    }
+    let strings = reader.take(strings_size)?;
+    if version >= 21 {
+        let dependencies = reader.count()?;
+        reader.take(dependencies.saturating_mul(4))?;
+    }
+
+    let mut nodes = flat
+        .into_iter()
+        .map(
+            |(level, flags, type_offset, name_offset, byte_size, meta_flag)| {
+                Ok((
+                    level,
+                    TypeNode {
+                        type_name: tree_string(strings, type_offset)?,
+                        name: tree_string(strings, name_offset)?,
+                        byte_size,
+                        flags,
+                        meta_flag,
+                        children: Vec::new(),
+                    },
+                ))
+            },
+        )
+        .collect::<Result<Vec<_>, FormatError>>()?;
+    let tree = build_tree(&mut nodes)?;
+    Ok(SerializedType {
+        class_id,
+        tree: Some(tree),
+    })
+}
+
+/// Nest the depth-first node list into a tree
+fn build_tree(nodes: &mut Vec<(u8, TypeNode)>) -> Result<TypeNode, FormatError> {
+    // Pop children off a stack of open nodes whenever the level drops
+    let mut stack: Vec<(u8, TypeNode)> = Vec::new();
+    for (level, node) in nodes.drain(..) {
+        while stack.last().is_some_and(|(open, _)| *open >= level) {
+            close_node(&mut stack)?;
+        }
+        stack.push((level, node));
+    }
+    while stack.len() > 1 {
+        close_node(&mut stack)?;
+    }
+    stack
+        .pop()
+        .map(|(_, root)| root)
+        .ok_or_else(|| parse_error("empty type tree".into()))
+}
+
+fn close_node(stack: &mut Vec<(u8, TypeNode)>) -> Result<(), FormatError> {
+    let (_, node) = stack
+        .pop()
+        .ok_or_else(|| parse_error("type tree has no open node".into()))?;
+    match stack.last_mut() {
+        Some((_, parent)) => {
+            parent.children.push(node);
+            Ok(())
+        }
+        None => Err(parse_error("type tree has more than one root".into())),
+    }
+}
+
+fn tree_string(strings: &[u8], offset: u32) -> Result<String, FormatError> {
+    let (source, offset) = if offset & 0x8000_0000 != 0 {
+        (COMMON_STRINGS.as_bytes(), (offset & 0x7FFF_FFFF) as usize)
+    } else {
+        (strings, offset as usize)
+    };
+    let rest = source
+        .get(offset..)
+        .ok_or_else(|| parse_error(format!("type tree string {offset} is out of range")))?;
+    let end = rest
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(rest.len());
+    Ok(String::from_utf8_lossy(&rest[..end]).into_owned())
+}
+
+/// Reads an object along its type tree, collecting string fields and, when
+/// writing, copying the data with strings replaced
+struct Walker<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+    out: Option<Vec<u8>>,
+    replacements: Option<&'a HashMap<String, String>>,
+    found: Vec<FoundString>,
+    /// Set when a field cannot be followed; the rest is copied as is
+    stopped: bool,
+}
+
+impl<'a> Walker<'a> {
+    fn new(data: &'a [u8], little_endian: bool, write: bool) -> Self {
+        Self {
+            data,
+            pos: 0,
+            little_endian,
+            out: write.then(|| Vec::with_capacity(data.len())),
+            replacements: None,
+            found: Vec::new(),
+            stopped: false,
+        }
+    }
+
+    fn walk(&mut self, node: &TypeNode, path: &str) -> Result<(), FormatError> {
+        if self.stopped {
+            return Ok(());
+        }
+        if node.type_name == "string" {
+            self.string(path)?;
+            if node.children.first().is_some_and(TypeNode::aligns) {
+                self.align()?;
+            }
+        } else if node.is_array() {
+            self.array(node, path)?;
+        } else if node.type_name == "TypelessData" {
+            let len = self.length()?;
+            self.copy(len)?;
+        } else if node.type_name == "ManagedReferencesRegistry" {
+            // [SerializeReference] data; always the last field
+            self.stopped = true;
+            return Ok(());
+        } else if !node.children.is_empty() {
+            for child in &node.children {
+                let child_path = if child.is_array() {
+                    path.to_string()
+                } else if path.is_empty() {
+                    child.name.clone()
+                } else {
+                    format!("{path}.{}", child.name)
+                };
+                self.walk(child, &child_path)?;
+            }
+        } else {
+            let size = usize::try_from(node.byte_size).map_err(|_| {
+                parse_error(format!(
+                    "field {path} of type {} has no size",
+                    node.type_name
+                ))
+            })?;
+            self.copy(size)?;
+        }
+        if node.aligns() {
+            self.align()?;
+        }
+        Ok(())
+    }
+
+    /// `Array` nodes: a count, then the elements
+    fn array(&mut self, node: &TypeNode, path: &str) -> Result<(), FormatError> {
+        let element = node
+            .children
+            .get(1)
+            .ok_or_else(|| parse_error(format!("array {path} has no element type")))?;
+        let count = self.length()?;
+        if element.children.is_empty() && element.type_name != "string" {
+            let size = usize::try_from(element.byte_size)
+                .map_err(|_| parse_error(format!("array {path} has elements without a size")))?;
+            let total = count
+                .checked_mul(size)
+                .ok_or_else(|| parse_error(format!("array {path} is too large")))?;
+            return self.copy(total);
+        }
+        for index in 0..count {
+            self.walk(element, &format!("{path}[{index}]"))?;
+        }
+        Ok(())
+    }
+
+    fn string(&mut self, path: &str) -> Result<(), FormatError> {
+        let len = self.read_length()?;
+        let raw = self.read(len)?;
+        let replacement = self.replacements.and_then(|map| map.get(path));
+        if let Some(out) = &mut self.out {
+            let text = replacement.map_or(raw, |text| text.as_bytes());
+            let len = to_i32(text.len(), "string")?;
+            out.extend_from_slice(&if self.little_endian {
+                len.to_le_bytes()
+            } else {
+                len.to_be_bytes()
+            });
+            out.extend_from_slice(text);
+        }
+        if let Ok(text) = std::str::from_utf8(raw) {
+            self.found.push(FoundString {
+                field: path.to_string(),
+                text: text.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reads and copies an element count
+    fn length(&mut self) -> Result<usize, FormatError> {
+        let len = self.read_length()?;
+        if let Some(out) = &mut self.out {
+            let len = len as i32;
+            out.extend_from_slice(&if self.little_endian {
+                len.to_le_bytes()
+            } else {
+                len.to_be_bytes()
+            });
+        }
+        Ok(len)
+    }
+
+    fn read_length(&mut self) -> Result<usize, FormatError> {
+        let raw: [u8; 4] = self.read(4)?.try_into().expect("4 bytes");
+        let len = if self.little_endian {
+            i32::from_le_bytes(raw)
+        } else {
+            i32::from_be_bytes(raw)
+        };
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= self.data.len() - self.pos)
+            .ok_or_else(|| parse_error(format!("length {len} at byte {} is invalid", self.pos)))
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], FormatError> {
+        let data = self.data;
+        let slice = data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| parse_error(format!("object ends before byte {}", self.pos + len)))?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn copy(&mut self, len: usize) -> Result<(), FormatError> {
+        let slice = self.read(len)?;
+        if let Some(out) = &mut self.out {
+            out.extend_from_slice(slice);
+        }
+        Ok(())
+    }
+
+    fn align(&mut self) -> Result<(), FormatError> {
+        self.pos = (self.pos + 3) & !3;
+        if self.pos > self.data.len() {
+            return Err(parse_error("object ends inside its padding".into()));
+        }
+        if let Some(out) = &mut self.out {
+            pad(out, 4);
+        }
+        Ok(())
+    }
+
+    /// The written object, with anything after the last field kept
+    fn finish(self) -> Vec<u8> {
+        let mut out = self.out.unwrap_or_default();
+        out.extend_from_slice(&self.data[self.pos..]);
+        out
+    }
+}
+
+/// Last field name of a path, without array indices: `lines[2].text` → `text`
+fn field_name(path: &str) -> &str {
+    let last = path.rsplit('.').next().unwrap_or(path);
+    last.split('[').next().unwrap_or(last)
+}
+
+fn decompress(input: &[u8], compression: u32, size: usize) -> Result<Vec<u8>, FormatError> {
+    let max_size = match compression {
+        COMPRESSION_LZ4 | COMPRESSION_LZ4HC => input.len().saturating_mul(MAX_LZ4_RATIO),
+        COMPRESSION_LZMA => input.len().saturating_mul(MAX_LZMA_RATIO),
+        _ => input.len(),
+    };
+    if size > max_size {
+        return Err(parse_error(format!(
+            "block of {} bytes cannot decompress to {size} bytes",
+            input.len()
+        )));
+    }
+    let output = match compression {
+        COMPRESSION_NONE => input.to_vec(),
+        COMPRESSION_LZ4 | COMPRESSION_LZ4HC => lz4_flex::block::decompress(input, size)
+            .map_err(|e| parse_error(format!("LZ4 block is corrupt: {e}")))?,
+        COMPRESSION_LZMA => {
+            let options = lzma_rs::decompress::Options {
+                unpacked_size: lzma_rs::decompress::UnpackedSize::UseProvided(Some(size as u64)),
+                ..Default::default()
+            };
+            let mut output = Vec::with_capacity(size);
+            lzma_rs::lzma_decompress_with_options(&mut &input[..], &mut output, &options)
+                .map_err(|e| parse_error(format!("LZMA block is corrupt: {e}")))?;
+            output
+        }
+        other => {
+            return Err(FormatError::UnsupportedFormat(format!(
+                "bundle compression type {other}"
+            )))
+        }
+    };
+    if output.len() != size {
+        return Err(parse_error(format!(
+            "block decompressed to {} bytes instead of {size}",
+            output.len()
+        )));
+    }
+    Ok(output)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], little_endian: bool) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            little_endian,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FormatError> {
+        let slice = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.pos..end))
+            .ok_or_else(|| parse_error(format!("unexpected end of data at byte {}", self.pos)))?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], FormatError> {
+        Ok(self.take(N)?.try_into().expect("N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, FormatError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, FormatError> {
+        let raw = self.array()?;
+        Ok(if self.little_endian {
+            u16::from_le_bytes(raw)
+        } else {
+            u16::from_be_bytes(raw)
+        })
+    }
+
+    fn i16(&mut self) -> Result<i16, FormatError> {
+        Ok(self.u16()? as i16)
+    }
+
+    fn u32(&mut self) -> Result<u32, FormatError> {
+        let raw = self.array()?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(raw)
+        } else {
+            u32::from_be_bytes(raw)
+        })
+    }
+
+    fn i32(&mut self) -> Result<i32, FormatError> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn i64(&mut self) -> Result<i64, FormatError> {
+        let raw = self.array()?;
+        Ok(if self.little_endian {
+            i64::from_le_bytes(raw)
+        } else {
+            i64::from_be_bytes(raw)
+        })
+    }
+
+    /// A non-negative count that fits in the remaining data
+    fn count(&mut self) -> Result<usize, FormatError> {
+        let count = self.i32()?;
+        usize::try_from(count)
+            .ok()
+            .filter(|count| *count <= self.bytes.len() - self.pos)
+            .ok_or_else(|| parse_error(format!("count {count} at byte {} is invalid", self.pos)))
+    }
+
+    fn cstring(&mut self) -> Result<String, FormatError> {
+        let rest = &self.bytes[self.pos.min(self.bytes.len())..];
+        let end = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| parse_error(format!("string at byte {} is not terminated", self.pos)))?;
+        self.pos += end + 1;
+        Ok(String::from_utf8_lossy(&rest[..end]).into_owned())
+    }
+
+    fn align(&mut self, to: usize) -> Result<(), FormatError> {
+        let aligned = self.pos.div_ceil(to) * to;
+        self.take(aligned - self.pos)?;
+        Ok(())
+    }
+}
+
+fn pad(bytes: &mut Vec<u8>, to: usize) {
+    bytes.resize(bytes.len().div_ceil(to) * to, 0);
+}
+
+fn parse_error(message: String) -> FormatError {
+    FormatError::ParseError(message)
+}
+
+fn to_u32(value: usize, what: &str) -> Result<u32, FormatError> {
+    u32::try_from(value)
+        .map_err(|_| FormatError::SerializationError(format!("{what} is too large for Unity")))
+}
+
+fn to_i32(value: usize, what: &str) -> Result<i32, FormatError> {
+    i32::try_from(value)
+        .map_err(|_| FormatError::SerializationError(format!("{what} is too large for Unity")))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::formats::TranslatedEntry;
+
+    /// A bundle holding one serialized file with a single `TextAsset`, for
+    /// tests outside this module
+    pub(crate) fn text_asset_bundle(name: &str, script: &str) -> Vec<u8> {
+        let serialized = serialized_file(
+            22,
+            true,
+            &[(CLASS_TEXT_ASSET, Some(text_asset_tree()))],
+            &[(1, 0, text_asset(true, name, script))],
+        );
+        AssetBundle {
+            version: 8,
+            unity_version: "5.x.x".into(),
+            unity_revision: "2022.3.10f1".into(),
+            files: vec![BundleFile {
+                path: "CAB-test".into(),
+                flags: NODE_SERIALIZED_FILE,
+                content: BundleContent::Serialized(SerializedFile::parse(&serialized).unwrap()),
+            }],
+        }
+        .to_bytes()
+        .unwrap()
+    }
+
+    /// Writes numbers in the file's byte order and aligns like Unity
+    struct Writer {
+        bytes: Vec<u8>,
+        little_endian: bool,
+    }
+
+    impl Writer {
+        fn new(little_endian: bool) -> Self {
+            Self {
+                bytes: Vec::new(),
+                little_endian,
+            }
+        }
+
+        fn u8(&mut self, value: u8) -> &mut Self {
+            self.bytes.push(value);
+            self
+        }
+
+        fn i16(&mut self, value: i16) -> &mut Self {
+            let raw = if self.little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            };
+            self.bytes.extend_from_slice(&raw);
+            self
+        }
+
+        fn i32(&mut self, value: i32) -> &mut Self {
+            let raw = if self.little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            };
+            self.bytes.extend_from_slice(&raw);
+            self
+        }
+
+        fn i64(&mut self, value: i64) -> &mut Self {
+            let raw = if self.little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            };
+            self.bytes.extend_from_slice(&raw);
+            self
+        }
+
+        fn raw(&mut self, raw: &[u8]) -> &mut Self {
+            self.bytes.extend_from_slice(raw);
+            self
+        }
+
+        fn cstring(&mut self, text: &str) -> &mut Self {
+            self.raw(text.as_bytes()).u8(0)
+        }
+
+        /// An aligned Unity string
+        fn string(&mut self, text: &str) -> &mut Self {
+            self.i32(text.len() as i32).raw(text.as_bytes()).align(4)
+        }
+
+        fn align(&mut self, to: usize) -> &mut Self {
+            pad(&mut self.bytes, to);
+            self
+        }
+    }
+
+    // (level, type, name, byte size, flags, meta flag)
+    type Node = (u8, &'static str, &'static str, i32, u8, i32);
+
+    fn string_nodes(level: u8, name: &'static str) -> [Node; 4] {
+        [
+            (level, "string", name, -1, 0, 0x8000),
+            (level + 1, "Array", "Array", -1, 1, META_FLAG_ALIGN),
+            (level + 2, "int", "size", 4, 0, 0),
+            (level + 2, "char", "data", 1, 0, 0),
+        ]
+    }
+
+    fn text_asset_tree() -> Vec<Node> {
+        let mut nodes = vec![(0, "TextAsset", "Base", -1, 0, 0)];
+        nodes.extend(string_nodes(1, "m_Name"));
+        nodes.extend(string_nodes(1, "m_Script"));
+        nodes
+    }
+
+    fn mono_behaviour_tree() -> Vec<Node> {
+        let mut nodes = vec![
+            (0, "MonoBehaviour", "Base", -1, 0, 0),
+            (1, "PPtr<GameObject>", "m_GameObject", 12, 0, 0),
+            (2, "int", "m_FileID", 4, 0, 0),
+            (2, "SInt64", "m_PathID", 8, 0, 0),
+            (1, "UInt8", "m_Enabled", 1, 0, META_FLAG_ALIGN),
+            (1, "PPtr<MonoScript>", "m_Script", 12, 0, 0),
+            (2, "int", "m_FileID", 4, 0, 0),
+            (2, "SInt64", "m_PathID", 8, 0, 0),
+        ];
+        nodes.extend(string_nodes(1, "m_Name"));
+        nodes.extend(string_nodes(1, "title"));
+        nodes.extend([
+            (1, "vector", "lines", -1, 0, 0),
+            (2, "Array", "Array", -1, 1, META_FLAG_ALIGN),
+            (3, "int", "size", 4, 0, 0),
+        ]);
+        nodes.extend(string_nodes(3, "data"));
+        nodes.extend(string_nodes(1, "id"));
+        nodes.push((1, "int", "count", 4, 0, 0));
+        nodes
+    }
+
+    /// Type tree blob, taking names from the common strings when they are there
+    fn write_tree(writer: &mut Writer, version: u32, nodes: &[Node]) {
+        let mut strings = Vec::new();
+        let mut offset = |name: &str| {
+            let common = COMMON_STRINGS
+                .split('\0')
+                .scan(0usize, |at, common| {
+                    let start = *at;
+                    *at += common.len() + 1;
+                    Some((start, common))
+                })
+                .find(|(_, common)| *common == name);
+            match common {
+                Some((start, _)) => 0x8000_0000 | start as u32,
+                None => {
+                    let start = strings.len() as u32;
+                    strings.extend_from_slice(name.as_bytes());
+                    strings.push(0);
+                    start
+                }
+            }
+        };
+        let mut records = Writer::new(writer.little_endian);
+        for (index, (level, type_name, name, size, flags, meta)) in nodes.iter().enumerate() {
+            let type_offset = offset(type_name);
+            let name_offset = offset(name);
+            records
+                .i16(1)
+                .u8(*level)
+                .u8(*flags)
+                .i32(type_offset as i32)
+                .i32(name_offset as i32)
+                .i32(*size)
+                .i32(index as i32)
+                .i32(*meta);
+            if version >= 19 {
+                records.i64(0);
+            }
+        }
+        writer
+            .i32(nodes.len() as i32)
+            .i32(strings.len() as i32)
+            .raw(&records.bytes)
+            .raw(&strings);
+        if version >= 21 {
+            writer.i32(0);
+        }
+    }
+
+    /// A serialized file holding `(path id, type index, data)` objects
+    fn serialized_file(
+        version: u32,
+        little_endian: bool,
+        types: &[(i32, Option<Vec<Node>>)],
+        objects: &[(i64, i32, Vec<u8>)],
+    ) -> Vec<u8> {
+        let header_len = if version >= 22 { 48 } else { 20 };
+        let mut meta = Writer::new(little_endian);
+        meta.cstring("2022.3.10f1").i32(19);
+        meta.u8(types.iter().all(|(_, tree)| tree.is_some()) as u8);
+        meta.i32(types.len() as i32);
+        for (class_id, tree) in types {
+            meta.i32(*class_id).u8(0);
+            if *class_id == CLASS_MONO_BEHAVIOUR {
+                meta.i16(0).raw(&[0xAB; 16]);
+            } else {
+                meta.i16(-1);
+            }
+            meta.raw(&[0xCD; 16]);
+            if let Some(tree) = tree {
+                write_tree(&mut meta, version, tree);
+            }
+        }
+
+        let mut data = Vec::new();
+        let mut placed = Vec::new();
+        for (_, _, object) in objects {
+            pad(&mut data, 8);
+            placed.push(data.len());
+            data.extend_from_slice(object);
+        }
+        meta.i32(objects.len() as i32);
+        for ((path_id, type_index, object), start) in objects.iter().zip(&placed) {
+            // Objects are aligned relative to the start of the file
+            let aligned = (header_len + meta.bytes.len()).div_ceil(4) * 4;
+            meta.bytes.resize(aligned - header_len, 0);
+            meta.i64(*path_id);
+            if version >= 22 {
+                meta.i64(*start as i64);
+            } else {
+                meta.i32(*start as i32);
+            }
+            meta.i32(object.len() as i32).i32(*type_index);
+        }
+        meta.i32(0).i32(0);
+        if version >= 20 {
+            meta.i32(0);
+        }
+        meta.cstring("");
+
+        let data_offset = (header_len + meta.bytes.len()).div_ceil(16) * 16;
+        let file_size = data_offset + data.len();
+        let mut header = Writer::new(false);
+        header
+            .i32(meta.bytes.len() as i32)
+            .i32(if version >= 22 { 0 } else { file_size as i32 })
+            .i32(version as i32)
+            .i32(if version >= 22 { 0 } else { data_offset as i32 })
+            .u8(!little_endian as u8)
+            .raw(&[0; 3]);
+        if version >= 22 {
+            header
+                .i32(meta.bytes.len() as i32)
+                .i64(file_size as i64)
+                .i64(data_offset as i64)
+                .i64(0);
+        }
+        let mut bytes = header.bytes;
+        bytes.extend_from_slice(&meta.bytes);
+        bytes.resize(data_offset, 0);
+        bytes.extend_from_slice(&data);
+        bytes
+    }
+
+    fn text_asset(little_endian: bool, name: &str, script: &str) -> Vec<u8> {
+        let mut writer = Writer::new(little_endian);
+        writer.string(name).string(script);
+        writer.bytes
+    }
+
+    fn quest_table() -> Vec<u8> {
+        let mut writer = Writer::new(true);
+        writer.i32(0).i64(0).u8(1).align(4).i32(0).i64(7);
+        writer
+            .string("QuestTable")
+            .string("Welcome back, traveler")
+            .i32(2)
+            .string("Open the gate")
+            .string("Take the key.")
+            .string("quest_intro")
+            .i32(42);
+        writer.bytes
+    }
+
+    fn translated(entries: &[(&str, &str)]) -> TranslationResult {
+        TranslationResult {
+            translated: entries
+                .iter()
+                .map(|(key, target)| TranslatedEntry {
+                    key: key.to_string(),
+                    source: String::new(),
+                    target: target.to_string(),
+                })
+                .collect(),
+            failed: vec![],
+        }
+    }
+
+    fn sources(entries: &[TranslatableEntry]) -> Vec<(&str, &str)> {
+        entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.source.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_and_rewrites_text_in_a_bundle() {
+        let serialized = serialized_file(
+            22,
+            true,
+            &[
+                (CLASS_TEXT_ASSET, Some(text_asset_tree())),
+                (CLASS_MONO_BEHAVIOUR, Some(mono_behaviour_tree())),
+            ],
+            &[
+                (1, 0, text_asset(true, "Dialogue", "Hello there.\nGoodbye.")),
+                (-5, 1, quest_table()),
+            ],
+        );
+        let parsed = SerializedFile::parse(&serialized).unwrap();
+        assert_eq!(parsed.to_bytes().unwrap(), serialized);
+
+        let bundle = AssetBundle {
+            version: 8,
+            unity_version: "5.x.x".into(),
+            unity_revision: "2022.3.10f1".into(),
+            files: vec![
+                BundleFile {
+                    path: "CAB-test".into(),
+                    flags: NODE_SERIALIZED_FILE,
+                    content: BundleContent::Serialized(parsed),
+                },
+                BundleFile {
+                    path: "CAB-test.resS".into(),
+                    flags: 0,
+                    content: BundleContent::Raw(vec![7; 300_000]),
+                },
+            ],
+        };
+        let bytes = bundle.to_bytes().unwrap();
+        assert!(is_asset_bundle(&bytes));
+        assert!(bytes.len() < 100_000, "blocks are LZ4 compressed");
+
+        let mut bundle = AssetBundle::parse(&bytes).unwrap();
+        let entries = bundle.extract();
+        assert_eq!(
+            sources(&entries),
+            [
+                ("CAB-test:1:m_Script", "Hello there.\nGoodbye."),
+                ("CAB-test:-5:title", "Welcome back, traveler"),
+                ("CAB-test:-5:lines[0]", "Open the gate"),
+                ("CAB-test:-5:lines[1]", "Take the key."),
+            ]
+        );
+        assert_eq!(
+            entries[1].context.as_deref(),
+            Some("MonoBehaviour QuestTable title")
+        );
+
+        bundle
+            .apply(&translated(&[
+                ("CAB-test:1:m_Script", "안녕하세요.\n잘 가요."),
+                ("CAB-test:-5:lines[0]", "성문을 여세요"),
+                ("CAB-test:-5:title", "돌아온 것을 환영합니다, 여행자여"),
+                ("other:-5:lines[1]", "다른 파일"),
+            ]))
+            .unwrap();
+        let bundle = AssetBundle::parse(&bundle.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            sources(&bundle.extract()),
+            [
+                ("CAB-test:1:m_Script", "안녕하세요.\n잘 가요."),
+                ("CAB-test:-5:title", "돌아온 것을 환영합니다, 여행자여"),
+                ("CAB-test:-5:lines[0]", "성문을 여세요"),
+                ("CAB-test:-5:lines[1]", "Take the key."),
+            ]
+        );
+        let BundleContent::Serialized(serialized) = &bundle.files[0].content else {
+            panic!("serialized file was not read back");
+        };
+        let quests = serialized.object_data(1);
+        assert!(quests.ends_with(&42i32.to_le_bytes()));
+        assert_eq!(serialized.objects[1].start % 8, 0);
+        let BundleContent::Raw(resources) = &bundle.files[1].content else {
+            panic!("resources were not kept");
+        };
+        assert_eq!(resources.len(), 300_000);
+    }
+
+    #[test]
+    fn reads_lzma_bundles_and_files_without_type_trees() {
+        let serialized = serialized_file(
+            17,
+            false,
+            &[(CLASS_TEXT_ASSET, None), (CLASS_MONO_BEHAVIOUR, None)],
+            &[
+                (3, 0, text_asset(false, "Credits", "Made by the mod team")),
+                (4, 1, quest_table()),
+            ],
+        );
+
+        // Unity's LZMA blocks have no size field after the properties
+        let mut compressed = Vec::new();
+        lzma_rs::lzma_compress(&mut &serialized[..], &mut compressed).unwrap();
+        compressed.drain(5..13);
+
+        let mut info = Writer::new(false);
+        info.raw(&[0; 16]).i32(1);
+        info.i32(serialized.len() as i32)
+            .i32(compressed.len() as i32)
+            .i16(COMPRESSION_LZMA as i16);
+        info.i32(1)
+            .i64(0)
+            .i64(serialized.len() as i64)
+            .i32(4)
+            .cstring("CAB-old");
+
+        let mut bundle = Writer::new(false);
+        bundle
+            .cstring("UnityFS")
+            .i32(6)
+            .cstring("5.x.x")
+            .cstring("2017.4.40f1");
+        bundle
+            .i64(0)
+            .i32(info.bytes.len() as i32)
+            .i32(info.bytes.len() as i32);
+        bundle.i32((BLOCKS_AND_DIRECTORY_COMBINED | BLOCKS_INFO_AT_END) as i32);
+        bundle.raw(&compressed).raw(&info.bytes);
+
+        let mut parsed = AssetBundle::parse(&bundle.bytes).unwrap();
+        assert_eq!(
+            sources(&parsed.extract()),
+            [("CAB-old:3:m_Script", "Made by the mod team")]
+        );
+        parsed
+            .apply(&translated(&[(
+                "CAB-old:3:m_Script",
+                "모드 팀이 만들었습니다",
+            )]))
+            .unwrap();
+        let reparsed = AssetBundle::parse(&parsed.to_bytes().unwrap()).unwrap();
+        assert_eq!(reparsed.version, 6);
+        assert_eq!(
+            sources(&reparsed.extract()),
+            [("CAB-old:3:m_Script", "모드 팀이 만들었습니다")]
+        );
+
+        assert!(AssetBundle::parse(b"UnityWeb\0").is_err());
+        assert!(AssetBundle::parse(&bundle.bytes[..40]).is_err());
+        assert!(SerializedFile::parse(&serialized[..30]).is_err());
+    }
+
+    #[test]
+    fn rejects_block_sizes_the_input_cannot_hold() {
+        let block = lz4_flex::block::compress(&[7; 1000]);
+        assert_eq!(
+            decompress(&block, COMPRESSION_LZ4, 1000).unwrap(),
+            [7; 1000]
+        );
+        assert!(matches!(
+            decompress(&block, COMPRESSION_LZ4, u32::MAX as usize),
+            Err(FormatError::ParseError(_))
+        ));
+        assert!(matches!(
+            decompress(&[0; 16], COMPRESSION_LZMA, u32::MAX as usize),
+            Err(FormatError::ParseError(_))
+        ));
+        assert!(decompress(&[1, 2, 3], COMPRESSION_NONE, 4).is_err());
+    }
+}
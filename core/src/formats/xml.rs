@@ -21,6 +21,28 @@ static COMMENT_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"<!--[\s\S]*?-->").expect("valid comment regex")
 });
 
+// A `&` that does not start an entity or character reference
+static BARE_AMPERSAND_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"&([A-Za-z][A-Za-z0-9]*;|#[0-9]+;|#x[0-9A-Fa-f]+;)?")
+        .expect("valid ampersand regex")
+});
+
+// Comments addressed to translators: <!-- EN: ... -->, <!-- NOTE: ... -->
+static TRANSLATOR_NOTE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)^<!--\s*(?:EN|NOTE|TN|TRANSLATOR|CONTEXT)\s*:\s*(.*?)\s*-->$")
+        .expect("valid translator note regex")
+});
+
+/// Returns the note text when `comment` is a whole XML comment marked for
+/// translators (`<!-- EN: ... -->`, `NOTE:`, `TN:`, `TRANSLATOR:` or
+/// `CONTEXT:`). Ordinary comments return `None`.
+pub fn translator_note(comment: &str) -> Option<String> {
+    TRANSLATOR_NOTE_RE
+        .captures(comment.trim())
+        .map(|cap| cap[1].to_string())
+        .filter(|note| !note.is_empty())
+}
+
 // Common translatable attribute names
 static TRANSLATABLE_ATTRS: &[&str] = &[
     "label", "title", "description", "text", "tooltip",
@@ -50,6 +72,8 @@ pub struct XmlHandler {
     skip_tags: Vec<String>,
     /// Attribute names that should be translated
     translatable_attrs: Vec<String>,
+    /// Attach marked comments to the next text node as context
+    translator_notes: bool,
 }
 
 impl XmlHandler {
@@ -57,6 +81,7 @@ impl XmlHandler {
         Self {
             skip_tags: SKIP_TAGS.iter().map(|s| s.to_string()).collect(),
             translatable_attrs: TRANSLATABLE_ATTRS.iter().map(|s| s.to_string()).collect(),
+            translator_notes: false,
         }
     }
     
@@ -65,9 +90,17 @@ impl XmlHandler {
         Self {
             skip_tags,
             translatable_attrs,
+            translator_notes: false,
         }
     }
     
+    /// Opt in to reading `<!-- EN: ... -->` style comments as context for
+    /// the text that follows them. The comments themselves are never output.
+    pub fn with_translator_notes(mut self, enabled: bool) -> Self {
+        self.translator_notes = enabled;
+        self
+    }
+    
     /// Check if text appears to be translatable (not code/path/ID)
     fn is_translatable_text(&self, text: &str) -> bool {
         let trimmed = text.trim();
@@ -173,6 +206,9 @@ impl FormatHandler for XmlHandler {
     fn extract(&self, content: &str) -> Result<Vec<TranslatableEntry>, FormatError> {
         let mut entries = Vec::new();
         let mut seen_texts: HashMap<String, usize> = HashMap::new();
+        let comments: Vec<_> = COMMENT_RE.find_iter(content).collect();
+        let mut next_comment = 0;
+        let mut pending_note: Option<String> = None;
         
         // First, extract text content between tags
         for cap in TAG_CONTENT_RE.captures_iter(content) {
@@ -185,6 +221,16 @@ impl FormatHandler for XmlHandler {
                     continue;
                 }
                 
+                // A marked comment applies to the next non-blank text node
+                let mut note = None;
+                if self.translator_notes && !text.is_empty() {
+                    while next_comment < comments.len() && comments[next_comment].end() <= position {
+                        pending_note = translator_note(comments[next_comment].as_str());
+                        next_comment += 1;
+                    }
+                    note = pending_note.take();
+                }
+                
                 // Get tag context
                 let tag = self.get_tag_context(content, position);
                 
@@ -211,14 +257,24 @@ impl FormatHandler for XmlHandler {
                     format!("{}_{}", base_key, position)
                 };
                 
+                let tag_context = tag.map(|t| format!("<{}>", t));
+                let context = match (&note, tag_context) {
+                    (Some(note), Some(tag)) => Some(format!("{} (note: {})", tag, note)),
+                    (Some(note), None) => Some(format!("note: {}", note)),
+                    (None, tag) => tag,
+                };
+                
                 entries.push(TranslatableEntry {
                     key,
                     source: text.to_string(),
-                    context: tag.map(|t| format!("<{}>", t)),
+                    context,
                     metadata: {
                         let mut meta = HashMap::new();
                         meta.insert("type".to_string(), "element_text".to_string());
                         meta.insert("position".to_string(), position.to_string());
+                        if let Some(note) = note {
+                            meta.insert("translator_note".to_string(), note);
+                        }
                         meta
                     },
                 });
@@ -283,7 +339,8 @@ impl FormatHandler for XmlHandler {
                     let original_with_tags = full_match.as_str();
                     
                     // Reconstruct with translated text
-                    let new_content = original_with_tags.replace(text, translation);
+                    let new_content =
+                        original_with_tags.replace(text, &escape_xml_text(translation, false));
                     result = result.replace(original_with_tags, &new_content);
                 }
             }
@@ -312,7 +369,7 @@ impl FormatHandler for XmlHandler {
                 
                 if let Ok(re) = Regex::new(&attr_pattern) {
                     result = re.replace_all(&result, |caps: &regex::Captures| {
-                        format!("{}{}", &caps[1], &entry.target)
+                        format!("{}{}", &caps[1], escape_xml_text(&entry.target, true))
                     }).to_string();
                 }
             }
@@ -326,6 +383,24 @@ impl FormatHandler for XmlHandler {
     }
 }
 
+/// Escapes characters a model may add that would break the document: bare
+/// `&`, `<` and, inside attribute values, `"`. Entities the translation
+/// already carries (kept from the source) are left alone.
+fn escape_xml_text(text: &str, in_attribute: bool) -> String {
+    let escaped = BARE_AMPERSAND_RE.replace_all(text, |caps: &regex::Captures| {
+        match caps.get(1) {
+            Some(entity) => format!("&{}", entity.as_str()),
+            None => "&amp;".to_string(),
+        }
+    });
+    let escaped = escaped.replace('<', "&lt;");
+    if in_attribute {
+        escaped.replace('"', "&quot;")
+    } else {
+        escaped
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,4 +504,32 @@ mod tests {
         assert!(sources.contains(&"Real Title"));
         assert!(!sources.iter().any(|s| s.contains("comment")));
     }
+    
+    #[test]
+    fn test_translator_notes_attach_to_next_text() {
+        let xml = r#"<root>
+            <!-- EN: shown on the trade screen -->
+            <label>Bulk goods</label>
+            <!-- ordinary comment -->
+            <desc>Heavy cargo</desc>
+        </root>"#;
+        
+        let entries = XmlHandler::new().with_translator_notes(true).extract(xml).unwrap();
+        let label = entries.iter().find(|e| e.source == "Bulk goods").unwrap();
+        assert_eq!(
+            label.metadata.get("translator_note").map(String::as_str),
+            Some("shown on the trade screen")
+        );
+        assert!(label.context.as_deref().unwrap().contains("shown on the trade screen"));
+        let desc = entries.iter().find(|e| e.source == "Heavy cargo").unwrap();
+        assert!(!desc.metadata.contains_key("translator_note"));
+        assert!(!entries.iter().any(|e| e.source.contains("trade screen")));
+        
+        // Off by default
+        let plain = XmlHandler::new().extract(xml).unwrap();
+        assert!(plain.iter().all(|e| !e.metadata.contains_key("translator_note")));
+        
+        assert_eq!(translator_note("<!-- NOTE: verb -->").as_deref(), Some("verb"));
+        assert_eq!(translator_note("<!-- just a comment -->"), None);
+    }
 }
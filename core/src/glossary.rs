@@ -0,0 +1,325 @@
+/// Glossary application with grammatical fix-ups.
+///
+/// Swapping a glossary term into a translated sentence is not enough on its
+/// own: Korean particles depend on whether the term ends in a consonant
+/// (`정착민을` but `드라이버를`), Japanese particles attach
+/// without a space, and European languages capitalize a term that starts a
+/// sentence. The applier replaces source terms the model left untranslated
+/// and then repairs the text around every glossary target.
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+// Korean particle pairs: (after a final consonant, after a vowel).
+// Longer forms come first so 으로 wins over 로 and 이나 over 이.
+const KOREAN_PARTICLES: &[(&str, &str)] = &[
+    ("으로", "로"),
+    ("이나", "나"),
+    ("이랑", "랑"),
+    ("은", "는"),
+    ("이", "가"),
+    ("을", "를"),
+    ("과", "와"),
+    ("아", "야"),
+];
+
+const JAPANESE_PARTICLES: &str = "から|まで|より|は|が|を|に|で|と|の|も|へ";
+
+// A particle only counts when it ends the word.
+const WORD_END: &str = r"([\s\p{P}]|$)";
+
+static SENTENCE_END_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[.!?¡¿…]\s*$").expect("valid sentence end regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Korean,
+    Japanese,
+    /// Languages with sentence-initial capitalization.
+    Cased,
+    Other,
+}
+
+impl Script {
+    fn for_language(lang: &str) -> Self {
+        let primary = lang
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "ko" => Self::Korean,
+            "ja" => Self::Japanese,
+            "en" | "de" | "fr" | "es" | "it" | "pt" | "nl" | "sv" | "da" | "no" | "nb" | "fi"
+            | "pl" | "cs" | "ro" | "hu" | "tr" | "ru" | "uk" => Self::Cased,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Term {
+    target: String,
+    /// Untranslated source term inside a translation.
+    source_re: Regex,
+    /// Target term followed by a particle (Korean/Japanese only).
+    particle_re: Option<Regex>,
+    /// Any occurrence of the target term, used for sentence-start casing.
+    target_re: Regex,
+}
+
+#[derive(Debug)]
+pub struct GlossaryApplier {
+    script: Script,
+    terms: Vec<Term>,
+}
+
+impl GlossaryApplier {
+    /// Builds an applier for `target_lang` from `source -> target` terms.
+    /// Longer source terms are matched first so phrases win over single words.
+    pub fn new(target_lang: &str, glossary: &HashMap<String, String>) -> Self {
+        let script = Script::for_language(target_lang);
+        let mut pairs: Vec<(&String, &String)> = glossary
+            .iter()
+            .filter(|(source, target)| !source.trim().is_empty() && !target.trim().is_empty())
+            .collect();
+        pairs.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(b.0)));
+
+        let terms = pairs
+            .into_iter()
+            .filter_map(|(source, target)| Term::new(script, source.trim(), target.trim()))
+            .collect();
+        Self { script, terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Replaces untranslated source terms and fixes particles or casing
+    /// around glossary targets.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for term in &self.terms {
+            result = term.replace_source(self.script, &result);
+        }
+        for term in &self.terms {
+            result = match self.script {
+                Script::Korean => term.fix_korean_particles(&result),
+                Script::Japanese => term.attach_japanese_particles(&result),
+                Script::Cased => term.capitalize_sentence_starts(&result),
+                Script::Other => result,
+            };
+        }
+        result
+    }
+}
+
+impl Term {
+    fn new(script: Script, source: &str, target: &str) -> Option<Self> {
+        let source_re = Regex::new(&format!("(?i){}", bounded(source))).ok()?;
+        let escaped_target = regex::escape(target);
+        let particle_re = match script {
+            Script::Korean => {
+                let alternatives: Vec<&str> = KOREAN_PARTICLES
+                    .iter()
+                    .flat_map(|(consonant, vowel)| [*consonant, *vowel])
+                    .collect();
+                Some(format!(
+                    "{escaped_target}({}){WORD_END}",
+                    alternatives.join("|")
+                ))
+            }
+            Script::Japanese => Some(format!("{escaped_target}\\s+({JAPANESE_PARTICLES})")),
+            Script::Cased | Script::Other => None,
+        }
+        .map(|pattern| Regex::new(&pattern))
+        .transpose()
+        .ok()?;
+        let target_re = Regex::new(&format!("(?i){}", bounded(target))).ok()?;
+
+        Some(Self {
+            target: target.to_string(),
+            source_re,
+            particle_re,
+            target_re,
+        })
+    }
+
+    fn replace_source(&self, script: Script, text: &str) -> String {
+        self.source_re
+            .replace_all(text, |caps: &Captures| {
+                let matched = &caps[0];
+                let is_shouting = matched.chars().count() > 1
+                    && matched.chars().any(char::is_alphabetic)
+                    && !matched.chars().any(char::is_lowercase);
+                if script == Script::Cased && is_shouting {
+                    self.target.to_uppercase()
+                } else {
+                    self.target.clone()
+                }
+            })
+            .into_owned()
+    }
+
+    fn fix_korean_particles(&self, text: &str) -> String {
+        let Some(re) = &self.particle_re else {
+            return text.to_string();
+        };
+        re.replace_all(text, |caps: &Captures| {
+            let particle = korean_particle(&self.target, &caps[1]).unwrap_or(&caps[1]);
+            format!("{}{}{}", self.target, particle, &caps[2])
+        })
+        .into_owned()
+    }
+
+    fn attach_japanese_particles(&self, text: &str) -> String {
+        let Some(re) = &self.particle_re else {
+            return text.to_string();
+        };
+        re.replace_all(text, |caps: &Captures| {
+            format!("{}{}", self.target, &caps[1])
+        })
+        .into_owned()
+    }
+
+    fn capitalize_sentence_starts(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in self.target_re.find_iter(text) {
+            let before = &text[..found.start()];
+            result.push_str(&text[last..found.start()]);
+            if is_sentence_start(before) {
+                result.push_str(&capitalize_first(found.as_str()));
+            } else {
+                result.push_str(found.as_str());
+            }
+            last = found.end();
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+}
+
+/// Wraps `term` in word boundaries where its ends are word characters.
+/// Latin terms use ASCII boundaries so `Colonist가` still matches `colonist`.
+fn bounded(term: &str) -> String {
+    let boundary = |c: Option<char>| match c {
+        Some(c) if c.is_ascii_alphanumeric() || c == '_' => r"(?-u:\b)",
+        Some(c) if c.is_alphanumeric() => r"\b",
+        _ => "",
+    };
+    let start = boundary(term.chars().next());
+    let end = boundary(term.chars().last());
+    format!("{start}{}{end}", regex::escape(term))
+}
+
+fn is_sentence_start(before: &str) -> bool {
+    let trimmed = before.trim_end_matches([' ', '\t']);
+    trimmed.is_empty() || trimmed.ends_with('\n') || SENTENCE_END_RE.is_match(trimmed)
+}
+
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Final consonant (batchim) of the last sound in `word`: `None` when it
+/// ends in a vowel, `Some(true)` for ㄹ, `Some(false)` for other consonants.
+/// Returns `Err(())` when the pronunciation cannot be inferred.
+fn final_consonant(word: &str) -> Result<Option<bool>, ()> {
+    let last = word.chars().rev().find(|c| c.is_alphanumeric()).ok_or(())?;
+    match last {
+        '\u{AC00}'..='\u{D7A3}' => {
+            let jongseong = (last as u32 - 0xAC00) % 28;
+            Ok(match jongseong {
+                0 => None,
+                8 => Some(true),
+                _ => Some(false),
+            })
+        }
+        // Sino-Korean digit readings: 영 일 이 삼 사 오 육 칠 팔 구
+        '0' | '3' | '6' => Ok(Some(false)),
+        '1' | '7' | '8' => Ok(Some(true)),
+        '2' | '4' | '5' | '9' => Ok(None),
+        _ => Err(()),
+    }
+}
+
+/// Picks the form of `particle` that agrees with the end of `word`.
+/// Returns `None` when the particle is unknown or the word's pronunciation
+/// cannot be inferred (e.g. Latin letters), leaving the text unchanged.
+pub fn korean_particle(word: &str, particle: &str) -> Option<&'static str> {
+    let (consonant_form, vowel_form) = KOREAN_PARTICLES
+        .iter()
+        .find(|(consonant, vowel)| *consonant == particle || *vowel == particle)?;
+    let batchim = final_consonant(word).ok()?;
+    Some(match (batchim, *consonant_form) {
+        // ㄹ takes 로, not 으로
+        (Some(true), "으로") => vowel_form,
+        (Some(_), _) => consonant_form,
+        (None, _) => vowel_form,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(source, target)| (source.to_string(), target.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn picks_korean_particles_from_the_final_syllable() {
+        assert_eq!(korean_particle("정착민", "를"), Some("을"));
+        assert_eq!(korean_particle("폰", "는"), Some("은"));
+        assert_eq!(korean_particle("드라이버", "을"), Some("를"));
+        assert_eq!(korean_particle("드라이버", "이"), Some("가"));
+        assert_eq!(korean_particle("마을", "으로"), Some("로"));
+        assert_eq!(korean_particle("집", "로"), Some("으로"));
+        assert_eq!(korean_particle("레벨 3", "와"), Some("과"));
+        assert_eq!(korean_particle("HP", "를"), None);
+    }
+
+    #[test]
+    fn korean_replaces_untranslated_terms_and_repairs_particles() {
+        let applier =
+            GlossaryApplier::new("ko", &glossary(&[("colonist", "정착민"), ("pawn", "폰")]));
+        assert_eq!(
+            applier.apply("Colonist가 pawn를 구했습니다."),
+            "정착민이 폰을 구했습니다."
+        );
+        // Particles that are part of a longer word are left alone
+        assert_eq!(applier.apply("정착민이다"), "정착민이다");
+        assert_eq!(applier.apply("정착민로 이동"), "정착민으로 이동");
+    }
+
+    #[test]
+    fn japanese_attaches_particles_without_space() {
+        let applier = GlossaryApplier::new("ja-JP", &glossary(&[("colonist", "入植者")]));
+        assert_eq!(applier.apply("colonist は眠っている"), "入植者は眠っている");
+    }
+
+    #[test]
+    fn cased_languages_capitalize_at_sentence_start_only() {
+        let applier = GlossaryApplier::new("fr", &glossary(&[("colonist", "colon")]));
+        assert_eq!(
+            applier.apply("colonist arrive. Le colonist dort! colon mange."),
+            "Colon arrive. Le colon dort! Colon mange."
+        );
+        assert_eq!(applier.apply("ATTENTION COLONIST"), "ATTENTION COLON");
+
+        let german = GlossaryApplier::new("de", &glossary(&[("colonist", "Kolonist")]));
+        assert_eq!(
+            german.apply("Der colonist schläft."),
+            "Der Kolonist schläft."
+        );
+    }
+}
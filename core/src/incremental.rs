@@ -0,0 +1,256 @@
+/// Incremental re-translation after a mod update.
+///
+/// Every finished file leaves a snapshot of its source entries and their
+/// translations, one per mod file and target language. When the mod updates,
+/// a job in incremental mode looks each segment up in the snapshot: entries
+/// whose source is unchanged keep their previous translation and only new or
+/// changed entries are sent to the provider. Entries are matched by key where
+/// the line has one (XML element, JSON key, `key=value`), by source text
+/// otherwise.
+use crate::backup::write_atomic;
+use crate::paths::app_data_dir;
+use log::warn;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_DIR_NAME: &str = "snapshots";
+
+// `<Key attr="…">`, but not a closing tag or comment
+static XML_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^<([A-Za-z_][\w.\-]*)[\s>/]").expect("valid xml key regex"));
+static JSON_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^"((?:[^"\\]|\\.)+)"\s*:"#).expect("valid json key regex"));
+// `key=value` and `key: value` (properties, lang, cfg, YAML)
+static ASSIGNMENT_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Za-z0-9_][\w.\-\[\]]*)\s*[=:]").expect("valid assignment key regex")
+});
+
+/// Key of the entry on a trimmed source line, if the line has one
+pub fn entry_key(line: &str) -> Option<String> {
+    [&*XML_KEY_RE, &*JSON_KEY_RE, &*ASSIGNMENT_KEY_RE]
+        .iter()
+        .find_map(|re| re.captures(line))
+        .map(|captures| captures[1].to_string())
+}
+
+/// One translated entry of a previous run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub source: String,
+    pub translation: String,
+}
+
+/// How a source entry compares to the snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryChange {
+    /// Same source as before; carries the previous translation
+    Unchanged(String),
+    /// The key exists with a different source
+    Changed,
+    New,
+}
+
+/// Source entries and translations of one file after a job
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSnapshot {
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl SourceSnapshot {
+    /// Builds a snapshot from a file's lines and the lines that replace
+    /// them. Lines without a replacement were not translated and are left
+    /// out, so the next run translates them again.
+    pub fn from_lines(lines: &[String], translated_lines: &[Option<String>]) -> Self {
+        let entries = lines
+            .iter()
+            .zip(translated_lines)
+            .filter_map(|(line, translated)| {
+                let source = line.trim();
+                let translation = translated.as_deref()?.trim();
+                (!source.is_empty()).then(|| SnapshotEntry {
+                    key: entry_key(source),
+                    source: source.to_string(),
+                    translation: translation.to_string(),
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents)
+            .map_err(|error| {
+                warn!(
+                    "ignoring unreadable source snapshot {}: {}",
+                    path.display(),
+                    error
+                )
+            })
+            .ok()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &serde_json::to_vec(self)?)
+    }
+
+    /// Compares a trimmed source line with the snapshot. Keys that occur
+    /// more than once (e.g. `<li>`) do not identify an entry, so those lines
+    /// are matched by source text.
+    pub fn lookup(&self, source: &str) -> EntryChange {
+        let key = entry_key(source);
+        let mut keyed = self
+            .entries
+            .iter()
+            .filter(|entry| key.is_some() && entry.key == key);
+        if let (Some(entry), None) = (keyed.next(), keyed.next()) {
+            return if entry.source == source {
+                EntryChange::Unchanged(entry.translation.clone())
+            } else {
+                EntryChange::Changed
+            };
+        }
+        self.entries
+            .iter()
+            .find(|entry| entry.source == source)
+            .map(|entry| EntryChange::Unchanged(entry.translation.clone()))
+            .unwrap_or(EntryChange::New)
+    }
+}
+
+/// Counts of a job's segments after comparing with the snapshots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub unchanged: usize,
+    pub changed: usize,
+    pub new: usize,
+}
+
+impl DiffSummary {
+    pub fn record(&mut self, change: &EntryChange) {
+        match change {
+            EntryChange::Unchanged(_) => self.unchanged += 1,
+            EntryChange::Changed => self.changed += 1,
+            EntryChange::New => self.new += 1,
+        }
+    }
+}
+
+/// Snapshot location for one mod file and target language
+pub fn snapshot_path(mod_root: &Path, relative_path: &str, target_lang: &str) -> PathBuf {
+    let mut hasher = Sha256::new();
+    for part in [
+        mod_root.to_string_lossy().as_ref(),
+        relative_path,
+        target_lang,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hex::encode(hasher.finalize());
+    app_data_dir()
+        .join(SNAPSHOT_DIR_NAME)
+        .join(format!("{}.json", &digest[..32]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> SourceSnapshot {
+        let lines: Vec<String> = [
+            "<LanguageData>",
+            "  <Demo_Sword.label>sword</Demo_Sword.label>",
+            "  <Demo_Sword.description>A sharp blade.</Demo_Sword.description>",
+            "  <li>Poke</li>",
+            "  <li>Slash</li>",
+            "</LanguageData>",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let translated = vec![
+            None,
+            Some("  <Demo_Sword.label>검</Demo_Sword.label>".into()),
+            Some("  <Demo_Sword.description>날카로운 칼날.</Demo_Sword.description>".into()),
+            Some("  <li>찌르기</li>".into()),
+            Some("  <li>베기</li>".into()),
+            None,
+        ];
+        SourceSnapshot::from_lines(&lines, &translated)
+    }
+
+    #[test]
+    fn keys_come_from_xml_json_and_assignments() {
+        assert_eq!(
+            entry_key("<Demo.label>x</Demo.label>").as_deref(),
+            Some("Demo.label")
+        );
+        assert_eq!(entry_key("<li>x</li>").as_deref(), Some("li"));
+        assert_eq!(
+            entry_key(r#""item.demo.wand": "Wand","#).as_deref(),
+            Some("item.demo.wand")
+        );
+        assert_eq!(
+            entry_key("tile.demo.name=Block").as_deref(),
+            Some("tile.demo.name")
+        );
+        assert_eq!(entry_key("title: Settings").as_deref(), Some("title"));
+        assert_eq!(entry_key("</LanguageData>"), None);
+        assert_eq!(entry_key("Just a sentence."), None);
+    }
+
+    #[test]
+    fn unchanged_entries_carry_their_translation() {
+        let snapshot = snapshot();
+        assert_eq!(snapshot.entries.len(), 4);
+
+        assert_eq!(
+            snapshot.lookup("<Demo_Sword.label>sword</Demo_Sword.label>"),
+            EntryChange::Unchanged("<Demo_Sword.label>검</Demo_Sword.label>".into())
+        );
+        assert_eq!(
+            snapshot.lookup("<Demo_Sword.description>A very sharp blade.</Demo_Sword.description>"),
+            EntryChange::Changed
+        );
+        assert_eq!(
+            snapshot.lookup("<Demo_Axe.label>axe</Demo_Axe.label>"),
+            EntryChange::New
+        );
+        // Repeated keys fall back to the source text
+        assert_eq!(
+            snapshot.lookup("<li>Slash</li>"),
+            EntryChange::Unchanged("<li>베기</li>".into())
+        );
+        assert_eq!(snapshot.lookup("<li>Stab</li>"), EntryChange::New);
+    }
+
+    #[test]
+    fn snapshots_round_trip_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshots").join("mod.json");
+        let snapshot = snapshot();
+        snapshot.save(&path).unwrap();
+        assert_eq!(SourceSnapshot::load(&path), Some(snapshot));
+        assert_eq!(SourceSnapshot::load(&dir.path().join("missing.json")), None);
+
+        let a = snapshot_path(Path::new("/mods/a"), "Keyed/Misc.xml", "ko");
+        assert_ne!(
+            a,
+            snapshot_path(Path::new("/mods/a"), "Keyed/Misc.xml", "ja")
+        );
+        assert_ne!(
+            a,
+            snapshot_path(Path::new("/mods/b"), "Keyed/Misc.xml", "ko")
+        );
+    }
+}
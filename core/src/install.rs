@@ -0,0 +1,708 @@
+/// Installing finished translations into the live game or mod directory.
+///
+/// Jobs record every output file they write. `install_translation` copies a
+/// job's outputs into a target directory, skipping files whose checksum
+/// already matches and verifying each copy after writing. Files it replaces
+/// are backed up and everything it touched is listed in an install manifest
+/// inside the target, so `uninstall_translation` can put the directory back
+/// the way it was.
+///
+/// The job output record also keeps the backup of every file a job replaced,
+/// so `rollback_translation_job` can undo the job itself.
+use crate::access_mode::ensure_writable;
+use crate::backup::write_atomic;
+use crate::file_lock::retry_while_locked;
+use crate::paths::app_data_dir;
+use crate::paths::normalize_relative_path;
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const JOB_OUTPUTS_DIR_NAME: &str = "job-outputs";
+/// Folder inside the install target holding the manifest and backups
+pub const INSTALL_DIR_NAME: &str = ".mod-translator";
+const INSTALL_MANIFEST_NAME: &str = "install.json";
+const INSTALL_BACKUP_DIR_NAME: &str = "backup";
+
+/// Serializes read-modify-write of the job output records
+static JOB_OUTPUTS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// One file a job wrote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobOutput {
+    /// Path relative to the output root, `/`-separated
+    pub relative_path: String,
+    pub absolute_path: PathBuf,
+    /// Copy of the file the job replaced
+    #[serde(default)]
+    pub backup_path: Option<PathBuf>,
+    /// The job created the file; records without a backup or this flag
+    /// predate rollback support and cannot be rolled back
+    #[serde(default)]
+    pub created: bool,
+    /// Checksum right after the job wrote the file, to spot later edits
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Entries the job wrote when the output is an archive
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archive_entries: Vec<String>,
+}
+
+/// On-disk file: every output of one job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobOutputsFile {
+    #[serde(default)]
+    outputs: Vec<JobOutput>,
+}
+
+impl JobOutputsFile {
+    fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|error| {
+            warn!(
+                "ignoring unreadable job outputs {}: {}",
+                path.display(),
+                error
+            );
+            Self::default()
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create job outputs dir: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize job outputs: {}", e))?;
+        write_atomic(path, contents.as_bytes())
+            .map_err(|e| format!("Failed to write job outputs: {}", e))
+    }
+
+    fn record(&mut self, mut output: JobOutput) {
+        let earlier = self
+            .outputs
+            .iter()
+            .position(|existing| existing.relative_path == output.relative_path);
+        if let Some(index) = earlier {
+            // Rolling back restores what was there before the job's first write
+            let earlier = self.outputs.remove(index);
+            output.backup_path = earlier.backup_path;
+            output.created = earlier.created;
+            for entry in earlier.archive_entries {
+                if !output.archive_entries.contains(&entry) {
+                    output.archive_entries.push(entry);
+                }
+            }
+        }
+        self.outputs.push(output);
+    }
+}
+
+/// Location of the output record for `job_id`.
+pub fn job_outputs_path(job_id: &str) -> PathBuf {
+    let file_stem: String = job_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    app_data_dir()
+        .join(JOB_OUTPUTS_DIR_NAME)
+        .join(format!("{file_stem}.json"))
+}
+
+/// Remembers that `job_id` wrote `absolute_path`, so it can be installed or
+/// rolled back later. `backup_path` is the copy of the file it replaced, or
+/// `None` when the job created the file.
+pub fn record_job_output(
+    job_id: &str,
+    relative_path: &Path,
+    absolute_path: &Path,
+    backup_path: Option<&Path>,
+    archive_entries: Vec<String>,
+) {
+    let Ok(relative_path) = normalize_relative_path(&relative_path.to_string_lossy()) else {
+        return;
+    };
+    let Ok(_guard) = JOB_OUTPUTS_LOCK.lock() else {
+        return;
+    };
+    let path = job_outputs_path(job_id);
+    let mut file = JobOutputsFile::load(&path);
+    file.record(JobOutput {
+        relative_path,
+        absolute_path: absolute_path.to_path_buf(),
+        backup_path: backup_path.map(Path::to_path_buf),
+        created: backup_path.is_none(),
+        sha256: file_checksum(absolute_path),
+        archive_entries,
+    });
+    if let Err(error) = file.save(&path) {
+        warn!("failed to record output for job {job_id}: {error}");
+    }
+}
+
+/// Outputs `job_id` has written so far
+pub fn job_outputs(job_id: &str) -> Vec<JobOutput> {
+    JobOutputsFile::load(&job_outputs_path(job_id)).outputs
+}
+
+/// A file placed by an install
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstalledFile {
+    sha256: String,
+    /// Backup of the file this one replaced, relative to the backup folder;
+    /// `None` when the install created the file
+    #[serde(default)]
+    backup: Option<String>,
+}
+
+/// Uninstall manifest kept in the target directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallManifest {
+    job_id: String,
+    installed_at: DateTime<Utc>,
+    files: BTreeMap<String, InstalledFile>,
+}
+
+fn manifest_path(target_dir: &Path) -> PathBuf {
+    target_dir
+        .join(INSTALL_DIR_NAME)
+        .join(INSTALL_MANIFEST_NAME)
+}
+
+fn backup_dir(target_dir: &Path) -> PathBuf {
+    target_dir
+        .join(INSTALL_DIR_NAME)
+        .join(INSTALL_BACKUP_DIR_NAME)
+}
+
+impl InstallManifest {
+    fn load(target_dir: &Path) -> Option<Self> {
+        let path = manifest_path(target_dir);
+        let contents = fs::read_to_string(&path).ok()?;
+        let mut manifest: Self = serde_json::from_str(&contents)
+            .map_err(|error| {
+                warn!(
+                    "ignoring unreadable install manifest {}: {}",
+                    path.display(),
+                    error
+                )
+            })
+            .ok()?;
+        // An edited manifest must not point outside the target directory
+        let inside =
+            |relative: &String| normalize_relative_path(relative).ok().as_ref() == Some(relative);
+        manifest
+            .files
+            .retain(|relative, installed| inside(relative) && installed.backup.iter().all(inside));
+        Some(manifest)
+    }
+
+    fn save(&self, target_dir: &Path) -> io::Result<()> {
+        let path = manifest_path(target_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// A file that could not be installed or restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallFileError {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallReport {
+    pub target_dir: String,
+    /// Copied, new or changed since the last install
+    pub installed: Vec<String>,
+    /// Already identical in the target
+    pub unchanged: Vec<String>,
+    pub errors: Vec<InstallFileError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackReport {
+    pub job_id: String,
+    /// Files put back from the job's backups
+    pub restored: Vec<String>,
+    /// Files the job had created
+    pub removed: Vec<String>,
+    /// Changed after the job, left in place
+    pub kept: Vec<String>,
+    pub errors: Vec<InstallFileError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallReport {
+    pub target_dir: String,
+    /// Original files put back from backup
+    pub restored: Vec<String>,
+    /// Files the install had created
+    pub removed: Vec<String>,
+    /// Changed after the install, left in place
+    pub kept: Vec<String>,
+    pub errors: Vec<InstallFileError>,
+}
+
+fn sha256_hex(contents: &[u8]) -> String {
+    hex::encode(Sha256::digest(contents))
+}
+
+pub(crate) fn file_checksum(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|contents| sha256_hex(&contents))
+}
+
+/// Replaces `target` through a temporary file and checks the result.
+fn write_verified(target: &Path, contents: &[u8], checksum: &str) -> io::Result<()> {
+    write_atomic(target, contents)?;
+    if file_checksum(target).as_deref() != Some(checksum) {
+        return Err(io::Error::other("체크섬이 일치하지 않습니다"));
+    }
+    Ok(())
+}
+
+/// Copies one output, returning whether the target changed
+fn install_file(
+    output: &JobOutput,
+    target_dir: &Path,
+    manifest: &mut InstallManifest,
+) -> io::Result<bool> {
+    let relative = &output.relative_path;
+    let contents = fs::read(&output.absolute_path)?;
+    let checksum = sha256_hex(&contents);
+    let target = target_dir.join(relative);
+    if file_checksum(&target).as_deref() == Some(checksum.as_str()) {
+        return Ok(false);
+    }
+
+    // Only the first install backs up the game's own file; later installs
+    // replace what an earlier install put there
+    let backup = match manifest.files.get(relative) {
+        Some(installed) => installed.backup.clone(),
+        None if target.exists() => {
+            let backup_path = backup_dir(target_dir).join(relative);
+            if let Some(parent) = backup_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            retry_while_locked(|| fs::copy(&target, &backup_path))?;
+            Some(relative.clone())
+        }
+        None => None,
+    };
+    write_verified(&target, &contents, &checksum)?;
+    manifest.files.insert(
+        relative.clone(),
+        InstalledFile {
+            sha256: checksum,
+            backup,
+        },
+    );
+    Ok(true)
+}
+
+/// Copies `outputs` into `target_dir`, updating the install manifest there.
+pub fn install_outputs(job_id: &str, outputs: &[JobOutput], target_dir: &Path) -> InstallReport {
+    let mut manifest = InstallManifest::load(target_dir).unwrap_or_else(|| InstallManifest {
+        job_id: job_id.to_string(),
+        installed_at: Utc::now(),
+        files: BTreeMap::new(),
+    });
+    let mut report = InstallReport {
+        target_dir: target_dir.to_string_lossy().to_string(),
+        installed: Vec::new(),
+        unchanged: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for output in outputs {
+        let relative = output.relative_path.clone();
+        let result = install_file(output, target_dir, &mut manifest);
+        match result {
+            Ok(true) => report.installed.push(relative),
+            Ok(false) => report.unchanged.push(relative),
+            Err(error) => report.errors.push(InstallFileError {
+                path: relative,
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    if !manifest.files.is_empty() {
+        manifest.job_id = job_id.to_string();
+        manifest.installed_at = Utc::now();
+        if let Err(error) = manifest.save(target_dir) {
+            report.errors.push(InstallFileError {
+                path: INSTALL_MANIFEST_NAME.to_string(),
+                message: error.to_string(),
+            });
+        }
+    }
+    report
+}
+
+/// Removes `dir` and its parents while they are empty, stopping at `root`.
+fn remove_empty_dirs(dir: Option<&Path>, root: &Path) {
+    let mut current = dir;
+    while let Some(dir) = current {
+        if dir == root || !dir.starts_with(root) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+/// Reverts the install recorded in `target_dir`. Files changed since the
+/// install are left alone and stay in the manifest unless `force` is set, in
+/// which case they are reverted too. Folders the install created are removed
+/// once empty.
+pub fn uninstall_outputs(target_dir: &Path, force: bool) -> Result<UninstallReport, String> {
+    let mut manifest = InstallManifest::load(target_dir)
+        .ok_or_else(|| "설치 기록이 없습니다. 이 폴더에 설치된 번역이 없습니다.".to_string())?;
+    let mut report = UninstallReport {
+        target_dir: target_dir.to_string_lossy().to_string(),
+        restored: Vec::new(),
+        removed: Vec::new(),
+        kept: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let files = std::mem::take(&mut manifest.files);
+    for (relative, installed) in files {
+        let target = target_dir.join(&relative);
+        let current = file_checksum(&target);
+        let changed = current.is_some() && current.as_deref() != Some(installed.sha256.as_str());
+        if changed && !force {
+            report.kept.push(relative.clone());
+            manifest.files.insert(relative, installed);
+            continue;
+        }
+
+        let result = match &installed.backup {
+            Some(backup) => {
+                let backup_path = backup_dir(target_dir).join(backup);
+                fs::read(&backup_path)
+                    .and_then(|original| write_verified(&target, &original, &sha256_hex(&original)))
+                    .map(|()| {
+                        let _ = fs::remove_file(&backup_path);
+                        remove_empty_dirs(backup_path.parent(), target_dir);
+                    })
+            }
+            None if current.is_some() => retry_while_locked(|| fs::remove_file(&target)),
+            None => Ok(()),
+        };
+        match result {
+            Ok(()) if installed.backup.is_some() => report.restored.push(relative),
+            Ok(()) => {
+                remove_empty_dirs(target.parent(), target_dir);
+                report.removed.push(relative);
+            }
+            Err(error) => {
+                report.errors.push(InstallFileError {
+                    path: relative.clone(),
+                    message: error.to_string(),
+                });
+                manifest.files.insert(relative, installed);
+            }
+        }
+    }
+
+    let result = if manifest.files.is_empty() {
+        fs::remove_dir_all(target_dir.join(INSTALL_DIR_NAME))
+    } else {
+        manifest.save(target_dir)
+    };
+    result.map_err(|e| format!("설치 기록을 갱신하지 못했습니다: {}", e))?;
+    Ok(report)
+}
+
+/// Undoes one output, returning whether a backup was restored
+fn rollback_output(output: &JobOutput, current: Option<&str>) -> io::Result<bool> {
+    let target = &output.absolute_path;
+    match &output.backup_path {
+        Some(backup_path) => {
+            let original = fs::read(backup_path)?;
+            write_verified(target, &original, &sha256_hex(&original))?;
+            let _ = fs::remove_file(backup_path);
+            Ok(true)
+        }
+        None if output.created => {
+            if current.is_some() {
+                retry_while_locked(|| fs::remove_file(target))?;
+            }
+            // The output root is what remains after dropping the relative path
+            let depth = Path::new(&output.relative_path).components().count();
+            if let Some(root) = target.ancestors().nth(depth) {
+                remove_empty_dirs(target.parent(), root);
+            }
+            Ok(false)
+        }
+        None => Err(io::Error::other("원본 백업이 없어 되돌릴 수 없습니다")),
+    }
+}
+
+/// Reverts every file `job_id` wrote, newest first: replaced files are
+/// restored from their backups and created files are removed. Files changed
+/// since the job are left alone and stay in the record unless `force` is set.
+pub fn rollback_job_outputs(job_id: &str, force: bool) -> Result<RollbackReport, String> {
+    let _guard = JOB_OUTPUTS_LOCK
+        .lock()
+        .map_err(|_| "job outputs lock poisoned".to_string())?;
+    rollback_record(&job_outputs_path(job_id), job_id, force)
+}
+
+fn rollback_record(path: &Path, job_id: &str, force: bool) -> Result<RollbackReport, String> {
+    let mut file = JobOutputsFile::load(path);
+    if file.outputs.is_empty() {
+        return Err(format!("되돌릴 번역 결과가 없습니다: {}", job_id));
+    }
+    let mut report = RollbackReport {
+        job_id: job_id.to_string(),
+        restored: Vec::new(),
+        removed: Vec::new(),
+        kept: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let outputs = std::mem::take(&mut file.outputs);
+    for output in outputs.into_iter().rev() {
+        let relative = output.relative_path.clone();
+        let current = file_checksum(&output.absolute_path);
+        let changed = current.is_some() && output.sha256.is_some() && current != output.sha256;
+        if changed && !force {
+            report.kept.push(relative);
+            file.outputs.push(output);
+            continue;
+        }
+        match rollback_output(&output, current.as_deref()) {
+            Ok(true) => report.restored.push(relative),
+            Ok(false) => report.removed.push(relative),
+            Err(error) => {
+                report.errors.push(InstallFileError {
+                    path: relative,
+                    message: error.to_string(),
+                });
+                file.outputs.push(output);
+            }
+        }
+    }
+
+    let result = if file.outputs.is_empty() {
+        fs::remove_file(path).map_err(|e| e.to_string())
+    } else {
+        file.outputs.reverse();
+        file.save(path)
+    };
+    result.map_err(|e| format!("작업 기록을 갱신하지 못했습니다: {}", e))?;
+    Ok(report)
+}
+
+/// Tauri command to copy a job's outputs into the game or mod directory
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn install_translation(
+    jobId: String,
+    targetDir: String,
+) -> Result<InstallReport, String> {
+    ensure_writable("번역 설치")?;
+    let outputs = job_outputs(&jobId);
+    if outputs.is_empty() {
+        return Err(format!("설치할 번역 결과가 없습니다: {}", jobId));
+    }
+    let target_dir = PathBuf::from(targetDir);
+    if !target_dir.is_dir() {
+        return Err(format!(
+            "설치 대상 폴더를 찾을 수 없습니다: {}",
+            target_dir.to_string_lossy()
+        ));
+    }
+    Ok(install_outputs(&jobId, &outputs, &target_dir))
+}
+
+/// Tauri command to revert the translation installed into a directory.
+/// `force` also reverts files edited after the install.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn uninstall_translation(
+    targetDir: String,
+    force: Option<bool>,
+) -> Result<UninstallReport, String> {
+    ensure_writable("번역 설치 되돌리기")?;
+    uninstall_outputs(Path::new(&targetDir), force.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(root: &Path, relative: &str, contents: &str) -> JobOutput {
+        let absolute_path = root.join(relative);
+        fs::create_dir_all(absolute_path.parent().unwrap()).unwrap();
+        fs::write(&absolute_path, contents).unwrap();
+        JobOutput {
+            relative_path: relative.to_string(),
+            absolute_path,
+            backup_path: None,
+            created: true,
+            sha256: None,
+            archive_entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn job_outputs_are_deduplicated_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("job.json");
+        let mut file = JobOutputsFile::load(&path);
+        file.record(output(dir.path(), "a.txt", "1"));
+        file.record(output(dir.path(), "b.txt", "2"));
+        file.record(output(dir.path(), "a.txt", "3"));
+        file.save(&path).unwrap();
+
+        let paths: Vec<String> = JobOutputsFile::load(&path)
+            .outputs
+            .into_iter()
+            .map(|output| output.relative_path)
+            .collect();
+        assert_eq!(paths, vec!["b.txt", "a.txt"]);
+    }
+
+    #[test]
+    fn rollback_restores_replaced_files_and_removes_created_ones() {
+        let mod_dir = tempfile::tempdir().unwrap();
+        let record = mod_dir.path().join("record.json");
+        let replaced = "Languages/Korean/Keyed/UI.xml";
+        let created = "Languages/Korean/Strings/Names.txt";
+        let edited = "About/About.ko.xml";
+
+        let mut file = JobOutputsFile::default();
+        let mut write = |relative: &str, original: Option<&str>, contents: &str| {
+            let absolute_path = mod_dir.path().join(relative);
+            let backup_path = original.map(|original| {
+                let backup = absolute_path.with_extension("xml.bak");
+                fs::create_dir_all(backup.parent().unwrap()).unwrap();
+                fs::write(&backup, original).unwrap();
+                backup
+            });
+            let mut written = output(mod_dir.path(), relative, contents);
+            written.created = backup_path.is_none();
+            written.backup_path = backup_path;
+            written.sha256 = file_checksum(&absolute_path);
+            file.record(written);
+        };
+        write(replaced, Some("original"), "번역 1");
+        write(replaced, None, "번역 2");
+        write(created, None, "이름");
+        write(edited, Some("<ModMetaData/>"), "번역");
+        file.save(&record).unwrap();
+        fs::write(mod_dir.path().join(edited), "직접 수정").unwrap();
+
+        let report = rollback_record(&record, "job-1", false).unwrap();
+        assert_eq!(report.restored, vec![replaced]);
+        assert_eq!(report.removed, vec![created]);
+        assert_eq!(report.kept, vec![edited]);
+        assert!(report.errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(mod_dir.path().join(replaced)).unwrap(),
+            "original"
+        );
+        assert!(!mod_dir.path().join("Languages/Korean/Strings").exists());
+
+        let forced = rollback_record(&record, "job-1", true).unwrap();
+        assert_eq!(forced.restored, vec![edited]);
+        assert_eq!(
+            fs::read_to_string(mod_dir.path().join(edited)).unwrap(),
+            "<ModMetaData/>"
+        );
+        assert!(!record.exists());
+        assert!(rollback_record(&record, "job-1", false).is_err());
+    }
+
+    #[test]
+    fn install_skips_unchanged_files_and_uninstall_restores() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let keyed = "Languages/Korean/Keyed/UI.xml";
+        let strings = "Languages/Korean/Strings/Names.txt";
+        fs::create_dir_all(target.path().join("Languages/Korean/Keyed")).unwrap();
+        fs::write(target.path().join(keyed), "old").unwrap();
+
+        let outputs = vec![
+            output(source.path(), keyed, "안녕"),
+            output(source.path(), strings, "이름"),
+        ];
+        let first = install_outputs("job-1", &outputs, target.path());
+        assert_eq!(first.installed, vec![keyed, strings]);
+        assert!(first.errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(target.path().join(keyed)).unwrap(),
+            "안녕"
+        );
+
+        let outputs = vec![
+            output(source.path(), keyed, "안녕하세요"),
+            output(source.path(), strings, "이름"),
+        ];
+        let second = install_outputs("job-2", &outputs, target.path());
+        assert_eq!(second.installed, vec![keyed]);
+        assert_eq!(second.unchanged, vec![strings]);
+
+        let uninstall = uninstall_outputs(target.path(), false).unwrap();
+        assert_eq!(uninstall.restored, vec![keyed]);
+        assert_eq!(uninstall.removed, vec![strings]);
+        assert_eq!(
+            fs::read_to_string(target.path().join(keyed)).unwrap(),
+            "old"
+        );
+        assert!(!target.path().join(strings).exists());
+        assert!(!target.path().join("Languages/Korean/Strings").exists());
+        assert!(target.path().join("Languages/Korean/Keyed").exists());
+        assert!(!target.path().join(INSTALL_DIR_NAME).exists());
+        assert!(uninstall_outputs(target.path(), false).is_err());
+    }
+
+    #[test]
+    fn uninstall_keeps_files_changed_after_install_unless_forced() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let outputs = vec![output(source.path(), "locale/ko/mod.cfg", "번역")];
+        install_outputs("job-1", &outputs, target.path());
+        fs::write(target.path().join("locale/ko/mod.cfg"), "직접 수정").unwrap();
+
+        let uninstall = uninstall_outputs(target.path(), false).unwrap();
+        assert_eq!(uninstall.kept, vec!["locale/ko/mod.cfg"]);
+        assert!(manifest_path(target.path()).exists());
+
+        let forced = uninstall_outputs(target.path(), true).unwrap();
+        assert_eq!(forced.removed, vec!["locale/ko/mod.cfg"]);
+        assert!(forced.kept.is_empty());
+        assert!(!target.path().join("locale").exists());
+        assert!(!target.path().join(INSTALL_DIR_NAME).exists());
+    }
+}
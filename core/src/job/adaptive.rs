@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+/// Limits for [`AdaptiveBatchController`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveBatchConfig {
+    pub min_size: usize,
+    pub max_size: usize,
+    pub initial_size: usize,
+    /// Requests answered faster than this count towards growing the batch.
+    pub fast_latency: Duration,
+    /// Consecutive fast successes needed before the batch grows by one.
+    pub grow_after: u32,
+}
+
+impl Default for AdaptiveBatchConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 1,
+            max_size: 8,
+            initial_size: 2,
+            fast_latency: Duration::from_secs(3),
+            grow_after: 4,
+        }
+    }
+}
+
+/// Outcome of one provider request, as seen by the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchSignal {
+    Success { latency: Duration },
+    RateLimited,
+    /// The response came back cut short (missing placeholders or structure).
+    Truncated,
+    Failed,
+}
+
+/// Decides how many segments are sent to the provider at once.
+///
+/// Additive increase, multiplicative decrease: a run of fast successes adds
+/// one request to the batch, while a rate limit or truncated response halves
+/// it. Slow responses and other failures only reset the run.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchController {
+    config: AdaptiveBatchConfig,
+    size: usize,
+    fast_streak: u32,
+}
+
+impl Default for AdaptiveBatchController {
+    fn default() -> Self {
+        Self::new(AdaptiveBatchConfig::default())
+    }
+}
+
+impl AdaptiveBatchController {
+    pub fn new(config: AdaptiveBatchConfig) -> Self {
+        let min_size = config.min_size.max(1);
+        let config = AdaptiveBatchConfig {
+            min_size,
+            max_size: config.max_size.max(min_size),
+            ..config
+        };
+        Self {
+            size: config.initial_size.clamp(config.min_size, config.max_size),
+            config,
+            fast_streak: 0,
+        }
+    }
+
+    /// Current number of segments to send concurrently.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Feeds the outcome of a request back and returns the new batch size.
+    pub fn record(&mut self, signal: BatchSignal) -> usize {
+        match signal {
+            BatchSignal::Success { latency } if latency <= self.config.fast_latency => {
+                self.fast_streak += 1;
+                if self.fast_streak >= self.config.grow_after {
+                    self.fast_streak = 0;
+                    self.size = (self.size + 1).min(self.config.max_size);
+                }
+            }
+            BatchSignal::Success { .. } | BatchSignal::Failed => {
+                self.fast_streak = 0;
+            }
+            BatchSignal::RateLimited | BatchSignal::Truncated => {
+                self.fast_streak = 0;
+                self.size = (self.size / 2).max(self.config.min_size);
+            }
+        }
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: BatchSignal = BatchSignal::Success {
+        latency: Duration::from_millis(200),
+    };
+
+    #[test]
+    fn grows_after_a_run_of_fast_successes_up_to_the_limit() {
+        let mut controller = AdaptiveBatchController::new(AdaptiveBatchConfig {
+            max_size: 3,
+            grow_after: 2,
+            ..AdaptiveBatchConfig::default()
+        });
+        assert_eq!(controller.size(), 2);
+        assert_eq!(controller.record(FAST), 2);
+        assert_eq!(controller.record(FAST), 3);
+        for _ in 0..4 {
+            controller.record(FAST);
+        }
+        assert_eq!(controller.size(), 3);
+    }
+
+    #[test]
+    fn slow_responses_reset_the_run() {
+        let mut controller = AdaptiveBatchController::new(AdaptiveBatchConfig {
+            grow_after: 2,
+            ..AdaptiveBatchConfig::default()
+        });
+        controller.record(FAST);
+        controller.record(BatchSignal::Success {
+            latency: Duration::from_secs(10),
+        });
+        assert_eq!(controller.record(FAST), 2);
+        assert_eq!(controller.record(FAST), 3);
+    }
+
+    #[test]
+    fn rate_limits_and_truncation_halve_the_batch() {
+        let mut controller = AdaptiveBatchController::new(AdaptiveBatchConfig {
+            initial_size: 8,
+            ..AdaptiveBatchConfig::default()
+        });
+        assert_eq!(controller.record(BatchSignal::RateLimited), 4);
+        assert_eq!(controller.record(BatchSignal::Truncated), 2);
+        assert_eq!(controller.record(BatchSignal::RateLimited), 1);
+        assert_eq!(controller.record(BatchSignal::RateLimited), 1);
+        assert_eq!(controller.record(BatchSignal::Failed), 1);
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+/// Channel the typed job events are emitted on. The free-form
+/// `translation-progress` event keeps being sent next to it for older
+/// listeners.
+pub const JOB_EVENT_CHANNEL: &str = "translation-job-event";
+
+/// Lifecycle event of a translation job.
+///
+/// Serialized with a `type` tag, e.g.
+/// `{"type":"file_written","jobId":"…","sourceRelativePath":"…",…}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum JobEvent {
+    /// Files are prepared and the first segment is about to be sent
+    Started {
+        job_id: String,
+        total_files: usize,
+        total_segments: u32,
+        /// Segments already done by an earlier run of the same job
+        resumed_segments: u32,
+    },
+    /// The first segment of a file is about to be sent
+    FileStarted {
+        job_id: String,
+        file_path: String,
+    },
+    SegmentTranslated {
+        job_id: String,
+        file_path: String,
+        line_number: usize,
+        /// `false` when the source text was kept, e.g. after a QC failure
+        applied: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
+        translated_count: u32,
+        total_count: u32,
+    },
+    /// Waiting before the next attempt of a failed request
+    Backoff {
+        job_id: String,
+        delay_ms: u64,
+        attempt: u32,
+        max_attempts: u32,
+        reason: String,
+    },
+    Retry {
+        job_id: String,
+        attempt: u32,
+    },
+    FileWritten {
+        job_id: String,
+        source_relative_path: String,
+        output_relative_path: String,
+        output_absolute_path: String,
+    },
+    Completed {
+        job_id: String,
+        /// Some files could not be translated or saved
+        partial_success: bool,
+        translated_count: u32,
+        total_count: u32,
+    },
+    Failed {
+        job_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    Canceled {
+        job_id: String,
+        translated_count: u32,
+    },
+}
+
+impl JobEvent {
+    pub fn job_id(&self) -> &str {
+        match self {
+            JobEvent::Started { job_id, .. }
+            | JobEvent::FileStarted { job_id, .. }
+            | JobEvent::SegmentTranslated { job_id, .. }
+            | JobEvent::Backoff { job_id, .. }
+            | JobEvent::Retry { job_id, .. }
+            | JobEvent::FileWritten { job_id, .. }
+            | JobEvent::Completed { job_id, .. }
+            | JobEvent::Failed { job_id, .. }
+            | JobEvent::Canceled { job_id, .. } => job_id,
+        }
+    }
+
+    /// The terminal event matching a legacy progress status, if the status
+    /// ends the job.
+    pub fn for_final_status(
+        job_id: &str,
+        status: &str,
+        message: Option<&str>,
+        translated_count: u32,
+        total_count: u32,
+    ) -> Option<JobEvent> {
+        let job_id = job_id.to_string();
+        match status {
+            "completed" | "partial_success" => Some(JobEvent::Completed {
+                job_id,
+                partial_success: status == "partial_success",
+                translated_count,
+                total_count,
+            }),
+            "failed" => Some(JobEvent::Failed {
+                job_id,
+                message: message.map(str::to_string),
+            }),
+            "canceled" => Some(JobEvent::Canceled {
+                job_id,
+                translated_count,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_with_type_tag_and_camel_case_fields() {
+        let event = JobEvent::SegmentTranslated {
+            job_id: "job-1".into(),
+            file_path: "Languages/English/Keyed/Main.xml".into(),
+            line_number: 12,
+            applied: true,
+            warnings: Vec::new(),
+            translated_count: 3,
+            total_count: 10,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "type": "segment_translated",
+                "jobId": "job-1",
+                "filePath": "Languages/English/Keyed/Main.xml",
+                "lineNumber": 12,
+                "applied": true,
+                "translatedCount": 3,
+                "totalCount": 10,
+            })
+        );
+        let parsed: JobEvent = serde_json::from_value(json!({
+            "type": "failed",
+            "jobId": "job-2",
+        }))
+        .unwrap();
+        assert_eq!(
+            parsed,
+            JobEvent::Failed {
+                job_id: "job-2".into(),
+                message: None
+            }
+        );
+        assert_eq!(parsed.job_id(), "job-2");
+    }
+
+    #[test]
+    fn maps_final_statuses_only() {
+        assert_eq!(
+            JobEvent::for_final_status("job", "partial_success", None, 4, 5),
+            Some(JobEvent::Completed {
+                job_id: "job".into(),
+                partial_success: true,
+                translated_count: 4,
+                total_count: 5,
+            })
+        );
+        assert!(matches!(
+            JobEvent::for_final_status("job", "canceled", None, 1, 5),
+            Some(JobEvent::Canceled { .. })
+        ));
+        assert!(JobEvent::for_final_status("job", "running", None, 1, 5).is_none());
+        assert!(JobEvent::for_final_status("job", "paused", None, 1, 5).is_none());
+    }
+}
@@ -1 +1,6 @@
+pub mod adaptive;
+pub mod events;
+pub mod queue;
+#[cfg(feature = "tauri")]
 pub mod runner;
+pub mod sink;
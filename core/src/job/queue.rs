@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QueueError {
+    #[error("job {0} is already queued")]
+    DuplicateJob(String),
+    #[error("job {0} is not queued")]
+    UnknownJob(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct QueuedJob<T> {
+    pub job_id: String,
+    pub priority: JobPriority,
+    pub payload: T,
+}
+
+/// Ordered queue of jobs waiting for a free slot.
+///
+/// New jobs are placed behind every waiting job of the same or higher
+/// priority, so higher priorities run first and equal priorities run in
+/// submission order. Users can still move a job to any position by hand.
+#[derive(Debug)]
+pub struct JobQueue<T> {
+    pending: Vec<QueuedJob<T>>,
+    max_concurrent: usize,
+}
+
+impl<T> Default for JobQueue<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_JOBS)
+    }
+}
+
+impl<T> JobQueue<T> {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Sets how many jobs may run at once. Values below one are clamped.
+    pub fn set_max_concurrent(&mut self, max_concurrent: usize) -> usize {
+        self.max_concurrent = max_concurrent.max(1);
+        self.max_concurrent
+    }
+
+    /// Adds a job and returns its position in the queue.
+    pub fn enqueue(
+        &mut self,
+        job_id: String,
+        priority: JobPriority,
+        payload: T,
+    ) -> Result<usize, QueueError> {
+        if self.position(&job_id).is_some() {
+            return Err(QueueError::DuplicateJob(job_id));
+        }
+        let index = self.insertion_index(priority);
+        self.pending.insert(
+            index,
+            QueuedJob {
+                job_id,
+                priority,
+                payload,
+            },
+        );
+        Ok(index)
+    }
+
+    pub fn remove(&mut self, job_id: &str) -> Option<QueuedJob<T>> {
+        let index = self.position(job_id)?;
+        Some(self.pending.remove(index))
+    }
+
+    /// Moves a job to `position` (clamped to the end of the queue).
+    pub fn move_to(&mut self, job_id: &str, position: usize) -> Result<usize, QueueError> {
+        let job = self
+            .remove(job_id)
+            .ok_or_else(|| QueueError::UnknownJob(job_id.to_string()))?;
+        let index = position.min(self.pending.len());
+        self.pending.insert(index, job);
+        Ok(index)
+    }
+
+    /// Changes a job's priority and re-sorts it among the waiting jobs.
+    pub fn set_priority(&mut self, job_id: &str, priority: JobPriority) -> Result<usize, QueueError> {
+        let mut job = self
+            .remove(job_id)
+            .ok_or_else(|| QueueError::UnknownJob(job_id.to_string()))?;
+        job.priority = priority;
+        let index = self.insertion_index(priority);
+        self.pending.insert(index, job);
+        Ok(index)
+    }
+
+    pub fn position(&self, job_id: &str) -> Option<usize> {
+        self.pending.iter().position(|job| job.job_id == job_id)
+    }
+
+    pub fn get(&self, job_id: &str) -> Option<&QueuedJob<T>> {
+        self.pending.iter().find(|job| job.job_id == job_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &QueuedJob<T>> {
+        self.pending.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Pops the next job when fewer than `max_concurrent` jobs are running.
+    pub fn pop_ready(&mut self, running: usize) -> Option<QueuedJob<T>> {
+        if running >= self.max_concurrent || self.pending.is_empty() {
+            return None;
+        }
+        Some(self.pending.remove(0))
+    }
+
+    fn insertion_index(&self, priority: JobPriority) -> usize {
+        self.pending
+            .iter()
+            .position(|job| job.priority < priority)
+            .unwrap_or(self.pending.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(queue: &JobQueue<()>) -> Vec<&str> {
+        queue.iter().map(|job| job.job_id.as_str()).collect()
+    }
+
+    #[test]
+    fn higher_priority_jumps_ahead_but_keeps_fifo_within_priority() {
+        let mut queue = JobQueue::new(1);
+        queue.enqueue("a".into(), JobPriority::Normal, ()).unwrap();
+        queue.enqueue("b".into(), JobPriority::Normal, ()).unwrap();
+        assert_eq!(queue.enqueue("c".into(), JobPriority::High, ()), Ok(0));
+        queue.enqueue("d".into(), JobPriority::Low, ()).unwrap();
+        queue.enqueue("e".into(), JobPriority::High, ()).unwrap();
+        assert_eq!(ids(&queue), ["c", "e", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn rejects_duplicates_and_unknown_jobs() {
+        let mut queue = JobQueue::new(1);
+        queue.enqueue("a".into(), JobPriority::Normal, ()).unwrap();
+        assert_eq!(
+            queue.enqueue("a".into(), JobPriority::High, ()),
+            Err(QueueError::DuplicateJob("a".into()))
+        );
+        assert_eq!(
+            queue.move_to("missing", 0),
+            Err(QueueError::UnknownJob("missing".into()))
+        );
+    }
+
+    #[test]
+    fn reorders_and_reprioritizes() {
+        let mut queue = JobQueue::new(1);
+        for id in ["a", "b", "c"] {
+            queue.enqueue(id.into(), JobPriority::Normal, ()).unwrap();
+        }
+        assert_eq!(queue.move_to("c", 0), Ok(0));
+        assert_eq!(queue.move_to("a", 99), Ok(2));
+        assert_eq!(ids(&queue), ["c", "b", "a"]);
+
+        assert_eq!(queue.set_priority("a", JobPriority::High), Ok(0));
+        assert_eq!(ids(&queue), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn pop_ready_respects_concurrency_limit() {
+        let mut queue = JobQueue::new(2);
+        queue.enqueue("a".into(), JobPriority::Normal, ()).unwrap();
+        queue.enqueue("b".into(), JobPriority::Normal, ()).unwrap();
+        assert!(queue.pop_ready(2).is_none());
+        assert_eq!(queue.pop_ready(1).map(|job| job.job_id), Some("a".into()));
+        assert_eq!(queue.set_max_concurrent(0), 1);
+        assert!(queue.pop_ready(1).is_none());
+        assert_eq!(queue.pop_ready(0).map(|job| job.job_id), Some("b".into()));
+        assert!(queue.is_empty());
+    }
+}
@@ -1,9 +1,13 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
+use log::warn;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::mpsc;
 
+/// How long exiting waits for running jobs to save their checkpoints
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Progress {
     pub total: u32,
@@ -27,6 +31,8 @@ pub struct JobRunner {
 
 impl JobRunner {
     pub fn new(app: AppHandle) -> Arc<Self> {
+        crate::jobs::emit_recoverable_jobs(&app);
+
         let (tx, mut rx) = mpsc::channel::<JobMsg>(8);
         let app_handle = app.clone();
 
@@ -129,4 +135,17 @@ impl JobRunner {
             .await
             .map_err(|e| e.to_string())
     }
+
+    /// Stops the jobs in flight and waits briefly for them to save their
+    /// checkpoints. Called when the app exits.
+    pub async fn shutdown(&self) {
+        let _ = self.tx.try_send(JobMsg::Cancel);
+        let unfinished = crate::jobs::shutdown_jobs(SHUTDOWN_TIMEOUT).await;
+        if !unfinished.is_empty() {
+            warn!(
+                "exiting while jobs are still running: {}",
+                unfinished.join(", ")
+            );
+        }
+    }
 }
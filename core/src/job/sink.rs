@@ -0,0 +1,99 @@
+use log::warn;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Mutex;
+#[cfg(feature = "tauri")]
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Where a translation job reports its progress.
+///
+/// The job runner only talks to this trait, so it runs the same way from the
+/// desktop app (events forwarded to the webview), a command-line front end,
+/// or a test that collects the events.
+pub trait ProgressSink: Send + Sync {
+    /// Delivers a payload on one of the job event channels
+    /// (`translation-progress`, `translation-job-event`, …)
+    fn send(&self, channel: &str, payload: Value);
+}
+
+impl dyn ProgressSink + '_ {
+    pub fn emit<T: Serialize>(&self, channel: &str, payload: T) {
+        match serde_json::to_value(payload) {
+            Ok(payload) => self.send(channel, payload),
+            Err(error) => warn!("failed to serialize {} payload: {}", channel, error),
+        }
+    }
+}
+
+/// Forwards job events to the webview as Tauri events
+#[cfg(feature = "tauri")]
+impl<R: Runtime> ProgressSink for AppHandle<R> {
+    fn send(&self, channel: &str, payload: Value) {
+        if let Err(error) = Emitter::emit(self, channel, payload) {
+            warn!("failed to emit {}: {}", channel, error);
+        }
+    }
+}
+
+/// Keeps every event in memory, in the order they were sent
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    events: Mutex<Vec<(String, Value)>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<(String, Value)> {
+        self.events
+            .lock()
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    /// Payloads sent on `channel`
+    pub fn payloads(&self, channel: &str) -> Vec<Value> {
+        self.events()
+            .into_iter()
+            .filter(|(sent_on, _)| sent_on == channel)
+            .map(|(_, payload)| payload)
+            .collect()
+    }
+}
+
+impl ProgressSink for MemorySink {
+    fn send(&self, channel: &str, payload: Value) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push((channel.to_string(), payload));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::events::{JobEvent, JOB_EVENT_CHANNEL};
+    use serde_json::json;
+
+    #[test]
+    fn memory_sink_collects_serialized_payloads_per_channel() {
+        let memory = MemorySink::new();
+        let sink: &dyn ProgressSink = &memory;
+        sink.emit(
+            JOB_EVENT_CHANNEL,
+            JobEvent::Retry {
+                job_id: "job-1".into(),
+                attempt: 2,
+            },
+        );
+        sink.emit("translation-progress", json!({ "jobId": "job-1" }));
+
+        assert_eq!(memory.events().len(), 2);
+        assert_eq!(
+            memory.payloads(JOB_EVENT_CHANNEL),
+            [json!({ "type": "retry", "jobId": "job-1", "attempt": 2 })]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+/// History of finished translation jobs.
+///
+/// Every job that runs to the end records a summary (files, segment counts
+/// by review status, duration, token usage, errors, provider and model) in
+/// an SQLite database under the local app data dir. `get_job_history` lists
+/// the summaries and `get_translation_stats` adds them up, for the whole
+/// history and per model, so models can be compared by cost and by how
+/// often their output needed repair or was rolled back.
+use crate::ai::ProviderId;
+use crate::jobs::TranslationUsage;
+use crate::paths::app_data_dir;
+use crate::review::ReviewSummary;
+use chrono::{DateTime, Utc};
+use log::warn;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const HISTORY_FILE_NAME: &str = "job-history.sqlite3";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS jobs (
+    job_id TEXT PRIMARY KEY,
+    finished_at INTEGER NOT NULL,
+    summary TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS jobs_by_finished_at ON jobs(finished_at);
+";
+
+/// Summary of a finished job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobHistoryEntry {
+    pub job_id: String,
+    /// `completed`, `partial_success` or `failed`
+    pub status: String,
+    pub provider: ProviderId,
+    /// 작업을 마칠 때 쓴 모델 (대체 모델로 바뀌었으면 대체 모델)
+    pub model_id: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub files: usize,
+    /// 세그먼트 수 (검토 상태별)
+    pub segments: ReviewSummary,
+    pub usage: TranslationUsage,
+    /// 번역하거나 저장하지 못한 파일 수
+    pub file_errors: usize,
+}
+
+/// Totals over a set of jobs
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobTotals {
+    pub jobs: u64,
+    pub failed_jobs: u64,
+    pub files: u64,
+    pub file_errors: u64,
+    pub duration_ms: u64,
+    pub segments: ReviewSummary,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// 가격을 아는 모델로 쓴 비용 합계 (USD)
+    pub estimated_cost_usd: f64,
+    /// 번역한 세그먼트 중 복구, 원문 유지, 검토 보류, 요청 실패로 끝난 비율 (0–1)
+    pub issue_rate: f64,
+    /// 번역한 세그먼트 1,000개당 비용 (USD); 가격을 모르면 없음
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_per_thousand_segments_usd: Option<f64>,
+}
+
+impl JobTotals {
+    fn add(&mut self, entry: &JobHistoryEntry) {
+        self.jobs += 1;
+        if entry.status == "failed" {
+            self.failed_jobs += 1;
+        }
+        self.files += entry.files as u64;
+        self.file_errors += entry.file_errors as u64;
+        self.duration_ms = self.duration_ms.saturating_add(entry.duration_ms);
+        let segments = &mut self.segments;
+        segments.total += entry.segments.total;
+        segments.translated += entry.segments.translated;
+        segments.recovered += entry.segments.recovered;
+        segments.rolled_back += entry.segments.rolled_back;
+        segments.held_for_review += entry.segments.held_for_review;
+        segments.failed += entry.segments.failed;
+        segments.untranslated += entry.segments.untranslated;
+        segments.invalid += entry.segments.invalid;
+        segments.already_translated += entry.segments.already_translated;
+        segments.do_not_translate += entry.segments.do_not_translate;
+        self.prompt_tokens = self.prompt_tokens.saturating_add(entry.usage.prompt_tokens);
+        self.completion_tokens = self
+            .completion_tokens
+            .saturating_add(entry.usage.completion_tokens);
+        self.estimated_cost_usd += entry.usage.estimated_cost_usd.unwrap_or(0.0);
+    }
+
+    fn finish(mut self) -> Self {
+        let segments = &self.segments;
+        let issues =
+            segments.recovered + segments.rolled_back + segments.held_for_review + segments.failed;
+        let attempted = segments.translated + issues;
+        if attempted > 0 {
+            self.issue_rate = issues as f64 / attempted as f64;
+        }
+        if attempted > 0 && self.estimated_cost_usd > 0.0 {
+            self.cost_per_thousand_segments_usd =
+                Some(self.estimated_cost_usd * 1000.0 / attempted as f64);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStats {
+    pub provider: ProviderId,
+    pub model_id: String,
+    #[serde(flatten)]
+    pub totals: JobTotals,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationStats {
+    pub lifetime: JobTotals,
+    /// 작업이 많은 모델부터
+    pub by_model: Vec<ModelStats>,
+}
+
+impl TranslationStats {
+    pub fn from_entries(entries: &[JobHistoryEntry]) -> Self {
+        let mut lifetime = JobTotals::default();
+        let mut by_model: BTreeMap<(String, String), (ProviderId, JobTotals)> = BTreeMap::new();
+        for entry in entries {
+            lifetime.add(entry);
+            by_model
+                .entry((entry.provider.label().to_string(), entry.model_id.clone()))
+                .or_insert_with(|| (entry.provider, JobTotals::default()))
+                .1
+                .add(entry);
+        }
+        let mut by_model: Vec<ModelStats> = by_model
+            .into_iter()
+            .map(|((_, model_id), (provider, totals))| ModelStats {
+                provider,
+                model_id,
+                totals: totals.finish(),
+            })
+            .collect();
+        by_model.sort_by_key(|stats| std::cmp::Reverse(stats.totals.jobs));
+        Self {
+            lifetime: lifetime.finish(),
+            by_model,
+        }
+    }
+}
+
+pub struct JobHistory {
+    conn: Connection,
+}
+
+fn db_error(error: rusqlite::Error) -> String {
+    format!("작업 기록을 사용할 수 없습니다: {}", error)
+}
+
+impl JobHistory {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("작업 기록 폴더를 만들지 못했습니다: {}", e))?;
+        }
+        let conn = Connection::open(path).map_err(db_error)?;
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Self { conn })
+    }
+
+    pub fn open_default() -> Result<Self, String> {
+        let path = app_data_dir().join(HISTORY_FILE_NAME);
+        Self::open(&path)
+    }
+
+    /// Stores a job summary; a resumed job replaces its earlier entry
+    pub fn record(&self, entry: &JobHistoryEntry) -> Result<(), String> {
+        let summary = serde_json::to_string(entry)
+            .map_err(|e| format!("작업 기록을 직렬화하지 못했습니다: {}", e))?;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO jobs (job_id, finished_at, summary) VALUES (?1, ?2, ?3)",
+                params![entry.job_id, entry.finished_at.timestamp(), summary],
+            )
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Summaries, newest first
+    pub fn entries(
+        &self,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<JobHistoryEntry>, String> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT summary FROM jobs ORDER BY finished_at DESC, rowid DESC LIMIT ?1 OFFSET ?2",
+            )
+            .map_err(db_error)?;
+        let limit = limit.map_or(-1, |limit| limit as i64);
+        let rows = statement
+            .query_map(params![limit, offset as i64], |row| row.get::<_, String>(0))
+            .map_err(db_error)?;
+        let mut entries = Vec::new();
+        for row in rows {
+            let summary = row.map_err(db_error)?;
+            match serde_json::from_str(&summary) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => warn!("skipping unreadable job history entry: {}", error),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Records a finished job; a failure only leaves it out of the history
+pub fn record_job_history(entry: &JobHistoryEntry) {
+    if let Err(error) = JobHistory::open_default().and_then(|history| history.record(entry)) {
+        warn!(
+            "failed to record job {} in the history: {}",
+            entry.job_id, error
+        );
+    }
+}
+
+/// Tauri command listing finished jobs, newest first
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_job_history(
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<Vec<JobHistoryEntry>, String> {
+    JobHistory::open_default()?.entries(offset.unwrap_or(0), limit)
+}
+
+/// Tauri command adding up the whole job history, overall and per model
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_translation_stats() -> Result<TranslationStats, String> {
+    let entries = JobHistory::open_default()?.entries(0, None)?;
+    Ok(TranslationStats::from_entries(&entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(job_id: &str, model_id: &str, minutes_ago: i64) -> JobHistoryEntry {
+        let finished_at = Utc::now() - Duration::minutes(minutes_ago);
+        JobHistoryEntry {
+            job_id: job_id.into(),
+            status: "completed".into(),
+            provider: ProviderId::Gemini,
+            model_id: model_id.into(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            started_at: finished_at - Duration::seconds(30),
+            finished_at,
+            duration_ms: 30_000,
+            files: 2,
+            segments: ReviewSummary {
+                total: 10,
+                translated: 8,
+                recovered: 1,
+                rolled_back: 1,
+                ..ReviewSummary::default()
+            },
+            usage: TranslationUsage {
+                prompt_tokens: 1_000,
+                completion_tokens: 500,
+                total_tokens: 1_500,
+                request_count: 10,
+                reported_request_count: 10,
+                estimated_cost_usd: Some(0.02),
+            },
+            file_errors: 0,
+        }
+    }
+
+    #[test]
+    fn records_jobs_and_adds_up_stats_per_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = JobHistory::open(&dir.path().join("history.sqlite3")).unwrap();
+        history.record(&entry("a", "gemini-2.0-flash", 30)).unwrap();
+        history.record(&entry("b", "gemini-1.5-pro", 20)).unwrap();
+        let mut failed = entry("c", "gemini-2.0-flash", 10);
+        failed.status = "failed".into();
+        failed.usage.estimated_cost_usd = None;
+        history.record(&failed).unwrap();
+        // A resumed job replaces its entry
+        history.record(&entry("a", "gemini-2.0-flash", 5)).unwrap();
+
+        let ids: Vec<String> = history
+            .entries(0, None)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.job_id)
+            .collect();
+        assert_eq!(ids, ["a", "c", "b"]);
+        assert_eq!(history.entries(1, Some(1)).unwrap()[0].job_id, "c");
+
+        let stats = TranslationStats::from_entries(&history.entries(0, None).unwrap());
+        assert_eq!(stats.lifetime.jobs, 3);
+        assert_eq!(stats.lifetime.failed_jobs, 1);
+        assert_eq!(stats.lifetime.segments.total, 30);
+        assert_eq!(stats.lifetime.prompt_tokens, 3_000);
+        assert!((stats.lifetime.issue_rate - 0.2).abs() < 1e-9);
+
+        assert_eq!(stats.by_model[0].model_id, "gemini-2.0-flash");
+        assert_eq!(stats.by_model[0].totals.jobs, 2);
+        let cost = stats.by_model[0]
+            .totals
+            .cost_per_thousand_segments_usd
+            .unwrap();
+        assert!((cost - 1.0).abs() < 1e-9);
+        assert_eq!(stats.by_model[1].model_id, "gemini-1.5-pro");
+    }
+}
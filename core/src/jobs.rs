@@ -1,36 +1,96 @@
+use crate::access_mode::ensure_writable;
 use crate::ai::{
+    catalog::{cached_models, fallback_model},
     hints::{RetryHint, RetryHintSource},
-    translate_text, ProviderId, TranslationError,
+    metrics, ping_provider,
+    pricing::{pricing_for, ModelPricing},
+    prompt::{PromptConfig, PromptVars},
+    rate_limit::{estimate_request_tokens, RateLimitConfig},
+    retry::{apply_jitter, jitter_sample},
+    translate_document, translate_text_with_context, ModelParams, ProviderId, TokenUsage,
+    TranslationError, TranslationOutput, TranslationSettings,
 };
 use crate::archive::{self, ArchiveModification};
-use crate::backup::backup_and_swap;
-use crate::placeholder_validator::{PlaceholderValidator, Segment as ValidatorSegment};
-use crate::protector::Protector;
-use crate::quality::{validate_segment, SegmentLimits};
+use crate::backup::{backup_and_swap, write_atomic};
+use crate::bidi::{self, BidiMarks};
+use crate::config::{matches_glob, RequestOptions};
+use crate::consistency::{
+    check_consistency, harmonize, ConsistencyMode, ConsistencyReport, TranslatedEntry,
+};
+use crate::encoding::{FileMetadata, OutputEncoding};
+use crate::file_lock::{is_sharing_violation, locked_file_message, running_lock_holders};
+use crate::format_validator::validate_for_format;
+use crate::formats::directive::{parse_directive, Directive};
+use crate::formats::plugin::plugin_for_path;
+use crate::formats::xml::translator_note;
+use crate::formats::{
+    self, get_handler, FileFormat, FormatError, FormatHandler, TranslatableEntry, TranslationResult,
+};
+use crate::glossary::GlossaryApplier;
+use crate::incremental::{entry_key, snapshot_path, DiffSummary, EntryChange, SourceSnapshot};
+use crate::install::{
+    file_checksum, job_outputs, record_job_output, rollback_job_outputs, JobOutput, RollbackReport,
+};
+use crate::job::adaptive::{AdaptiveBatchController, BatchSignal};
+use crate::job::events::{JobEvent, JOB_EVENT_CHANNEL};
+use crate::job::queue::{JobPriority, JobQueue};
+use crate::job::sink::ProgressSink;
+use crate::job_history::{record_job_history, JobHistoryEntry};
+use crate::key_usage::KeyUsageIndex;
+use crate::language_detect::{detect_language, is_written_in};
+use crate::llm_guards::{OutputFilterConfig, OutputFilterMode, OutputIssue};
+use crate::paths::app_data_dir;
+use crate::paths::{normalize_optional_relative_path, normalize_relative_path, PathError};
+use crate::placeholder_validator::{
+    PlaceholderValidator, RecoveryStep, RetryInfo, Segment as ValidatorSegment,
+    ValidationErrorCode, ValidationFailureReport, ValidationSuccess, ValidatorConfig,
+};
+use crate::profiles::layout::{sanitized_language_tag, OutputLayout, SuffixLayout};
+use crate::profiles::GameProfile;
+use crate::protector::{MarkerScheme, ProtectedFragment, ProtectionMode, Protector};
+use crate::provenance::{prepend_provenance, render_provenance_header_for_path, ProvenanceInfo};
+use crate::qc_report::{save_qc_report, QcFileError, QcReport, QcSegment};
+use crate::quality::{normalize_punctuation, validate_segment, SegmentLimits};
+use crate::quality_estimation::{
+    back_translate, combine_scores, comet_score, heuristic_score, similarity,
+    QualityEstimationConfig,
+};
+use crate::review::{
+    review_report_path, write_review_report, FileReviewReport, ReviewMetadata, ReviewSummary,
+    SegmentReview, SegmentReviewStatus,
+};
+use crate::script_hooks::{load_profile_script, HookContext, ScriptHooks};
+use crate::secrets::stored_api_key;
+use crate::skip_list::{SkipList, SkipMatcher};
+use crate::tone_analyzer::{tone_mismatch, TargetTone, ToneAnalyzer};
+use crate::triage::{save_triage_store, FailedSegment, TriageFile, TriageStore};
+use crate::usage_stats::{record_job_usage, JobUsageEvent};
 use crate::validation_logger::{validation_logger, ValidationOutcome};
-use log::warn;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
 use once_cell::sync::Lazy;
+use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{DefaultHasher, Entry};
-use std::collections::HashMap;
-use std::ffi::OsStr;
-use std::fs::{self, File};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::future::Future;
 use std::hash::{Hash, Hasher};
-use std::io::Write;
+use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
+use std::time::{Duration, Instant, SystemTime};
+#[cfg(feature = "tauri")]
+use tauri::AppHandle;
 use tokio::sync::Notify;
 use tokio::time::sleep;
 
-const MAX_RETRY_ATTEMPTS: usize = 3;
-
 const RATE_LIMIT_BASE_BACKOFF_MS: u64 = 1_000;
 const RATE_LIMIT_MAX_BACKOFF_MS: u64 = 60_000;
-const RESUME_DIR_NAME: &str = ".resume";
+pub const RESUME_DIR_NAME: &str = ".resume";
+const JOB_STATE_DIR_NAME: &str = "jobs";
 
 static ACTIVE_JOBS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
@@ -41,6 +101,23 @@ static JOB_STATES: Lazy<Mutex<HashMap<String, JobState>>> =
 static JOB_BACKOFFS: Lazy<Mutex<HashMap<String, Arc<BackoffController>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+static JOB_QUEUE: Lazy<Mutex<JobQueue<StartTranslationJobPayload>>> =
+    Lazy::new(|| Mutex::new(JobQueue::default()));
+
+/// Set once the app is closing: queued jobs stay queued and jobs stopped for
+/// the exit keep their state recoverable
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Jobs taken off the queue whose launch has not registered them as active
+/// yet; they count against the concurrency limit like running jobs
+static LAUNCHING_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+static JOB_STATUSES: Lazy<Mutex<HashMap<String, JobStatusSnapshot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static APPROVAL_SESSIONS: Lazy<Mutex<HashMap<String, ApprovalSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct ActiveBackoff {
@@ -112,8 +189,9 @@ impl BackoffController {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct TranslationCheckpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current_file_path: Option<String>,
@@ -123,14 +201,134 @@ pub struct TranslationCheckpoint {
     pub total_count: u32,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Cumulative token usage for a job, aggregated from provider responses.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct TranslationUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// Number of successful provider requests made by the job.
+    pub request_count: u32,
+    /// Number of those requests whose response included usage metadata.
+    pub reported_request_count: u32,
+    /// Estimated spend in USD; absent when the model has no known pricing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl TranslationUsage {
+    fn record(&mut self, usage: Option<TokenUsage>, pricing: Option<ModelPricing>) {
+        self.request_count = self.request_count.saturating_add(1);
+        if let Some(usage) = usage {
+            self.prompt_tokens = self.prompt_tokens.saturating_add(usage.prompt_tokens);
+            self.completion_tokens = self
+                .completion_tokens
+                .saturating_add(usage.completion_tokens);
+            self.total_tokens = self.total_tokens.saturating_add(usage.total_tokens());
+            self.reported_request_count = self.reported_request_count.saturating_add(1);
+            if let Some(pricing) = pricing {
+                let spent = self.estimated_cost_usd.unwrap_or(0.0);
+                self.estimated_cost_usd = Some(spent + pricing.cost_usd(&usage));
+            }
+        }
+    }
+}
+
+/// Optional spending limits for a job. Limits are compared against the
+/// cumulative usage of the job, so resuming a paused job requires raising or
+/// clearing the limit that was hit.
+#[derive(Debug, Clone, Copy, Default)]
+struct JobBudget {
+    max_cost_usd: Option<f64>,
+    max_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BudgetLimitKind {
+    Tokens,
+    Cost,
+}
+
+impl BudgetLimitKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BudgetLimitKind::Tokens => "tokens",
+            BudgetLimitKind::Cost => "cost",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BudgetExceeded {
+    kind: BudgetLimitKind,
+    limit: f64,
+    used: f64,
+}
+
+impl JobBudget {
+    fn from_payload(payload: &StartTranslationJobPayload) -> Self {
+        Self {
+            max_cost_usd: payload
+                .max_cost_usd
+                .filter(|value| value.is_finite() && *value >= 0.0),
+            max_tokens: payload.max_tokens,
+        }
+    }
+
+    fn check(&self, usage: &TranslationUsage) -> Option<BudgetExceeded> {
+        if let Some(max_tokens) = self.max_tokens {
+            if usage.total_tokens >= max_tokens {
+                return Some(BudgetExceeded {
+                    kind: BudgetLimitKind::Tokens,
+                    limit: max_tokens as f64,
+                    used: usage.total_tokens as f64,
+                });
+            }
+        }
+
+        if let (Some(max_cost), Some(spent)) = (self.max_cost_usd, usage.estimated_cost_usd) {
+            if spent >= max_cost {
+                return Some(BudgetExceeded {
+                    kind: BudgetLimitKind::Cost,
+                    limit: max_cost,
+                    used: spent,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Whether about `tokens` more tokens can be spent without passing a
+    /// limit. Without a price the cost limit is not checked.
+    fn allows(&self, usage: &TranslationUsage, tokens: u64, pricing: Option<ModelPricing>) -> bool {
+        if let Some(max_tokens) = self.max_tokens {
+            if usage.total_tokens.saturating_add(tokens) > max_tokens {
+                return false;
+            }
+        }
+        if let (Some(max_cost), Some(pricing)) = (self.max_cost_usd, pricing) {
+            // Estimates count about as many reply tokens as prompt tokens
+            let estimate = TokenUsage::new(tokens / 2, tokens - tokens / 2);
+            let spent = usage.estimated_cost_usd.unwrap_or(0.0);
+            if spent + pricing.cost_usd(&estimate) > max_cost {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct FileSignature {
     modified: Option<SystemTime>,
     hash: u64,
     len: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct FileProgress {
     signature: FileSignature,
     replacements: HashMap<usize, String>,
@@ -145,29 +343,59 @@ impl FileProgress {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct JobState {
+    /// Id of the job the state belongs to; the file name only keeps a
+    /// sanitized form of it
+    #[serde(default)]
+    job_id: String,
     checkpoint: TranslationCheckpoint,
     files: HashMap<String, FileProgress>,
+    #[serde(default)]
+    usage: TranslationUsage,
+    /// Status the last run ended with; `None` while it runs, so a state
+    /// left without one after a restart belongs to a run that crashed
+    #[serde(default)]
+    ended_with: Option<String>,
 }
 
 impl JobState {
     fn new() -> Self {
         Self {
+            job_id: String::new(),
             checkpoint: TranslationCheckpoint::default(),
             files: HashMap::new(),
+            usage: TranslationUsage::default(),
+            ended_with: None,
         }
     }
 }
 
-fn load_job_state(job_id: &str) -> Option<JobState> {
-    JOB_STATES
+/// Returns the state for `job_id`. When `resume_from_disk` is set and the job
+/// is not in memory (e.g. after an app restart), the persisted state is
+/// reloaded from the app data dir.
+fn load_job_state(job_id: &str, resume_from_disk: bool) -> Option<JobState> {
+    let in_memory = JOB_STATES
         .lock()
         .ok()
-        .and_then(|guard| guard.get(job_id).cloned())
+        .and_then(|guard| guard.get(job_id).cloned());
+    if in_memory.is_some() || !resume_from_disk {
+        return in_memory;
+    }
+
+    let state = read_job_state_file(&job_state_file_path(job_id))?;
+    if let Ok(mut guard) = JOB_STATES.lock() {
+        guard.insert(job_id.to_string(), state.clone());
+    }
+    Some(state)
 }
 
-fn save_job_state(job_id: &str, state: JobState) {
+fn save_job_state(job_id: &str, mut state: JobState) {
+    state.job_id = job_id.to_string();
+    state.ended_with = None;
+    if let Err(error) = write_job_state_file(&job_state_file_path(job_id), &state) {
+        warn!("failed to persist job state for {job_id}: {error}");
+    }
     if let Ok(mut guard) = JOB_STATES.lock() {
         guard.insert(job_id.to_string(), state);
     }
@@ -177,6 +405,72 @@ fn clear_job_state(job_id: &str) {
     if let Ok(mut guard) = JOB_STATES.lock() {
         guard.remove(job_id);
     }
+    let path = job_state_file_path(job_id);
+    if path.exists() {
+        if let Err(error) = fs::remove_file(&path) {
+            warn!(
+                "failed to remove persisted job state {}: {error}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Records how the run ended in the persisted state, if the job kept one
+fn mark_job_state_ended(job_id: &str, status: &str) {
+    // A job stopped because the app is closing is offered for recovery
+    if status == "canceled" && SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return;
+    }
+    let path = job_state_file_path(job_id);
+    if !path.exists() {
+        return;
+    }
+    let Ok(mut guard) = JOB_STATES.lock() else {
+        return;
+    };
+    let Some(state) = guard.get_mut(job_id) else {
+        return;
+    };
+    state.ended_with = Some(status.to_string());
+    if let Err(error) = write_job_state_file(&path, state) {
+        warn!("failed to persist job state for {job_id}: {error}");
+    }
+}
+
+fn job_state_dir() -> PathBuf {
+    app_data_dir().join(JOB_STATE_DIR_NAME)
+}
+
+fn job_state_file_path(job_id: &str) -> PathBuf {
+    let file_stem: String = job_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    job_state_dir().join(format!("{file_stem}.json"))
+}
+
+fn read_job_state_file(path: &Path) -> Option<JobState> {
+    let contents = fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<JobState>(&contents) {
+        Ok(state) => Some(state),
+        Err(error) => {
+            warn!("ignoring unreadable job state {}: {error}", path.display());
+            None
+        }
+    }
+}
+
+/// Writes the state atomically so a crash mid-write never leaves a
+/// truncated checkpoint behind.
+fn write_job_state_file(path: &Path, state: &JobState) -> std::io::Result<()> {
+    write_atomic(path, &serde_json::to_vec(state)?)
 }
 
 fn current_checkpoint(job_id: &str) -> Option<TranslationCheckpoint> {
@@ -186,6 +480,13 @@ fn current_checkpoint(job_id: &str) -> Option<TranslationCheckpoint> {
         .and_then(|guard| guard.get(job_id).map(|state| state.checkpoint.clone()))
 }
 
+fn current_usage(job_id: &str) -> Option<TranslationUsage> {
+    JOB_STATES
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(job_id).map(|state| state.usage.clone()))
+}
+
 fn compute_file_signature(path: &Path, content: &str) -> FileSignature {
     let metadata = fs::metadata(path).ok();
     let modified = metadata.as_ref().and_then(|data| data.modified().ok());
@@ -203,6 +504,94 @@ fn compute_file_signature(path: &Path, content: &str) -> FileSignature {
     }
 }
 
+/// Incremental mode: segments whose source is unchanged since the last run
+/// take their previous translation and are dropped from the segments to
+/// translate.
+fn carry_over_unchanged(
+    target_lang: &str,
+    file_contexts: &mut [FileContext],
+    segments: &mut Vec<Segment>,
+) -> DiffSummary {
+    let mut snapshots: HashMap<usize, Option<SourceSnapshot>> = HashMap::new();
+    let mut summary = DiffSummary::default();
+    segments.retain(|segment| {
+        let Some(context) = file_contexts.get_mut(segment.file_index) else {
+            return true;
+        };
+        let snapshot = snapshots.entry(segment.file_index).or_insert_with(|| {
+            SourceSnapshot::load(&snapshot_path(
+                &context.mod_install_path,
+                &context.relative_path,
+                target_lang,
+            ))
+        });
+        let change = snapshot
+            .as_ref()
+            .map_or(EntryChange::New, |snapshot| snapshot.lookup(&segment.text));
+        summary.record(&change);
+        let EntryChange::Unchanged(translation) = change else {
+            return true;
+        };
+        context.translated_lines[segment.line_index] = Some(format!(
+            "{}{}{}",
+            segment.prefix, translation, segment.suffix
+        ));
+        false
+    });
+    summary
+}
+
+/// Keeps the file's sources and translations for the next incremental run
+fn record_source_snapshot(context: &FileContext, target_lang: &str) {
+    let path = snapshot_path(
+        &context.mod_install_path,
+        &context.relative_path,
+        target_lang,
+    );
+    let snapshot = SourceSnapshot::from_lines(&context.lines, &context.translated_lines);
+    if let Err(error) = snapshot.save(&path) {
+        warn!(
+            "failed to save source snapshot for {}: {error}",
+            context.relative_path
+        );
+    }
+}
+
+/// Takes out the segments whose text is already written in the target
+/// language. Their lines are left as they are in the output.
+fn take_already_translated(target_lang: &str, segments: &mut Vec<Segment>) -> Vec<Segment> {
+    let (skipped, kept) = std::mem::take(segments)
+        .into_iter()
+        .partition(|segment| is_already_translated(segment, target_lang));
+    *segments = kept;
+    skipped
+}
+
+fn is_already_translated(segment: &Segment, target_lang: &str) -> bool {
+    is_written_in(&detection_text(segment), target_lang)
+}
+
+/// The segment's text without its placeholders and entry key, which would
+/// otherwise read as Latin-script words to language detection
+fn detection_text(segment: &Segment) -> String {
+    let fragment = segment.protect();
+    let mut text = segment
+        .marker_scheme
+        .regex()
+        .replace_all(fragment.masked_text(), " ")
+        .into_owned();
+    if let Some(key) = entry_key(&segment.text) {
+        text = text.replacen(&key, " ", 1);
+    }
+    text
+}
+
+/// Language of a file's segments, for jobs whose source language is `auto`
+fn detect_file_language(segments: &[Segment]) -> Option<String> {
+    let texts: Vec<String> = segments.iter().map(detection_text).collect();
+    detect_language(texts.iter().map(String::as_str))
+}
+
 fn apply_stored_translations(
     job_state: &JobState,
     file_contexts: &mut [FileContext],
@@ -252,45 +641,292 @@ fn set_checkpoint_for_pending_segment(
     job_state.checkpoint.next_line_index = Some(segment.line_index as u32);
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct TranslationFileInput {
     pub relative_path: String,
     pub mod_install_path: String,
     /// 아카이브 내부 파일인 경우 아카이브의 상대 경로
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub archive_path: Option<String>,
     /// 아카이브 내부 엔트리 경로
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub archive_entry_path: Option<String>,
 }
 
+impl StartTranslationJobPayload {
+    /// A job translating `files` with `model_id` of `provider`, every other
+    /// option left at its default
+    pub fn new(
+        job_id: impl Into<String>,
+        provider: impl Into<String>,
+        model_id: impl Into<String>,
+        files: Vec<TranslationFileInput>,
+    ) -> Self {
+        Self {
+            job_id: job_id.into(),
+            provider: provider.into(),
+            model_id: model_id.into(),
+            files,
+            ..Self::default()
+        }
+    }
+
+    /// Languages the job translates into, `ko` when none is given
+    fn target_languages(&self) -> Vec<String> {
+        let languages = self
+            .target_lang
+            .as_ref()
+            .map(TargetLangs::languages)
+            .unwrap_or_default();
+        if languages.is_empty() {
+            vec!["ko".to_string()]
+        } else {
+            languages
+        }
+    }
+
+    /// The job of one language of a multi-language job
+    fn for_target_language(&self, language: &str) -> Self {
+        let mut payload = self.clone();
+        payload.job_id = language_job_id(&self.job_id, language);
+        payload.target_lang = Some(TargetLangs::One(language.to_string()));
+        payload
+    }
+
+    /// Why the include/exclude globs leave out the file at `relative_path`,
+    /// if they do
+    fn glob_skip(&self, relative_path: &str) -> Option<GlobSkip> {
+        if let Some(index) = self
+            .exclude_globs
+            .iter()
+            .position(|glob| matches_glob(relative_path, glob))
+        {
+            return Some(GlobSkip::Excluded(index));
+        }
+        let included = self.include_globs.is_empty()
+            || self
+                .include_globs
+                .iter()
+                .any(|glob| matches_glob(relative_path, glob));
+        (!included).then_some(GlobSkip::NotIncluded)
+    }
+}
+
+/// A file left out by the job's globs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GlobSkip {
+    /// Matched the exclude glob at this index
+    Excluded(usize),
+    /// Matched none of the include globs
+    NotIncluded,
+}
+
+/// Id of the job that translates a multi-language job into `language`
+fn language_job_id(job_id: &str, language: &str) -> String {
+    format!("{job_id}-{}", sanitized_language_tag(language))
+}
+
 impl TranslationFileInput {
+    /// 모드 폴더 `mod_install_path` 안의 `relative_path` 파일
+    pub fn new(relative_path: impl Into<String>, mod_install_path: impl Into<String>) -> Self {
+        Self {
+            relative_path: relative_path.into(),
+            mod_install_path: mod_install_path.into(),
+            archive_path: None,
+            archive_entry_path: None,
+        }
+    }
+
     /// 아카이브 내부 파일인지 확인
     pub fn is_archive_entry(&self) -> bool {
         self.archive_path.is_some() && self.archive_entry_path.is_some()
     }
+
+    /// UI에서 넘어온 상대 경로의 구분자와 중복 세그먼트를 정규화
+    pub fn normalize_paths(&mut self) -> Result<(), PathError> {
+        self.relative_path = normalize_relative_path(&self.relative_path)?;
+        self.mod_install_path = self.mod_install_path.trim().to_string();
+        self.archive_path = normalize_optional_relative_path(self.archive_path.as_deref())?;
+        self.archive_entry_path =
+            normalize_optional_relative_path(self.archive_entry_path.as_deref())?;
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+fn normalize_file_inputs(files: &mut [TranslationFileInput]) -> Result<(), String> {
+    for file in files.iter_mut() {
+        let original = file.relative_path.clone();
+        file.normalize_paths()
+            .map_err(|error| format!("잘못된 파일 경로입니다 ({original}): {error}"))?;
+    }
+    Ok(())
+}
+
+/// Neighboring segments sent on each side in `ContextMode::Surrounding`
+/// when the job does not set `context_neighbors`
+pub const DEFAULT_CONTEXT_NEIGHBORS: usize = 2;
+const MAX_CONTEXT_NEIGHBORS: usize = 10;
+
+/// What is sent to the model with each segment as read-only context, besides
+/// translator notes and key usages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContextMode {
+    #[default]
+    Off,
+    /// File path and entry key
+    Key,
+    /// File path, entry key and neighboring segments of the same file
+    Surrounding,
+}
+
+/// Target language of a job: one code (`"ko"`) or a list of codes
+/// (`["ko", "ja", "zh-cn"]`) translated from the same source in turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TargetLangs {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl TargetLangs {
+    /// Distinct non-empty codes, in the order given
+    pub fn languages(&self) -> Vec<String> {
+        let codes: Vec<&String> = match self {
+            Self::One(code) => vec![code],
+            Self::Many(codes) => codes.iter().collect(),
+        };
+        let mut languages: Vec<String> = Vec::new();
+        for code in codes {
+            let code = code.trim();
+            if !code.is_empty()
+                && !languages
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(code))
+            {
+                languages.push(code.to_string());
+            }
+        }
+        languages
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct StartTranslationJobPayload {
     pub job_id: String,
     pub provider: String,
-    pub api_key: Option<String>,
     pub model_id: String,
     pub files: Vec<TranslationFileInput>,
     pub source_lang: Option<String>,
-    pub target_lang: Option<String>,
+    /// 대상 언어 (여러 개면 언어마다 `{jobId}-{언어}` 작업으로 차례로 번역)
+    pub target_lang: Option<TargetLangs>,
     pub output_override_dir: Option<String>,
     #[serde(default)]
     pub resume_from_checkpoint: bool,
     #[serde(default)]
     pub reset_resume_state: bool,
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    #[serde(default)]
+    pub model_params: ModelParams,
+    /// 출력 파일 상단에 기계 번역 출처 주석을 추가할지 여부
+    #[serde(default)]
+    pub embed_provenance: bool,
+    /// 엄격 모드: 자동 복구된 세그먼트를 기록하지 않고 검토 대상으로 돌림
+    #[serde(default)]
+    pub strict_mode: bool,
+    /// 작업 후 원문/번역 대조 검토 보고서(JSON)를 출력 파일 옆에 작성
+    #[serde(default)]
+    pub export_review: bool,
+    /// 승인 모드: 번역 결과를 바로 쓰지 않고 세그먼트별 승인을 기다림
+    #[serde(default)]
+    pub approval_mode: bool,
+    /// 사용자 용어집 (원문 용어 → 번역 용어), 게임 프로필 용어보다 우선
+    #[serde(default)]
+    pub glossary: HashMap<String, String>,
+    /// XML의 `<!-- EN: ... -->` 주석을 다음 문장의 번역 참고 문맥으로 사용
+    #[serde(default)]
+    pub translator_notes: bool,
+    /// 출력 경로가 원본 파일과 같을 때 백업 후 덮어쓰기를 허용 (기본값은 별도 폴더로 우회)
+    #[serde(default)]
+    pub allow_source_overwrite: bool,
+    /// 자리표시자 마커 괄호 방식 (없으면 원문과 겹치지 않는 방식을 자동 선택)
+    #[serde(default)]
+    pub marker_scheme: Option<MarkerScheme>,
+    /// 출력 파일 인코딩 (기본값은 원본 인코딩 유지)
+    #[serde(default)]
+    pub output_encoding: OutputEncoding,
+    /// 증분 모드: 이전 실행 이후 바뀌지 않은 원문은 이전 번역을 재사용하고
+    /// 새로 추가되거나 바뀐 문장만 번역
+    #[serde(default)]
+    pub incremental: bool,
+    /// 번역 프롬프트 템플릿 (설정의 `prompts`, 게임 프로필의 프롬프트 설정이 우선)
+    #[serde(default)]
+    pub prompts: PromptConfig,
+    /// 문장과 함께 참고 문맥으로 보낼 정보 (키, 파일 경로, 앞뒤 문장)
+    #[serde(default)]
+    pub context_mode: ContextMode,
+    /// `surrounding` 모드에서 앞뒤로 보낼 문장 수 (기본값 2, 최대 10)
+    #[serde(default)]
+    pub context_neighbors: Option<usize>,
+    /// 번역 어조 (formal, casual, preserve). 프롬프트에 넣고 번역 후 어조가 다르면 QC 경고
+    #[serde(default)]
+    pub target_tone: Option<TargetTone>,
+    /// 작업 후 같은 원문·용어집 용어가 다르게 번역된 곳을 찾는 2차 검사
+    /// (report: 보고만, harmonize: 가장 많이 쓰인 번역으로 통일 후 보고)
+    #[serde(default)]
+    pub consistency_pass: Option<ConsistencyMode>,
+    /// 분당 요청/토큰 수 제한. 같은 번역기·모델을 쓰는 모든 작업이 함께 사용
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// 연결/요청 시간 제한과 세그먼트당 최대 시도 횟수
+    #[serde(default)]
+    pub request_options: RequestOptions,
+    /// 번역기 API 대신 요청을 보낼 주소 (같은 API를 제공하는 프록시 등, 없으면 번역기 주소)
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// 모델을 찾을 수 없을 때 대신 쓸 모델 (원래 모델 → 대체 모델). 없는 모델은 내장 대체
+    /// 모델을 쓰고, 빈 값이면 대체하지 않습니다.
+    #[serde(default)]
+    pub model_fallbacks: HashMap<String, String>,
+    /// 자리표시자 검증 방식 (검증 모드, 자동 복구, 재시도 횟수, 퍼센트 결합 유지)
+    #[serde(default)]
+    pub validator_config: ValidatorConfig,
+    /// 번역 후 세그먼트마다 0~100 신뢰도 점수를 매기는 품질 추정 (길이·문자 휴리스틱,
+    /// 저렴한 모델로 표본 역번역, 로컬 COMET 방식 채점). 낮은 점수는 QC 보고서에 표시
+    #[serde(default)]
+    pub quality_estimation: QualityEstimationConfig,
+    /// 번역 결과의 욕설·비하 표현·거절 응답·지시문 주입 검사
+    /// (block: 원문 유지, warn: QC 경고, allow: 검사 안 함)
+    #[serde(default)]
+    pub output_filter: OutputFilterConfig,
+    /// 원문이 이미 대상 언어로 쓰인 세그먼트는 번역하지 않고 그대로 둠 (언어 감지)
+    #[serde(default)]
+    pub skip_already_translated: bool,
+    /// 아랍어·히브리어 등 RTL 대상 언어에서 보호된 토큰을 감쌀 방향 제어 문자
+    /// (isolate: LRI/PDI, mark: LRM, off: 넣지 않음)
+    #[serde(default)]
+    pub bidi_marks: BidiMarks,
+    /// 번역할 파일의 글롭 (비어 있으면 모든 파일, 예: `lang/**`)
+    #[serde(default)]
+    pub include_globs: Vec<String>,
+    /// 건너뛸 파일의 글롭 (예: `README.md`, `CHANGELOG*`, `docs/`). 포함 글롭보다 우선
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// 추출은 하되 번역하지 않고 그대로 둘 항목 키와 정규식 (게임 프로필의 목록과 합침)
+    #[serde(default)]
+    pub skip_list: SkipList,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct TranslationProgressEventPayload {
     pub job_id: String,
     pub status: String,
@@ -308,6 +944,9 @@ pub struct TranslationProgressEventPayload {
     pub file_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_success: Option<bool>,
+    /// `file_name` 파일의 진행률 (문자 수 기준, 0–100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_progress_pct: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_errors: Option<Vec<TranslationFileErrorEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -315,9 +954,81 @@ pub struct TranslationProgressEventPayload {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checkpoint: Option<TranslationCheckpoint>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TranslationUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryStatusPayload>,
 }
 
+/// A segment that strict mode held back because the validator had to repair
+/// it. The source text is kept in the output until the segment is reviewed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ReviewSegment {
+    pub file_path: String,
+    pub line_number: usize,
+    pub source: String,
+    /// The auto-recovered translation that was not written.
+    pub candidate: String,
+    pub recovery_steps: Vec<RecoveryStep>,
+}
+
+/// Latest known state of a job, as returned by `list_jobs` and `get_job_status`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct JobStatusSnapshot {
+    pub job_id: String,
+    pub status: String,
+    /// Zero-based position in the queue while the job is waiting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<JobPriority>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_pct: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translated_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TranslationUsage>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JobStatusSnapshot {
+    fn new(job_id: String) -> Self {
+        Self {
+            job_id,
+            status: "pending".into(),
+            queue_position: None,
+            priority: None,
+            progress_pct: None,
+            translated_count: None,
+            total_count: None,
+            usage: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn apply(&mut self, payload: &TranslationProgressEventPayload) {
+        self.status = payload.status.clone();
+        if payload.progress_pct.is_some() {
+            self.progress_pct = payload.progress_pct;
+        }
+        if payload.translated_count.is_some() {
+            self.translated_count = payload.translated_count;
+        }
+        if payload.total_count.is_some() {
+            self.total_count = payload.total_count;
+        }
+        if payload.usage.is_some() {
+            self.usage = payload.usage.clone();
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LastWrittenInfo {
@@ -361,26 +1072,482 @@ struct Segment {
     text: String,
     prefix: String,
     suffix: String,
+    /// Translator note from a marked XML comment above this line
+    note: Option<String>,
+    /// Where the text or key is used elsewhere in the mod
+    usage: Option<String>,
+    /// File path, key and neighboring segments, per the job's context mode
+    surroundings: Option<String>,
+    /// Extra placeholders from the mod's game profile
+    placeholder_patterns: Arc<Vec<Regex>>,
+    /// Hooks of the game profile's script
+    script: Option<Arc<ScriptHooks>>,
+    /// Marker brackets negotiated for the job
+    marker_scheme: MarkerScheme,
+    /// The text masked once the marker scheme is settled
+    protected: Option<ProtectedFragment>,
+}
+
+impl Segment {
+    /// Masks the text with the built-in token classes and the profile's patterns.
+    fn protect(&self) -> ProtectedFragment {
+        match &self.protected {
+            Some(fragment) => fragment.clone(),
+            None => self.protect_part(&self.text),
+        }
+    }
+
+    /// Masks a chunk of the text the same way as the whole segment.
+    fn protect_part(&self, text: &str) -> ProtectedFragment {
+        let hooked = self.script.as_ref().map(|script| {
+            script.before_send(
+                text,
+                HookContext {
+                    file: &self.relative_path,
+                    line: self.line_number,
+                    target_lang: None,
+                },
+            )
+        });
+        Protector::protect_with_scheme(
+            hooked.as_deref().unwrap_or(text),
+            ProtectionMode::Full,
+            &self.placeholder_patterns,
+            self.marker_scheme,
+        )
+    }
+
+    /// Extra context sent with the segment: translator note, usage summary
+    /// and surroundings.
+    fn note_context(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(surroundings) = &self.surroundings {
+            lines.push(surroundings.clone());
+        }
+        if let Some(note) = &self.note {
+            lines.push(format!("Translator note: {note}"));
+        }
+        if let Some(usage) = &self.usage {
+            lines.push(format!("Usage in this mod: {usage}"));
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+/// Lookups on the source side of a job that do not depend on the target
+/// language. The languages of a multi-language job share them, so each mod
+/// is resolved and indexed and each segment masked only once.
+#[derive(Default)]
+struct SourceLookups {
+    profiles: HashMap<PathBuf, Option<GameProfile>>,
+    placeholder_patterns: HashMap<PathBuf, Arc<Vec<Regex>>>,
+    scripts: HashMap<PathBuf, Option<Arc<ScriptHooks>>>,
+    key_usages: HashMap<PathBuf, KeyUsageIndex>,
+    /// (mod root, marker scheme, text) -> masked text
+    fragments: HashMap<(PathBuf, MarkerScheme, String), ProtectedFragment>,
+}
+
+/// Context block for the segment at `index` of a file's `texts`: the file
+/// path and entry key, and with `ContextMode::Surrounding` up to `neighbors`
+/// segments on each side
+fn segment_surroundings(
+    mode: ContextMode,
+    relative_path: &str,
+    texts: &[&str],
+    index: usize,
+    neighbors: usize,
+) -> Option<String> {
+    if mode == ContextMode::Off {
+        return None;
+    }
+    let mut lines = vec![format!("File: {relative_path}")];
+    if let Some(key) = entry_key(texts[index]) {
+        lines.push(format!("Key: {key}"));
+    }
+    if mode == ContextMode::Surrounding {
+        let before = &texts[index.saturating_sub(neighbors)..index];
+        let after = &texts[index + 1..(index + 1 + neighbors).min(texts.len())];
+        if !before.is_empty() {
+            lines.push("Previous lines:".into());
+            lines.extend(before.iter().map(|text| text.to_string()));
+        }
+        if !after.is_empty() {
+            lines.push("Next lines:".into());
+            lines.extend(after.iter().map(|text| text.to_string()));
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// Sends upcoming segments to the provider concurrently and hands back their
+/// first-attempt results in order. The window size follows an
+/// [`AdaptiveBatchController`], so it shrinks on rate limits or truncated
+/// output and grows while responses stay fast. Document-mode segments are
+/// never prefetched; they are chunked and translated on their own.
+///
+/// Usage is recorded as soon as a window completes, since every request in
+/// it is billed even if the job stops before its result is used, and a
+/// window never holds more than the job's remaining budget.
+struct SegmentPrefetcher {
+    controller: AdaptiveBatchController,
+    results: HashMap<usize, Result<TranslationOutput, TranslationError>>,
+    budget: JobBudget,
+    pricing: Option<ModelPricing>,
+    cancel_flag: Arc<AtomicBool>,
+}
+
+impl SegmentPrefetcher {
+    fn new(budget: JobBudget, pricing: Option<ModelPricing>, cancel_flag: Arc<AtomicBool>) -> Self {
+        Self {
+            controller: AdaptiveBatchController::default(),
+            results: HashMap::new(),
+            budget,
+            pricing,
+            cancel_flag,
+        }
+    }
+
+    /// Returns the first-attempt result for `segments[index]`, dispatching a
+    /// new window starting at `index` when it has not been fetched yet. The
+    /// usage of every request sent is added to `usage`. Requests still out
+    /// when the job is cancelled are dropped.
+    async fn take(
+        &mut self,
+        client: &Client,
+        file_settings: &[TranslationSettings],
+        segments: &[Segment],
+        index: usize,
+        usage: &mut TranslationUsage,
+    ) -> Result<TranslationOutput, TranslationError> {
+        if let Some(result) = self.results.remove(&index) {
+            return result;
+        }
+
+        let mut projected_tokens = 0u64;
+        let window: Vec<usize> = (index..segments.len())
+            .filter(|&candidate| {
+                let segment = &segments[candidate];
+                candidate == index
+                    || !file_settings[segment.file_index].needs_document_mode(&segment.text)
+            })
+            .take(self.controller.size())
+            .take_while(|&candidate| {
+                let segment = &segments[candidate];
+                projected_tokens +=
+                    estimate_request_tokens(&segment.text, segment.note_context().as_deref());
+                candidate == index || self.budget.allows(usage, projected_tokens, self.pricing)
+            })
+            .collect();
+        let handles: Vec<_> = window
+            .iter()
+            .map(|&candidate| {
+                let client = client.clone();
+                let settings = file_settings[segments[candidate].file_index].clone();
+                let fragment = segments[candidate].protect();
+                let note = segments[candidate].note_context();
+                tokio::spawn(async move {
+                    let started = Instant::now();
+                    let result =
+                        translate_text_with_context(&client, &settings, &fragment, note.as_deref())
+                            .await;
+                    (result, started.elapsed())
+                })
+            })
+            .collect();
+
+        let mut handles = handles.into_iter();
+        for candidate in window {
+            let Some(mut handle) = handles.next() else {
+                break;
+            };
+            let cancelled = wait_for_cancel_flag(self.cancel_flag.clone());
+            tokio::pin!(cancelled);
+            let joined = tokio::select! {
+                joined = &mut handle => joined,
+                _ = &mut cancelled => {
+                    handle.abort();
+                    handles.for_each(|handle| handle.abort());
+                    break;
+                }
+            };
+            let Ok((result, latency)) = joined else {
+                continue;
+            };
+            self.controller.record(batch_signal(&result, latency));
+            if let Ok(output) = &result {
+                usage.record(output.usage, self.pricing);
+            }
+            self.results.insert(candidate, result);
+        }
+
+        if let Some(result) = self.results.remove(&index) {
+            return result;
+        }
+        let segment = &segments[index];
+        let settings = &file_settings[segment.file_index];
+        if self.cancel_flag.load(Ordering::SeqCst) {
+            return Err(TranslationError::NetworkTransient {
+                provider: settings.provider,
+                message: "작업이 취소되어 요청을 중단했습니다.".into(),
+            });
+        }
+        let fragment = segment.protect();
+        let note = segment.note_context();
+        let result =
+            translate_text_with_context(client, settings, &fragment, note.as_deref()).await;
+        if let Ok(output) = &result {
+            usage.record(output.usage, self.pricing);
+        }
+        result
+    }
+}
+
+fn batch_signal(
+    result: &Result<TranslationOutput, TranslationError>,
+    latency: Duration,
+) -> BatchSignal {
+    match result {
+        Ok(_) => BatchSignal::Success { latency },
+        Err(TranslationError::RateLimited { .. }) => BatchSignal::RateLimited,
+        Err(TranslationError::PlaceholderMismatch(_) | TranslationError::StructureMismatch(_)) => {
+            BatchSignal::Truncated
+        }
+        Err(_) => BatchSignal::Failed,
+    }
+}
+
+/// QC outcome of a segment translated in this run, used for review reports.
+struct SegmentReviewNote {
+    status: SegmentReviewStatus,
+    notes: Vec<String>,
+    recovery_steps: Vec<RecoveryStep>,
+    /// Quality estimation score, see `quality_estimation.rs`
+    confidence: Option<u8>,
+}
+
+/// A translated segment waiting for approval, as shown to the user.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PendingSegment {
+    pub segment_id: usize,
+    pub file_path: String,
+    pub line_number: usize,
+    pub source: String,
+    pub translation: String,
+    /// Where the text or key is used elsewhere in the mod
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentDecision {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+struct StagedSegment {
+    file_index: usize,
+    line_index: usize,
+    line_number: usize,
+    source: String,
+    prefix: String,
+    suffix: String,
+    translation: String,
+    usage: Option<String>,
+    decision: SegmentDecision,
+}
+
+/// Translations of an approval-mode job that have not been written yet.
+/// Regular files are written as soon as all of their segments are decided;
+/// archive entries are written together once the whole job is decided.
+struct ApprovalSession {
+    target_lang: String,
+    files: Vec<FileContext>,
+    written: Vec<bool>,
+    segments: Vec<StagedSegment>,
+    /// Files that could not be written, reported when the session ends
+    write_errors: Vec<TranslationFileErrorEntry>,
+    /// Batches of files taken out to be written without holding the lock
+    writes_in_flight: usize,
+}
+
+impl ApprovalSession {
+    fn file_decided(&self, file_index: usize) -> bool {
+        self.segments
+            .iter()
+            .filter(|segment| segment.file_index == file_index)
+            .all(|segment| segment.decision != SegmentDecision::Pending)
+    }
+
+    fn decided_count(&self) -> u32 {
+        self.segments
+            .iter()
+            .filter(|segment| segment.decision != SegmentDecision::Pending)
+            .count() as u32
+    }
+
+    /// Regular files whose segments have all been decided and that were not
+    /// written yet; they are marked written so only one caller writes them
+    fn take_ready_files(&mut self) -> Vec<FileContext> {
+        let mut ready = Vec::new();
+        for file_index in 0..self.files.len() {
+            if self.written[file_index]
+                || self.files[file_index].archive_path.is_some()
+                || !self.file_decided(file_index)
+            {
+                continue;
+            }
+            self.written[file_index] = true;
+            ready.push(self.files[file_index].clone());
+        }
+        if !ready.is_empty() {
+            self.writes_in_flight += 1;
+        }
+        ready
+    }
+
+    fn is_finished(&self) -> bool {
+        self.writes_in_flight == 0 && self.decided_count() as usize == self.segments.len()
+    }
+}
+
+#[derive(Clone)]
+struct FileContext {
+    relative_path: String,
+    mod_install_path: PathBuf,
+    lines: Vec<String>,
+    translated_lines: Vec<Option<String>>,
+    had_trailing_newline: bool,
+    output_relative_path: PathBuf,
+    output_absolute_path: PathBuf,
+    resume_metadata_path: PathBuf,
+    resume_line_index: usize,
+    /// 아카이브 파일 경로 (아카이브 내부 파일인 경우)
+    archive_path: Option<PathBuf>,
+    /// 아카이브 내부 엔트리 경로
+    archive_entry_path: Option<String>,
+    /// 출력 파일 상단에 넣을 출처 주석 (형식이 주석을 지원하는 경우)
+    provenance_header: Option<String>,
+    /// 원본 파일의 인코딩, 줄바꿈 방식, BOM 여부 (출력에 그대로 재현)
+    source_encoding: FileMetadata,
+    output_encoding: OutputEncoding,
+    /// 경로와 내용으로 판별한 파일 형식 (세그먼트 검증에 사용)
+    format: FileFormat,
+    /// 형식 처리기로 항목 단위로 번역하는 파일 (없으면 줄 단위로 번역)
+    document: Option<EntryDocument>,
+}
+
+/// A file translated entry by entry through its format handler. The file
+/// context has one line per entry, holding the entry's source text, and the
+/// translated file is the handler's merge of the translated lines into the
+/// original.
+#[derive(Clone)]
+struct EntryDocument {
+    handler: Arc<dyn FormatHandler>,
+    /// Decoded source the translations are merged into (empty for binary files)
+    original: String,
+    /// Raw source of a binary file, merged with `merge_bytes` instead
+    binary: Option<BinarySource>,
+    /// Extracted entries, in file order
+    entries: Vec<TranslatableEntry>,
+}
+
+#[derive(Clone)]
+struct BinarySource {
+    path: PathBuf,
+    bytes: Vec<u8>,
+    target_lang: String,
+}
+
+impl EntryDocument {
+    /// Reads the entries of `content`, for formats translated entry by entry
+    fn open(format: FileFormat, content: &str) -> Option<Result<Self, FormatError>> {
+        if !format.translates_entries() {
+            return None;
+        }
+        let handler: Arc<dyn FormatHandler> = get_handler(format)?.into();
+        Some(handler.extract(content).map(|entries| Self {
+            handler,
+            original: content.to_string(),
+            binary: None,
+            entries,
+        }))
+    }
+
+    /// Reads the entries of the binary file at `path` with `handler`; the
+    /// merge is written for `target_lang`
+    fn open_binary(
+        handler: Arc<dyn FormatHandler>,
+        path: &Path,
+        bytes: Vec<u8>,
+        target_lang: &str,
+    ) -> Result<Self, FormatError> {
+        let entries = handler.extract_bytes(path, &bytes)?;
+        Ok(Self {
+            handler,
+            original: String::new(),
+            binary: Some(BinarySource {
+                path: path.to_path_buf(),
+                bytes,
+                target_lang: target_lang.to_string(),
+            }),
+            entries,
+        })
+    }
+
+    /// Line of the entry at `index` in the file, when the handler records it
+    fn line_number(&self, index: usize) -> Option<usize> {
+        self.entries.get(index)?.metadata.get("line")?.parse().ok()
+    }
+
+    /// Comment the file keeps for translators next to the entry at `index`
+    fn note(&self, index: usize) -> Option<String> {
+        self.entries.get(index)?.metadata.get("comment").cloned()
+    }
+
+    fn translations(&self, translated_lines: &[Option<String>]) -> TranslationResult {
+        let translated = self
+            .entries
+            .iter()
+            .zip(translated_lines)
+            .filter_map(|(entry, translation)| {
+                Some(formats::TranslatedEntry {
+                    key: entry.key.clone(),
+                    source: entry.source.clone(),
+                    target: translation.clone()?,
+                })
+            })
+            .collect();
+        TranslationResult {
+            translated,
+            failed: Vec::new(),
+        }
+    }
+
+    /// The original with the translated entries merged in
+    fn merge(&self, translated_lines: &[Option<String>]) -> Result<String, FormatError> {
+        self.handler
+            .merge(&self.original, &self.translations(translated_lines))
+    }
+
+    /// Bytes of a binary file with the translated entries merged in, or
+    /// `None` for text files
+    fn merge_bytes(
+        &self,
+        translated_lines: &[Option<String>],
+    ) -> Option<Result<Vec<u8>, FormatError>> {
+        let source = self.binary.as_ref()?;
+        Some(self.handler.merge_bytes(
+            &source.path,
+            &source.bytes,
+            &self.translations(translated_lines),
+            &source.target_lang,
+        ))
+    }
 }
 
-#[derive(Clone)]
-struct FileContext {
-    relative_path: String,
-    #[allow(dead_code)]
-    mod_install_path: PathBuf,
-    lines: Vec<String>,
-    translated_lines: Vec<Option<String>>,
-    had_trailing_newline: bool,
-    output_relative_path: PathBuf,
-    output_absolute_path: PathBuf,
-    resume_metadata_path: PathBuf,
-    resume_line_index: usize,
-    /// 아카이브 파일 경로 (아카이브 내부 파일인 경우)
-    archive_path: Option<PathBuf>,
-    /// 아카이브 내부 엔트리 경로
-    archive_entry_path: Option<String>,
-}
-
 fn compute_backoff_ms(attempt: u32) -> u64 {
     if attempt == 0 {
         return RATE_LIMIT_BASE_BACKOFF_MS;
@@ -409,7 +1576,10 @@ fn compute_retry_plan(error: &TranslationError, attempt: u32) -> RetryPlan {
             used_hint: true,
         }
     } else {
-        let delay = Duration::from_millis(compute_backoff_ms(attempt));
+        let delay = apply_jitter(
+            Duration::from_millis(compute_backoff_ms(attempt)),
+            jitter_sample(),
+        );
         RetryPlan {
             delay,
             reason: format!("Automatic backoff (attempt {attempt})"),
@@ -461,107 +1631,480 @@ fn duration_to_retry_seconds(duration: Duration) -> u32 {
     }
 }
 
-#[tauri::command]
-#[allow(non_snake_case)]
-pub fn start_translation_job(
-    app: AppHandle,
-    jobId: String,
-    provider: String,
-    apiKey: Option<String>,
-    modelId: String,
-    sourceLang: Option<String>,
-    targetLang: Option<String>,
-    files: Vec<TranslationFileInput>,
-    outputOverrideDir: Option<String>,
-    resumeFromCheckpoint: Option<bool>,
-    resetResumeState: Option<bool>,
-) -> Result<(), String> {
-    let mut payload = StartTranslationJobPayload {
-        job_id: jobId,
-        provider,
-        api_key: apiKey,
-        model_id: modelId,
-        files,
-        source_lang: sourceLang,
-        target_lang: targetLang,
-        output_override_dir: outputOverrideDir,
-        resume_from_checkpoint: false,
-        reset_resume_state: false,
-    };
-    payload.resume_from_checkpoint = resumeFromCheckpoint.unwrap_or(false);
-    payload.reset_resume_state = resetResumeState.unwrap_or(false);
-    payload.model_id = payload.model_id.trim().to_string();
-    if payload.files.is_empty() {
-        return Err("번역할 파일을 하나 이상 선택해야 합니다.".into());
+/// Starts a job, or queues it behind the running jobs when no slot is free
+/// (see `set_max_concurrent_jobs`).
+#[cfg(feature = "tauri")]
+#[tauri::command]
+pub fn start_translation_job(
+    app: AppHandle,
+    payload: StartTranslationJobPayload,
+) -> Result<(), String> {
+    queue_translation_job(Arc::new(app), payload, JobPriority::default()).map(|_| ())
+}
+
+/// Queues a job to run once a slot is free (see `set_max_concurrent_jobs`).
+#[cfg(feature = "tauri")]
+#[tauri::command]
+pub fn enqueue_translation_job(
+    app: AppHandle,
+    payload: StartTranslationJobPayload,
+    priority: Option<JobPriority>,
+) -> Result<JobStatusSnapshot, String> {
+    let job_id = payload.job_id.clone();
+    queue_translation_job(Arc::new(app), payload, priority.unwrap_or_default())?;
+    job_status_snapshot(&job_id).ok_or_else(|| format!("작업을 찾을 수 없습니다: {job_id}"))
+}
+
+/// Validates a job, queues it and starts it if a slot is free. Returns its
+/// queue position, or why it failed when it was started right away.
+pub fn queue_translation_job(
+    sink: Arc<dyn ProgressSink>,
+    mut payload: StartTranslationJobPayload,
+    priority: JobPriority,
+) -> Result<usize, String> {
+    ensure_writable("번역 시작")?;
+    payload.model_id = payload.model_id.trim().to_string();
+    if payload.files.is_empty() {
+        return Err("번역할 파일을 하나 이상 선택해야 합니다.".into());
+    }
+    normalize_file_inputs(&mut payload.files)?;
+    payload.prompts.validate()?;
+    let job_id = payload.job_id.clone();
+    if is_job_active(&job_id) {
+        return Err(format!("이미 실행 중인 작업입니다: {job_id}"));
+    }
+
+    let position = {
+        let mut queue = JOB_QUEUE
+            .lock()
+            .map_err(|_| "job queue lock poisoned".to_string())?;
+        queue
+            .enqueue(job_id.clone(), priority, payload)
+            .map_err(|error| error.to_string())?
+    };
+
+    emit_progress(
+        sink.as_ref(),
+        TranslationProgressEventPayload {
+            job_id: job_id.clone(),
+            status: "pending".into(),
+            progress_pct: Some(0.0),
+            cancel_requested: None,
+            log: Some(format!("대기열에 추가되었습니다 ({}번째)", position + 1)),
+            translated_count: None,
+            total_count: None,
+            file_name: None,
+            file_success: None,
+            file_progress_pct: None,
+            file_errors: None,
+            last_written: None,
+            checkpoint: None,
+            usage: None,
+            retry: None,
+        },
+    );
+
+    match dispatch_queued_jobs(&sink)
+        .into_iter()
+        .find(|(failed, _)| *failed == job_id)
+    {
+        Some((_, error)) => Err(error),
+        None => Ok(position),
+    }
+}
+
+/// Moves a waiting job to `position` (zero-based) in the queue.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn reorder_queued_job(jobId: String, position: usize) -> Result<usize, String> {
+    JOB_QUEUE
+        .lock()
+        .map_err(|_| "job queue lock poisoned".to_string())?
+        .move_to(&jobId, position)
+        .map_err(|error| error.to_string())
+}
+
+/// Changes the priority of a waiting job and returns its new queue position.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn set_job_priority(jobId: String, priority: JobPriority) -> Result<usize, String> {
+    JOB_QUEUE
+        .lock()
+        .map_err(|_| "job queue lock poisoned".to_string())?
+        .set_priority(&jobId, priority)
+        .map_err(|error| error.to_string())
+}
+
+/// Sets how many jobs may run at once and starts waiting jobs if slots opened.
+#[cfg(feature = "tauri")]
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn set_max_concurrent_jobs(app: AppHandle, maxJobs: usize) -> Result<usize, String> {
+    set_max_concurrent_jobs_with(Arc::new(app), maxJobs)
+}
+
+/// [`set_max_concurrent_jobs`] reporting the jobs it starts to `sink`
+pub fn set_max_concurrent_jobs_with(
+    sink: Arc<dyn ProgressSink>,
+    max_jobs: usize,
+) -> Result<usize, String> {
+    let applied = JOB_QUEUE
+        .lock()
+        .map_err(|_| "job queue lock poisoned".to_string())?
+        .set_max_concurrent(max_jobs);
+    dispatch_queued_jobs(&sink);
+    Ok(applied)
+}
+
+/// Lists running and waiting jobs first (in queue order), then finished ones
+/// from most to least recently updated.
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn list_jobs() -> Result<Vec<JobStatusSnapshot>, String> {
+    let statuses = JOB_STATUSES
+        .lock()
+        .map_err(|_| "job status lock poisoned".to_string())?
+        .clone();
+    let queue = JOB_QUEUE
+        .lock()
+        .map_err(|_| "job queue lock poisoned".to_string())?;
+    let active: Vec<String> = ACTIVE_JOBS
+        .lock()
+        .map_err(|_| "job registry lock poisoned".to_string())?
+        .keys()
+        .cloned()
+        .collect();
+
+    let mut running = Vec::new();
+    let mut finished = Vec::new();
+    for (job_id, snapshot) in &statuses {
+        if active.contains(job_id) {
+            running.push(snapshot.clone());
+        } else if queue.position(job_id).is_none() {
+            finished.push(snapshot.clone());
+        }
+    }
+    running.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+    finished.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.updated_at));
+
+    let waiting = queue.iter().enumerate().map(|(position, job)| {
+        let mut snapshot = statuses
+            .get(&job.job_id)
+            .cloned()
+            .unwrap_or_else(|| JobStatusSnapshot::new(job.job_id.clone()));
+        snapshot.queue_position = Some(position);
+        snapshot.priority = Some(job.priority);
+        snapshot
+    });
+
+    Ok(running.into_iter().chain(waiting).chain(finished).collect())
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn get_job_status(jobId: String) -> Result<Option<JobStatusSnapshot>, String> {
+    Ok(job_status_snapshot(&jobId))
+}
+
+/// Lists the segments of an approval-mode job that still await a decision.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn get_pending_segments(jobId: String) -> Result<Vec<PendingSegment>, String> {
+    let sessions = APPROVAL_SESSIONS
+        .lock()
+        .map_err(|_| "approval registry lock poisoned".to_string())?;
+    let session = sessions
+        .get(&jobId)
+        .ok_or_else(|| format!("승인 대기 중인 작업이 없습니다: {jobId}"))?;
+    Ok(session
+        .segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| segment.decision == SegmentDecision::Pending)
+        .map(|(segment_id, segment)| PendingSegment {
+            segment_id,
+            file_path: session.files[segment.file_index].relative_path.clone(),
+            line_number: segment.line_number,
+            source: segment.source.clone(),
+            translation: segment.translation.clone(),
+            usage: segment.usage.clone(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "tauri")]
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn approve_segment(app: AppHandle, jobId: String, segmentId: usize) -> Result<(), String> {
+    approve_segment_with(&app, &jobId, segmentId)
+}
+
+/// [`approve_segment`] reporting to `sink`
+pub fn approve_segment_with(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    segment_id: usize,
+) -> Result<(), String> {
+    decide_segment(sink, job_id, segment_id, SegmentDecision::Approved, None)
+}
+
+/// Rejects a translation; the source line is kept in the output.
+#[cfg(feature = "tauri")]
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn reject_segment(app: AppHandle, jobId: String, segmentId: usize) -> Result<(), String> {
+    reject_segment_with(&app, &jobId, segmentId)
+}
+
+/// [`reject_segment`] reporting to `sink`
+pub fn reject_segment_with(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    segment_id: usize,
+) -> Result<(), String> {
+    decide_segment(sink, job_id, segment_id, SegmentDecision::Rejected, None)
+}
+
+/// Replaces a translation with the user's text and approves it.
+#[cfg(feature = "tauri")]
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn edit_segment(
+    app: AppHandle,
+    jobId: String,
+    segmentId: usize,
+    text: String,
+) -> Result<(), String> {
+    edit_segment_with(&app, &jobId, segmentId, text)
+}
+
+/// [`edit_segment`] reporting to `sink`
+pub fn edit_segment_with(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    segment_id: usize,
+    text: String,
+) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("수정한 번역이 비어 있습니다.".into());
+    }
+    decide_segment(
+        sink,
+        job_id,
+        segment_id,
+        SegmentDecision::Approved,
+        Some(text),
+    )
+}
+
+fn decide_segment(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    segment_id: usize,
+    decision: SegmentDecision,
+    edited: Option<String>,
+) -> Result<(), String> {
+    ensure_writable("세그먼트 승인")?;
+    let mut sessions = APPROVAL_SESSIONS
+        .lock()
+        .map_err(|_| "approval registry lock poisoned".to_string())?;
+    let session = sessions
+        .get_mut(job_id)
+        .ok_or_else(|| format!("승인 대기 중인 작업이 없습니다: {job_id}"))?;
+    let segment = session
+        .segments
+        .get_mut(segment_id)
+        .ok_or_else(|| format!("세그먼트를 찾을 수 없습니다: {segment_id}"))?;
+    if segment.decision != SegmentDecision::Pending {
+        return Err(format!("이미 처리된 세그먼트입니다: {segment_id}"));
+    }
+
+    if let Some(text) = edited {
+        segment.translation = text.trim().to_string();
+    }
+    segment.decision = decision;
+    let replacement = (decision == SegmentDecision::Approved).then(|| {
+        format!(
+            "{}{}{}",
+            segment.prefix, segment.translation, segment.suffix
+        )
+    });
+    let (file_index, line_index) = (segment.file_index, segment.line_index);
+    if let Some(line) = session.files[file_index]
+        .translated_lines
+        .get_mut(line_index)
+    {
+        *line = replacement;
+    }
+
+    let decided = session.decided_count();
+    let total = session.segments.len() as u32;
+    emit_progress(
+        sink,
+        TranslationProgressEventPayload {
+            job_id: job_id.to_string(),
+            status: "awaiting_approval".into(),
+            progress_pct: Some(percentage(decided, total)),
+            cancel_requested: None,
+            log: None,
+            translated_count: Some(decided),
+            total_count: Some(total),
+            file_name: Some(session.files[file_index].relative_path.clone()),
+            file_success: None,
+            file_progress_pct: None,
+            file_errors: None,
+            last_written: None,
+            checkpoint: None,
+            usage: None,
+            retry: None,
+        },
+    );
+
+    drop(sessions);
+
+    flush_approved_files(sink, job_id);
+    Ok(())
+}
+
+fn job_status_snapshot(job_id: &str) -> Option<JobStatusSnapshot> {
+    let mut snapshot = JOB_STATUSES.lock().ok()?.get(job_id).cloned();
+    if let Ok(queue) = JOB_QUEUE.lock() {
+        if let Some(position) = queue.position(job_id) {
+            let queued = snapshot.get_or_insert_with(|| JobStatusSnapshot::new(job_id.to_string()));
+            queued.queue_position = Some(position);
+            queued.priority = queue.get(job_id).map(|job| job.priority);
+        }
+    }
+    snapshot
+}
+
+fn is_job_active(job_id: &str) -> bool {
+    ACTIVE_JOBS
+        .lock()
+        .map(|guard| guard.contains_key(job_id))
+        .unwrap_or(false)
+}
+
+/// Stops every running job so it saves its checkpoint and waits up to
+/// `timeout` for them to return. Called when the app exits; returns the ids
+/// of jobs still running when the time is up.
+pub async fn shutdown_jobs(timeout: Duration) -> Vec<String> {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    let running: Vec<(String, Arc<AtomicBool>)> = ACTIVE_JOBS
+        .lock()
+        .map(|guard| {
+            guard
+                .iter()
+                .map(|(job_id, flag)| (job_id.clone(), flag.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if running.is_empty() {
+        return Vec::new();
+    }
+    info!("stopping {} running job(s) before exit", running.len());
+    for (job_id, flag) in &running {
+        flag.store(true, Ordering::SeqCst);
+        let controller = JOB_BACKOFFS
+            .lock()
+            .ok()
+            .and_then(|guard| guard.get(job_id).cloned());
+        if let Some(controller) = controller {
+            controller.cancel_logic();
+        }
+    }
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining: Vec<String> = ACTIVE_JOBS
+            .lock()
+            .map(|guard| guard.keys().cloned().collect())
+            .unwrap_or_default();
+        if remaining.is_empty() || Instant::now() >= deadline {
+            return remaining;
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Starts waiting jobs while there are free slots. The queue lock is held
+/// while launching so concurrent callers cannot overfill the slots. Jobs
+/// that fail to start are reported as failed and returned with the reason.
+fn dispatch_queued_jobs(sink: &Arc<dyn ProgressSink>) -> Vec<(String, String)> {
+    let mut failed = Vec::new();
+    if SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return failed;
     }
-
-    let provider = match ProviderId::try_from(payload.provider.as_str()) {
-        Ok(provider) => provider,
-        Err(_) => {
+    loop {
+        // Only the pop happens under the queue lock; the launch reads the
+        // stored key and settings, which must not block queue commands
+        let job = {
+            let Ok(mut queue) = JOB_QUEUE.lock() else {
+                warn!("job queue lock poisoned; skipping dispatch");
+                return failed;
+            };
+            let running = ACTIVE_JOBS.lock().map(|guard| guard.len()).unwrap_or(0)
+                + LAUNCHING_JOBS.load(Ordering::SeqCst);
+            let Some(job) = queue.pop_ready(running) else {
+                return failed;
+            };
+            LAUNCHING_JOBS.fetch_add(1, Ordering::SeqCst);
+            job
+        };
+        if is_job_active(&job.job_id) {
+            LAUNCHING_JOBS.fetch_sub(1, Ordering::SeqCst);
+            // Reporting it as failed would mark the running job failed
+            warn!("dropping queued job {}: already running", job.job_id);
+            continue;
+        }
+        let launched = launch_translation_job(sink.clone(), job.payload);
+        LAUNCHING_JOBS.fetch_sub(1, Ordering::SeqCst);
+        if let Err(error) = launched {
+            warn!("failed to start queued job {}: {}", job.job_id, error);
             emit_progress(
-                &app,
+                sink.as_ref(),
                 TranslationProgressEventPayload {
-                    job_id: payload.job_id.clone(),
+                    job_id: job.job_id.clone(),
                     status: "failed".into(),
                     progress_pct: Some(0.0),
                     cancel_requested: None,
-                    log: Some(format!("지원하지 않는 번역기: {}", payload.provider)),
+                    log: Some(error.clone()),
                     translated_count: Some(0),
                     total_count: Some(0),
                     file_name: None,
                     file_success: None,
+                    file_progress_pct: None,
                     file_errors: None,
                     last_written: None,
                     checkpoint: None,
+                    usage: None,
                     retry: None,
                 },
             );
-            return Err(format!("지원하지 않는 번역기: {}", payload.provider));
+            failed.push((job.job_id, error));
         }
-    };
+    }
+}
+
+/// Validates and starts a job now; callers go through the queue
+/// (`queue_translation_job`). Its progress, and that of the queued jobs
+/// started when it ends, goes to `sink`.
+fn launch_translation_job(
+    sink: Arc<dyn ProgressSink>,
+    payload: StartTranslationJobPayload,
+) -> Result<(), String> {
+    // Read-only mode may have been turned on while the job waited
+    ensure_writable("번역 시작")?;
+
+    let provider = ProviderId::try_from(payload.provider.as_str())
+        .map_err(|_| format!("지원하지 않는 번역기: {}", payload.provider))?;
 
-    let api_key = payload.api_key.clone().unwrap_or_default();
+    // Keys never travel with the job; the provider's stored key is used
+    let api_key = stored_api_key(provider).unwrap_or_default();
     if api_key.trim().is_empty() {
-        emit_progress(
-            &app,
-            TranslationProgressEventPayload {
-                job_id: payload.job_id.clone(),
-                status: "failed".into(),
-                progress_pct: Some(0.0),
-                cancel_requested: None,
-                log: Some("API 키가 설정되지 않았습니다.".into()),
-                translated_count: Some(0),
-                total_count: Some(0),
-                file_name: None,
-                file_success: None,
-                file_errors: None,
-                last_written: None,
-                checkpoint: None,
-                retry: None,
-            },
-        );
         return Err("선택한 번역기의 API 키를 설정해 주세요.".into());
     }
 
     if payload.model_id.is_empty() {
-        emit_progress(
-            &app,
-            TranslationProgressEventPayload {
-                job_id: payload.job_id.clone(),
-                status: "failed".into(),
-                progress_pct: Some(0.0),
-                cancel_requested: None,
-                log: Some("번역 모델이 선택되지 않았습니다.".into()),
-                translated_count: Some(0),
-                total_count: Some(0),
-                file_name: None,
-                file_success: None,
-                file_errors: None,
-                last_written: None,
-                checkpoint: None,
-                retry: None,
-            },
-        );
         return Err("번역에 사용할 모델을 선택해 주세요.".into());
     }
 
@@ -572,6 +2115,9 @@ pub fn start_translation_job(
         let mut guard = ACTIVE_JOBS
             .lock()
             .map_err(|_| "job registry lock poisoned".to_string())?;
+        if guard.contains_key(&payload.job_id) {
+            return Err(format!("이미 실행 중인 작업입니다: {}", payload.job_id));
+        }
         guard.insert(payload.job_id.clone(), cancel_flag.clone());
     }
 
@@ -585,21 +2131,40 @@ pub fn start_translation_job(
         guard.insert(payload.job_id.clone(), backoff_controller.clone());
     }
 
+    record_job_usage(|| job_usage_event(provider, &payload));
+
     let job_id = payload.job_id.clone();
-    let app_handle = app.clone();
-    tauri::async_runtime::spawn({
+    let task_sink = sink.clone();
+    spawn_job({
         let cancel_flag = cancel_flag.clone();
         let backoff_controller = backoff_controller.clone();
         async move {
-            run_translation_job(
-                app_handle.clone(),
-                payload,
-                provider,
-                api_key.trim().to_string(),
-                cancel_flag,
-                backoff_controller.clone(),
-            )
-            .await;
+            let mut lookups = SourceLookups::default();
+            let languages = payload.target_languages();
+            if languages.len() > 1 {
+                run_multi_language_job(
+                    task_sink.as_ref(),
+                    &payload,
+                    &languages,
+                    provider,
+                    api_key.trim(),
+                    &cancel_flag,
+                    &backoff_controller,
+                    &mut lookups,
+                )
+                .await;
+            } else {
+                run_translation_job(
+                    task_sink.as_ref(),
+                    payload,
+                    provider,
+                    api_key.trim().to_string(),
+                    cancel_flag,
+                    backoff_controller.clone(),
+                    &mut lookups,
+                )
+                .await;
+            }
 
             if let Ok(mut guard) = ACTIVE_JOBS.lock() {
                 guard.remove(&job_id);
@@ -607,37 +2172,370 @@ pub fn start_translation_job(
             if let Ok(mut guard) = JOB_BACKOFFS.lock() {
                 guard.remove(&job_id);
             }
+            dispatch_queued_jobs(&task_sink);
         }
     });
 
     Ok(())
 }
 
+/// Runs a job on the caller's Tokio runtime, or on the shared job runtime
+/// when launched from outside one (synchronous desktop commands)
+fn spawn_job<F>(job: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    static JOB_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the job runtime")
+    });
+    if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::spawn(job);
+    } else {
+        JOB_RUNTIME.spawn(job);
+    }
+}
+
+/// Runs a multi-language job as one job per language (`{job_id}-{language}`),
+/// one after another, sharing the source lookups. The language jobs report
+/// their own progress and outputs; the job itself reports which of them ran
+/// and how they ended.
+#[allow(clippy::too_many_arguments)]
+async fn run_multi_language_job(
+    sink: &dyn ProgressSink,
+    payload: &StartTranslationJobPayload,
+    languages: &[String],
+    provider: ProviderId,
+    api_key: &str,
+    cancel_flag: &Arc<AtomicBool>,
+    backoff_controller: &Arc<BackoffController>,
+    lookups: &mut SourceLookups,
+) {
+    let total = languages.len() as u32;
+    // (language, final status of its job)
+    let mut outcomes: Vec<(&str, String)> = Vec::new();
+    for (index, language) in languages.iter().enumerate() {
+        if cancel_flag.load(Ordering::SeqCst) {
+            break;
+        }
+        let language_payload = payload.for_target_language(language);
+        let language_job_id = language_payload.job_id.clone();
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(percentage(index as u32, total)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "{language} 번역을 시작합니다 ({}/{total}, 작업 {language_job_id})",
+                    index + 1
+                )),
+                translated_count: Some(index as u32),
+                total_count: Some(total),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: None,
+                last_written: None,
+                checkpoint: None,
+                usage: None,
+                retry: None,
+            },
+        );
+
+        // Cancelling either the job or a language job stops the rest
+        if let Ok(mut guard) = ACTIVE_JOBS.lock() {
+            guard.insert(language_job_id.clone(), cancel_flag.clone());
+        }
+        if let Ok(mut guard) = JOB_BACKOFFS.lock() {
+            guard.insert(language_job_id.clone(), backoff_controller.clone());
+        }
+        run_translation_job(
+            sink,
+            language_payload,
+            provider,
+            api_key.to_string(),
+            cancel_flag.clone(),
+            backoff_controller.clone(),
+            lookups,
+        )
+        .await;
+        if let Ok(mut guard) = ACTIVE_JOBS.lock() {
+            guard.remove(&language_job_id);
+        }
+        if let Ok(mut guard) = JOB_BACKOFFS.lock() {
+            guard.remove(&language_job_id);
+        }
+
+        let status = job_status_snapshot(&language_job_id)
+            .map_or_else(|| "failed".to_string(), |snapshot| snapshot.status);
+        outcomes.push((language, status));
+    }
+
+    let canceled = cancel_flag.load(Ordering::SeqCst);
+    let completed = outcomes
+        .iter()
+        .filter(|(_, status)| status == "completed")
+        .count();
+    let status = if canceled {
+        "canceled"
+    } else if completed == languages.len() {
+        "completed"
+    } else if completed > 0 {
+        "partial_success"
+    } else {
+        "failed"
+    };
+    emit_progress(
+        sink,
+        TranslationProgressEventPayload {
+            job_id: payload.job_id.clone(),
+            status: status.into(),
+            progress_pct: Some(percentage(outcomes.len() as u32, total)),
+            cancel_requested: canceled.then_some(true),
+            log: Some(format!(
+                "언어별 번역 결과: {}",
+                outcomes
+                    .iter()
+                    .map(|(language, status)| format!("{language} {status}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            translated_count: Some(outcomes.len() as u32),
+            total_count: Some(total),
+            file_name: None,
+            file_success: None,
+            file_progress_pct: None,
+            file_errors: None,
+            last_written: None,
+            checkpoint: None,
+            usage: None,
+            retry: None,
+        },
+    );
+}
+
+/// What a recoverable job's user can do about it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryOption {
+    /// Start the job again with `resumeFromCheckpoint`
+    Resume,
+    /// Undo the outputs it wrote with `rollback_translation_job`
+    Rollback,
+    /// Forget the saved progress with `discard_recoverable_job`
+    Discard,
+}
+
+/// State of an output a crashed job had written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveredOutputState {
+    /// Matches the checksum taken right after the write
+    Intact,
+    /// Changed since; a write may have been cut short or the file was edited
+    Modified,
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredOutput {
+    pub relative_path: String,
+    pub absolute_path: String,
+    pub state: RecoveredOutputState,
+}
+
+/// A job that was running when the app last exited without finishing it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoverableJob {
+    pub job_id: String,
+    pub checkpoint: TranslationCheckpoint,
+    pub usage: TranslationUsage,
+    /// 마지막으로 체크포인트를 저장한 시각
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_at: Option<DateTime<Utc>>,
+    /// 번역 결과가 체크포인트에만 있는 원문 파일 수
+    pub files_in_progress: usize,
+    /// 작업이 이미 쓴 출력 파일과 그 상태
+    pub outputs: Vec<RecoveredOutput>,
+    pub options: Vec<RecoveryOption>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecoverableJobsEventPayload {
+    jobs: Vec<RecoverableJob>,
+}
+
+fn recoverable_job(
+    job_id: String,
+    state: JobState,
+    saved_at: Option<DateTime<Utc>>,
+    outputs: Vec<JobOutput>,
+) -> Option<RecoverableJob> {
+    if state.ended_with.is_some() {
+        return None;
+    }
+    let outputs: Vec<RecoveredOutput> = outputs
+        .into_iter()
+        .map(|output| {
+            let state = match file_checksum(&output.absolute_path) {
+                None => RecoveredOutputState::Missing,
+                Some(checksum) if Some(&checksum) == output.sha256.as_ref() => {
+                    RecoveredOutputState::Intact
+                }
+                Some(_) => RecoveredOutputState::Modified,
+            };
+            RecoveredOutput {
+                relative_path: output.relative_path,
+                absolute_path: output.absolute_path.to_string_lossy().to_string(),
+                state,
+            }
+        })
+        .collect();
+    let mut options = vec![RecoveryOption::Resume];
+    if outputs
+        .iter()
+        .any(|output| output.state != RecoveredOutputState::Missing)
+    {
+        options.push(RecoveryOption::Rollback);
+    }
+    options.push(RecoveryOption::Discard);
+    Some(RecoverableJob {
+        job_id,
+        files_in_progress: state
+            .files
+            .values()
+            .filter(|progress| !progress.replacements.is_empty())
+            .count(),
+        checkpoint: state.checkpoint,
+        usage: state.usage,
+        saved_at,
+        outputs,
+        options,
+    })
+}
+
+/// Jobs whose persisted state has no final status: they were running when
+/// the app crashed or was killed
+pub fn recoverable_jobs() -> Vec<RecoverableJob> {
+    recoverable_jobs_in(&job_state_dir())
+}
+
+fn recoverable_jobs_in(dir: &Path) -> Vec<RecoverableJob> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut jobs: Vec<RecoverableJob> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let state = read_job_state_file(&path)?;
+            // States saved before the id was stored only have the file name
+            let job_id = if state.job_id.is_empty() {
+                path.file_stem()?.to_string_lossy().to_string()
+            } else {
+                state.job_id.clone()
+            };
+            if is_job_active(&job_id) {
+                return None;
+            }
+            let saved_at = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(DateTime::<Utc>::from);
+            let outputs = job_outputs(&job_id);
+            recoverable_job(job_id, state, saved_at, outputs)
+        })
+        .collect();
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.saved_at));
+    jobs
+}
+
+/// Looks for jobs a crash interrupted and reports them with the
+/// `recoverable-jobs` event. Called once when the app starts.
+pub fn emit_recoverable_jobs(sink: &dyn ProgressSink) {
+    let jobs = recoverable_jobs();
+    if jobs.is_empty() {
+        return;
+    }
+    info!("found {} job(s) interrupted by the last exit", jobs.len());
+    sink.emit("recoverable-jobs", RecoverableJobsEventPayload { jobs });
+}
+
+/// Tauri command listing jobs a crash interrupted, as sent with the
+/// `recoverable-jobs` event at startup
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn list_recoverable_jobs() -> Vec<RecoverableJob> {
+    recoverable_jobs()
+}
+
+/// Drops the saved progress of an interrupted job. Its outputs stay; roll
+/// them back first to remove them.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn discard_recoverable_job(jobId: String) -> Result<(), String> {
+    if is_job_active(&jobId) {
+        return Err(format!("실행 중인 작업입니다: {jobId}"));
+    }
+    clear_job_state(&jobId);
+    Ok(())
+}
+/// Undoes everything a finished job wrote: replaced files and archives are
+/// restored from their backups and created files are removed. `force` also
+/// reverts files edited after the job.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn rollback_translation_job(
+    jobId: String,
+    force: Option<bool>,
+) -> Result<RollbackReport, String> {
+    ensure_writable("번역 작업 되돌리기")?;
+    if is_job_active(&jobId) {
+        return Err(format!("실행 중인 작업은 되돌릴 수 없습니다: {jobId}"));
+    }
+    rollback_job_outputs(&jobId, force.unwrap_or(false))
+}
+
+#[cfg(feature = "tauri")]
 #[tauri::command]
 #[allow(non_snake_case)]
 pub fn cancel_translation_job(app: AppHandle, jobId: String) -> Result<(), String> {
+    cancel_job(&app, jobId)
+}
+
+/// Cancels a running job, or drops a queued or waiting one.
+pub fn cancel_job(sink: &dyn ProgressSink, job_id: String) -> Result<(), String> {
     let guard = ACTIVE_JOBS
         .lock()
         .map_err(|_| "job registry lock poisoned".to_string())?;
 
-    if let Some(flag) = guard.get(&jobId) {
+    if let Some(flag) = guard.get(&job_id) {
         flag.store(true, Ordering::SeqCst);
         drop(guard);
 
         if let Some(controller) = JOB_BACKOFFS
             .lock()
             .map_err(|_| "backoff registry lock poisoned".to_string())?
-            .get(&jobId)
+            .get(&job_id)
             .cloned()
         {
             controller.cancel_logic();
         }
 
-        let checkpoint = current_checkpoint(&jobId);
+        let checkpoint = current_checkpoint(&job_id);
+        let usage = current_usage(&job_id);
         emit_progress(
-            &app,
+            sink,
             TranslationProgressEventPayload {
-                job_id: jobId,
+                job_id,
                 status: "running".into(),
                 progress_pct: None,
                 cancel_requested: Some(true),
@@ -646,9 +2544,11 @@ pub fn cancel_translation_job(app: AppHandle, jobId: String) -> Result<(), Strin
                 total_count: None,
                 file_name: None,
                 file_success: None,
+                file_progress_pct: None,
                 file_errors: None,
                 last_written: None,
                 checkpoint,
+                usage,
                 retry: None,
             },
         );
@@ -657,20 +2557,28 @@ pub fn cancel_translation_job(app: AppHandle, jobId: String) -> Result<(), Strin
     } else {
         drop(guard);
 
+        if let Ok(mut queue) = JOB_QUEUE.lock() {
+            queue.remove(&job_id);
+        }
+        if let Ok(mut sessions) = APPROVAL_SESSIONS.lock() {
+            sessions.remove(&job_id);
+        }
+
         if let Some(controller) = JOB_BACKOFFS
             .lock()
             .map_err(|_| "backoff registry lock poisoned".to_string())?
-            .get(&jobId)
+            .get(&job_id)
             .cloned()
         {
             controller.cancel_logic();
         }
 
-        let checkpoint = current_checkpoint(&jobId);
+        let checkpoint = current_checkpoint(&job_id);
+        let usage = current_usage(&job_id);
         emit_progress(
-            &app,
+            sink,
             TranslationProgressEventPayload {
-                job_id: jobId,
+                job_id,
                 status: "canceled".into(),
                 progress_pct: Some(0.0),
                 cancel_requested: Some(true),
@@ -679,9 +2587,11 @@ pub fn cancel_translation_job(app: AppHandle, jobId: String) -> Result<(), Strin
                 total_count: Some(0),
                 file_name: None,
                 file_success: None,
+                file_progress_pct: None,
                 file_errors: None,
                 last_written: None,
                 checkpoint,
+                usage,
                 retry: None,
             },
         );
@@ -690,7 +2600,7 @@ pub fn cancel_translation_job(app: AppHandle, jobId: String) -> Result<(), Strin
     }
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub fn open_output_folder(path: String) -> Result<(), String> {
     let path_buf = PathBuf::from(&path);
     if !path_buf.exists() {
@@ -700,7 +2610,7 @@ pub fn open_output_folder(path: String) -> Result<(), String> {
     open::that_detached(path_buf).map_err(|error| format!("폴더를 열 수 없습니다: {error}"))
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 #[allow(non_snake_case)]
 pub fn retry_translation_now(jobId: String) -> Result<(), String> {
     let controller = JOB_BACKOFFS
@@ -713,51 +2623,398 @@ pub fn retry_translation_now(jobId: String) -> Result<(), String> {
         controller.cancel_manual();
     }
 
-    Ok(())
-}
-
-async fn run_translation_job(
-    app: AppHandle,
-    payload: StartTranslationJobPayload,
-    provider: ProviderId,
-    api_key: String,
-    cancel_flag: Arc<AtomicBool>,
-    backoff_controller: Arc<BackoffController>,
-) {
-    let source_lang = payload.source_lang.as_deref().unwrap_or("auto").to_string();
-    let target_lang = payload.target_lang.as_deref().unwrap_or("ko").to_string();
-    let override_root = payload
-        .output_override_dir
-        .as_ref()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
-        .map(PathBuf::from);
-    let _resume_from_checkpoint = payload.resume_from_checkpoint;
-    let _reset_resume_state = payload.reset_resume_state;
+    Ok(())
+}
+
+/// Glossary terms for one mod: the game profile's terminology (written for
+/// Korean output) overlaid with the user's glossary from the payload.
+fn glossary_terms(
+    profile: Option<&GameProfile>,
+    target_lang: &str,
+    user_glossary: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut terms = HashMap::new();
+    if target_lang.to_ascii_lowercase().starts_with("ko") {
+        if let Some(profile) = profile {
+            terms.extend(profile.terminology.clone());
+        }
+    }
+    terms.extend(user_glossary.clone());
+    terms
+}
+
+/// Job settings with the prompt variables of one mod and its game profile's
+/// prompt overrides
+fn mod_translation_settings(
+    base: &TranslationSettings,
+    profile: Option<&GameProfile>,
+    glossary: &HashMap<String, String>,
+) -> TranslationSettings {
+    let mut settings = base.clone();
+    settings.prompt_vars.glossary = PromptVars::glossary_lines(glossary);
+    if let Some(profile) = profile {
+        let prompt_config = &profile.prompt_config;
+        settings.prompt = settings.prompt.overlay(&prompt_config.template());
+        settings.prompt_vars.game = Some(profile.name.clone());
+        settings.prompt_vars.game_context = prompt_config.game_context.clone();
+        if prompt_config.tone.is_some() {
+            settings.prompt_vars.tone = prompt_config.tone.clone();
+        }
+    }
+    settings
+}
+
+fn job_usage_event(provider: ProviderId, payload: &StartTranslationJobPayload) -> JobUsageEvent {
+    let formats = payload
+        .files
+        .iter()
+        .filter_map(|file| {
+            let path = file
+                .archive_entry_path
+                .as_deref()
+                .unwrap_or(&file.relative_path);
+            Path::new(path)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_string())
+        })
+        .collect();
+
+    let mut mod_roots: Vec<&str> = payload
+        .files
+        .iter()
+        .map(|file| file.mod_install_path.as_str())
+        .collect();
+    mod_roots.sort_unstable();
+    mod_roots.dedup();
+    let games = mod_roots
+        .into_iter()
+        .map(|root| {
+            GameProfile::resolve(Path::new(root))
+                .map(|profile| profile.id)
+                .unwrap_or_else(|| "unknown".to_string())
+        })
+        .collect();
+
+    let flags = [
+        (payload.resume_from_checkpoint, "resume"),
+        (payload.strict_mode, "strict_mode"),
+        (payload.export_review, "export_review"),
+        (payload.embed_provenance, "provenance"),
+        (
+            payload.max_cost_usd.is_some() || payload.max_tokens.is_some(),
+            "budget",
+        ),
+        (
+            payload.model_params != ModelParams::default(),
+            "model_params",
+        ),
+        (payload.output_override_dir.is_some(), "output_override"),
+        (payload.context_mode != ContextMode::Off, "context"),
+        (payload.target_tone.is_some(), "target_tone"),
+        (payload.consistency_pass.is_some(), "consistency_pass"),
+        (!payload.rate_limit.is_unlimited(), "rate_limit"),
+        (
+            payload.request_options != RequestOptions::default(),
+            "request_options",
+        ),
+        (!payload.model_fallbacks.is_empty(), "model_fallbacks"),
+        (
+            payload.validator_config != ValidatorConfig::default(),
+            "validator_config",
+        ),
+        (payload.quality_estimation.enabled, "quality_estimation"),
+        (
+            payload.output_filter != OutputFilterConfig::default(),
+            "output_filter",
+        ),
+        (payload.skip_already_translated, "skip_already_translated"),
+        (payload.target_languages().len() > 1, "multi_language"),
+        (payload.bidi_marks != BidiMarks::default(), "bidi_marks"),
+        (
+            !payload.include_globs.is_empty() || !payload.exclude_globs.is_empty(),
+            "file_globs",
+        ),
+        (!payload.skip_list.is_empty(), "skip_list"),
+        (
+            payload
+                .files
+                .iter()
+                .any(TranslationFileInput::is_archive_entry),
+            "archive",
+        ),
+    ];
+
+    JobUsageEvent {
+        provider: provider.label().to_string(),
+        formats,
+        games,
+        features: flags
+            .into_iter()
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect(),
+    }
+}
+
+/// Runs `payload` to the end on the current task with `api_key`, skipping the
+/// queue and the stored key lookup. Progress and results go to `sink`; this
+/// is how integration tests drive whole jobs against a mock provider.
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub async fn run_translation_job_with(
+    sink: &dyn ProgressSink,
+    payload: StartTranslationJobPayload,
+    api_key: String,
+) -> Result<(), String> {
+    let provider = ProviderId::try_from(payload.provider.as_str())
+        .map_err(|_| format!("지원하지 않는 번역기: {}", payload.provider))?;
+    run_translation_job(
+        sink,
+        payload,
+        provider,
+        api_key,
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(BackoffController::new()),
+        &mut SourceLookups::default(),
+    )
+    .await;
+    Ok(())
+}
+
+async fn run_translation_job(
+    sink: &dyn ProgressSink,
+    payload: StartTranslationJobPayload,
+    provider: ProviderId,
+    api_key: String,
+    cancel_flag: Arc<AtomicBool>,
+    backoff_controller: Arc<BackoffController>,
+    lookups: &mut SourceLookups,
+) {
+    let started_at = Utc::now();
+    let source_lang = payload.source_lang.as_deref().unwrap_or("auto").to_string();
+    let target_lang = payload
+        .target_languages()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "ko".to_string());
+    let override_root = payload
+        .output_override_dir
+        .as_ref()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let budget = JobBudget::from_payload(&payload);
+    let context_neighbors = payload
+        .context_neighbors
+        .unwrap_or(DEFAULT_CONTEXT_NEIGHBORS)
+        .min(MAX_CONTEXT_NEIGHBORS);
+    // The job's target tone wins over the configured and the profiles' tones
+    let tone_instruction = payload
+        .target_tone
+        .map(|tone| tone.prompt_instruction(&target_lang));
+    let mut settings = TranslationSettings {
+        provider,
+        api_key: api_key.clone(),
+        model_id: payload.model_id.clone(),
+        source_lang: source_lang.clone(),
+        target_lang: target_lang.clone(),
+        params: payload.model_params.clone(),
+        prompt: payload.prompts.template_for(provider),
+        prompt_vars: PromptVars {
+            tone: tone_instruction
+                .clone()
+                .or_else(|| payload.prompts.tone.clone()),
+            ..PromptVars::default()
+        },
+        rate_limit: payload.rate_limit,
+        base_url: payload.api_base_url.clone(),
+    };
+
+    let mut tried_models = vec![settings.model_id.clone()];
+    let preflight = loop {
+        let result = preflight_check(&settings, &payload.request_options).await;
+        let Err(TranslationError::ModelNotFound { model_id, .. }) = &result else {
+            break result;
+        };
+        let Some(fallback) = next_fallback_model(
+            &payload,
+            &settings.api_key,
+            provider,
+            model_id,
+            &mut tried_models,
+        ) else {
+            break result;
+        };
+        emit_model_fallback(sink, &payload.job_id, provider, model_id, &fallback);
+        settings.model_id = fallback;
+    };
+    if let Err(error) = preflight {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "failed".into(),
+                progress_pct: Some(0.0),
+                cancel_requested: None,
+                log: Some(format_preflight_error(&error)),
+                translated_count: Some(0),
+                total_count: Some(0),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: None,
+                last_written: None,
+                checkpoint: None,
+                usage: None,
+                retry: None,
+            },
+        );
+        return;
+    }
+
+    let provenance = payload.embed_provenance.then(|| ProvenanceInfo {
+        provider: provider.label().to_string(),
+        model_id: settings.model_id.clone(),
+        source_lang: source_lang.clone(),
+        target_lang: target_lang.clone(),
+        generated_at: Utc::now(),
+    });
+    let mut model_pricing = pricing_for(provider, &settings.model_id);
 
     let mut file_contexts: Vec<FileContext> = Vec::new();
     let mut segments: Vec<Segment> = Vec::new();
     let mut file_errors: Vec<TranslationFileErrorEntry> = Vec::new();
-    let qc_limits = SegmentLimits::default();
+    let qc_limits = SegmentLimits::for_target(&target_lang);
     let mut rolled_back_segments: Vec<String> = Vec::new();
-    let mut job_state = load_job_state(&payload.job_id).unwrap_or_else(JobState::new);
+    let mut review_segments: Vec<ReviewSegment> = Vec::new();
+    let mut segment_reviews: HashMap<(usize, usize), SegmentReviewNote> = HashMap::new();
+    let mut validation_failures: Vec<ValidationFailureReport> = Vec::new();
+    if payload.reset_resume_state {
+        clear_job_state(&payload.job_id);
+    }
+    let mut job_state = load_job_state(&payload.job_id, payload.resume_from_checkpoint)
+        .unwrap_or_else(JobState::new);
     let mut changed_files: Vec<String> = Vec::new();
     let mut already_processed_segments: u32 = 0;
 
+    let SourceLookups {
+        profiles,
+        placeholder_patterns,
+        scripts,
+        key_usages,
+        fragments,
+    } = lookups;
+    let mut glossaries: HashMap<PathBuf, GlossaryApplier> = HashMap::new();
+    let mut mod_settings: HashMap<PathBuf, TranslationSettings> = HashMap::new();
+    // The job's do-not-translate list merged with each mod's profile list
+    let mut skip_matchers: HashMap<PathBuf, Arc<SkipMatcher>> = HashMap::new();
+    // Segments on a do-not-translate list or under an `mt:skip`/`mt:lock`
+    // directive, with the review note saying why
+    let mut do_not_translate: Vec<(Segment, String)> = Vec::new();
+    // Mod glossary plus the file's `mt:glossary` terms, for files that have any
+    let mut file_glossaries: HashMap<usize, GlossaryApplier> = HashMap::new();
+    // Settings of each entry of `file_contexts`, with the mod's prompt overrides
+    let mut file_settings: Vec<TranslationSettings> = Vec::new();
+    let source_paths: HashSet<PathBuf> = payload
+        .files
+        .iter()
+        .filter(|file| !file.is_archive_entry())
+        .map(|file| canonical_or_self(&Path::new(&file.mod_install_path).join(&file.relative_path)))
+        .collect();
+    // (source, output path, diverted to the mirror folder)
+    let mut source_collisions: Vec<(String, PathBuf, bool)> = Vec::new();
+    // (source, profile name) for files outside the profile's translation paths
+    let mut skipped_by_profile: Vec<(String, String)> = Vec::new();
+    // Profile scripts that could not be loaded; their mods run without hooks
+    let mut script_errors: Vec<String> = Vec::new();
+    // Source language detected for files of an `auto` job -> file count
+    let mut detected_languages: BTreeMap<String, usize> = BTreeMap::new();
+
     for file in &payload.files {
+        if payload.glob_skip(&file.relative_path).is_some() {
+            continue;
+        }
         let relative_path = PathBuf::from(&file.relative_path);
         let mod_root_raw = PathBuf::from(&file.mod_install_path);
         let mod_root = mod_root_raw.canonicalize().unwrap_or(mod_root_raw.clone());
+        let profile = profiles
+            .entry(mod_root.clone())
+            .or_insert_with(|| GameProfile::resolve(&mod_root));
+        let profile_path = file
+            .archive_entry_path
+            .as_deref()
+            .unwrap_or(&file.relative_path);
+        if let Some(profile) = profile.as_ref() {
+            if !profile.accepts_path(profile_path) {
+                skipped_by_profile.push((file.relative_path.clone(), profile.name.clone()));
+                continue;
+            }
+        }
+        let skip_matcher = skip_matchers
+            .entry(mod_root.clone())
+            .or_insert_with(|| {
+                let list = match profile.as_ref() {
+                    Some(profile) => payload.skip_list.merged(&profile.skip_list),
+                    None => payload.skip_list.clone(),
+                };
+                Arc::new(list.matcher())
+            })
+            .clone();
+        let terms = glossary_terms(profile.as_ref(), &target_lang, &payload.glossary);
+        glossaries
+            .entry(mod_root.clone())
+            .or_insert_with(|| GlossaryApplier::new(&target_lang, &terms));
+        let mut file_setting = mod_settings
+            .entry(mod_root.clone())
+            .or_insert_with(|| {
+                let mut for_mod = mod_translation_settings(&settings, profile.as_ref(), &terms);
+                if tone_instruction.is_some() {
+                    for_mod.prompt_vars.tone = tone_instruction.clone();
+                }
+                for_mod
+            })
+            .clone();
+        let patterns = placeholder_patterns
+            .entry(mod_root.clone())
+            .or_insert_with(|| {
+                Arc::new(
+                    profile
+                        .as_ref()
+                        .map(GameProfile::placeholder_patterns)
+                        .unwrap_or_default(),
+                )
+            })
+            .clone();
+        let script = scripts
+            .entry(mod_root.clone())
+            .or_insert_with(|| match profile.as_ref().and_then(load_profile_script)? {
+                Ok(script) => Some(Arc::new(script)),
+                Err(error) => {
+                    script_errors.push(error);
+                    None
+                }
+            })
+            .clone();
+        key_usages
+            .entry(mod_root.clone())
+            .or_insert_with(|| KeyUsageIndex::build(&mod_root));
         
         // 아카이브 내부 파일인지 확인
-        let (content, archive_path, archive_entry_path, source_file_path) = if file.is_archive_entry() {
+        let format_by_path = FileFormat::from_path(Path::new(&file.relative_path));
+        // Installed extractor plugins come before the built-in handlers
+        let plugin = (!file.is_archive_entry())
+            .then(|| plugin_for_path(Path::new(&file.relative_path)))
+            .flatten();
+        let mut binary_source: Option<Vec<u8>> = None;
+        let (content, source_encoding, archive_path, archive_entry_path, source_file_path) = if file
+            .is_archive_entry()
+        {
             let archive_rel = file.archive_path.as_ref().unwrap();
             let entry_path = file.archive_entry_path.as_ref().unwrap();
             let archive_full_path = mod_root.join(archive_rel);
             
-            match archive::read_archive_entry_string(&archive_full_path, entry_path) {
-                Ok(content) => (
+            match archive::read_archive_entry_text(&archive_full_path, entry_path) {
+                Ok((content, metadata)) => (
                     content,
+                    metadata,
                     Some(archive_full_path.clone()),
                     Some(entry_path.clone()),
                     archive_full_path,
@@ -777,8 +3034,17 @@ async fn run_translation_job(
             }
         } else {
             let source_file_path = mod_root.join(&relative_path);
-            match fs::read_to_string(&source_file_path) {
-                Ok(value) => (value, None, None, source_file_path),
+            let read = fs::read(&source_file_path).and_then(|bytes| {
+                if format_by_path.is_binary() || plugin.is_some() {
+                    // Binary files keep their raw bytes for the handler
+                    let metadata = FileMetadata::detect(&bytes);
+                    binary_source = Some(bytes);
+                    return Ok((String::new(), metadata));
+                }
+                FileMetadata::decode_bytes(&bytes)
+            });
+            match read {
+                Ok((value, metadata)) => (value, metadata, None, None, source_file_path),
                 Err(err) => {
                     let message = format!(
                         "Failed to read {}: {}",
@@ -795,15 +3061,77 @@ async fn run_translation_job(
             }
         };
 
+        let format = format_by_path.refine_with_content(&content);
+        let opened = match binary_source {
+            Some(bytes) => plugin
+                .map(|plugin| plugin as Arc<dyn FormatHandler>)
+                .or_else(|| get_handler(format).map(Into::into))
+                .map(|handler| {
+                    let path = Path::new(&file.relative_path);
+                    EntryDocument::open_binary(handler, path, bytes, &target_lang)
+                }),
+            None => EntryDocument::open(format, &content),
+        };
+        let document = match opened.transpose() {
+            Ok(document) => document,
+            Err(err) => {
+                file_errors.push(TranslationFileErrorEntry {
+                    file_path: file.relative_path.clone(),
+                    message: format!(
+                        "Failed to read the entries of {}: {}",
+                        file.relative_path, err
+                    ),
+                    code: Some("PARSE_FAILED".into()),
+                });
+                continue;
+            }
+        };
         let had_trailing_newline = content.ends_with('\n');
-        let lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
-        let output_relative_path = derive_output_relative_path(&file.relative_path, &target_lang);
+        let lines: Vec<String> = match &document {
+            Some(document) => document
+                .entries
+                .iter()
+                .map(|entry| entry.source.clone())
+                .collect(),
+            None => content.lines().map(|line| line.to_string()).collect(),
+        };
+        let layout: Box<dyn OutputLayout> = match profile.as_ref() {
+            Some(profile) => profile.output_layout(),
+            None => Box::new(SuffixLayout),
+        };
+        // The games pick string tables by the language in their file name
+        let localized_table = (format == FileFormat::Bethesda)
+            .then(|| {
+                formats::bethesda::localized_table_path(
+                    Path::new(&file.relative_path),
+                    &target_lang,
+                )
+            })
+            .flatten();
+        let mut output_relative_path = localized_table.unwrap_or_else(|| {
+            derive_output_relative_path(&file.relative_path, &target_lang, layout.as_ref())
+        });
         let base_root = override_root
             .as_ref()
             .cloned()
             .unwrap_or_else(|| mod_root.clone());
-        let output_absolute_path = base_root.join(&output_relative_path);
+        let mut output_absolute_path = base_root.join(&output_relative_path);
+        if collides_with_source(&output_absolute_path, &source_paths) {
+            let divert = !payload.allow_source_overwrite;
+            if divert {
+                output_relative_path = collision_mirror_path(&output_relative_path, &target_lang);
+                output_absolute_path = base_root.join(&output_relative_path);
+            }
+            source_collisions.push((
+                file.relative_path.clone(),
+                output_absolute_path.clone(),
+                divert,
+            ));
+        }
         let resume_metadata_path = build_resume_metadata_path(&output_absolute_path);
+        let provenance_header = provenance
+            .as_ref()
+            .and_then(|info| render_provenance_header_for_path(&output_absolute_path, info));
 
         let mut context = FileContext {
             relative_path: file.relative_path.clone(),
@@ -817,10 +3145,24 @@ async fn run_translation_job(
             resume_line_index: 0,
             archive_path,
             archive_entry_path,
+            provenance_header,
+            source_encoding,
+            output_encoding: payload.output_encoding,
+            format,
+            document,
         };
         context.translated_lines = vec![None; context.lines.len()];
 
-        let signature = compute_file_signature(&source_file_path, &content);
+        // Binary files have no decoded text; their entries stand in for it
+        let is_binary = context
+            .document
+            .as_ref()
+            .is_some_and(|document| document.binary.is_some());
+        let signature = if is_binary {
+            compute_file_signature(&source_file_path, &context.lines.join("\n"))
+        } else {
+            compute_file_signature(&source_file_path, &content)
+        };
         match job_state.files.entry(context.relative_path.clone()) {
             Entry::Occupied(mut entry) => {
                 if entry.get().signature != signature {
@@ -835,12 +3177,56 @@ async fn run_translation_job(
         }
 
         let file_index = file_contexts.len();
+        let first_segment = segments.len();
+        let read_notes = payload.translator_notes
+            && Path::new(&context.relative_path)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+        let mut pending_note: Option<String> = None;
+        let mut skip_next = false;
+        let mut locked = false;
+        let mut file_terms: HashMap<String, String> = HashMap::new();
+        let usage_index = key_usages
+            .get(&context.mod_install_path)
+            .filter(|index| !index.is_empty());
         for (line_index, line) in context.lines.iter().enumerate() {
             let trimmed = line.trim();
             if trimmed.is_empty() {
                 continue;
             }
 
+            // Author directives are comments too; the line itself is kept as is
+            if let Some(directive) = parse_directive(trimmed) {
+                match directive {
+                    Directive::Skip => skip_next = true,
+                    Directive::Lock => locked = true,
+                    Directive::Unlock => locked = false,
+                    Directive::Glossary(terms) => file_terms.extend(terms),
+                }
+                continue;
+            }
+
+            // Marked comments are context only; the line itself is kept as is
+            if read_notes {
+                if let Some(note) = translator_note(trimmed) {
+                    pending_note = Some(note);
+                    continue;
+                }
+            }
+            let note = pending_note.take().or_else(|| {
+                context
+                    .document
+                    .as_ref()
+                    .and_then(|document| document.note(line_index))
+            });
+            let directive = if locked {
+                Some("mt:lock")
+            } else if std::mem::take(&mut skip_next) {
+                Some("mt:skip")
+            } else {
+                None
+            };
+
             if line_index < context.resume_line_index {
                 already_processed_segments += 1;
                 continue;
@@ -851,46 +3237,404 @@ async fn run_translation_job(
             let prefix = line[..prefix_len].to_string();
             let suffix = line[suffix_start..].to_string();
 
-            segments.push(Segment {
+            let segment = Segment {
                 file_index,
                 relative_path: context.relative_path.clone(),
                 line_index,
-                line_number: line_index + 1,
+                line_number: context
+                    .document
+                    .as_ref()
+                    .and_then(|document| document.line_number(line_index))
+                    .unwrap_or(line_index + 1),
                 text: trimmed.to_string(),
                 prefix,
                 suffix,
-            });
+                note,
+                usage: usage_index
+                    .and_then(|index| index.describe_line(&context.relative_path, trimmed)),
+                surroundings: None,
+                placeholder_patterns: patterns.clone(),
+                script: script.clone(),
+                marker_scheme: MarkerScheme::default(),
+                protected: None,
+            };
+            // Listed entries keep their line as is and never reach the provider
+            let skip_note = match directive {
+                Some(directive) => Some(format!(
+                    "건너뜀: 파일의 {directive} 지시문으로 번역하지 않습니다."
+                )),
+                None => skip_matcher
+                    .reason(trimmed)
+                    .map(|reason| format!("건너뜀: 번역 제외 목록({reason})에 있습니다.")),
+            };
+            if let Some(note) = skip_note {
+                do_not_translate.push((segment, note));
+                continue;
+            }
+            segments.push(segment);
+        }
+        if !file_terms.is_empty() {
+            let mut merged = terms.clone();
+            merged.extend(file_terms);
+            file_setting.prompt_vars.glossary = PromptVars::glossary_lines(&merged);
+            file_glossaries.insert(file_index, GlossaryApplier::new(&target_lang, &merged));
+        }
+        if payload.context_mode != ContextMode::Off {
+            let file_segments = &mut segments[first_segment..];
+            let texts: Vec<String> = file_segments
+                .iter()
+                .map(|segment| segment.text.clone())
+                .collect();
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            for (index, segment) in file_segments.iter_mut().enumerate() {
+                segment.surroundings = segment_surroundings(
+                    payload.context_mode,
+                    &context.relative_path,
+                    &texts,
+                    index,
+                    context_neighbors,
+                );
+            }
+        }
+
+        if source_lang == "auto" {
+            if let Some(detected) = detect_file_language(&segments[first_segment..]) {
+                *detected_languages.entry(detected.clone()).or_default() += 1;
+                file_setting.source_lang = detected;
+            }
         }
 
         file_contexts.push(context);
+        file_settings.push(file_setting);
+    }
+
+    let diff = payload
+        .incremental
+        .then(|| carry_over_unchanged(&target_lang, &mut file_contexts, &mut segments));
+    let already_translated = if payload.skip_already_translated {
+        take_already_translated(&target_lang, &mut segments)
+    } else {
+        Vec::new()
+    };
+    for segment in &already_translated {
+        segment_reviews.insert(
+            (segment.file_index, segment.line_index),
+            SegmentReviewNote {
+                status: SegmentReviewStatus::AlreadyTranslated,
+                notes: vec![format!(
+                    "건너뜀: 원문이 이미 대상 언어({target_lang})입니다."
+                )],
+                recovery_steps: Vec::new(),
+                confidence: None,
+            },
+        );
+    }
+
+    for (segment, note) in &do_not_translate {
+        segment_reviews.insert(
+            (segment.file_index, segment.line_index),
+            SegmentReviewNote {
+                status: SegmentReviewStatus::DoNotTranslate,
+                notes: vec![note.clone()],
+                recovery_steps: Vec::new(),
+                confidence: None,
+            },
+        );
+    }
+
+    let rtl_target = bidi::is_rtl_language(&target_lang);
+    let expects_polite = payload.target_tone.map(|tone| {
+        let samples: Vec<&str> = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+        tone.expects_polite(&ToneAnalyzer::new().analyze(&samples))
+    });
+
+    let marker_scheme = payload.marker_scheme.unwrap_or_else(|| {
+        MarkerScheme::negotiate(segments.iter().map(|segment| segment.text.as_str()))
+    });
+    if marker_scheme != MarkerScheme::default() {
+        info!(
+            "job {} uses {:?} placeholder markers",
+            payload.job_id, marker_scheme
+        );
+    }
+    for segment in &mut segments {
+        segment.marker_scheme = marker_scheme;
+        let mod_root = file_contexts[segment.file_index].mod_install_path.clone();
+        let fragment = fragments
+            .entry((mod_root, marker_scheme, segment.text.clone()))
+            .or_insert_with(|| segment.protect_part(&segment.text))
+            .clone();
+        segment.protected = Some(fragment);
+    }
+
+    let total_segments = segments.len() as u32;
+    let job_progress = WeightedProgress::new(&segments);
+    let mut processed_segments =
+        apply_stored_translations(&job_state, &mut file_contexts, &segments);
+    processed_segments = processed_segments.max(already_processed_segments);
+    update_checkpoint_for_next_segment(&mut job_state, &segments, processed_segments);
+    save_job_state(&payload.job_id, job_state.clone());
+
+    if !payload.include_globs.is_empty() || !payload.exclude_globs.is_empty() {
+        let filter = file_filter_summary(&payload);
+        let excluded: usize = filter.excluded_by_pattern.values().sum();
+        let mut log = format!(
+            "파일 필터: {}개 중 {}개를 번역합니다. 제외 글롭으로 {}개",
+            filter.total_files, filter.translated_files, excluded
+        );
+        let matched: Vec<String> = payload
+            .exclude_globs
+            .iter()
+            .filter_map(|glob| {
+                let count = filter.excluded_by_pattern.get(glob)?;
+                (*count > 0).then(|| format!("{glob} {count}개"))
+            })
+            .collect();
+        if !matched.is_empty() {
+            log.push_str(&format!(" ({})", matched.join(", ")));
+        }
+        if !payload.include_globs.is_empty() {
+            log.push_str(&format!(
+                ", 포함 글롭에 맞지 않아 {}개",
+                filter.not_included
+            ));
+        }
+        log.push_str("를 건너뜁니다.");
+        sink.emit("translation-file-filter", filter);
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(log),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+
+    for (source, output_path, diverted) in source_collisions {
+        let log = if diverted {
+            format!(
+                "출력 경로가 원본 파일과 같아 다른 폴더에 저장합니다: {} → {}",
+                source,
+                output_path.to_string_lossy()
+            )
+        } else {
+            format!(
+                "출력 경로가 원본 파일과 같아 백업 후 덮어씁니다: {}",
+                output_path.to_string_lossy()
+            )
+        };
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(log),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+
+    for error in script_errors {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!("프로필 스크립트 없이 번역합니다: {}", error)),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+
+    for (source, profile_name) in skipped_by_profile {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "{} 프로필의 번역 대상 경로가 아니어서 건너뜁니다: {}",
+                    profile_name, source
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+
+    for changed in changed_files {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "원본 파일이 변경되어 체크포인트를 초기화했습니다: {}",
+                    changed
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+    if let Some(diff) = diff {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "증분 모드: 이전 번역 {}건을 재사용하고 변경 {}건, 신규 {}건을 번역합니다.",
+                    diff.unchanged, diff.changed, diff.new
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+    if !detected_languages.is_empty() {
+        let summary: Vec<String> = detected_languages
+            .iter()
+            .map(|(language, files)| format!("{language} {files}개"))
+            .collect();
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "파일별 원문 언어를 감지했습니다: {}",
+                    summary.join(", ")
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
+    if !already_translated.is_empty() {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "원문이 이미 대상 언어인 세그먼트 {}건은 번역하지 않고 건너뜁니다.",
+                    already_translated.len()
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
     }
-
-    let total_segments = segments.len() as u32;
-    let mut processed_segments =
-        apply_stored_translations(&job_state, &mut file_contexts, &segments);
-    processed_segments = processed_segments.max(already_processed_segments);
-    update_checkpoint_for_next_segment(&mut job_state, &segments, processed_segments);
-    save_job_state(&payload.job_id, job_state.clone());
-
-    for changed in changed_files {
+    if !do_not_translate.is_empty() {
         emit_progress(
-            &app,
+            sink,
             TranslationProgressEventPayload {
                 job_id: payload.job_id.clone(),
                 status: "running".into(),
-                progress_pct: Some(percentage(processed_segments, total_segments)),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
                 cancel_requested: None,
                 log: Some(format!(
-                    "원본 파일이 변경되어 체크포인트를 초기화했습니다: {}",
-                    changed
+                    "번역 제외 목록이나 지시문으로 지정된 세그먼트 {}건은 원문 그대로 둡니다.",
+                    do_not_translate.len()
                 )),
                 translated_count: Some(processed_segments),
                 total_count: Some(total_segments),
                 file_name: None,
                 file_success: None,
+                file_progress_pct: None,
                 file_errors: clone_errors(&file_errors),
                 last_written: None,
                 checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
                 retry: None,
             },
         );
@@ -899,34 +3643,67 @@ async fn run_translation_job(
     let mut last_file_success: Option<bool> = None;
 
     emit_progress(
-        &app,
+        sink,
         TranslationProgressEventPayload {
             job_id: payload.job_id.clone(),
             status: "running".into(),
-            progress_pct: Some(percentage(processed_segments, total_segments)),
+            progress_pct: Some(job_progress.job_pct(processed_segments)),
             cancel_requested: None,
             log: Some("번역을 준비하는 중입니다.".into()),
             translated_count: Some(processed_segments),
             total_count: Some(total_segments),
             file_name: None,
             file_success: None,
+            file_progress_pct: None,
             file_errors: clone_errors(&file_errors),
             last_written: None,
             checkpoint: Some(job_state.checkpoint.clone()),
+            usage: Some(job_state.usage.clone()),
             retry: None,
         },
     );
+    emit_job_event(
+        sink,
+        JobEvent::Started {
+            job_id: payload.job_id.clone(),
+            total_files: file_contexts.len(),
+            total_segments,
+            resumed_segments: processed_segments,
+        },
+    );
+
+    if budget.max_cost_usd.is_some() && model_pricing.is_none() {
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "모델 '{}'의 가격 정보를 알 수 없어 비용 한도를 적용하지 않습니다.",
+                    settings.model_id
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
 
     if total_segments > processed_segments {
-        let client = match Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(120))
-            .build()
-        {
+        let client = match build_http_client(&payload.request_options) {
             Ok(client) => client,
             Err(_err) => {
                 emit_progress(
-                    &app,
+                    sink,
                     TranslationProgressEventPayload {
                         job_id: payload.job_id.clone(),
                         status: "failed".into(),
@@ -937,16 +3714,21 @@ async fn run_translation_job(
                         total_count: Some(total_segments),
                         file_name: last_file_name.clone(),
                         file_success: last_file_success,
+                        file_progress_pct: None,
                         file_errors: clone_errors(&file_errors),
                         last_written: None,
                         checkpoint: Some(job_state.checkpoint.clone()),
+                        usage: Some(job_state.usage.clone()),
                         retry: None,
                     },
                 );
                 return;
             }
         };
+        let max_attempts = payload.request_options.max_attempts();
 
+        let mut prefetcher = SegmentPrefetcher::new(budget, model_pricing, cancel_flag.clone());
+        let mut current_file: Option<usize> = None;
         for (index, segment) in segments
             .iter()
             .enumerate()
@@ -964,10 +3746,10 @@ async fn run_translation_job(
 
             if cancel_flag.load(Ordering::SeqCst) {
                 emit_cancelled_progress(
-                    &app,
+                    sink,
                     &payload,
                     processed,
-                    total_segments,
+                    &job_progress,
                     &last_file_name,
                     last_file_success,
                     &file_errors,
@@ -975,47 +3757,191 @@ async fn run_translation_job(
                 return;
             }
 
-            let fragment = Protector::protect(&segment.text);
+            if let Some(exceeded) = budget.check(&job_state.usage) {
+                emit_budget_exceeded(sink, &payload.job_id, exceeded, &job_state);
+                emit_progress(
+                    sink,
+                    TranslationProgressEventPayload {
+                        job_id: payload.job_id.clone(),
+                        status: "paused".into(),
+                        progress_pct: Some(job_progress.job_pct(processed)),
+                        cancel_requested: None,
+                        log: Some(describe_budget_exceeded(exceeded)),
+                        translated_count: Some(processed),
+                        total_count: Some(total_segments),
+                        file_name: last_file_name.clone(),
+                        file_success: last_file_success,
+                        file_progress_pct: None,
+                        file_errors: clone_errors(&file_errors),
+                        last_written: None,
+                        checkpoint: Some(job_state.checkpoint.clone()),
+                        usage: Some(job_state.usage.clone()),
+                        retry: None,
+                    },
+                );
+                return;
+            }
+
+            if current_file != Some(segment.file_index) {
+                current_file = Some(segment.file_index);
+                emit_job_event(
+                    sink,
+                    JobEvent::FileStarted {
+                        job_id: payload.job_id.clone(),
+                        file_path: segment.relative_path.clone(),
+                    },
+                );
+            }
+
+            let fragment = segment.protect();
             let mut attempt: u32 = 0;
             let mut last_error: Option<TranslationError> = None;
             let mut translated_value: Option<String> = None;
             let mut wait_cancelled_by_job = false;
             let mut apply_translation = false;
             let mut qc_messages: Option<Vec<String>> = None;
+            let mut review_status = SegmentReviewStatus::Translated;
+            let mut recovery_steps: Vec<RecoveryStep> = Vec::new();
+            let mut validation_retries = 0usize;
+            let mut retry_note: Option<String> = None;
 
             loop {
                 if cancel_flag.load(Ordering::SeqCst) {
                     break;
                 }
 
-                match translate_text(
-                    &client,
-                    provider,
-                    &api_key,
-                    &payload.model_id,
-                    &fragment,
-                    &source_lang,
-                    &target_lang,
-                )
-                .await
-                {
-                    Ok(value) => {
+                let settings = &file_settings[segment.file_index];
+                let document_mode = settings.needs_document_mode(&segment.text);
+                // The prefetcher records the usage of what it sends itself
+                let prefetched = !document_mode && attempt == 0;
+                let translation = if document_mode {
+                    translate_document(&client, settings, &fragment, |part| {
+                        segment.protect_part(part)
+                    })
+                    .await
+                } else if prefetched {
+                    prefetcher
+                        .take(
+                            &client,
+                            &file_settings,
+                            &segments,
+                            index,
+                            &mut job_state.usage,
+                        )
+                        .await
+                } else {
+                    let note = match (segment.note_context(), &retry_note) {
+                        (Some(context), Some(retry)) => Some(format!("{context}\n{retry}")),
+                        (context, retry) => context.or_else(|| retry.clone()),
+                    };
+                    translate_text_with_context(&client, settings, &fragment, note.as_deref()).await
+                };
+
+                if let (false, Ok(output)) = (prefetched, &translation) {
+                    job_state.usage.record(output.usage, model_pricing);
+                }
+                if cancel_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                match translation {
+                    Ok(output) => {
+                        let value = match file_glossaries.get(&segment.file_index).or_else(|| {
+                            glossaries.get(&file_contexts[segment.file_index].mod_install_path)
+                        }) {
+                            Some(glossary) if !glossary.is_empty() => glossary.apply(&output.text),
+                            _ => output.text,
+                        };
+                        let value = match &segment.script {
+                            Some(script) => script.after_receive(
+                                &segment.text,
+                                &value,
+                                HookContext {
+                                    file: &segment.relative_path,
+                                    line: segment.line_number,
+                                    target_lang: Some(&target_lang),
+                                },
+                            ),
+                            None => value,
+                        };
+                        let limits = match profiles
+                            .get(&file_contexts[segment.file_index].mod_install_path)
+                        {
+                            Some(Some(profile)) => qc_limits.with_rules(&profile.qc_rules),
+                            _ => qc_limits,
+                        };
+                        let value = normalize_punctuation(&value, &limits).into_owned();
+
+                        let filter_issues = payload.output_filter.scan(&segment.text, &value);
+                        if !filter_issues.is_empty() {
+                            warn!(
+                                "{}:{}: output filter matched {:?}",
+                                segment.relative_path, segment.line_number, filter_issues
+                            );
+                        }
+                        if payload.output_filter.mode == OutputFilterMode::Block
+                            && !filter_issues.is_empty()
+                        {
+                            qc_messages =
+                                Some(filter_issues.iter().map(OutputIssue::describe).collect());
+                            translated_value = Some(segment.text.clone());
+                            apply_translation = false;
+                            review_status = SegmentReviewStatus::RolledBack;
+                            rolled_back_segments
+                                .push(format!("{}:{}", segment.relative_path, segment.line_number));
+                            last_error = None;
+                            break;
+                        }
+
                         // First, run existing quality validation
                         let validation =
-                            validate_segment(segment.text.as_str(), value.as_str(), &qc_limits);
+                            validate_segment(segment.text.as_str(), value.as_str(), &limits);
 
                         // Then, run enhanced placeholder validation with auto-recovery
-                        let placeholder_validator = PlaceholderValidator::with_default_config();
+                        let mut placeholder_validator =
+                            PlaceholderValidator::new(payload.validator_config.clone())
+                                .with_token_map(fragment.token_map());
+                        if let Some(Some(profile)) =
+                            profiles.get(&file_contexts[segment.file_index].mod_install_path)
+                        {
+                            placeholder_validator =
+                                placeholder_validator.with_profile(&profile.validator_config);
+                        }
+                        let token_types = fragment
+                            .token_map()
+                            .tokens
+                            .iter()
+                            .map(|token| token.kind.code().to_string())
+                            .collect();
+                        // The reply already had its markers restored, so it is
+                        // checked against the source as written; checking it
+                        // against the masked source would "recover" every marker
+                        // a second time
                         let validator_segment = ValidatorSegment::new(
                             segment.relative_path.clone(),
                             segment.line_number as u32,
                             format!("line_{}", segment.line_index),
                             segment.text.clone(),
-                            fragment.masked_text().to_string(),
-                        );
+                            segment.text.clone(),
+                        )
+                        .with_format(file_contexts[segment.file_index].format.into())
+                        .with_token_types(token_types);
 
                         let placeholder_result =
                             placeholder_validator.validate(&validator_segment, &value);
+                        let misordered = match &placeholder_result {
+                            Ok(success) if rtl_target => {
+                                let markup: Vec<&str> = fragment
+                                    .token_map()
+                                    .tokens
+                                    .iter()
+                                    .filter(|token| bidi::is_markup(token.kind))
+                                    .map(|token| token.value.as_str())
+                                    .collect();
+                                bidi::misordered_markup(&segment.text, &success.value, &markup)
+                            }
+                            _ => Vec::new(),
+                        };
 
                         match placeholder_result {
                             Ok(success) => {
@@ -1026,11 +3952,77 @@ async fn run_translation_job(
                                     ValidationOutcome::Clean
                                 };
                                 validation_logger().log_success(outcome);
-
-                                if validation.is_pass() {
-                                    translated_value = Some(success.value.clone());
+                                if validation_retries > 0 {
+                                    validation_logger().log_retry(true);
+                                }
+                                recovery_steps = success.autofix.steps.clone();
+
+                                if held_by_strict_mode(&payload, &success) {
+                                    // Strict mode: keep the source and queue the segment
+                                    // for retranslation
+                                    qc_messages = Some(vec![format!(
+                                        "엄격 모드: 자동 복구된 번역은 검토가 필요합니다 ({:?})",
+                                        success.autofix.steps
+                                    )]);
+                                    translated_value = Some(segment.text.clone());
+                                    apply_translation = false;
+                                    review_status = SegmentReviewStatus::HeldForReview;
+                                    review_segments.push(ReviewSegment {
+                                        file_path: segment.relative_path.clone(),
+                                        line_number: segment.line_number,
+                                        source: segment.text.clone(),
+                                        candidate: success.value.clone(),
+                                        recovery_steps: success.autofix.steps.clone(),
+                                    });
+                                } else if !misordered.is_empty() {
+                                    // Closing markup before its opening shows up
+                                    // reversed around the wrong words in RTL text
+                                    qc_messages = Some(vec![format!(
+                                        "RTL 마크업 순서 오류: 여는 태그보다 닫는 태그가 먼저 나옵니다 ({})",
+                                        misordered.join(", ")
+                                    )]);
+                                    translated_value = Some(segment.text.clone());
+                                    apply_translation = false;
+                                    review_status = SegmentReviewStatus::RolledBack;
+                                    rolled_back_segments.push(format!(
+                                        "{}:{}",
+                                        segment.relative_path, segment.line_number
+                                    ));
+                                } else if validation.is_pass() {
+                                    let tone_warning = expects_polite.and_then(|polite| {
+                                        tone_mismatch(polite, &target_lang, &success.value)
+                                    });
+                                    if let Some(warning) = &tone_warning {
+                                        warn!(
+                                            "{}:{}: {}",
+                                            segment.relative_path, segment.line_number, warning
+                                        );
+                                    }
+                                    translated_value = Some(if rtl_target {
+                                        let tokens: Vec<&str> = fragment
+                                            .token_map()
+                                            .tokens
+                                            .iter()
+                                            .filter(|token| bidi::needs_isolation(token.kind))
+                                            .map(|token| token.value.as_str())
+                                            .collect();
+                                        bidi::isolate_tokens(
+                                            &success.value,
+                                            &tokens,
+                                            payload.bidi_marks,
+                                        )
+                                    } else {
+                                        success.value.clone()
+                                    });
                                     apply_translation = true;
-                                    qc_messages = None;
+                                    let mut messages = validation.warnings.clone();
+                                    messages.extend(tone_warning);
+                                    qc_messages = (!messages.is_empty()).then_some(messages);
+                                    review_status = if success.recovered_with_warning {
+                                        SegmentReviewStatus::Recovered
+                                    } else {
+                                        SegmentReviewStatus::Translated
+                                    };
                                 } else {
                                     // Quality validation failed even with recovered placeholders
                                     let mut messages = Vec::new();
@@ -1047,17 +4039,15 @@ async fn run_translation_job(
                                     };
                                     translated_value = Some(segment.text.clone());
                                     apply_translation = false;
+                                    review_status = SegmentReviewStatus::RolledBack;
                                     rolled_back_segments.push(format!(
                                         "{}:{}",
                                         segment.relative_path, segment.line_number
                                     ));
                                 }
                             }
-                            Err(failure_report) => {
+                            Err(mut failure_report) => {
                                 // Placeholder validation failed and auto-recovery didn't help
-                                // Log to validation logger
-                                validation_logger().log_failure(&failure_report);
-
                                 warn!(
                                     "Placeholder validation failed for {}:{} ({}): {:?}",
                                     segment.relative_path,
@@ -1066,6 +4056,25 @@ async fn run_translation_job(
                                     failure_report.code
                                 );
 
+                                // Ask the model again, naming the tokens it has to keep
+                                let validator_config = &payload.validator_config;
+                                if validator_config.retry_on_fail
+                                    && validation_retries < validator_config.retry_limit
+                                {
+                                    validation_retries += 1;
+                                    metrics::record_retry(provider);
+                                    retry_note =
+                                        Some(validation_retry_note(&fragment, &failure_report));
+                                    // Later attempts skip the prefetched result
+                                    attempt = attempt.saturating_add(1);
+                                    continue;
+                                }
+                                if validation_retries > 0 {
+                                    failure_report.retry = RetryInfo::attempted(false);
+                                }
+                                validation_logger().log_failure(&failure_report);
+                                validation_failures.push(failure_report.clone());
+
                                 // Store failure report for UI (could be expanded later)
                                 let mut messages = Vec::new();
                                 messages.push(format!(
@@ -1073,19 +4082,54 @@ async fn run_translation_job(
                                     failure_report.code
                                 ));
                                 qc_messages = Some(messages);
+                                recovery_steps = failure_report.autofix.steps.clone();
 
                                 translated_value = Some(segment.text.clone());
                                 apply_translation = false;
+                                review_status = SegmentReviewStatus::RolledBack;
                                 rolled_back_segments.push(format!(
                                     "{}:{}",
                                     segment.relative_path, segment.line_number
                                 ));
                             }
                         }
+                        if apply_translation && !filter_issues.is_empty() {
+                            qc_messages
+                                .get_or_insert_with(Vec::new)
+                                .extend(filter_issues.iter().map(OutputIssue::describe));
+                        }
                         last_error = None;
                         break;
                     }
                     Err(error) => {
+                        if let TranslationError::ModelNotFound { model_id, .. } = &error {
+                            if let Some(fallback) = next_fallback_model(
+                                &payload,
+                                &settings.api_key,
+                                provider,
+                                model_id,
+                                &mut tried_models,
+                            ) {
+                                emit_model_fallback(
+                                    sink,
+                                    &payload.job_id,
+                                    provider,
+                                    model_id,
+                                    &fallback,
+                                );
+                                for file_setting in file_settings.iter_mut() {
+                                    file_setting.model_id = fallback.clone();
+                                }
+                                model_pricing = pricing_for(provider, &fallback);
+                                // Prefetched results were requested from the missing model
+                                prefetcher = SegmentPrefetcher::new(
+                                    budget,
+                                    model_pricing,
+                                    cancel_flag.clone(),
+                                );
+                                continue;
+                            }
+                        }
                         last_error = Some(error);
 
                         if !should_retry_error(last_error.as_ref().unwrap()) {
@@ -1093,13 +4137,14 @@ async fn run_translation_job(
                         }
 
                         attempt = attempt.saturating_add(1);
-                        if attempt >= MAX_RETRY_ATTEMPTS as u32 {
+                        if attempt >= max_attempts {
                             break;
                         }
+                        metrics::record_retry(provider);
 
                         let plan = compute_retry_plan(last_error.as_ref().unwrap(), attempt);
                         if plan.delay.is_zero() {
-                            emit_retry_started(&app, &payload.job_id, attempt);
+                            emit_retry_started(sink, &payload.job_id, attempt);
                             continue;
                         }
 
@@ -1111,10 +4156,11 @@ async fn run_translation_job(
                         );
 
                         emit_backoff_started(
-                            &app,
+                            sink,
                             &payload.job_id,
                             plan.delay,
                             attempt,
+                            max_attempts,
                             plan.used_hint,
                             &plan.reason,
                         );
@@ -1122,27 +4168,29 @@ async fn run_translation_job(
                         let delay_seconds = duration_to_retry_seconds(plan.delay);
                         let log_message = format!(
                             "Retry attempt {}/{} scheduled in {}s ({})",
-                            attempt, MAX_RETRY_ATTEMPTS, delay_seconds, plan.reason
+                            attempt, max_attempts, delay_seconds, plan.reason
                         );
 
                         emit_progress(
-                            &app,
+                            sink,
                             TranslationProgressEventPayload {
                                 job_id: payload.job_id.clone(),
                                 status: "running".into(),
-                                progress_pct: Some(percentage(processed_segments, total_segments)),
+                                progress_pct: Some(job_progress.job_pct(processed_segments)),
                                 cancel_requested: None,
                                 log: Some(log_message),
                                 translated_count: Some(processed_segments),
                                 total_count: Some(total_segments),
                                 file_name: last_file_name.clone(),
                                 file_success: last_file_success,
+                                file_progress_pct: None,
                                 file_errors: clone_errors(&file_errors),
                                 last_written: None,
                                 checkpoint: Some(job_state.checkpoint.clone()),
+                                usage: Some(job_state.usage.clone()),
                                 retry: Some(RetryStatusPayload {
                                     attempt,
-                                    max_attempts: MAX_RETRY_ATTEMPTS as u32,
+                                    max_attempts,
                                     delay_seconds,
                                     reason: plan.reason.clone(),
                                 }),
@@ -1150,7 +4198,7 @@ async fn run_translation_job(
                         );
 
                         match wait_with_cancellation(
-                            &app,
+                            sink,
                             &payload.job_id,
                             &cancel_flag,
                             backoff_controller.clone(),
@@ -1163,7 +4211,7 @@ async fn run_translation_job(
                                 break;
                             }
                             BackoffWaitOutcome::Manual | BackoffWaitOutcome::Completed => {
-                                emit_retry_started(&app, &payload.job_id, attempt);
+                                emit_retry_started(sink, &payload.job_id, attempt);
                                 continue;
                             }
                         }
@@ -1173,10 +4221,10 @@ async fn run_translation_job(
 
             if cancel_flag.load(Ordering::SeqCst) || wait_cancelled_by_job {
                 emit_cancelled_progress(
-                    &app,
+                    sink,
                     &payload,
                     processed,
-                    total_segments,
+                    &job_progress,
                     &last_file_name,
                     last_file_success,
                     &file_errors,
@@ -1189,14 +4237,7 @@ async fn run_translation_job(
                 let error = last_error.expect("translation error must exist on failure");
                 
                 // Check if this is a fatal error that should stop the entire job
-                let is_fatal = matches!(
-                    &error,
-                    TranslationError::Unauthorized { .. } 
-                    | TranslationError::Forbidden { .. }
-                    | TranslationError::ModelNotFound { .. }
-                );
-                
-                if is_fatal {
+                if error.is_fatal() {
                     // For authentication/authorization errors, stop the job
                     let log_message = format_translation_error(segment, &error);
                     let file_message = format_file_error_message(segment, &error);
@@ -1209,20 +4250,22 @@ async fn run_translation_job(
                     });
                     save_job_state(&payload.job_id, job_state.clone());
                     emit_progress(
-                        &app,
+                        sink,
                         TranslationProgressEventPayload {
                             job_id: payload.job_id.clone(),
                             status: "failed".into(),
-                            progress_pct: Some(percentage(processed, total_segments)),
+                            progress_pct: Some(job_progress.job_pct(processed)),
                             cancel_requested: None,
                             log: Some(log_message.clone()),
                             translated_count: Some(processed_segments),
                             total_count: Some(total_segments),
                             file_name: last_file_name.clone(),
                             file_success: last_file_success,
+                            file_progress_pct: None,
                             file_errors: clone_errors(&file_errors),
                             last_written: None,
                             checkpoint: Some(job_state.checkpoint.clone()),
+                            usage: Some(job_state.usage.clone()),
                             retry: None,
                         },
                     );
@@ -1251,6 +4294,15 @@ async fn run_translation_job(
                     "{}:{}",
                     segment.relative_path, segment.line_number
                 ));
+                segment_reviews.insert(
+                    (segment.file_index, segment.line_index),
+                    SegmentReviewNote {
+                        status: SegmentReviewStatus::Failed,
+                        notes: vec![error.to_string()],
+                        recovery_steps: Vec::new(),
+                        confidence: None,
+                    },
+                );
                 
                 // Continue processing with original text
                 processed_segments = processed + 1;
@@ -1260,20 +4312,24 @@ async fn run_translation_job(
                 save_job_state(&payload.job_id, job_state.clone());
                 
                 emit_progress(
-                    &app,
+                    sink,
                     TranslationProgressEventPayload {
                         job_id: payload.job_id.clone(),
                         status: "running".into(),
-                        progress_pct: Some(percentage(processed_segments, total_segments)),
+                        progress_pct: Some(job_progress.job_pct(processed_segments)),
                         cancel_requested: None,
                         log: Some(log_message),
                         translated_count: Some(processed_segments),
                         total_count: Some(total_segments),
                         file_name: last_file_name.clone(),
                         file_success: last_file_success,
+                        file_progress_pct: Some(
+                            job_progress.file_pct(segment.file_index, processed_segments),
+                        ),
                         file_errors: clone_errors(&file_errors),
                         last_written: None,
                         checkpoint: Some(job_state.checkpoint.clone()),
+                        usage: Some(job_state.usage.clone()),
                         retry: None,
                     },
                 );
@@ -1302,17 +4358,47 @@ async fn run_translation_job(
                 }
             }
 
+            segment_reviews.insert(
+                (segment.file_index, segment.line_index),
+                SegmentReviewNote {
+                    status: review_status,
+                    notes: qc_messages.clone().unwrap_or_default(),
+                    recovery_steps,
+                    confidence: None,
+                },
+            );
+
             processed_segments = processed + 1;
             last_file_name = Some(segment.relative_path.clone());
             last_file_success = Some(apply_translation);
             update_checkpoint_for_next_segment(&mut job_state, &segments, processed_segments);
             save_job_state(&payload.job_id, job_state.clone());
+            emit_job_event(
+                sink,
+                JobEvent::SegmentTranslated {
+                    job_id: payload.job_id.clone(),
+                    file_path: segment.relative_path.clone(),
+                    line_number: segment.line_number,
+                    applied: apply_translation,
+                    warnings: qc_messages.clone().unwrap_or_default(),
+                    translated_count: processed_segments,
+                    total_count: total_segments,
+                },
+            );
 
             let progress_log = if apply_translation {
-                format!(
-                    "{} {}행 번역 완료",
-                    segment.relative_path, segment.line_number
-                )
+                match &qc_messages {
+                    Some(messages) => format!(
+                        "{} {}행 번역 완료 (QC 경고: {})",
+                        segment.relative_path,
+                        segment.line_number,
+                        messages.join("; ")
+                    ),
+                    None => format!(
+                        "{} {}행 번역 완료",
+                        segment.relative_path, segment.line_number
+                    ),
+                }
             } else if let Some(messages) = &qc_messages {
                 format!(
                     "QC 검증으로 원본 유지: {} {}행 ({})",
@@ -1328,20 +4414,24 @@ async fn run_translation_job(
             };
 
             emit_progress(
-                &app,
+                sink,
                 TranslationProgressEventPayload {
                     job_id: payload.job_id.clone(),
                     status: "running".into(),
-                    progress_pct: Some(percentage(processed_segments, total_segments)),
+                    progress_pct: Some(job_progress.job_pct(processed_segments)),
                     cancel_requested: None,
                     log: Some(progress_log),
                     translated_count: Some(processed_segments),
                     total_count: Some(total_segments),
                     file_name: last_file_name.clone(),
                     file_success: last_file_success,
+                    file_progress_pct: Some(
+                        job_progress.file_pct(segment.file_index, processed_segments),
+                    ),
                     file_errors: clone_errors(&file_errors),
                     last_written: None,
                     checkpoint: Some(job_state.checkpoint.clone()),
+                    usage: Some(job_state.usage.clone()),
                     retry: None,
                 },
             );
@@ -1350,10 +4440,10 @@ async fn run_translation_job(
 
     if cancel_flag.load(Ordering::SeqCst) {
         emit_cancelled_progress(
-            &app,
+            sink,
             &payload,
             processed_segments,
-            total_segments,
+            &job_progress,
             &last_file_name,
             last_file_success,
             &file_errors,
@@ -1361,120 +4451,220 @@ async fn run_translation_job(
         return;
     }
 
-    // 일반 파일 저장 (아카이브 내부 파일은 별도 처리)
-    for context in &mut file_contexts {
-        if cancel_flag.load(Ordering::SeqCst) {
-            emit_cancelled_progress(
-                &app,
-                &payload,
-                processed_segments,
-                total_segments,
-                &last_file_name,
-                last_file_success,
-                &file_errors,
-            );
-            return;
-        }
+    if let Some(mode) = payload.consistency_pass {
+        let report = run_consistency_pass(
+            mode,
+            &target_lang,
+            &payload.glossary,
+            profiles,
+            &mut file_contexts,
+            &segments,
+            &mut job_state,
+        );
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(describe_consistency_report(&report)),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+        emit_consistency_report(sink, &payload.job_id, mode, report);
+    }
 
-        // 아카이브 내부 파일은 나중에 일괄 처리
-        if context.archive_path.is_some() {
-            continue;
+    for (file_index, error) in check_merged_files(&mut file_contexts, &segments, &mut job_state) {
+        let relative_path = file_contexts[file_index].relative_path.clone();
+        warn!(
+            "{}: translated file failed the format check: {}",
+            relative_path, error
+        );
+        for segment in segments
+            .iter()
+            .filter(|segment| segment.file_index == file_index)
+        {
+            let Some(note) = segment_reviews.get_mut(&(file_index, segment.line_index)) else {
+                continue;
+            };
+            if note.status != SegmentReviewStatus::Translated
+                && note.status != SegmentReviewStatus::Recovered
+            {
+                continue;
+            }
+            note.status = SegmentReviewStatus::RolledBack;
+            note.notes
+                .push(format!("File format check failed: {error}"));
+            rolled_back_segments.push(format!("{}:{}", relative_path, segment.line_number));
         }
+        let message = format!(
+            "{} no longer parses after translation; the whole file was rolled back: {}",
+            relative_path, error
+        );
+        file_errors.push(TranslationFileErrorEntry {
+            file_path: relative_path.clone(),
+            message: message.clone(),
+            code: Some("FORMAT_INVALID".into()),
+        });
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(message),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: Some(relative_path),
+                file_success: Some(false),
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
 
-        let output_absolute_path = context.output_absolute_path.clone();
-        let output_relative = context.output_relative_path.clone();
+    let quality_estimation = &payload.quality_estimation;
+    if quality_estimation.enabled {
+        let (scored, low) = run_quality_estimation(
+            quality_estimation,
+            &payload.request_options,
+            &file_settings,
+            &file_contexts,
+            &segments,
+            &mut segment_reviews,
+        )
+        .await;
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "running".into(),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
+                cancel_requested: None,
+                log: Some(format!(
+                    "품질 추정: 번역 {scored}건 중 {low}건의 신뢰도가 {}점 미만입니다.",
+                    quality_estimation.low_confidence_threshold
+                )),
+                translated_count: Some(processed_segments),
+                total_count: Some(total_segments),
+                file_name: None,
+                file_success: None,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+    }
 
-        if let Some(parent_dir) = output_absolute_path.parent() {
-            if let Err(err) = fs::create_dir_all(parent_dir) {
-                let message = format!(
-                    "Failed to write {}: {}",
-                    output_absolute_path.to_string_lossy(),
-                    err
-                );
-                last_file_name = Some(context.relative_path.clone());
-                last_file_success = Some(false);
-                file_errors.push(TranslationFileErrorEntry {
-                    file_path: context.relative_path.clone(),
-                    message: message.clone(),
-                    code: Some("WRITE_FAILED".into()),
-                });
-                save_job_state(&payload.job_id, job_state.clone());
-                emit_progress(
-                    &app,
-                    TranslationProgressEventPayload {
-                        job_id: payload.job_id.clone(),
-                        status: "running".into(),
-                        progress_pct: Some(percentage(processed_segments, total_segments)),
-                        cancel_requested: None,
-                        log: Some(message),
-                        translated_count: Some(processed_segments),
-                        total_count: Some(total_segments),
-                        file_name: last_file_name.clone(),
-                        file_success: last_file_success,
-                        file_errors: clone_errors(&file_errors),
-                        last_written: None,
-                        checkpoint: Some(job_state.checkpoint.clone()),
-                        retry: None,
-                    },
-                );
-                continue;
+    if payload.approval_mode {
+        let session = stage_for_approval(&target_lang, file_contexts, &segments);
+        let staged = session.segments.len();
+        save_job_state(&payload.job_id, job_state.clone());
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: payload.job_id.clone(),
+                status: "awaiting_approval".into(),
+                progress_pct: Some(0.0),
+                cancel_requested: None,
+                log: Some(format!(
+                    "번역된 세그먼트 {staged}건이 승인을 기다리고 있습니다."
+                )),
+                translated_count: Some(0),
+                total_count: Some(staged as u32),
+                file_name: last_file_name.clone(),
+                file_success: last_file_success,
+                file_progress_pct: None,
+                file_errors: clone_errors(&file_errors),
+                last_written: None,
+                checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
+                retry: None,
+            },
+        );
+        match APPROVAL_SESSIONS.lock() {
+            Ok(mut sessions) => {
+                sessions.insert(payload.job_id.clone(), session);
+            }
+            Err(_) => {
+                warn!("approval registry lock poisoned");
+                return;
             }
         }
+        flush_approved_files(sink, &payload.job_id);
+        return;
+    }
 
-        let contents = render_translated_file(context);
-        let write_result = if output_absolute_path.exists() {
-            match backup_and_swap(&output_absolute_path, contents.as_bytes()) {
-                Ok(outcome) => Ok(outcome.backup_path),
-                Err(err) => Err(err.to_string()),
-            }
-        } else {
-            match File::create(&output_absolute_path) {
-                Ok(mut file) => {
-                    if let Err(err) = file.write_all(contents.as_bytes()) {
-                        Err(err.to_string())
-                    } else if let Err(err) = file.sync_all() {
-                        Err(err.to_string())
-                    } else {
-                        Ok(None)
-                    }
-                }
-                Err(err) => Err(err.to_string()),
-            }
-        };
+    // 일반 파일 저장 (아카이브 내부 파일은 별도 처리)
+    for context in &mut file_contexts {
+        if cancel_flag.load(Ordering::SeqCst) {
+            emit_cancelled_progress(
+                sink,
+                &payload,
+                processed_segments,
+                &job_progress,
+                &last_file_name,
+                last_file_success,
+                &file_errors,
+            );
+            return;
+        }
 
-        let backup_display = match write_result {
-            Ok(backup_path) => backup_path
-                .map(|path| path.to_string_lossy().to_string())
-                .filter(|path| !path.is_empty()),
-            Err(message) => {
-                let log_message = format!(
-                    "Failed to write {}: {}",
-                    output_absolute_path.to_string_lossy(),
-                    message
-                );
+        // 아카이브 내부 파일은 나중에 일괄 처리
+        if context.archive_path.is_some() {
+            continue;
+        }
+
+        let output_absolute_path = context.output_absolute_path.clone();
+        let output_relative = context.output_relative_path.clone();
+
+        let backup_display = match write_output_file(context) {
+            Ok(backup_display) => backup_display,
+            Err(failure) => {
+                let log_message = failure.message;
                 last_file_name = Some(context.relative_path.clone());
                 last_file_success = Some(false);
                 file_errors.push(TranslationFileErrorEntry {
                     file_path: context.relative_path.clone(),
                     message: log_message.clone(),
-                    code: Some("WRITE_FAILED".into()),
+                    code: Some(failure.code.into()),
                 });
                 save_job_state(&payload.job_id, job_state.clone());
                 emit_progress(
-                    &app,
+                    sink,
                     TranslationProgressEventPayload {
                         job_id: payload.job_id.clone(),
                         status: "running".into(),
-                        progress_pct: Some(percentage(processed_segments, total_segments)),
+                        progress_pct: Some(job_progress.job_pct(processed_segments)),
                         cancel_requested: None,
                         log: Some(log_message),
                         translated_count: Some(processed_segments),
                         total_count: Some(total_segments),
                         file_name: last_file_name.clone(),
                         file_success: last_file_success,
+                        file_progress_pct: None,
                         file_errors: clone_errors(&file_errors),
                         last_written: None,
                         checkpoint: Some(job_state.checkpoint.clone()),
+                        usage: Some(job_state.usage.clone()),
                         retry: None,
                     },
                 );
@@ -1482,6 +4672,14 @@ async fn run_translation_job(
             }
         };
 
+        record_job_output(
+            &payload.job_id,
+            &output_relative,
+            &output_absolute_path,
+            backup_display.as_deref().map(Path::new),
+            Vec::new(),
+        );
+        record_source_snapshot(context, &target_lang);
         let absolute_display = output_absolute_path
             .canonicalize()
             .unwrap_or_else(|_| output_absolute_path.clone())
@@ -1493,11 +4691,11 @@ async fn run_translation_job(
         last_file_success = Some(true);
 
         emit_progress(
-            &app,
+            sink,
             TranslationProgressEventPayload {
                 job_id: payload.job_id.clone(),
                 status: "running".into(),
-                progress_pct: Some(percentage(processed_segments, total_segments)),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
                 cancel_requested: None,
                 log: Some(match &backup_display {
                     Some(backup) => format!(
@@ -1510,6 +4708,7 @@ async fn run_translation_job(
                 total_count: Some(total_segments),
                 file_name: last_file_name.clone(),
                 file_success: last_file_success,
+                file_progress_pct: None,
                 file_errors: clone_errors(&file_errors),
                 last_written: Some(LastWrittenInfo {
                     source_relative_path: context.relative_path.clone(),
@@ -1517,6 +4716,7 @@ async fn run_translation_job(
                     output_relative_path: output_relative_display,
                 }),
                 checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
                 retry: None,
             },
         );
@@ -1529,20 +4729,22 @@ async fn run_translation_job(
     
     if !archive_contexts.is_empty() {
         emit_progress(
-            &app,
+            sink,
             TranslationProgressEventPayload {
                 job_id: payload.job_id.clone(),
                 status: "running".into(),
-                progress_pct: Some(percentage(processed_segments, total_segments)),
+                progress_pct: Some(job_progress.job_pct(processed_segments)),
                 cancel_requested: None,
                 log: Some("아카이브 파일을 저장하는 중...".into()),
                 translated_count: Some(processed_segments),
                 total_count: Some(total_segments),
                 file_name: None,
                 file_success: None,
+                file_progress_pct: None,
                 file_errors: clone_errors(&file_errors),
                 last_written: None,
                 checkpoint: Some(job_state.checkpoint.clone()),
+                usage: Some(job_state.usage.clone()),
                 retry: None,
             },
         );
@@ -1553,7 +4755,7 @@ async fn run_translation_job(
             .cloned()
             .collect();
         
-        match save_archive_translations(&archive_save_contexts, &target_lang) {
+        match save_archive_translations(&payload.job_id, &archive_save_contexts, &target_lang) {
             Ok(results) => {
                 for (archive_path, count) in results {
                     let archive_name = archive_path.file_name()
@@ -1561,20 +4763,22 @@ async fn run_translation_job(
                         .unwrap_or_else(|| archive_path.to_string_lossy().to_string());
                     
                     emit_progress(
-                        &app,
+                        sink,
                         TranslationProgressEventPayload {
                             job_id: payload.job_id.clone(),
                             status: "running".into(),
-                            progress_pct: Some(percentage(processed_segments, total_segments)),
+                            progress_pct: Some(job_progress.job_pct(processed_segments)),
                             cancel_requested: None,
                             log: Some(format!("{} 아카이브에 {}개 파일 저장 완료", archive_name, count)),
                             translated_count: Some(processed_segments),
                             total_count: Some(total_segments),
                             file_name: Some(archive_name),
                             file_success: Some(true),
+                            file_progress_pct: None,
                             file_errors: clone_errors(&file_errors),
                             last_written: None,
                             checkpoint: Some(job_state.checkpoint.clone()),
+                            usage: Some(job_state.usage.clone()),
                             retry: None,
                         },
                     );
@@ -1587,20 +4791,22 @@ async fn run_translation_job(
                     code: Some("ARCHIVE_WRITE_FAILED".into()),
                 });
                 emit_progress(
-                    &app,
+                    sink,
                     TranslationProgressEventPayload {
                         job_id: payload.job_id.clone(),
                         status: "running".into(),
-                        progress_pct: Some(percentage(processed_segments, total_segments)),
+                        progress_pct: Some(job_progress.job_pct(processed_segments)),
                         cancel_requested: None,
                         log: Some(format!("아카이브 저장 실패: {}", err)),
                         translated_count: Some(processed_segments),
                         total_count: Some(total_segments),
                         file_name: None,
                         file_success: Some(false),
+                        file_progress_pct: None,
                         file_errors: clone_errors(&file_errors),
                         last_written: None,
                         checkpoint: Some(job_state.checkpoint.clone()),
+                        usage: Some(job_state.usage.clone()),
                         retry: None,
                     },
                 );
@@ -1608,145 +4814,982 @@ async fn run_translation_job(
         }
     }
 
-    let final_status = if !file_errors.is_empty() {
-        if processed_segments > 0 || total_segments > 0 {
-            "partial_success"
+    let final_status = if !review_segments.is_empty() {
+        "failed"
+    } else if !file_errors.is_empty() {
+        if processed_segments > 0 || total_segments > 0 {
+            "partial_success"
+        } else {
+            "failed"
+        }
+    } else {
+        "completed"
+    };
+    let mut final_log = if total_segments == 0 && file_errors.is_empty() {
+        "번역할 문자열이 없습니다.".to_string()
+    } else if final_status == "completed" {
+        "번역이 완료되었습니다.".to_string()
+    } else if final_status == "partial_success" {
+        "일부 파일을 번역하거나 저장하지 못했습니다.".to_string()
+    } else {
+        "번역을 완료하지 못했습니다.".to_string()
+    };
+
+    if !rolled_back_segments.is_empty() {
+        let preview: Vec<_> = rolled_back_segments.iter().take(3).cloned().collect();
+        let remainder = rolled_back_segments.len().saturating_sub(preview.len());
+        if !preview.is_empty() {
+            final_log.push(' ');
+            let detail = if remainder > 0 {
+                format!("{} 외 {}건", preview.join(", "), remainder)
+            } else {
+                preview.join(", ")
+            };
+            final_log.push_str(&format!(
+                "자동 롤백 {}건 ({detail}).",
+                rolled_back_segments.len()
+            ));
+        }
+    }
+
+    if !review_segments.is_empty() {
+        final_log.push_str(&format!(
+            " 엄격 모드: 자동 복구가 필요했던 세그먼트 {}건을 기록하지 않고 재번역 대기열로 보냈습니다.",
+            review_segments.len()
+        ));
+        emit_review_required(sink, &payload.job_id, &review_segments);
+    }
+
+    // Skipped segments are listed in the reports in line order
+    if !already_translated.is_empty() || !do_not_translate.is_empty() {
+        segments.extend(already_translated);
+        segments.extend(do_not_translate.into_iter().map(|(segment, _)| segment));
+        segments.sort_by_key(|segment| (segment.file_index, segment.line_index));
+    }
+
+    let metadata = ReviewMetadata {
+        job_id: payload.job_id.clone(),
+        source_lang: source_lang.clone(),
+        target_lang: target_lang.clone(),
+        provider: provider.label().to_string(),
+        model_id: payload.model_id.clone(),
+    };
+    if payload.export_review {
+        let written = write_review_reports(&metadata, &file_contexts, &segments, &segment_reviews);
+        if written > 0 {
+            final_log.push_str(&format!(" 검토 보고서 {written}개를 작성했습니다."));
+        }
+    }
+    let (triage_files, failed_segments) = failed_segments_for_triage(
+        &payload.job_id,
+        &file_settings,
+        &file_contexts,
+        &segments,
+        &segment_reviews,
+        &validation_failures,
+    );
+    save_triage_store(&TriageStore {
+        metadata: ReviewMetadata {
+            // The model the job ended with, after any fallback
+            model_id: file_settings.first().map_or_else(
+                || payload.model_id.clone(),
+                |settings| settings.model_id.clone(),
+            ),
+            ..metadata.clone()
+        },
+        provider_id: provider,
+        params: payload.model_params.clone(),
+        request_options: payload.request_options,
+        api_base_url: payload.api_base_url.clone(),
+        validator_config: payload.validator_config.clone(),
+        marker_scheme,
+        files: triage_files,
+        segments: failed_segments,
+    });
+    if write_job_qc_report(
+        &metadata,
+        &file_contexts,
+        &segments,
+        &segment_reviews,
+        validation_failures,
+        &file_errors,
+        quality_estimation
+            .enabled
+            .then_some(quality_estimation.low_confidence_threshold),
+    ) > 0
+    {
+        final_log.push_str(" QC 보고서(.review/qc-report.md)를 작성했습니다.");
+    }
+
+    let mut final_progress = if total_segments == 0 {
+        100.0
+    } else {
+        job_progress.job_pct(processed_segments)
+    };
+    if final_progress < 100.0 && final_status != "failed" {
+        final_progress = 100.0;
+    }
+
+    if final_status == "completed" {
+        clear_job_state(&payload.job_id);
+    } else {
+        save_job_state(&payload.job_id, job_state.clone());
+    }
+
+    let finished_at = Utc::now();
+    record_job_history(&JobHistoryEntry {
+        job_id: payload.job_id.clone(),
+        status: final_status.to_string(),
+        provider,
+        model_id: file_settings.first().map_or_else(
+            || payload.model_id.clone(),
+            |settings| settings.model_id.clone(),
+        ),
+        source_lang: source_lang.clone(),
+        target_lang: target_lang.clone(),
+        started_at,
+        finished_at,
+        duration_ms: (finished_at - started_at).num_milliseconds().max(0) as u64,
+        files: file_contexts.len(),
+        segments: ReviewSummary::from_statuses(segments.iter().map(|segment| {
+            file_contexts
+                .get(segment.file_index)
+                .map_or(SegmentReviewStatus::Untranslated, |context| {
+                    segment_status(context, segment, &segment_reviews)
+                })
+        })),
+        usage: job_state.usage.clone(),
+        file_errors: file_errors.len(),
+    });
+
+    emit_progress(
+        sink,
+        TranslationProgressEventPayload {
+            job_id: payload.job_id,
+            status: final_status.into(),
+            progress_pct: Some(final_progress),
+            cancel_requested: None,
+            log: Some(final_log),
+            translated_count: Some(processed_segments),
+            total_count: Some(total_segments),
+            file_name: last_file_name,
+            file_success: last_file_success,
+            file_progress_pct: None,
+            file_errors: clone_errors(&file_errors),
+            last_written: None,
+            checkpoint: if final_status == "completed" {
+                None
+            } else {
+                Some(job_state.checkpoint.clone())
+            },
+            usage: Some(job_state.usage.clone()),
+            retry: None,
+        },
+    );
+
+    if final_status == "completed" {
+        for context in &file_contexts {
+            if let Err(err) = clear_resume_metadata(&context.resume_metadata_path) {
+                warn!(
+                    "failed to clean resume metadata for {}: {}",
+                    context.relative_path, err
+                );
+            }
+        }
+    }
+}
+
+fn clone_errors(errors: &[TranslationFileErrorEntry]) -> Option<Vec<TranslationFileErrorEntry>> {
+    if errors.is_empty() {
+        None
+    } else {
+        Some(errors.to_vec())
+    }
+}
+
+/// Output path for a source file, placed where the game loads translations
+fn derive_output_relative_path(
+    relative_path: &str,
+    target_lang: &str,
+    layout: &dyn OutputLayout,
+) -> PathBuf {
+    layout.output_path(relative_path, target_lang)
+}
+
+/// Folder under the output root that receives files whose output path would
+/// overwrite a source file of the job.
+pub const COLLISION_MIRROR_DIR: &str = "_translated";
+
+fn canonical_or_self(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// True when writing to `output` would replace one of the job's source files.
+fn collides_with_source(output: &Path, source_paths: &HashSet<PathBuf>) -> bool {
+    source_paths.contains(&canonical_or_self(output))
+}
+
+/// Output path inside the collision mirror folder, keeping the relative layout.
+fn collision_mirror_path(output_relative_path: &Path, target_lang: &str) -> PathBuf {
+    Path::new(COLLISION_MIRROR_DIR)
+        .join(sanitized_language_tag(target_lang))
+        .join(output_relative_path)
+}
+
+/// Writes one review report per file and returns how many were written.
+fn write_review_reports(
+    metadata: &ReviewMetadata,
+    file_contexts: &[FileContext],
+    segments: &[Segment],
+    notes: &HashMap<(usize, usize), SegmentReviewNote>,
+) -> usize {
+    let mut written = 0;
+    for (file_index, context) in file_contexts.iter().enumerate() {
+        let reviews: Vec<SegmentReview> = segments
+            .iter()
+            .filter(|segment| segment.file_index == file_index)
+            .map(|segment| segment_review(context, segment, notes))
+            .collect();
+        if reviews.is_empty() {
+            continue;
+        }
+
+        let report = FileReviewReport::new(
+            metadata.clone(),
+            context.relative_path.clone(),
+            context.output_relative_path.to_string_lossy().to_string(),
+            reviews,
+        );
+        let path = review_report_path(&context.output_absolute_path);
+        match write_review_report(&path, &report) {
+            Ok(()) => written += 1,
+            Err(error) => warn!(
+                "failed to write review report {}: {}",
+                path.display(),
+                error
+            ),
+        }
+    }
+    written
+}
+
+/// Review entry of a segment: its output line and how it got there
+fn segment_review(
+    context: &FileContext,
+    segment: &Segment,
+    notes: &HashMap<(usize, usize), SegmentReviewNote>,
+) -> SegmentReview {
+    let translation = context
+        .translated_lines
+        .get(segment.line_index)
+        .cloned()
+        .flatten()
+        .map(|line| line.trim().to_string());
+    let note = notes.get(&(segment.file_index, segment.line_index));
+    let status = segment_status(context, segment, notes);
+    SegmentReview {
+        line_number: segment.line_number,
+        source: segment.text.clone(),
+        translation,
+        status,
+        usage: segment.usage.clone(),
+        notes: note.map(|note| note.notes.clone()).unwrap_or_default(),
+        recovery_steps: note
+            .map(|note| note.recovery_steps.clone())
+            .unwrap_or_default(),
+        confidence: note.and_then(|note| note.confidence),
+    }
+}
+
+/// How a segment ended up: its review note, or whether the output has a line
+fn segment_status(
+    context: &FileContext,
+    segment: &Segment,
+    notes: &HashMap<(usize, usize), SegmentReviewNote>,
+) -> SegmentReviewStatus {
+    match notes.get(&(segment.file_index, segment.line_index)) {
+        Some(note) => note.status,
+        None if matches!(
+            context.translated_lines.get(segment.line_index),
+            Some(Some(_))
+        ) =>
+        {
+            SegmentReviewStatus::Translated
+        }
+        None => SegmentReviewStatus::Untranslated,
+    }
+}
+
+/// Builds the job's QC report and writes it next to the outputs. Returns
+/// the number of output roots that received it.
+fn write_job_qc_report(
+    metadata: &ReviewMetadata,
+    file_contexts: &[FileContext],
+    segments: &[Segment],
+    notes: &HashMap<(usize, usize), SegmentReviewNote>,
+    validation_failures: Vec<ValidationFailureReport>,
+    file_errors: &[TranslationFileErrorEntry],
+    low_confidence_below: Option<u8>,
+) -> usize {
+    let qc_segments = segments
+        .iter()
+        .filter_map(|segment| {
+            let context = file_contexts.get(segment.file_index)?;
+            let review = segment_review(context, segment, notes);
+            Some(QcSegment {
+                file_path: context.relative_path.clone(),
+                line_number: review.line_number,
+                source: review.source,
+                translation: review.translation,
+                status: review.status,
+                messages: review.notes,
+                confidence: review.confidence,
+            })
+        })
+        .collect();
+    let file_errors = file_errors
+        .iter()
+        .map(|error| QcFileError {
+            file_path: error.file_path.clone(),
+            message: error.message.clone(),
+            code: error.code.clone(),
+        })
+        .collect();
+    let report = QcReport::new(
+        metadata.clone(),
+        qc_segments,
+        validation_failures,
+        file_errors,
+        low_confidence_below,
+    );
+
+    let output_roots: BTreeSet<PathBuf> = file_contexts
+        .iter()
+        .filter_map(|context| {
+            let depth = context.output_relative_path.components().count();
+            context.output_absolute_path.ancestors().nth(depth)
+        })
+        .map(Path::to_path_buf)
+        .collect();
+    let output_roots: Vec<PathBuf> = output_roots.into_iter().collect();
+    save_qc_report(&report, &output_roots)
+}
+
+/// The rolled-back, failed and strict-mode held segments of the job, kept
+/// for `retranslate_failed_segments` and `accept_original` (see `triage.rs`)
+fn failed_segments_for_triage(
+    job_id: &str,
+    file_settings: &[TranslationSettings],
+    file_contexts: &[FileContext],
+    segments: &[Segment],
+    notes: &HashMap<(usize, usize), SegmentReviewNote>,
+    validation_failures: &[ValidationFailureReport],
+) -> (Vec<TriageFile>, Vec<FailedSegment>) {
+    let mut files: Vec<TriageFile> = Vec::new();
+    let mut triage_indices: HashMap<usize, usize> = HashMap::new();
+    let mut failed: Vec<FailedSegment> = Vec::new();
+    for segment in segments {
+        let Some(note) = notes.get(&(segment.file_index, segment.line_index)) else {
+            continue;
+        };
+        if !matches!(
+            note.status,
+            SegmentReviewStatus::RolledBack
+                | SegmentReviewStatus::Failed
+                | SegmentReviewStatus::HeldForReview
+        ) {
+            continue;
+        }
+        let (Some(context), Some(settings)) = (
+            file_contexts.get(segment.file_index),
+            file_settings.get(segment.file_index),
+        ) else {
+            continue;
+        };
+        // Lines inside archives cannot be rewritten in place
+        if context.archive_path.is_some() {
+            continue;
+        }
+        let file_index = *triage_indices.entry(segment.file_index).or_insert_with(|| {
+            files.push(TriageFile {
+                file_path: context.relative_path.clone(),
+                output_path: context.output_absolute_path.clone(),
+                prompt: settings.prompt.clone(),
+                prompt_vars: settings.prompt_vars.clone(),
+                placeholder_patterns: segment
+                    .placeholder_patterns
+                    .iter()
+                    .map(|pattern| pattern.as_str().to_string())
+                    .collect(),
+            });
+            files.len() - 1
+        });
+        let failure = validation_failures
+            .iter()
+            .rev()
+            .find(|report| {
+                report.file == segment.relative_path && report.line as usize == segment.line_number
+            })
+            .cloned();
+        failed.push(FailedSegment {
+            segment_id: format!("{job_id}:{}", failed.len()),
+            file_index,
+            file_path: segment.relative_path.clone(),
+            line_number: segment.line_number,
+            source: segment.text.clone(),
+            prefix: segment.prefix.clone(),
+            suffix: segment.suffix.clone(),
+            status: note.status,
+            messages: note.notes.clone(),
+            failure,
+            resolution: None,
+            translation: None,
+        });
+    }
+
+    (files, failed)
+}
+
+/// Scores the job's translations (see `quality_estimation.rs`) and records
+/// the scores in the segment notes. Returns the number of scored segments
+/// and how many of them fell below the threshold.
+async fn run_quality_estimation(
+    config: &QualityEstimationConfig,
+    request_options: &RequestOptions,
+    file_settings: &[TranslationSettings],
+    file_contexts: &[FileContext],
+    segments: &[Segment],
+    notes: &mut HashMap<(usize, usize), SegmentReviewNote>,
+) -> (usize, usize) {
+    let client = (config.back_translation_model.is_some() || config.comet_endpoint.is_some())
+        .then(|| build_http_client(request_options).ok())
+        .flatten();
+    let mut scored = 0;
+    let mut low = 0;
+    for segment in segments {
+        let (Some(context), Some(settings)) = (
+            file_contexts.get(segment.file_index),
+            file_settings.get(segment.file_index),
+        ) else {
+            continue;
+        };
+        let Some(Some(line)) = context.translated_lines.get(segment.line_index) else {
+            continue;
+        };
+        let key = (segment.file_index, segment.line_index);
+        if notes.get(&key).is_some_and(|note| {
+            !matches!(
+                note.status,
+                SegmentReviewStatus::Translated | SegmentReviewStatus::Recovered
+            )
+        }) {
+            continue;
+        }
+        let translation = segment_translation(line, segment);
+        let heuristic = heuristic_score(&segment.text, translation, &settings.target_lang);
+
+        let mut back_translation = None;
+        let mut comet = None;
+        if let Some(client) = &client {
+            if let (Some(model), true) = (&config.back_translation_model, config.samples(scored)) {
+                if settings.source_lang != "auto" {
+                    let fragment = segment.protect_part(translation);
+                    match back_translate(client, settings, model, &fragment).await {
+                        Ok(text) => back_translation = Some(similarity(&segment.text, &text)),
+                        Err(error) => warn!(
+                            "back-translation of {}:{} failed: {}",
+                            segment.relative_path, segment.line_number, error
+                        ),
+                    }
+                }
+            }
+            if let Some(endpoint) = &config.comet_endpoint {
+                match comet_score(client, endpoint, &segment.text, translation).await {
+                    Ok(score) => comet = Some(score),
+                    Err(error) => warn!("quality scoring server {} failed: {}", endpoint, error),
+                }
+            }
+        }
+
+        let score = combine_scores(heuristic.score, back_translation, comet);
+        let note = notes.entry(key).or_insert_with(|| SegmentReviewNote {
+            status: SegmentReviewStatus::Translated,
+            notes: Vec::new(),
+            recovery_steps: Vec::new(),
+            confidence: None,
+        });
+        note.confidence = Some(score);
+        scored += 1;
+        if score < config.low_confidence_threshold {
+            low += 1;
+            let mut reasons = heuristic.reasons;
+            if let Some(similarity) = back_translation {
+                reasons.push(format!("역번역 유사도 {:.0}%", similarity * 100.0));
+            }
+            if let Some(comet) = comet {
+                reasons.push(format!("COMET 점수 {:.2}", comet));
+            }
+            note.notes.push(if reasons.is_empty() {
+                format!("신뢰도 낮음 ({score})")
+            } else {
+                format!("신뢰도 낮음 ({score}): {}", reasons.join(" "))
+            });
+        }
+    }
+    (scored, low)
+}
+
+/// Translated text of a segment's line, without its indentation and suffix
+fn segment_translation<'a>(line: &'a str, segment: &Segment) -> &'a str {
+    line.strip_prefix(segment.prefix.as_str())
+        .and_then(|rest| rest.strip_suffix(segment.suffix.as_str()))
+        .unwrap_or(line.trim())
+}
+
+/// Checks the job's translations for consistency (see `consistency.rs`) and,
+/// in harmonize mode, rewrites the differing lines in the file contexts and
+/// the resume state.
+fn run_consistency_pass(
+    mode: ConsistencyMode,
+    target_lang: &str,
+    user_glossary: &HashMap<String, String>,
+    profiles: &HashMap<PathBuf, Option<GameProfile>>,
+    files: &mut [FileContext],
+    segments: &[Segment],
+    job_state: &mut JobState,
+) -> ConsistencyReport {
+    let glossaries: HashMap<&PathBuf, HashMap<String, String>> = profiles
+        .iter()
+        .map(|(root, profile)| {
+            (
+                root,
+                glossary_terms(profile.as_ref(), target_lang, user_glossary),
+            )
+        })
+        .collect();
+    let no_glossary = HashMap::new();
+    let translations: Vec<Option<String>> = segments
+        .iter()
+        .map(|segment| {
+            let line = files
+                .get(segment.file_index)?
+                .translated_lines
+                .get(segment.line_index)?
+                .as_deref()?;
+            Some(segment_translation(line, segment).to_string())
+        })
+        .collect();
+    let (indices, entries): (Vec<usize>, Vec<TranslatedEntry>) = segments
+        .iter()
+        .zip(&translations)
+        .enumerate()
+        .filter_map(|(index, (segment, translation))| {
+            let entry = TranslatedEntry {
+                file: &segment.relative_path,
+                line_number: segment.line_number,
+                source: &segment.text,
+                translation: translation.as_deref()?,
+                glossary: glossaries
+                    .get(&files[segment.file_index].mod_install_path)
+                    .unwrap_or(&no_glossary),
+            };
+            Some((index, entry))
+        })
+        .unzip();
+
+    let mut report = check_consistency(&entries);
+    if mode == ConsistencyMode::Harmonize {
+        let changes = harmonize(&entries, &report);
+        report.harmonized = changes.len();
+        for (entry_index, translation) in changes {
+            let segment = &segments[indices[entry_index]];
+            let replacement = format!("{}{}{}", segment.prefix, translation, segment.suffix);
+            files[segment.file_index].translated_lines[segment.line_index] =
+                Some(replacement.clone());
+            if let Some(progress) = job_state.files.get_mut(&segment.relative_path) {
+                progress
+                    .replacements
+                    .insert(segment.line_index, replacement);
+            }
+        }
+    }
+    report
+}
+
+fn describe_consistency_report(report: &ConsistencyReport) -> String {
+    if report.is_clean() {
+        return "일관성 검사: 다르게 번역된 원문이나 용어가 없습니다.".to_string();
+    }
+    let mut message = format!(
+        "일관성 검사: 다르게 번역된 원문 {}건, 용어집과 다른 번역 {}건",
+        report.sources.len(),
+        report.terms.len()
+    );
+    if report.harmonized > 0 {
+        message.push_str(&format!(
+            ", {}개 세그먼트를 통일했습니다",
+            report.harmonized
+        ));
+    }
+    message
+}
+
+/// Moves translated lines out of the file contexts into an approval session so
+/// nothing is written until the user decides on each segment.
+fn stage_for_approval(
+    target_lang: &str,
+    mut files: Vec<FileContext>,
+    segments: &[Segment],
+) -> ApprovalSession {
+    let mut staged = Vec::new();
+    for segment in segments {
+        let Some(context) = files.get_mut(segment.file_index) else {
+            continue;
+        };
+        let Some(line) = context
+            .translated_lines
+            .get_mut(segment.line_index)
+            .and_then(Option::take)
+        else {
+            continue;
+        };
+        let translation = segment_translation(&line, segment).to_string();
+        staged.push(StagedSegment {
+            file_index: segment.file_index,
+            line_index: segment.line_index,
+            line_number: segment.line_number,
+            source: segment.text.clone(),
+            prefix: segment.prefix.clone(),
+            suffix: segment.suffix.clone(),
+            translation,
+            usage: segment.usage.clone(),
+            decision: SegmentDecision::Pending,
+        });
+    }
+
+    ApprovalSession {
+        target_lang: target_lang.to_string(),
+        written: vec![false; files.len()],
+        files,
+        segments: staged,
+        write_errors: Vec::new(),
+        writes_in_flight: 0,
+    }
+}
+
+/// Writes every regular file whose segments have all been decided, and
+/// completes the job once every segment is decided and written. The files
+/// are written after releasing the approval lock, so other decisions and
+/// commands are not held up by the disk.
+fn flush_approved_files(sink: &dyn ProgressSink, job_id: &str) {
+    let (ready, target_lang) = {
+        let Ok(mut sessions) = APPROVAL_SESSIONS.lock() else {
+            warn!("approval registry lock poisoned");
+            return;
+        };
+        let Some(session) = sessions.get_mut(job_id) else {
+            return;
+        };
+        (session.take_ready_files(), session.target_lang.clone())
+    };
+
+    let errors = write_approved_files(sink, job_id, &target_lang, &ready);
+
+    let finished = {
+        let Ok(mut sessions) = APPROVAL_SESSIONS.lock() else {
+            warn!("approval registry lock poisoned");
+            return;
+        };
+        let Some(session) = sessions.get_mut(job_id) else {
+            return;
+        };
+        session.write_errors.extend(errors);
+        if !ready.is_empty() {
+            session.writes_in_flight -= 1;
+        }
+        if session.is_finished() {
+            sessions.remove(job_id)
         } else {
-            "failed"
+            None
         }
-    } else {
-        "completed"
-    };
-    let mut final_log = if total_segments == 0 && file_errors.is_empty() {
-        "번역할 문자열이 없습니다.".to_string()
-    } else if final_status == "completed" {
-        "번역이 완료되었습니다.".to_string()
-    } else if final_status == "partial_success" {
-        "일부 파일을 번역하거나 저장하지 못했습니다.".to_string()
-    } else {
-        "번역을 완료하지 못했습니다.".to_string()
     };
+    if let Some(session) = finished {
+        finish_approval_session(sink, job_id, session);
+    }
+}
 
-    if !rolled_back_segments.is_empty() {
-        let preview: Vec<_> = rolled_back_segments.iter().take(3).cloned().collect();
-        let remainder = rolled_back_segments.len().saturating_sub(preview.len());
-        if !preview.is_empty() {
-            final_log.push(' ');
-            let detail = if remainder > 0 {
-                format!("{} 외 {}건", preview.join(", "), remainder)
-            } else {
-                preview.join(", ")
-            };
-            final_log.push_str(&format!(
-                "자동 롤백 {}건 ({detail}).",
-                rolled_back_segments.len()
-            ));
-        }
+/// Writes approved files and reports each one. Returns the files that could
+/// not be written.
+fn write_approved_files(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    target_lang: &str,
+    files: &[FileContext],
+) -> Vec<TranslationFileErrorEntry> {
+    let mut errors = Vec::new();
+    for context in files {
+        let (log, success, last_written) = match write_output_file(context) {
+            Ok(backup) => {
+                record_job_output(
+                    job_id,
+                    &context.output_relative_path,
+                    &context.output_absolute_path,
+                    backup.as_deref().map(Path::new),
+                    Vec::new(),
+                );
+                record_source_snapshot(context, target_lang);
+                (
+                    match backup {
+                        Some(backup) => format!(
+                            "{} 번역 결과를 저장했습니다. (백업: {})",
+                            context.relative_path, backup
+                        ),
+                        None => format!("{} 번역 결과를 저장했습니다.", context.relative_path),
+                    },
+                    true,
+                    Some(LastWrittenInfo {
+                        source_relative_path: context.relative_path.clone(),
+                        output_absolute_path: context
+                            .output_absolute_path
+                            .canonicalize()
+                            .unwrap_or_else(|_| context.output_absolute_path.clone())
+                            .to_string_lossy()
+                            .to_string(),
+                        output_relative_path: normalize_relative_display(
+                            &context.output_relative_path,
+                        ),
+                    }),
+                )
+            }
+            Err(failure) => {
+                errors.push(TranslationFileErrorEntry {
+                    file_path: context.relative_path.clone(),
+                    message: failure.message.clone(),
+                    code: Some(failure.code.into()),
+                });
+                (failure.message, false, None)
+            }
+        };
+
+        emit_progress(
+            sink,
+            TranslationProgressEventPayload {
+                job_id: job_id.to_string(),
+                status: "awaiting_approval".into(),
+                progress_pct: None,
+                cancel_requested: None,
+                log: Some(log),
+                translated_count: None,
+                total_count: None,
+                file_name: Some(context.relative_path.clone()),
+                file_success: Some(success),
+                file_progress_pct: None,
+                file_errors: None,
+                last_written,
+                checkpoint: None,
+                usage: None,
+                retry: None,
+            },
+        );
     }
+    errors
+}
 
-    let mut final_progress = if total_segments == 0 {
-        100.0
-    } else {
-        percentage(processed_segments, total_segments)
-    };
-    if final_progress < 100.0 && final_status != "failed" {
-        final_progress = 100.0;
+/// Writes pending archive entries and completes the job. The job is
+/// `completed` only when every file was written; it is `partial_success`
+/// when some were and `failed` when none were.
+fn finish_approval_session(sink: &dyn ProgressSink, job_id: &str, session: ApprovalSession) {
+    let mut errors = session.write_errors;
+
+    let archive_contexts: Vec<FileContext> = session
+        .files
+        .iter()
+        .filter(|context| context.archive_path.is_some())
+        .cloned()
+        .collect();
+    if !archive_contexts.is_empty() {
+        if let Err(error) =
+            save_archive_translations(job_id, &archive_contexts, &session.target_lang)
+        {
+            errors.extend(
+                archive_contexts
+                    .iter()
+                    .map(|context| TranslationFileErrorEntry {
+                        file_path: context.relative_path.clone(),
+                        message: format!("아카이브 저장 실패: {}", error),
+                        code: Some("ARCHIVE_WRITE_FAILED".into()),
+                    }),
+            );
+        }
     }
 
-    if final_status == "completed" {
-        clear_job_state(&payload.job_id);
+    let approved = session
+        .segments
+        .iter()
+        .filter(|segment| segment.decision == SegmentDecision::Approved)
+        .count();
+    let total = session.segments.len() as u32;
+    let (status, log) = if errors.is_empty() {
+        (
+            "completed",
+            format!("승인 완료: {approved}/{total}건의 번역을 저장했습니다."),
+        )
     } else {
-        save_job_state(&payload.job_id, job_state.clone());
+        let status = if errors.len() < session.files.len() {
+            "partial_success"
+        } else {
+            "failed"
+        };
+        (
+            status,
+            format!(
+                "승인 완료: 파일 {}개 중 {}개를 저장하지 못했습니다.",
+                session.files.len(),
+                errors.len()
+            ),
+        )
+    };
+    if status == "completed" {
+        clear_job_state(job_id);
     }
 
     emit_progress(
-        &app,
+        sink,
         TranslationProgressEventPayload {
-            job_id: payload.job_id,
-            status: final_status.into(),
-            progress_pct: Some(final_progress),
+            job_id: job_id.to_string(),
+            status: status.into(),
+            progress_pct: Some(100.0),
             cancel_requested: None,
-            log: Some(final_log),
-            translated_count: Some(processed_segments),
-            total_count: Some(total_segments),
-            file_name: last_file_name,
-            file_success: last_file_success,
-            file_errors: clone_errors(&file_errors),
+            log: Some(log),
+            translated_count: Some(total),
+            total_count: Some(total),
+            file_name: None,
+            file_success: None,
+            file_progress_pct: None,
+            file_errors: (!errors.is_empty()).then_some(errors),
             last_written: None,
-            checkpoint: if final_status == "completed" {
-                None
-            } else {
-                Some(job_state.checkpoint.clone())
-            },
+            checkpoint: None,
+            usage: None,
             retry: None,
         },
     );
+}
 
-    if final_status == "completed" {
-        for context in &file_contexts {
-            if let Err(err) = clear_resume_metadata(&context.resume_metadata_path) {
-                warn!(
-                    "failed to clean resume metadata for {}: {}",
-                    context.relative_path, err
-                );
-            }
+/// 출력 파일을 쓰지 못한 이유 (파일 오류로 보고할 메시지와 코드)
+#[derive(Debug)]
+struct WriteFailure {
+    message: String,
+    code: &'static str,
+}
+
+impl WriteFailure {
+    fn new(path: &Path, error: String, locked: bool) -> Self {
+        if locked {
+            return Self {
+                message: locked_file_message(path, &running_lock_holders()),
+                code: "FILE_LOCKED",
+            };
+        }
+        Self {
+            message: format!("Failed to write {}: {}", path.to_string_lossy(), error),
+            code: "WRITE_FAILED",
         }
     }
-}
 
-fn clone_errors(errors: &[TranslationFileErrorEntry]) -> Option<Vec<TranslationFileErrorEntry>> {
-    if errors.is_empty() {
-        None
-    } else {
-        Some(errors.to_vec())
+    fn from_io(path: &Path, error: io::Error) -> Self {
+        let locked = is_sharing_violation(&error);
+        Self::new(path, error.to_string(), locked)
     }
 }
 
-fn derive_output_relative_path(relative_path: &str, target_lang: &str) -> PathBuf {
-    let path = Path::new(relative_path);
-    let mut result = PathBuf::new();
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            result.push(parent);
-        }
+/// Writes a regular (non-archive) output file, backing up any existing file.
+/// Returns the backup path for display, or why the file could not be written.
+fn write_output_file(context: &FileContext) -> Result<Option<String>, WriteFailure> {
+    let output_absolute_path = &context.output_absolute_path;
+    let io_failure = |err: io::Error| WriteFailure::from_io(output_absolute_path, err);
+
+    if let Some(parent_dir) = output_absolute_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(io_failure)?;
     }
 
-    let output_name = path
-        .file_name()
-        .map(|name| build_output_filename(name, target_lang))
-        .unwrap_or_else(|| {
-            let lang = sanitized_language_tag(target_lang);
-            format!("translated.{lang}")
-        });
+    let contents = encode_translated_file(context);
+    let backup_path = if output_absolute_path.exists() {
+        // Fails before writing when the backup cannot be made, so a source
+        // file the user agreed to overwrite always keeps its original
+        backup_and_swap(output_absolute_path, &contents)
+            .map_err(|err| {
+                WriteFailure::new(output_absolute_path, err.to_string(), err.is_file_locked())
+            })?
+            .backup_path
+    } else {
+        write_atomic(output_absolute_path, &contents).map_err(io_failure)?;
+        None
+    };
 
-    result.push(output_name);
-    result
+    Ok(backup_path
+        .map(|path| path.to_string_lossy().to_string())
+        .filter(|path| !path.is_empty()))
 }
 
-fn sanitized_language_tag(target_lang: &str) -> String {
-    let trimmed = target_lang.trim();
-    if trimmed.is_empty() {
-        "translated".to_string()
-    } else {
-        trimmed.to_lowercase()
+/// Bytes of the translated file in the output encoding. Warns when the
+/// source encoding cannot hold the translation and UTF-8 is used instead.
+/// Binary files are the handler's merge, or the source if it fails.
+fn encode_translated_file(context: &FileContext) -> Vec<u8> {
+    if let Some(document) = &context.document {
+        if let Some(merged) = document.merge_bytes(&context.translated_lines) {
+            return merged.unwrap_or_else(|error| {
+                warn!(
+                    "{}: could not merge the translation, keeping the source: {}",
+                    context.relative_path, error
+                );
+                document
+                    .binary
+                    .as_ref()
+                    .map(|source| source.bytes.clone())
+                    .unwrap_or_default()
+            });
+        }
+    }
+    let (bytes, encoding) = context
+        .source_encoding
+        .encode_output(&render_translated_file(context), context.output_encoding);
+    if context.output_encoding == OutputEncoding::Preserve
+        && encoding != context.source_encoding.encoding
+    {
+        warn!(
+            "{}: {:?} cannot represent the translation, writing {:?} instead",
+            context.relative_path, context.source_encoding.encoding, encoding
+        );
     }
+    bytes
 }
 
-fn build_output_filename(source_name: &OsStr, target_lang: &str) -> String {
-    let lang = sanitized_language_tag(target_lang);
-    let temp = PathBuf::from(source_name);
-    let stem = temp.file_stem().and_then(|s| s.to_str());
-    let extension = temp.extension().and_then(|s| s.to_str());
-
-    match (stem, extension) {
-        (Some(stem), Some(ext)) => format!("{stem}.{lang}.{ext}"),
-        (Some(stem), None) => format!("{stem}.{lang}"),
-        (None, Some(ext)) => format!("translated.{lang}.{ext}"),
-        (None, None) => format!("translated.{lang}"),
+fn render_translated_file(context: &FileContext) -> String {
+    let body = render_file_body(context, true);
+    match &context.provenance_header {
+        Some(header) if !context.lines.is_empty() => prepend_provenance(&body, header),
+        _ => body,
     }
 }
 
-fn render_translated_file(context: &FileContext) -> String {
+/// Lines of the file joined back together, with the translated lines when
+/// `translated` is set and the source lines otherwise. Files translated entry
+/// by entry are the handler's merge instead, or the source if it fails.
+fn render_file_body(context: &FileContext, translated: bool) -> String {
+    if let Some(document) = &context.document {
+        if !translated {
+            return document.original.clone();
+        }
+        return document
+            .merge(&context.translated_lines)
+            .unwrap_or_else(|error| {
+                warn!(
+                    "{}: could not merge the translation, keeping the source: {}",
+                    context.relative_path, error
+                );
+                document.original.clone()
+            });
+    }
     if context.lines.is_empty() {
         return if context.had_trailing_newline {
             "\n".to_string()
@@ -1761,24 +5804,117 @@ fn render_translated_file(context: &FileContext) -> String {
             buffer.push('\n');
         }
 
-        if let Some(replacement) = &context.translated_lines[index] {
-            buffer.push_str(replacement);
-        } else {
-            buffer.push_str(original_line);
+        match &context.translated_lines[index] {
+            Some(replacement) if translated => buffer.push_str(replacement),
+            _ => buffer.push_str(original_line),
         }
     }
 
     if context.had_trailing_newline {
         buffer.push('\n');
     }
-
     buffer
 }
 
+/// Post-merge pass over the translated files. Markers still in translated
+/// lines, e.g. ones reinjected by placeholder recovery, are restored with
+/// the segment's token map. Then every file is parsed as its format; a file
+/// whose source parses but whose translation does not, or whose handler
+/// cannot merge its translation, is rolled back as a whole, since a single
+/// broken line can make the game reject the entire file. Returns the index
+/// and parser error of each rolled back file.
+fn check_merged_files(
+    file_contexts: &mut [FileContext],
+    segments: &[Segment],
+    job_state: &mut JobState,
+) -> Vec<(usize, String)> {
+    for segment in segments {
+        let Some(context) = file_contexts.get_mut(segment.file_index) else {
+            continue;
+        };
+        let Some(Some(line)) = context.translated_lines.get_mut(segment.line_index) else {
+            continue;
+        };
+        if !segment.marker_scheme.regex().is_match(line) {
+            continue;
+        }
+        let restored = segment.protect().restore_remaining(line).into_owned();
+        if let Some(progress) = job_state.files.get_mut(&segment.relative_path) {
+            progress
+                .replacements
+                .insert(segment.line_index, restored.clone());
+        }
+        *line = restored;
+    }
+
+    let mut rolled_back = Vec::new();
+    for (file_index, context) in file_contexts.iter_mut().enumerate() {
+        if context.translated_lines.iter().all(Option::is_none) {
+            continue;
+        }
+        let Some(error) = merge_error(context) else {
+            continue;
+        };
+        context.translated_lines.fill(None);
+        if let Some(progress) = job_state.files.get_mut(&context.relative_path) {
+            progress.replacements.clear();
+        }
+        rolled_back.push((file_index, error));
+    }
+    rolled_back
+}
+
+/// Why the translation of a file cannot be written as is: the handler
+/// cannot merge it, or the merge does not parse while the source does.
+/// Binary files are checked by reading the merged bytes back.
+fn merge_error(context: &FileContext) -> Option<String> {
+    if let Some(document) = &context.document {
+        if let (Some(source), Some(merged)) = (
+            &document.binary,
+            document.merge_bytes(&context.translated_lines),
+        ) {
+            return merged
+                .and_then(|bytes| document.handler.extract_bytes(&source.path, &bytes))
+                .err()
+                .map(|error| error.to_string());
+        }
+    }
+    let merged = match &context.document {
+        Some(document) => document.merge(&context.translated_lines),
+        None => Ok(render_file_body(context, true)),
+    };
+    let body = match merged {
+        Ok(body) => body,
+        Err(error) => return Some(error.to_string()),
+    };
+    if validate_for_format(context.format, &render_file_body(context, false)).is_err() {
+        return None;
+    }
+    validate_for_format(context.format, &body)
+        .err()
+        .map(|error| error.to_string())
+}
+
+/// Text of a translated archive entry. Entries are always rewritten as
+/// UTF-8, but keep the source line endings and BOM.
+fn render_archive_entry(context: &FileContext) -> String {
+    let text = FileMetadata::normalize_newlines(
+        &render_translated_file(context),
+        context.source_encoding.newline,
+    );
+    if context.source_encoding.has_bom {
+        format!("\u{FEFF}{}", text)
+    } else {
+        text
+    }
+}
+
 /// 아카이브 파일에 번역된 내용 저장
 /// 
-/// 같은 아카이브에 속한 모든 번역된 파일을 한 번에 처리합니다.
+/// 같은 아카이브에 속한 모든 번역된 파일을 한 번에 처리하고,
+/// 되돌릴 수 있도록 작업 결과 기록에 남깁니다.
 fn save_archive_translations(
+    job_id: &str,
     contexts: &[FileContext],
     target_lang: &str,
 ) -> Result<Vec<(PathBuf, usize)>, String> {
@@ -1798,13 +5934,15 @@ fn save_archive_translations(
     
     for (archive_path, group_contexts) in archive_groups {
         let mut modifications = ArchiveModification::new();
+        let mut written_entries = Vec::new();
         
         for context in &group_contexts {
             if let Some(entry_path) = &context.archive_entry_path {
-                let translated_content = render_translated_file(context);
+                let translated_content = render_archive_entry(context);
                 
                 // 대상 언어로 경로 변환 (예: en_us.json -> ko_kr.json)
                 let target_entry_path = derive_archive_entry_output_path(entry_path, target_lang);
+                written_entries.push(target_entry_path.clone());
                 
                 // 원본 경로와 다른 경우 새 파일로 추가, 같으면 업데이트
                 if target_entry_path != *entry_path {
@@ -1824,17 +5962,33 @@ fn save_archive_translations(
             .map(|p| p.join(".backup"))
             .unwrap_or_else(|| PathBuf::from(".backup"));
         
-        // 아카이브 수정 적용
-        archive::update_archive_with_translations(
+        // 아카이브 수정 적용 (7z/RAR은 옆의 ZIP에 저장)
+        let update = archive::update_archive_with_translations(
             &archive_path,
             modifications.updates.into_iter()
-                .chain(modifications.additions.into_iter())
+                .chain(modifications.additions)
                 .map(|(k, v)| (k, String::from_utf8_lossy(&v).to_string()))
                 .collect(),
             Some(&backup_dir),
         ).map_err(|e| format!("아카이브 수정 실패: {}", e))?;
         
-        results.push((archive_path, group_contexts.len()));
+        let mod_root = &group_contexts[0].mod_install_path;
+        let relative_path = update
+            .written_path
+            .strip_prefix(mod_root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| PathBuf::from(update.written_path.file_name().unwrap_or_default()));
+        record_job_output(
+            job_id,
+            &relative_path,
+            &update.written_path,
+            update.backup_path.as_deref(),
+            written_entries,
+        );
+        for context in &group_contexts {
+            record_source_snapshot(context, target_lang);
+        }
+        results.push((update.written_path, group_contexts.len()));
     }
     
     Ok(results)
@@ -1931,7 +6085,7 @@ fn save_resume_metadata(path: &Path, metadata: &ResumeMetadata) -> Result<(), St
     }
     let serialized = serde_json::to_vec(metadata)
         .map_err(|err| format!("resume metadata serialization error: {err}"))?;
-    fs::write(path, serialized).map_err(|err| format!("resume metadata write error: {err}"))
+    write_atomic(path, &serialized).map_err(|err| format!("resume metadata write error: {err}"))
 }
 
 fn clear_resume_metadata(path: &Path) -> Result<(), String> {
@@ -1949,8 +6103,8 @@ fn persist_partial_translation(context: &FileContext) -> Result<(), String> {
             .map_err(|err| format!("output directory creation failed: {err}"))?;
     }
 
-    let contents = render_translated_file(context);
-    fs::write(&context.output_absolute_path, contents)
+    let contents = encode_translated_file(context);
+    write_atomic(&context.output_absolute_path, &contents)
         .map_err(|err| format!("partial output write failed: {err}"))?;
 
     let metadata = ResumeMetadata {
@@ -1959,6 +6113,55 @@ fn persist_partial_translation(context: &FileContext) -> Result<(), String> {
     save_resume_metadata(&context.resume_metadata_path, &metadata)
 }
 
+/// Instructions added to the context of a segment translated again after
+/// its placeholder validation failed: every marker of the segment, plus the
+/// profile's fixed text when that was what went missing.
+pub fn validation_retry_note(
+    fragment: &ProtectedFragment,
+    report: &ValidationFailureReport,
+) -> String {
+    let mut tokens: Vec<&str> = fragment
+        .token_map()
+        .tokens
+        .iter()
+        .map(|token| token.marker.as_str())
+        .collect();
+    if report.code == ValidationErrorCode::ProfileRuleViolation {
+        tokens.extend(report.expected_protected.iter().map(String::as_str));
+    }
+    let mut note = format!(
+        "The previous translation was rejected by validation ({:?}).",
+        report.code
+    );
+    if !tokens.is_empty() {
+        note.push_str(" You must keep these tokens exactly as written: ");
+        note.push_str(&tokens.join(", "));
+    }
+    note
+}
+
+/// Model replacing `failed_model` for the rest of the job, picked from the
+/// job's fallbacks and the cached model list of `api_key`. The pick is added
+/// to `tried_models`.
+fn next_fallback_model(
+    payload: &StartTranslationJobPayload,
+    api_key: &str,
+    provider: ProviderId,
+    failed_model: &str,
+    tried_models: &mut Vec<String>,
+) -> Option<String> {
+    let catalog = cached_models(provider, Some(api_key));
+    let fallback = fallback_model(
+        provider,
+        failed_model,
+        &payload.model_fallbacks,
+        catalog.as_deref(),
+        tried_models,
+    )?;
+    tried_models.push(fallback.clone());
+    Some(fallback)
+}
+
 fn should_retry_error(error: &TranslationError) -> bool {
     matches!(
         error,
@@ -1968,41 +6171,134 @@ fn should_retry_error(error: &TranslationError) -> bool {
     )
 }
 
-fn emit_progress(app: &AppHandle, payload: TranslationProgressEventPayload) {
-    if let Err(error) = app.emit("translation-progress", payload) {
-        warn!("failed to emit translation progress: {}", error);
+fn emit_progress(sink: &dyn ProgressSink, payload: TranslationProgressEventPayload) {
+    if !matches!(payload.status.as_str(), "running" | "pending") {
+        mark_job_state_ended(&payload.job_id, &payload.status);
+    }
+    if let Ok(mut guard) = JOB_STATUSES.lock() {
+        guard
+            .entry(payload.job_id.clone())
+            .or_insert_with(|| JobStatusSnapshot::new(payload.job_id.clone()))
+            .apply(&payload);
+    }
+    if let Some(written) = &payload.last_written {
+        emit_job_event(
+            sink,
+            JobEvent::FileWritten {
+                job_id: payload.job_id.clone(),
+                source_relative_path: written.source_relative_path.clone(),
+                output_relative_path: written.output_relative_path.clone(),
+                output_absolute_path: written.output_absolute_path.clone(),
+            },
+        );
+    }
+    if let Some(event) = JobEvent::for_final_status(
+        &payload.job_id,
+        &payload.status,
+        payload.log.as_deref(),
+        payload.translated_count.unwrap_or(0),
+        payload.total_count.unwrap_or(0),
+    ) {
+        emit_job_event(sink, event);
     }
+    sink.emit("translation-progress", payload);
+}
+
+fn emit_job_event(sink: &dyn ProgressSink, event: JobEvent) {
+    sink.emit(JOB_EVENT_CHANNEL, event);
 }
 
 fn emit_cancelled_progress(
-    app: &AppHandle,
+    sink: &dyn ProgressSink,
     payload: &StartTranslationJobPayload,
     processed: u32,
-    total: u32,
+    job_progress: &WeightedProgress,
     last_file_name: &Option<String>,
     last_file_success: Option<bool>,
     file_errors: &[TranslationFileErrorEntry],
 ) {
     emit_progress(
-        app,
+        sink,
         TranslationProgressEventPayload {
             job_id: payload.job_id.clone(),
             status: "canceled".into(),
-            progress_pct: Some(percentage(processed, total)),
+            progress_pct: Some(job_progress.job_pct(processed)),
             cancel_requested: Some(true),
             log: Some("사용자가 작업을 중단했습니다.".into()),
             translated_count: Some(processed),
-            total_count: Some(total),
+            total_count: Some(job_progress.segments()),
             file_name: last_file_name.clone(),
             file_success: last_file_success,
+            file_progress_pct: None,
             file_errors: clone_errors(file_errors),
             last_written: None,
             checkpoint: None,
+            usage: current_usage(&payload.job_id),
             retry: None,
         },
     );
 }
 
+/// Job and file progress weighted by the characters of each segment, so a
+/// file with one huge line counts for its size instead of as one step
+struct WeightedProgress {
+    /// Characters in the segments before each index
+    done_before: Vec<u64>,
+    /// Characters before each file's first segment and in the whole file
+    files: HashMap<usize, (u64, u64)>,
+}
+
+impl WeightedProgress {
+    fn new(segments: &[Segment]) -> Self {
+        Self::from_weights(
+            segments
+                .iter()
+                .map(|segment| (segment.file_index, segment.text.chars().count() as u64)),
+        )
+    }
+
+    /// Builds the progress from `(file index, characters)` in segment order
+    fn from_weights(weights: impl IntoIterator<Item = (usize, u64)>) -> Self {
+        let mut done_before = vec![0];
+        let mut files: HashMap<usize, (u64, u64)> = HashMap::new();
+        for (file_index, weight) in weights {
+            // Empty segments still take a request
+            let weight = weight.max(1);
+            let before = *done_before.last().expect("starts with zero");
+            files.entry(file_index).or_insert((before, 0)).1 += weight;
+            done_before.push(before + weight);
+        }
+        Self { done_before, files }
+    }
+
+    fn segments(&self) -> u32 {
+        (self.done_before.len() - 1) as u32
+    }
+
+    fn done(&self, processed: u32) -> u64 {
+        let index = (processed as usize).min(self.done_before.len() - 1);
+        self.done_before[index]
+    }
+
+    /// Progress of the job once the first `processed` segments are done
+    fn job_pct(&self, processed: u32) -> f32 {
+        let total = *self.done_before.last().expect("starts with zero");
+        if total == 0 {
+            return 0.0;
+        }
+        (self.done(processed) as f64 / total as f64 * 100.0).clamp(0.0, 100.0) as f32
+    }
+
+    /// Progress of one file once the first `processed` segments are done
+    fn file_pct(&self, file_index: usize, processed: u32) -> f32 {
+        let Some((before, total)) = self.files.get(&file_index) else {
+            return 0.0;
+        };
+        let done = self.done(processed).saturating_sub(*before);
+        (done as f64 / *total as f64 * 100.0).clamp(0.0, 100.0) as f32
+    }
+}
+
 fn percentage(processed: u32, total: u32) -> f32 {
     if total == 0 {
         return 0.0;
@@ -2045,9 +6341,58 @@ struct BackoffStartedEventPayload {
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct BackoffCancelledEventPayload {
+struct BackoffCancelledEventPayload {
+    job_id: String,
+    by: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BudgetExceededEventPayload {
+    job_id: String,
+    limit_kind: String,
+    limit: f64,
+    used: f64,
+    usage: TranslationUsage,
+    checkpoint: TranslationCheckpoint,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConsistencyReportEventPayload {
+    job_id: String,
+    mode: ConsistencyMode,
+    report: ConsistencyReport,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewRequiredEventPayload {
+    job_id: String,
+    segments: Vec<ReviewSegment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelFallbackEventPayload {
+    job_id: String,
+    provider: ProviderId,
+    failed_model: String,
+    fallback_model: String,
+}
+
+/// How many of the job's files the include and exclude globs left out
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileFilterEventPayload {
     job_id: String,
-    by: String,
+    total_files: usize,
+    translated_files: usize,
+    /// Files each exclude glob left out; a file matching several globs is
+    /// counted for the first one only
+    excluded_by_pattern: HashMap<String, usize>,
+    /// Files no include glob matched
+    not_included: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -2058,10 +6403,11 @@ struct RetryStartedEventPayload {
 }
 
 fn emit_backoff_started(
-    app: &AppHandle,
+    sink: &dyn ProgressSink,
     job_id: &str,
     delay: Duration,
     attempt: u32,
+    max_attempts: u32,
     used_hint: bool,
     reason: &str,
 ) {
@@ -2070,40 +6416,162 @@ fn emit_backoff_started(
         job_id: job_id.to_string(),
         delay_ms,
         attempt,
-        max_attempts: MAX_RETRY_ATTEMPTS as u32,
+        max_attempts,
         reason: reason.to_string(),
         used_hint,
     };
+    emit_job_event(
+        sink,
+        JobEvent::Backoff {
+            job_id: job_id.to_string(),
+            delay_ms,
+            attempt,
+            max_attempts,
+            reason: reason.to_string(),
+        },
+    );
 
-    if let Err(error) = app.emit("translation-backoff-started", payload) {
-        warn!("failed to emit translation-backoff-started: {}", error);
-    }
+    sink.emit("translation-backoff-started", payload);
 }
 
-fn emit_backoff_cancelled(app: &AppHandle, job_id: &str, source: BackoffCancelSource) {
+fn emit_backoff_cancelled(sink: &dyn ProgressSink, job_id: &str, source: BackoffCancelSource) {
     let payload = BackoffCancelledEventPayload {
         job_id: job_id.to_string(),
         by: source.as_str().to_string(),
     };
 
-    if let Err(error) = app.emit("translation-backoff-cancelled", payload) {
-        warn!("failed to emit translation-backoff-cancelled: {}", error);
-    }
+    sink.emit("translation-backoff-cancelled", payload);
 }
 
-fn emit_retry_started(app: &AppHandle, job_id: &str, attempt: u32) {
+fn emit_retry_started(sink: &dyn ProgressSink, job_id: &str, attempt: u32) {
     let payload = RetryStartedEventPayload {
         job_id: job_id.to_string(),
         attempt,
     };
+    emit_job_event(
+        sink,
+        JobEvent::Retry {
+            job_id: job_id.to_string(),
+            attempt,
+        },
+    );
+
+    sink.emit("translation-retry-started", payload);
+}
+
+fn emit_model_fallback(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    provider: ProviderId,
+    failed_model: &str,
+    fallback_model: &str,
+) {
+    warn!(
+        "{} model '{}' is unavailable; continuing job {} with '{}'",
+        provider, failed_model, job_id, fallback_model
+    );
+    let payload = ModelFallbackEventPayload {
+        job_id: job_id.to_string(),
+        provider,
+        failed_model: failed_model.to_string(),
+        fallback_model: fallback_model.to_string(),
+    };
+
+    sink.emit("translation-model-fallback", payload);
+}
+
+fn emit_budget_exceeded(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    exceeded: BudgetExceeded,
+    job_state: &JobState,
+) {
+    let payload = BudgetExceededEventPayload {
+        job_id: job_id.to_string(),
+        limit_kind: exceeded.kind.as_str().to_string(),
+        limit: exceeded.limit,
+        used: exceeded.used,
+        usage: job_state.usage.clone(),
+        checkpoint: job_state.checkpoint.clone(),
+    };
+
+    sink.emit("budget-exceeded", payload);
+}
+
+/// Counts the job's files each glob leaves out
+fn file_filter_summary(payload: &StartTranslationJobPayload) -> FileFilterEventPayload {
+    let mut excluded_by_pattern: HashMap<String, usize> = payload
+        .exclude_globs
+        .iter()
+        .map(|glob| (glob.clone(), 0))
+        .collect();
+    let mut not_included = 0;
+    for file in &payload.files {
+        match payload.glob_skip(&file.relative_path) {
+            Some(GlobSkip::Excluded(index)) => {
+                *excluded_by_pattern
+                    .entry(payload.exclude_globs[index].clone())
+                    .or_default() += 1;
+            }
+            Some(GlobSkip::NotIncluded) => not_included += 1,
+            None => {}
+        }
+    }
+    let excluded: usize = excluded_by_pattern.values().sum();
+    FileFilterEventPayload {
+        job_id: payload.job_id.clone(),
+        total_files: payload.files.len(),
+        translated_files: payload.files.len() - excluded - not_included,
+        excluded_by_pattern,
+        not_included,
+    }
+}
+
+fn emit_consistency_report(
+    sink: &dyn ProgressSink,
+    job_id: &str,
+    mode: ConsistencyMode,
+    report: ConsistencyReport,
+) {
+    let payload = ConsistencyReportEventPayload {
+        job_id: job_id.to_string(),
+        mode,
+        report,
+    };
+
+    sink.emit("translation-consistency-report", payload);
+}
+
+/// Strict mode keeps the source of translations the validator had to
+/// repair, so a reviewer sees every repair before it is written
+fn held_by_strict_mode(payload: &StartTranslationJobPayload, success: &ValidationSuccess) -> bool {
+    payload.strict_mode && success.recovered_with_warning
+}
+
+fn emit_review_required(sink: &dyn ProgressSink, job_id: &str, segments: &[ReviewSegment]) {
+    let payload = ReviewRequiredEventPayload {
+        job_id: job_id.to_string(),
+        segments: segments.to_vec(),
+    };
+
+    sink.emit("review-required", payload);
+}
 
-    if let Err(error) = app.emit("translation-retry-started", payload) {
-        warn!("failed to emit translation-retry-started: {}", error);
+fn describe_budget_exceeded(exceeded: BudgetExceeded) -> String {
+    match exceeded.kind {
+        BudgetLimitKind::Tokens => format!(
+            "토큰 한도({:.0})에 도달하여 작업을 일시 중지했습니다. 사용량: {:.0} 토큰",
+            exceeded.limit, exceeded.used
+        ),
+        BudgetLimitKind::Cost => format!(
+            "비용 한도(${:.2})에 도달하여 작업을 일시 중지했습니다. 예상 비용: ${:.4}",
+            exceeded.limit, exceeded.used
+        ),
     }
 }
 
 async fn wait_with_cancellation(
-    app: &AppHandle,
+    sink: &dyn ProgressSink,
     job_id: &str,
     cancel_flag: &Arc<AtomicBool>,
     controller: Arc<BackoffController>,
@@ -2117,7 +6585,7 @@ async fn wait_with_cancellation(
                 } else {
                     BackoffCancelSource::Logic
                 };
-                emit_backoff_cancelled(app, job_id, source);
+                emit_backoff_cancelled(sink, job_id, source);
             }
         }
         return BackoffWaitOutcome::Completed;
@@ -2131,7 +6599,7 @@ async fn wait_with_cancellation(
                 } else {
                     BackoffCancelSource::Logic
                 };
-                emit_backoff_cancelled(app, job_id, source);
+                emit_backoff_cancelled(sink, job_id, source);
             }
         }
         return BackoffWaitOutcome::JobCancelled;
@@ -2161,7 +6629,7 @@ async fn wait_with_cancellation(
     });
 
     if let Some(source) = source {
-        emit_backoff_cancelled(app, job_id, source);
+        emit_backoff_cancelled(sink, job_id, source);
     }
 
     outcome
@@ -2180,6 +6648,54 @@ async fn wait_for_cancel_flag(cancel_flag: Arc<AtomicBool>) {
     }
 }
 
+pub fn build_http_client(options: &RequestOptions) -> reqwest::Result<Client> {
+    Client::builder()
+        .connect_timeout(options.connect_timeout())
+        .timeout(options.request_timeout())
+        .build()
+}
+
+/// Pings the provider before any files are scanned so a rejected key or an
+/// unknown model fails the job up front. Other failures are only logged; the
+/// regular retry handling deals with them once segments are sent.
+async fn preflight_check(
+    settings: &TranslationSettings,
+    options: &RequestOptions,
+) -> Result<(), TranslationError> {
+    let Ok(client) = build_http_client(options) else {
+        return Ok(());
+    };
+    match ping_provider(&client, settings).await {
+        Err(error) if error.is_fatal() => Err(error),
+        Err(error) => {
+            warn!("provider preflight check failed: {}", error);
+            Ok(())
+        }
+        Ok(()) => Ok(()),
+    }
+}
+
+fn format_preflight_error(error: &TranslationError) -> String {
+    match error {
+        TranslationError::Unauthorized { message, .. } => {
+            format!("API 키가 거부되어 작업을 시작하지 않았습니다: {message}")
+        }
+        TranslationError::Forbidden { message, .. } => {
+            format!("요청이 거부되어 작업을 시작하지 않았습니다: {message}")
+        }
+        TranslationError::ModelNotFound {
+            model_id, message, ..
+        } => {
+            format!("모델 '{model_id}'을(를) 사용할 수 없어 작업을 시작하지 않았습니다: {message}")
+        }
+        TranslationError::OfflineMode { .. } => {
+            "오프라인 모드에서는 번역 작업을 시작할 수 없습니다. 설정에서 오프라인 모드를 해제하세요."
+                .to_string()
+        }
+        other => format!("번역기 연결 확인에 실패했습니다: {other}"),
+    }
+}
+
 fn error_code_for(error: &TranslationError) -> &'static str {
     match error {
         TranslationError::RateLimited { .. } => "RATE_LIMITED",
@@ -2188,7 +6704,10 @@ fn error_code_for(error: &TranslationError) -> &'static str {
         TranslationError::Unauthorized { .. } => "UNAUTHORIZED",
         TranslationError::Forbidden { .. } => "FORBIDDEN",
         TranslationError::ModelNotFound { .. } => "MODEL_NOT_FOUND",
+        TranslationError::ContextOverflow { .. } => "CONTEXT_OVERFLOW",
+        TranslationError::OfflineMode { .. } => "OFFLINE_MODE",
         TranslationError::PlaceholderMismatch(_) => "PLACEHOLDER_MISMATCH",
+        TranslationError::StructureMismatch(_) => "STRUCTURE_MISMATCH",
         TranslationError::IoError { .. } => "IO_ERROR",
     }
 }
@@ -2224,6 +6743,18 @@ fn format_translation_error(segment: &Segment, error: &TranslationError) -> Stri
         TranslationError::IoError { message, .. } => {
             format!("{location} 번역 파일 처리 중 I/O 오류가 발생했습니다: {message}")
         }
+        TranslationError::OfflineMode { .. } => {
+            format!("{location} 오프라인 모드라서 번역 요청을 보내지 않았습니다.")
+        }
+        TranslationError::ContextOverflow {
+            model_id,
+            estimated_tokens,
+            context_window,
+            ..
+        } => format!(
+            "{location} 문장이 너무 길어 보내지 않았습니다: 약 {estimated_tokens} 토큰으로 \
+             모델 '{model_id}'의 컨텍스트 창({context_window} 토큰)을 넘습니다."
+        ),
         TranslationError::PlaceholderMismatch(missing) => {
             if missing.is_empty() {
                 format!("{location} 번역 중 자리표시자 검증에 실패했습니다.")
@@ -2231,6 +6762,9 @@ fn format_translation_error(segment: &Segment, error: &TranslationError) -> Stri
                 format!("{location} 번역 중 자리표시자 누락: {}", missing.join(", "))
             }
         }
+        TranslationError::StructureMismatch(message) => {
+            format!("{location} 긴 문단 번역 결과의 구조가 원문과 다릅니다: {message}")
+        }
     }
 }
 
@@ -2264,6 +6798,1250 @@ fn format_file_error_message(segment: &Segment, error: &TranslationError) -> Str
         TranslationError::IoError { message, .. } => {
             format!("A local I/O error occurred while processing the file: {message}")
         }
+        TranslationError::OfflineMode { .. } => {
+            "Translation requests are not sent while offline mode is on".to_string()
+        }
+        TranslationError::ContextOverflow {
+            model_id,
+            estimated_tokens,
+            context_window,
+            ..
+        } => format!(
+            "A segment of about {estimated_tokens} tokens does not fit the \
+             {context_window}-token context window of '{model_id}'"
+        ),
         TranslationError::PlaceholderMismatch(_) => format_translation_error(segment, error),
+        TranslationError::StructureMismatch(message) => {
+            format!(
+                "The translated document no longer matches the source paragraph layout: {message}"
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_inputs_are_normalized_before_launch() {
+        let mut files = vec![TranslationFileInput {
+            relative_path: r"Mods\Pack.zip\.\lang\ko_kr.json".into(),
+            mod_install_path: " /mods/1234 ".into(),
+            archive_path: Some(r".\Mods\Pack.zip".into()),
+            archive_entry_path: Some(r"assets\\lang\en_us.json".into()),
+        }];
+        normalize_file_inputs(&mut files).unwrap();
+        assert_eq!(files[0].relative_path, "Mods/Pack.zip/lang/ko_kr.json");
+        assert_eq!(files[0].mod_install_path, "/mods/1234");
+        assert_eq!(files[0].archive_path.as_deref(), Some("Mods/Pack.zip"));
+        assert_eq!(
+            files[0].archive_entry_path.as_deref(),
+            Some("assets/lang/en_us.json")
+        );
+
+        files[0].relative_path = r"..\outside.xml".into();
+        assert!(normalize_file_inputs(&mut files).is_err());
+    }
+
+    #[test]
+    fn target_lang_accepts_one_or_several_languages() {
+        let payload: StartTranslationJobPayload = serde_json::from_value(serde_json::json!({
+            "jobId": "job-1",
+            "provider": "gemini",
+            "modelId": "gemini-2.5-flash",
+            "files": [],
+            "targetLang": ["ko", " ja ", "", "KO", "zh-CN"],
+        }))
+        .unwrap();
+        assert_eq!(payload.target_languages(), ["ko", "ja", "zh-CN"]);
+        let chinese = payload.for_target_language("zh-CN");
+        assert_eq!(chinese.job_id, "job-1-zh-cn");
+        assert_eq!(chinese.target_languages(), ["zh-CN"]);
+
+        let single: StartTranslationJobPayload = serde_json::from_value(serde_json::json!({
+            "jobId": "job-2",
+            "provider": "gemini",
+            "modelId": "gemini-2.5-flash",
+            "files": [],
+            "targetLang": "ja",
+        }))
+        .unwrap();
+        assert_eq!(single.target_languages(), ["ja"]);
+        let unset = StartTranslationJobPayload {
+            target_lang: None,
+            ..single
+        };
+        assert_eq!(unset.target_languages(), ["ko"]);
+    }
+
+    #[test]
+    fn globs_leave_out_excluded_and_unlisted_files() {
+        let payload: StartTranslationJobPayload = serde_json::from_value(serde_json::json!({
+            "jobId": "job-1",
+            "provider": "gemini",
+            "modelId": "gemini-2.5-flash",
+            "files": [],
+            "includeGlobs": ["lang/**", "*.cfg"],
+            "excludeGlobs": ["README*", "lang/old/"],
+        }))
+        .unwrap();
+        assert_eq!(payload.glob_skip("lang/en_us.json"), None);
+        assert_eq!(payload.glob_skip("locale/en/items.cfg"), None);
+        assert_eq!(
+            payload.glob_skip("lang/README.md"),
+            Some(GlobSkip::Excluded(0))
+        );
+        assert_eq!(
+            payload.glob_skip("lang/old/en_us.json"),
+            Some(GlobSkip::Excluded(1))
+        );
+        assert_eq!(
+            payload.glob_skip("CHANGELOG.txt"),
+            Some(GlobSkip::NotIncluded)
+        );
+
+        let unfiltered = StartTranslationJobPayload {
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            ..payload
+        };
+        assert_eq!(unfiltered.glob_skip("CHANGELOG.txt"), None);
+    }
+
+    #[test]
+    fn file_filter_summary_counts_files_per_glob() {
+        let files = [
+            "lang/en_us.json",
+            "lang/README.md",
+            "lang/old/en_us.json",
+            "lang/old/de_de.json",
+            "CHANGELOG.txt",
+        ];
+        let payload = StartTranslationJobPayload {
+            include_globs: vec!["lang/**".into()],
+            exclude_globs: vec!["README*".into(), "lang/old/".into(), "*.png".into()],
+            ..StartTranslationJobPayload::new(
+                "job-1",
+                "gemini",
+                "gemini-2.5-flash",
+                files
+                    .iter()
+                    .map(|path| TranslationFileInput::new(*path, "/mods/alpha"))
+                    .collect(),
+            )
+        };
+
+        let summary = file_filter_summary(&payload);
+        assert_eq!(summary.total_files, 5);
+        assert_eq!(summary.translated_files, 1);
+        assert_eq!(summary.not_included, 1);
+        assert_eq!(
+            summary.excluded_by_pattern,
+            HashMap::from([
+                ("README*".to_string(), 1),
+                ("lang/old/".to_string(), 2),
+                ("*.png".to_string(), 0),
+            ])
+        );
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["excludedByPattern"]["lang/old/"], 2);
+    }
+
+    #[test]
+    fn job_state_roundtrips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs").join("job-1.json");
+
+        let mut state = JobState::new();
+        state.checkpoint = TranslationCheckpoint {
+            current_file_path: Some("lang/en.json".into()),
+            next_line_index: Some(12),
+            translated_count: 11,
+            total_count: 40,
+        };
+        let mut progress = FileProgress::new(FileSignature {
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            hash: 42,
+            len: 128,
+        });
+        progress.replacements.insert(3, "번역된 줄".into());
+        state.files.insert("lang/en.json".into(), progress);
+        state.usage.record(Some(TokenUsage::new(100, 20)), None);
+
+        write_job_state_file(&path, &state).unwrap();
+        let restored = read_job_state_file(&path).unwrap();
+
+        assert_eq!(restored.checkpoint.next_line_index, Some(12));
+        assert_eq!(restored.checkpoint.translated_count, 11);
+        assert_eq!(restored.usage.total_tokens, 120);
+        let file = &restored.files["lang/en.json"];
+        assert_eq!(file.signature, state.files["lang/en.json"].signature);
+        assert_eq!(
+            file.replacements.get(&3).map(String::as_str),
+            Some("번역된 줄")
+        );
+    }
+
+    #[test]
+    fn interrupted_jobs_are_recoverable_with_checked_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = |name: &str, contents: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            JobOutput {
+                relative_path: name.into(),
+                sha256: file_checksum(&path),
+                absolute_path: path,
+                backup_path: None,
+                created: true,
+                archive_entries: Vec::new(),
+            }
+        };
+        let intact = output("ko.json", "{}");
+        let modified = output("ko.xml", "<a/>");
+        fs::write(&modified.absolute_path, "<a").unwrap();
+        let missing = output("ko.txt", "");
+        fs::remove_file(&missing.absolute_path).unwrap();
+
+        let mut state = JobState::new();
+        let mut progress = FileProgress::new(FileSignature {
+            modified: None,
+            hash: 1,
+            len: 1,
+        });
+        progress.replacements.insert(0, "번역".into());
+        state.files.insert("en.json".into(), progress);
+
+        let job = recoverable_job(
+            "job-1".into(),
+            state.clone(),
+            None,
+            vec![intact, modified, missing],
+        )
+        .unwrap();
+        let states: Vec<_> = job.outputs.iter().map(|output| output.state).collect();
+        assert_eq!(
+            states,
+            [
+                RecoveredOutputState::Intact,
+                RecoveredOutputState::Modified,
+                RecoveredOutputState::Missing
+            ]
+        );
+        assert_eq!(job.files_in_progress, 1);
+        assert_eq!(
+            job.options,
+            [
+                RecoveryOption::Resume,
+                RecoveryOption::Rollback,
+                RecoveryOption::Discard
+            ]
+        );
+
+        let nothing_written =
+            recoverable_job("job-2".into(), state.clone(), None, Vec::new()).unwrap();
+        assert_eq!(
+            nothing_written.options,
+            [RecoveryOption::Resume, RecoveryOption::Discard]
+        );
+
+        state.ended_with = Some("failed".into());
+        assert!(recoverable_job("job-3".into(), state, None, Vec::new()).is_none());
+    }
+
+    #[test]
+    fn recovered_jobs_keep_ids_the_file_name_sanitizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = JobState::new();
+        state.job_id = "mods/alpha:job 1".into();
+        write_job_state_file(&dir.path().join("mods_alpha_job_1.json"), &state).unwrap();
+        let legacy = JobState::new();
+        write_job_state_file(&dir.path().join("job-2.json"), &legacy).unwrap();
+
+        let mut ids: Vec<String> = recoverable_jobs_in(dir.path())
+            .into_iter()
+            .map(|job| job.job_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["job-2", "mods/alpha:job 1"]);
+    }
+
+    #[test]
+    fn outputs_that_would_overwrite_a_source_are_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let lang = dir.path().join("lang");
+        fs::create_dir_all(&lang).unwrap();
+        fs::write(lang.join("en.properties"), "a=b").unwrap();
+        fs::write(lang.join("en.ko.properties"), "a=b").unwrap();
+        let sources: HashSet<PathBuf> = ["lang/en.properties", "lang/en.ko.properties"]
+            .iter()
+            .map(|relative| canonical_or_self(&dir.path().join(relative)))
+            .collect();
+
+        let output = derive_output_relative_path("lang/en.properties", "ko", &SuffixLayout);
+        assert!(collides_with_source(&dir.path().join(&output), &sources));
+        let other = derive_output_relative_path("lang/en.ko.properties", "ko", &SuffixLayout);
+        assert!(!collides_with_source(&dir.path().join(&other), &sources));
+
+        assert_eq!(
+            collision_mirror_path(&output, "KO"),
+            Path::new("_translated/ko/lang/en.ko.properties")
+        );
+    }
+
+    #[test]
+    fn unreadable_job_state_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.json");
+        fs::write(&path, "{ not json").unwrap();
+        assert!(read_job_state_file(&path).is_none());
+        assert!(read_job_state_file(&dir.path().join("missing.json")).is_none());
+    }
+
+    fn properties_context(source: &[u8]) -> FileContext {
+        let (content, source_encoding) = FileMetadata::decode_bytes(source).unwrap();
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        FileContext {
+            relative_path: "lang/en.properties".into(),
+            mod_install_path: PathBuf::from("mod"),
+            translated_lines: vec![None; lines.len()],
+            lines,
+            had_trailing_newline: content.ends_with('\n'),
+            output_relative_path: PathBuf::from("lang/en.ko.properties"),
+            output_absolute_path: PathBuf::from("mod/lang/en.ko.properties"),
+            resume_metadata_path: PathBuf::from("mod/lang/.resume/en.ko.properties.resume.json"),
+            resume_line_index: 0,
+            archive_path: None,
+            archive_entry_path: None,
+            provenance_header: None,
+            source_encoding,
+            output_encoding: OutputEncoding::default(),
+            format: FileFormat::Properties,
+            document: None,
+        }
+    }
+
+    #[test]
+    fn output_keeps_source_line_endings_and_bom() {
+        let mut context = properties_context(b"\xEF\xBB\xBFgreeting=Hello\r\nfarewell=Bye\r\n");
+        context.translated_lines[0] = Some("greeting=안녕".into());
+
+        let mut expected = vec![0xEF, 0xBB, 0xBF];
+        expected.extend_from_slice("greeting=안녕\r\nfarewell=Bye\r\n".as_bytes());
+        assert_eq!(encode_translated_file(&context), expected);
+        assert_eq!(
+            render_archive_entry(&context),
+            "\u{FEFF}greeting=안녕\r\nfarewell=Bye\r\n"
+        );
+
+        let plain = properties_context(b"greeting=Hello\nfarewell=Bye");
+        assert_eq!(
+            encode_translated_file(&plain),
+            b"greeting=Hello\nfarewell=Bye"
+        );
+    }
+
+    #[test]
+    fn approval_staging_holds_back_translated_lines() {
+        let mut context = properties_context("  greeting=Hello\nfarewell=Bye\n".as_bytes());
+        context.translated_lines[0] = Some("  greeting=안녕".into());
+        let segment = |line_index: usize, prefix: &str, text: &str| Segment {
+            file_index: 0,
+            relative_path: "lang/en.properties".into(),
+            line_index,
+            line_number: line_index + 1,
+            text: text.into(),
+            prefix: prefix.into(),
+            suffix: String::new(),
+            note: None,
+            usage: None,
+            surroundings: None,
+            placeholder_patterns: Arc::default(),
+            script: None,
+            marker_scheme: MarkerScheme::default(),
+            protected: None,
+        };
+        let segments = vec![
+            segment(0, "  greeting=", "Hello"),
+            segment(1, "farewell=", "Bye"),
+        ];
+
+        let session = stage_for_approval("ko", vec![context], &segments);
+
+        assert_eq!(session.segments.len(), 1);
+        assert_eq!(session.segments[0].translation, "안녕");
+        assert_eq!(session.files[0].translated_lines[0], None);
+        assert!(!session.file_decided(0));
+        assert_eq!(
+            render_translated_file(&session.files[0]),
+            "  greeting=Hello\nfarewell=Bye\n"
+        );
+    }
+
+    #[test]
+    fn approval_session_fails_when_approved_files_cannot_be_written() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file where the output folder should be makes the write fail
+        fs::write(dir.path().join("lang"), "").unwrap();
+        let mut context = properties_context(b"greeting=Hello\n");
+        context.output_absolute_path = dir.path().join("lang/en.ko.properties");
+        context.translated_lines[0] = Some("greeting=안녕".into());
+        let segments = vec![Segment {
+            file_index: 0,
+            relative_path: "lang/en.properties".into(),
+            line_index: 0,
+            line_number: 1,
+            text: "Hello".into(),
+            prefix: "greeting=".into(),
+            suffix: String::new(),
+            note: None,
+            usage: None,
+            surroundings: None,
+            placeholder_patterns: Arc::default(),
+            script: None,
+            marker_scheme: MarkerScheme::default(),
+            protected: None,
+        }];
+        let job_id = "approval-write-failure";
+        APPROVAL_SESSIONS.lock().unwrap().insert(
+            job_id.into(),
+            stage_for_approval("ko", vec![context], &segments),
+        );
+
+        let sink = crate::job::sink::MemorySink::new();
+        decide_segment(&sink, job_id, 0, SegmentDecision::Approved, None).unwrap();
+
+        assert!(!APPROVAL_SESSIONS.lock().unwrap().contains_key(job_id));
+        let progress = sink.payloads("translation-progress");
+        let last = progress.last().unwrap();
+        assert_eq!(last["status"], "failed");
+        assert_eq!(last["fileErrors"][0]["filePath"], "lang/en.properties");
+        assert_eq!(last["fileErrors"][0]["code"], "WRITE_FAILED");
+        assert!(progress
+            .iter()
+            .all(|payload| payload["status"] != "completed"));
+    }
+
+    #[test]
+    fn strict_mode_queues_recovered_segments_for_retranslation() {
+        let source = "Visit https://example.com";
+        let validator_segment = ValidatorSegment::new(
+            "lang/en.properties".into(),
+            1,
+            "line_0".into(),
+            source.into(),
+            source.into(),
+        );
+        let success = PlaceholderValidator::with_default_config()
+            .validate(&validator_segment, "방문하세요")
+            .unwrap();
+        let mut payload =
+            StartTranslationJobPayload::new("job-strict", "gemini", "gemini-2.5-flash", Vec::new());
+        assert!(!held_by_strict_mode(&payload, &success));
+        payload.strict_mode = true;
+        assert!(held_by_strict_mode(&payload, &success));
+
+        let segment = |line_index: usize, prefix: &str, text: &str| Segment {
+            file_index: 0,
+            relative_path: "lang/en.properties".into(),
+            line_index,
+            line_number: line_index + 1,
+            text: text.into(),
+            prefix: prefix.into(),
+            suffix: String::new(),
+            note: None,
+            usage: None,
+            surroundings: None,
+            placeholder_patterns: Arc::default(),
+            script: None,
+            marker_scheme: MarkerScheme::default(),
+            protected: None,
+        };
+        let segments = vec![segment(0, "link=", source), segment(1, "farewell=", "Bye")];
+        let mut context = properties_context(b"link=Visit https://example.com\nfarewell=Bye\n");
+        context.translated_lines[1] = Some("farewell=안녕".into());
+        let note = |status: SegmentReviewStatus| SegmentReviewNote {
+            status,
+            notes: Vec::new(),
+            recovery_steps: success.autofix.steps.clone(),
+            confidence: None,
+        };
+        let notes = HashMap::from([
+            ((0, 0), note(SegmentReviewStatus::HeldForReview)),
+            ((0, 1), note(SegmentReviewStatus::Translated)),
+        ]);
+        let settings = TranslationSettings {
+            provider: ProviderId::Gemini,
+            api_key: String::new(),
+            model_id: payload.model_id.clone(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            params: ModelParams::default(),
+            prompt: Default::default(),
+            prompt_vars: PromptVars::default(),
+            rate_limit: RateLimitConfig::default(),
+            base_url: None,
+        };
+
+        let (files, queued) = failed_segments_for_triage(
+            &payload.job_id,
+            &[settings],
+            std::slice::from_ref(&context),
+            &segments,
+            &notes,
+            &[],
+        );
+        assert_eq!(files.len(), 1);
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].segment_id, "job-strict:0");
+        assert_eq!(queued[0].status, SegmentReviewStatus::HeldForReview);
+        assert_eq!(queued[0].source, source);
+        // The held line keeps its source in the output until it is retranslated
+        assert_eq!(
+            render_translated_file(&context),
+            "link=Visit https://example.com\nfarewell=안녕\n"
+        );
+    }
+
+    #[test]
+    fn validation_retry_note_names_the_tokens_to_keep() {
+        let fragment = Protector::protect("Deals %s damage");
+        let masked = fragment.masked_text().to_string();
+        let segment = ValidatorSegment::new(
+            "lang/en.properties".into(),
+            1,
+            "line_0".into(),
+            "Deals %s damage".into(),
+            masked.clone(),
+        );
+        let validator = PlaceholderValidator::new(ValidatorConfig {
+            enable_autofix: false,
+            ..ValidatorConfig::default()
+        });
+        let report = validator.validate(&segment, "피해를 줍니다").unwrap_err();
+
+        let note = validation_retry_note(&fragment, &report);
+        assert!(note.contains("PlaceholderMismatch"));
+        assert!(note.ends_with(&fragment.token_map().tokens[0].marker));
+    }
+
+    #[test]
+    fn merged_files_restore_markers_and_roll_back_when_unparsable() {
+        let segment = |line_index: usize, text: &str| Segment {
+            file_index: 0,
+            relative_path: "lang/en.properties".into(),
+            line_index,
+            line_number: line_index + 1,
+            text: text.into(),
+            prefix: String::new(),
+            suffix: String::new(),
+            note: None,
+            usage: None,
+            surroundings: None,
+            placeholder_patterns: Arc::default(),
+            script: None,
+            marker_scheme: MarkerScheme::default(),
+            protected: None,
+        };
+        let segments = vec![segment(0, "greeting=Hello %s"), segment(1, "farewell=Bye")];
+        let marker = segments[0].protect().token_map().tokens[0].marker.clone();
+        let mut job_state = JobState::new();
+        let mut contexts = vec![properties_context(b"greeting=Hello %s\nfarewell=Bye\n")];
+        contexts[0].translated_lines[0] = Some(format!("greeting=안녕 {marker}"));
+
+        let rolled_back = check_merged_files(&mut contexts, &segments, &mut job_state);
+        assert!(rolled_back.is_empty());
+        assert_eq!(
+            contexts[0].translated_lines[0].as_deref(),
+            Some("greeting=안녕 %s")
+        );
+
+        // The translation lost the separator: the whole file keeps the source
+        contexts[0].translated_lines[1] = Some("farewell 안녕히".into());
+        let rolled_back = check_merged_files(&mut contexts, &segments, &mut job_state);
+        assert_eq!(rolled_back.len(), 1);
+        assert_eq!(rolled_back[0].0, 0);
+        assert!(contexts[0].translated_lines.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn surroundings_follow_the_context_mode() {
+        let texts = [
+            "<Title>Colony</Title>",
+            "<Start>Start</Start>",
+            "<Quit>Quit</Quit>",
+            "<Load>Load</Load>",
+        ];
+        let path = "Languages/English/Keyed/Menu.xml";
+        assert_eq!(
+            segment_surroundings(ContextMode::Off, path, &texts, 1, 2),
+            None
+        );
+        assert_eq!(
+            segment_surroundings(ContextMode::Key, path, &texts, 1, 2).unwrap(),
+            "File: Languages/English/Keyed/Menu.xml\nKey: Start"
+        );
+        assert_eq!(
+            segment_surroundings(ContextMode::Surrounding, path, &texts, 1, 1).unwrap(),
+            "File: Languages/English/Keyed/Menu.xml\nKey: Start\nPrevious lines:\n\
+             <Title>Colony</Title>\nNext lines:\n<Quit>Quit</Quit>"
+        );
+        let last = segment_surroundings(ContextMode::Surrounding, path, &texts, 3, 5).unwrap();
+        assert!(last.ends_with("<Quit>Quit</Quit>"));
+        assert!(!last.contains("Next lines:"));
+    }
+
+    #[test]
+    fn progress_is_weighted_by_characters() {
+        // One file with a single 900-character line, one with ten short lines
+        let weights = std::iter::once((0, 900)).chain((0..10).map(|_| (1, 10)));
+        let job_progress = WeightedProgress::from_weights(weights);
+        assert_eq!(job_progress.segments(), 11);
+        assert_eq!(job_progress.job_pct(0), 0.0);
+        assert_eq!(job_progress.job_pct(1), 90.0);
+        assert_eq!(job_progress.file_pct(0, 1), 100.0);
+        assert_eq!(job_progress.file_pct(1, 1), 0.0);
+        assert_eq!(job_progress.job_pct(6), 95.0);
+        assert_eq!(job_progress.file_pct(1, 6), 50.0);
+        assert_eq!(job_progress.job_pct(11), 100.0);
+        assert_eq!(job_progress.job_pct(20), 100.0);
+
+        let empty = WeightedProgress::from_weights([(0, 0)]);
+        assert_eq!(empty.job_pct(1), 100.0);
+        assert_eq!(WeightedProgress::from_weights([]).job_pct(0), 0.0);
+    }
+
+    #[test]
+    fn jobs_that_cannot_start_are_reported_failed() {
+        let memory = Arc::new(crate::job::sink::MemorySink::new());
+        let payload: StartTranslationJobPayload = serde_json::from_value(serde_json::json!({
+            "jobId": "job-unknown-provider",
+            "provider": "babelfish",
+            "modelId": "fish-1",
+            "files": [{ "relativePath": "lang/en.json", "modInstallPath": "/mods/1" }],
+        }))
+        .unwrap();
+
+        let error =
+            queue_translation_job(memory.clone(), payload, JobPriority::default()).unwrap_err();
+        assert_eq!(error, "지원하지 않는 번역기: babelfish");
+        let snapshot = job_status_snapshot("job-unknown-provider").unwrap();
+        assert_eq!(snapshot.status, "failed");
+        assert_eq!(snapshot.queue_position, None);
+        let statuses: Vec<_> = memory
+            .payloads("translation-progress")
+            .into_iter()
+            .map(|payload| payload["status"].clone())
+            .collect();
+        assert_eq!(statuses, ["pending", "failed"]);
+        assert!(!is_job_active("job-unknown-provider"));
+    }
+
+    #[test]
+    fn prefetch_windows_stay_within_the_remaining_budget() {
+        let mut usage = TranslationUsage::default();
+        usage.record(Some(TokenUsage::new(600, 300)), None);
+        let tokens = JobBudget {
+            max_cost_usd: None,
+            max_tokens: Some(1_000),
+        };
+        assert!(tokens.allows(&usage, 100, None));
+        assert!(!tokens.allows(&usage, 101, None));
+
+        // $1 per million tokens either way: $0.0009 spent of $0.001
+        let pricing = ModelPricing {
+            input_per_million: 1.0,
+            output_per_million: 1.0,
+        };
+        usage.estimated_cost_usd = Some(0.0009);
+        let cost = JobBudget {
+            max_cost_usd: Some(0.001),
+            max_tokens: None,
+        };
+        assert!(cost.allows(&usage, 50, Some(pricing)));
+        assert!(!cost.allows(&usage, 200, Some(pricing)));
+        assert!(cost.allows(&usage, 200, None));
+    }
+
+    #[test]
+    fn job_state_file_names_are_sanitized() {
+        let path = job_state_file_path("../job:1");
+        assert_eq!(path.file_name().unwrap(), "___job_1.json");
+        assert_eq!(
+            path.parent().unwrap().file_name().unwrap(),
+            JOB_STATE_DIR_NAME
+        );
+    }
+
+    /// Answers OpenAI chat requests from a table of translations. Jobs run by
+    /// [`run_mock_job`] send the bare text as the user message.
+    struct MockTranslator(HashMap<String, String>);
+
+    impl wiremock::Respond for MockTranslator {
+        fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let text = body["messages"]
+                .as_array()
+                .and_then(|messages| messages.last())
+                .and_then(|message| message["content"].as_str())
+                .unwrap_or_default();
+            let translation = self
+                .0
+                .get(text)
+                .cloned()
+                .unwrap_or_else(|| "번역".to_string());
+            wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{ "message": { "content": translation } }]
+            }))
+        }
+    }
+
+    /// Runs a job translating `files` of `mod_dir` into Korean against a mock
+    /// GPT server that knows `translations`
+    async fn run_mock_job(
+        mod_dir: &Path,
+        files: &[&str],
+        translations: &[(&str, &str)],
+    ) -> crate::job::sink::MemorySink {
+        use wiremock::matchers::{method, path};
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(MockTranslator(
+                translations
+                    .iter()
+                    .map(|(source, target)| (source.to_string(), target.to_string()))
+                    .collect(),
+            ))
+            .mount(&server)
+            .await;
+
+        let mod_path = mod_dir.to_string_lossy().to_string();
+        let mut payload = StartTranslationJobPayload::new(
+            format!("mock-{}", uuid::Uuid::new_v4()),
+            "gpt",
+            "gpt-4o-mini",
+            files
+                .iter()
+                .map(|file| TranslationFileInput::new(*file, mod_path.clone()))
+                .collect(),
+        );
+        payload.target_lang = Some(TargetLangs::One("ko".into()));
+        payload.api_base_url = Some(server.uri());
+        payload.model_params.structured_output = Some(false);
+        payload.prompts.default = crate::ai::prompt::PromptTemplate {
+            system: Some(String::new()),
+            user: Some("{{text}}".into()),
+        };
+
+        let sink = crate::job::sink::MemorySink::new();
+        run_translation_job(
+            &sink,
+            payload,
+            ProviderId::Gpt,
+            "test-key".into(),
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(BackoffController::new()),
+            &mut SourceLookups::default(),
+        )
+        .await;
+        sink
+    }
+
+    /// Final status the job reported
+    fn final_status(sink: &crate::job::sink::MemorySink) -> String {
+        sink.payloads("translation-progress")
+            .last()
+            .and_then(|payload| payload["status"].as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn renpy_jobs_translate_strings_and_keep_labels_and_python_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = r##"define e = Character(_("Eileen"), color="#c8ffc8")
+
+label start:
+    e "Welcome to the village."
+    menu:
+        "Stay a while":
+            jump stay
+        "Leave":
+            return
+
+init python:
+    path = "audio/theme.ogg"
+    greeting = _("Good morning")
+
+label stay:
+    "The fire crackles."
+    return
+"##;
+        fs::create_dir_all(dir.path().join("game")).unwrap();
+        fs::write(dir.path().join("game/script.rpy"), source).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["game/script.rpy"],
+            &[
+                ("Eileen", "에일린"),
+                ("Welcome to the village.", "마을에 온 걸 환영해."),
+                ("Stay a while", "잠시 머문다"),
+                ("Leave", "떠난다"),
+                ("Good morning", "좋은 아침"),
+                ("The fire crackles.", "모닥불이 \"탁탁\" 튄다."),
+            ],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir.path().join(derive_output_relative_path(
+            "game/script.rpy",
+            "ko",
+            &SuffixLayout,
+        ));
+        assert_eq!(
+            fs::read_to_string(output).unwrap(),
+            r##"define e = Character(_("에일린"), color="#c8ffc8")
+
+label start:
+    e "마을에 온 걸 환영해."
+    menu:
+        "잠시 머문다":
+            jump stay
+        "떠난다":
+            return
+
+init python:
+    path = "audio/theme.ogg"
+    greeting = _("좋은 아침")
+
+label stay:
+    "모닥불이 \"탁탁\" 튄다."
+    return
+"##
+        );
+    }
+
+    #[tokio::test]
+    async fn toml_jobs_translate_string_values_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = r#"# UI strings
+[menu]
+title = "Main Menu"
+quit = 'Quit game'
+max_players = 4
+
+[tooltips]
+sword = """
+A sharp blade."""
+"#;
+        fs::write(dir.path().join("strings.toml"), source).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["strings.toml"],
+            &[
+                ("Main Menu", "메인 메뉴"),
+                ("Quit game", "게임 '종료'"),
+                ("A sharp blade.", "날카로운 \"칼날\"."),
+            ],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir.path().join(derive_output_relative_path(
+            "strings.toml",
+            "ko",
+            &SuffixLayout,
+        ));
+        assert_eq!(
+            fs::read_to_string(output).unwrap(),
+            r#"# UI strings
+[menu]
+title = "메인 메뉴"
+quit = "게임 '종료'"
+max_players = 4
+
+[tooltips]
+sword = """
+날카로운 \"칼날\"."""
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn minecraft_jobs_translate_text_components_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let function = r#"# Announce the winner
+say Round over
+tellraw @a[tag=winner] {"text":"You win!","color":"gold"}
+give @p diamond_sword{display:{Name:'{"text":"Old Sword"}'}} 1
+"#;
+        let snbt = r#"{
+	display: {text:'Welcome home'}
+}
+"#;
+        fs::write(dir.path().join("announce.mcfunction"), function).unwrap();
+        fs::write(dir.path().join("welcome.snbt"), snbt).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["announce.mcfunction", "welcome.snbt"],
+            &[
+                ("You win!", "\"승리\"했습니다!"),
+                ("Old Sword", "낡은 검"),
+                ("Welcome home", "'집'에 오신 것을 환영합니다"),
+            ],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = |rel: &str| {
+            fs::read_to_string(dir.path().join(derive_output_relative_path(
+                rel,
+                "ko",
+                &SuffixLayout,
+            )))
+            .unwrap()
+        };
+        assert_eq!(
+            output("announce.mcfunction"),
+            r#"# Announce the winner
+say Round over
+tellraw @a[tag=winner] {"text":"\"승리\"했습니다!","color":"gold"}
+give @p diamond_sword{display:{Name:'{"text":"낡은 검"}'}} 1
+"#
+        );
+        assert_eq!(
+            output("welcome.snbt"),
+            r#"{
+	display: {text:'\'집\'에 오신 것을 환영합니다'}
+}
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn resx_jobs_translate_string_values_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = r#"<?xml version="1.0" encoding="utf-8"?>
+<root>
+  <resheader name="resmimetype">
+    <value>text/microsoft-resx</value>
+  </resheader>
+  <data name="Greeting" xml:space="preserve">
+    <value>Hello &amp; welcome</value>
+    <comment>Shown on the title screen</comment>
+  </data>
+  <data name="Logo" type="System.Resources.ResXFileRef, System.Windows.Forms">
+    <value>logo.png;System.Drawing.Bitmap</value>
+  </data>
+</root>
+"#;
+        fs::write(dir.path().join("Strings.resx"), source).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["Strings.resx"],
+            &[("Hello & welcome", "안녕하세요 & 환영합니다")],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir.path().join(derive_output_relative_path(
+            "Strings.resx",
+            "ko",
+            &SuffixLayout,
+        ));
+        assert_eq!(
+            fs::read_to_string(output).unwrap(),
+            source.replace("Hello &amp; welcome", "안녕하세요 &amp; 환영합니다")
+        );
+    }
+
+    #[tokio::test]
+    async fn paradox_jobs_translate_values_and_keep_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "\u{feff}l_english:\n # Events\n event.1.t:0 \"The Great Feast\"\n event.1.d: \"Guests arrive\" # shown in popup\n";
+        fs::write(dir.path().join("events_l_english.yml"), source).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["events_l_english.yml"],
+            &[
+                ("The Great Feast", "\"대\"연회"),
+                ("Guests arrive", "손님들이\n도착합니다"),
+            ],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir.path().join(derive_output_relative_path(
+            "events_l_english.yml",
+            "ko",
+            &SuffixLayout,
+        ));
+        assert_eq!(
+            fs::read_to_string(output).unwrap(),
+            "\u{feff}l_english:\n # Events\n event.1.t:0 \"\"대\"연회\"\n event.1.d: \"손님들이\\n도착합니다\" # shown in popup\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn unity_asset_jobs_translate_text_and_keep_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = r#"%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!114 &11400000
+MonoBehaviour:
+  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_Name: LocalizationTable
+  entries:
+  - key: menu_start
+    value: Start game
+  - key: menu_quit
+    value: 'Quit now'
+  tooltipIcon: Assets/UI/Icons/tooltip.png
+"#;
+        fs::write(dir.path().join("Strings.asset"), source).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["Strings.asset"],
+            &[("Start game", "게임 시작 #1"), ("Quit now", "지금 '종료'")],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir.path().join(derive_output_relative_path(
+            "Strings.asset",
+            "ko",
+            &SuffixLayout,
+        ));
+        assert_eq!(
+            fs::read_to_string(output).unwrap(),
+            source
+                .replace("value: Start game", "value: \"게임 시작 #1\"")
+                .replace("value: 'Quit now'", "value: '지금 ''종료'''")
+        );
+    }
+
+    #[tokio::test]
+    async fn quest_jobs_translate_only_quest_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let chapter = r#"{
+	id: "1A2B3C4D"
+	filename: "getting_started"
+	quests: [{
+		title: "Wood"
+		description: [
+			"Punch a tree."
+			"{image:ftbquests:textures/logo.png width:50 height:50}"
+		]
+	}]
+}
+"#;
+        let database = r#"{
+  "questDatabase:9": {
+    "0:10": {
+      "properties:10": {
+        "betterquesting:10": {
+          "name:8": "Into the Mines",
+          "icon:10": { "id:8": "minecraft:iron_ore" }
+        }
+      }
+    }
+  }
+}"#;
+        let chapter_path = "config/ftbquests/quests/chapters/intro.snbt";
+        let database_path = "config/betterquesting/DefaultQuests.json";
+        for (rel, content) in [(chapter_path, chapter), (database_path, database)] {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let sink = run_mock_job(
+            dir.path(),
+            &[chapter_path, database_path],
+            &[
+                ("Wood", "큰 \"나무\""),
+                ("Punch a tree.", "나무를 때리세요."),
+                ("Into the Mines", "광산 속으로"),
+            ],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = |rel: &str| {
+            fs::read_to_string(dir.path().join(derive_output_relative_path(
+                rel,
+                "ko",
+                &SuffixLayout,
+            )))
+            .unwrap()
+        };
+        assert_eq!(
+            output(chapter_path),
+            chapter
+                .replace("title: \"Wood\"", "title: \"큰 \\\"나무\\\"\"")
+                .replace("\"Punch a tree.\"", "\"나무를 때리세요.\"")
+        );
+        let database: serde_json::Value = serde_json::from_str(&output(database_path)).unwrap();
+        let quest = &database["questDatabase:9"]["0:10"]["properties:10"]["betterquesting:10"];
+        assert_eq!(quest["name:8"], "광산 속으로");
+        assert_eq!(quest["icon:10"]["id:8"], "minecraft:iron_ore");
+    }
+
+    #[tokio::test]
+    async fn bethesda_jobs_write_a_localized_string_table() {
+        use crate::formats::bethesda::{StringEncoding, StringEntry, StringTable, StringTableKind};
+
+        let dir = tempfile::tempdir().unwrap();
+        let table = StringTable {
+            kind: StringTableKind::DlStrings,
+            encoding: StringEncoding::Windows1252,
+            entries: vec![
+                StringEntry {
+                    id: 0x10,
+                    text: "A blade forged in Whiterun.".into(),
+                },
+                StringEntry {
+                    id: 0x11,
+                    text: String::new(),
+                },
+            ],
+        };
+        fs::create_dir_all(dir.path().join("Strings")).unwrap();
+        fs::write(
+            dir.path().join("Strings/Mod_English.DLSTRINGS"),
+            table.to_bytes().unwrap(),
+        )
+        .unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &["Strings/Mod_English.DLSTRINGS"],
+            &[("A blade forged in Whiterun.", "화이트런에서 벼린 칼날.")],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = fs::read(dir.path().join("Strings/Mod_Korean.DLSTRINGS")).unwrap();
+        let translated = StringTable::parse(&output, StringTableKind::DlStrings).unwrap();
+        assert_eq!(translated.encoding, StringEncoding::Utf8);
+        assert_eq!(
+            translated.entries,
+            vec![
+                StringEntry {
+                    id: 0x10,
+                    text: "화이트런에서 벼린 칼날.".into(),
+                },
+                StringEntry {
+                    id: 0x11,
+                    text: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mo_jobs_write_a_translated_catalog() {
+        use crate::formats::mo::{ByteOrder, MoCatalog, MoMessage};
+
+        let dir = tempfile::tempdir().unwrap();
+        let message = |context: Option<&str>, msgid: &str, translation: &str| MoMessage {
+            context: context.map(str::to_string),
+            msgid: msgid.to_string(),
+            msgid_plural: None,
+            translations: vec![translation.to_string()],
+        };
+        let catalog = MoCatalog {
+            byte_order: ByteOrder::Little,
+            revision: 0,
+            messages: vec![
+                message(
+                    None,
+                    "",
+                    "Content-Type: text/plain; charset=UTF-8\nLanguage: en\n",
+                ),
+                message(Some("menu"), "Open", "Open"),
+                message(None, "Save game", "Save game"),
+            ],
+        };
+        let rel = "locale/en/LC_MESSAGES/game.mo";
+        fs::create_dir_all(dir.path().join("locale/en/LC_MESSAGES")).unwrap();
+        fs::write(dir.path().join(rel), catalog.to_bytes().unwrap()).unwrap();
+
+        let sink = run_mock_job(
+            dir.path(),
+            &[rel],
+            &[("Open", "열기"), ("Save game", "게임 저장")],
+        )
+        .await;
+        assert_eq!(final_status(&sink), "completed");
+
+        // Catalogs under a language directory are written to the target language's directory.
+        let output = dir.path().join("locale/ko/LC_MESSAGES/game.mo");
+        let translated = MoCatalog::parse(&fs::read(output).unwrap()).unwrap();
+        assert!(translated
+            .header()
+            .is_some_and(|header| header.contains("Language: ko\n")));
+        assert_eq!(
+            translated.messages[1..],
+            [
+                message(None, "Save game", "게임 저장"),
+                message(Some("menu"), "Open", "열기"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "unity-bundles")]
+    #[tokio::test]
+    async fn unity_bundle_jobs_write_a_translated_bundle() {
+        use crate::formats::unity_bundle::{tests::text_asset_bundle, AssetBundle};
+
+        let dir = tempfile::tempdir().unwrap();
+        let rel = "dialogue.unity3d";
+        fs::write(
+            dir.path().join(rel),
+            text_asset_bundle("Dialogue", "Hello there."),
+        )
+        .unwrap();
+
+        let sink = run_mock_job(dir.path(), &[rel], &[("Hello there.", "안녕하세요.")]).await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir
+            .path()
+            .join(derive_output_relative_path(rel, "ko", &SuffixLayout));
+        let bundle = AssetBundle::parse(&fs::read(output).unwrap()).unwrap();
+        let entries = bundle.extract();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "CAB-test:1:m_Script");
+        assert_eq!(entries[0].source, "안녕하세요.");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn jobs_translate_files_through_a_registered_plugin() {
+        use crate::formats::plugin::tests::register_title_plugin;
+
+        let plugin_dir = tempfile::tempdir().unwrap();
+        register_title_plugin(plugin_dir.path(), "jobtitle");
+        let dir = tempfile::tempdir().unwrap();
+        let rel = "ui.jobtitle";
+        fs::write(dir.path().join(rel), [0, 159, 146, 150]).unwrap();
+
+        let sink = run_mock_job(dir.path(), &[rel], &[("Hello", "안녕")]).await;
+        assert_eq!(final_status(&sink), "completed");
+
+        let output = dir
+            .path()
+            .join(derive_output_relative_path(rel, "ko", &SuffixLayout));
+        assert_eq!(fs::read_to_string(output).unwrap(), "안녕");
     }
 }
@@ -0,0 +1,362 @@
+/// Reverse index of where text and keys are used across a mod's Defs.
+///
+/// A label such as "Charge" is ambiguous on its own: it may name a weapon,
+/// a research project or an ability. The index records which Def type and
+/// field hold every value in the mod's `Defs` folders and which other Defs
+/// reference a Def by name, so a segment can carry a short usage summary
+/// into the prompt and the review report.
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Upper bound on Def files read per mod.
+const MAX_DEF_FILES: usize = 2000;
+/// Usages listed per group before the summary is cut short.
+const MAX_SUMMARY_ITEMS: usize = 3;
+
+static XML_TOKEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?s)<!--.*?-->|<\?.*?\?>|<!\[CDATA\[(.*?)\]\]>|<(/?)([A-Za-z_][\w.\-]*)[^>]*?(/?)>",
+    )
+    .expect("valid xml token regex")
+});
+
+// A line holding one element, e.g. `<Gun_Charge.label>charge rifle</Gun_Charge.label>`
+static ELEMENT_LINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^<([A-Za-z_][\w.\-]*)[^>]*>([^<]*)</([A-Za-z_][\w.\-]*)>$")
+        .expect("valid element line regex")
+});
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyUsage {
+    pub def_type: String,
+    pub def_name: String,
+    /// Field path inside the Def, e.g. `label` or `verbs.li.label`
+    pub field: String,
+    /// Def file, relative to the mod root
+    pub file: String,
+}
+
+impl KeyUsage {
+    fn describe(&self) -> String {
+        format!("{} {} ({})", self.def_type, self.def_name, self.field)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct KeyUsageIndex {
+    /// Trimmed field value -> every field holding it
+    by_value: HashMap<String, Vec<KeyUsage>>,
+    /// defName -> Def type
+    def_types: HashMap<String, String>,
+}
+
+impl KeyUsageIndex {
+    /// Indexes every XML file under the mod's `Defs` folders, including
+    /// versioned ones such as `1.5/Defs` or `Common/Defs`.
+    pub fn build(mod_root: &Path) -> Self {
+        let mut index = Self::default();
+        let mut pending: Vec<std::path::PathBuf> = def_roots(mod_root);
+        let mut read = 0;
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let is_xml = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+                if !is_xml || read >= MAX_DEF_FILES {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let relative = path
+                    .strip_prefix(mod_root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                index.add_defs(&relative, &content);
+                read += 1;
+            }
+        }
+        index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.def_types.is_empty()
+    }
+
+    /// Adds the Defs declared in one file (`<Defs><ThingDef>...</ThingDef></Defs>`).
+    pub fn add_defs(&mut self, file: &str, content: &str) {
+        let mut stack: Vec<String> = Vec::new();
+        let mut text = String::new();
+        // Leaves of the Def being read; its defName may come after them
+        let mut leaves: Vec<(String, String)> = Vec::new();
+        let mut last_end = 0;
+
+        for token in XML_TOKEN_RE.captures_iter(content) {
+            let whole = token.get(0).expect("match");
+            text.push_str(&content[last_end..whole.start()]);
+            last_end = whole.end();
+
+            if let Some(cdata) = token.get(1) {
+                text.push_str(cdata.as_str());
+                continue;
+            }
+            let Some(name) = token.get(3) else {
+                continue;
+            };
+            let closing = token.get(2).is_some_and(|m| !m.as_str().is_empty());
+            let self_closing = token.get(4).is_some_and(|m| !m.as_str().is_empty());
+            if self_closing {
+                continue;
+            }
+            if !closing {
+                stack.push(name.as_str().to_string());
+                text.clear();
+                continue;
+            }
+
+            let value = text.trim();
+            if stack.len() >= 3 && !value.is_empty() {
+                leaves.push((stack[2..].join("."), value.to_string()));
+            }
+            text.clear();
+            stack.pop();
+
+            // Closing a Def: `Defs > ThingDef`
+            if stack.len() == 1 {
+                self.add_def(file, name.as_str(), std::mem::take(&mut leaves));
+            }
+        }
+    }
+
+    fn add_def(&mut self, file: &str, def_type: &str, leaves: Vec<(String, String)>) {
+        let Some(def_name) = leaves
+            .iter()
+            .find(|(field, _)| field == "defName")
+            .map(|(_, value)| value.clone())
+        else {
+            // Abstract parents have no defName and are never referenced by one
+            return;
+        };
+        self.def_types
+            .insert(def_name.clone(), def_type.to_string());
+        for (field, value) in leaves {
+            if field == "defName" || !value.chars().any(char::is_alphabetic) {
+                continue;
+            }
+            self.by_value.entry(value).or_default().push(KeyUsage {
+                def_type: def_type.to_string(),
+                def_name: def_name.clone(),
+                field,
+                file: file.to_string(),
+            });
+        }
+    }
+
+    /// Usages of the text or key on one translation line: the Def fields
+    /// it fills, and the Defs that reference those Defs by name.
+    pub fn usages_for_line(
+        &self,
+        relative_path: &str,
+        line: &str,
+    ) -> (Vec<KeyUsage>, Vec<KeyUsage>) {
+        let Some(caps) = ELEMENT_LINE_RE.captures(line.trim()) else {
+            return (Vec::new(), Vec::new());
+        };
+        if caps[1] != caps[3] {
+            return (Vec::new(), Vec::new());
+        }
+        let (tag, value) = (&caps[1], caps[2].trim());
+
+        let is_def_injected = relative_path
+            .split(['/', '\\'])
+            .any(|part| part.eq_ignore_ascii_case("DefInjected"));
+        let mut own: Vec<KeyUsage> = Vec::new();
+        match tag.split_once('.') {
+            // DefInjected keys name the Def directly: `Gun_Charge.label`
+            Some((def_name, field)) if is_def_injected => {
+                if let Some(def_type) = self.def_types.get(def_name) {
+                    own.push(KeyUsage {
+                        def_type: def_type.clone(),
+                        def_name: def_name.to_string(),
+                        field: field.to_string(),
+                        file: String::new(),
+                    });
+                }
+            }
+            _ => own.extend(self.by_value.get(value).into_iter().flatten().cloned()),
+        }
+        dedup(&mut own);
+
+        let mut references: Vec<KeyUsage> = Vec::new();
+        let mut def_names: Vec<&str> = own.iter().map(|usage| usage.def_name.as_str()).collect();
+        def_names.dedup();
+        for def_name in def_names {
+            references.extend(
+                self.by_value
+                    .get(def_name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|usage| usage.def_name != def_name)
+                    .cloned(),
+            );
+        }
+        dedup(&mut references);
+        (own, references)
+    }
+
+    /// One-line summary for prompts and review, e.g.
+    /// `Used as ThingDef Gun_Charge (label); referenced by RecipeDef Make_Gun (products.Gun_Charge)`.
+    pub fn describe_line(&self, relative_path: &str, line: &str) -> Option<String> {
+        let (own, references) = self.usages_for_line(relative_path, line);
+        let mut parts = Vec::new();
+        if !own.is_empty() {
+            parts.push(format!("Used as {}", summarize(&own)));
+        }
+        if !references.is_empty() {
+            parts.push(format!("referenced by {}", summarize(&references)));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+}
+
+/// `Defs` folders at the mod root or one level down (versions, `Common`).
+fn def_roots(mod_root: &Path) -> Vec<std::path::PathBuf> {
+    let mut roots = Vec::new();
+    let mut candidates = vec![mod_root.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(mod_root) {
+        candidates.extend(
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir()),
+        );
+    }
+    for candidate in candidates {
+        let defs = candidate.join("Defs");
+        if defs.is_dir() {
+            roots.push(defs);
+        }
+    }
+    roots
+}
+
+fn dedup(usages: &mut Vec<KeyUsage>) {
+    let mut seen = HashSet::new();
+    usages.retain(|usage| {
+        seen.insert((
+            usage.def_type.clone(),
+            usage.def_name.clone(),
+            usage.field.clone(),
+        ))
+    });
+}
+
+fn summarize(usages: &[KeyUsage]) -> String {
+    let mut listed: Vec<String> = usages
+        .iter()
+        .take(MAX_SUMMARY_ITEMS)
+        .map(KeyUsage::describe)
+        .collect();
+    if usages.len() > MAX_SUMMARY_ITEMS {
+        listed.push(format!("{} more", usages.len() - MAX_SUMMARY_ITEMS));
+    }
+    listed.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Defs>
+  <ThingDef ParentName="BaseGun">
+    <label>charge rifle</label>
+    <defName>Gun_ChargeRifle</defName>
+    <verbs>
+      <li><label>Charge</label></li>
+    </verbs>
+    <recipeMaker>
+      <researchPrerequisite>ChargedShot</researchPrerequisite>
+    </recipeMaker>
+  </ThingDef>
+  <ResearchProjectDef>
+    <defName>ChargedShot</defName>
+    <label>Charge</label>
+    <description><![CDATA[Pulse-charged <b>munitions</b>.]]></description>
+  </ResearchProjectDef>
+  <ThingDef Name="BaseGun" Abstract="True">
+    <label>Charge</label>
+  </ThingDef>
+</Defs>"#;
+
+    fn index() -> KeyUsageIndex {
+        let mut index = KeyUsageIndex::default();
+        index.add_defs("Defs/Weapons.xml", DEFS);
+        index
+    }
+
+    #[test]
+    fn finds_every_def_field_holding_a_text() {
+        let (own, references) =
+            index().usages_for_line("Languages/English/Keyed/UI.xml", "<Charge>Charge</Charge>");
+        let described: Vec<String> = own.iter().map(KeyUsage::describe).collect();
+        assert_eq!(
+            described,
+            vec![
+                "ThingDef Gun_ChargeRifle (verbs.li.label)",
+                "ResearchProjectDef ChargedShot (label)",
+            ]
+        );
+        assert_eq!(references.len(), 1);
+        assert_eq!(
+            references[0].describe(),
+            "ThingDef Gun_ChargeRifle (recipeMaker.researchPrerequisite)"
+        );
+        assert_eq!(own[0].file, "Defs/Weapons.xml");
+    }
+
+    #[test]
+    fn def_injected_keys_resolve_to_their_def() {
+        let summary = index()
+            .describe_line(
+                "Languages/Korean/DefInjected/ResearchProjectDef/Research.xml",
+                "  <ChargedShot.label>Charge</ChargedShot.label>",
+            )
+            .unwrap();
+        assert_eq!(
+            summary,
+            "Used as ResearchProjectDef ChargedShot (label); referenced by ThingDef Gun_ChargeRifle (recipeMaker.researchPrerequisite)"
+        );
+        assert!(index()
+            .describe_line("Defs/Weapons.xml", "<label>unused text</label>")
+            .is_none());
+        assert!(index()
+            .describe_line("lang/en.properties", "charge=Charge")
+            .is_none());
+    }
+
+    #[test]
+    fn builds_from_versioned_def_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let defs = dir.path().join("1.5").join("Defs").join("ThingDefs");
+        fs::create_dir_all(&defs).unwrap();
+        fs::write(defs.join("Guns.xml"), DEFS).unwrap();
+
+        let index = KeyUsageIndex::build(dir.path());
+        assert!(!index.is_empty());
+        let (own, _) = index.usages_for_line("x.xml", "<label>charge rifle</label>");
+        assert_eq!(own[0].file, "1.5/Defs/ThingDefs/Guns.xml");
+    }
+}
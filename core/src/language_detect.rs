@@ -0,0 +1,196 @@
+/// Language detection of mod text with whatlang.
+///
+/// Mods often ship half-finished translations or copy lines between their
+/// language files, and sending those lines to the model only spends
+/// requests. Jobs can check each segment with whatlang first and leave the
+/// lines it reliably recognizes as the target language untouched.
+///
+/// Jobs whose source language is `auto` also detect each file's language
+/// from its entries, and the library falls back to a file's content when
+/// its path does not name a language.
+use crate::incremental::entry_key;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use whatlang::{Lang, Script};
+
+/// Latin-script text shorter than this is too ambiguous to tell languages
+/// apart (names, `OK`, `Menu`)
+const MIN_LATIN_LETTERS: usize = 16;
+/// Letters needed in other scripts, where a few characters already narrow
+/// the language down
+const MIN_LETTERS: usize = 2;
+/// Bytes of text sampled for a file's language; a few thousand characters
+/// settle it and keep large files cheap
+const SAMPLE_BYTES: usize = 4096;
+
+/// Tags, format placeholders and Paradox-style `$VAR$`/`[Scope]` commands,
+/// which read as English words
+static MARKUP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<[^>]*>|\{[^}]*\}|\[[^\]]*\]|\$[^$\s]*\$|%[\w.$]+|\\[nrt]")
+        .expect("valid markup regex")
+});
+
+/// Whether `text` is reliably detected as `target_lang` (e.g. `ko`,
+/// `pt-BR`, `zh_CN`). Unknown target languages never match.
+pub fn is_written_in(text: &str, target_lang: &str) -> bool {
+    let Some(target) = whatlang_lang(target_lang) else {
+        return false;
+    };
+    let Some(info) = whatlang::detect(text) else {
+        return false;
+    };
+    if info.lang() != target || !info.is_reliable() {
+        return false;
+    }
+    has_enough_letters(text, info.script())
+}
+
+/// Language `texts` are written in, as the code library language hints use
+/// (`en`, `ko`, `zh-cn`). The texts are sampled together up to a few
+/// kilobytes; `None` when whatlang is not confident.
+pub fn detect_language<'a>(texts: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut sample = String::new();
+    for text in texts {
+        if sample.len() >= SAMPLE_BYTES {
+            break;
+        }
+        sample.push_str(text.trim());
+        sample.push('\n');
+    }
+    let info = whatlang::detect(&sample)?;
+    if !info.is_reliable() || !has_enough_letters(&sample, info.script()) {
+        return None;
+    }
+    Some(language_code(info.lang()))
+}
+
+/// Language of a language file's entries, ignoring their keys and markup
+pub fn detect_content_language(content: &str) -> Option<String> {
+    let texts: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(SAMPLE_BYTES / 8)
+        .map(prose)
+        .collect();
+    detect_language(texts.iter().map(String::as_str))
+}
+
+/// `line` without its entry key and markup
+fn prose(line: &str) -> String {
+    let mut text = line.to_string();
+    if let Some(key) = entry_key(line) {
+        text = text.replacen(&key, " ", 1);
+    }
+    MARKUP_RE.replace_all(&text, " ").into_owned()
+}
+
+fn has_enough_letters(text: &str, script: Script) -> bool {
+    let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+    let min_letters = if script == Script::Latin {
+        MIN_LATIN_LETTERS
+    } else {
+        MIN_LETTERS
+    };
+    letters >= min_letters
+}
+
+/// Short code of a whatlang language, matching `whatlang_lang`
+fn language_code(lang: Lang) -> String {
+    if lang == Lang::Cmn {
+        return "zh-cn".into();
+    }
+    LANGUAGE_CODES
+        .iter()
+        .find(|(_, candidate)| *candidate == lang)
+        .map_or_else(|| lang.code().to_string(), |(code, _)| code.to_string())
+}
+
+/// The whatlang language of a BCP 47 or ISO 639 language code
+fn whatlang_lang(code: &str) -> Option<Lang> {
+    let base = code
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(code)
+        .to_ascii_lowercase();
+    LANGUAGE_CODES
+        .iter()
+        .find(|(candidate, _)| *candidate == base)
+        .map(|(_, lang)| *lang)
+        .or_else(|| Lang::from_code(base))
+}
+
+/// ISO 639-1 codes of the languages whatlang detects
+const LANGUAGE_CODES: &[(&str, Lang)] = &[
+    ("en", Lang::Eng),
+    ("ko", Lang::Kor),
+    ("ja", Lang::Jpn),
+    ("zh", Lang::Cmn),
+    ("ru", Lang::Rus),
+    ("uk", Lang::Ukr),
+    ("be", Lang::Bel),
+    ("bg", Lang::Bul),
+    ("sr", Lang::Srp),
+    ("de", Lang::Deu),
+    ("fr", Lang::Fra),
+    ("es", Lang::Spa),
+    ("pt", Lang::Por),
+    ("it", Lang::Ita),
+    ("pl", Lang::Pol),
+    ("nl", Lang::Nld),
+    ("sv", Lang::Swe),
+    ("da", Lang::Dan),
+    ("nb", Lang::Nob),
+    ("no", Lang::Nob),
+    ("fi", Lang::Fin),
+    ("cs", Lang::Ces),
+    ("sk", Lang::Slk),
+    ("hu", Lang::Hun),
+    ("ro", Lang::Ron),
+    ("tr", Lang::Tur),
+    ("el", Lang::Ell),
+    ("vi", Lang::Vie),
+    ("th", Lang::Tha),
+    ("id", Lang::Ind),
+    ("ar", Lang::Ara),
+    ("he", Lang::Heb),
+    ("hi", Lang::Hin),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_text_already_in_the_target_language() {
+        assert!(is_written_in("강철 검을 만든다", "ko"));
+        assert!(is_written_in("철검", "ko-KR"));
+        assert!(!is_written_in("Forge a steel sword", "ko"));
+        assert!(!is_written_in("강철 검을 만든다", "ja"));
+        assert!(is_written_in("鋼の剣を鍛える", "ja"));
+        assert!(is_written_in(
+            "Der Schmied schmiedet ein scharfes Schwert aus Stahl",
+            "de"
+        ));
+        // Too short to trust in Latin script
+        assert!(!is_written_in("Menü", "de"));
+        assert!(!is_written_in("강철 검", "tlh"));
+    }
+
+    #[test]
+    fn detects_the_language_of_a_file() {
+        let rimworld = "<LanguageData>\n  <SteelSword.label>강철 검</SteelSword.label>\n  \
+            <SteelSword.description>튼튼한 강철로 만든 검입니다. {0}에게 잘 어울립니다.</SteelSword.description>\n\
+            </LanguageData>\n";
+        assert_eq!(detect_content_language(rimworld).as_deref(), Some("ko"));
+        let paradox = "l_english:\n sword_name:0 \"Steel Sword\"\n \
+            sword_desc:0 \"A sturdy blade forged from [Root.GetName]'s finest steel.\"\n \
+            sword_lore:0 \"Every soldier of the northern army carries one into battle.\"\n";
+        assert_eq!(detect_content_language(paradox).as_deref(), Some("en"));
+        assert_eq!(
+            detect_language(["钢剑", "用优质钢材锻造的坚固长剑。"]).as_deref(),
+            Some("zh-cn")
+        );
+        assert_eq!(detect_language(["OK", "Menu"]), None);
+    }
+}
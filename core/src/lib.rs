@@ -1,58 +1,196 @@
+// Only `api` (and `server` with the `serve` feature) is the documented,
+// semver-stable surface; the other modules stay public for the desktop app
+// and are hidden from the docs unless the `unstable` feature is enabled.
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod access_mode;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod ai;
+pub mod api;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod archive;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod backup;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod bidi;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod codex_spec_tests;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod config;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod consistency;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod def_injected;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod document;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod encoding;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod file_lock;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod format_validator;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod formats;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod glossary;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod incremental;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod install;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod job;
+mod job_history;
 mod jobs;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod key_usage;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod language_detect;
 mod library;
+mod library_index;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod llm_guards;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod math_units;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod paths;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod pipeline;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod placeholder_validator;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod policy;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod profiles;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod project_bundle;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod projects;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod protector;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod provenance;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod qc_report;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod quality;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod quality_estimation;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod review;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod scanner;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod scanners;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod script_hooks;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod secrets;
+#[cfg(feature = "serve")]
+pub mod server;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod skip_list;
 mod steam;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod text_extractor;
 mod time;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod token_inspector;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod tone_analyzer;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod translate;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod triage;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod usage_stats;
 mod validation;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod validation_logger;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
 pub mod validator;
+#[cfg_attr(not(feature = "unstable"), doc(hidden))]
+pub mod watch;
 
 #[cfg(test)]
 mod integration_tests;
 
+pub use access_mode::{get_offline_mode, get_read_only_mode, set_offline_mode, set_read_only_mode};
+pub use ai::metrics::{get_provider_usage_metrics, ProviderUsageMetrics};
 pub use archive::{
-    is_archive_file, scan_archive, ArchiveEntry, ArchiveModification, ArchiveScanResult, ArchiveType,
+    is_archive_file, scan_archive, scan_archive_with_progress, ArchiveEntry, ArchiveModification,
+    ArchiveScanProgress, ArchiveScanResult, ArchiveType,
 };
-pub use config::{TranslatorConfig, UiOptions, ValidatorOptions};
+pub use config::{RequestOptions, TranslatorConfig, UiOptions, ValidatorOptions};
+pub use def_injected::{generate_def_injected, DefInjectedReport};
+pub use formats::plugin::{list_extractor_plugins, PluginListing, PluginManifest};
+pub use install::{
+    install_translation, uninstall_translation, InstallReport, RollbackReport, UninstallReport,
+};
+pub use job_history::{
+    get_job_history, get_translation_stats, JobHistoryEntry, ModelStats, TranslationStats,
+};
+#[cfg(feature = "tauri")]
 pub use jobs::{
-    cancel_translation_job, open_output_folder, retry_translation_now, start_translation_job,
-    StartTranslationJobPayload, TranslationFileInput, TranslationProgressEventPayload,
+    approve_segment, cancel_translation_job, edit_segment, enqueue_translation_job, reject_segment,
+    set_max_concurrent_jobs, start_translation_job,
 };
+pub use jobs::{
+    approve_segment_with, cancel_job, discard_recoverable_job, edit_segment_with,
+    emit_recoverable_jobs, get_job_status, get_pending_segments, list_jobs, list_recoverable_jobs,
+    open_output_folder, queue_translation_job, reject_segment_with, reorder_queued_job,
+    retry_translation_now, rollback_translation_job, run_translation_job_with, set_job_priority,
+    set_max_concurrent_jobs_with, shutdown_jobs, ContextMode, JobStatusSnapshot, PendingSegment,
+    RecoverableJob, ReviewSegment, StartTranslationJobPayload, TranslationFileInput,
+    TranslationProgressEventPayload,
+};
+#[cfg(feature = "tauri")]
+pub use library::{list_mod_files, scan_steam_library};
 pub use library::{
-    list_mod_files, scan_steam_library, LibraryEntry, LibraryScanDebug, LibraryScanResponse,
-    LibraryScanner, LibraryWorkshopDebugEntry, ModFileDescriptor, ModFileListing,
+    list_mod_files_with, scan_library_with, CurseForgeSource, FolderSource, LibraryEntry,
+    LibraryScanDebug, LibraryScanResponse, LibraryScanner, LibraryWorkshopDebugEntry,
+    ModFileDescriptor, ModFileListing, ModSource, ModSourceKind, ModSummary, NexusSource,
+};
+#[cfg(feature = "tauri")]
+pub use library_index::rescan_changed;
+pub use library_index::{
+    query_mods, rescan_changed_with, IndexedMod, LibraryIndex, ModQuery, ModQueryResult,
+    ModSortKey, RescanReport,
 };
 pub use pipeline::PipelinePlan;
 pub use placeholder_validator::{
     PlaceholderValidator, Segment, ValidationErrorCode, ValidationFailureReport, ValidatorConfig,
 };
 pub use policy::{default_policy_banner, PolicyBanner, PolicyProfile};
+pub use profiles::user::{list_game_profiles, GameProfileListing};
+pub use profiles::overrides::{detect_mod_profiles, set_mod_profile, ModProfileDetection};
+pub use profiles::stardew::{sync_stardew_i18n, StardewI18nReport};
+pub use project_bundle::{export_project, import_project, ExportedProject, ImportedProject};
+pub use projects::{
+    list_projects, load_project, save_project, ProjectMod, ProjectOutputOverrides, ProjectSummary,
+    SavedProject,
+};
 pub use protector::{ProtectionMode, Protector, ProtectedFragment, ProtectorError};
+pub use qc_report::{get_job_qc_report, QcReport};
+pub use review::{revalidate_file, RevalidationIssue, RevalidationResult};
+pub use secrets::{add_api_key, list_api_keys, remove_api_key, test_api_key, StoredApiKey};
 pub use steam::{detect_steam_path, SteamLocator, SteamPathResponse};
+pub use token_inspector::{inspect_protection, InspectedToken, ProtectionInspection};
+pub use triage::{
+    accept_original, list_validation_failures, retranslate_failed_segments, FailedSegment,
+    RetranslationResult,
+};
+pub use usage_stats::{
+    export_usage_stats, get_usage_stats_enabled, get_usage_stats_path, reset_usage_stats,
+    set_usage_stats_enabled,
+};
 pub use validation::validate_api_key_and_list_models;
 pub use validation_logger::{
     export_validation_metrics, get_validation_log_file_path, get_validation_log_path,
     get_validation_metrics, init_validation_logging, reset_validation_metrics, validation_logger,
     ValidationLogEntry, ValidationLogger, ValidationMetrics, ValidationOutcome,
 };
+#[cfg(feature = "tauri")]
+pub use watch::start_watch_mode;
+pub use watch::{
+    get_watch_status, set_watch_mod_enabled, start_watch_mode_with, stop_watch_mode,
+    TranslationAutoStartedPayload, WatchRoot, WatchRootKind, WatchStatus,
+};
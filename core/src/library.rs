@@ -1,4 +1,12 @@
+use crate::access_mode::is_offline;
 use crate::archive::{self, ArchiveType};
+use crate::encoding::FileMetadata;
+use crate::formats::plugin::plugin_for_path;
+use crate::formats::{get_handler, FileFormat, FormatHandler};
+use crate::incremental;
+use crate::job::sink::ProgressSink;
+use crate::language_detect;
+use crate::library_index::{indexed_libraries, record_library_scan};
 use crate::policy::{self, PolicyBanner, PolicyProfile};
 use crate::steam::{resolve_app_name, LibraryDiscovery, LibraryDiscoveryDebug, SteamLocator};
 use crate::time::{format_system_time, FormattedTimestamp};
@@ -6,12 +14,20 @@ use log::{info, warn};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::ffi::OsStr;
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "tauri")]
+use tauri::AppHandle;
 use uuid::Uuid;
 
+/// Bytes of a language file read to detect its language from content
+const CONTENT_SAMPLE_BYTES: u64 = 8 * 1024;
+
 static RIMWORLD_ABOUT_NAME_CAPTURE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?is)<name>\s*([^<]+?)\s*</name>").expect("valid RimWorld About.xml name regex")
 });
@@ -33,6 +49,39 @@ pub struct ModSummary {
     pub last_updated: FormattedTimestamp,
     pub policy: PolicyProfile,
     pub warnings: Vec<String>,
+    #[serde(default)]
+    pub source: ModSourceKind,
+    /// 한국어 번역 적용 범위; 언어별 파일이 없으면 `None`
+    #[serde(default)]
+    pub translation_coverage: Option<TranslationCoverage>,
+}
+
+/// 원문 항목 중 대상 언어 파일에 같은 키가 있는 비율
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TranslationCoverage {
+    pub target_language: String,
+    /// 원문 언어 파일의 항목(키) 수
+    pub source_entries: usize,
+    /// 그중 대상 언어 파일에도 있는 항목 수
+    pub translated_entries: usize,
+    /// 0–100, 소수점 한 자리
+    pub percent: f64,
+}
+
+/// Where a library scan looks for mods
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ModSourceKind {
+    /// Steam Workshop content of every detected Steam library
+    #[default]
+    Steam,
+    /// Any folder whose subfolders are mods
+    Folder,
+    /// CurseForge Minecraft instance; every archive in `mods/` is a mod
+    #[serde(rename = "curseforge")]
+    CurseForge,
+    /// Vortex staging folder or Mod Organizer 2 instance
+    Nexus,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -100,6 +149,15 @@ pub struct LibraryScanResponse {
     pub debug: Option<LibraryScanDebug>,
 }
 
+/// 라이브러리 스캔 진행 상황 (`library-scan-progress` 이벤트)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LibraryScanProgress {
+    pub library: String,
+    /// 지금까지 정보를 읽은 워크샵 항목 수
+    pub scanned_mods: usize,
+    pub total_mods: usize,
+}
+
 #[derive(Debug, Default)]
 pub struct LibraryScanner;
 
@@ -112,6 +170,17 @@ impl LibraryScanner {
         &self,
         candidates: &[PathBuf],
         debug: &mut LibraryScanDebug,
+    ) -> Result<Vec<LibraryEntry>, String> {
+        self.scan_with_progress(candidates, debug, &|_| {})
+    }
+
+    /// Like [`scan`](Self::scan). Workshop items are summarized in parallel
+    /// and `progress` is called as they finish.
+    pub fn scan_with_progress(
+        &self,
+        candidates: &[PathBuf],
+        debug: &mut LibraryScanDebug,
+        progress: &(dyn Fn(LibraryScanProgress) + Sync),
     ) -> Result<Vec<LibraryEntry>, String> {
         if candidates.is_empty() {
             return Ok(vec![
@@ -134,7 +203,12 @@ impl LibraryScanner {
             };
 
             let mods = if exists {
-                match self.detect_workshop_mods(&steamapps, &mut global_mods, &mut workshop_debug) {
+                match self.detect_workshop_mods(
+                    &steamapps,
+                    &mut global_mods,
+                    &mut workshop_debug,
+                    progress,
+                ) {
                     Ok(result) => result,
                     Err(err) => {
                         notes.push(err);
@@ -229,8 +303,11 @@ impl LibraryScanner {
         steamapps: &Path,
         global_unique: &mut HashSet<String>,
         workshop_debug: &mut LibraryWorkshopDebugEntry,
+        progress: &(dyn Fn(LibraryScanProgress) + Sync),
     ) -> Result<Vec<ModSummary>, String> {
         let mut mods = Vec::new();
+        // `(app id, item id, game name, path)` of items to summarize
+        let mut items = Vec::new();
         let content_root = steamapps.join("workshop/content");
         if !content_root.exists() {
             return Ok(mods);
@@ -323,45 +400,26 @@ impl LibraryScanner {
                 }
 
                 workshop_debug.unique_mods += 1;
+                items.push((app_id.clone(), mod_id, game_name.clone(), mod_path));
+            }
+        }
 
-                let metadata = fs::metadata(&mod_path).ok();
-                let last_updated = metadata
-                    .and_then(|meta| meta.modified().ok())
-                    .and_then(|time| format_system_time(time).ok())
-                    .unwrap_or_else(|| FormattedTimestamp::new("알 수 없음".into()));
-
-                let languages = self.detect_languages(&mod_path);
-                let warnings = self.collect_warnings(&mod_path);
-                let resolved_name = self
-                    .resolve_mod_name(&steamapps, &app_id, &mod_id, &mod_path)
-                    .unwrap_or_else(|| format!("워크샵 항목 {mod_id}"));
-
-                let directory = match to_utf8_string(&mod_path) {
-                    Ok(value) => value,
-                    Err(err) => {
-                        mods.push(self.synthetic_mod(
-                            "invalid-path",
-                            "모드 경로를 UTF-8로 변환하지 못했습니다",
-                            game_name.clone(),
-                            vec!["en".into()],
-                            err,
-                        ));
-                        continue;
-                    }
-                };
-
-                mods.push(ModSummary {
-                    id: dedupe_key,
-                    name: resolved_name,
-                    game: game_name.clone(),
-                    directory,
-                    installed_languages: languages,
-                    last_updated,
-                    policy: PolicyProfile::conservative(game_name.clone()),
-                    warnings,
+        // Reading every item's files dominates large libraries
+        let scanned = AtomicUsize::new(0);
+        let total = items.len();
+        mods.par_extend(items.par_iter().map(|(app_id, mod_id, game_name, mod_path)| {
+            let summary = self.workshop_mod_summary(steamapps, app_id, mod_id, game_name, mod_path);
+            let scanned = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+            // At most one event per percent
+            if scanned * 100 / total != (scanned - 1) * 100 / total {
+                progress(LibraryScanProgress {
+                    library: workshop_debug.library.clone(),
+                    scanned_mods: scanned,
+                    total_mods: total,
                 });
             }
-        }
+            summary
+        }));
 
         if mods.is_empty() && workshop_debug.total_candidates == 0 {
             mods.push(self.synthetic_mod(
@@ -376,6 +434,54 @@ impl LibraryScanner {
         Ok(mods)
     }
 
+    /// Summary of the Workshop item `mod_id` of app `app_id` at `mod_path`
+    pub fn workshop_mod_summary(
+        &self,
+        steamapps: &Path,
+        app_id: &str,
+        mod_id: &str,
+        game_name: &str,
+        mod_path: &Path,
+    ) -> ModSummary {
+        let metadata = fs::metadata(mod_path).ok();
+        let last_updated = metadata
+            .and_then(|meta| meta.modified().ok())
+            .and_then(|time| format_system_time(time).ok())
+            .unwrap_or_else(|| FormattedTimestamp::new("알 수 없음".into()));
+
+        let languages = self.detect_languages(mod_path);
+        let warnings = self.collect_warnings(mod_path);
+        let resolved_name = self
+            .resolve_mod_name(steamapps, app_id, mod_id, mod_path)
+            .unwrap_or_else(|| format!("워크샵 항목 {mod_id}"));
+
+        let directory = match to_utf8_string(mod_path) {
+            Ok(value) => value,
+            Err(err) => {
+                return self.synthetic_mod(
+                    "invalid-path",
+                    "모드 경로를 UTF-8로 변환하지 못했습니다",
+                    game_name.to_string(),
+                    vec!["en".into()],
+                    err,
+                );
+            }
+        };
+
+        ModSummary {
+            id: format!("{app_id}:{mod_id}"),
+            name: resolved_name,
+            game: game_name.to_string(),
+            directory,
+            installed_languages: languages,
+            last_updated,
+            policy: PolicyProfile::conservative(game_name.to_string()),
+            warnings,
+            source: ModSourceKind::Steam,
+            translation_coverage: translation_coverage(mod_path, COVERAGE_TARGET_LANGUAGE),
+        }
+    }
+
     fn synthetic_mod(
         &self,
         mod_id: &str,
@@ -393,6 +499,8 @@ impl LibraryScanner {
             last_updated: FormattedTimestamp::new("알 수 없음".into()),
             policy: PolicyProfile::conservative(game_name),
             warnings: vec![warning],
+            source: ModSourceKind::Steam,
+            translation_coverage: None,
         }
     }
 
@@ -438,7 +546,10 @@ impl LibraryScanner {
                 if name.ends_with(".dll") {
                     contains_dll = true;
                 }
-                if name.ends_with(".assetbundle") || name.ends_with(".unity3d") {
+                // Bundles are only skipped without a handler to read them
+                if (name.ends_with(".assetbundle") || name.ends_with(".unity3d"))
+                    && !FileFormat::from_path(Path::new(&name)).is_binary()
+                {
                     contains_binary = true;
                 }
             }
@@ -468,21 +579,259 @@ impl LibraryScanner {
         resolve_workshop_title(steamapps, app_id, mod_id)
             .or_else(|| resolve_about_metadata_title(mod_path))
     }
+
+    /// Scans a non-Steam mod source rooted at `root` into one library entry
+    pub fn scan_source(&self, source: &dyn ModSource, root: &Path) -> Result<LibraryEntry, String> {
+        let path = to_utf8_string(root)?;
+        if !root.is_dir() {
+            return Ok(LibraryEntry {
+                path,
+                status: LibraryStatus::Missing,
+                mods: Vec::new(),
+                workshop_root: None,
+                notes: vec!["모드 폴더를 찾을 수 없습니다.".into()],
+            });
+        }
+
+        let mods = source.scan(self, root)?;
+        let mut notes = Vec::new();
+        if mods.is_empty() {
+            notes.push("이 폴더에서 모드를 찾지 못했습니다.".into());
+        }
+        Ok(LibraryEntry {
+            path,
+            status: LibraryStatus::Healthy,
+            mods,
+            workshop_root: None,
+            notes,
+        })
+    }
+
+    /// Summary of a mod folder or archive found by a non-Steam source
+    fn local_mod_summary(
+        &self,
+        source: ModSourceKind,
+        name: String,
+        game_name: &str,
+        mod_path: &Path,
+    ) -> Result<ModSummary, String> {
+        let directory = to_utf8_string(mod_path)?;
+        let last_updated = fs::metadata(mod_path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|time| format_system_time(time).ok())
+            .unwrap_or_else(|| FormattedTimestamp::new("알 수 없음".into()));
+        let source_id = serde_json::to_value(source)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        Ok(ModSummary {
+            id: format!("{source_id}:{directory}"),
+            name,
+            game: game_name.to_string(),
+            directory,
+            installed_languages: self.detect_languages(mod_path),
+            last_updated,
+            policy: PolicyProfile::conservative(game_name.to_string()),
+            warnings: self.collect_warnings(mod_path),
+            source,
+            translation_coverage: translation_coverage(mod_path, COVERAGE_TARGET_LANGUAGE),
+        })
+    }
+}
+
+/// A place mods are installed outside Steam Workshop
+pub trait ModSource {
+    fn kind(&self) -> ModSourceKind;
+
+    /// Lists the mods under `root`, which the user picked for this source
+    fn scan(&self, scanner: &LibraryScanner, root: &Path) -> Result<Vec<ModSummary>, String>;
+}
+
+impl ModSourceKind {
+    /// Scanner for the source; Steam uses library discovery instead
+    pub fn source(self) -> Option<Box<dyn ModSource>> {
+        match self {
+            ModSourceKind::Steam => None,
+            ModSourceKind::Folder => Some(Box::new(FolderSource)),
+            ModSourceKind::CurseForge => Some(Box::new(CurseForgeSource)),
+            ModSourceKind::Nexus => Some(Box::new(NexusSource)),
+        }
+    }
+}
+
+const UNKNOWN_GAME: &str = "알 수 없는 게임";
+
+/// Subfolders of `dir`, skipping symlinks and hidden folders
+fn mod_subdirectories(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("모드 폴더를 열거하지 못했습니다: {err}"))?;
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| !name.starts_with('.'))
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Value of `key` in an INI file, ignoring sections
+fn ini_value(contents: &str, key: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.trim().split_once('=')?;
+        let value = value.trim();
+        (name.trim().eq_ignore_ascii_case(key) && !value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Every subfolder is a mod, named after About.xml or the folder
+pub struct FolderSource;
+
+impl ModSource for FolderSource {
+    fn kind(&self) -> ModSourceKind {
+        ModSourceKind::Folder
+    }
+
+    fn scan(&self, scanner: &LibraryScanner, root: &Path) -> Result<Vec<ModSummary>, String> {
+        mod_subdirectories(root)?
+            .into_par_iter()
+            .map(|mod_path| {
+                let name = resolve_about_metadata_title(&mod_path)
+                    .map_or_else(|| file_name_to_string(&mod_path), Ok)?;
+                scanner.local_mod_summary(self.kind(), name, UNKNOWN_GAME, &mod_path)
+            })
+            .collect()
+    }
 }
 
+/// CurseForge Minecraft instance: the instance folder, its `.minecraft`
+/// folder or the `mods` folder itself
+pub struct CurseForgeSource;
+
+impl CurseForgeSource {
+    fn mods_dir(root: &Path) -> Option<PathBuf> {
+        [root.join("mods"), root.join(".minecraft/mods")]
+            .into_iter()
+            .find(|candidate| candidate.is_dir())
+            .or_else(|| {
+                (root.file_name() == Some(OsStr::new("mods"))).then(|| root.to_path_buf())
+            })
+    }
+}
+
+impl ModSource for CurseForgeSource {
+    fn kind(&self) -> ModSourceKind {
+        ModSourceKind::CurseForge
+    }
+
+    fn scan(&self, scanner: &LibraryScanner, root: &Path) -> Result<Vec<ModSummary>, String> {
+        let mods_dir = Self::mods_dir(root)
+            .ok_or_else(|| "CurseForge 인스턴스의 mods 폴더를 찾을 수 없습니다.".to_string())?;
+        let entries = fs::read_dir(&mods_dir)
+            .map_err(|err| format!("mods 폴더를 열거하지 못했습니다: {err}"))?;
+        let mut archives: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && archive::is_archive_file(path))
+            .collect();
+        archives.sort();
+
+        archives
+            .into_par_iter()
+            .map(|archive_path| {
+                let name = archive_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                scanner.local_mod_summary(self.kind(), name, "Minecraft", &archive_path)
+            })
+            .collect()
+    }
+}
+
+/// Nexus Mods installed through Vortex (a staging folder marked with
+/// `__vortex_staging_folder`) or Mod Organizer 2 (an instance with
+/// `ModOrganizer.ini` and one folder per mod under `mods/`)
+pub struct NexusSource;
+
+const VORTEX_STAGING_MARKER: &str = "__vortex_staging_folder";
+const MO2_INI: &str = "ModOrganizer.ini";
+
+impl ModSource for NexusSource {
+    fn kind(&self) -> ModSourceKind {
+        ModSourceKind::Nexus
+    }
+
+    fn scan(&self, scanner: &LibraryScanner, root: &Path) -> Result<Vec<ModSummary>, String> {
+        let mo2_instance = [Some(root), root.parent()]
+            .into_iter()
+            .flatten()
+            .find(|dir| dir.join(MO2_INI).is_file());
+
+        let (mods_dir, game_name) = if let Some(instance) = mo2_instance {
+            let game = fs::read_to_string(instance.join(MO2_INI))
+                .ok()
+                .and_then(|contents| ini_value(&contents, "gameName"));
+            (instance.join("mods"), game)
+        } else if root.join(VORTEX_STAGING_MARKER).is_file() {
+            // Vortex stages into `<game>/mods` by default
+            let game = (root.file_name() == Some(OsStr::new("mods")))
+                .then(|| root.parent().and_then(|parent| file_name_to_string(parent).ok()))
+                .flatten();
+            (root.to_path_buf(), game)
+        } else {
+            return Err(
+                "Vortex 스테이징 폴더나 Mod Organizer 2 인스턴스를 찾을 수 없습니다.".into(),
+            );
+        };
+        let game_name = game_name.unwrap_or_else(|| UNKNOWN_GAME.to_string());
+
+        mod_subdirectories(&mods_dir)?
+            .into_par_iter()
+            // MO2 separators are empty folders that group the mod list
+            .filter(|mod_path| !mod_path.to_string_lossy().ends_with("_separator"))
+            .map(|mod_path| {
+                let name = file_name_to_string(&mod_path)?;
+                scanner.local_mod_summary(self.kind(), name, &game_name, &mod_path)
+            })
+            .collect()
+    }
+}
+
+/// Tauri command to list translatable files of a mod, including language
+/// files inside archives. Archive scans report `archive-scan-progress` events.
+/// A mod that is a single archive (CurseForge) lists that archive only,
+/// relative to its folder.
+#[cfg(feature = "tauri")]
 #[tauri::command]
-pub fn list_mod_files(mod_directory: String) -> Result<ModFileListing, String> {
-    let root = PathBuf::from(&mod_directory);
+pub fn list_mod_files(app: AppHandle, mod_directory: String) -> Result<ModFileListing, String> {
+    list_mod_files_with(&app, mod_directory)
+}
+
+/// [`list_mod_files`] reporting archive scans to `sink`
+pub fn list_mod_files_with(
+    sink: &dyn ProgressSink,
+    mod_directory: String,
+) -> Result<ModFileListing, String> {
+    let mut root = PathBuf::from(&mod_directory);
     if !root.exists() {
         return Err("모드 디렉터리를 찾을 수 없습니다.".into());
     }
-    if !root.is_dir() {
+    let mut single_archive = None;
+    if root.is_file() && archive::is_archive_file(&root) {
+        let parent = root.parent().map(Path::to_path_buf).unwrap_or_default();
+        single_archive = Some(std::mem::replace(&mut root, parent));
+    } else if !root.is_dir() {
         return Err("지정된 경로가 디렉터리가 아닙니다.".into());
     }
 
-    let canonical_root = root
-        .canonicalize()
-        .unwrap_or_else(|_| PathBuf::from(&mod_directory));
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
     let mod_install_path = canonical_root
         .to_str()
         .map(|value| value.to_string())
@@ -494,8 +843,12 @@ pub fn list_mod_files(mod_directory: String) -> Result<ModFileListing, String> {
         })?;
 
     let mut queue = VecDeque::new();
-    queue.push_back(root.clone());
     let mut files = Vec::new();
+    let mut archives = Vec::new();
+    match single_archive {
+        Some(archive_path) => archives.push(archive_path),
+        None => queue.push_back(root.clone()),
+    }
 
     while let Some(dir) = queue.pop_front() {
         let entries = match fs::read_dir(&dir) {
@@ -524,28 +877,9 @@ pub fn list_mod_files(mod_directory: String) -> Result<ModFileListing, String> {
                 continue;
             }
 
-            // JAR/ZIP 아카이브 파일인 경우 내부 스캔
+            // 아카이브 파일인 경우 내부 스캔 (아래에서 병렬로)
             if archive::is_archive_file(&path) {
-                if let Ok(scan_result) = archive::scan_archive(&path) {
-                    let archive_rel = path.strip_prefix(&root)
-                        .map(|p| normalize_relative_path(p))
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string());
-                    
-                    for entry in scan_result.language_files {
-                        let language_hint = detect_archive_entry_language(&entry.path);
-                        let auto_selected = language_hint.as_deref().map_or(true, |lang| lang != "ko");
-                        
-                        files.push(ModFileDescriptor {
-                            path: entry.path,
-                            mod_install_path: mod_install_path.clone(),
-                            translatable: true,
-                            auto_selected,
-                            language_hint,
-                            archive_path: Some(archive_rel.clone()),
-                            archive_type: Some(scan_result.archive_type),
-                        });
-                    }
-                }
+                archives.push(path);
                 continue;
             }
 
@@ -555,24 +889,106 @@ pub fn list_mod_files(mod_directory: String) -> Result<ModFileListing, String> {
         }
     }
 
+    // Archive scans read and inflate entries, so they run side by side
+    files.par_extend(
+        archives
+            .par_iter()
+            .flat_map_iter(|path| archive_files(sink, &root, path, &mod_install_path)),
+    );
     files.sort_by(|a, b| a.path.cmp(&b.path));
 
     Ok(ModFileListing { files })
 }
 
+/// Language files inside the archive at `path`
+fn archive_files(
+    sink: &dyn ProgressSink,
+    root: &Path,
+    path: &Path,
+    mod_install_path: &str,
+) -> Vec<ModFileDescriptor> {
+    let scan = archive::scan_archive_with_progress(path, |progress| {
+        sink.emit("archive-scan-progress", progress)
+    });
+    let Ok(scan_result) = scan else {
+        return Vec::new();
+    };
+    let archive_rel = path
+        .strip_prefix(root)
+        .map(normalize_relative_path)
+        .unwrap_or_else(|_| path.to_string_lossy().to_string());
+
+    scan_result
+        .language_files
+        .into_iter()
+        .map(|entry| {
+            let language_hint = detect_archive_entry_language(&entry.path);
+            let auto_selected = language_hint.as_deref().map_or(true, |lang| lang != "ko");
+
+            ModFileDescriptor {
+                path: entry.path,
+                mod_install_path: mod_install_path.to_string(),
+                translatable: true,
+                auto_selected,
+                language_hint,
+                archive_path: Some(archive_rel.clone()),
+                archive_type: Some(scan_result.archive_type),
+            }
+        })
+        .collect()
+}
+
+/// Tauri command to scan for mods. Steam (the default) discovers every
+/// Steam library, starting from `explicit_path` if given; other sources scan
+/// the folder in `explicit_path`.
+#[cfg(feature = "tauri")]
 #[tauri::command]
-pub fn scan_steam_library(explicit_path: Option<String>) -> Result<LibraryScanResponse, String> {
+pub fn scan_steam_library(
+    app: AppHandle,
+    explicit_path: Option<String>,
+    source: Option<ModSourceKind>,
+) -> Result<LibraryScanResponse, String> {
+    scan_library_with(&app, explicit_path, source)
+}
+
+/// [`scan_steam_library`] reporting progress to `sink`
+pub fn scan_library_with(
+    sink: &dyn ProgressSink,
+    explicit_path: Option<String>,
+    source: Option<ModSourceKind>,
+) -> Result<LibraryScanResponse, String> {
     let locator = SteamLocator::new();
     let scanner = LibraryScanner::new();
 
+    let explicit_path = explicit_path.and_then(|value| {
+        if value.trim().is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    });
+
+    if let Some(mod_source) = source.unwrap_or_default().source() {
+        let root = explicit_path.ok_or_else(|| "스캔할 모드 폴더를 지정해야 합니다.".to_string())?;
+        let library = scanner.scan_source(mod_source.as_ref(), Path::new(root.trim()))?;
+        return Ok(LibraryScanResponse {
+            libraries: vec![library],
+            policy_banner: policy::default_policy_banner(),
+            debug: None,
+        });
+    }
+
+    if is_offline() && explicit_path.is_none() {
+        if let Some(libraries) = indexed_libraries() {
+            return Ok(LibraryScanResponse {
+                libraries,
+                policy_banner: policy::default_policy_banner(),
+                debug: None,
+            });
+        }
+    }
+
     let primary_path = explicit_path
-        .and_then(|value| {
-            if value.trim().is_empty() {
-                None
-            } else {
-                Some(value.into())
-            }
-        })
         .or_else(|| {
             locator
                 .discover_path()
@@ -584,7 +1000,10 @@ pub fn scan_steam_library(explicit_path: Option<String>) -> Result<LibraryScanRe
         debug: discovery_debug,
     } = locator.library_candidates(primary_path.as_deref());
     let mut debug = LibraryScanDebug::new(discovery_debug);
-    let libraries = scanner.scan(&candidates, &mut debug)?;
+    let libraries = scanner.scan_with_progress(&candidates, &mut debug, &|progress| {
+        sink.emit("library-scan-progress", progress)
+    })?;
+    record_library_scan(&libraries);
 
     Ok(LibraryScanResponse {
         libraries,
@@ -732,6 +1151,318 @@ mod tests {
 
         fs::remove_dir_all(root).ok();
     }
+
+    #[test]
+    fn parallel_scan_reports_progress_per_library() {
+        let root = temp_dir("scan_progress");
+        let content = root.join("steamapps/workshop/content/294100");
+        for item in 1..=3 {
+            fs::create_dir_all(content.join(item.to_string())).expect("create workshop item");
+        }
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let mut debug = LibraryScanDebug::new(LibraryDiscoveryDebug::default());
+        let entries = LibraryScanner::new()
+            .scan_with_progress(std::slice::from_ref(&root), &mut debug, &|progress| {
+                events.lock().unwrap().push(progress)
+            })
+            .expect("scan library");
+
+        let mut ids: Vec<_> = entries[0].mods.iter().map(|summary| summary.id.clone()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["294100:1", "294100:2", "294100:3"]);
+        let events = events.into_inner().unwrap();
+        let mut scanned: Vec<_> = events.iter().map(|event| event.scanned_mods).collect();
+        scanned.sort();
+        assert_eq!(scanned, vec![1, 2, 3]);
+        assert!(events.iter().all(|event| event.total_mods == 3));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn language_versions_share_a_neutral_path() {
+        for (english, korean) in [
+            ("Languages/English/Keyed/UI.xml", "Languages/Korean (한국어)/Keyed/UI.xml"),
+            (
+                "localisation/english/events_l_english.yml",
+                "localisation/korean/events_l_korean.yml",
+            ),
+            ("assets/demo/lang/en_us.json", "assets/demo/lang/ko_kr.json"),
+            ("locale/en/strings.cfg", "locale/ko/strings.cfg"),
+        ] {
+            assert_eq!(language_neutral_path(english), language_neutral_path(korean));
+        }
+        assert_ne!(
+            language_neutral_path("Languages/English/Keyed/UI.xml"),
+            language_neutral_path("Languages/English/Keyed/Misc.xml")
+        );
+    }
+
+    #[test]
+    fn bethesda_string_tables_are_listed_and_covered() {
+        use crate::formats::bethesda::{StringEncoding, StringEntry, StringTable, StringTableKind};
+
+        let root = temp_dir("bethesda_tables");
+        let strings = root.join("Strings");
+        fs::create_dir_all(&strings).unwrap();
+        let write = |name: &str, texts: &[(u32, &str)]| {
+            let table = StringTable {
+                kind: StringTableKind::Strings,
+                encoding: StringEncoding::Utf8,
+                entries: texts
+                    .iter()
+                    .map(|(id, text)| StringEntry {
+                        id: *id,
+                        text: text.to_string(),
+                    })
+                    .collect(),
+            };
+            fs::write(strings.join(name), table.to_bytes().unwrap()).unwrap();
+        };
+        write(
+            "Mod_English.STRINGS",
+            &[(1, "Iron Sword"), (2, "Steel Sword")],
+        );
+        write("Mod_Korean.STRINGS", &[(1, "철검")]);
+
+        let descriptor = classify_mod_file(&root, &strings.join("Mod_English.STRINGS"), "")
+            .expect("string table");
+        assert_eq!(descriptor.language_hint.as_deref(), Some("en"));
+        assert!(descriptor.auto_selected);
+
+        let coverage = translation_coverage(&root, "ko").expect("coverage");
+        assert_eq!(coverage.source_entries, 2);
+        assert_eq!(coverage.translated_entries, 1);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn gettext_catalogs_are_listed() {
+        use crate::formats::mo::{ByteOrder, MoCatalog, MoMessage};
+
+        let root = temp_dir("gettext_catalogs");
+        let messages = root.join("locale/en/LC_MESSAGES");
+        fs::create_dir_all(&messages).unwrap();
+        let catalog = MoCatalog {
+            byte_order: ByteOrder::Little,
+            revision: 0,
+            messages: vec![MoMessage {
+                context: None,
+                msgid: "Open".to_string(),
+                msgid_plural: None,
+                translations: vec!["Open".to_string()],
+            }],
+        };
+        fs::write(messages.join("game.mo"), catalog.to_bytes().unwrap()).unwrap();
+
+        let descriptor = classify_mod_file(&root, &messages.join("game.mo"), "").expect("catalog");
+        assert!(descriptor.translatable);
+        assert_eq!(descriptor.language_hint.as_deref(), Some("en"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[cfg(feature = "unity-bundles")]
+    #[test]
+    fn unity_bundles_are_listed_without_a_skip_warning() {
+        use crate::formats::unity_bundle::tests::text_asset_bundle;
+
+        let root = temp_dir("unity_bundles");
+        fs::write(
+            root.join("dialogue.unity3d"),
+            text_asset_bundle("Dialogue", "Hello there."),
+        )
+        .unwrap();
+
+        let descriptor =
+            classify_mod_file(&root, &root.join("dialogue.unity3d"), "").expect("bundle");
+        assert!(descriptor.translatable);
+        assert!(LibraryScanner::new().collect_warnings(&root).is_empty());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn files_a_registered_plugin_handles_are_listed_and_covered() {
+        use crate::formats::plugin::tests::register_title_plugin;
+
+        let plugin_dir = temp_dir("title_plugin");
+        register_title_plugin(&plugin_dir, "libtitle");
+        let root = temp_dir("plugin_files");
+        let english = root.join("Languages/English/ui.libtitle");
+        let korean = root.join("Languages/Korean/ui.libtitle");
+        for path in [&english, &korean] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, [0, 159, 146, 150]).unwrap();
+        }
+
+        let descriptor = classify_mod_file(&root, &english, "").expect("plugin file");
+        assert!(descriptor.translatable);
+        let coverage = translation_coverage(&root, "ko").expect("coverage");
+        assert_eq!(coverage.source_entries, 1);
+        assert_eq!(coverage.translated_entries, 1);
+
+        fs::remove_dir_all(root).ok();
+        fs::remove_dir_all(plugin_dir).ok();
+    }
+
+    #[test]
+    fn coverage_counts_source_keys_present_in_korean_files() {
+        let root = temp_dir("coverage");
+        let write = |relative: &str, contents: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(
+            "Languages/English/Keyed/UI.xml",
+            "<LanguageData>\n<A>a</A>\n<B>b</B>\n<C>c</C>\n</LanguageData>\n",
+        );
+        // Keys only the translation has do not count
+        write(
+            "Languages/Korean/Keyed/UI.xml",
+            "<LanguageData>\n<A>가</A>\n<Z>z</Z>\n</LanguageData>\n",
+        );
+        write("Languages/English/Keyed/Misc.xml", "<LanguageData>\n<D>d</D>\n</LanguageData>\n");
+
+        let coverage = translation_coverage(&root, "ko").expect("coverage");
+        assert_eq!(coverage.source_entries, 4);
+        assert_eq!(coverage.translated_entries, 1);
+        assert_eq!(coverage.percent, 25.0);
+
+        let summary = LibraryScanner::new().workshop_mod_summary(
+            &root.join("steamapps"),
+            "294100",
+            "1",
+            "RimWorld",
+            &root,
+        );
+        assert_eq!(summary.translation_coverage, Some(coverage));
+        assert_eq!(translation_coverage(&root.join("Languages/Korean"), "ko"), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn language_hint_prefers_the_file_content() {
+        let root = temp_dir("content_language");
+        let english = root.join("Languages/English/Keyed/UI.xml");
+        fs::create_dir_all(english.parent().unwrap()).unwrap();
+        // Translated in place over the English file
+        fs::write(
+            &english,
+            "<LanguageData>\n<SteelSword>강철 검</SteelSword>\n\
+             <SteelSwordDesc>튼튼한 강철로 만든 검입니다.</SteelSwordDesc>\n</LanguageData>\n",
+        )
+        .unwrap();
+        let descriptor = classify_mod_file(&root, &english, "").expect("language file");
+        assert_eq!(descriptor.language_hint.as_deref(), Some("ko"));
+        assert!(!descriptor.auto_selected);
+
+        // Too little text to tell; the path decides
+        fs::write(&english, "<LanguageData>\n<A>a</A>\n</LanguageData>\n").unwrap();
+        let descriptor = classify_mod_file(&root, &english, "").expect("language file");
+        assert_eq!(descriptor.language_hint.as_deref(), Some("en"));
+        assert!(descriptor.auto_selected);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    fn scan_names(kind: ModSourceKind, root: &Path) -> Vec<(String, String)> {
+        let source = kind.source().expect("non-Steam source");
+        let entry = LibraryScanner::new()
+            .scan_source(source.as_ref(), root)
+            .expect("scan mod source");
+        entry
+            .mods
+            .into_iter()
+            .map(|summary| {
+                assert_eq!(summary.source, kind);
+                (summary.name, summary.game)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn folder_source_lists_each_subfolder() {
+        let root = temp_dir("folder_source");
+        fs::create_dir_all(root.join("Plain")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("Named/About")).unwrap();
+        fs::write(
+            root.join("Named/About/About.xml"),
+            "<ModMetaData><name>Named Mod</name></ModMetaData>",
+        )
+        .unwrap();
+
+        assert_eq!(
+            scan_names(ModSourceKind::Folder, &root),
+            vec![
+                ("Named Mod".to_string(), "알 수 없는 게임".to_string()),
+                ("Plain".to_string(), "알 수 없는 게임".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn curseforge_source_lists_mod_archives() {
+        let root = temp_dir("curseforge_source");
+        let mods = root.join(".minecraft/mods");
+        fs::create_dir_all(&mods).unwrap();
+        fs::write(mods.join("jei-1.20.1.jar"), b"PK").unwrap();
+        fs::write(mods.join("old.jar.disabled"), b"PK").unwrap();
+        fs::write(mods.join("notes.txt"), "").unwrap();
+
+        let expected = vec![("jei-1.20.1".to_string(), "Minecraft".to_string())];
+        assert_eq!(scan_names(ModSourceKind::CurseForge, &root), expected);
+        assert_eq!(scan_names(ModSourceKind::CurseForge, &mods), expected);
+
+        let source = ModSourceKind::CurseForge.source().unwrap();
+        let empty = temp_dir("curseforge_empty");
+        assert!(LibraryScanner::new()
+            .scan_source(source.as_ref(), &empty)
+            .is_err());
+
+        fs::remove_dir_all(root).ok();
+        fs::remove_dir_all(empty).ok();
+    }
+
+    #[test]
+    fn nexus_source_reads_mo2_and_vortex_layouts() {
+        let mo2 = temp_dir("nexus_mo2");
+        fs::write(
+            mo2.join("ModOrganizer.ini"),
+            "[General]\ngameName=Skyrim Special Edition\n",
+        )
+        .unwrap();
+        fs::create_dir_all(mo2.join("mods/SkyUI")).unwrap();
+        fs::create_dir_all(mo2.join("mods/Interface_separator")).unwrap();
+        let skyui = vec![("SkyUI".to_string(), "Skyrim Special Edition".to_string())];
+        assert_eq!(scan_names(ModSourceKind::Nexus, &mo2), skyui);
+        assert_eq!(scan_names(ModSourceKind::Nexus, &mo2.join("mods")), skyui);
+
+        let vortex = temp_dir("nexus_vortex");
+        let staging = vortex.join("skyrimse/mods");
+        fs::create_dir_all(staging.join("SkyUI-12604-5-2SE")).unwrap();
+        fs::write(staging.join("__vortex_staging_folder"), "").unwrap();
+        assert_eq!(
+            scan_names(ModSourceKind::Nexus, &staging),
+            vec![("SkyUI-12604-5-2SE".to_string(), "skyrimse".to_string())]
+        );
+
+        let source = ModSourceKind::Nexus.source().unwrap();
+        assert!(LibraryScanner::new()
+            .scan_source(source.as_ref(), &vortex)
+            .is_err());
+
+        fs::remove_dir_all(mo2).ok();
+        fs::remove_dir_all(vortex).ok();
+    }
 }
 
 
@@ -750,7 +1481,9 @@ fn classify_mod_file(
     let relative_str = normalize_relative_path(relative);
     let lowered = relative_str.to_lowercase();
 
-    if should_ignore_file(&lowered) {
+    // Installed extractor plugins come before the built-in formats
+    let handled_by_plugin = plugin_for_path(path).is_some();
+    if should_ignore_file(&lowered) && !handled_by_plugin {
         return None;
     }
 
@@ -770,22 +1503,32 @@ fn classify_mod_file(
         .and_then(|ext| ext.to_str())
         .map(|ext| ext.to_lowercase());
 
-    let language_hint = detect_language_hint(path);
+    let path_hint = detect_language_hint(path);
 
     let is_text_extension = extension
         .as_deref()
         .map_or(false, |ext| matches_text_extension(ext));
-
-    let translatable = is_text_extension || (in_localization_dir && language_hint.is_some());
+    // Binary files with a handler, e.g. Bethesda string tables
+    let is_binary_format = handled_by_plugin
+        || extension
+            .as_deref()
+            .is_some_and(|ext| FileFormat::from_extension(ext).is_binary());
+
+    let translatable =
+        is_text_extension || is_binary_format || (in_localization_dir && path_hint.is_some());
     if !translatable {
         return None;
     }
 
-    let auto_selected = if let Some(lang) = language_hint.as_deref() {
-        lang != "ko"
-    } else {
-        in_localization_dir
-    };
+    // Language files are often translated in place, so their content wins
+    // over the language their path names
+    let is_language_file = in_localization_dir || path_hint.is_some();
+    let language_hint = (is_language_file && !is_binary_format)
+        .then(|| detect_file_content_language(path))
+        .flatten()
+        .or(path_hint);
+
+    let auto_selected = is_language_file && language_hint.as_deref() != Some("ko");
 
     Some(ModFileDescriptor {
         path: relative_str,
@@ -798,6 +1541,161 @@ fn classify_mod_file(
     })
 }
 
+/// Whether a file under `root` would be picked for translation by default:
+/// an archive, or a source-language file `list_mod_files` auto-selects
+pub fn is_translation_source(root: &Path, path: &Path) -> bool {
+    archive::is_archive_file(path)
+        || classify_mod_file(root, path, "").is_some_and(|descriptor| descriptor.auto_selected)
+}
+
+/// Language the library reports translation coverage for
+pub const COVERAGE_TARGET_LANGUAGE: &str = "ko";
+
+/// How much of a mod's language files already exist in `target_lang`.
+///
+/// Loose files whose path names a language are grouped by their path with
+/// the language parts removed, so `Languages/English/Keyed/UI.xml` pairs
+/// with `Languages/Korean/Keyed/UI.xml` and `foo_l_english.yml` with
+/// `foo_l_korean.yml`. Each group's source is its English file, or any
+/// other non-target file, and an entry counts as translated when the
+/// target file has the same key. Files inside archives are not read.
+pub fn translation_coverage(mod_path: &Path, target_lang: &str) -> Option<TranslationCoverage> {
+    // neutral path -> (language, file) of each language version
+    let mut groups: BTreeMap<String, Vec<(String, PathBuf)>> = BTreeMap::new();
+    let mut pending = vec![mod_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let Some(descriptor) = classify_mod_file(mod_path, &path, "") else {
+                continue;
+            };
+            let Some(language) = descriptor.language_hint else {
+                continue;
+            };
+            groups
+                .entry(language_neutral_path(&descriptor.path))
+                .or_default()
+                .push((language, path));
+        }
+    }
+
+    let mut source_entries = 0;
+    let mut translated_entries = 0;
+    for versions in groups.values() {
+        let source = versions
+            .iter()
+            .find(|(language, _)| language == "en")
+            .or_else(|| versions.iter().find(|(language, _)| language != target_lang));
+        let Some((_, source_path)) = source else {
+            continue;
+        };
+        let source_keys = entry_keys(source_path);
+        let target_keys = versions
+            .iter()
+            .find(|(language, _)| language == target_lang)
+            .map(|(_, path)| entry_keys(path))
+            .unwrap_or_default();
+        source_entries += source_keys.len();
+        translated_entries += source_keys.intersection(&target_keys).count();
+    }
+
+    if source_entries == 0 {
+        return None;
+    }
+    let percent = (translated_entries as f64 * 1000.0 / source_entries as f64).round() / 10.0;
+    Some(TranslationCoverage {
+        target_language: target_lang.to_string(),
+        source_entries,
+        translated_entries,
+        percent,
+    })
+}
+
+/// Entry keys of a language file, as incremental translation matches them.
+/// XML elements that do not close on their line (`<LanguageData>`) hold
+/// entries rather than being one. Binary files use the keys of their
+/// handler's entries.
+fn entry_keys(path: &Path) -> HashSet<String> {
+    let Ok(bytes) = fs::read(path) else {
+        return HashSet::new();
+    };
+    // Binary tables pair their entries by the keys the handler reads
+    if let Some(plugin) = plugin_for_path(path) {
+        return plugin
+            .extract_bytes(path, &bytes)
+            .map(|entries| entries.into_iter().map(|entry| entry.key).collect())
+            .unwrap_or_default();
+    }
+    let format = FileFormat::from_path(path);
+    if format.is_binary() {
+        return get_handler(format)
+            .and_then(|handler| handler.extract_bytes(path, &bytes).ok())
+            .map(|entries| entries.into_iter().map(|entry| entry.key).collect())
+            .unwrap_or_default();
+    }
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('<') || line.contains("</"))
+        .filter_map(incremental::entry_key)
+        .collect()
+}
+
+/// Lowercased relative path with directory names that name a language and
+/// language tokens of the file name replaced by `{lang}`
+fn language_neutral_path(relative: &str) -> String {
+    let segments: Vec<&str> = relative.split('/').collect();
+    let last = segments.len().saturating_sub(1);
+    let names_language = |token: &str| normalize_language_code(token).is_some();
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let lower = segment.to_lowercase();
+            if index < last {
+                return if split_language_tokens(&lower).iter().any(|t| names_language(t)) {
+                    "{lang}".to_string()
+                } else {
+                    lower
+                };
+            }
+            let (stem, extension) = match lower.rsplit_once('.') {
+                Some((stem, extension)) => (stem, format!(".{extension}")),
+                None => (lower.as_str(), String::new()),
+            };
+            if names_language(stem) {
+                return format!("{{lang}}{extension}");
+            }
+            let stem: String = stem
+                .split_inclusive(['.', '_', '-', ' '])
+                .map(|piece| {
+                    let token = piece.trim_end_matches(['.', '_', '-', ' ']);
+                    if names_language(token) {
+                        piece.replacen(token, "{lang}", 1)
+                    } else {
+                        piece.to_string()
+                    }
+                })
+                .collect();
+            format!("{stem}{extension}")
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 fn normalize_relative_path(path: &Path) -> String {
     path.components()
         .map(|component| component.as_os_str().to_string_lossy())
@@ -832,7 +1730,8 @@ fn should_ignore_file(path: &str) -> bool {
     ];
 
     if let Some(ext) = path.rsplit('.').next() {
-        if BINARY_EXTENSIONS.contains(&ext) {
+        // Binary formats with a handler, e.g. Unity bundles, are translatable
+        if BINARY_EXTENSIONS.contains(&ext) && !FileFormat::from_extension(ext).is_binary() {
             return true;
         }
     }
@@ -854,10 +1753,13 @@ fn matches_text_extension(ext: &str) -> bool {
         "pot",
         "resx",
         "resw",
-        "strings",
         "properties",
         "loc",
         "lua",
+        "rpy",
+        "toml",
+        "mcfunction",
+        "snbt",
         "md",
         "html",
         "htm",
@@ -901,6 +1803,23 @@ fn detect_language_hint(path: &Path) -> Option<String> {
     None
 }
 
+/// Language of a file's entries, read from its first few kilobytes
+fn detect_file_content_language(path: &Path) -> Option<String> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(CONTENT_SAMPLE_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    // A cut-off last line could split a character and fail decoding
+    if bytes.len() as u64 == CONTENT_SAMPLE_BYTES {
+        let end = bytes.iter().rposition(|&byte| byte == b'\n')?;
+        bytes.truncate(end + 1);
+    }
+    let (text, _) = FileMetadata::decode_bytes(&bytes).ok()?;
+    language_detect::detect_content_language(&text)
+}
+
 fn split_language_tokens(segment: &str) -> Vec<String> {
     segment
         .split(|c: char| c == '.' || c == '_' || c == '-' || c == ' ')
@@ -0,0 +1,832 @@
+/// Persistent index of scanned Steam libraries.
+///
+/// A full `scan_steam_library` records every library, mod summary, and the
+/// directories and files of each mod with their modification times in an
+/// SQLite database. `rescan_changed` then lists the Workshop folders and
+/// compares directory mtimes only: unchanged mods are not read, mods with a
+/// changed directory are walked again and get a fresh summary when their
+/// files differ, and new or removed Workshop items are added or dropped.
+/// Steam replaces files when it updates an item, which touches the parent
+/// directory; an in-place edit that leaves every directory mtime alone needs
+/// a full scan to be noticed.
+use crate::access_mode::is_offline;
+use crate::job::sink::ProgressSink;
+use crate::library::{scan_library_with, LibraryEntry, LibraryScanner, ModSummary};
+use crate::paths::app_data_dir;
+use crate::policy::{self, PolicyBanner};
+use crate::steam::resolve_app_name;
+use log::warn;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, UNIX_EPOCH};
+#[cfg(feature = "tauri")]
+use tauri::AppHandle;
+
+const INDEX_FILE_NAME: &str = "library-index.sqlite3";
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS libraries (
+    path TEXT PRIMARY KEY,
+    entry TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS mods (
+    id TEXT PRIMARY KEY,
+    library TEXT NOT NULL,
+    directory TEXT NOT NULL,
+    summary TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS dirs (
+    path TEXT PRIMARY KEY,
+    mod_id TEXT NOT NULL,
+    mtime INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS files (
+    path TEXT PRIMARY KEY,
+    mod_id TEXT NOT NULL,
+    mtime INTEGER NOT NULL,
+    size INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS dirs_by_mod ON dirs(mod_id);
+CREATE INDEX IF NOT EXISTS files_by_mod ON files(mod_id);
+";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RescanReport {
+    pub libraries: Vec<LibraryEntry>,
+    pub policy_banner: PolicyBanner,
+    /// No index existed, so a full scan was run instead
+    pub full_scan: bool,
+    /// Mod ids found since the last scan
+    pub added: Vec<String>,
+    /// Mod ids whose files changed
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+/// Sort order of `query_mods`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ModSortKey {
+    #[default]
+    Name,
+    Game,
+    LastUpdated,
+    FileCount,
+    /// Korean translation coverage; mods without language files sort first
+    TranslationCoverage,
+}
+
+/// Filters of `query_mods`; every field is optional and empty lists match
+/// everything
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ModQuery {
+    /// Case-insensitive text in the mod name or id
+    pub text: Option<String>,
+    /// Games to include
+    pub games: Vec<String>,
+    /// Languages every result must have installed
+    pub languages: Vec<String>,
+    /// Only mods with (`true`) or without (`false`) a Korean translation
+    pub has_korean: Option<bool>,
+    pub min_files: Option<usize>,
+    pub max_files: Option<usize>,
+    /// Unix seconds
+    pub updated_after: Option<i64>,
+    pub updated_before: Option<i64>,
+    pub sort: ModSortKey,
+    pub descending: bool,
+    pub offset: usize,
+    /// Page size; all remaining results when unset
+    pub limit: Option<usize>,
+}
+
+/// A mod from the index with the facts `query_mods` filters on
+#[derive(Debug, Serialize, Clone)]
+pub struct IndexedMod {
+    #[serde(flatten)]
+    pub summary: ModSummary,
+    pub library: String,
+    pub file_count: usize,
+    /// Latest directory change in the mod, Unix seconds
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModQueryResult {
+    pub mods: Vec<IndexedMod>,
+    /// Matches before pagination
+    pub total: usize,
+    pub offset: usize,
+}
+
+impl ModQuery {
+    fn matches(&self, indexed: &IndexedMod) -> bool {
+        let summary = &indexed.summary;
+        let has_language = |lang: &str| {
+            summary
+                .installed_languages
+                .iter()
+                .any(|installed| installed == lang)
+        };
+        let text_matches = self.text.iter().all(|text| {
+            let text = text.trim().to_lowercase();
+            summary.name.to_lowercase().contains(&text) || summary.id.to_lowercase().contains(&text)
+        });
+
+        // `Option::iter().all` holds for unset filters
+        text_matches
+            && (self.games.is_empty() || self.games.contains(&summary.game))
+            && self.languages.iter().all(|lang| has_language(lang))
+            && self
+                .has_korean
+                .iter()
+                .all(|wanted| has_language("ko") == *wanted)
+            && self.min_files.iter().all(|min| indexed.file_count >= *min)
+            && self.max_files.iter().all(|max| indexed.file_count <= *max)
+            && self
+                .updated_after
+                .iter()
+                .all(|after| indexed.updated_at >= *after)
+            && self
+                .updated_before
+                .iter()
+                .all(|before| indexed.updated_at <= *before)
+    }
+
+    fn compare(&self, a: &IndexedMod, b: &IndexedMod) -> std::cmp::Ordering {
+        let by_name = || {
+            a.summary
+                .name
+                .to_lowercase()
+                .cmp(&b.summary.name.to_lowercase())
+        };
+        let ordering = match self.sort {
+            ModSortKey::Name => by_name(),
+            ModSortKey::Game => a.summary.game.cmp(&b.summary.game).then_with(by_name),
+            ModSortKey::LastUpdated => a.updated_at.cmp(&b.updated_at).then_with(by_name),
+            ModSortKey::FileCount => a.file_count.cmp(&b.file_count).then_with(by_name),
+            ModSortKey::TranslationCoverage => {
+                let percent = |indexed: &IndexedMod| {
+                    indexed
+                        .summary
+                        .translation_coverage
+                        .as_ref()
+                        .map_or(-1.0, |coverage| coverage.percent)
+                };
+                percent(a).total_cmp(&percent(b)).then_with(by_name)
+            }
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Directory and file mtimes of one mod, keyed by path
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ModTree {
+    dirs: BTreeMap<String, i64>,
+    files: BTreeMap<String, (i64, u64)>,
+}
+
+fn mtime(meta: &fs::Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as i64)
+}
+
+impl ModTree {
+    /// Walks `root` without following symlinks, like `list_mod_files`
+    fn walk(root: &Path) -> Self {
+        let mut tree = Self::default();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(meta) = fs::symlink_metadata(&dir) else {
+                continue;
+            };
+            tree.dirs
+                .insert(dir.to_string_lossy().to_string(), mtime(&meta));
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                let file_type = meta.file_type();
+                if file_type.is_symlink() {
+                    continue;
+                }
+                if file_type.is_dir() {
+                    pending.push(entry.path());
+                } else if file_type.is_file() {
+                    tree.files.insert(
+                        entry.path().to_string_lossy().to_string(),
+                        (mtime(&meta), meta.len()),
+                    );
+                }
+            }
+        }
+        tree
+    }
+
+    /// Whether any recorded directory is gone or has a different mtime
+    fn dirs_changed(&self) -> bool {
+        self.dirs.iter().any(|(path, recorded)| {
+            fs::symlink_metadata(path).map_or(true, |meta| mtime(&meta) != *recorded)
+        })
+    }
+}
+
+pub struct LibraryIndex {
+    conn: Connection,
+}
+
+fn db_error(error: rusqlite::Error) -> String {
+    format!("라이브러리 색인을 사용할 수 없습니다: {}", error)
+}
+
+impl LibraryIndex {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("라이브러리 색인 폴더를 만들지 못했습니다: {}", e))?;
+        }
+        let conn = Connection::open(path).map_err(db_error)?;
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Self { conn })
+    }
+
+    pub fn open_default() -> Result<Self, String> {
+        let path = app_data_dir().join(INDEX_FILE_NAME);
+        Self::open(&path)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, String> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM libraries", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|count| count == 0)
+            .map_err(db_error)
+    }
+
+    /// Replaces the index with the result of a full scan
+    pub fn record_scan(&mut self, libraries: &[LibraryEntry]) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(db_error)?;
+        tx.execute_batch(
+            "DELETE FROM libraries; DELETE FROM mods; DELETE FROM dirs; DELETE FROM files;",
+        )
+        .map_err(db_error)?;
+        for library in libraries {
+            let mut entry = library.clone();
+            entry.mods.clear();
+            tx.execute(
+                "INSERT OR REPLACE INTO libraries (path, entry) VALUES (?1, ?2)",
+                params![library.path, to_json(&entry)?],
+            )
+            .map_err(db_error)?;
+            for summary in &library.mods {
+                insert_mod(&tx, &library.path, summary)?;
+            }
+        }
+        tx.commit().map_err(db_error)
+    }
+
+    /// Libraries as recorded, with their mods in scan order
+    pub fn libraries(&self) -> Result<Vec<LibraryEntry>, String> {
+        let mut libraries: Vec<LibraryEntry> = {
+            let mut statement = self
+                .conn
+                .prepare("SELECT entry FROM libraries ORDER BY rowid")
+                .map_err(db_error)?;
+            let rows = statement
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(db_error)?;
+            rows.filter_map(|row| row.ok())
+                .filter_map(|entry| serde_json::from_str(&entry).ok())
+                .collect()
+        };
+        let mut statement = self
+            .conn
+            .prepare("SELECT library, summary FROM mods ORDER BY rowid")
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(db_error)?;
+        for (library_path, summary) in rows.filter_map(|row| row.ok()) {
+            let Ok(summary) = serde_json::from_str::<ModSummary>(&summary) else {
+                continue;
+            };
+            if let Some(library) = libraries
+                .iter_mut()
+                .find(|library| library.path == library_path)
+            {
+                library.mods.push(summary);
+            }
+        }
+        Ok(libraries)
+    }
+
+    /// Updates the index for Workshop items added, removed, or changed since
+    /// the last scan
+    pub fn rescan_changed(&mut self, scanner: &LibraryScanner) -> Result<RescanReport, String> {
+        let started = Instant::now();
+        let mut report = RescanReport {
+            libraries: Vec::new(),
+            policy_banner: policy::default_policy_banner(),
+            full_scan: false,
+            added: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+            elapsed_ms: 0,
+        };
+
+        let indexed: HashMap<String, (String, String)> = {
+            let mut statement = self
+                .conn
+                .prepare("SELECT id, library, directory FROM mods WHERE directory != ''")
+                .map_err(db_error)?;
+            let rows = statement
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+                })
+                .map_err(db_error)?;
+            rows.filter_map(|row| row.ok()).collect()
+        };
+        let library_paths: Vec<String> = self
+            .libraries()?
+            .into_iter()
+            .map(|library| library.path)
+            .collect();
+
+        let tx = self.conn.transaction().map_err(db_error)?;
+        let mut seen = HashSet::new();
+        for library_path in &library_paths {
+            let steamapps = Path::new(library_path).join("steamapps");
+            for (app_id, mod_id, mod_path) in workshop_items(&steamapps) {
+                let id = format!("{app_id}:{mod_id}");
+                if !seen.insert(id.clone()) {
+                    continue;
+                }
+                match indexed.get(&id) {
+                    // Another library already holds this item
+                    Some((library, _)) if library != library_path => {}
+                    Some(_) => {
+                        let recorded = load_tree(&tx, &id)?;
+                        if !recorded.dirs_changed() {
+                            continue;
+                        }
+                        let current = ModTree::walk(&mod_path);
+                        if current.files != recorded.files {
+                            report.updated.push(id.clone());
+                            let game = resolve_app_name(&steamapps, &app_id)
+                                .unwrap_or_else(|| format!("앱 {app_id}"));
+                            let summary = scanner.workshop_mod_summary(
+                                &steamapps, &app_id, &mod_id, &game, &mod_path,
+                            );
+                            tx.execute(
+                                "UPDATE mods SET summary = ?2 WHERE id = ?1",
+                                params![id, to_json(&summary)?],
+                            )
+                            .map_err(db_error)?;
+                        }
+                        store_tree(&tx, &id, &current)?;
+                    }
+                    None => {
+                        let game = resolve_app_name(&steamapps, &app_id)
+                            .unwrap_or_else(|| format!("앱 {app_id}"));
+                        let summary = scanner
+                            .workshop_mod_summary(&steamapps, &app_id, &mod_id, &game, &mod_path);
+                        insert_mod(&tx, library_path, &summary)?;
+                        report.added.push(id);
+                    }
+                }
+            }
+        }
+
+        for id in indexed.keys().filter(|id| !seen.contains(*id)) {
+            delete_mod(&tx, id)?;
+            report.removed.push(id.clone());
+        }
+        tx.commit().map_err(db_error)?;
+
+        report.added.sort();
+        report.updated.sort();
+        report.removed.sort();
+        report.libraries = self.libraries()?;
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    /// Every indexed mod with its file count and latest directory mtime
+    pub fn indexed_mods(&self) -> Result<Vec<IndexedMod>, String> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT m.library, m.summary,
+                    (SELECT COUNT(*) FROM files f WHERE f.mod_id = m.id),
+                    (SELECT COALESCE(MAX(d.mtime), 0) FROM dirs d WHERE d.mod_id = m.id)
+                 FROM mods m ORDER BY m.rowid",
+            )
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            })
+            .map_err(db_error)?;
+        Ok(rows
+            .filter_map(|row| row.ok())
+            .filter_map(|(library, summary, file_count, mtime)| {
+                Some(IndexedMod {
+                    summary: serde_json::from_str(&summary).ok()?,
+                    library,
+                    file_count: file_count as usize,
+                    updated_at: mtime / 1_000_000_000,
+                })
+            })
+            .collect())
+    }
+
+    pub fn query_mods(&self, query: &ModQuery) -> Result<ModQueryResult, String> {
+        let mut matches: Vec<IndexedMod> = self
+            .indexed_mods()?
+            .into_iter()
+            // Placeholder entries for scan problems are not mods
+            .filter(|indexed| !indexed.summary.directory.is_empty())
+            .filter(|indexed| query.matches(indexed))
+            .collect();
+        matches.sort_by(|a, b| query.compare(a, b));
+
+        let total = matches.len();
+        let mods = matches
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(ModQueryResult {
+            mods,
+            total,
+            offset: query.offset,
+        })
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| e.to_string())
+}
+
+/// `(app id, item id, path)` of every Workshop item folder of a library
+fn workshop_items(steamapps: &Path) -> Vec<(String, String, PathBuf)> {
+    let mut items = Vec::new();
+    let Ok(apps) = fs::read_dir(steamapps.join("workshop/content")) else {
+        return items;
+    };
+    for app in apps.flatten() {
+        if !app.file_type().is_ok_and(|kind| kind.is_dir()) {
+            continue;
+        }
+        let Some(app_id) = app.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Ok(mods) = fs::read_dir(app.path()) else {
+            continue;
+        };
+        for item in mods.flatten() {
+            if !item.file_type().is_ok_and(|kind| kind.is_dir()) {
+                continue;
+            }
+            if let Some(mod_id) = item.file_name().to_str() {
+                items.push((app_id.clone(), mod_id.to_string(), item.path()));
+            }
+        }
+    }
+    items
+}
+
+fn insert_mod(conn: &Connection, library: &str, summary: &ModSummary) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO mods (id, library, directory, summary) VALUES (?1, ?2, ?3, ?4)",
+        params![summary.id, library, summary.directory, to_json(summary)?],
+    )
+    .map_err(db_error)?;
+    if !summary.directory.is_empty() {
+        store_tree(
+            conn,
+            &summary.id,
+            &ModTree::walk(Path::new(&summary.directory)),
+        )?;
+    }
+    Ok(())
+}
+
+fn delete_mod(conn: &Connection, id: &str) -> Result<(), String> {
+    for statement in [
+        "DELETE FROM mods WHERE id = ?1",
+        "DELETE FROM dirs WHERE mod_id = ?1",
+        "DELETE FROM files WHERE mod_id = ?1",
+    ] {
+        conn.execute(statement, params![id]).map_err(db_error)?;
+    }
+    Ok(())
+}
+
+fn load_tree(conn: &Connection, id: &str) -> Result<ModTree, String> {
+    let mut tree = ModTree::default();
+    let mut statement = conn
+        .prepare_cached("SELECT path, mtime FROM dirs WHERE mod_id = ?1")
+        .map_err(db_error)?;
+    let rows = statement
+        .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(db_error)?;
+    tree.dirs = rows.filter_map(|row| row.ok()).collect();
+
+    let mut statement = conn
+        .prepare_cached("SELECT path, mtime, size FROM files WHERE mod_id = ?1")
+        .map_err(db_error)?;
+    let rows = statement
+        .query_map(params![id], |row| {
+            Ok((row.get(0)?, (row.get(1)?, row.get::<_, i64>(2)? as u64)))
+        })
+        .map_err(db_error)?;
+    tree.files = rows.filter_map(|row| row.ok()).collect();
+    Ok(tree)
+}
+
+fn store_tree(conn: &Connection, id: &str, tree: &ModTree) -> Result<(), String> {
+    conn.execute("DELETE FROM dirs WHERE mod_id = ?1", params![id])
+        .map_err(db_error)?;
+    conn.execute("DELETE FROM files WHERE mod_id = ?1", params![id])
+        .map_err(db_error)?;
+    let mut insert_dir = conn
+        .prepare_cached("INSERT OR REPLACE INTO dirs (path, mod_id, mtime) VALUES (?1, ?2, ?3)")
+        .map_err(db_error)?;
+    for (path, mtime) in &tree.dirs {
+        insert_dir
+            .execute(params![path, id, mtime])
+            .map_err(db_error)?;
+    }
+    let mut insert_file = conn
+        .prepare_cached(
+            "INSERT OR REPLACE INTO files (path, mod_id, mtime, size) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .map_err(db_error)?;
+    for (path, (mtime, size)) in &tree.files {
+        insert_file
+            .execute(params![path, id, mtime, *size as i64])
+            .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+/// Records a full Steam scan in the index; failures only cost the next
+/// rescan its speed
+pub fn record_library_scan(libraries: &[LibraryEntry]) {
+    if let Err(error) =
+        LibraryIndex::open_default().and_then(|mut index| index.record_scan(libraries))
+    {
+        warn!("failed to update the library index: {}", error);
+    }
+}
+
+/// Indexed libraries, or `None` when nothing was indexed yet. Offline mode
+/// answers library scans with these instead of walking the mod folders.
+pub fn indexed_libraries() -> Option<Vec<LibraryEntry>> {
+    let index = LibraryIndex::open_default().ok()?;
+    if index.is_empty().unwrap_or(true) {
+        return None;
+    }
+    index.libraries().ok()
+}
+
+/// Tauri command to refresh the library from the index, reading only
+/// Workshop items that changed since the last scan. Runs a full Steam scan
+/// when nothing was indexed yet. In offline mode the index is returned
+/// unchanged.
+#[cfg(feature = "tauri")]
+#[tauri::command]
+pub fn rescan_changed(app: AppHandle) -> Result<RescanReport, String> {
+    rescan_changed_with(&app)
+}
+
+/// [`rescan_changed`] reporting a full scan's progress to `sink`
+pub fn rescan_changed_with(sink: &dyn ProgressSink) -> Result<RescanReport, String> {
+    if is_offline() {
+        if let Some(libraries) = indexed_libraries() {
+            return Ok(RescanReport {
+                libraries,
+                policy_banner: policy::default_policy_banner(),
+                full_scan: false,
+                added: Vec::new(),
+                updated: Vec::new(),
+                removed: Vec::new(),
+                elapsed_ms: 0,
+            });
+        }
+    }
+    let mut index = LibraryIndex::open_default()?;
+    if index.is_empty()? {
+        let started = Instant::now();
+        let response = scan_library_with(sink, None, None)?;
+        let added = response
+            .libraries
+            .iter()
+            .flat_map(|library| library.mods.iter().map(|summary| summary.id.clone()))
+            .collect();
+        return Ok(RescanReport {
+            libraries: response.libraries,
+            policy_banner: response.policy_banner,
+            full_scan: true,
+            added,
+            updated: Vec::new(),
+            removed: Vec::new(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+    index.rescan_changed(&LibraryScanner::new())
+}
+
+/// Tauri command to search, filter, sort and page the indexed mods
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn query_mods(query: ModQuery) -> Result<ModQueryResult, String> {
+    let index = LibraryIndex::open_default()?;
+    if index.is_empty()? {
+        return Err("라이브러리 색인이 없습니다. 먼저 라이브러리를 스캔하세요.".into());
+    }
+    index.query_mods(&query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::LibraryScanDebug;
+    use crate::steam::LibraryDiscoveryDebug;
+    use std::time::Duration;
+
+    fn scan(scanner: &LibraryScanner, library: &Path) -> Vec<LibraryEntry> {
+        let mut debug = LibraryScanDebug::new(LibraryDiscoveryDebug::default());
+        scanner
+            .scan(&[library.to_path_buf()], &mut debug)
+            .expect("scan library")
+    }
+
+    fn mod_ids(libraries: &[LibraryEntry]) -> Vec<String> {
+        libraries
+            .iter()
+            .flat_map(|library| library.mods.iter().map(|summary| summary.id.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn rescan_reports_added_updated_and_removed_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("Steam");
+        let content = library.join("steamapps/workshop/content/294100");
+        for item in ["111", "222", "333"] {
+            fs::create_dir_all(content.join(item).join("Languages")).unwrap();
+            fs::write(content.join(item).join("Languages/Keyed.xml"), "<a/>").unwrap();
+        }
+
+        let scanner = LibraryScanner::new();
+        let mut index = LibraryIndex::open(&dir.path().join("index.sqlite3")).unwrap();
+        assert!(index.is_empty().unwrap());
+        index.record_scan(&scan(&scanner, &library)).unwrap();
+        assert_eq!(
+            mod_ids(&index.libraries().unwrap()),
+            mod_ids(&scan(&scanner, &library))
+        );
+
+        let unchanged = index.rescan_changed(&scanner).unwrap();
+        assert!(unchanged.added.is_empty() && unchanged.updated.is_empty());
+        assert!(unchanged.removed.is_empty());
+
+        // Coarse filesystem clocks need a moment for the mtime to move
+        std::thread::sleep(Duration::from_millis(20));
+        fs::write(content.join("111/Languages/Added.xml"), "<b/>").unwrap();
+        fs::remove_dir_all(content.join("222")).unwrap();
+        fs::create_dir_all(content.join("444")).unwrap();
+
+        let report = index.rescan_changed(&scanner).unwrap();
+        assert!(!report.full_scan);
+        assert_eq!(report.added, vec!["294100:444"]);
+        assert_eq!(report.updated, vec!["294100:111"]);
+        assert_eq!(report.removed, vec!["294100:222"]);
+        let mut ids = mod_ids(&report.libraries);
+        ids.sort();
+        assert_eq!(ids, vec!["294100:111", "294100:333", "294100:444"]);
+
+        let again = index.rescan_changed(&scanner).unwrap();
+        assert!(again.added.is_empty() && again.updated.is_empty() && again.removed.is_empty());
+    }
+
+    #[test]
+    fn query_filters_sorts_and_pages_indexed_mods() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("Steam");
+        let content = library.join("steamapps/workshop/content/294100");
+        for (item, name, extra_files) in [
+            (
+                "111",
+                "Alpha Weapons",
+                &["Korean/Keyed.xml", "Defs/A.xml"][..],
+            ),
+            ("222", "beta Armor", &[][..]),
+            ("333", "Gamma", &["Defs/B.xml"][..]),
+        ] {
+            let about = content.join(item).join("About/About.xml");
+            fs::create_dir_all(about.parent().unwrap()).unwrap();
+            fs::write(
+                &about,
+                format!("<ModMetaData><name>{name}</name></ModMetaData>"),
+            )
+            .unwrap();
+            for file in extra_files {
+                let path = content.join(item).join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, "<a/>").unwrap();
+            }
+        }
+
+        let mut index = LibraryIndex::open(&dir.path().join("index.sqlite3")).unwrap();
+        index
+            .record_scan(&scan(&LibraryScanner::new(), &library))
+            .unwrap();
+        let names = |query: ModQuery| -> Vec<String> {
+            index
+                .query_mods(&query)
+                .unwrap()
+                .mods
+                .into_iter()
+                .map(|indexed| indexed.summary.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names(ModQuery::default()),
+            vec!["Alpha Weapons", "beta Armor", "Gamma"]
+        );
+        assert_eq!(
+            names(ModQuery {
+                text: Some("ARMOR".into()),
+                ..Default::default()
+            }),
+            vec!["beta Armor"]
+        );
+        assert_eq!(
+            names(ModQuery {
+                has_korean: Some(false),
+                ..Default::default()
+            }),
+            vec!["beta Armor", "Gamma"]
+        );
+        assert_eq!(
+            names(ModQuery {
+                languages: vec!["ko".into()],
+                ..Default::default()
+            }),
+            vec!["Alpha Weapons"]
+        );
+        assert_eq!(
+            names(ModQuery {
+                min_files: Some(2),
+                sort: ModSortKey::FileCount,
+                descending: true,
+                ..Default::default()
+            }),
+            vec!["Alpha Weapons", "Gamma"]
+        );
+        assert!(names(ModQuery {
+            updated_before: Some(0),
+            ..Default::default()
+        })
+        .is_empty());
+
+        let page = index
+            .query_mods(&ModQuery {
+                offset: 1,
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.mods.len(), 1);
+        assert_eq!(page.mods[0].summary.name, "beta Armor");
+        assert_eq!(page.mods[0].file_count, 1);
+        assert!(page.mods[0].updated_at > 0);
+    }
+}
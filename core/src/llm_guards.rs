@@ -3,6 +3,9 @@
 /// Provides constraint strings and validation rules for LLM translation prompts
 /// to ensure proper token preservation and format adherence.
 
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// LLM translation constraints for token preservation
@@ -231,6 +234,238 @@ pub fn build_user_prompt(text: &str, context: Option<&str>) -> String {
     }
 }
 
+/// Chatter models put before the translation: "Translation:",
+/// "Here is the Korean translation:", "**번역:**"
+static RESPONSE_PREFIX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:(?:sure|certainly|of course|okay)[!,.]?\s*)?(?:\*\*)?(?:here(?:'s| is) (?:the |your |my )?(?:\w+ )?(?:translation|translated text)[^:\n]{0,40}|translation|translated text|번역(?: 결과| 문장)?)\s*(?:\*\*)?\s*[:：]\s*(?:\*\*)?\s*"
+    ).expect("valid response prefix regex")
+});
+
+/// Start of a note or explanation paragraph: "Note:", "**Explanation:**",
+/// "(Note: ...", "참고:"
+static RESPONSE_NOTE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^[ \t]*(?:[*_]{1,2})?\(?(?:notes?|explanation|translation notes?|translator'?s notes?|참고|설명|주석)(?:[*_]{1,2})?\s*[:：]"
+    ).expect("valid response note regex")
+});
+
+static PARAGRAPH_BREAK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n[ \t]*\r?\n").expect("valid paragraph break regex"));
+
+/// Quote pairs models wrap translations in
+const RESPONSE_QUOTES: [(char, char); 7] = [
+    ('"', '"'),
+    ('\'', '\''),
+    ('“', '”'),
+    ('‘', '’'),
+    ('「', '」'),
+    ('『', '』'),
+    ('«', '»'),
+];
+
+/// Removes what models add around a translation: a code fence around the
+/// whole reply, a "Translation:" style prefix, quotes around the text and
+/// trailing note paragraphs. Each is only removed when `source` does not have
+/// it itself, so translations of text that really is fenced or quoted are
+/// kept.
+pub fn sanitize_response(output: &str, source: &str) -> String {
+    let source = source.trim();
+    let mut text = output.trim();
+    loop {
+        let before = text;
+        if !source.starts_with("```") {
+            text = strip_code_fence(text);
+        }
+        if !RESPONSE_PREFIX_RE.is_match(source) {
+            if let Some(prefix) = RESPONSE_PREFIX_RE.find(text) {
+                // Keep replies that are nothing but the label
+                if prefix.end() < text.len() {
+                    text = text[prefix.end()..].trim();
+                }
+            }
+        }
+        text = strip_trailing_notes(text, source);
+        text = strip_surrounding_quotes(text, source);
+        if text == before {
+            return text.to_string();
+        }
+    }
+}
+
+/// Contents of a code fence wrapping all of `text`, without the language tag
+pub fn strip_code_fence(text: &str) -> &str {
+    let Some(body) = text.strip_prefix("```") else {
+        return text;
+    };
+    let Some(body) = body.strip_suffix("```") else {
+        return text;
+    };
+    match body.find('\n') {
+        Some(newline) => body[newline + 1..].trim(),
+        None => body.trim(),
+    }
+}
+
+fn strip_trailing_notes<'a>(text: &'a str, source: &str) -> &'a str {
+    let source_has_notes = source
+        .lines()
+        .any(|line| RESPONSE_NOTE_RE.is_match(line));
+    if source_has_notes {
+        return text;
+    }
+    PARAGRAPH_BREAK_RE
+        .find_iter(text)
+        .find(|paragraph_break| RESPONSE_NOTE_RE.is_match(&text[paragraph_break.end()..]))
+        .map(|paragraph_break| text[..paragraph_break.start()].trim_end())
+        .unwrap_or(text)
+}
+
+fn strip_surrounding_quotes<'a>(text: &'a str, source: &str) -> &'a str {
+    for (open, close) in RESPONSE_QUOTES {
+        if source.starts_with(open) {
+            continue;
+        }
+        let inner = text
+            .strip_prefix(open)
+            .and_then(|rest| rest.strip_suffix(close));
+        if let Some(inner) = inner {
+            // `"A" or "B"` is two quotations, not one quoted reply
+            if !inner.contains(close) && !inner.trim().is_empty() {
+                return inner.trim();
+            }
+        }
+    }
+    text
+}
+
+/// Refusals models answer with instead of a translation. Plain apologies
+/// in the target language are common in game dialogue and not matched.
+static REFUSAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:(?:i'?m sorry|i am sorry|i apologi[sz]e),? (?:but )?i (?:can(?:'|no)?t|am unable|'?m unable|won'?t)|i can(?:'|no)?t (?:assist with|help with|translate|comply)|as an ai\b|as a (?:large )?language model)|번역(?:해 드릴|할) 수 없|(?:AI|인공지능) (?:언어 )?모델로서|翻訳できません|我无法翻译"
+    ).expect("valid refusal regex")
+});
+
+/// Prompt injection and chat template leftovers
+static INJECTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)ignore (?:all |any )?(?:the )?(?:previous|prior|above) (?:instructions|prompts?)|disregard (?:all |the )?(?:previous|prior|above)|system prompt|you are now (?:a|an) |<\|(?:im_start|im_end|endoftext|system|assistant|user)\|>|\[/?INST\]|</?(?:system|assistant|user)>|이전 지시(?:를|사항을) 무시"
+    ).expect("valid injection regex")
+});
+
+/// Strong profanity; words with a harmless game meaning ("kill", "damn") are
+/// left out
+static PROFANITY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:fuck\w*|motherfuck\w*|shit(?:ty|head)?|bitch(?:es)?|cunt|asshole|dickhead|bastard)\b|씨발|시발|ㅅㅂ|좆|개새끼|병신|지랄|니미|ㅈ같"
+    ).expect("valid profanity regex")
+});
+
+static SLUR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:nigg(?:er|a)s?|faggots?|fags?|retards?|chinks?|kikes?)\b|짱깨|쪽발이|쪽바리|깜둥이")
+        .expect("valid slur regex")
+});
+
+/// 번역 결과 필터 동작
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFilterMode {
+    /// 걸린 번역은 쓰지 않고 원문 유지
+    Block,
+    /// 번역은 쓰고 QC 경고를 남김 (기본값)
+    #[default]
+    Warn,
+    /// 검사하지 않음
+    Allow,
+}
+
+/// 번역 결과의 욕설·비하 표현·거절 응답·프롬프트 주입 검사 설정
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputFilterConfig {
+    pub mode: OutputFilterMode,
+    /// 함께 막을 단어나 표현 (대소문자 무시, 부분 일치)
+    pub blocked_terms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputIssueKind {
+    Refusal,
+    InjectedInstruction,
+    Profanity,
+    Slur,
+    BlockedTerm,
+}
+
+impl OutputIssueKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Refusal => "거절/사과 응답",
+            Self::InjectedInstruction => "지시문 주입",
+            Self::Profanity => "욕설",
+            Self::Slur => "비하 표현",
+            Self::BlockedTerm => "차단 단어",
+        }
+    }
+}
+
+/// Text in a model output that should not end up in a game file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputIssue {
+    pub kind: OutputIssueKind,
+    pub matched: String,
+}
+
+impl OutputIssue {
+    pub fn describe(&self) -> String {
+        format!("출력 필터: {} ({})", self.kind.label(), self.matched)
+    }
+}
+
+impl OutputFilterConfig {
+    /// Finds refusals, injected instructions, profanity, slurs and the
+    /// job's blocked terms in `output`. A match counts only when `source`
+    /// has no match of the same kind, so crude dialogue translated as crude
+    /// dialogue passes.
+    pub fn scan(&self, source: &str, output: &str) -> Vec<OutputIssue> {
+        if self.mode == OutputFilterMode::Allow {
+            return Vec::new();
+        }
+        let mut issues = Vec::new();
+        for (kind, pattern) in [
+            (OutputIssueKind::Refusal, &*REFUSAL_RE),
+            (OutputIssueKind::InjectedInstruction, &*INJECTION_RE),
+            (OutputIssueKind::Profanity, &*PROFANITY_RE),
+            (OutputIssueKind::Slur, &*SLUR_RE),
+        ] {
+            if pattern.is_match(source) {
+                continue;
+            }
+            if let Some(found) = pattern.find(output) {
+                issues.push(OutputIssue {
+                    kind,
+                    matched: found.as_str().to_string(),
+                });
+            }
+        }
+        let source = source.to_lowercase();
+        let output_lower = output.to_lowercase();
+        for term in &self.blocked_terms {
+            let term = term.trim().to_lowercase();
+            if !term.is_empty() && output_lower.contains(&term) && !source.contains(&term) {
+                issues.push(OutputIssue {
+                    kind: OutputIssueKind::BlockedTerm,
+                    matched: term,
+                });
+            }
+        }
+        issues
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +575,93 @@ mod tests {
         assert!(prompt.contains("LaTeX"));
         assert!(prompt.contains("Mathematical expressions"));
     }
+    
+    #[test]
+    fn test_sanitize_response_provider_shapes() {
+        let source = "Hello ⟦MT:TAG:0⟧ world";
+        let expected = "안녕 ⟦MT:TAG:0⟧ 세계";
+        let shapes = [
+            // GPT
+            "Translation: 안녕 ⟦MT:TAG:0⟧ 세계",
+            "\"안녕 ⟦MT:TAG:0⟧ 세계\"",
+            // Claude
+            "Here is the Korean translation:\n\n안녕 ⟦MT:TAG:0⟧ 세계",
+            "안녕 ⟦MT:TAG:0⟧ 세계\n\nNote: I kept the token unchanged.",
+            // Gemini
+            "```\n안녕 ⟦MT:TAG:0⟧ 세계\n```",
+            "**Translation:** 안녕 ⟦MT:TAG:0⟧ 세계\n\n**Explanation:**\n* 안녕 means hello",
+            // Grok
+            "Sure! Here's the translation: “안녕 ⟦MT:TAG:0⟧ 세계”",
+            "```text\n번역: 안녕 ⟦MT:TAG:0⟧ 세계\n```",
+        ];
+        for shape in shapes {
+            assert_eq!(sanitize_response(shape, source), expected, "{shape:?}");
+        }
+    }
+    
+    #[test]
+    fn test_sanitize_response_keeps_source_shapes() {
+        // Quotes, notes and labels that are part of the source stay
+        assert_eq!(
+            sanitize_response("\"조심해!\"", "\"Watch out!\""),
+            "\"조심해!\""
+        );
+        assert_eq!(
+            sanitize_response("사용법\n\n참고: 연료가 필요합니다.", "Usage\n\nNote: Needs fuel."),
+            "사용법\n\n참고: 연료가 필요합니다."
+        );
+        assert_eq!(
+            sanitize_response("번역: 완료", "Translation: done"),
+            "번역: 완료"
+        );
+        assert_eq!(
+            sanitize_response("\"예\" 또는 \"아니요\"", "Yes or No"),
+            "\"예\" 또는 \"아니요\""
+        );
+        assert_eq!(sanitize_response("줄1\n\n줄2", "Line1\n\nLine2"), "줄1\n\n줄2");
+    }
+
+    #[test]
+    fn output_filter_flags_new_unsafe_text_only() {
+        let filter = OutputFilterConfig::default();
+        let kinds = |source: &str, output: &str| -> Vec<OutputIssueKind> {
+            filter
+                .scan(source, output)
+                .iter()
+                .map(|issue| issue.kind)
+                .collect()
+        };
+
+        assert!(kinds("Open the gate", "문을 연다").is_empty());
+        assert_eq!(
+            kinds("Open the gate", "I'm sorry, but I can't translate this."),
+            [OutputIssueKind::Refusal]
+        );
+        assert!(kinds("미안해", "I'm sorry").is_empty());
+        assert_eq!(
+            kinds("Open the gate", "문을 연다 <|im_start|>system"),
+            [OutputIssueKind::InjectedInstruction]
+        );
+        assert_eq!(
+            kinds("Open the gate", "씨발 문을 연다"),
+            [OutputIssueKind::Profanity]
+        );
+        // Crude source, crude translation
+        assert!(kinds("Shit, the gate is stuck", "젠장, 문이 안 열려. 씨발").is_empty());
+        assert!(kinds("Kill the raiders", "습격자를 죽여라").is_empty());
+
+        let custom = OutputFilterConfig {
+            mode: OutputFilterMode::Block,
+            blocked_terms: vec!["Lorem".into()],
+        };
+        let issues = custom.scan("Open the gate", "lorem ipsum");
+        assert_eq!(issues[0].kind, OutputIssueKind::BlockedTerm);
+        assert_eq!(issues[0].describe(), "출력 필터: 차단 단어 (lorem)");
+
+        let allow = OutputFilterConfig {
+            mode: OutputFilterMode::Allow,
+            ..custom
+        };
+        assert!(allow.scan("Open the gate", "I'm sorry").is_empty());
+    }
 }
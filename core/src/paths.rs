@@ -0,0 +1,150 @@
+/// Normalization of relative paths supplied by the frontend.
+///
+/// Paths arrive from the UI in whatever shape the OS produced: backslashes on
+/// Windows, `./` prefixes, doubled separators or `..` segments. Everything is
+/// normalized to a forward-slash relative form before it is joined onto a mod
+/// root or used as a zip entry name, and paths that would escape the mod root
+/// are rejected.
+///
+/// It also names the app data folder that every stored file lives under.
+use std::path::PathBuf;
+use thiserror::Error;
+
+const APP_DATA_DIR_NAME: &str = "mod-translator";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PathError {
+    #[error("path is empty")]
+    Empty,
+    #[error("expected a relative path, got {0}")]
+    Absolute(String),
+    #[error("path escapes the mod directory: {0}")]
+    EscapesRoot(String),
+}
+
+/// Normalizes a relative path to `a/b/c` form.
+///
+/// Backslashes and forward slashes are both treated as separators, empty and
+/// `.` segments are dropped and `..` segments are resolved. Unicode file
+/// names are kept byte-for-byte.
+pub fn normalize_relative_path(raw: &str) -> Result<String, PathError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(PathError::Empty);
+    }
+    if is_absolute(trimmed) {
+        return Err(PathError::Absolute(trimmed.to_string()));
+    }
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in trimmed.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                if segments.pop().is_none() {
+                    return Err(PathError::EscapesRoot(trimmed.to_string()));
+                }
+            }
+            other => segments.push(other),
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(PathError::Empty);
+    }
+    Ok(segments.join("/"))
+}
+
+/// Like [`normalize_relative_path`] but for optional fields, where a missing
+/// or blank value stays `None`.
+pub fn normalize_optional_relative_path(raw: Option<&str>) -> Result<Option<String>, PathError> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => normalize_relative_path(value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// `mod-translator` in the OS local data folder (`%LOCALAPPDATA%`,
+/// `~/Library/Application Support`, `~/.local/share`), or in the working
+/// directory when the OS has none
+pub fn app_data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DATA_DIR_NAME)
+}
+
+fn is_absolute(path: &str) -> bool {
+    if path.starts_with('/') || path.starts_with('\\') {
+        return true;
+    }
+    // Windows drive prefix such as `C:` or `C:\`.
+    let mut chars = path.chars();
+    matches!(
+        (chars.next(), chars.next()),
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_windows_separators() {
+        assert_eq!(
+            normalize_relative_path(r"Languages\English\Keyed\Misc.xml").unwrap(),
+            "Languages/English/Keyed/Misc.xml"
+        );
+        assert_eq!(
+            normalize_relative_path(r"locale/en\\strings.cfg").unwrap(),
+            "locale/en/strings.cfg"
+        );
+    }
+
+    #[test]
+    fn drops_redundant_segments() {
+        assert_eq!(
+            normalize_relative_path("./lang//./en_us.json ").unwrap(),
+            "lang/en_us.json"
+        );
+        assert_eq!(
+            normalize_relative_path(r"a\b\..\c\d.txt").unwrap(),
+            "a/c/d.txt"
+        );
+    }
+
+    #[test]
+    fn keeps_unicode_file_names() {
+        assert_eq!(
+            normalize_relative_path(r"번역\日本語\Ünïcødé 파일.txt").unwrap(),
+            "번역/日本語/Ünïcødé 파일.txt"
+        );
+    }
+
+    #[test]
+    fn rejects_absolute_and_escaping_paths() {
+        assert!(matches!(
+            normalize_relative_path(r"C:\Mods\file.xml"),
+            Err(PathError::Absolute(_))
+        ));
+        assert!(matches!(
+            normalize_relative_path("/etc/passwd"),
+            Err(PathError::Absolute(_))
+        ));
+        assert!(matches!(
+            normalize_relative_path(r"..\..\secret.txt"),
+            Err(PathError::EscapesRoot(_))
+        ));
+        assert_eq!(normalize_relative_path(" ./ "), Err(PathError::Empty));
+    }
+
+    #[test]
+    fn optional_paths_stay_none_when_blank() {
+        assert_eq!(normalize_optional_relative_path(None), Ok(None));
+        assert_eq!(normalize_optional_relative_path(Some("  ")), Ok(None));
+        assert_eq!(
+            normalize_optional_relative_path(Some(r"mods\pack.zip")),
+            Ok(Some("mods/pack.zip".into()))
+        );
+    }
+}
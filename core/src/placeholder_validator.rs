@@ -2,6 +2,8 @@
 ///
 /// This module implements comprehensive validation of protected tokens (⟦MT:TAG:n⟧, etc.)
 /// and format tokens ({n}) during translation, with automatic recovery mechanisms.
+use crate::profiles::{TokenSubstitution, ValidatorProfileConfig};
+use crate::protector::{MarkerScheme, TokenMap};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -11,7 +13,7 @@ use std::collections::HashMap;
 // NOTE: This list must be kept synchronized with TokenClass enum in protector.rs
 // When adding new token types, update both the enum and this regex pattern
 static PROTECTED_TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"⟦MT:(PRINTF|DOTNET|NAMED|SHELL|FACTORIO|FLINK|ICU|TAG|BBCODE|RWCOLOR|MCCOLOR|RICHTEXT|FCOLOR|DBLBRACK|MUSTACHE|MATHEXPR|RANGE|PERCENT|SCIENTIFIC|UNIT|ESCBRACE|ESCPCT|ENTITY|ESCAPE|ATTR|KEY|PIPE|IDPATH):(\d+)⟧")
+    Regex::new(r"⟦MT:(PRINTF|DOTNET|NAMED|SHELL|FACTORIO|FLINK|FPLURAL|ICU|TAG|BBCODE|RWCOLOR|MCCOLOR|MCSEL|PDXCOLOR|PDXLOC|PDXVAR|PDXICON|RPYVAR|RPYTAG|RICHTEXT|FCOLOR|DBLBRACK|MUSTACHE|CPTOKEN|MATHEXPR|RANGE|PERCENT|SCIENTIFIC|UNIT|ESCBRACE|ESCPCT|ENTITY|ESCAPE|PROFILE|ATTR|KEY|PIPE|IDPATH):(\d+)⟧")
         .expect("valid protected token regex")
 });
 
@@ -66,6 +68,8 @@ pub enum ValidationErrorCode {
     PropertiesEscapeInvalid,
     /// Lua string literal unbalanced
     LuaStringUnbalanced,
+    /// Game profile fixed pattern dropped or forbidden substitution made
+    ProfileRuleViolation,
 }
 
 /// Auto-recovery step types
@@ -89,6 +93,7 @@ pub enum RecoveryStep {
 /// Auto-recovery result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct AutofixResult {
     pub applied: bool,
     pub steps: Vec<RecoveryStep>,
@@ -127,8 +132,7 @@ impl RetryInfo {
         }
     }
 
-    #[allow(dead_code)]
-    fn attempted(success: bool) -> Self {
+    pub fn attempted(success: bool) -> Self {
         Self {
             attempted: true,
             success: Some(success),
@@ -158,6 +162,7 @@ impl Default for UiHint {
 /// Comprehensive failure report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ValidationFailureReport {
     pub code: ValidationErrorCode,
     pub file: String,
@@ -172,6 +177,9 @@ pub struct ValidationFailureReport {
     pub source_line: String,
     pub preprocessed_source: String,
     pub candidate_line: String,
+    /// Classes of the tokens protected in the source, e.g. `TAG`, `PRINTF`
+    #[serde(default)]
+    pub token_types: Vec<String>,
     pub autofix: AutofixResult,
     pub retry: RetryInfo,
     pub ui_hint: UiHint,
@@ -180,6 +188,7 @@ pub struct ValidationFailureReport {
 /// Successful validation result, including optional recovery metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ValidationSuccess {
     pub value: String,
     pub autofix: AutofixResult,
@@ -560,6 +569,28 @@ pub enum FileFormat {
     Unknown,
 }
 
+impl From<crate::formats::FileFormat> for FileFormat {
+    /// Maps a detected file format to the parser check it can use. Formats
+    /// without a line-level parser check map to `Unknown`.
+    fn from(format: crate::formats::FileFormat) -> Self {
+        use crate::formats::FileFormat as Detected;
+        match format {
+            Detected::Xml | Detected::Resx => FileFormat::Xml,
+            Detected::Json | Detected::BetterQuesting => FileFormat::Json,
+            Detected::Yaml => FileFormat::Yaml,
+            Detected::Po => FileFormat::Po,
+            Detected::Ini => FileFormat::Ini,
+            Detected::Cfg => FileFormat::Cfg,
+            Detected::Csv => FileFormat::Csv,
+            Detected::Properties => FileFormat::Properties,
+            Detected::Lua => FileFormat::Lua,
+            Detected::Txt => FileFormat::Txt,
+            Detected::Markdown => FileFormat::Markdown,
+            _ => FileFormat::Unknown,
+        }
+    }
+}
+
 impl Segment {
     pub fn new(
         file: String,
@@ -613,9 +644,11 @@ impl Default for ValidationMode {
     }
 }
 
-/// Validator configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Validator configuration. Fields left out when deserializing keep their
+/// defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+#[non_exhaustive]
 pub struct ValidatorConfig {
     pub enable_autofix: bool,
     pub retry_on_fail: bool,
@@ -641,23 +674,168 @@ impl Default for ValidatorConfig {
 /// Main validator
 pub struct PlaceholderValidator {
     config: ValidatorConfig,
+    fixed_patterns: Vec<Regex>,
+    forbidden_substitutions: Vec<TokenSubstitution>,
+    marker_scheme: MarkerScheme,
 }
 
 impl PlaceholderValidator {
     pub fn new(config: ValidatorConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            fixed_patterns: Vec::new(),
+            forbidden_substitutions: Vec::new(),
+            marker_scheme: MarkerScheme::default(),
+        }
     }
 
     pub fn with_default_config() -> Self {
         Self::new(ValidatorConfig::default())
     }
 
-    /// Validate translated text against segment expectations
+    /// Also enforce a game profile's rules: text matching a fixed pattern
+    /// must survive verbatim, and forbidden substitutions must not be made.
+    pub fn with_profile(mut self, profile: &ValidatorProfileConfig) -> Self {
+        self.fixed_patterns = profile
+            .force_fixed_patterns
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect();
+        self.forbidden_substitutions = profile.forbidden_substitutions.clone();
+        self
+    }
+
+    /// Read markers in the scheme the segment was protected with
+    pub fn with_token_map(mut self, map: &TokenMap) -> Self {
+        self.marker_scheme = map.scheme;
+        self
+    }
+
+    /// Validate translated text against segment expectations. Markers of a
+    /// non-default scheme are checked in the default form and the returned
+    /// value is converted back.
     pub fn validate(
         &self,
         segment: &Segment,
         translated: &str,
     ) -> Result<ValidationSuccess, ValidationFailureReport> {
+        let scheme = self.marker_scheme;
+        if scheme == MarkerScheme::default() {
+            return self
+                .validate_canonical(segment, translated)
+                .map_err(|report| *report);
+        }
+        let source_preprocessed = scheme.to_canonical(&segment.source_preprocessed).into_owned();
+        let canonical_segment = Segment {
+            source_raw: scheme.to_canonical(&segment.source_raw).into_owned(),
+            expected: PlaceholderSet::from_text(&source_preprocessed),
+            source_preprocessed,
+            ..segment.clone()
+        };
+        let mut success = self
+            .validate_canonical(&canonical_segment, &scheme.to_canonical(translated))
+            .map_err(|report| *report)?;
+        success.value = scheme.from_canonical(&success.value).into_owned();
+        Ok(success)
+    }
+
+    fn validate_canonical(
+        &self,
+        segment: &Segment,
+        translated: &str,
+    ) -> Result<ValidationSuccess, Box<ValidationFailureReport>> {
+        let success = self.validate_placeholders(segment, translated)?;
+        if let Some(code) = self.check_format_regression(segment, &success.value) {
+            let found_set = PlaceholderSet::from_text(&success.value);
+            let mut report = self.create_failure_report(
+                segment,
+                &success.value,
+                &found_set,
+                success.autofix,
+                RetryInfo::not_attempted(),
+                Vec::new(),
+                Vec::new(),
+            );
+            report.code = code;
+            return Err(Box::new(report));
+        }
+        match self.check_profile_rules(&segment.source_raw, &success.value) {
+            None => Ok(success),
+            Some((expected, found)) => {
+                let found_set = PlaceholderSet::from_text(&success.value);
+                let mut report = self.create_failure_report(
+                    segment,
+                    &success.value,
+                    &found_set,
+                    success.autofix,
+                    RetryInfo::not_attempted(),
+                    Vec::new(),
+                    Vec::new(),
+                );
+                report.code = ValidationErrorCode::ProfileRuleViolation;
+                report.expected_protected = expected;
+                report.found_protected = found;
+                Err(Box::new(report))
+            }
+        }
+    }
+
+    /// Runs the parser check of the segment's format on the translation.
+    /// Segments are single lines that often do not parse on their own, so
+    /// only a translation breaking a source that parses is reported.
+    fn check_format_regression(
+        &self,
+        segment: &Segment,
+        translated: &str,
+    ) -> Option<ValidationErrorCode> {
+        let format = segment.format?;
+        self.validate_format_after_restore(&segment.source_raw, format)
+            .ok()?;
+        self.validate_format_after_restore(translated, format).err()
+    }
+
+    /// Returns the offending source and translated values when the
+    /// translation breaks a profile rule
+    fn check_profile_rules(
+        &self,
+        source: &str,
+        translated: &str,
+    ) -> Option<(Vec<String>, Vec<String>)> {
+        let substitution = self.forbidden_substitutions.iter().find(|rule| {
+            source.contains(&rule.from)
+                && !source.contains(&rule.to)
+                && !translated.contains(&rule.from)
+                && translated.contains(&rule.to)
+        });
+        if let Some(rule) = substitution {
+            return Some((vec![rule.from.clone()], vec![rule.to.clone()]));
+        }
+
+        for pattern in &self.fixed_patterns {
+            let mut remaining: Vec<&str> =
+                pattern.find_iter(translated).map(|m| m.as_str()).collect();
+            let mut missing = Vec::new();
+            for found in pattern.find_iter(source) {
+                match remaining.iter().position(|value| *value == found.as_str()) {
+                    Some(index) => {
+                        remaining.swap_remove(index);
+                    }
+                    None => missing.push(found.as_str().to_string()),
+                }
+            }
+            if !missing.is_empty() {
+                let found = remaining.into_iter().map(str::to_string).collect();
+                return Some((missing, found));
+            }
+        }
+        None
+    }
+
+    fn validate_placeholders(
+        &self,
+        segment: &Segment,
+        translated: &str,
+    ) -> Result<ValidationSuccess, Box<ValidationFailureReport>> {
         let mut translated_candidate = translated.to_string();
         let mut combined_steps: Vec<RecoveryStep> = Vec::new();
         let mut recovered_with_warning = false;
@@ -692,7 +870,7 @@ impl PlaceholderValidator {
                                 let (_, translated_for_validation) =
                                     self.prepare_for_validation(segment, translated);
                                 let found = PlaceholderSet::from_text(&translated_for_validation);
-                                return Err(self.create_failure_report(
+                                return Err(Box::new(self.create_failure_report(
                                     segment,
                                     translated,
                                     &found,
@@ -700,14 +878,14 @@ impl PlaceholderValidator {
                                     RetryInfo::not_attempted(),
                                     structure_expected_signature,
                                     structure_found_signature,
-                                ));
+                                )));
                             }
                         }
                         None => {
                             let (_, translated_for_validation) =
                                 self.prepare_for_validation(segment, translated);
                             let found = PlaceholderSet::from_text(&translated_for_validation);
-                            return Err(self.create_failure_report(
+                            return Err(Box::new(self.create_failure_report(
                                 segment,
                                 translated,
                                 &found,
@@ -715,14 +893,14 @@ impl PlaceholderValidator {
                                 RetryInfo::not_attempted(),
                                 structure_expected_signature,
                                 structure_found_signature,
-                            ));
+                            )));
                         }
                     }
                 } else {
                     let (_, translated_for_validation) =
                         self.prepare_for_validation(segment, translated);
                     let found = PlaceholderSet::from_text(&translated_for_validation);
-                    return Err(self.create_failure_report(
+                    return Err(Box::new(self.create_failure_report(
                         segment,
                         translated,
                         &found,
@@ -730,7 +908,7 @@ impl PlaceholderValidator {
                         RetryInfo::not_attempted(),
                         structure_expected_signature,
                         structure_found_signature,
-                    ));
+                    )));
                 }
             }
         }
@@ -812,7 +990,7 @@ impl PlaceholderValidator {
             AutofixResult::with_steps(combined_steps.clone())
         };
 
-        Err(self.create_failure_report(
+        Err(Box::new(self.create_failure_report(
             segment,
             &translated_candidate,
             &found,
@@ -820,7 +998,7 @@ impl PlaceholderValidator {
             RetryInfo::not_attempted(),
             structure_expected_signature,
             structure_found_signature,
-        ))
+        )))
     }
     fn prepare_for_validation(&self, segment: &Segment, translated: &str) -> (String, String) {
         if matches!(
@@ -1238,6 +1416,7 @@ impl PlaceholderValidator {
             source_line: segment.source_raw.clone(),
             preprocessed_source: segment.source_preprocessed.clone(),
             candidate_line: candidate.to_string(),
+            token_types: segment.token_types.clone(),
             autofix,
             retry,
             ui_hint: UiHint::default(),
@@ -1521,4 +1700,115 @@ mod tests {
         assert!(!stripped.contains("\\frac{a}{b}"));
         assert!(stripped.contains("{0}"), "Should preserve format token");
     }
+
+    #[test]
+    fn test_profile_rules_reject_dropped_and_substituted_tokens() {
+        let profile = crate::profiles::minecraft::MinecraftProfile::profile();
+        let config = ValidatorConfig {
+            validation_mode: ValidationMode::Strict,
+            ..ValidatorConfig::default()
+        };
+        let validator =
+            PlaceholderValidator::new(config.clone()).with_profile(&profile.validator_config);
+        let segment = Segment::new(
+            "en_us.json".to_string(),
+            1,
+            "item.gem".to_string(),
+            "§aShiny %s".to_string(),
+            "§aShiny %s".to_string(),
+        );
+
+        assert!(validator.validate(&segment, "§a빛나는 %s").is_ok());
+
+        let dropped = validator.validate(&segment, "빛나는 %s").unwrap_err();
+        assert_eq!(dropped.code, ValidationErrorCode::ProfileRuleViolation);
+        assert_eq!(dropped.expected_protected, vec!["§a"]);
+
+        assert_eq!(
+            validator.check_profile_rules("§aShiny %s", "§a빛나는 {0}"),
+            Some((vec!["%s".to_string()], vec!["{0}".to_string()]))
+        );
+
+        // Without the profile, only placeholder markers are checked
+        let plain = PlaceholderValidator::new(config);
+        assert!(plain.validate(&segment, "빛나는 %s").is_ok());
+    }
+
+    #[test]
+    fn test_validates_markers_in_the_token_map_scheme() {
+        let fragment = crate::protector::Protector::protect_with_scheme(
+            "Hello {0} and %s",
+            crate::protector::ProtectionMode::Full,
+            &[],
+            MarkerScheme::DoubleAngle,
+        );
+        let masked = fragment.masked_text().to_string();
+        assert_eq!(masked, "Hello ⟪MT:DOTNET:0⟫ and ⟪MT:PRINTF:1⟫");
+        let segment = Segment::new(
+            "test.xml".to_string(),
+            1,
+            "key".to_string(),
+            masked.clone(),
+            masked,
+        );
+        let validator =
+            PlaceholderValidator::with_default_config().with_token_map(fragment.token_map());
+
+        let translated = "안녕 ⟪MT:DOTNET:0⟫ 그리고 ⟪MT:PRINTF:1⟫";
+        let success = validator.validate(&segment, translated).unwrap();
+        assert_eq!(success.value, translated);
+
+        let recovered = validator
+            .validate(&segment, "안녕 ⟪MT:DOTNET:0⟫ 그리고")
+            .unwrap();
+        assert!(recovered.recovered_with_warning);
+        assert!(recovered.value.contains("⟪MT:PRINTF:1⟫"));
+        assert!(!recovered.value.contains('⟦'));
+
+        // The default scheme does not read these markers
+        let plain = PlaceholderValidator::with_default_config();
+        assert!(plain.validate(&segment, "안녕 ⟪MT:DOTNET:0⟫ 그리고").is_ok());
+    }
+
+    #[test]
+    fn test_format_check_reports_only_regressions() {
+        let segment = |source: &str, format: FileFormat| {
+            Segment::new(
+                "lang/en.lua".to_string(),
+                1,
+                "key".to_string(),
+                source.to_string(),
+                source.to_string(),
+            )
+            .with_format(format)
+            .with_token_types(vec!["PRINTF".to_string()])
+        };
+        let validator = PlaceholderValidator::with_default_config();
+
+        let lua = segment(r#"greeting = "Hello %s","#, FileFormat::Lua);
+        assert!(validator.validate(&lua, r#"greeting = "안녕 %s","#).is_ok());
+        let broken = validator
+            .validate(&lua, r#"greeting = "안녕 %s,"#)
+            .unwrap_err();
+        assert_eq!(broken.code, ValidationErrorCode::LuaStringUnbalanced);
+        assert_eq!(broken.token_types, vec!["PRINTF".to_string()]);
+
+        // A JSON line does not parse on its own, so it is not checked
+        let json = segment(r#""greeting": "Hello %s","#, FileFormat::Json);
+        assert!(validator.validate(&json, r#""greeting": "안녕 %s,"#).is_ok());
+        assert_eq!(
+            FileFormat::from(crate::formats::FileFormat::Resx),
+            FileFormat::Xml
+        );
+    }
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let config: ValidatorConfig =
+            serde_json::from_str(r#"{"validationMode": "strict", "retryLimit": 3}"#).unwrap();
+        assert_eq!(config.validation_mode, ValidationMode::Strict);
+        assert_eq!(config.retry_limit, 3);
+        assert!(config.enable_autofix);
+        assert!(config.preserve_percent_binding);
+    }
 }
@@ -1,6 +1,10 @@
 /// Factorio game profile
-use super::{DetectionRules, GameProfile, ValidatorProfileConfig, FormatRule, TokenSubstitution};
-use std::path::Path;
+use super::layout::{replace_folder_after, split_language_tag, OutputLayout, SuffixLayout};
+use super::{
+    DetectionRules, FormatRule, GameProfile, PromptProfileConfig, QcRules, SkipList,
+    TokenSubstitution, ValidatorProfileConfig,
+};
+use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
 
 pub struct FactorioProfile;
@@ -27,6 +31,7 @@ impl FactorioProfile {
         let mut allowed_token_types = HashSet::new();
         allowed_token_types.insert("FACTORIO".to_string());    // __1__, __ENTITY__*
         allowed_token_types.insert("FLINK".to_string());       // [img=item/plate]
+        allowed_token_types.insert("FPLURAL".to_string());     // __plural_for_parameter__1__{...}__
         allowed_token_types.insert("FCOLOR".to_string());      // [color=red]
         allowed_token_types.insert("BBCODE".to_string());      // [b], [i]
         allowed_token_types.insert("PRINTF".to_string());      // %s
@@ -97,6 +102,36 @@ impl FactorioProfile {
             ],
             terminology: HashMap::new(),
             validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
         }
     }
 }
+
+impl OutputLayout for FactorioProfile {
+    /// `locale/en/items.cfg` -> `locale/ko/items.cfg`
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf {
+        replace_folder_after(relative_path, "locale", &locale_code(target_lang))
+            .unwrap_or_else(|| SuffixLayout.output_path(relative_path, target_lang))
+    }
+}
+
+/// Factorio locale folder for a target language: `ko`, `zh-CN`, `pt-BR`
+fn locale_code(target_lang: &str) -> String {
+    let (language, region) = split_language_tag(target_lang);
+    let region = region.or_else(|| {
+        let default = match language.as_str() {
+            "zh" => "cn",
+            "pt" => "br",
+            "es" => "es",
+            _ => return None,
+        };
+        Some(default.to_string())
+    });
+    match region {
+        Some(region) => format!("{}-{}", language, region.to_uppercase()),
+        None => language,
+    }
+}
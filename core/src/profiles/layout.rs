@@ -0,0 +1,178 @@
+/// Where translated files are written, per game.
+///
+/// Games only load translations from their own locations: RimWorld reads
+/// `Languages/<Language>/`, Minecraft reads `assets/<ns>/lang/<locale>.json`
+/// and Factorio reads `locale/<code>/`. Profiles for those games implement
+/// [`OutputLayout`]; everything else gets [`SuffixLayout`], which writes
+/// `stem.<lang>.ext` next to the source.
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Maps a source file to the path its translation is written to
+pub trait OutputLayout {
+    /// Output path for `relative_path` (`/`-separated, relative to the mod
+    /// root) translated into `target_lang`
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf;
+}
+
+/// Generic layout: `dir/stem.<lang>.ext` next to the source file
+pub struct SuffixLayout;
+
+impl OutputLayout for SuffixLayout {
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf {
+        let path = Path::new(relative_path);
+        let mut result = PathBuf::new();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                result.push(parent);
+            }
+        }
+
+        let output_name = path
+            .file_name()
+            .map(|name| suffixed_filename(name, target_lang))
+            .unwrap_or_else(|| {
+                let lang = sanitized_language_tag(target_lang);
+                format!("translated.{lang}")
+            });
+
+        result.push(output_name);
+        result
+    }
+}
+
+fn suffixed_filename(source_name: &OsStr, target_lang: &str) -> String {
+    let lang = sanitized_language_tag(target_lang);
+    let temp = PathBuf::from(source_name);
+    let stem = temp.file_stem().and_then(|s| s.to_str());
+    let extension = temp.extension().and_then(|s| s.to_str());
+
+    match (stem, extension) {
+        (Some(stem), Some(ext)) => format!("{stem}.{lang}.{ext}"),
+        (Some(stem), None) => format!("{stem}.{lang}"),
+        (None, Some(ext)) => format!("translated.{lang}.{ext}"),
+        (None, None) => format!("translated.{lang}"),
+    }
+}
+
+/// Lowercased target language, or `translated` when none is set
+pub fn sanitized_language_tag(target_lang: &str) -> String {
+    let trimmed = target_lang.trim();
+    if trimmed.is_empty() {
+        "translated".to_string()
+    } else {
+        trimmed.to_lowercase()
+    }
+}
+
+/// Splits a language tag like `pt-BR` or `zh_cn` into lowercase language and
+/// optional region
+pub fn split_language_tag(target_lang: &str) -> (String, Option<String>) {
+    let normalized = target_lang.trim().to_lowercase().replace('_', "-");
+    match normalized.split_once('-') {
+        Some((language, region)) if !region.is_empty() => {
+            (language.to_string(), Some(region.to_string()))
+        }
+        _ => (normalized.trim_end_matches('-').to_string(), None),
+    }
+}
+
+/// Replaces the folder right after `anchor` (matched case-insensitively)
+/// with `folder`. `None` when the path has no such folder with a file below.
+pub fn replace_folder_after(relative_path: &str, anchor: &str, folder: &str) -> Option<PathBuf> {
+    let mut segments: Vec<&str> = relative_path.split('/').collect();
+    let index = segments
+        .iter()
+        .position(|segment| segment.eq_ignore_ascii_case(anchor))?;
+    if index + 2 >= segments.len() {
+        return None;
+    }
+    segments[index + 1] = folder;
+    Some(PathBuf::from(segments.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffix_layout_inserts_the_language_before_the_extension() {
+        let layout = SuffixLayout;
+        assert_eq!(
+            layout.output_path("lang/en.properties", "KO"),
+            Path::new("lang/en.ko.properties")
+        );
+        assert_eq!(layout.output_path("README", "ko"), Path::new("README.ko"));
+        assert_eq!(
+            layout.output_path("a/b.tar.gz", "ja"),
+            Path::new("a/b.tar.ja.gz")
+        );
+        assert_eq!(
+            layout.output_path("strings.txt", " "),
+            Path::new("strings.translated.txt")
+        );
+    }
+
+    #[test]
+    fn splits_language_tags() {
+        assert_eq!(split_language_tag("ko"), ("ko".to_string(), None));
+        assert_eq!(
+            split_language_tag("pt_BR"),
+            ("pt".to_string(), Some("br".to_string()))
+        );
+        assert_eq!(
+            replace_folder_after("1.5/Languages/English/Keyed/A.xml", "languages", "Korean"),
+            Some(PathBuf::from("1.5/Languages/Korean/Keyed/A.xml"))
+        );
+        assert_eq!(
+            replace_folder_after("Languages/A.xml", "Languages", "Korean"),
+            None
+        );
+    }
+
+    #[test]
+    fn game_layouts_write_where_the_game_loads_translations() {
+        use super::super::factorio::FactorioProfile;
+        use super::super::minecraft::MinecraftProfile;
+        use super::super::rimworld::RimWorldProfile;
+
+        assert_eq!(
+            RimWorldProfile.output_path("1.5/Languages/English/Keyed/Misc.xml", "ko"),
+            Path::new("1.5/Languages/Korean/Keyed/Misc.xml")
+        );
+        assert_eq!(
+            RimWorldProfile.output_path("Languages/English/DefInjected/A.xml", "zh-TW"),
+            Path::new("Languages/ChineseTraditional/DefInjected/A.xml")
+        );
+        assert_eq!(
+            RimWorldProfile.output_path("Defs/Things.xml", "ko"),
+            Path::new("Defs/Things.ko.xml")
+        );
+        assert_eq!(
+            RimWorldProfile.output_path("Languages/English/Keyed/A.xml", "eo"),
+            Path::new("Languages/English/Keyed/A.eo.xml")
+        );
+
+        assert_eq!(
+            MinecraftProfile.output_path("assets/mymod/lang/en_us.json", "ko"),
+            Path::new("assets/mymod/lang/ko_kr.json")
+        );
+        assert_eq!(
+            MinecraftProfile.output_path("assets/mymod/lang/en_US.lang", "pt-BR"),
+            Path::new("assets/mymod/lang/pt_BR.lang")
+        );
+        assert_eq!(
+            MinecraftProfile.output_path("config/ftbquests/quests/chapter.snbt", "ko"),
+            Path::new("config/ftbquests/quests/chapter.ko.snbt")
+        );
+
+        assert_eq!(
+            FactorioProfile.output_path("locale/en/items.cfg", "ko"),
+            Path::new("locale/ko/items.cfg")
+        );
+        assert_eq!(
+            FactorioProfile.output_path("locale/en/items.cfg", "zh"),
+            Path::new("locale/zh-CN/items.cfg")
+        );
+    }
+}
@@ -1,6 +1,10 @@
 /// Minecraft game profile
-use super::{DetectionRules, GameProfile, ValidatorProfileConfig, FormatRule, TokenSubstitution};
-use std::path::Path;
+use super::layout::{split_language_tag, OutputLayout, SuffixLayout};
+use super::{
+    DetectionRules, FormatRule, GameProfile, PromptProfileConfig, QcRules, SkipList,
+    TokenSubstitution, ValidatorProfileConfig,
+};
+use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
 
 pub struct MinecraftProfile;
@@ -19,6 +23,17 @@ impl MinecraftProfile {
             return true;
         }
         
+        // Check for pack.mcmeta (datapacks and resource packs)
+        if mod_path.join("pack.mcmeta").exists() {
+            return true;
+        }
+        
+        // Check for quest books in a modpack (FTB Quests, BetterQuesting)
+        let config = mod_path.join("config");
+        if config.join("ftbquests").is_dir() || config.join("betterquesting").is_dir() {
+            return true;
+        }
+        
         // Check for assets/ directory
         let assets = mod_path.join("assets");
         if assets.exists() && assets.is_dir() {
@@ -34,6 +49,7 @@ impl MinecraftProfile {
         allowed_token_types.insert("PRINTF".to_string());      // %s, %d, %1$s
         allowed_token_types.insert("NAMED".to_string());       // {name}
         allowed_token_types.insert("MCCOLOR".to_string());     // §a, §l
+        allowed_token_types.insert("MCSEL".to_string());       // @p, @a[tag=x]
         allowed_token_types.insert("ENTITY".to_string());      // &nbsp;
         allowed_token_types.insert("PERCENT".to_string());     // percentages
         allowed_token_types.insert("UNIT".to_string());        // units with numbers
@@ -87,13 +103,24 @@ impl MinecraftProfile {
             id: "minecraft".to_string(),
             name: "Minecraft".to_string(),
             detector: DetectionRules {
-                folder_patterns: vec!["assets/".to_string(), "lang/".to_string()],
-                file_patterns: vec!["mcmod.info".to_string(), "fabric.mod.json".to_string()],
+                folder_patterns: vec![
+                    "assets/".to_string(),
+                    "lang/".to_string(),
+                    "config/ftbquests/".to_string(),
+                    "config/betterquesting/".to_string(),
+                ],
+                file_patterns: vec![
+                    "mcmod.info".to_string(),
+                    "fabric.mod.json".to_string(),
+                    "DefaultQuests.json".to_string(),
+                ],
                 manifest_signatures: vec!["\"modid\"".to_string()],
             },
             include_paths: vec![
                 "assets/".to_string(),
                 "lang/".to_string(),
+                "config/ftbquests/quests/".to_string(),
+                "config/betterquesting/".to_string(),
             ],
             exclude_paths: vec![
                 "textures/".to_string(),
@@ -106,6 +133,71 @@ impl MinecraftProfile {
             ],
             terminology: HashMap::new(),
             validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
+        }
+    }
+}
+
+impl OutputLayout for MinecraftProfile {
+    /// `assets/<ns>/lang/en_us.json` -> `assets/<ns>/lang/ko_kr.json`.
+    /// Files that are not locale files stay next to the source.
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf {
+        let path = Path::new(relative_path);
+        let in_lang_folder = path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .is_some_and(|name| name.eq_ignore_ascii_case("lang"));
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let extension = path.extension().and_then(|s| s.to_str()).unwrap_or_default();
+        let is_locale_file = in_lang_folder
+            && is_locale_stem(stem)
+            && (extension.eq_ignore_ascii_case("json") || extension.eq_ignore_ascii_case("lang"));
+        if !is_locale_file {
+            return SuffixLayout.output_path(relative_path, target_lang);
         }
+
+        let mut locale = locale_code(target_lang);
+        // Pre-1.13 `.lang` files are named like `en_US.lang`
+        if stem.chars().any(|c| c.is_ascii_uppercase()) {
+            if let Some((language, region)) = locale.split_once('_') {
+                locale = format!("{}_{}", language, region.to_uppercase());
+            }
+        }
+        path.with_file_name(format!("{}.{}", locale, extension))
+    }
+}
+
+/// `en_us`, `en_US`, `zh_cn`: a locale code, not an arbitrary file name
+fn is_locale_stem(stem: &str) -> bool {
+    match stem.split_once('_') {
+        Some((language, region)) => {
+            (2..=3).contains(&language.len())
+                && (2..=3).contains(&region.len())
+                && language.chars().all(|c| c.is_ascii_alphabetic())
+                && region.chars().all(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    }
+}
+
+/// Minecraft locale code for a target language, e.g. `ko_kr`
+fn locale_code(target_lang: &str) -> String {
+    let (language, region) = split_language_tag(target_lang);
+    if let Some(region) = region {
+        return format!("{}_{}", language, region);
     }
+    let region = match language.as_str() {
+        "ko" => "kr",
+        "ja" => "jp",
+        "zh" => "cn",
+        "en" => "us",
+        "pt" => "br",
+        "uk" => "ua",
+        "cs" => "cz",
+        other => other,
+    };
+    format!("{}_{}", language, region)
 }
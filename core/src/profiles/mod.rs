@@ -3,28 +3,100 @@ pub mod rimworld;
 pub mod factorio;
 pub mod stardew;
 pub mod minecraft;
+pub mod paradox;
+pub mod renpy;
+pub mod layout;
+pub mod overrides;
+pub mod user;
 
+use crate::ai::prompt::PromptTemplate;
+use crate::quality::QcRules;
+use crate::skip_list::SkipList;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Score given when a profile's own detector accepts the mod
+const DETECTOR_SCORE: f32 = 0.4;
+/// Score added per matching folder, file or manifest signature
+const RULE_SCORE: f32 = 0.2;
+/// Manifests larger than this are not searched for signatures
+const MAX_MANIFEST_BYTES: u64 = 256 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct GameProfile {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub detector: DetectionRules,
+    #[serde(default)]
     pub include_paths: Vec<String>,
+    #[serde(default)]
     pub exclude_paths: Vec<String>,
+    #[serde(default)]
     pub extra_placeholders: Vec<String>,
+    #[serde(default)]
     pub terminology: HashMap<String, String>,
     
     /// Validator configuration (Section 9)
     #[serde(default)]
     pub validator_config: ValidatorProfileConfig,
+
+    /// Prompt overrides for this game's mods
+    #[serde(default)]
+    pub prompt_config: PromptProfileConfig,
+
+    /// Line width and punctuation QC rules for this game's UI strings
+    #[serde(default)]
+    pub qc_rules: QcRules,
+
+    /// Entry keys and patterns this game's mods never translate
+    #[serde(default)]
+    pub skip_list: SkipList,
+
+    /// Rhai script with `before_send`/`after_receive` hooks, relative to the
+    /// user profile directory (see `script_hooks`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+/// Prompt overrides of a game profile, applied over the translator config
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptProfileConfig {
+    /// System prompt template
+    #[serde(default)]
+    pub system: Option<String>,
+
+    /// User prompt template
+    #[serde(default)]
+    pub user: Option<String>,
+
+    /// Value of `{{game_context}}`, e.g. the game's setting and style
+    #[serde(default)]
+    pub game_context: Option<String>,
+
+    /// Value of `{{tone}}`, replacing the configured tone
+    #[serde(default)]
+    pub tone: Option<String>,
+}
+
+impl PromptProfileConfig {
+    pub fn template(&self) -> PromptTemplate {
+        PromptTemplate {
+            system: self.system.clone(),
+            user: self.user.clone(),
+        }
+    }
 }
 
 /// Validator-specific profile configuration (Section 9)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ValidatorProfileConfig {
     /// Allowed token types for this game
     #[serde(default)]
@@ -65,39 +137,196 @@ pub struct FormatRule {
     pub description: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct DetectionRules {
     /// Folder patterns to match
+    #[serde(default)]
     pub folder_patterns: Vec<String>,
     
     /// File patterns to match
+    #[serde(default)]
     pub file_patterns: Vec<String>,
     
     /// Manifest signatures
+    #[serde(default)]
     pub manifest_signatures: Vec<String>,
 }
 
-impl GameProfile {
-    /// Auto-detect game from mod path
-    pub fn detect(mod_path: &std::path::Path) -> Option<GameProfile> {
-        // Try each built-in profile
-        if rimworld::RimWorldProfile::detect(mod_path) {
-            return Some(rimworld::RimWorldProfile::profile());
+impl DetectionRules {
+    /// Rules that match the mod: folders and files (at the root or inside
+    /// a matching folder), and manifest signatures found in matching files
+    pub fn signals(&self, mod_path: &Path) -> Vec<String> {
+        let mut signals = Vec::new();
+        let mut search_dirs = vec![mod_path.to_path_buf()];
+        for folder in &self.folder_patterns {
+            let dir = mod_path.join(folder.trim_end_matches('/'));
+            if dir.is_dir() {
+                signals.push(format!("folder {}", folder));
+                search_dirs.push(dir);
+            }
         }
-        
-        if factorio::FactorioProfile::detect(mod_path) {
-            return Some(factorio::FactorioProfile::profile());
+
+        let mut manifests = Vec::new();
+        for pattern in &self.file_patterns {
+            let found = search_dirs
+                .iter()
+                .find_map(|dir| find_file(dir, pattern));
+            if let Some(path) = found {
+                signals.push(format!("file {}", pattern));
+                manifests.push(path);
+            }
         }
-        
-        if minecraft::MinecraftProfile::detect(mod_path) {
-            return Some(minecraft::MinecraftProfile::profile());
+
+        let contents: Vec<String> = manifests
+            .iter()
+            .filter(|path| {
+                fs::metadata(path).is_ok_and(|meta| meta.len() <= MAX_MANIFEST_BYTES)
+            })
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect();
+        for signature in &self.manifest_signatures {
+            if contents.iter().any(|content| content.contains(signature.as_str())) {
+                signals.push(format!("manifest {}", signature));
+            }
         }
-        
-        if stardew::StardewValleyProfile::detect(mod_path) {
-            return Some(stardew::StardewValleyProfile::profile());
+        signals
+    }
+}
+
+/// First file in `dir` named `pattern`; a leading `*` matches any prefix
+fn find_file(dir: &Path, pattern: &str) -> Option<std::path::PathBuf> {
+    if let Some(suffix) = pattern.strip_prefix('*') {
+        let suffix = suffix.to_ascii_lowercase();
+        return fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                let name = path.file_name().map(|name| name.to_string_lossy().to_ascii_lowercase());
+                path.is_file() && name.is_some_and(|name| name.ends_with(&suffix))
+            });
+    }
+    let path = dir.join(pattern);
+    path.is_file().then_some(path)
+}
+
+/// A built-in profile's detector and constructor
+type ProfileDetector = (fn(&Path) -> bool, fn() -> GameProfile);
+
+/// A profile that matched a mod, with how confident the match is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileCandidate {
+    pub profile_id: String,
+    pub name: String,
+    /// 0.0 - 1.0
+    pub confidence: f32,
+    /// Whether the profile's own detector accepted the mod
+    pub detected: bool,
+    /// Evidence found, e.g. `folder locale/` or `manifest "modid"`
+    pub signals: Vec<String>,
+}
+
+impl GameProfile {
+    /// Auto-detect game from mod path: the most confident profile whose
+    /// detector accepts the mod. Ignores manual overrides; see `resolve`.
+    pub fn detect(mod_path: &Path) -> Option<GameProfile> {
+        let best = Self::detect_candidates(mod_path)
+            .into_iter()
+            .find(|candidate| candidate.detected)?;
+        Self::by_id(&best.profile_id)
+    }
+
+    /// Profile for a mod: the user's manual override if one is set,
+    /// otherwise the detected profile
+    pub fn resolve(mod_path: &Path) -> Option<GameProfile> {
+        overrides::get_profile_override(mod_path)
+            .and_then(|id| Self::by_id(&id))
+            .or_else(|| Self::detect(mod_path))
+    }
+
+    /// Every profile with any evidence for the mod, most confident first.
+    /// Ties keep the built-in detection order, then user profiles. User
+    /// profiles have no detector of their own; any matching rule detects them.
+    pub fn detect_candidates(mod_path: &Path) -> Vec<ProfileCandidate> {
+        let detectors: [ProfileDetector; 6] = [
+            (rimworld::RimWorldProfile::detect, rimworld::RimWorldProfile::profile),
+            (factorio::FactorioProfile::detect, factorio::FactorioProfile::profile),
+            (paradox::ParadoxProfile::detect, paradox::ParadoxProfile::profile),
+            (renpy::RenpyProfile::detect, renpy::RenpyProfile::profile),
+            (minecraft::MinecraftProfile::detect, minecraft::MinecraftProfile::profile),
+            (stardew::StardewValleyProfile::detect, stardew::StardewValleyProfile::profile),
+        ];
+
+        let builtin = detectors
+            .iter()
+            .map(|(detect, profile)| (profile(), Some(detect(mod_path))));
+        let user = user::user_profiles().into_iter().map(|profile| (profile, None));
+        let mut candidates: Vec<ProfileCandidate> = builtin
+            .chain(user)
+            .filter_map(|(profile, detected)| {
+                let signals = profile.detector.signals(mod_path);
+                let detected = detected.unwrap_or(!signals.is_empty());
+                if !detected && signals.is_empty() {
+                    return None;
+                }
+                let score = if detected { DETECTOR_SCORE } else { 0.0 }
+                    + RULE_SCORE * signals.len() as f32;
+                Some(ProfileCandidate {
+                    profile_id: profile.id,
+                    name: profile.name,
+                    confidence: score.min(1.0),
+                    detected,
+                    signals,
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+
+    /// Whether a file under the mod root should be translated with this
+    /// profile: not under an excluded path, and under an included path when
+    /// the profile lists any. Paths match at any folder depth, ignoring case.
+    pub fn accepts_path(&self, relative_path: &str) -> bool {
+        let path = format!("/{}", relative_path.replace('\\', "/").to_ascii_lowercase());
+        let matches = |pattern: &String| {
+            let pattern = pattern.replace('\\', "/").to_ascii_lowercase();
+            let pattern = pattern.trim_start_matches('/');
+            !pattern.is_empty() && path.contains(&format!("/{}", pattern))
+        };
+        if self.exclude_paths.iter().any(matches) {
+            return false;
         }
-        
-        None
+        self.include_paths.is_empty() || self.include_paths.iter().any(matches)
+    }
+
+    /// Compiled extra placeholders and fixed validator patterns, for the
+    /// Protector. Invalid patterns are skipped.
+    pub fn placeholder_patterns(&self) -> Vec<Regex> {
+        self.extra_placeholders
+            .iter()
+            .chain(&self.validator_config.force_fixed_patterns)
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect()
+    }
+
+    /// Where this game loads translations from. Games without a known
+    /// layout get translations written next to the source file.
+    pub fn output_layout(&self) -> Box<dyn layout::OutputLayout> {
+        match self.id.as_str() {
+            "rimworld" => Box::new(rimworld::RimWorldProfile),
+            "minecraft" => Box::new(minecraft::MinecraftProfile),
+            "factorio" => Box::new(factorio::FactorioProfile),
+            "stardew" => Box::new(stardew::StardewValleyProfile),
+            _ => Box::new(layout::SuffixLayout),
+        }
+    }
+
+    /// Built-in or user profile by id, including `generic`
+    pub fn by_id(id: &str) -> Option<GameProfile> {
+        list_profiles().into_iter().find(|profile| profile.id == id)
     }
     
     /// Get generic profile (fallback)
@@ -110,15 +339,24 @@ impl GameProfile {
         allowed_token_types.insert("SHELL".to_string());
         allowed_token_types.insert("FACTORIO".to_string());
         allowed_token_types.insert("FLINK".to_string());
+        allowed_token_types.insert("FPLURAL".to_string());
         allowed_token_types.insert("ICU".to_string());
         allowed_token_types.insert("TAG".to_string());
         allowed_token_types.insert("BBCODE".to_string());
         allowed_token_types.insert("RWCOLOR".to_string());
         allowed_token_types.insert("MCCOLOR".to_string());
+        allowed_token_types.insert("MCSEL".to_string());
+        allowed_token_types.insert("PDXCOLOR".to_string());
+        allowed_token_types.insert("PDXLOC".to_string());
+        allowed_token_types.insert("PDXVAR".to_string());
+        allowed_token_types.insert("PDXICON".to_string());
+        allowed_token_types.insert("RPYVAR".to_string());
+        allowed_token_types.insert("RPYTAG".to_string());
         allowed_token_types.insert("RICHTEXT".to_string());
         allowed_token_types.insert("FCOLOR".to_string());
         allowed_token_types.insert("DBLBRACK".to_string());
         allowed_token_types.insert("MUSTACHE".to_string());
+        allowed_token_types.insert("CPTOKEN".to_string());
         allowed_token_types.insert("MATHEXPR".to_string());
         allowed_token_types.insert("RANGE".to_string());
         allowed_token_types.insert("PERCENT".to_string());
@@ -126,6 +364,7 @@ impl GameProfile {
         allowed_token_types.insert("UNIT".to_string());
         allowed_token_types.insert("ENTITY".to_string());
         allowed_token_types.insert("ESCAPE".to_string());
+        allowed_token_types.insert("PROFILE".to_string());
         
         let validator_config = ValidatorProfileConfig {
             allowed_token_types,
@@ -152,17 +391,76 @@ impl GameProfile {
             extra_placeholders: Vec::new(),
             terminology: HashMap::new(),
             validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
         }
     }
 }
 
-/// Get all available profiles
+/// Get all available profiles: the built-in ones, then user profiles
 pub fn list_profiles() -> Vec<GameProfile> {
+    let mut profiles = builtin_profiles();
+    profiles.extend(user::user_profiles());
+    profiles
+}
+
+/// Profiles shipped with the app
+pub fn builtin_profiles() -> Vec<GameProfile> {
     vec![
         rimworld::RimWorldProfile::profile(),
         factorio::FactorioProfile::profile(),
         minecraft::MinecraftProfile::profile(),
+        paradox::ParadoxProfile::profile(),
+        renpy::RenpyProfile::profile(),
         stardew::StardewValleyProfile::profile(),
         GameProfile::generic(),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_are_scored_by_evidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("assets")).unwrap();
+        fs::create_dir_all(root.join("locale")).unwrap();
+        fs::write(root.join("mcmod.info"), r#"[{"modid": "examplemod"}]"#).unwrap();
+
+        let candidates = GameProfile::detect_candidates(root);
+        let ids: Vec<&str> = candidates.iter().map(|c| c.profile_id.as_str()).collect();
+        assert_eq!(ids, vec!["minecraft", "factorio"]);
+        assert_eq!(
+            candidates[0].signals,
+            vec!["folder assets/", "file mcmod.info", "manifest \"modid\""]
+        );
+        assert!(candidates[0].confidence > candidates[1].confidence);
+        assert_eq!(GameProfile::detect(root).unwrap().id, "minecraft");
+
+        assert!(GameProfile::detect_candidates(&root.join("missing")).is_empty());
+        assert!(GameProfile::by_id("generic").is_some());
+    }
+
+    #[test]
+    fn paths_follow_include_and_exclude_rules() {
+        let rimworld = rimworld::RimWorldProfile::profile();
+        assert!(rimworld.accepts_path("Languages/English/Keyed/Misc.xml"));
+        assert!(rimworld.accepts_path("1.5\\languages\\English\\Keyed\\Misc.xml"));
+        assert!(rimworld.accepts_path("Defs/ThingDefs/Weapons.xml"));
+        assert!(!rimworld.accepts_path("About/About.xml"));
+        assert!(!rimworld.accepts_path("Textures/Languages/readme.xml"));
+
+        let mut open = GameProfile::generic();
+        open.include_paths.clear();
+        assert!(open.accepts_path("anything.json"));
+
+        let paradox = paradox::ParadoxProfile::profile();
+        let patterns = paradox.placeholder_patterns();
+        assert!(!patterns.is_empty());
+        assert!(patterns.iter().any(|pattern| pattern.is_match("£gold£")));
+    }
+}
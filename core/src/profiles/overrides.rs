@@ -0,0 +1,188 @@
+/// Per-mod manual game profile overrides.
+///
+/// Detection can pick the wrong profile, e.g. a generic JSON pack with an
+/// `assets/` folder looks like a Minecraft mod. Users pin the right profile
+/// per mod; the choice lives in a JSON file under the local app data dir.
+use super::{GameProfile, ProfileCandidate};
+use crate::paths::app_data_dir;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const PROFILE_OVERRIDES_FILE_NAME: &str = "profile-overrides.json";
+
+/// On-disk file: mod path -> profile id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileOverridesFile {
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+}
+
+impl ProfileOverridesFile {
+    fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|error| {
+            warn!(
+                "ignoring unreadable profile overrides {}: {}",
+                path.display(),
+                error
+            );
+            Self::default()
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create profile overrides dir: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize profile overrides: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write profile overrides: {}", e))
+    }
+
+    fn get(&self, mod_path: &Path) -> Option<String> {
+        self.overrides.get(&override_key(mod_path)).cloned()
+    }
+
+    fn set(&mut self, mod_path: &Path, profile_id: Option<String>) {
+        let key = override_key(mod_path);
+        match profile_id {
+            Some(id) => self.overrides.insert(key, id),
+            None => self.overrides.remove(&key),
+        };
+    }
+}
+
+static PROFILE_OVERRIDES: Lazy<Mutex<ProfileOverridesFile>> =
+    Lazy::new(|| Mutex::new(ProfileOverridesFile::load(&get_profile_overrides_path())));
+
+/// Location of the profile overrides file.
+pub fn get_profile_overrides_path() -> PathBuf {
+    app_data_dir().join(PROFILE_OVERRIDES_FILE_NAME)
+}
+
+/// The same mod reached through different spellings shares one override
+fn override_key(mod_path: &Path) -> String {
+    fs::canonicalize(mod_path)
+        .unwrap_or_else(|_| mod_path.to_path_buf())
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Profile id the user pinned for a mod, if any
+pub fn get_profile_override(mod_path: &Path) -> Option<String> {
+    PROFILE_OVERRIDES
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(mod_path))
+}
+
+/// Pins a mod's profile, or clears the override with `None`
+pub fn set_profile_override(mod_path: &Path, profile_id: Option<String>) -> Result<(), String> {
+    let mut guard = PROFILE_OVERRIDES
+        .lock()
+        .map_err(|_| "profile overrides lock poisoned".to_string())?;
+    guard.set(mod_path, profile_id);
+    guard.save(&get_profile_overrides_path())
+}
+
+/// Detection result for one mod, as shown in the profile picker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModProfileDetection {
+    pub candidates: Vec<ProfileCandidate>,
+    pub override_profile_id: Option<String>,
+    /// Profile jobs will use: the override, else the best detected one
+    pub active_profile_id: Option<String>,
+}
+
+fn mod_profile_detection(
+    mod_path: &Path,
+    override_profile_id: Option<String>,
+) -> ModProfileDetection {
+    let candidates = GameProfile::detect_candidates(mod_path);
+    let active_profile_id = override_profile_id.clone().or_else(|| {
+        candidates
+            .iter()
+            .find(|candidate| candidate.detected)
+            .map(|candidate| candidate.profile_id.clone())
+    });
+    ModProfileDetection {
+        candidates,
+        override_profile_id,
+        active_profile_id,
+    }
+}
+
+/// Tauri command to list scored profile candidates and the override for a mod
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn detect_mod_profiles(modPath: String) -> Result<ModProfileDetection, String> {
+    let mod_path = PathBuf::from(modPath);
+    Ok(mod_profile_detection(
+        &mod_path,
+        get_profile_override(&mod_path),
+    ))
+}
+
+/// Tauri command to pin a mod's game profile; `None` returns to detection
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn set_mod_profile(
+    modPath: String,
+    profileId: Option<String>,
+) -> Result<ModProfileDetection, String> {
+    let profile_id = profileId
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if let Some(id) = &profile_id {
+        if GameProfile::by_id(id).is_none() {
+            return Err(format!("알 수 없는 게임 프로필입니다: {}", id));
+        }
+    }
+
+    let mod_path = PathBuf::from(modPath);
+    set_profile_override(&mod_path, profile_id.clone())?;
+    Ok(mod_profile_detection(&mod_path, profile_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_roundtrip_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let mod_path = dir.path().join("SomeMod");
+        fs::create_dir_all(mod_path.join("assets")).unwrap();
+        let path = dir.path().join("nested").join(PROFILE_OVERRIDES_FILE_NAME);
+
+        let mut file = ProfileOverridesFile::load(&path);
+        assert!(file.get(&mod_path).is_none());
+        file.set(&mod_path, Some("generic".to_string()));
+        file.save(&path).unwrap();
+
+        let mut reloaded = ProfileOverridesFile::load(&path);
+        assert_eq!(reloaded.get(&mod_path).as_deref(), Some("generic"));
+        // A different spelling of the same folder finds the same override
+        assert_eq!(
+            reloaded.get(&mod_path.join("assets").join("..")).as_deref(),
+            Some("generic")
+        );
+
+        let detection = mod_profile_detection(&mod_path, reloaded.get(&mod_path));
+        assert_eq!(detection.candidates[0].profile_id, "minecraft");
+        assert_eq!(detection.active_profile_id.as_deref(), Some("generic"));
+
+        reloaded.set(&mod_path, None);
+        assert!(reloaded.get(&mod_path).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+/// Paradox game profile (Crusader Kings III, Europa Universalis IV, Stellaris, Hearts of Iron IV)
+use super::{DetectionRules, FormatRule, GameProfile, ValidatorProfileConfig, PromptProfileConfig, QcRules, SkipList};
+use std::path::Path;
+use std::collections::{HashMap, HashSet};
+
+pub struct ParadoxProfile;
+
+impl ParadoxProfile {
+    pub fn detect(mod_path: &Path) -> bool {
+        // Check for descriptor.mod (launcher mod descriptor)
+        let descriptor = mod_path.join("descriptor.mod");
+        if descriptor.exists() {
+            return true;
+        }
+
+        // Check for .metadata/metadata.json (CK3/Victoria 3 launcher)
+        let metadata = mod_path.join(".metadata").join("metadata.json");
+        if metadata.exists() {
+            return true;
+        }
+
+        // Check for localisation/ directory (British spelling is Paradox-specific)
+        let localisation = mod_path.join("localisation");
+        if localisation.exists() && localisation.is_dir() {
+            return true;
+        }
+
+        false
+    }
+
+    pub fn profile() -> GameProfile {
+        // Validator configuration for Paradox localisation
+        let mut allowed_token_types = HashSet::new();
+        allowed_token_types.insert("PDXCOLOR".to_string());    // §Y, §!
+        allowed_token_types.insert("PDXLOC".to_string());      // [Root.GetName]
+        allowed_token_types.insert("PDXVAR".to_string());      // $VARIABLE$
+        allowed_token_types.insert("PDXICON".to_string());     // £energy£
+        allowed_token_types.insert("SHELL".to_string());       // $NAME$
+        allowed_token_types.insert("ESCAPE".to_string());      // \n
+        allowed_token_types.insert("PERCENT".to_string());     // percentages
+        allowed_token_types.insert("UNIT".to_string());        // units with numbers
+        allowed_token_types.insert("RANGE".to_string());       // ranges
+
+        let validator_config = ValidatorProfileConfig {
+            allowed_token_types,
+            csv_target_columns: vec![],
+            force_fixed_patterns: vec![
+                // Color codes: §Y ... §!
+                r"§[A-Za-z0-9!]".to_string(),
+                // Variables: $NAME$, $VALUE|Y$
+                r"\$[A-Za-z0-9_|.]+\$".to_string(),
+                // Scripted localisation: [Root.GetName], [GetDateText]
+                r"\[[A-Za-z0-9_.|'()]+\]".to_string(),
+                // Icons: £gold£
+                r"£[A-Za-z0-9_]+£".to_string(),
+            ],
+            forbidden_substitutions: vec![],
+            format_rules: vec![
+                FormatRule {
+                    format: "paradox".to_string(),
+                    rule_type: "language_header_first".to_string(),
+                    description: "Keep the l_<language>: header as the first line".to_string(),
+                },
+                FormatRule {
+                    format: "paradox".to_string(),
+                    rule_type: "color_block_preservation".to_string(),
+                    description: "Every §X color code must keep its closing §!".to_string(),
+                },
+            ],
+        };
+
+        GameProfile {
+            id: "paradox".to_string(),
+            name: "Paradox (CK3/EU4/Stellaris/HOI4)".to_string(),
+            detector: DetectionRules {
+                folder_patterns: vec!["localisation/".to_string()],
+                file_patterns: vec![
+                    "descriptor.mod".to_string(),
+                    "*_l_english.yml".to_string(),
+                ],
+                manifest_signatures: vec!["supported_version".to_string()],
+            },
+            include_paths: vec!["localisation/".to_string()],
+            exclude_paths: vec![
+                "common/".to_string(),
+                "events/".to_string(),
+                "gfx/".to_string(),
+                "music/".to_string(),
+                "sound/".to_string(),
+            ],
+            extra_placeholders: vec![
+                r"\$[A-Za-z0-9_|.]+\$".to_string(),
+                r"\[[A-Za-z0-9_.|'()]+\]".to_string(),
+                r"£[A-Za-z0-9_]+£".to_string(),
+            ],
+            terminology: HashMap::new(),
+            validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
+        }
+    }
+}
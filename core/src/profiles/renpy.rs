@@ -0,0 +1,90 @@
+/// Ren'Py visual novel profile
+use super::{DetectionRules, FormatRule, GameProfile, ValidatorProfileConfig, PromptProfileConfig, QcRules, SkipList};
+use std::path::Path;
+use std::collections::{HashMap, HashSet};
+
+pub struct RenpyProfile;
+
+impl RenpyProfile {
+    pub fn detect(mod_path: &Path) -> bool {
+        // Scripts live in game/ for full games, or at the root for drop-in mods
+        [mod_path.to_path_buf(), mod_path.join("game")]
+            .iter()
+            .any(|dir| contains_scripts(dir))
+    }
+
+    pub fn profile() -> GameProfile {
+        // Validator configuration for Ren'Py scripts
+        let mut allowed_token_types = HashSet::new();
+        allowed_token_types.insert("RPYVAR".to_string());     // [player_name]
+        allowed_token_types.insert("RPYTAG".to_string());     // {color=#f00}, {/b}
+        allowed_token_types.insert("NAMED".to_string());      // {b}, {w}
+        allowed_token_types.insert("PRINTF".to_string());     // %s in old-style interpolation
+        allowed_token_types.insert("ESCPCT".to_string());     // %%
+        allowed_token_types.insert("ESCAPE".to_string());     // \n
+
+        let validator_config = ValidatorProfileConfig {
+            allowed_token_types,
+            csv_target_columns: vec![],
+            force_fixed_patterns: vec![
+                // Interpolation: [player_name], [mc.name!t]
+                r"\[[A-Za-z_][A-Za-z0-9_.]*(?:![a-z]+)?(?::[^\[\]]*)?\]".to_string(),
+                // Text tags: {b}, {/b}, {color=#f00}, {w=0.5}
+                r"\{/?[a-z]+(?:=[^{}]*)?\}".to_string(),
+            ],
+            forbidden_substitutions: vec![],
+            format_rules: vec![
+                FormatRule {
+                    format: "renpy".to_string(),
+                    rule_type: "dialogue_only".to_string(),
+                    description: "Translate dialogue, menu choices and _() strings; never labels or python code".to_string(),
+                },
+                FormatRule {
+                    format: "renpy".to_string(),
+                    rule_type: "text_tag_pairing".to_string(),
+                    description: "Every {tag} must keep its closing {/tag}".to_string(),
+                },
+            ],
+        };
+
+        GameProfile {
+            id: "renpy".to_string(),
+            name: "Ren'Py".to_string(),
+            detector: DetectionRules {
+                folder_patterns: vec!["game/".to_string(), "game/tl/".to_string()],
+                file_patterns: vec!["*.rpy".to_string(), "script.rpy".to_string()],
+                manifest_signatures: vec!["config.name".to_string()],
+            },
+            include_paths: vec!["game/".to_string()],
+            exclude_paths: vec![
+                "renpy/".to_string(),
+                "lib/".to_string(),
+                "game/cache/".to_string(),
+                "game/saves/".to_string(),
+            ],
+            extra_placeholders: vec![
+                r"\[[A-Za-z_][A-Za-z0-9_.]*(?:![a-z]+)?(?::[^\[\]]*)?\]".to_string(),
+                r"\{/?[a-z]+(?:=[^{}]*)?\}".to_string(),
+            ],
+            terminology: HashMap::new(),
+            validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
+        }
+    }
+}
+
+fn contains_scripts(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rpy"))
+    })
+}
@@ -1,6 +1,10 @@
 /// RimWorld game profile
-use super::{DetectionRules, GameProfile, ValidatorProfileConfig, FormatRule, TokenSubstitution};
-use std::path::Path;
+use super::layout::{replace_folder_after, split_language_tag, OutputLayout, SuffixLayout};
+use super::{
+    DetectionRules, FormatRule, GameProfile, PromptProfileConfig, QcRules, SkipList,
+    TokenSubstitution, ValidatorProfileConfig,
+};
+use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
 
 pub struct RimWorldProfile;
@@ -95,6 +99,44 @@ impl RimWorldProfile {
             ],
             terminology,
             validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
         }
     }
 }
+
+impl OutputLayout for RimWorldProfile {
+    /// `Languages/English/Keyed/A.xml` -> `Languages/Korean/Keyed/A.xml`
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf {
+        language_folder(target_lang)
+            .and_then(|folder| replace_folder_after(relative_path, "Languages", folder))
+            .unwrap_or_else(|| SuffixLayout.output_path(relative_path, target_lang))
+    }
+}
+
+/// RimWorld's legacy language folder name for a target language
+fn language_folder(target_lang: &str) -> Option<&'static str> {
+    let (language, region) = split_language_tag(target_lang);
+    let folder = match (language.as_str(), region.as_deref()) {
+        ("ko", _) => "Korean",
+        ("ja", _) => "Japanese",
+        ("zh", Some("tw" | "hk" | "hant")) => "ChineseTraditional",
+        ("zh", _) => "ChineseSimplified",
+        ("en", _) => "English",
+        ("de", _) => "German",
+        ("fr", _) => "French",
+        ("es", _) => "Spanish",
+        ("ru", _) => "Russian",
+        ("pt", Some("br")) => "PortugueseBrazilian",
+        ("pt", _) => "Portuguese",
+        ("pl", _) => "Polish",
+        ("it", _) => "Italian",
+        ("tr", _) => "Turkish",
+        ("uk", _) => "Ukrainian",
+        ("cs", _) => "Czech",
+        _ => return None,
+    };
+    Some(folder)
+}
@@ -1,8 +1,27 @@
 /// Stardew Valley game profile
-use super::{DetectionRules, GameProfile, ValidatorProfileConfig};
-use std::path::Path;
+use super::layout::{replace_folder_after, split_language_tag, OutputLayout, SuffixLayout};
+use super::{DetectionRules, GameProfile, ValidatorProfileConfig, PromptProfileConfig, QcRules, SkipList};
+use crate::access_mode::ensure_writable;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
 
+/// Content Patcher's unique id, as listed under `ContentPackFor`
+const CONTENT_PATCHER_ID: &str = "Pathoschild.ContentPatcher";
+
+// {{i18n:key}}, {{ i18n: key |defaultValue=...}}
+static I18N_TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\{\{\s*i18n\s*:\s*([^{}|\s]+)").expect("valid i18n token regex")
+});
+
+// Keys of a flat i18n JSON object; tolerant of comments and trailing commas
+static I18N_KEY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)(?:^|[{,])\s*"((?:[^"\\]|\\.)*)"\s*:"#).expect("valid i18n key regex")
+});
+
 pub struct StardewValleyProfile;
 
 impl StardewValleyProfile {
@@ -28,6 +47,7 @@ impl StardewValleyProfile {
         allowed_token_types.insert("DOTNET".to_string());
         allowed_token_types.insert("NAMED".to_string());
         allowed_token_types.insert("ENTITY".to_string());
+        allowed_token_types.insert("CPTOKEN".to_string());
         
         let validator_config = ValidatorProfileConfig {
             allowed_token_types,
@@ -42,8 +62,11 @@ impl StardewValleyProfile {
             name: "Stardew Valley".to_string(),
             detector: DetectionRules {
                 folder_patterns: vec!["i18n/".to_string()],
-                file_patterns: vec!["manifest.json".to_string()],
-                manifest_signatures: vec!["ContentPackFor".to_string()],
+                file_patterns: vec!["manifest.json".to_string(), "content.json".to_string()],
+                manifest_signatures: vec![
+                    "ContentPackFor".to_string(),
+                    CONTENT_PATCHER_ID.to_string(),
+                ],
             },
             include_paths: vec!["i18n/".to_string()],
             exclude_paths: vec![
@@ -55,6 +78,251 @@ impl StardewValleyProfile {
             ],
             terminology: HashMap::new(),
             validator_config,
+            prompt_config: PromptProfileConfig::default(),
+            qc_rules: QcRules::default(),
+            skip_list: SkipList::default(),
+            script: None,
+        }
+    }
+}
+
+impl OutputLayout for StardewValleyProfile {
+    /// `i18n/default.json` -> `i18n/ko.json`, and the folder form
+    /// `i18n/default/items.json` -> `i18n/ko/items.json`
+    fn output_path(&self, relative_path: &str, target_lang: &str) -> PathBuf {
+        let (language, _) = split_language_tag(target_lang);
+        if language.is_empty() {
+            return SuffixLayout.output_path(relative_path, target_lang);
+        }
+        let segments: Vec<&str> = relative_path.split('/').collect();
+        let is_i18n = |segment: &&str| segment.eq_ignore_ascii_case("i18n");
+        match segments.as_slice() {
+            [.., folder, file] if is_i18n(folder) && file.eq_ignore_ascii_case("default.json") => {
+                Path::new(relative_path).with_file_name(format!("{}.json", language))
+            }
+            _ if segments
+                .windows(2)
+                .any(|pair| is_i18n(&pair[0]) && pair[1].eq_ignore_ascii_case("default")) =>
+            {
+                replace_folder_after(relative_path, "i18n", &language)
+                    .unwrap_or_else(|| SuffixLayout.output_path(relative_path, target_lang))
+            }
+            _ => SuffixLayout.output_path(relative_path, target_lang),
+        }
+    }
+}
+
+impl StardewValleyProfile {
+    /// Whether the mod is a Content Patcher pack: a `content.json` next to a
+    /// manifest that names Content Patcher
+    pub fn is_content_pack(mod_path: &Path) -> bool {
+        let for_content_patcher = fs::read_to_string(mod_path.join("manifest.json"))
+            .map(|manifest| {
+                manifest
+                    .to_ascii_lowercase()
+                    .contains(&CONTENT_PATCHER_ID.to_ascii_lowercase())
+            })
+            .unwrap_or(false);
+        for_content_patcher && mod_path.join("content.json").is_file()
+    }
+}
+
+/// Keys referenced by `{{i18n:key}}` tokens, in first-use order
+pub fn i18n_token_keys(content: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for capture in I18N_TOKEN_REGEX.captures_iter(content) {
+        let key = capture[1].to_string();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Keys defined in an i18n JSON file
+pub fn i18n_file_keys(content: &str) -> HashSet<String> {
+    I18N_KEY_REGEX
+        .captures_iter(content)
+        .map(|capture| capture[1].replace("\\\"", "\""))
+        .collect()
+}
+
+/// Appends `"key": ""` entries before the closing brace of a JSON object,
+/// keeping the existing text, comments included, untouched
+fn append_i18n_keys(content: &str, keys: &[String]) -> Option<String> {
+    let close = content.rfind('}')?;
+    let body_end = content[..close].trim_end().len();
+    let last_line = &content[content[..body_end].rfind('\n').map_or(0, |i| i + 1)..body_end];
+    let needs_comma = !content[..body_end].ends_with(['{', ',']);
+    let entries: Vec<String> = keys
+        .iter()
+        .map(|key| format!("  {}: \"\"", serde_json::to_string(key).unwrap_or_default()))
+        .collect();
+
+    let mut result = content[..body_end].to_string();
+    if needs_comma {
+        // A trailing `//` comment would swallow the comma
+        if last_line.contains("//") {
+            result.push('\n');
+        }
+        result.push(',');
+    }
+    result.push('\n');
+    result.push_str(&entries.join(",\n"));
+    result.push('\n');
+    result.push_str(&content[close..]);
+    Some(result)
+}
+
+/// Result of syncing a Content Patcher pack's default i18n file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StardewI18nReport {
+    /// `i18n/default.json`, relative to the mod root
+    pub default_file: String,
+    /// Keys the pack's JSON files reference through `{{i18n:...}}`
+    pub referenced_keys: usize,
+    /// Keys added to the default file with an empty value
+    pub added_keys: Vec<String>,
+    /// Whether the default file had to be created
+    pub created: bool,
+}
+
+fn pack_json_files(dir: &Path, root: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            if path != root.join("i18n") {
+                pack_json_files(&path, root, out);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            && path != root.join("manifest.json")
+        {
+            out.push(path);
+        }
+    }
+}
+
+/// Makes sure `i18n/default.json` defines every key the pack's JSON files
+/// reference, creating the file when needed. The translation job then writes
+/// `i18n/<lang>.json` from it.
+pub fn sync_default_i18n(mod_path: &Path) -> Result<StardewI18nReport, String> {
+    let mut sources = Vec::new();
+    pack_json_files(mod_path, mod_path, &mut sources);
+    let mut referenced: Vec<String> = Vec::new();
+    for source in sources {
+        let Ok(content) = fs::read_to_string(&source) else {
+            continue;
+        };
+        for key in i18n_token_keys(&content) {
+            if !referenced.contains(&key) {
+                referenced.push(key);
+            }
         }
     }
+
+    let default_path = mod_path.join("i18n").join("default.json");
+    let existing = match fs::read_to_string(&default_path) {
+        Ok(content) => Some(content),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => return Err(format!("i18n/default.json을 읽지 못했습니다: {}", error)),
+    };
+    let defined = existing.as_deref().map(i18n_file_keys).unwrap_or_default();
+    let added_keys: Vec<String> = referenced
+        .iter()
+        .filter(|key| !defined.contains(*key))
+        .cloned()
+        .collect();
+
+    let report = StardewI18nReport {
+        default_file: "i18n/default.json".to_string(),
+        referenced_keys: referenced.len(),
+        created: existing.is_none() && !added_keys.is_empty(),
+        added_keys,
+    };
+    if report.added_keys.is_empty() {
+        return Ok(report);
+    }
+
+    let content = existing.unwrap_or_else(|| "{\n}\n".to_string());
+    let updated = append_i18n_keys(&content, &report.added_keys)
+        .ok_or_else(|| "i18n/default.json이 JSON 객체가 아닙니다".to_string())?;
+    fs::create_dir_all(mod_path.join("i18n"))
+        .and_then(|_| fs::write(&default_path, updated))
+        .map_err(|e| format!("i18n/default.json을 저장하지 못했습니다: {}", e))?;
+    Ok(report)
+}
+
+/// Tauri command to add the i18n keys a Content Patcher pack references to
+/// its `i18n/default.json`
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn sync_stardew_i18n(modDirectory: String) -> Result<StardewI18nReport, String> {
+    ensure_writable("Stardew i18n 동기화")?;
+    let root = PathBuf::from(&modDirectory);
+    if !StardewValleyProfile::is_content_pack(&root) {
+        return Err(format!("Content Patcher 콘텐츠 팩이 아닙니다: {}", modDirectory));
+    }
+    sync_default_i18n(&root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_referenced_keys_to_the_default_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("manifest.json"),
+            r#"{ "ContentPackFor": { "UniqueID": "Pathoschild.ContentPatcher" } }"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("content.json"),
+            r#"{ "Changes": [ { "Entries": {
+                "Sword": "{{i18n:item.sword.name}}/{{i18n: item.sword.desc |name={{PlayerName}}}}",
+                "Again": "{{i18n:item.sword.name}}" } } ] }"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("i18n")).unwrap();
+        fs::write(
+            root.join("i18n/default.json"),
+            "{\n  // names\n  \"item.sword.name\": \"Sword\" // shown in shops\n}\n",
+        )
+        .unwrap();
+        assert!(StardewValleyProfile::is_content_pack(root));
+
+        let report = sync_default_i18n(root).unwrap();
+        assert_eq!(report.referenced_keys, 2);
+        assert_eq!(report.added_keys, vec!["item.sword.desc"]);
+        assert!(!report.created);
+        assert_eq!(
+            fs::read_to_string(root.join("i18n/default.json")).unwrap(),
+            "{\n  // names\n  \"item.sword.name\": \"Sword\" // shown in shops\n,\n  \"item.sword.desc\": \"\"\n}\n"
+        );
+
+        let again = sync_default_i18n(root).unwrap();
+        assert!(again.added_keys.is_empty());
+
+        assert_eq!(
+            StardewValleyProfile.output_path("i18n/default.json", "ko-KR"),
+            Path::new("i18n/ko.json")
+        );
+        assert_eq!(
+            StardewValleyProfile.output_path("i18n/default/items.json", "ko"),
+            Path::new("i18n/ko/items.json")
+        );
+        assert_eq!(
+            StardewValleyProfile.output_path("i18n/de.json", "ko"),
+            Path::new("i18n/de.ko.json")
+        );
+    }
 }
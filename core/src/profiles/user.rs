@@ -0,0 +1,244 @@
+/// User-defined game profiles.
+///
+/// Communities can support a new game without recompiling by dropping a
+/// `GameProfile` definition (detector rules, include paths, placeholders,
+/// terminology, validator config) into the user profile directory as a
+/// `.toml` or `.json` file. Files are read once at startup and again on
+/// `list_game_profiles`, so new definitions show up without a restart.
+use super::{builtin_profiles, GameProfile};
+use crate::paths::app_data_dir;
+use log::warn;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const USER_PROFILES_DIR_NAME: &str = "profiles";
+/// Profile files larger than this are skipped
+const MAX_PROFILE_BYTES: u64 = 1024 * 1024;
+
+/// A profile file that could not be used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileLoadError {
+    pub file: String,
+    pub message: String,
+}
+
+/// Every available profile, with the user profiles marked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameProfileListing {
+    pub profiles: Vec<GameProfile>,
+    pub user_profile_ids: Vec<String>,
+    pub errors: Vec<ProfileLoadError>,
+    pub user_profile_dir: String,
+}
+
+static USER_PROFILES: Lazy<Mutex<Vec<GameProfile>>> = Lazy::new(|| {
+    let (profiles, errors) = load_user_profiles(&user_profiles_dir());
+    for error in errors {
+        warn!("skipping user profile {}: {}", error.file, error.message);
+    }
+    Mutex::new(profiles)
+});
+
+/// Directory holding user profile files.
+pub fn user_profiles_dir() -> PathBuf {
+    app_data_dir().join(USER_PROFILES_DIR_NAME)
+}
+
+/// User profiles loaded so far
+pub fn user_profiles() -> Vec<GameProfile> {
+    USER_PROFILES
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+/// Reads every `.toml` and `.json` profile in `dir`, in file name order.
+/// Invalid files, ids taken by a built-in or earlier profile, and patterns
+/// that are not valid regexes are reported instead of loaded.
+pub fn load_user_profiles(dir: &Path) -> (Vec<GameProfile>, Vec<ProfileLoadError>) {
+    let mut profiles: Vec<GameProfile> = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (profiles, errors);
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    let builtin_ids: Vec<String> = builtin_profiles().into_iter().map(|p| p.id).collect();
+    for path in paths {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if extension != "toml" && extension != "json" {
+            continue;
+        }
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let result = parse_profile(&path, &extension).and_then(|profile| {
+            let taken = builtin_ids.contains(&profile.id)
+                || profiles.iter().any(|loaded| loaded.id == profile.id);
+            if taken {
+                return Err(format!("이미 사용 중인 프로필 ID입니다: {}", profile.id));
+            }
+            Ok(profile)
+        });
+        match result {
+            Ok(profile) => profiles.push(profile),
+            Err(message) => errors.push(ProfileLoadError { file, message }),
+        }
+    }
+    (profiles, errors)
+}
+
+fn parse_profile(path: &Path, extension: &str) -> Result<GameProfile, String> {
+    let too_large = fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_PROFILE_BYTES);
+    if too_large {
+        return Err("파일이 너무 큽니다".to_string());
+    }
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let profile: GameProfile = if extension == "toml" {
+        toml::from_str(&contents).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())?
+    };
+
+    if profile.id.trim().is_empty() {
+        return Err("프로필 ID가 비어 있습니다".to_string());
+    }
+    let patterns = profile
+        .extra_placeholders
+        .iter()
+        .chain(&profile.validator_config.force_fixed_patterns);
+    for pattern in patterns {
+        Regex::new(pattern).map_err(|e| format!("잘못된 정규식 {}: {}", pattern, e))?;
+    }
+    profile.prompt_config.template().validate()?;
+    Ok(profile)
+}
+
+/// Tauri command to list built-in and user game profiles, re-reading the
+/// user profile directory
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn list_game_profiles() -> Result<GameProfileListing, String> {
+    let dir = user_profiles_dir();
+    let (profiles, errors) = load_user_profiles(&dir);
+    let user_profile_ids = profiles.iter().map(|profile| profile.id.clone()).collect();
+    *USER_PROFILES
+        .lock()
+        .map_err(|_| "user profiles lock poisoned".to_string())? = profiles;
+
+    Ok(GameProfileListing {
+        profiles: super::list_profiles(),
+        user_profile_ids,
+        errors,
+        user_profile_dir: dir.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_toml_and_json_profiles_and_reports_bad_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a_terraria.toml"),
+            r#"
+id = "terraria"
+name = "Terraria (tModLoader)"
+include_paths = ["Localization/"]
+extra_placeholders = ['\{\$[A-Za-z.]+\}']
+
+[detector]
+file_patterns = ["build.txt"]
+manifest_signatures = ["displayName"]
+
+[terminology]
+"boss" = "보스"
+
+[validator_config]
+allowedTokenTypes = ["NAMED"]
+
+[prompt_config]
+gameContext = "Terraria is a 2D sandbox adventure game."
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b_kenshi.json"),
+            r#"{ "id": "kenshi", "name": "Kenshi", "detector": { "folder_patterns": ["locale/"] } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("c_clash.json"),
+            r#"{ "id": "rimworld", "name": "Mine" }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("d_regex.json"),
+            r#"{ "id": "broken", "name": "Broken", "extra_placeholders": ["(unclosed"] }"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("e_syntax.toml"), "id = ").unwrap();
+        fs::write(
+            dir.path().join("f_prompt.json"),
+            r#"{ "id": "prompt", "name": "Prompt", "prompt_config": { "user": "{{txt}}" } }"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let (profiles, errors) = load_user_profiles(dir.path());
+        let ids: Vec<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["terraria", "kenshi"]);
+        assert_eq!(profiles[0].terminology["boss"], "보스");
+        assert_eq!(profiles[0].detector.file_patterns, vec!["build.txt"]);
+        assert!(profiles[0]
+            .validator_config
+            .allowed_token_types
+            .contains("NAMED"));
+        assert!(profiles[0].prompt_config.game_context.is_some());
+        assert!(profiles[1].include_paths.is_empty());
+
+        let failed: Vec<&str> = errors.iter().map(|e| e.file.as_str()).collect();
+        assert_eq!(
+            failed,
+            vec!["c_clash.json", "d_regex.json", "e_syntax.toml", "f_prompt.json"]
+        );
+
+        let missing = load_user_profiles(&dir.path().join("missing"));
+        assert!(missing.0.is_empty() && missing.1.is_empty());
+    }
+
+    #[test]
+    fn user_profile_rules_match_mods() {
+        let profiles_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            profiles_dir.path().join("terraria.json"),
+            r#"{ "id": "terraria", "name": "Terraria",
+                 "detector": { "file_patterns": ["build.txt"], "manifest_signatures": ["displayName"] } }"#,
+        )
+        .unwrap();
+        let (profiles, _) = load_user_profiles(profiles_dir.path());
+
+        let mod_dir = tempfile::tempdir().unwrap();
+        fs::write(mod_dir.path().join("build.txt"), "displayName = Calamity").unwrap();
+        assert_eq!(
+            profiles[0].detector.signals(mod_dir.path()),
+            vec!["file build.txt", "manifest displayName"]
+        );
+    }
+}
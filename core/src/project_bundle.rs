@@ -0,0 +1,467 @@
+/// Portable translation project bundles.
+///
+/// A bundle is a ZIP archive holding everything needed to continue or review
+/// a mod's translation on another machine: the user's settings and glossary,
+/// the game profile override, a translation memory built from the review
+/// reports, and every output file with its `.review` / `.resume` sidecars.
+/// Files are stored under `files/` with paths relative to the mod root, next
+/// to a `project.json` manifest.
+use crate::access_mode::ensure_writable;
+use crate::backup::backup_and_swap;
+use crate::jobs::{COLLISION_MIRROR_DIR, RESUME_DIR_NAME};
+use crate::profiles::overrides::{get_profile_override, set_profile_override};
+use crate::profiles::GameProfile;
+use crate::review::{read_review_report, SegmentReviewStatus, REVIEW_DIR_NAME};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::read::ZipArchive;
+use zip::write::{FileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+const MANIFEST_NAME: &str = "project.json";
+const FILES_PREFIX: &str = "files/";
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+/// Upper bound on files packed into one bundle
+const MAX_BUNDLE_FILES: usize = 10_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("ZIP error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("프로젝트 정보를 읽지 못했습니다: {0}")]
+    Manifest(#[from] serde_json::Error),
+
+    #[error("지원하지 않는 프로젝트 번들입니다: {0}")]
+    Unsupported(String),
+}
+
+/// One source/target pair from a review report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryEntry {
+    pub source: String,
+    pub target: String,
+    /// Output file the pair was taken from, relative to the mod root
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectManifest {
+    pub format_version: u32,
+    pub tool_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub mod_name: String,
+    #[serde(default)]
+    pub profile_override: Option<String>,
+    /// Frontend settings, stored as given
+    #[serde(default)]
+    pub settings: Option<Value>,
+    #[serde(default)]
+    pub glossary: BTreeMap<String, String>,
+    #[serde(default)]
+    pub translation_memory: Vec<MemoryEntry>,
+    /// Packed files, relative to the mod root
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedProject {
+    pub bundle_path: String,
+    pub file_count: usize,
+    pub memory_entries: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedProject {
+    pub mod_name: String,
+    pub settings: Option<Value>,
+    pub glossary: BTreeMap<String, String>,
+    pub translation_memory: Vec<MemoryEntry>,
+    pub profile_override: Option<String>,
+    /// Files written into the mod folder
+    pub written: Vec<String>,
+    /// Files left alone because they already exist
+    pub skipped: Vec<String>,
+}
+
+/// Output files and sidecars under the mod root, relative and `/`-separated
+pub fn collect_project_files(mod_root: &Path) -> Vec<String> {
+    let mut files = BTreeSet::new();
+    let mut pending = vec![mod_root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == COLLISION_MIRROR_DIR {
+                collect_all(&path, &mut files, mod_root);
+            } else if let Some(suffix) = sidecar_suffix(&name) {
+                collect_sidecars(&path, suffix, &mut files, mod_root);
+            } else {
+                pending.push(path);
+            }
+        }
+    }
+    files.into_iter().take(MAX_BUNDLE_FILES).collect()
+}
+
+fn sidecar_suffix(dir_name: &str) -> Option<&'static str> {
+    match dir_name {
+        REVIEW_DIR_NAME => Some(".review.json"),
+        RESUME_DIR_NAME => Some(".resume.json"),
+        _ => None,
+    }
+}
+
+/// Sidecars in a `.review` / `.resume` folder and the outputs they describe
+fn collect_sidecars(dir: &Path, suffix: &str, files: &mut BTreeSet<String>, mod_root: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let output_dir = dir.parent().unwrap_or(mod_root);
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(output_name) = name.strip_suffix(suffix) else {
+            continue;
+        };
+        files.insert(relative(mod_root, &path));
+        let output = output_dir.join(output_name);
+        if output.is_file() {
+            files.insert(relative(mod_root, &output));
+        }
+    }
+}
+
+fn collect_all(dir: &Path, files: &mut BTreeSet<String>, mod_root: &Path) {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.insert(relative(mod_root, &path));
+            }
+        }
+    }
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Translated pairs from every packed review report, without duplicates
+fn build_translation_memory(mod_root: &Path, files: &[String]) -> Vec<MemoryEntry> {
+    let mut seen = HashSet::new();
+    let mut memory = Vec::new();
+    for file in files.iter().filter(|file| file.ends_with(".review.json")) {
+        let Ok(report) = read_review_report(&mod_root.join(file)) else {
+            continue;
+        };
+        for segment in report.segments {
+            let accepted = matches!(
+                segment.status,
+                SegmentReviewStatus::Translated | SegmentReviewStatus::Recovered
+            );
+            let Some(target) = segment.translation.filter(|_| accepted) else {
+                continue;
+            };
+            if seen.insert((segment.source.clone(), target.clone())) {
+                memory.push(MemoryEntry {
+                    source: segment.source,
+                    target,
+                    file: report.output_file.clone(),
+                });
+            }
+        }
+    }
+    memory
+}
+
+pub fn export_bundle(
+    mod_root: &Path,
+    bundle_path: &Path,
+    settings: Option<Value>,
+    glossary: BTreeMap<String, String>,
+) -> Result<ExportedProject, BundleError> {
+    if !mod_root.is_dir() {
+        return Err(BundleError::Unsupported(format!(
+            "모드 폴더를 찾을 수 없습니다: {}",
+            mod_root.display()
+        )));
+    }
+    let files = collect_project_files(mod_root);
+    let manifest = ProjectManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        mod_name: mod_root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        profile_override: get_profile_override(mod_root),
+        settings,
+        glossary,
+        translation_memory: build_translation_memory(mod_root, &files),
+        files,
+    };
+
+    if let Some(parent) = bundle_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = ZipWriter::new(File::create(bundle_path)?);
+    let options = FileOptions::<()>::default()
+        .compression_method(CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    writer.start_file(MANIFEST_NAME, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    for file in &manifest.files {
+        writer.start_file(format!("{}{}", FILES_PREFIX, file), options)?;
+        writer.write_all(&fs::read(mod_root.join(file))?)?;
+    }
+    writer.finish()?;
+
+    Ok(ExportedProject {
+        bundle_path: bundle_path.to_string_lossy().to_string(),
+        file_count: manifest.files.len(),
+        memory_entries: manifest.translation_memory.len(),
+    })
+}
+
+/// Unpacks a bundle into `mod_root`. Existing files are kept unless
+/// `overwrite` is set, in which case they are backed up first.
+pub fn import_bundle(
+    bundle_path: &Path,
+    mod_root: &Path,
+    overwrite: bool,
+) -> Result<ImportedProject, BundleError> {
+    let mut archive = ZipArchive::new(File::open(bundle_path)?)?;
+    let manifest: ProjectManifest = {
+        let entry = archive
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| BundleError::Unsupported(format!("{} 없음", MANIFEST_NAME)))?;
+        serde_json::from_reader(entry)?
+    };
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(BundleError::Unsupported(format!(
+            "번들 형식 버전 {}",
+            manifest.format_version
+        )));
+    }
+
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        // `enclosed_name` rejects absolute paths and `..` components
+        let Some(relative_path) = entry
+            .enclosed_name()
+            .and_then(|name| name.strip_prefix(FILES_PREFIX).ok().map(PathBuf::from))
+        else {
+            continue;
+        };
+        let target = mod_root.join(&relative_path);
+        let display = relative(Path::new(""), &relative_path);
+        if target.exists() && !overwrite {
+            skipped.push(display);
+            continue;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        backup_and_swap(&target, &contents).map_err(|err| io::Error::other(err.to_string()))?;
+        written.push(display);
+    }
+
+    let profile_override = manifest
+        .profile_override
+        .filter(|id| GameProfile::by_id(id).is_some());
+    if profile_override.is_some() {
+        set_profile_override(mod_root, profile_override.clone()).map_err(io::Error::other)?;
+    }
+
+    Ok(ImportedProject {
+        mod_name: manifest.mod_name,
+        settings: manifest.settings,
+        glossary: manifest.glossary,
+        translation_memory: manifest.translation_memory,
+        profile_override,
+        written,
+        skipped,
+    })
+}
+
+/// Tauri command to pack a mod's translation project into a bundle
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn export_project(
+    modPath: String,
+    bundlePath: String,
+    settings: Option<Value>,
+    glossary: Option<BTreeMap<String, String>>,
+) -> Result<ExportedProject, String> {
+    export_bundle(
+        Path::new(&modPath),
+        Path::new(&bundlePath),
+        settings,
+        glossary.unwrap_or_default(),
+    )
+    .map_err(|err| err.to_string())
+}
+
+/// Tauri command to unpack a project bundle into a mod folder
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn import_project(
+    bundlePath: String,
+    modPath: String,
+    overwrite: Option<bool>,
+) -> Result<ImportedProject, String> {
+    ensure_writable("프로젝트 가져오기")?;
+    import_bundle(
+        Path::new(&bundlePath),
+        Path::new(&modPath),
+        overwrite.unwrap_or(false),
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::review::{
+        review_report_path, write_review_report, FileReviewReport, ReviewMetadata, SegmentReview,
+    };
+
+    fn segment(source: &str, translation: &str, status: SegmentReviewStatus) -> SegmentReview {
+        SegmentReview {
+            line_number: 1,
+            source: source.into(),
+            translation: Some(translation.into()),
+            status,
+            usage: None,
+            notes: vec![],
+            recovery_steps: vec![],
+            confidence: None,
+        }
+    }
+
+    fn sample_mod(root: &Path) {
+        let lang = root.join("Languages").join("Korean").join("Keyed");
+        fs::create_dir_all(&lang).unwrap();
+        fs::create_dir_all(root.join("Textures")).unwrap();
+        fs::write(root.join("Textures").join("icon.png"), b"png").unwrap();
+        let output = lang.join("UI.xml");
+        fs::write(&output, "<LanguageData><Hello>안녕</Hello></LanguageData>").unwrap();
+        let report = FileReviewReport::new(
+            ReviewMetadata {
+                job_id: "job-1".into(),
+                source_lang: "en".into(),
+                target_lang: "ko".into(),
+                provider: "gemini".into(),
+                model_id: "model".into(),
+            },
+            "Languages/English/Keyed/UI.xml".into(),
+            "Languages/Korean/Keyed/UI.xml".into(),
+            vec![
+                segment("Hello", "안녕", SegmentReviewStatus::Translated),
+                segment("Hello", "안녕", SegmentReviewStatus::Recovered),
+                segment("Bye", "Bye", SegmentReviewStatus::RolledBack),
+            ],
+        );
+        write_review_report(&review_report_path(&output), &report).unwrap();
+        let mirror = root.join(COLLISION_MIRROR_DIR).join("ko");
+        fs::create_dir_all(&mirror).unwrap();
+        fs::write(mirror.join("strings.txt"), "번역").unwrap();
+    }
+
+    #[test]
+    fn collects_outputs_sidecars_and_mirrored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        sample_mod(dir.path());
+        assert_eq!(
+            collect_project_files(dir.path()),
+            vec![
+                "Languages/Korean/Keyed/.review/UI.xml.review.json",
+                "Languages/Korean/Keyed/UI.xml",
+                "_translated/ko/strings.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn bundle_roundtrips_into_another_mod_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("SourceMod");
+        sample_mod(&source);
+        let bundle = dir.path().join("out").join("project.zip");
+        let glossary = BTreeMap::from([("pawn".to_string(), "폰".to_string())]);
+        let settings = serde_json::json!({ "targetLanguage": "ko" });
+
+        let exported = export_bundle(&source, &bundle, Some(settings.clone()), glossary).unwrap();
+        assert_eq!(exported.file_count, 3);
+        assert_eq!(exported.memory_entries, 1);
+
+        let target = dir.path().join("TargetMod");
+        let existing = target.join("_translated").join("ko");
+        fs::create_dir_all(&existing).unwrap();
+        fs::write(existing.join("strings.txt"), "local edit").unwrap();
+
+        let imported = import_bundle(&bundle, &target, false).unwrap();
+        assert_eq!(imported.mod_name, "SourceMod");
+        assert_eq!(imported.settings, Some(settings));
+        assert_eq!(imported.glossary["pawn"], "폰");
+        assert_eq!(
+            imported.translation_memory,
+            vec![MemoryEntry {
+                source: "Hello".into(),
+                target: "안녕".into(),
+                file: "Languages/Korean/Keyed/UI.xml".into(),
+            }]
+        );
+        assert_eq!(imported.written.len(), 2);
+        assert_eq!(imported.skipped, vec!["_translated/ko/strings.txt"]);
+        assert_eq!(
+            fs::read_to_string(existing.join("strings.txt")).unwrap(),
+            "local edit"
+        );
+        assert!(target
+            .join("Languages/Korean/Keyed/.review/UI.xml.review.json")
+            .is_file());
+
+        let forced = import_bundle(&bundle, &target, true).unwrap();
+        assert_eq!(forced.written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(existing.join("strings.txt")).unwrap(),
+            "번역"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+/// Saved translation projects.
+///
+/// A project keeps the setup of recurring translation work under a name: the
+/// selected mods and files, provider and model, languages and output
+/// overrides. Projects are stored as one JSON file each in the user data
+/// directory. API keys are never part of a project; the frontend supplies
+/// them from its own settings when a job starts.
+use crate::backup::write_atomic;
+use crate::encoding::OutputEncoding;
+use crate::jobs::TranslationFileInput;
+use crate::paths::app_data_dir;
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PROJECTS_DIR_NAME: &str = "projects";
+
+/// A mod of a project and the files selected in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMod {
+    pub mod_path: String,
+    /// 선택한 파일 (비어 있으면 기본 선택 파일)
+    #[serde(default)]
+    pub files: Vec<TranslationFileInput>,
+}
+
+/// Output settings that differ from the defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectOutputOverrides {
+    /// 출력 폴더 (없으면 모드 폴더)
+    #[serde(default)]
+    pub output_override_dir: Option<String>,
+    #[serde(default)]
+    pub output_encoding: OutputEncoding,
+    #[serde(default)]
+    pub allow_source_overwrite: bool,
+    #[serde(default)]
+    pub embed_provenance: bool,
+    #[serde(default)]
+    pub export_review: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedProject {
+    pub name: String,
+    #[serde(default)]
+    pub mods: Vec<ProjectMod>,
+    pub provider: String,
+    pub model_id: String,
+    #[serde(default)]
+    pub source_lang: Option<String>,
+    #[serde(default)]
+    pub target_lang: Option<String>,
+    #[serde(default)]
+    pub output: ProjectOutputOverrides,
+    /// Set when the project is first saved
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Set on every save
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Listing entry of a saved project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSummary {
+    pub name: String,
+    pub mod_count: usize,
+    pub file_count: usize,
+    pub provider: String,
+    pub model_id: String,
+    pub target_lang: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&SavedProject> for ProjectSummary {
+    fn from(project: &SavedProject) -> Self {
+        Self {
+            name: project.name.clone(),
+            mod_count: project.mods.len(),
+            file_count: project.mods.iter().map(|entry| entry.files.len()).sum(),
+            provider: project.provider.clone(),
+            model_id: project.model_id.clone(),
+            target_lang: project.target_lang.clone(),
+            updated_at: project.updated_at,
+        }
+    }
+}
+
+/// Directory holding saved projects
+pub fn projects_dir() -> PathBuf {
+    app_data_dir().join(PROJECTS_DIR_NAME)
+}
+
+/// File of the project called `name`; characters that are not safe in file
+/// names become `_`
+fn project_path(dir: &Path, name: &str) -> PathBuf {
+    let file_stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}.json", file_stem))
+}
+
+fn read_project(path: &Path) -> Result<SavedProject, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("프로젝트 파일을 읽지 못했습니다: {}", e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("프로젝트 파일이 올바르지 않습니다: {}", e))
+}
+
+/// Saves `project` in `dir`, keeping the creation time of an earlier save
+pub fn save_project_in(dir: &Path, mut project: SavedProject) -> Result<SavedProject, String> {
+    project.name = project.name.trim().to_string();
+    if project.name.is_empty() {
+        return Err("프로젝트 이름을 입력해야 합니다.".into());
+    }
+    let path = project_path(dir, &project.name);
+    let now = Utc::now();
+    project.created_at = match read_project(&path) {
+        Ok(existing) if existing.name != project.name => {
+            return Err(format!(
+                "'{}' 프로젝트와 파일 이름이 겹칩니다. 다른 이름을 사용하세요.",
+                existing.name
+            ));
+        }
+        Ok(existing) => existing.created_at.or(Some(now)),
+        Err(_) => Some(now),
+    };
+    project.updated_at = Some(now);
+
+    let contents = serde_json::to_vec_pretty(&project).map_err(|e| e.to_string())?;
+    write_atomic(&path, &contents).map_err(|e| format!("프로젝트를 저장하지 못했습니다: {}", e))?;
+    Ok(project)
+}
+
+pub fn load_project_from(dir: &Path, name: &str) -> Result<SavedProject, String> {
+    let path = project_path(dir, name.trim());
+    if !path.is_file() {
+        return Err(format!("'{}' 프로젝트를 찾을 수 없습니다.", name.trim()));
+    }
+    read_project(&path)
+}
+
+/// Saved projects in `dir`, most recently saved first. Unreadable files are
+/// skipped.
+pub fn list_projects_in(dir: &Path) -> Vec<ProjectSummary> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut projects: Vec<ProjectSummary> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| match read_project(&path) {
+            Ok(project) => Some(ProjectSummary::from(&project)),
+            Err(error) => {
+                warn!("skipping project {}: {}", path.display(), error);
+                None
+            }
+        })
+        .collect();
+    projects.sort_by(|a, b| {
+        b.updated_at
+            .cmp(&a.updated_at)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    projects
+}
+
+/// Tauri command to save a project under its name, replacing an earlier
+/// save of the same name
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn save_project(project: SavedProject) -> Result<SavedProject, String> {
+    save_project_in(&projects_dir(), project)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn load_project(name: String) -> Result<SavedProject, String> {
+    load_project_from(&projects_dir(), &name)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn list_projects() -> Result<Vec<ProjectSummary>, String> {
+    Ok(list_projects_in(&projects_dir()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> SavedProject {
+        SavedProject {
+            name: name.into(),
+            mods: vec![ProjectMod {
+                mod_path: "/mods/demo".into(),
+                files: vec![TranslationFileInput {
+                    relative_path: "Languages/English/Keyed/UI.xml".into(),
+                    mod_install_path: "/mods/demo".into(),
+                    archive_path: None,
+                    archive_entry_path: None,
+                }],
+            }],
+            provider: "gemini".into(),
+            model_id: "gemini-2.5-flash".into(),
+            source_lang: Some("en".into()),
+            target_lang: Some("ko".into()),
+            output: ProjectOutputOverrides {
+                output_override_dir: Some("/out".into()),
+                output_encoding: OutputEncoding::Utf8Bom,
+                ..Default::default()
+            },
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn projects_round_trip_and_keep_their_creation_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = save_project_in(dir.path(), project("  림월드 모음 ")).unwrap();
+        assert_eq!(first.name, "림월드 모음");
+        assert!(first.created_at.is_some());
+
+        let mut changed = project("림월드 모음");
+        changed.model_id = "gemini-2.5-pro".into();
+        let second = save_project_in(dir.path(), changed).unwrap();
+        assert_eq!(second.created_at, first.created_at);
+
+        let loaded = load_project_from(dir.path(), "림월드 모음").unwrap();
+        assert_eq!(loaded.model_id, "gemini-2.5-pro");
+        assert_eq!(
+            loaded.mods[0].files[0].relative_path,
+            "Languages/English/Keyed/UI.xml"
+        );
+        assert_eq!(loaded.output.output_encoding, OutputEncoding::Utf8Bom);
+        assert_eq!(loaded.output.output_override_dir.as_deref(), Some("/out"));
+
+        assert!(load_project_from(dir.path(), "missing").is_err());
+        assert!(save_project_in(dir.path(), project("   ")).is_err());
+    }
+
+    #[test]
+    fn listing_shows_newest_first_and_rejects_file_name_clashes() {
+        let dir = tempfile::tempdir().unwrap();
+        save_project_in(dir.path(), project("a/b")).unwrap();
+        save_project_in(dir.path(), project("weekly")).unwrap();
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+
+        let listed = list_projects_in(dir.path());
+        let names: Vec<&str> = listed.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["weekly", "a/b"]);
+        assert_eq!((listed[0].mod_count, listed[0].file_count), (1, 1));
+
+        // "a_b" and "a/b" share a file name
+        assert!(save_project_in(dir.path(), project("a_b")).is_err());
+    }
+}
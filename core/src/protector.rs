@@ -1,6 +1,7 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 
@@ -31,6 +32,11 @@ static FACTORIO_MACRO_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"__(?:[A-Z]+(?:__[A-Za-z0-9_\-\.]+__)?|[0-9]+__)").expect("valid Factorio macro regex")
 });
 
+// Factorio plural forms: __plural_for_parameter__1__{1=item|rest=items}__
+static FACTORIO_PLURAL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"__plural_for_parameter__?[0-9]+__?\{[^{}]*\}__").expect("valid Factorio plural regex")
+});
+
 // Factorio images/links: [img=item/iron-plate], [entity=iron-ore]
 static FACTORIO_LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\[(?:img|item|entity|technology|virtual-signal)=[^\]]+\]").expect("valid Factorio link regex")
@@ -62,6 +68,44 @@ static MINECRAFT_COLOR_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"§[0-9A-FK-ORa-fk-or]").expect("valid Minecraft color regex")
 });
 
+// Paradox color codes: §Y...§! (section sign + color letter, § ! closes)
+static PARADOX_COLOR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"§[A-Za-z0-9!]").expect("valid Paradox color regex")
+});
+
+// Paradox scripted localisation: [GetName], [Root.GetFirstName], [ROOT.Char.GetName|U]
+// The called function starts with an uppercase letter, unlike Ren'Py variables
+static PARADOX_SCRIPTED_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[(?:[A-Za-z_][A-Za-z0-9_]*(?:\('[^'\]]*'\))?\.)*[A-Z][A-Za-z0-9_]*(?:\('[^'\]]*'\))?(?:\|[A-Za-z0-9%+\-]*)?\]")
+        .expect("valid Paradox scripted localisation regex")
+});
+
+// Paradox variables: $VARIABLE$, $cost|Y$
+static PARADOX_VARIABLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$[A-Za-z_][A-Za-z0-9_.]*(?:\|[A-Za-z0-9%+\-=]*)?\$").expect("valid Paradox variable regex")
+});
+
+// Paradox icons: £energy£, £minerals|1£
+static PARADOX_ICON_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"£[A-Za-z0-9_]+(?:\|[A-Za-z0-9_]*)?£").expect("valid Paradox icon regex")
+});
+
+// Minecraft target selectors: @p, @a, @e[type=zombie,limit=1]
+static MINECRAFT_SELECTOR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\B@[aeprsn](?:\[[^\]\n]*\]|\b)").expect("valid Minecraft selector regex")
+});
+
+// Ren'Py interpolation: [player_name], [mc.name!t], [count:d]
+static RENPY_INTERP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[[A-Za-z_][A-Za-z0-9_.]*(?:\[[^\[\]]*\])?(?:![a-z]+)?(?::[^\[\]]*)?\]")
+        .expect("valid Ren'Py interpolation regex")
+});
+
+// Ren'Py text tags: {b}, {/b}, {color=#f00}, {w=0.5}
+static RENPY_TAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{/?[a-z]+(?:=[^{}]*)?\}").expect("valid Ren'Py text tag regex")
+});
+
 // Unity/RimWorld rich text: <color=#abc>, <sprite=name>, <size=14>
 static RICH_TEXT_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"</?(?:color|size|sprite|material)(?:=[^>]+)?>").expect("valid rich text regex")
@@ -79,6 +123,13 @@ static DOUBLE_BRACKET_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\[\[[^\]]+\]\]|<<[^>]+>>").expect("valid double bracket regex")
 });
 
+// Content Patcher tokens (Stardew Valley): {{PlayerName}}, {{i18n:key}},
+// {{Random: a, b |inputSeparator=,}}, one nested token deep
+static CONTENT_PATCHER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*[A-Za-z][\w.]*\s*(?:[:|](?:[^{}]|\{\{[^{}]*\}\})*)?\}\}")
+        .expect("valid Content Patcher token regex")
+});
+
 // Mustache/Handlebars: {{var}}, {{#each}}, {{/each}}
 static MUSTACHE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\{\{[^}]+\}\}").expect("valid mustache regex")
@@ -170,6 +221,103 @@ static ID_PATH_REGEX: Lazy<Regex> = Lazy::new(|| {
 static MARKER_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"⟦MT:([A-Z_]+):([0-9]+)⟧").expect("valid marker regex"));
 
+static DOUBLE_ANGLE_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"⟪MT:([A-Z_]+):([0-9]+)⟫").expect("valid marker regex"));
+
+static WHITE_CURLY_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"⦃MT:([A-Z_]+):([0-9]+)⦄").expect("valid marker regex"));
+
+static ASCII_MARKER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[MT:([A-Z_]+):([0-9]+)\]\]").expect("valid marker regex"));
+
+/// Brackets around the `MT:<CLASS>:<n>` markers sent to the model.
+///
+/// The default `⟦…⟧` can collide with mods that use those glyphs, and some
+/// providers mangle rare Unicode, so a job can pick another alphabet or let
+/// [`MarkerScheme::negotiate`] choose one its source text does not use.
+/// Validation works on the default form; [`MarkerScheme::to_canonical`] and
+/// [`MarkerScheme::from_canonical`] convert between the two.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum MarkerScheme {
+    /// `⟦MT:TAG:0⟧`
+    #[default]
+    WhiteSquare,
+    /// `⟪MT:TAG:0⟫`
+    DoubleAngle,
+    /// `⦃MT:TAG:0⦄`
+    WhiteCurly,
+    /// `[[MT:TAG:0]]`, for providers that mangle non-ASCII brackets
+    Ascii,
+}
+
+impl MarkerScheme {
+    /// Every scheme, in negotiation order
+    pub const ALL: [MarkerScheme; 4] = [
+        MarkerScheme::WhiteSquare,
+        MarkerScheme::DoubleAngle,
+        MarkerScheme::WhiteCurly,
+        MarkerScheme::Ascii,
+    ];
+
+    pub fn delimiters(self) -> (&'static str, &'static str) {
+        match self {
+            MarkerScheme::WhiteSquare => ("⟦", "⟧"),
+            MarkerScheme::DoubleAngle => ("⟪", "⟫"),
+            MarkerScheme::WhiteCurly => ("⦃", "⦄"),
+            MarkerScheme::Ascii => ("[[", "]]"),
+        }
+    }
+
+    /// Matches this scheme's markers, capturing class code and index
+    pub fn regex(self) -> &'static Regex {
+        match self {
+            MarkerScheme::WhiteSquare => &MARKER_REGEX,
+            MarkerScheme::DoubleAngle => &DOUBLE_ANGLE_MARKER_REGEX,
+            MarkerScheme::WhiteCurly => &WHITE_CURLY_MARKER_REGEX,
+            MarkerScheme::Ascii => &ASCII_MARKER_REGEX,
+        }
+    }
+
+    pub fn marker(self, code: &str, index: usize) -> String {
+        let (open, close) = self.delimiters();
+        format!("{}MT:{}:{}{}", open, code, index, close)
+    }
+
+    /// First scheme whose brackets appear in none of `texts`, falling back
+    /// to the default when every scheme collides
+    pub fn negotiate<'a>(texts: impl IntoIterator<Item = &'a str>) -> MarkerScheme {
+        let mut free = Self::ALL.to_vec();
+        for text in texts {
+            free.retain(|scheme| {
+                let (open, close) = scheme.delimiters();
+                !text.contains(open) && !text.contains(close)
+            });
+            if free.is_empty() {
+                break;
+            }
+        }
+        free.first().copied().unwrap_or_default()
+    }
+
+    /// Rewrites this scheme's markers in the default `⟦…⟧` form
+    pub fn to_canonical(self, text: &str) -> Cow<'_, str> {
+        if self == MarkerScheme::WhiteSquare {
+            return Cow::Borrowed(text);
+        }
+        self.regex().replace_all(text, "⟦MT:${1}:${2}⟧")
+    }
+
+    /// Rewrites default `⟦…⟧` markers in this scheme
+    pub fn from_canonical(self, text: &str) -> Cow<'_, str> {
+        if self == MarkerScheme::WhiteSquare {
+            return Cow::Borrowed(text);
+        }
+        let (open, close) = self.delimiters();
+        MARKER_REGEX.replace_all(text, format!("{}MT:${{1}}:${{2}}{}", open, close).as_str())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TokenClass {
@@ -180,6 +328,7 @@ pub enum TokenClass {
     ShellVar,         // $VAR, ${count}
     FactorioMacro,    // __1__, __ENTITY__foo__
     FactorioLink,     // [img=item/plate]
+    FactorioPlural,   // __plural_for_parameter__1__{1=item|rest=items}__
     Icu,              // {n, plural, ...}
     
     // Markup/color
@@ -187,12 +336,20 @@ pub enum TokenClass {
     BbCode,           // [b], [color=...]
     RimworldColor,    // <color=#fff>, </color>
     MinecraftColor,   // §a, §l
+    MinecraftSelector, // @p, @e[type=zombie]
+    ParadoxColor,     // §Y, §!
+    ParadoxScripted,  // [GetName], [Root.GetFirstName]
+    ParadoxVariable,  // $VARIABLE$
+    ParadoxIcon,      // £energy£
+    RenpyInterp,      // [player_name]
+    RenpyTag,         // {color=#f00}, {/b}
     RichText,         // <sprite=...>
     FactorioColor,    // [color=red]
     
     // Resource/macro
     DoubleBracket,    // [[res]], <<macro>>
     Mustache,         // {{var}}
+    ContentPatcher,   // {{i18n:key}}, {{PlayerName}}
     
     // Math/numerical (Section 2.1)
     MathExpr,         // 3.14 × r^2, (a+b)/2
@@ -207,6 +364,9 @@ pub enum TokenClass {
     Entity,           // &nbsp;
     Escape,           // \n, \t
     
+    // Game profile
+    ProfilePattern,   // spans matched by a profile's extra placeholders
+
     // Legacy (for backward compat)
     Attr,
     Key,
@@ -215,7 +375,7 @@ pub enum TokenClass {
 }
 
 impl TokenClass {
-    fn code(&self) -> &'static str {
+    pub fn code(&self) -> &'static str {
         match self {
             TokenClass::Printf => "PRINTF",
             TokenClass::DotnetBrace => "DOTNET",
@@ -223,15 +383,24 @@ impl TokenClass {
             TokenClass::ShellVar => "SHELL",
             TokenClass::FactorioMacro => "FACTORIO",
             TokenClass::FactorioLink => "FLINK",
+            TokenClass::FactorioPlural => "FPLURAL",
             TokenClass::Icu => "ICU",
             TokenClass::Tag => "TAG",
             TokenClass::BbCode => "BBCODE",
             TokenClass::RimworldColor => "RWCOLOR",
             TokenClass::MinecraftColor => "MCCOLOR",
+            TokenClass::MinecraftSelector => "MCSEL",
+            TokenClass::ParadoxColor => "PDXCOLOR",
+            TokenClass::ParadoxScripted => "PDXLOC",
+            TokenClass::ParadoxVariable => "PDXVAR",
+            TokenClass::ParadoxIcon => "PDXICON",
+            TokenClass::RenpyInterp => "RPYVAR",
+            TokenClass::RenpyTag => "RPYTAG",
             TokenClass::RichText => "RICHTEXT",
             TokenClass::FactorioColor => "FCOLOR",
             TokenClass::DoubleBracket => "DBLBRACK",
             TokenClass::Mustache => "MUSTACHE",
+            TokenClass::ContentPatcher => "CPTOKEN",
             TokenClass::MathExpr => "MATHEXPR",
             TokenClass::Range => "RANGE",
             TokenClass::Percent => "PERCENT",
@@ -241,6 +410,7 @@ impl TokenClass {
             TokenClass::EscapedPercent => "ESCPCT",
             TokenClass::Entity => "ENTITY",
             TokenClass::Escape => "ESCAPE",
+            TokenClass::ProfilePattern => "PROFILE",
             TokenClass::Attr => "ATTR",
             TokenClass::Key => "KEY",
             TokenClass::Pipe => "PIPE",
@@ -265,6 +435,8 @@ pub struct ProtectedToken {
 pub struct TokenMap {
     pub content_hash: String,
     pub tokens: Vec<ProtectedToken>,
+    /// Brackets the markers were written with
+    pub scheme: MarkerScheme,
 }
 
 #[derive(Debug, Clone)]
@@ -338,7 +510,10 @@ impl Protector {
     /// Check if text contains translatable natural language
     pub fn has_translatable_text(text: &str) -> bool {
         // Remove all protected token markers first
-        let cleaned = MARKER_REGEX.replace_all(text, "");
+        let mut cleaned = text.to_string();
+        for scheme in MarkerScheme::ALL {
+            cleaned = scheme.regex().replace_all(&cleaned, "").into_owned();
+        }
         let trimmed = cleaned.trim();
         
         if trimmed.is_empty() {
@@ -361,6 +536,26 @@ impl Protector {
     
     /// Protect input with specified mode
     pub fn protect_with_mode(input: &str, mode: ProtectionMode) -> ProtectedFragment {
+        Self::protect_with_patterns(input, mode, &[])
+    }
+
+    /// Protect input with specified mode plus a game profile's own patterns.
+    /// Profile patterns only claim spans no built-in class already covers.
+    pub fn protect_with_patterns(
+        input: &str,
+        mode: ProtectionMode,
+        profile_patterns: &[Regex],
+    ) -> ProtectedFragment {
+        Self::protect_with_scheme(input, mode, profile_patterns, MarkerScheme::default())
+    }
+
+    /// Protect input, writing markers with the job's negotiated `scheme`
+    pub fn protect_with_scheme(
+        input: &str,
+        mode: ProtectionMode,
+        profile_patterns: &[Regex],
+        scheme: MarkerScheme,
+    ) -> ProtectedFragment {
         let original = input.to_string();
         if input.is_empty() {
             return ProtectedFragment {
@@ -369,6 +564,7 @@ impl Protector {
                 map: TokenMap {
                     content_hash: compute_hash(input),
                     tokens: Vec::new(),
+                    scheme,
                 },
             };
         }
@@ -377,6 +573,15 @@ impl Protector {
         let mut tokens = Vec::new();
 
         // Collect tokens in priority order (more specific patterns first)
+        // Content Patcher tokens before the {{ }} escapes they contain
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::ContentPatcher,
+            &CONTENT_PATCHER_REGEX,
+        );
+
         // Escapes first (must be protected before their patterns)
         collect_tokens(
             &mut tokens,
@@ -409,7 +614,14 @@ impl Protector {
             &MUSTACHE_REGEX,
         );
         
-        // Game-specific tokens
+        // Game-specific tokens; plural forms before the __1__ macro inside them
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::FactorioPlural,
+            &FACTORIO_PLURAL_REGEX,
+        );
         collect_tokens(
             &mut tokens,
             &mut occupied,
@@ -438,6 +650,35 @@ impl Protector {
             TokenClass::MinecraftColor,
             &MINECRAFT_COLOR_REGEX,
         );
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::ParadoxColor,
+            &PARADOX_COLOR_REGEX,
+        );
+        // Paradox localisation, before shell variables and Ren'Py interpolation
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::ParadoxScripted,
+            &PARADOX_SCRIPTED_REGEX,
+        );
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::ParadoxVariable,
+            &PARADOX_VARIABLE_REGEX,
+        );
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::ParadoxIcon,
+            &PARADOX_ICON_REGEX,
+        );
         
         // Markup tags (specific to general)
         collect_tokens(
@@ -519,6 +760,32 @@ impl Protector {
             &SHELL_VAR_REGEX,
         );
         
+        // Minecraft selectors, before Ren'Py interpolation claims @e[...]'s brackets
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::MinecraftSelector,
+            &MINECRAFT_SELECTOR_REGEX,
+        );
+        
+        // Ren'Py interpolation and text tags, after BBCode and named braces
+        // so [b] and {name} keep their existing classes
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::RenpyInterp,
+            &RENPY_INTERP_REGEX,
+        );
+        collect_tokens(
+            &mut tokens,
+            &mut occupied,
+            input,
+            TokenClass::RenpyTag,
+            &RENPY_TAG_REGEX,
+        );
+        
         // Math/numerical patterns - only in Full mode
         // In Minimal/CodeOnly mode, let the LLM handle numbers naturally
         if mode == ProtectionMode::Full {
@@ -577,6 +844,17 @@ impl Protector {
             );
         }
         
+        // Game profile patterns - in every mode, like other game-specific tokens
+        for pattern in profile_patterns {
+            collect_tokens(
+                &mut tokens,
+                &mut occupied,
+                input,
+                TokenClass::ProfilePattern,
+                pattern,
+            );
+        }
+
         // Legacy patterns (low priority) - only in Full mode
         if mode == ProtectionMode::Full {
             collect_tokens(
@@ -599,7 +877,7 @@ impl Protector {
 
         for (index, token) in tokens.iter_mut().enumerate() {
             token.id = format!("T{:04}", index);
-            token.marker = format!("⟦MT:{}:{}⟧", token.kind.code(), index);
+            token.marker = scheme.marker(token.kind.code(), index);
         }
 
         let mut masked = String::with_capacity(input.len());
@@ -622,6 +900,7 @@ impl Protector {
             map: TokenMap {
                 content_hash: compute_hash(input),
                 tokens,
+                scheme,
             },
         }
     }
@@ -655,7 +934,7 @@ impl ProtectedFragment {
         let mut unknown_markers = Vec::new();
         let mut output = String::with_capacity(translated.len());
         let mut cursor = 0usize;
-        for capture in MARKER_REGEX.captures_iter(translated) {
+        for capture in self.map.scheme.regex().captures_iter(translated) {
             let matched = capture.get(0).expect("match");
             let marker = matched.as_str();
             let start = matched.start();
@@ -694,6 +973,24 @@ impl ProtectedFragment {
 
         Ok(output)
     }
+
+    /// Replaces the markers of this fragment that are still in `text`, e.g.
+    /// ones reinjected into an already restored translation. Unlike
+    /// [`restore`](Self::restore) nothing has to be present; markers of other
+    /// fragments are kept.
+    pub fn restore_remaining<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.map
+            .scheme
+            .regex()
+            .replace_all(text, |captures: &regex::Captures| {
+                let marker = &captures[0];
+                self.map
+                    .tokens
+                    .iter()
+                    .find(|token| token.marker == marker)
+                    .map_or_else(|| marker.to_string(), |token| token.value.clone())
+            })
+    }
 }
 
 fn compute_hash(input: &str) -> String {
@@ -795,6 +1092,169 @@ mod tests {
         assert_eq!(restored, input);
     }
     
+    #[test]
+    fn test_paradox_color_codes() {
+        let input = "§YGold§! and §Hhighlight§!";
+        let fragment = Protector::protect(input);
+        
+        // §Y, §H and both §! closers are protected
+        assert_eq!(fragment.token_map().tokens.len(), 4);
+        assert!(fragment.masked_text().contains("⟦MT:PDXCOLOR:"));
+        assert!(!fragment.masked_text().contains('§'));
+        
+        let restored = fragment.restore(fragment.masked_text()).unwrap();
+        assert_eq!(restored, input);
+    }
+    
+    #[test]
+    fn test_minecraft_selectors() {
+        let input = "Give @p[limit=1] a hug, @a! Mail me at dev@pack.net";
+        let fragment = Protector::protect(input);
+        
+        let selectors: Vec<&str> = fragment
+            .token_map()
+            .tokens
+            .iter()
+            .filter(|token| token.kind == TokenClass::MinecraftSelector)
+            .map(|token| token.value.as_str())
+            .collect();
+        assert_eq!(selectors, vec!["@p[limit=1]", "@a"]);
+        
+        let restored = fragment.restore(fragment.masked_text()).unwrap();
+        assert_eq!(restored, input);
+    }
+    
+    #[test]
+    fn test_factorio_plural_forms() {
+        let input = "Found __1__ __plural_for_parameter__1__{1=ore patch|rest=ore patches}__";
+        let fragment = Protector::protect(input);
+        
+        let kinds: Vec<TokenClass> = fragment.token_map().tokens.iter().map(|token| token.kind).collect();
+        assert_eq!(kinds, vec![TokenClass::FactorioMacro, TokenClass::FactorioPlural]);
+        assert!(fragment.masked_text().starts_with("Found ⟦MT:FACTORIO:0⟧ ⟦MT:FPLURAL:1⟧"));
+        
+        let restored = fragment.restore(fragment.masked_text()).unwrap();
+        assert_eq!(restored, input);
+    }
+    
+    #[test]
+    fn test_paradox_scripted_localisation() {
+        let input = "[Root.GetName] spends $COST|Y$ £energy£ on [GetDateText], §Ynow§!";
+        let fragment = Protector::protect(input);
+        
+        let tokens: Vec<(TokenClass, &str)> = fragment
+            .token_map()
+            .tokens
+            .iter()
+            .map(|token| (token.kind, token.value.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenClass::ParadoxScripted, "[Root.GetName]"),
+                (TokenClass::ParadoxVariable, "$COST|Y$"),
+                (TokenClass::ParadoxIcon, "£energy£"),
+                (TokenClass::ParadoxScripted, "[GetDateText]"),
+                (TokenClass::ParadoxColor, "§Y"),
+                (TokenClass::ParadoxColor, "§!"),
+            ]
+        );
+        
+        // Ren'Py variables keep their own class
+        let renpy = Protector::protect("[player_name] waves");
+        assert_eq!(renpy.token_map().tokens[0].kind, TokenClass::RenpyInterp);
+    }
+    
+    #[test]
+    fn test_profile_patterns() {
+        let patterns = vec![Regex::new(r"#[A-Z]+#").unwrap()];
+        let input = "Deal #DMG# damage to {0}";
+        let fragment = Protector::protect_with_patterns(input, ProtectionMode::CodeOnly, &patterns);
+
+        let tokens: Vec<(TokenClass, &str)> = fragment
+            .token_map()
+            .tokens
+            .iter()
+            .map(|token| (token.kind, token.value.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenClass::ProfilePattern, "#DMG#"),
+                (TokenClass::DotnetBrace, "{0}"),
+            ]
+        );
+        assert!(fragment.masked_text().contains("⟦MT:PROFILE:0⟧"));
+        assert_eq!(fragment.restore(fragment.masked_text()).unwrap(), input);
+    }
+
+    #[test]
+    fn test_content_patcher_tokens() {
+        let input = "{{i18n: item.desc |name={{PlayerName}}}} bought {{Count}} for {{ }}";
+        let fragment = Protector::protect(input);
+        let tokens: Vec<(TokenClass, &str)> = fragment
+            .token_map()
+            .tokens
+            .iter()
+            .map(|token| (token.kind, token.value.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenClass::ContentPatcher, "{{i18n: item.desc |name={{PlayerName}}}}"),
+                (TokenClass::ContentPatcher, "{{Count}}"),
+                (TokenClass::EscapedBrace, "{{"),
+                (TokenClass::EscapedBrace, "}}"),
+            ]
+        );
+        assert!(fragment.masked_text().starts_with("⟦MT:CPTOKEN:0⟧ bought"));
+        assert_eq!(fragment.restore(fragment.masked_text()).unwrap(), input);
+    }
+
+    #[test]
+    fn test_marker_schemes() {
+        assert_eq!(MarkerScheme::negotiate(["Plain {0}"]), MarkerScheme::WhiteSquare);
+        assert_eq!(
+            MarkerScheme::negotiate(["Uses ⟦brackets⟧", "and ⟪these⟫"]),
+            MarkerScheme::WhiteCurly
+        );
+        assert_eq!(
+            MarkerScheme::negotiate(["⟦ ⟪ ⦃ [[link]]"]),
+            MarkerScheme::WhiteSquare
+        );
+
+        let input = "Literal ⟦MT:TAG:0⟧ then {0}";
+        let fragment = Protector::protect_with_scheme(
+            input,
+            ProtectionMode::Full,
+            &[],
+            MarkerScheme::Ascii,
+        );
+        assert_eq!(fragment.token_map().scheme, MarkerScheme::Ascii);
+        assert_eq!(fragment.masked_text(), "Literal ⟦MT:TAG:0⟧ then [[MT:DOTNET:0]]");
+        assert_eq!(fragment.restore(fragment.masked_text()).unwrap(), input);
+
+        let scheme = MarkerScheme::DoubleAngle;
+        let canonical = scheme.to_canonical("a ⟪MT:TAG:0⟫ b");
+        assert_eq!(canonical, "a ⟦MT:TAG:0⟧ b");
+        assert_eq!(scheme.from_canonical(&canonical), "a ⟪MT:TAG:0⟫ b");
+        assert!(!Protector::has_translatable_text("[[MT:TAG:0]] ⦃MT:NAMED:1⦄"));
+    }
+    
+    #[test]
+    fn test_renpy_interpolation_and_text_tags() {
+        let input = "[player_name], {color=#f00}run{/color} to [places[0]!t]!";
+        let fragment = Protector::protect(input);
+        
+        assert_eq!(fragment.token_map().tokens.len(), 4);
+        assert!(fragment.masked_text().contains("⟦MT:RPYVAR:"));
+        assert!(fragment.masked_text().contains("⟦MT:RPYTAG:"));
+        assert!(fragment.masked_text().contains("run"));
+        
+        let restored = fragment.restore(fragment.masked_text()).unwrap();
+        assert_eq!(restored, input);
+    }
+    
     #[test]
     fn test_rimworld_color_tags() {
         let input = "<color=#ff0000>Red text</color>";
@@ -0,0 +1,167 @@
+/// Provenance comments for generated translation files.
+///
+/// When enabled, a short header naming the tool version, generation date and
+/// model is written at the top of output files whose format has a comment
+/// syntax, so downstream users can tell the file was machine-translated.
+/// It is switched on per job (`embedProvenance`) or per saved project.
+use crate::formats::FileFormat;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+const TOOL_NAME: &str = "mod-translator";
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenanceInfo {
+    pub provider: String,
+    pub model_id: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ProvenanceInfo {
+    fn lines(&self) -> [String; 2] {
+        [
+            format!(
+                "Machine-translated by {TOOL_NAME} {TOOL_VERSION} on {}",
+                self.generated_at.format("%Y-%m-%d")
+            ),
+            format!(
+                "Model: {}/{} ({} -> {})",
+                self.provider, self.model_id, self.source_lang, self.target_lang
+            ),
+        ]
+    }
+}
+
+/// Renders the provenance header for `format`, or `None` when the format has
+/// no comment syntax (JSON, CSV, plain text, Markdown).
+pub fn render_provenance_header(format: FileFormat, info: &ProvenanceInfo) -> Option<String> {
+    let [first, second] = info.lines();
+    let header = match format {
+        FileFormat::Xml | FileFormat::Resx => format!(
+            "<!-- {} -->\n<!-- {} -->\n",
+            sanitize_xml(&first),
+            sanitize_xml(&second)
+        ),
+        FileFormat::Po
+        | FileFormat::Properties
+        | FileFormat::Yaml
+        | FileFormat::RenPy
+        | FileFormat::Toml
+        | FileFormat::McFunction => {
+            format!("# {first}\n# {second}\n")
+        }
+        FileFormat::Ini | FileFormat::Cfg => format!("; {first}\n; {second}\n"),
+        FileFormat::Lua => format!("-- {first}\n-- {second}\n"),
+        FileFormat::Json
+        | FileFormat::Csv
+        | FileFormat::Txt
+        | FileFormat::Markdown
+        // Paradox games expect the language header on the first line
+        | FileFormat::Paradox
+        | FileFormat::UnityAsset
+        | FileFormat::Snbt
+        | FileFormat::FtbQuests
+        | FileFormat::BetterQuesting
+        | FileFormat::Bethesda
+        | FileFormat::Mo
+        | FileFormat::UnityBundle
+        | FileFormat::Unknown => return None,
+    };
+    Some(header)
+}
+
+/// Convenience wrapper that picks the format from the output path.
+pub fn render_provenance_header_for_path(path: &Path, info: &ProvenanceInfo) -> Option<String> {
+    render_provenance_header(FileFormat::from_path(path), info)
+}
+
+/// Inserts `header` at the top of `contents`, after a UTF-8 BOM and, for XML,
+/// after the `<?xml ...?>` declaration which must stay first in the file.
+pub fn prepend_provenance(contents: &str, header: &str) -> String {
+    let (bom, body) = match contents.strip_prefix('\u{feff}') {
+        Some(rest) => ("\u{feff}", rest),
+        None => ("", contents),
+    };
+
+    let split_at = if body.starts_with("<?xml") {
+        body.find("?>").map(|end| {
+            let after = end + 2;
+            let rest = &body[after..];
+            after + rest.len() - rest.trim_start_matches(['\r', '\n']).len()
+        })
+    } else {
+        None
+    };
+
+    match split_at {
+        Some(index) => {
+            let (declaration, rest) = body.split_at(index);
+            let separator = if declaration.ends_with('\n') { "" } else { "\n" };
+            format!("{bom}{declaration}{separator}{header}{rest}")
+        }
+        None => format!("{bom}{header}{body}"),
+    }
+}
+
+/// XML comments may not contain `--`.
+fn sanitize_xml(text: &str) -> String {
+    text.replace("--", "- -")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn info() -> ProvenanceInfo {
+        ProvenanceInfo {
+            provider: "Gemini".into(),
+            model_id: "gemini-2.5-flash".into(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            generated_at: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn renders_format_specific_comments() {
+        let po = render_provenance_header(FileFormat::Po, &info()).unwrap();
+        assert!(po.starts_with("# Machine-translated by mod-translator"));
+        assert!(po.contains("2026-03-01"));
+        assert!(po.contains("Gemini/gemini-2.5-flash (en -> ko)"));
+
+        let xml = render_provenance_header(FileFormat::Xml, &info()).unwrap();
+        assert!(xml.lines().all(|line| line.starts_with("<!-- ") && line.ends_with(" -->")));
+
+        let mut dashed = info();
+        dashed.model_id = "custom--model".into();
+        let xml = render_provenance_header(FileFormat::Xml, &dashed).unwrap();
+        assert!(xml.contains("custom- -model"));
+
+        assert!(render_provenance_header(FileFormat::Json, &info()).is_none());
+    }
+
+    #[test]
+    fn header_goes_after_xml_declaration_and_bom() {
+        let header = "<!-- generated -->\n";
+        let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<LanguageData />\n";
+        assert_eq!(
+            prepend_provenance(xml, header),
+            "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!-- generated -->\n<LanguageData />\n"
+        );
+
+        let inline = "<?xml version=\"1.0\"?><root/>";
+        assert_eq!(
+            prepend_provenance(inline, header),
+            "<?xml version=\"1.0\"?>\n<!-- generated -->\n<root/>"
+        );
+    }
+
+    #[test]
+    fn header_goes_first_without_declaration() {
+        assert_eq!(prepend_provenance("key=값\n", "# generated\n"), "# generated\nkey=값\n");
+    }
+}
@@ -0,0 +1,461 @@
+/// Quality report written at the end of a translation job.
+///
+/// Placeholder validation failures, QC warnings, rolled-back segments and
+/// lines that kept their source text otherwise only pass by in the progress
+/// log. The report gathers them per job as `qc-report.json` plus a Markdown
+/// rendering in the hidden review directory of every output root, and a copy
+/// is kept in the app data directory for [`get_job_qc_report`].
+use crate::backup::write_atomic;
+use crate::paths::app_data_dir;
+use crate::placeholder_validator::ValidationFailureReport;
+use crate::review::{ReviewMetadata, SegmentReviewStatus, REVIEW_DIR_NAME};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const QC_REPORT_FILE_NAME: &str = "qc-report.json";
+pub const QC_REPORT_MARKDOWN_FILE_NAME: &str = "qc-report.md";
+const QC_REPORT_DIR_NAME: &str = "qc-reports";
+
+/// A segment listed in the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcSegment {
+    pub file_path: String,
+    pub line_number: usize,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    pub status: SegmentReviewStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+    /// Quality estimation score (0–100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+}
+
+/// A file that could not be read, translated or written as a whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcFileError {
+    pub file_path: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcSummary {
+    pub total_segments: usize,
+    /// Translated segments, including recovered ones
+    pub translated: usize,
+    pub recovered: usize,
+    pub warnings: usize,
+    /// Translated segments scored below the quality estimation threshold
+    #[serde(default)]
+    pub low_confidence: usize,
+    pub rolled_back: usize,
+    /// Segments held for review or whose request failed
+    pub kept_original: usize,
+    pub validation_failures: usize,
+    pub file_errors: usize,
+    /// Segments skipped because the source is already in the target language
+    #[serde(default)]
+    pub already_translated: usize,
+    /// Segments skipped because they are on a do-not-translate list
+    #[serde(default)]
+    pub do_not_translate: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcReport {
+    #[serde(flatten)]
+    pub metadata: ReviewMetadata,
+    pub generated_at: DateTime<Utc>,
+    pub summary: QcSummary,
+    pub validation_failures: Vec<ValidationFailureReport>,
+    /// Applied translations that came with QC warnings, e.g. a tone mismatch
+    pub warnings: Vec<QcSegment>,
+    /// Applied translations scored below the quality estimation threshold,
+    /// lowest first
+    #[serde(default)]
+    pub low_confidence: Vec<QcSegment>,
+    pub rolled_back: Vec<QcSegment>,
+    pub kept_original: Vec<QcSegment>,
+    pub file_errors: Vec<QcFileError>,
+    #[serde(default)]
+    pub already_translated: Vec<QcSegment>,
+    #[serde(default)]
+    pub do_not_translate: Vec<QcSegment>,
+}
+
+impl QcReport {
+    /// Sorts the job's segments into the report sections. Translations
+    /// scored below `low_confidence_below` are listed as low confidence
+    /// instead of as warnings.
+    pub fn new(
+        metadata: ReviewMetadata,
+        segments: Vec<QcSegment>,
+        validation_failures: Vec<ValidationFailureReport>,
+        file_errors: Vec<QcFileError>,
+        low_confidence_below: Option<u8>,
+    ) -> Self {
+        let mut summary = QcSummary {
+            total_segments: segments.len(),
+            validation_failures: validation_failures.len(),
+            file_errors: file_errors.len(),
+            ..QcSummary::default()
+        };
+        let mut warnings = Vec::new();
+        let mut low_confidence = Vec::new();
+        let mut rolled_back = Vec::new();
+        let mut kept_original = Vec::new();
+        let mut already_translated = Vec::new();
+        let mut do_not_translate = Vec::new();
+        for segment in segments {
+            match segment.status {
+                SegmentReviewStatus::Translated | SegmentReviewStatus::Recovered => {
+                    summary.translated += 1;
+                    if segment.status == SegmentReviewStatus::Recovered {
+                        summary.recovered += 1;
+                    }
+                    let is_low = matches!(
+                        (segment.confidence, low_confidence_below),
+                        (Some(score), Some(threshold)) if score < threshold
+                    );
+                    if is_low {
+                        low_confidence.push(segment);
+                    } else if !segment.messages.is_empty() {
+                        warnings.push(segment);
+                    }
+                }
+                SegmentReviewStatus::RolledBack => rolled_back.push(segment),
+                SegmentReviewStatus::HeldForReview | SegmentReviewStatus::Failed => {
+                    kept_original.push(segment)
+                }
+                SegmentReviewStatus::AlreadyTranslated => already_translated.push(segment),
+                SegmentReviewStatus::DoNotTranslate => do_not_translate.push(segment),
+                SegmentReviewStatus::Untranslated | SegmentReviewStatus::Invalid => {}
+            }
+        }
+        low_confidence.sort_by_key(|segment| segment.confidence);
+        summary.warnings = warnings.len();
+        summary.low_confidence = low_confidence.len();
+        summary.rolled_back = rolled_back.len();
+        summary.kept_original = kept_original.len();
+        summary.already_translated = already_translated.len();
+        summary.do_not_translate = do_not_translate.len();
+        Self {
+            metadata,
+            generated_at: Utc::now(),
+            summary,
+            validation_failures,
+            warnings,
+            low_confidence,
+            rolled_back,
+            kept_original,
+            file_errors,
+            already_translated,
+            do_not_translate,
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.validation_failures.is_empty()
+            && self.warnings.is_empty()
+            && self.low_confidence.is_empty()
+            && self.rolled_back.is_empty()
+            && self.kept_original.is_empty()
+            && self.file_errors.is_empty()
+    }
+
+    /// Human-readable rendering of the report
+    pub fn to_markdown(&self) -> String {
+        let summary = &self.summary;
+        let mut out = String::new();
+        let _ = writeln!(out, "# QC 보고서: {}", self.metadata.job_id);
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "- 번역기: {} ({})",
+            self.metadata.provider, self.metadata.model_id
+        );
+        let _ = writeln!(
+            out,
+            "- 언어: {} → {}",
+            self.metadata.source_lang, self.metadata.target_lang
+        );
+        let _ = writeln!(out, "- 작성 시각: {}", self.generated_at.to_rfc3339());
+        let _ = writeln!(out);
+        let _ = writeln!(out, "| 항목 | 건수 |");
+        let _ = writeln!(out, "| --- | ---: |");
+        for (label, count) in [
+            ("전체 세그먼트", summary.total_segments),
+            ("번역됨", summary.translated),
+            ("자동 복구", summary.recovered),
+            ("QC 경고", summary.warnings),
+            ("신뢰도 낮음", summary.low_confidence),
+            ("롤백", summary.rolled_back),
+            ("원문 유지", summary.kept_original),
+            ("자리표시자 검증 실패", summary.validation_failures),
+            ("파일 오류", summary.file_errors),
+            ("이미 대상 언어 (건너뜀)", summary.already_translated),
+            ("번역 제외 목록 (건너뜀)", summary.do_not_translate),
+        ] {
+            let _ = writeln!(out, "| {label} | {count} |");
+        }
+
+        if !self.file_errors.is_empty() {
+            let _ = writeln!(out, "\n## 파일 오류\n");
+            for error in &self.file_errors {
+                let code = error.code.as_deref().unwrap_or("-");
+                let _ = writeln!(out, "- `{}` ({}): {}", error.file_path, code, error.message);
+            }
+        }
+        if !self.validation_failures.is_empty() {
+            let _ = writeln!(out, "\n## 자리표시자 검증 실패\n");
+            for failure in &self.validation_failures {
+                let _ = writeln!(
+                    out,
+                    "- `{}:{}` {:?}\n  - 원문: {}\n  - 번역: {}",
+                    failure.file,
+                    failure.line,
+                    failure.code,
+                    inline(&failure.source_line),
+                    inline(&failure.candidate_line)
+                );
+            }
+        }
+        for (title, segments) in [
+            ("롤백된 세그먼트", &self.rolled_back),
+            ("원문을 유지한 세그먼트", &self.kept_original),
+            ("신뢰도 낮은 번역", &self.low_confidence),
+            ("QC 경고", &self.warnings),
+            ("이미 대상 언어라 건너뛴 세그먼트", &self.already_translated),
+            ("번역 제외 목록에 있어 건너뛴 세그먼트", &self.do_not_translate),
+        ] {
+            if segments.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "\n## {title}\n");
+            for segment in segments {
+                let _ = writeln!(
+                    out,
+                    "- `{}:{}` {}",
+                    segment.file_path,
+                    segment.line_number,
+                    inline(&segment.source)
+                );
+                if let Some(confidence) = segment.confidence {
+                    let translation = segment.translation.as_deref().unwrap_or_default();
+                    let _ = writeln!(out, "  - 신뢰도 {confidence}: {}", inline(translation));
+                }
+                for message in &segment.messages {
+                    let _ = writeln!(out, "  - {}", inline(message));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Text shown in a Markdown list item: one line, as code
+fn inline(text: &str) -> String {
+    format!("`{}`", text.replace('`', "'").replace(['\r', '\n'], " "))
+}
+
+/// Writes `qc-report.json` and `qc-report.md` to `dir`
+pub fn write_qc_report(dir: &Path, report: &QcReport) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let contents = serde_json::to_string_pretty(report)?;
+    write_atomic(&dir.join(QC_REPORT_FILE_NAME), contents.as_bytes())?;
+    write_atomic(
+        &dir.join(QC_REPORT_MARKDOWN_FILE_NAME),
+        report.to_markdown().as_bytes(),
+    )
+}
+
+/// Directory of the report of an output root: its hidden review directory
+pub fn qc_report_dir(output_root: &Path) -> PathBuf {
+    output_root.join(REVIEW_DIR_NAME)
+}
+
+/// Copy of a job's report kept for [`get_job_qc_report`]
+pub fn stored_qc_report_path(job_id: &str) -> PathBuf {
+    stored_job_file(job_id, "json")
+}
+
+/// File of a job in the app data report directory, named after the job
+pub fn stored_job_file(job_id: &str, extension: &str) -> PathBuf {
+    let file_stem: String = job_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    app_data_dir()
+        .join(QC_REPORT_DIR_NAME)
+        .join(format!("{file_stem}.{extension}"))
+}
+
+/// Writes the report next to the outputs in every root and keeps the app
+/// data copy. Returns the number of roots written.
+pub fn save_qc_report(report: &QcReport, output_roots: &[PathBuf]) -> usize {
+    let stored = stored_qc_report_path(&report.metadata.job_id);
+    let stored_result = serde_json::to_string_pretty(report)
+        .map_err(io::Error::from)
+        .and_then(|contents| {
+            if let Some(parent) = stored.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_atomic(&stored, contents.as_bytes())
+        });
+    if let Err(error) = stored_result {
+        log::warn!("failed to keep QC report {}: {error}", stored.display());
+    }
+
+    let mut written = 0;
+    for root in output_roots {
+        let dir = qc_report_dir(root);
+        match write_qc_report(&dir, report) {
+            Ok(()) => written += 1,
+            Err(error) => log::warn!("failed to write QC report to {}: {error}", dir.display()),
+        }
+    }
+    written
+}
+
+pub fn read_qc_report(path: &Path) -> io::Result<QcReport> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(io::Error::from)
+}
+
+/// Tauri command: the QC report of a finished job
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn get_job_qc_report(jobId: String) -> Result<QcReport, String> {
+    let path = stored_qc_report_path(&jobId);
+    read_qc_report(&path).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => format!("작업 {jobId}의 QC 보고서가 없습니다."),
+        _ => format!(
+            "QC 보고서를 읽을 수 없습니다 ({}): {}",
+            path.display(),
+            error
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(line_number: usize, status: SegmentReviewStatus, messages: &[&str]) -> QcSegment {
+        QcSegment {
+            file_path: "Languages/Korean/Keyed/Menu.xml".into(),
+            line_number,
+            source: format!("<Line{line_number}>Text</Line{line_number}>"),
+            translation: None,
+            status,
+            messages: messages.iter().map(|message| message.to_string()).collect(),
+            confidence: None,
+        }
+    }
+
+    fn metadata() -> ReviewMetadata {
+        ReviewMetadata {
+            job_id: "job-1".into(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            provider: "Gemini".into(),
+            model_id: "gemini-2.5-flash".into(),
+        }
+    }
+
+    #[test]
+    fn segments_are_sorted_into_sections() {
+        let report = QcReport::new(
+            metadata(),
+            vec![
+                segment(1, SegmentReviewStatus::Translated, &[]),
+                segment(2, SegmentReviewStatus::Translated, &["tone mismatch"]),
+                QcSegment {
+                    confidence: Some(42),
+                    ..segment(3, SegmentReviewStatus::Recovered, &["length ratio"])
+                },
+                segment(
+                    4,
+                    SegmentReviewStatus::RolledBack,
+                    &["Placeholder validation failed"],
+                ),
+                segment(5, SegmentReviewStatus::Failed, &["timeout"]),
+                segment(6, SegmentReviewStatus::Untranslated, &[]),
+                segment(7, SegmentReviewStatus::AlreadyTranslated, &[]),
+                segment(8, SegmentReviewStatus::DoNotTranslate, &["키 ModVersion"]),
+            ],
+            Vec::new(),
+            vec![QcFileError {
+                file_path: "Languages/Korean/Keyed/Broken.xml".into(),
+                message: "no longer parses".into(),
+                code: Some("FORMAT_INVALID".into()),
+            }],
+            Some(60),
+        );
+
+        assert_eq!(
+            report.summary,
+            QcSummary {
+                total_segments: 8,
+                translated: 3,
+                recovered: 1,
+                warnings: 1,
+                low_confidence: 1,
+                rolled_back: 1,
+                kept_original: 1,
+                validation_failures: 0,
+                file_errors: 1,
+                already_translated: 1,
+                do_not_translate: 1,
+            }
+        );
+        assert_eq!(report.warnings[0].line_number, 2);
+        assert_eq!(report.low_confidence[0].line_number, 3);
+        assert_eq!(report.already_translated[0].line_number, 7);
+        assert_eq!(report.do_not_translate[0].line_number, 8);
+        assert!(!report.is_clean());
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| 롤백 | 1 |"));
+        assert!(markdown.contains("## 신뢰도 낮은 번역"));
+        assert!(markdown.contains("## 파일 오류"));
+        assert!(markdown.contains("`Languages/Korean/Keyed/Menu.xml:5` `<Line5>Text</Line5>`"));
+        assert!(!markdown.contains("## 자리표시자 검증 실패"));
+    }
+
+    #[test]
+    fn reports_are_written_as_json_and_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = QcReport::new(
+            metadata(),
+            vec![segment(1, SegmentReviewStatus::RolledBack, &[])],
+            Vec::new(),
+            Vec::new(),
+            None,
+        );
+        write_qc_report(&qc_report_dir(dir.path()), &report).unwrap();
+
+        let review_dir = dir.path().join(REVIEW_DIR_NAME);
+        let read = read_qc_report(&review_dir.join(QC_REPORT_FILE_NAME)).unwrap();
+        assert_eq!(read.metadata.job_id, "job-1");
+        assert_eq!(read.rolled_back.len(), 1);
+        assert!(review_dir.join(QC_REPORT_MARKDOWN_FILE_NAME).exists());
+    }
+}
@@ -1,6 +1,7 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(\{\w+\}|\{\d+\}|%\d*\$?[sd]|%s|%d|\$[A-Z0-9_]+\$|\{Pawn_[^}]+\}|\$\{[^}]+\})")
@@ -23,11 +24,60 @@ impl SegmentValidationResult {
     }
 }
 
+/// Closing punctuation that must not start a line (kinsoku) in Japanese and
+/// Chinese text
+const CJK_NO_LEADING: &str = "、。，．・：；？！ー）〕］｝〉》」』】〙〗〟’”…‥),.:;!?]}";
+/// Closing punctuation that must not start a line in Korean text
+const KOREAN_NO_LEADING: &str = "),.:;!?]}」』’”…";
+
+/// Target scripts with their own line-break and punctuation conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CjkScript {
+    Korean,
+    Japanese,
+    Chinese,
+}
+
+impl CjkScript {
+    /// Script of a target language code (`ko`, `ja-JP`, `zh_TW`)
+    pub fn for_language(code: &str) -> Option<Self> {
+        let base = code
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(code)
+            .trim()
+            .to_ascii_lowercase();
+        match base.as_str() {
+            "ko" => Some(Self::Korean),
+            "ja" => Some(Self::Japanese),
+            "zh" => Some(Self::Chinese),
+            _ => None,
+        }
+    }
+
+    fn forbidden_leading(self) -> &'static str {
+        match self {
+            Self::Korean => KOREAN_NO_LEADING,
+            Self::Japanese | Self::Chinese => CJK_NO_LEADING,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SegmentLimits {
     pub warn_ratio: f32,
     pub max_ratio: f32,
     pub max_length: Option<usize>,
+    /// Widest line a translation may have, counting full-width characters as
+    /// two columns (UI strings with a fixed box)
+    pub max_visual_width: Option<usize>,
+    /// Script of the target language, for the rules below
+    pub cjk: Option<CjkScript>,
+    /// Warn when a line of the translation starts with closing punctuation
+    pub forbid_leading_punctuation: bool,
+    /// Use the target script's punctuation forms: full-width after Japanese
+    /// and Chinese text, half-width in Korean
+    pub normalize_punctuation: bool,
 }
 
 impl Default for SegmentLimits {
@@ -36,10 +86,55 @@ impl Default for SegmentLimits {
             warn_ratio: 1.8,
             max_ratio: 3.0,
             max_length: None,
+            max_visual_width: None,
+            cjk: None,
+            forbid_leading_punctuation: false,
+            normalize_punctuation: false,
         }
     }
 }
 
+impl SegmentLimits {
+    /// Default limits with the line-break and punctuation rules of
+    /// `target_lang` turned on when it is Korean, Japanese or Chinese
+    pub fn for_target(target_lang: &str) -> Self {
+        let cjk = CjkScript::for_language(target_lang);
+        Self {
+            cjk,
+            forbid_leading_punctuation: cjk.is_some(),
+            normalize_punctuation: cjk.is_some(),
+            ..Self::default()
+        }
+    }
+
+    /// These limits with a game profile's overrides
+    pub fn with_rules(mut self, rules: &QcRules) -> Self {
+        if rules.max_visual_width.is_some() {
+            self.max_visual_width = rules.max_visual_width;
+        }
+        if let Some(forbid) = rules.forbid_leading_punctuation {
+            self.forbid_leading_punctuation = forbid;
+        }
+        if let Some(normalize) = rules.normalize_punctuation {
+            self.normalize_punctuation = normalize;
+        }
+        self
+    }
+}
+
+/// QC rules a game profile overrides; unset fields keep the target
+/// language's defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QcRules {
+    #[serde(default)]
+    pub max_visual_width: Option<usize>,
+    #[serde(default)]
+    pub forbid_leading_punctuation: Option<bool>,
+    #[serde(default)]
+    pub normalize_punctuation: Option<bool>,
+}
+
 pub fn validate_segment(
     source: &str,
     candidate: &str,
@@ -68,6 +163,33 @@ pub fn validate_segment(
         }
     }
 
+    if let Some(max_width) = limits.max_visual_width {
+        let widest = display_lines(candidate)
+            .map(visual_width)
+            .max()
+            .unwrap_or(0);
+        if widest > max_width {
+            warnings.push(format!(
+                "번역 결과 한 줄의 표시 폭({})이 허용된 폭({})을 넘습니다.",
+                widest, max_width
+            ));
+        }
+    }
+
+    if let (Some(cjk), true) = (limits.cjk, limits.forbid_leading_punctuation) {
+        let leading: Vec<char> = display_lines(candidate)
+            .skip(1)
+            .filter_map(|line| line.trim_start().chars().next())
+            .filter(|first| cjk.forbidden_leading().contains(*first))
+            .collect();
+        if !leading.is_empty() {
+            warnings.push(format!(
+                "줄 첫머리에 올 수 없는 문장 부호로 시작하는 줄이 있습니다 ({}).",
+                leading.iter().collect::<String>()
+            ));
+        }
+    }
+
     if length_ratio > limits.max_ratio {
         errors.push(format!(
             "번역 결과가 원문 대비 너무 깁니다 (비율 {:.2}).",
@@ -89,6 +211,112 @@ pub fn validate_segment(
     }
 }
 
+/// `text` with its punctuation in the forms of the target script. Only marks
+/// right after Japanese or Chinese text become full-width, so numbers,
+/// placeholders and Latin words keep theirs.
+pub fn normalize_punctuation<'a>(text: &'a str, limits: &SegmentLimits) -> Cow<'a, str> {
+    let Some(cjk) = limits.cjk.filter(|_| limits.normalize_punctuation) else {
+        return Cow::Borrowed(text);
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut changed = false;
+    for (index, &current) in chars.iter().enumerate() {
+        let replacement = match cjk {
+            CjkScript::Korean => half_width_punctuation(current),
+            CjkScript::Japanese | CjkScript::Chinese => {
+                let after_cjk = index > 0 && is_ideographic(chars[index - 1]);
+                let next = chars.get(index + 1).copied();
+                // `1.5`, `...` and `?!` runs stay as they are
+                let standalone = next.is_none_or(|next| {
+                    !next.is_ascii_alphanumeric() && !next.is_ascii_punctuation()
+                });
+                if after_cjk && standalone {
+                    full_width_punctuation(current, cjk)
+                } else {
+                    None
+                }
+            }
+        };
+        match replacement {
+            Some(replacement) => {
+                output.push(replacement);
+                changed = true;
+            }
+            None => output.push(current),
+        }
+    }
+    if changed {
+        Cow::Owned(output)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn full_width_punctuation(c: char, cjk: CjkScript) -> Option<char> {
+    Some(match (c, cjk) {
+        (',', CjkScript::Japanese) => '、',
+        (',', _) => '，',
+        ('.', _) => '。',
+        ('!', _) => '！',
+        ('?', _) => '？',
+        (':', _) => '：',
+        (';', _) => '；',
+        _ => return None,
+    })
+}
+
+fn half_width_punctuation(c: char) -> Option<char> {
+    Some(match c {
+        '、' | '，' => ',',
+        '。' | '．' => '.',
+        '！' => '!',
+        '？' => '?',
+        '：' => ':',
+        '；' => ';',
+        _ => return None,
+    })
+}
+
+/// Lines of a translation as displayed: split at line breaks and at `\n`
+/// escapes
+fn display_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\\n").flat_map(|part| part.split('\n'))
+}
+
+/// Columns `line` takes in a monospaced or CJK-aware font
+pub fn visual_width(line: &str) -> usize {
+    line.chars().map(|c| if is_wide(c) { 2 } else { 1 }).sum()
+}
+
+/// East Asian wide and full-width characters
+fn is_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x2FFFD
+            | 0x30000..=0x3FFFD
+    )
+}
+
+/// Kana and Han characters (not Hangul or punctuation)
+fn is_ideographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3041..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FFFD
+    )
+}
+
 fn compute_length_ratio(source: &str, candidate: &str) -> f32 {
     let source_len = source.chars().count().max(1) as f32;
     let candidate_len = candidate.chars().count() as f32;
@@ -151,11 +379,61 @@ mod tests {
         assert!(!result.pipe_parity_ok);
     }
 
+    #[test]
+    fn cjk_rules_flag_wide_lines_and_leading_punctuation() {
+        let limits = SegmentLimits::for_target("ja").with_rules(&QcRules {
+            max_visual_width: Some(12),
+            ..QcRules::default()
+        });
+        assert_eq!(visual_width("鋼の剣 x2"), 9);
+        let result = validate_segment("Steel sword\\nSharp", "鋼の剣です\\n。鋭い刃です", &limits);
+        assert!(result.is_pass());
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        let result = validate_segment("A very long label", "とても長いラベルの文字列", &limits);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("표시 폭")));
+
+        let off = limits.with_rules(&QcRules {
+            forbid_leading_punctuation: Some(false),
+            ..QcRules::default()
+        });
+        assert!(validate_segment("a\nb", "剣\n、盾", &off)
+            .warnings
+            .is_empty());
+    }
+
+    #[test]
+    fn punctuation_follows_the_target_script() {
+        let japanese = SegmentLimits::for_target("ja");
+        assert_eq!(
+            normalize_punctuation("剣を手に入れた! 価格は1.5倍, {0}です.", &japanese),
+            "剣を手に入れた！ 価格は1.5倍、 {0}です。"
+        );
+        let chinese = SegmentLimits::for_target("zh-CN");
+        assert_eq!(
+            normalize_punctuation("获得钢剑,攻击力+5.", &chinese),
+            "获得钢剑，攻击力+5."
+        );
+        let korean = SegmentLimits::for_target("ko");
+        assert_eq!(
+            normalize_punctuation("강철 검을 얻었다。", &korean),
+            "강철 검을 얻었다."
+        );
+        assert_eq!(
+            normalize_punctuation("Sword!", &SegmentLimits::default()),
+            "Sword!"
+        );
+    }
+
     #[test]
     fn detect_length_warning_and_error() {
-        let mut limits = SegmentLimits::default();
-        limits.max_ratio = 1.5; // Set lower ratio so 2x length fails
-        limits.warn_ratio = 1.2;
+        let limits = SegmentLimits {
+            max_ratio: 1.5, // Set lower ratio so 2x length fails
+            warn_ratio: 1.2,
+            ..SegmentLimits::default()
+        };
         // "abc" (3 chars) -> "abcdefgh" (8 chars) = ratio 2.67 > 1.5 max
         let result = validate_segment("abc", "abcdefgh", &limits);
         assert!(!result.is_pass());
@@ -0,0 +1,359 @@
+/// Quality estimation of finished translations.
+///
+/// Validation only tells whether a translation kept its tokens; it says
+/// nothing about whether the text reads as a translation at all. This pass
+/// gives every translated segment a 0–100 confidence score from length and
+/// script heuristics, optionally refined by back-translating a sample of the
+/// segments with a cheaper model and by a local COMET-style scoring server.
+/// Segments below the threshold are listed in the job's QC report.
+use crate::ai::{translate_text_with_context, TranslationError, TranslationSettings};
+use crate::protector::ProtectedFragment;
+use crate::quality::{validate_segment, SegmentLimits};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const DEFAULT_SAMPLE_RATE: f32 = 0.1;
+pub const DEFAULT_LOW_CONFIDENCE_THRESHOLD: u8 = 60;
+
+/// 품질 추정 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QualityEstimationConfig {
+    /// 번역이 끝난 뒤 세그먼트마다 신뢰도 점수를 매길지 여부
+    pub enabled: bool,
+    /// 역번역에 쓸 저렴한 모델 (없으면 역번역하지 않음)
+    pub back_translation_model: Option<String>,
+    /// 역번역할 세그먼트 비율 (0~1, 기본 0.1)
+    pub sample_rate: f32,
+    /// 이 점수보다 낮으면 QC 보고서에 신뢰도 낮음으로 표시 (기본 60)
+    pub low_confidence_threshold: u8,
+    /// 로컬 COMET 방식 채점 서버 주소. `{"src", "mt"}`를 POST하면
+    /// `{"score"}`(0~1)를 돌려줘야 함
+    pub comet_endpoint: Option<String>,
+}
+
+impl Default for QualityEstimationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            back_translation_model: None,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            low_confidence_threshold: DEFAULT_LOW_CONFIDENCE_THRESHOLD,
+            comet_endpoint: None,
+        }
+    }
+}
+
+impl QualityEstimationConfig {
+    /// Whether the `index`-th scored segment is back-translated: spread
+    /// evenly so that every n-th segment is picked for a sample rate of 1/n
+    pub fn samples(&self, index: usize) -> bool {
+        if self.back_translation_model.is_none() {
+            return false;
+        }
+        let rate = f64::from(self.sample_rate.clamp(0.0, 1.0));
+        ((index + 1) as f64 * rate).floor() > (index as f64 * rate).floor()
+    }
+}
+
+/// Confidence of one segment with the reasons for lost points
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityScore {
+    pub score: u8,
+    pub reasons: Vec<String>,
+}
+
+/// Scores a translation from its length ratio, the share of target-script
+/// letters, source words left untranslated and the QC warnings
+pub fn heuristic_score(source: &str, translation: &str, target_lang: &str) -> QualityScore {
+    let source = source.trim();
+    let translation = translation.trim();
+    let mut reasons = Vec::new();
+    if translation.is_empty() {
+        if source.is_empty() {
+            return QualityScore {
+                score: 100,
+                reasons,
+            };
+        }
+        reasons.push("번역이 비어 있습니다.".to_string());
+        return QualityScore { score: 0, reasons };
+    }
+
+    let mut penalty = 0.0f32;
+    let has_letters = source.chars().any(char::is_alphabetic);
+    if has_letters && translation == source {
+        penalty += 50.0;
+        reasons.push("번역이 원문과 같습니다.".to_string());
+    }
+
+    let script = TargetScript::for_lang(target_lang);
+    let source_len = source.chars().count();
+    if source_len >= 8 {
+        let ratio = translation.chars().count() as f32 / source_len as f32;
+        let (low, high) = match script {
+            Some(_) => (0.25, 1.6),
+            None => (0.5, 2.0),
+        };
+        let off = if ratio < low {
+            (low - ratio) / low
+        } else if ratio > high {
+            (ratio - high) / high
+        } else {
+            0.0
+        };
+        if off > 0.0 {
+            penalty += (off * 40.0).min(40.0);
+            reasons.push(format!(
+                "길이 비율 {ratio:.2}가 예상 범위({low}~{high})를 벗어났습니다."
+            ));
+        }
+    }
+
+    if let (Some(script), true) = (script, has_letters) {
+        let letters: Vec<char> = translation.chars().filter(|c| c.is_alphabetic()).collect();
+        if !letters.is_empty() {
+            let share = letters.iter().filter(|&&c| script.contains(c)).count() as f32
+                / letters.len() as f32;
+            if share < 0.5 {
+                penalty += (0.5 - share) * 80.0;
+                reasons.push(format!(
+                    "대상 언어 문자 비율이 낮습니다 ({:.0}%).",
+                    share * 100.0
+                ));
+            }
+        }
+    }
+
+    let source_words = content_words(source);
+    if source_words.len() >= 3 && translation != source {
+        let translated_words = content_words(translation);
+        let kept =
+            source_words.intersection(&translated_words).count() as f32 / source_words.len() as f32;
+        if kept > 0.5 {
+            penalty += ((kept - 0.5) * 60.0).min(30.0);
+            reasons.push(format!(
+                "원문 단어가 번역에 많이 남아 있습니다 ({:.0}%).",
+                kept * 100.0
+            ));
+        }
+    }
+
+    let validation = validate_segment(source, translation, &SegmentLimits::default());
+    penalty += 25.0 * validation.errors.len() as f32 + 10.0 * validation.warnings.len() as f32;
+    reasons.extend(validation.errors);
+    reasons.extend(validation.warnings);
+
+    QualityScore {
+        score: (100.0 - penalty).clamp(0.0, 100.0).round() as u8,
+        reasons,
+    }
+}
+
+/// Character bigram overlap (Dice coefficient) of two texts, ignoring case,
+/// punctuation and spacing
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let bigrams = |text: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = text
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    let left = bigrams(a);
+    let mut right = bigrams(b);
+    if left.is_empty() || right.is_empty() {
+        return if a.trim().eq_ignore_ascii_case(b.trim()) {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    let total = left.len() + right.len();
+    let mut shared = 0;
+    for bigram in left {
+        if let Some(position) = right.iter().position(|candidate| *candidate == bigram) {
+            right.swap_remove(position);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f32 / total as f32
+}
+
+/// Weighted mean of the heuristic score and, when available, the
+/// back-translation similarity (0–1) and the COMET score (0–1, counted twice)
+pub fn combine_scores(heuristic: u8, back_translation: Option<f32>, comet: Option<f32>) -> u8 {
+    let mut total = f32::from(heuristic);
+    let mut weight = 1.0;
+    if let Some(similarity) = back_translation {
+        total += similarity.clamp(0.0, 1.0) * 100.0;
+        weight += 1.0;
+    }
+    if let Some(comet) = comet {
+        total += comet.clamp(0.0, 1.0) * 200.0;
+        weight += 2.0;
+    }
+    (total / weight).round() as u8
+}
+
+/// Translates `fragment`, a translation protected like its source, back to
+/// the source language with `model`
+pub async fn back_translate(
+    client: &Client,
+    settings: &TranslationSettings,
+    model: &str,
+    fragment: &ProtectedFragment,
+) -> Result<String, TranslationError> {
+    let reverse = TranslationSettings {
+        model_id: model.to_string(),
+        source_lang: settings.target_lang.clone(),
+        target_lang: settings.source_lang.clone(),
+        // Glossary and tone describe the forward direction
+        prompt_vars: Default::default(),
+        ..settings.clone()
+    };
+    translate_text_with_context(client, &reverse, fragment, None)
+        .await
+        .map(|output| output.text)
+}
+
+#[derive(Serialize)]
+struct CometRequest<'a> {
+    src: &'a str,
+    mt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct CometResponse {
+    score: f32,
+}
+
+/// Asks the local scoring server for a 0–1 score; scores above 1 are read
+/// as percentages
+pub async fn comet_score(
+    client: &Client,
+    endpoint: &str,
+    source: &str,
+    translation: &str,
+) -> reqwest::Result<f32> {
+    let response: CometResponse = client
+        .post(endpoint)
+        .json(&CometRequest {
+            src: source,
+            mt: translation,
+        })
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let score = if response.score > 1.0 {
+        response.score / 100.0
+    } else {
+        response.score
+    };
+    Ok(score.clamp(0.0, 1.0))
+}
+
+/// Letters of the target languages whose script differs from Latin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetScript {
+    Hangul,
+    Japanese,
+    Han,
+    Cyrillic,
+}
+
+impl TargetScript {
+    fn for_lang(lang: &str) -> Option<Self> {
+        let base = lang
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(lang)
+            .to_ascii_lowercase();
+        match base.as_str() {
+            "ko" => Some(Self::Hangul),
+            "ja" => Some(Self::Japanese),
+            "zh" => Some(Self::Han),
+            "ru" | "uk" | "bg" | "sr" | "be" => Some(Self::Cyrillic),
+            _ => None,
+        }
+    }
+
+    fn contains(self, c: char) -> bool {
+        let han = ('\u{4E00}'..='\u{9FFF}').contains(&c) || ('\u{3400}'..='\u{4DBF}').contains(&c);
+        match self {
+            Self::Hangul => {
+                ('\u{AC00}'..='\u{D7AF}').contains(&c)
+                    || ('\u{1100}'..='\u{11FF}').contains(&c)
+                    || ('\u{3130}'..='\u{318F}').contains(&c)
+            }
+            Self::Japanese => han || ('\u{3040}'..='\u{30FF}').contains(&c),
+            Self::Han => han,
+            Self::Cyrillic => ('\u{0400}'..='\u{04FF}').contains(&c),
+        }
+    }
+}
+
+/// Lowercased Latin words of four letters or more
+fn content_words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| word.len() >= 4)
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn good_translations_score_high_and_copies_low() {
+        let good = heuristic_score("Open the storage menu", "보관함 메뉴를 엽니다", "ko");
+        assert_eq!(good.score, 100, "{:?}", good.reasons);
+
+        let copied = heuristic_score("Open the storage menu", "Open the storage menu", "ko");
+        assert!(
+            copied.score < DEFAULT_LOW_CONFIDENCE_THRESHOLD,
+            "{copied:?}"
+        );
+        assert!(copied
+            .reasons
+            .iter()
+            .any(|reason| reason.contains("원문과 같습니다")));
+
+        let partial = heuristic_score(
+            "Harvest ripe crops before winter arrives",
+            "Harvest ripe crops before 겨울",
+            "ko-KR",
+        );
+        assert!(partial.score < good.score);
+        assert_eq!(heuristic_score("", "", "ko").score, 100);
+        assert_eq!(heuristic_score("Hello", " ", "ko").score, 0);
+    }
+
+    #[test]
+    fn scores_combine_with_back_translation_and_comet() {
+        assert!(similarity("Open the storage menu", "open the Storage menu!") > 0.99);
+        assert!(similarity("Open the storage menu", "Close the door") < 0.5);
+        assert_eq!(combine_scores(80, None, None), 80);
+        assert_eq!(combine_scores(80, Some(0.4), None), 60);
+        assert_eq!(combine_scores(80, Some(0.4), Some(0.9)), 75);
+    }
+
+    #[test]
+    fn back_translation_samples_every_nth_segment() {
+        let mut config = QualityEstimationConfig {
+            back_translation_model: Some("gemini-2.0-flash-lite".into()),
+            sample_rate: 0.25,
+            ..QualityEstimationConfig::default()
+        };
+        let sampled: Vec<usize> = (0..10).filter(|&index| config.samples(index)).collect();
+        assert_eq!(sampled, [3, 7]);
+        config.back_translation_model = None;
+        assert!(!config.samples(0));
+    }
+}
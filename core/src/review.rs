@@ -0,0 +1,466 @@
+/// Side-by-side review reports written after a translation job.
+///
+/// Each output file gets a JSON report pairing every source segment with its
+/// translation, QC status and placeholder recovery notes, so reviewers do not
+/// have to diff raw files by hand.
+use crate::access_mode::ensure_writable;
+use crate::backup::write_atomic;
+use crate::format_validator::validate_for_format;
+use crate::formats::FileFormat;
+use crate::placeholder_validator::{
+    PlaceholderValidator, RecoveryStep, Segment as ValidatorSegment, ValidatorConfig,
+};
+use crate::protector::Protector;
+use crate::quality::{validate_segment, SegmentLimits};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const REVIEW_DIR_NAME: &str = ".review";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentReviewStatus {
+    /// Translated and passed validation without changes.
+    Translated,
+    /// Translated after the validator repaired placeholders.
+    Recovered,
+    /// QC or placeholder validation failed; the source text was kept.
+    RolledBack,
+    /// Strict mode kept the source until a reviewer approves the repair.
+    HeldForReview,
+    /// The provider request failed; the source text was kept.
+    Failed,
+    /// No translation was produced (e.g. the job stopped early).
+    Untranslated,
+    /// The output was edited by hand and the edit failed re-validation.
+    Invalid,
+    /// Left as is because the source is already in the target language.
+    AlreadyTranslated,
+    /// Left as is because its key or text is on a do-not-translate list.
+    DoNotTranslate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SegmentReview {
+    pub line_number: usize,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    pub status: SegmentReviewStatus,
+    /// Where the text or key is used elsewhere in the mod
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recovery_steps: Vec<RecoveryStep>,
+    /// Quality estimation score (0–100), when the job ran the pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ReviewSummary {
+    pub total: usize,
+    pub translated: usize,
+    pub recovered: usize,
+    pub rolled_back: usize,
+    pub held_for_review: usize,
+    pub failed: usize,
+    pub untranslated: usize,
+    #[serde(default)]
+    pub invalid: usize,
+    #[serde(default)]
+    pub already_translated: usize,
+    #[serde(default)]
+    pub do_not_translate: usize,
+}
+
+impl ReviewSummary {
+    fn from_segments(segments: &[SegmentReview]) -> Self {
+        Self::from_statuses(segments.iter().map(|segment| segment.status))
+    }
+
+    /// Counts segments by their review status
+    pub fn from_statuses(statuses: impl IntoIterator<Item = SegmentReviewStatus>) -> Self {
+        let mut summary = Self::default();
+        for status in statuses {
+            summary.total += 1;
+            let counter = match status {
+                SegmentReviewStatus::Translated => &mut summary.translated,
+                SegmentReviewStatus::Recovered => &mut summary.recovered,
+                SegmentReviewStatus::RolledBack => &mut summary.rolled_back,
+                SegmentReviewStatus::HeldForReview => &mut summary.held_for_review,
+                SegmentReviewStatus::Failed => &mut summary.failed,
+                SegmentReviewStatus::Untranslated => &mut summary.untranslated,
+                SegmentReviewStatus::Invalid => &mut summary.invalid,
+                SegmentReviewStatus::AlreadyTranslated => &mut summary.already_translated,
+                SegmentReviewStatus::DoNotTranslate => &mut summary.do_not_translate,
+            };
+            *counter += 1;
+        }
+        summary
+    }
+}
+
+/// Job-level details shared by every report of a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ReviewMetadata {
+    pub job_id: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub provider: String,
+    pub model_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FileReviewReport {
+    #[serde(flatten)]
+    pub metadata: ReviewMetadata,
+    pub generated_at: DateTime<Utc>,
+    pub source_file: String,
+    pub output_file: String,
+    pub summary: ReviewSummary,
+    pub segments: Vec<SegmentReview>,
+    /// Set when [`revalidate_report`] checked hand edits to the output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revalidated_at: Option<DateTime<Utc>>,
+}
+
+impl FileReviewReport {
+    pub fn new(
+        metadata: ReviewMetadata,
+        source_file: String,
+        output_file: String,
+        segments: Vec<SegmentReview>,
+    ) -> Self {
+        Self {
+            metadata,
+            generated_at: Utc::now(),
+            source_file,
+            output_file,
+            summary: ReviewSummary::from_segments(&segments),
+            segments,
+            revalidated_at: None,
+        }
+    }
+}
+
+/// Report location for an output file: `<dir>/.review/<file>.review.json`.
+/// The hidden directory keeps games from loading the report as a language file.
+pub fn review_report_path(output_path: &Path) -> PathBuf {
+    let parent = output_path.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = output_path
+        .file_name()
+        .map(|name| format!("{}.review.json", name.to_string_lossy()))
+        .unwrap_or_else(|| "review.json".to_string());
+    parent.join(REVIEW_DIR_NAME).join(file_name)
+}
+
+pub fn write_review_report(path: &Path, report: &FileReviewReport) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(report)?;
+    write_atomic(path, contents.as_bytes())
+}
+
+pub fn read_review_report(path: &Path) -> io::Result<FileReviewReport> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(io::Error::from)
+}
+
+/// A segment whose hand edit failed re-validation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevalidationIssue {
+    pub line_number: usize,
+    pub text: Option<String>,
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevalidationResult {
+    pub output_file: String,
+    pub report_file: String,
+    /// Segments whose output line differed from the report and were re-checked.
+    pub changed: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Whole-file format error, e.g. an XML tag broken by the edit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_error: Option<String>,
+    pub issues: Vec<RevalidationIssue>,
+    pub summary: ReviewSummary,
+}
+
+/// Re-checks a report against the output file after hand edits.
+///
+/// Only segments whose output line no longer matches the recorded translation
+/// are validated again, with the same placeholder and length checks as the
+/// job but without auto-repair: the file is never modified. Segments edited
+/// back to the source text become untranslated. Returns the changed-segment
+/// counts and the format error, if any.
+pub fn revalidate_report(
+    report: &mut FileReviewReport,
+    output_contents: &str,
+    format: FileFormat,
+) -> (usize, usize, Option<String>) {
+    let output_contents = output_contents.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = output_contents.lines().collect();
+    let limits = SegmentLimits::default();
+    let validator = PlaceholderValidator::new(ValidatorConfig {
+        enable_autofix: false,
+        retry_on_fail: false,
+        ..ValidatorConfig::default()
+    });
+
+    let mut changed = 0;
+    let mut passed = 0;
+    for segment in &mut report.segments {
+        let current = lines
+            .get(segment.line_number.wrapping_sub(1))
+            .map(|line| line.trim().to_string());
+        if current == segment.translation {
+            continue;
+        }
+        changed += 1;
+        // The score was for the replaced text
+        segment.confidence = None;
+
+        let Some(current) = current else {
+            segment.translation = None;
+            segment.status = SegmentReviewStatus::Invalid;
+            segment.notes = vec!["수동 편집 후 해당 줄이 사라졌습니다.".to_string()];
+            segment.recovery_steps.clear();
+            continue;
+        };
+
+        if current == segment.source {
+            segment.translation = None;
+            segment.status = SegmentReviewStatus::Untranslated;
+            segment.notes = vec!["수동 편집으로 원문이 복원되었습니다.".to_string()];
+            segment.recovery_steps.clear();
+            passed += 1;
+            continue;
+        }
+
+        let quality = validate_segment(&segment.source, &current, &limits);
+        let masked = Protector::protect(&segment.source).masked_text().to_string();
+        let placeholder_segment = ValidatorSegment::new(
+            report.output_file.clone(),
+            segment.line_number as u32,
+            format!("line_{}", segment.line_number.saturating_sub(1)),
+            segment.source.clone(),
+            masked,
+        );
+
+        let mut messages = quality.errors.clone();
+        if let Err(failure) = validator.validate(&placeholder_segment, &current) {
+            messages.push(format!("Placeholder validation failed: {:?}", failure.code));
+        }
+
+        if messages.is_empty() {
+            segment.status = SegmentReviewStatus::Translated;
+            segment.notes = quality.warnings;
+            segment.notes.push("수동 편집 후 재검증을 통과했습니다.".to_string());
+            passed += 1;
+        } else {
+            segment.status = SegmentReviewStatus::Invalid;
+            messages.extend(quality.warnings);
+            segment.notes = messages;
+        }
+        segment.recovery_steps.clear();
+        segment.translation = Some(current);
+    }
+
+    report.summary = ReviewSummary::from_segments(&report.segments);
+    report.revalidated_at = Some(Utc::now());
+    let format_error = validate_for_format(format, output_contents)
+        .err()
+        .map(|error| error.to_string());
+    (changed, passed, format_error)
+}
+
+/// Tauri command: re-validate a translated file after it was edited by hand
+/// and update its review report.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn revalidate_file(outputPath: String) -> Result<RevalidationResult, String> {
+    ensure_writable("재검증")?;
+    let output_path = PathBuf::from(&outputPath);
+    let report_path = review_report_path(&output_path);
+    let mut report = read_review_report(&report_path).map_err(|error| {
+        format!(
+            "검토 보고서를 읽을 수 없습니다 ({}): {}",
+            report_path.display(),
+            error
+        )
+    })?;
+    let contents = fs::read_to_string(&output_path).map_err(|error| {
+        format!(
+            "번역 파일을 읽을 수 없습니다 ({}): {}",
+            output_path.display(),
+            error
+        )
+    })?;
+
+    let format = FileFormat::from_path(&output_path).refine_with_content(&contents);
+    let (changed, passed, format_error) = revalidate_report(&mut report, &contents, format);
+    write_review_report(&report_path, &report)
+        .map_err(|error| format!("검토 보고서를 저장할 수 없습니다: {}", error))?;
+
+    let issues = report
+        .segments
+        .iter()
+        .filter(|segment| segment.status == SegmentReviewStatus::Invalid)
+        .map(|segment| RevalidationIssue {
+            line_number: segment.line_number,
+            text: segment.translation.clone(),
+            messages: segment.notes.clone(),
+        })
+        .collect();
+
+    Ok(RevalidationResult {
+        output_file: outputPath,
+        report_file: report_path.to_string_lossy().to_string(),
+        changed,
+        passed,
+        failed: changed - passed,
+        format_error,
+        issues,
+        summary: report.summary.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(line_number: usize, status: SegmentReviewStatus) -> SegmentReview {
+        SegmentReview {
+            line_number,
+            source: format!("source {line_number}"),
+            translation: Some(format!("번역 {line_number}")),
+            status,
+            usage: None,
+            notes: Vec::new(),
+            recovery_steps: Vec::new(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn summary_counts_statuses() {
+        let segments = vec![
+            segment(1, SegmentReviewStatus::Translated),
+            segment(2, SegmentReviewStatus::Translated),
+            segment(3, SegmentReviewStatus::Recovered),
+            segment(4, SegmentReviewStatus::RolledBack),
+        ];
+        let summary = ReviewSummary::from_segments(&segments);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.translated, 2);
+        assert_eq!(summary.recovered, 1);
+        assert_eq!(summary.rolled_back, 1);
+        assert_eq!(summary.failed, 0);
+    }
+
+    #[test]
+    fn revalidation_only_checks_edited_segments() {
+        let metadata = ReviewMetadata {
+            job_id: "job-1".into(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            provider: "Gemini".into(),
+            model_id: "gemini-2.5-flash".into(),
+        };
+        let review = |line_number: usize, source: &str, translation: &str| SegmentReview {
+            line_number,
+            source: source.into(),
+            translation: Some(translation.into()),
+            status: SegmentReviewStatus::Translated,
+            usage: None,
+            notes: Vec::new(),
+            recovery_steps: Vec::new(),
+            confidence: None,
+        };
+        let mut report = FileReviewReport::new(
+            metadata,
+            "Keyed.json".into(),
+            "Keyed.ko.json".into(),
+            vec![
+                review(2, "\"a\": \"Hello {0}\",", "\"a\": \"안녕 {0}\","),
+                review(3, "\"b\": \"Bye {name}\",", "\"b\": \"잘 가 {name}\","),
+                review(4, "\"c\": \"Run\"", "\"c\": \"달려\""),
+            ],
+        );
+
+        // Line 2 untouched, line 3 loses {name}, line 4 is fixed by hand
+        let edited = "{\n\"a\": \"안녕 {0}\",\n\"b\": \"잘 가\",\n\"c\": \"뛰어\"\n}\n";
+        let (changed, passed, format_error) =
+            revalidate_report(&mut report, edited, FileFormat::Json);
+
+        assert_eq!((changed, passed), (2, 1));
+        assert!(format_error.is_none());
+        assert_eq!(report.segments[0].status, SegmentReviewStatus::Translated);
+        assert!(report.segments[0].notes.is_empty());
+        assert_eq!(report.segments[1].status, SegmentReviewStatus::Invalid);
+        assert_eq!(report.segments[2].status, SegmentReviewStatus::Translated);
+        assert_eq!(report.segments[2].translation.as_deref(), Some("\"c\": \"뛰어\""));
+        assert_eq!(report.summary.invalid, 1);
+        assert!(report.revalidated_at.is_some());
+
+        let broken = "{\n\"a\": \"안녕 {0}\",\n\"b\": \"잘 가 {name}\",\n\"c\": \"뛰어\n}\n";
+        let (_, _, format_error) = revalidate_report(&mut report, broken, FileFormat::Json);
+        assert!(format_error.is_some());
+    }
+
+    #[test]
+    fn report_is_written_to_hidden_review_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("Languages").join("Keyed.ko.xml");
+        let path = review_report_path(&output);
+        assert_eq!(path, dir.path().join("Languages/.review/Keyed.ko.xml.review.json"));
+
+        let mut recovered = segment(7, SegmentReviewStatus::Recovered);
+        recovered.recovery_steps.push(RecoveryStep::ReinjectMissingProtected);
+        let metadata = ReviewMetadata {
+            job_id: "job-1".into(),
+            source_lang: "en".into(),
+            target_lang: "ko".into(),
+            provider: "Gemini".into(),
+            model_id: "gemini-2.5-flash".into(),
+        };
+        let report = FileReviewReport::new(
+            metadata,
+            "Languages/Keyed.xml".into(),
+            output.to_string_lossy().to_string(),
+            vec![recovered],
+        );
+        write_review_report(&path, &report).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["jobId"], "job-1");
+        assert_eq!(written["summary"]["recovered"], 1);
+        assert_eq!(written["segments"][0]["status"], "recovered");
+        assert_eq!(
+            written["segments"][0]["recoverySteps"][0],
+            "REINJECT_MISSING_PROTECTED"
+        );
+        assert!(written["segments"][0].get("notes").is_none());
+    }
+}
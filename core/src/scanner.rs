@@ -182,17 +182,17 @@ impl FileScanner {
             return Ok(None);
         }
         
-        // Check if binary
-        if self.is_binary(path)? {
-            return Ok(None);
-        }
-        
         // Detect format
         let format = FileFormat::from_path(path);
         if format == FileFormat::Unknown {
             return Ok(None);
         }
         
+        // Check if binary (binary formats are read by their handler)
+        if !format.is_binary() && self.is_binary(path)? {
+            return Ok(None);
+        }
+        
         // Check if handler exists
         if get_handler(format).is_none() {
             return Ok(None);
@@ -337,4 +337,30 @@ mod tests {
         
         assert_eq!(files.len(), 0);
     }
+    
+    #[test]
+    fn scans_bethesda_string_tables() {
+        use crate::formats::bethesda::{StringEncoding, StringEntry, StringTable, StringTableKind};
+        
+        let dir = TempDir::new().unwrap();
+        let table = StringTable {
+            kind: StringTableKind::DlStrings,
+            encoding: StringEncoding::Utf8,
+            entries: vec![StringEntry {
+                id: 1,
+                text: "An old sword.".into(),
+            }],
+        };
+        fs::write(
+            dir.path().join("Mod_English.DLSTRINGS"),
+            table.to_bytes().unwrap(),
+        )
+        .unwrap();
+        
+        let scanner = FileScanner::new(ScanConfig::default());
+        let files = scanner.scan(dir.path()).unwrap();
+        
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].format, FileFormat::Bethesda);
+    }
 }
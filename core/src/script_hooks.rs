@@ -0,0 +1,266 @@
+/// Game profile scripts run on each segment.
+///
+/// Some fixes are specific to one game and too involved for the regex
+/// settings: masking a custom markup, replacing a term only in some
+/// contexts, repairing a mistake a model keeps making. A game profile can
+/// name a [Rhai](https://rhai.rs) script (`script = "rimworld.rhai"`,
+/// relative to the user profile directory) that defines either or both of
+///
+/// ```text
+/// fn before_send(text, ctx) { … }               // ctx: #{ file, line }
+/// fn after_receive(source, translation, ctx) { … } // ctx also has targetLang
+/// ```
+///
+/// `before_send` gets the source text before it is masked and sent and
+/// returns the text to send; `after_receive` gets each translation before
+/// QC and returns the text to keep. Both must return a string.
+///
+/// Scripts are sandboxed: Rhai cannot reach files, the network or other
+/// processes, `eval` is disabled, and limits on operations, call depth and
+/// value sizes stop runaway scripts. A hook that fails leaves the text as it
+/// was and logs why.
+use crate::profiles::user::user_profiles_dir;
+use crate::profiles::GameProfile;
+use log::{info, warn};
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const BEFORE_SEND: &str = "before_send";
+const AFTER_RECEIVE: &str = "after_receive";
+
+/// Script files larger than this are not loaded
+const MAX_SCRIPT_BYTES: u64 = 256 * 1024;
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_SIZE: usize = 1024 * 1024;
+const MAX_COLLECTION_SIZE: usize = 10_000;
+
+/// Where the text a hook gets comes from
+#[derive(Debug, Clone, Copy)]
+pub struct HookContext<'a> {
+    pub file: &'a str,
+    pub line: usize,
+    /// Only known after translation; the masked source is shared by every
+    /// target language of a job
+    pub target_lang: Option<&'a str>,
+}
+
+impl HookContext<'_> {
+    fn to_map(self) -> Map {
+        let mut map = Map::new();
+        map.insert("file".into(), self.file.into());
+        map.insert("line".into(), Dynamic::from_int(self.line as rhai::INT));
+        if let Some(target_lang) = self.target_lang {
+            map.insert("targetLang".into(), target_lang.into());
+        }
+        map
+    }
+}
+
+/// A compiled profile script
+pub struct ScriptHooks {
+    name: String,
+    engine: Engine,
+    ast: AST,
+    before_send: bool,
+    after_receive: bool,
+}
+
+impl fmt::Debug for ScriptHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptHooks")
+            .field("name", &self.name)
+            .field("before_send", &self.before_send)
+            .field("after_receive", &self.after_receive)
+            .finish()
+    }
+}
+
+fn sandboxed_engine(name: &str) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        .disable_symbol("eval");
+    let print_name = name.to_string();
+    engine.on_print(move |text| info!("script {}: {}", print_name, text));
+    let debug_name = name.to_string();
+    engine
+        .on_debug(move |text, _, position| info!("script {} ({}): {}", debug_name, position, text));
+    engine
+}
+
+impl ScriptHooks {
+    /// Compiles `source`. Fails when it does not parse or defines neither
+    /// hook.
+    pub fn compile(name: &str, source: &str) -> Result<Self, String> {
+        let engine = sandboxed_engine(name);
+        let ast = engine
+            .compile(source)
+            .map_err(|e| format!("스크립트 {}을(를) 해석하지 못했습니다: {}", name, e))?;
+        let defines = |function: &str, params: usize| {
+            ast.iter_functions()
+                .any(|f| f.name == function && f.params.len() == params)
+        };
+        let before_send = defines(BEFORE_SEND, 2);
+        let after_receive = defines(AFTER_RECEIVE, 3);
+        if !before_send && !after_receive {
+            return Err(format!(
+                "스크립트 {}에 before_send(text, ctx)나 after_receive(source, translation, ctx) 함수가 없습니다.",
+                name
+            ));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            engine,
+            ast,
+            before_send,
+            after_receive,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let name = path.to_string_lossy().to_string();
+        if fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_SCRIPT_BYTES) {
+            return Err(format!("스크립트 {}가 너무 큽니다.", name));
+        }
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("스크립트 {}을(를) 읽지 못했습니다: {}", name, e))?;
+        Self::compile(&name, &source)
+    }
+
+    /// The text to mask and send in place of `text`
+    pub fn before_send(&self, text: &str, context: HookContext<'_>) -> String {
+        if !self.before_send {
+            return text.to_string();
+        }
+        self.call(BEFORE_SEND, (text.to_string(), context.to_map()), context)
+            .unwrap_or_else(|| text.to_string())
+    }
+
+    /// The translation to keep in place of `translation`
+    pub fn after_receive(
+        &self,
+        source: &str,
+        translation: &str,
+        context: HookContext<'_>,
+    ) -> String {
+        if !self.after_receive {
+            return translation.to_string();
+        }
+        self.call(
+            AFTER_RECEIVE,
+            (
+                source.to_string(),
+                translation.to_string(),
+                context.to_map(),
+            ),
+            context,
+        )
+        .unwrap_or_else(|| translation.to_string())
+    }
+
+    fn call(
+        &self,
+        function: &str,
+        args: impl rhai::FuncArgs,
+        context: HookContext<'_>,
+    ) -> Option<String> {
+        // Only the function runs; top-level statements are never evaluated
+        let options = CallFnOptions::new().eval_ast(false);
+        match self.engine.call_fn_with_options::<String>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            function,
+            args,
+        ) {
+            Ok(text) => Some(text),
+            Err(error) => {
+                warn!(
+                    "script {} failed in {} for {}:{}: {}",
+                    self.name, function, context.file, context.line, error
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Loads the script `profile` names, if any. Relative paths are resolved
+/// against the user profile directory.
+pub fn load_profile_script(profile: &GameProfile) -> Option<Result<ScriptHooks, String>> {
+    let script = profile.script.as_deref()?.trim();
+    if script.is_empty() {
+        return None;
+    }
+    let path = Path::new(script);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        user_profiles_dir().join(path)
+    };
+    Some(ScriptHooks::load(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTEXT: HookContext<'static> = HookContext {
+        file: "Languages/English/Keyed/Main.xml",
+        line: 3,
+        target_lang: Some("ko"),
+    };
+
+    #[test]
+    fn runs_hooks_with_the_segment_context() {
+        let hooks = ScriptHooks::compile(
+            "test",
+            r#"
+            fn before_send(text, ctx) {
+                text.replace("[c]", "{COLOR}");
+                text
+            }
+            fn after_receive(source, translation, ctx) {
+                if ctx.targetLang == "ko" && source.contains("Pawn") {
+                    translation.replace("폰", "정착민");
+                }
+                translation + " @" + ctx.line
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(hooks.before_send("[c]Red[c]", CONTEXT), "{COLOR}Red{COLOR}");
+        assert_eq!(
+            hooks.after_receive("Pawn died", "폰이 죽었습니다", CONTEXT),
+            "정착민이 죽었습니다 @3"
+        );
+    }
+
+    #[test]
+    fn keeps_the_text_when_a_hook_fails_or_runs_away() {
+        let hooks = ScriptHooks::compile(
+            "test",
+            r#"
+            fn before_send(text, ctx) { loop { } }
+            fn after_receive(source, translation, ctx) { 42 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(hooks.before_send("Hello", CONTEXT), "Hello");
+        assert_eq!(hooks.after_receive("Hello", "안녕", CONTEXT), "안녕");
+
+        assert!(ScriptHooks::compile("test", "fn unrelated(x) { x }").is_err());
+        assert!(ScriptHooks::compile("test", "fn before_send(text, ctx) {").is_err());
+        assert!(ScriptHooks::compile("test", r#"fn before_send(t, c) { eval("t") }"#).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Provider API keys kept by the core.
+//!
+//! Keys are encrypted with ChaCha20-Poly1305 under a random 256-bit vault
+//! key. The vault key lives in the OS credential store (Keychain on macOS,
+//! Credential Manager on Windows, the Secret Service on Linux), so the
+//! encrypted file alone does not reveal the keys. The frontend sends a key
+//! once, when it is added; jobs and key checks look up the stored key of
+//! their provider, so keys never travel back through the frontend.
+
+use crate::ai::ProviderId;
+use crate::backup::write_atomic;
+use crate::paths::app_data_dir;
+use crate::validation::{validate_api_key_and_list_models, ProviderValidationResult};
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VAULT_FILE_NAME: &str = "secrets.json";
+/// Vault key file of earlier versions, moved into the credential store
+const LEGACY_VAULT_KEY_FILE_NAME: &str = "secrets.key";
+const VAULT_VERSION: u32 = 1;
+
+const KEYRING_SERVICE: &str = "mod-translator";
+const KEYRING_USER: &str = "api-key-vault";
+
+const PROVIDERS: [ProviderId; 4] = [
+    ProviderId::Gemini,
+    ProviderId::Gpt,
+    ProviderId::Claude,
+    ProviderId::Grok,
+];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultFile {
+    version: u32,
+    #[serde(default)]
+    keys: HashMap<ProviderId, SealedKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SealedKey {
+    /// Hex encoded
+    nonce: String,
+    /// Hex encoded, authenticated with the provider name
+    ciphertext: String,
+    added_at: DateTime<Utc>,
+}
+
+/// A stored key as shown to the user; the key itself never leaves the core
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredApiKey {
+    pub provider: ProviderId,
+    /// 앞 4자와 끝 2자만 보이는 키
+    pub masked: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Where the vault key is kept
+pub trait VaultKeyStore: Send + Sync {
+    fn load(&self) -> Result<Option<Vec<u8>>, String>;
+    fn save(&self, key: &[u8]) -> Result<(), String>;
+}
+
+/// The vault key in the OS credential store
+pub struct OsKeyring;
+
+impl OsKeyring {
+    fn entry() -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .map_err(|e| format!("운영체제 키 저장소를 열지 못했습니다: {}", e))
+    }
+}
+
+impl VaultKeyStore for OsKeyring {
+    fn load(&self) -> Result<Option<Vec<u8>>, String> {
+        match Self::entry()?.get_secret() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(error) => Err(format!("운영체제 키 저장소를 읽지 못했습니다: {}", error)),
+        }
+    }
+
+    fn save(&self, key: &[u8]) -> Result<(), String> {
+        Self::entry()?
+            .set_secret(key)
+            .map_err(|e| format!("운영체제 키 저장소에 쓰지 못했습니다: {}", e))
+    }
+}
+
+/// Encrypted API keys in one directory
+pub struct SecretStore {
+    dir: PathBuf,
+    key_store: Box<dyn VaultKeyStore>,
+}
+
+impl SecretStore {
+    /// Store whose vault key is kept in the OS credential store
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::with_key_store(dir, Box::new(OsKeyring))
+    }
+
+    pub fn with_key_store(dir: impl Into<PathBuf>, key_store: Box<dyn VaultKeyStore>) -> Self {
+        Self {
+            dir: dir.into(),
+            key_store,
+        }
+    }
+
+    fn vault_path(&self) -> PathBuf {
+        self.dir.join(VAULT_FILE_NAME)
+    }
+
+    /// Vault key, created on first use when `create` is set
+    fn vault_key(&self, create: bool) -> Result<Option<Key>, String> {
+        let stored = match self.key_store.load()? {
+            Some(key) => Some(key),
+            None => self.migrate_legacy_key()?,
+        };
+        match stored {
+            Some(bytes) => match <[u8; 32]>::try_from(bytes.as_slice()) {
+                Ok(key) => Ok(Some(Key::from(key))),
+                Err(_) => Err("API 키 저장소의 암호화 키가 손상되었습니다.".into()),
+            },
+            None if !create => Ok(None),
+            None => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                self.key_store.save(&key[..])?;
+                Ok(Some(key))
+            }
+        }
+    }
+
+    /// Moves a vault key file left by an earlier version into the key store
+    fn migrate_legacy_key(&self) -> Result<Option<Vec<u8>>, String> {
+        let path = self.dir.join(LEGACY_VAULT_KEY_FILE_NAME);
+        let key = match fs::read(&path) {
+            Ok(key) => key,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(format!("API 키 저장소를 열지 못했습니다: {}", error)),
+        };
+        self.key_store.save(&key)?;
+        if let Err(error) = fs::remove_file(&path) {
+            warn!("failed to remove the legacy vault key file: {}", error);
+        }
+        Ok(Some(key))
+    }
+
+    fn read_vault(&self) -> Result<VaultFile, String> {
+        match fs::read_to_string(self.vault_path()) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("API 키 저장소 파일이 올바르지 않습니다: {}", e)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(VaultFile {
+                version: VAULT_VERSION,
+                keys: HashMap::new(),
+            }),
+            Err(error) => Err(format!("API 키 저장소를 읽지 못했습니다: {}", error)),
+        }
+    }
+
+    fn write_vault(&self, vault: &VaultFile) -> Result<(), String> {
+        let contents = serde_json::to_vec_pretty(vault).map_err(|e| e.to_string())?;
+        write_private(&self.vault_path(), &contents)
+    }
+
+    /// Stores `api_key` for `provider`, replacing an earlier key
+    pub fn set(&self, provider: ProviderId, api_key: &str) -> Result<StoredApiKey, String> {
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return Err("API 키를 입력해야 합니다.".into());
+        }
+        let key = self
+            .vault_key(true)?
+            .expect("vault key is created on demand");
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: api_key.as_bytes(),
+                    aad: provider.label().as_bytes(),
+                },
+            )
+            .map_err(|_| "API 키를 암호화하지 못했습니다.".to_string())?;
+
+        let mut vault = self.read_vault()?;
+        vault.version = VAULT_VERSION;
+        let sealed = SealedKey {
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            added_at: Utc::now(),
+        };
+        let stored = StoredApiKey {
+            provider,
+            masked: mask_api_key(api_key),
+            added_at: sealed.added_at,
+        };
+        vault.keys.insert(provider, sealed);
+        self.write_vault(&vault)?;
+        Ok(stored)
+    }
+
+    /// Stored key of `provider`
+    pub fn get(&self, provider: ProviderId) -> Result<Option<String>, String> {
+        let vault = self.read_vault()?;
+        let Some(sealed) = vault.keys.get(&provider) else {
+            return Ok(None);
+        };
+        let Some(key) = self.vault_key(false)? else {
+            return Err("API 키 저장소의 암호화 키가 없습니다. 키를 다시 추가해 주세요.".into());
+        };
+        open_sealed(&key, provider, sealed).map(Some)
+    }
+
+    /// Removes the key of `provider`; false when there was none
+    pub fn remove(&self, provider: ProviderId) -> Result<bool, String> {
+        let mut vault = self.read_vault()?;
+        if vault.keys.remove(&provider).is_none() {
+            return Ok(false);
+        }
+        self.write_vault(&vault)?;
+        Ok(true)
+    }
+
+    /// Stored keys in provider order, masked. Keys that no longer decrypt are
+    /// left out.
+    pub fn list(&self) -> Result<Vec<StoredApiKey>, String> {
+        let vault = self.read_vault()?;
+        if vault.keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(key) = self.vault_key(false)? else {
+            return Ok(Vec::new());
+        };
+        Ok(PROVIDERS
+            .iter()
+            .filter_map(|provider| {
+                let sealed = vault.keys.get(provider)?;
+                match open_sealed(&key, *provider, sealed) {
+                    Ok(api_key) => Some(StoredApiKey {
+                        provider: *provider,
+                        masked: mask_api_key(&api_key),
+                        added_at: sealed.added_at,
+                    }),
+                    Err(error) => {
+                        warn!("skipping stored {} key: {}", provider, error);
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+}
+
+fn open_sealed(key: &Key, provider: ProviderId, sealed: &SealedKey) -> Result<String, String> {
+    let corrupted = || format!("저장된 {} API 키를 읽지 못했습니다.", provider);
+    let nonce = hex::decode(&sealed.nonce).map_err(|_| corrupted())?;
+    let nonce = <[u8; 12]>::try_from(nonce.as_slice()).map_err(|_| corrupted())?;
+    let ciphertext = hex::decode(&sealed.ciphertext).map_err(|_| corrupted())?;
+    let plaintext = ChaCha20Poly1305::new(key)
+        .decrypt(
+            &Nonce::from(nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: provider.label().as_bytes(),
+            },
+        )
+        .map_err(|_| corrupted())?;
+    String::from_utf8(plaintext).map_err(|_| corrupted())
+}
+
+/// Writes `contents` atomically, readable only by the current user on Unix
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    write_atomic(path, contents).map_err(|e| format!("API 키 저장소에 쓰지 못했습니다: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("API 키 저장소 권한을 설정하지 못했습니다: {}", e))?;
+    }
+    Ok(())
+}
+
+/// First 4 and last 2 characters of a key, the rest masked
+pub fn mask_api_key(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    match chars.len() {
+        0..=2 => "*".repeat(chars.len()),
+        3..=8 => format!(
+            "{}{}{}",
+            chars[0],
+            "*".repeat(chars.len() - 2),
+            chars[chars.len() - 1]
+        ),
+        len => format!(
+            "{}{}{}",
+            chars[..4].iter().collect::<String>(),
+            "*".repeat(len - 6),
+            chars[len - 2..].iter().collect::<String>()
+        ),
+    }
+}
+
+/// Directory holding the key vault
+pub fn secrets_dir() -> PathBuf {
+    app_data_dir()
+}
+
+/// Stored key of `provider`, used when a job is started without one
+pub fn stored_api_key(provider: ProviderId) -> Option<String> {
+    match SecretStore::new(secrets_dir()).get(provider) {
+        Ok(key) => key,
+        Err(error) => {
+            warn!("failed to read stored {} API key: {}", provider, error);
+            None
+        }
+    }
+}
+
+fn parse_provider(provider: &str) -> Result<ProviderId, String> {
+    ProviderId::try_from(provider).map_err(|_| format!("지원하지 않는 번역기: {}", provider))
+}
+
+/// Tauri command to store the API key of a provider, replacing an earlier one
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn add_api_key(provider: String, api_key: String) -> Result<StoredApiKey, String> {
+    SecretStore::new(secrets_dir()).set(parse_provider(&provider)?, &api_key)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn remove_api_key(provider: String) -> Result<bool, String> {
+    SecretStore::new(secrets_dir()).remove(parse_provider(&provider)?)
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn list_api_keys() -> Result<Vec<StoredApiKey>, String> {
+    SecretStore::new(secrets_dir()).list()
+}
+
+/// Tauri command to check the stored key of a provider against its API
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn test_api_key(
+    provider: String,
+    model_hint: Option<String>,
+) -> Result<ProviderValidationResult, String> {
+    let provider_id = parse_provider(&provider)?;
+    let api_key = SecretStore::new(secrets_dir())
+        .get(provider_id)?
+        .ok_or_else(|| format!("저장된 {} API 키가 없습니다.", provider_id))?;
+    validate_api_key_and_list_models(provider, api_key, model_hint, Some(true)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Vault key kept in memory, shared by the stores cloned from it
+    #[derive(Clone, Default)]
+    struct MemoryKeyStore(Arc<Mutex<Option<Vec<u8>>>>);
+
+    impl VaultKeyStore for MemoryKeyStore {
+        fn load(&self) -> Result<Option<Vec<u8>>, String> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn save(&self, key: &[u8]) -> Result<(), String> {
+            *self.0.lock().unwrap() = Some(key.to_vec());
+            Ok(())
+        }
+    }
+
+    fn memory_store(dir: &Path) -> (SecretStore, MemoryKeyStore) {
+        let keys = MemoryKeyStore::default();
+        (
+            SecretStore::with_key_store(dir, Box::new(keys.clone())),
+            keys,
+        )
+    }
+
+    #[test]
+    fn keys_round_trip_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, keys) = memory_store(dir.path());
+        assert_eq!(store.get(ProviderId::Gemini).unwrap(), None);
+        assert!(store.list().unwrap().is_empty());
+
+        let stored = store
+            .set(ProviderId::Gemini, "  AIzaSyExampleKey42 ")
+            .unwrap();
+        assert_eq!(stored.masked, "AIza************42");
+        store.set(ProviderId::Claude, "sk-ant-123456").unwrap();
+
+        assert_eq!(
+            store.get(ProviderId::Gemini).unwrap().as_deref(),
+            Some("AIzaSyExampleKey42")
+        );
+        let vault = fs::read_to_string(dir.path().join(VAULT_FILE_NAME)).unwrap();
+        assert!(!vault.contains("AIzaSyExampleKey42"));
+        // Only the vault file is written; its key stays in the key store
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(keys.load().unwrap().map(|key| key.len()), Some(32));
+
+        let listed: Vec<ProviderId> = store.list().unwrap().iter().map(|k| k.provider).collect();
+        assert_eq!(listed, vec![ProviderId::Gemini, ProviderId::Claude]);
+
+        assert!(store.remove(ProviderId::Gemini).unwrap());
+        assert!(!store.remove(ProviderId::Gemini).unwrap());
+        assert_eq!(store.get(ProviderId::Gemini).unwrap(), None);
+        assert!(store.set(ProviderId::Grok, "   ").is_err());
+    }
+
+    #[test]
+    fn tampered_or_swapped_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, keys) = memory_store(dir.path());
+        store.set(ProviderId::Gpt, "sk-openai-key").unwrap();
+
+        // Moving a sealed key to another provider fails authentication
+        let mut vault = store.read_vault().unwrap();
+        let sealed = vault.keys.remove(&ProviderId::Gpt).unwrap();
+        vault.keys.insert(ProviderId::Grok, sealed);
+        store.write_vault(&vault).unwrap();
+        assert!(store.get(ProviderId::Grok).is_err());
+        assert!(store.list().unwrap().is_empty());
+
+        keys.save(b"short").unwrap();
+        assert!(store.get(ProviderId::Grok).is_err());
+    }
+
+    #[test]
+    fn legacy_key_files_move_into_the_key_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, keys) = memory_store(dir.path());
+        store.set(ProviderId::Claude, "sk-ant-123456").unwrap();
+        let legacy = dir.path().join(LEGACY_VAULT_KEY_FILE_NAME);
+        fs::write(&legacy, keys.load().unwrap().unwrap()).unwrap();
+
+        let (store, keys) = memory_store(dir.path());
+        assert_eq!(
+            store.get(ProviderId::Claude).unwrap().as_deref(),
+            Some("sk-ant-123456")
+        );
+        assert!(!legacy.exists());
+        assert!(keys.load().unwrap().is_some());
+    }
+
+    #[test]
+    fn masking_matches_the_settings_view() {
+        assert_eq!(mask_api_key("ab"), "**");
+        assert_eq!(mask_api_key("abcdef"), "a****f");
+        assert_eq!(mask_api_key("sk-1234567890"), "sk-1*******90");
+    }
+}
@@ -0,0 +1,643 @@
+/// Localhost control server (`serve` feature).
+///
+/// Exposes the core operations over a small REST API so translations can be
+/// driven from scripts, or from a web UI on another machine:
+///
+/// - `GET /api/health`
+/// - `POST /api/library/scan` with `{"explicitPath"?, "source"?}`
+/// - `POST /api/mods/files` with `{"modDirectory"}`
+/// - `GET /api/jobs`, `GET /api/jobs/{jobId}`
+/// - `POST /api/jobs` with a `start_translation_job` payload
+/// - `POST /api/jobs/{jobId}/cancel`
+/// - `GET /api/events[?jobId=…]`: job and scan events as server-sent events,
+///   named after the channel the desktop app receives them on
+///
+/// Requests carry the access token as `Authorization: Bearer <token>` or as
+/// `?token=<token>` (a browser `EventSource` cannot set headers). A token is
+/// always required; when none is configured the server makes one up and
+/// reports it. Since web pages open in the user's browser can reach
+/// localhost too, requests sent from another origin (or, on loopback, naming
+/// another host) are refused, and request bodies must be
+/// `application/json`, which a page cannot send without a CORS preflight.
+use crate::job::queue::JobPriority;
+use crate::job::sink::ProgressSink;
+use crate::jobs::{
+    cancel_job, get_job_status, list_jobs, queue_translation_job, StartTranslationJobPayload,
+};
+use crate::library::{list_mod_files_with, scan_library_with, ModSourceKind};
+use bytes::Bytes;
+use futures_util::stream::{self, StreamExt};
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::header::{HeaderValue, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, HOST, ORIGIN};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use log::{info, warn};
+use percent_encoding::percent_decode_str;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+pub const DEFAULT_PORT: u16 = 7420;
+
+/// Events kept for event streams that fall behind
+const EVENT_BUFFER: usize = 1024;
+
+/// Largest request body accepted (job payloads list every file)
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+type Body = BoxBody<Bytes, Infallible>;
+
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    pub addr: SocketAddr,
+    /// Required from clients; generated when not set
+    pub token: Option<String>,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_PORT)),
+            token: None,
+        }
+    }
+}
+
+/// Passes job and scan events on to every open event stream
+#[derive(Debug, Clone)]
+pub struct BroadcastSink {
+    events: broadcast::Sender<(String, Value)>,
+}
+
+impl BroadcastSink {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self { events }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<(String, Value)> {
+        self.events.subscribe()
+    }
+}
+
+impl Default for BroadcastSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressSink for BroadcastSink {
+    fn send(&self, channel: &str, payload: Value) {
+        // Fails only when no stream is open, and then nobody is listening
+        let _ = self.events.send((channel.to_string(), payload));
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanRequest {
+    explicit_path: Option<String>,
+    source: Option<ModSourceKind>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListFilesRequest {
+    mod_directory: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobAccepted {
+    job_id: String,
+}
+
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+struct Server {
+    token: String,
+    /// Listening on a loopback address, so only local host names are valid
+    loopback: bool,
+    sink: BroadcastSink,
+}
+
+impl ServeOptions {
+    /// The configured token, or a new random one when none is set
+    pub fn access_token(&self) -> String {
+        match self.token.as_deref().map(str::trim) {
+            Some(token) if !token.is_empty() => token.to_string(),
+            _ => generate_token(),
+        }
+    }
+
+    pub async fn bind(&self) -> Result<TcpListener, String> {
+        TcpListener::bind(self.addr)
+            .await
+            .map_err(|e| format!("{}에서 서버를 열지 못했습니다: {}", self.addr, e))
+    }
+}
+
+/// 256 random bits, hex encoded
+pub fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Listens on `options.addr` and serves requests until the task is dropped.
+/// A generated token is written to the log.
+pub async fn serve(options: ServeOptions) -> Result<(), String> {
+    let listener = options.bind().await?;
+    let token = options.access_token();
+    if options
+        .token
+        .as_deref()
+        .is_none_or(|given| given.trim().is_empty())
+    {
+        info!("control server access token: {}", token);
+    }
+    serve_listener(listener, token).await
+}
+
+/// Serves requests on an already bound listener.
+pub async fn serve_listener(listener: TcpListener, token: String) -> Result<(), String> {
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err("서버를 열려면 접근 토큰이 필요합니다.".into());
+    }
+    let addr = listener
+        .local_addr()
+        .map_err(|e| format!("서버 주소를 알 수 없습니다: {}", e))?;
+    info!("control server listening on http://{}", addr);
+    let server = Arc::new(Server {
+        token,
+        loopback: addr.ip().is_loopback(),
+        sink: BroadcastSink::new(),
+    });
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(error) => {
+                warn!("failed to accept control connection: {}", error);
+                continue;
+            }
+        };
+        let server = server.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let server = server.clone();
+                async move { Ok::<_, Infallible>(server.handle(request).await) }
+            });
+            if let Err(error) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                warn!("control connection from {} failed: {}", peer, error);
+            }
+        });
+    }
+}
+
+impl Server {
+    async fn handle(&self, request: Request<Incoming>) -> Response<Body> {
+        if !same_origin(&request, self.loopback) {
+            return error_response(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "다른 사이트에서 보낸 요청은 받지 않습니다.",
+            ));
+        }
+        if !authorized(&self.token, &request) {
+            return error_response(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "접근 토큰이 올바르지 않습니다.",
+            ));
+        }
+        self.route(request).await.unwrap_or_else(error_response)
+    }
+
+    async fn route(&self, request: Request<Incoming>) -> Result<Response<Body>, ApiError> {
+        let method = request.method().clone();
+        let path = request.uri().path().trim_end_matches('/').to_string();
+        let segments: Vec<&str> = path.split('/').skip(1).collect();
+        match (&method, segments.as_slice()) {
+            (&Method::GET, ["api", "health"]) => Ok(json_response(
+                StatusCode::OK,
+                &json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }),
+            )),
+            (&Method::POST, ["api", "library", "scan"]) => {
+                let body: ScanRequest = read_json(request).await?;
+                let sink = self.sink.clone();
+                let response =
+                    blocking(move || scan_library_with(&sink, body.explicit_path, body.source))
+                        .await?;
+                Ok(json_response(StatusCode::OK, &response))
+            }
+            (&Method::POST, ["api", "mods", "files"]) => {
+                let body: ListFilesRequest = read_json(request).await?;
+                let sink = self.sink.clone();
+                let listing =
+                    blocking(move || list_mod_files_with(&sink, body.mod_directory)).await?;
+                Ok(json_response(StatusCode::OK, &listing))
+            }
+            (&Method::GET, ["api", "jobs"]) => {
+                let jobs = list_jobs().map_err(ApiError::bad_request)?;
+                Ok(json_response(StatusCode::OK, &jobs))
+            }
+            (&Method::POST, ["api", "jobs"]) => {
+                let payload: StartTranslationJobPayload = read_json(request).await?;
+                let job_id = payload.job_id.clone();
+                queue_translation_job(Arc::new(self.sink.clone()), payload, JobPriority::default())
+                    .map_err(ApiError::bad_request)?;
+                Ok(json_response(StatusCode::ACCEPTED, &JobAccepted { job_id }))
+            }
+            (&Method::GET, ["api", "jobs", job_id]) => {
+                match get_job_status(job_id.to_string()).map_err(ApiError::bad_request)? {
+                    Some(status) => Ok(json_response(StatusCode::OK, &status)),
+                    None => Err(ApiError::new(
+                        StatusCode::NOT_FOUND,
+                        format!("작업을 찾을 수 없습니다: {job_id}"),
+                    )),
+                }
+            }
+            (&Method::POST, ["api", "jobs", job_id, "cancel"]) => {
+                cancel_job(&self.sink, job_id.to_string()).map_err(ApiError::bad_request)?;
+                Ok(json_response(StatusCode::ACCEPTED, &json!({})))
+            }
+            (&Method::GET, ["api", "events"]) => {
+                let job_id = query_param(request.uri().query(), "jobId").map(Cow::into_owned);
+                Ok(self.event_stream(job_id))
+            }
+            (_, ["api", ..]) => Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                format!("알 수 없는 요청입니다: {method} {path}"),
+            )),
+            _ => Err(ApiError::new(StatusCode::NOT_FOUND, "찾을 수 없습니다.")),
+        }
+    }
+
+    /// Server-sent events for every emitted event, or only those of one job
+    /// (and of its per-language jobs, `{jobId}-{language}`)
+    fn event_stream(&self, job_id: Option<String>) -> Response<Body> {
+        let receiver = self.sink.subscribe();
+        let opened =
+            stream::once(async { Ok(Frame::data(Bytes::from_static(b": connected\n\n"))) });
+        let events = stream::unfold((receiver, job_id), |(mut receiver, job_id)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok((channel, payload)) => {
+                        if !event_matches_job(&payload, job_id.as_deref()) {
+                            continue;
+                        }
+                        let frame = Frame::data(sse_event(&channel, &payload));
+                        return Some((Ok(frame), (receiver, job_id)));
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("event stream fell behind; skipped {} events", skipped);
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        let mut response = Response::new(BodyExt::boxed(StreamBody::new(opened.chain(events))));
+        let headers = response.headers_mut();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        response
+    }
+}
+
+/// Runs a blocking core operation off the async workers
+async fn blocking<T, F>(operation: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(ApiError::bad_request)
+}
+
+/// Parses the JSON body; an empty body reads as `{}`. A body sent with
+/// any other content type is refused.
+async fn read_json<T: DeserializeOwned>(request: Request<Incoming>) -> Result<T, ApiError> {
+    let json_content = is_json_content(&request);
+    let declared = request.headers().contains_key(CONTENT_TYPE);
+    let body = http_body_util::Limited::new(request.into_body(), MAX_BODY_BYTES)
+        .collect()
+        .await
+        .map_err(|e| ApiError::bad_request(format!("요청 본문을 읽지 못했습니다: {e}")))?
+        .to_bytes();
+    let empty = body.iter().all(u8::is_ascii_whitespace);
+    if !json_content && (declared || !empty) {
+        return Err(ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "요청 본문은 application/json이어야 합니다.",
+        ));
+    }
+    let body: &[u8] = if empty { b"{}" } else { &body };
+    serde_json::from_slice(body)
+        .map_err(|e| ApiError::bad_request(format!("요청 본문이 올바른 JSON이 아닙니다: {e}")))
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    let body = serde_json::to_vec(body).unwrap_or_else(|_| b"null".to_vec());
+    let mut response = Response::new(Full::new(Bytes::from(body)).boxed());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn error_response(error: ApiError) -> Response<Body> {
+    json_response(error.status, &json!({ "error": error.message }))
+}
+
+fn is_json_content<B>(request: &Request<B>) -> bool {
+    request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+}
+
+fn authorized<B>(token: &str, request: &Request<B>) -> bool {
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(Cow::Borrowed);
+    bearer
+        .or_else(|| query_param(request.uri().query(), "token"))
+        .is_some_and(|given| tokens_match(given.trim(), token))
+}
+
+/// Refuses requests a browser sent from a page of another origin, and on
+/// loopback, requests naming a host other than this machine (a rebound DNS
+/// name). Clients outside a browser send no `Origin`.
+fn same_origin<B>(request: &Request<B>, loopback: bool) -> bool {
+    let headers = request.headers();
+    let host = headers
+        .get(HOST)
+        .map(|value| value.to_str().unwrap_or_default());
+    if loopback && host.is_some_and(|host| !is_loopback_host(host)) {
+        return false;
+    }
+    match headers
+        .get(ORIGIN)
+        .map(|value| value.to_str().unwrap_or_default())
+    {
+        None => true,
+        Some(origin) => {
+            let origin_host = origin
+                .strip_prefix("http://")
+                .or_else(|| origin.strip_prefix("https://"));
+            origin_host.is_some_and(|origin_host| {
+                host.is_some_and(|host| origin_host.eq_ignore_ascii_case(host))
+            })
+        }
+    }
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Compares without stopping at the first differing byte
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The percent-decoded value of `name`
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<Cow<'a, str>> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| percent_decode_str(value).decode_utf8().ok())
+}
+
+fn event_matches_job(payload: &Value, job_id: Option<&str>) -> bool {
+    let Some(job_id) = job_id else {
+        return true;
+    };
+    payload
+        .get("jobId")
+        .and_then(Value::as_str)
+        .is_some_and(|event_job| {
+            event_job == job_id
+                || event_job
+                    .strip_prefix(job_id)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+}
+
+fn sse_event(channel: &str, payload: &Value) -> Bytes {
+    Bytes::from(format!("event: {channel}\ndata: {payload}\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(uri: &str, authorization: Option<&str>) -> Request<()> {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(value) = authorization {
+            builder = builder.header(AUTHORIZATION, value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn accepts_the_token_as_bearer_header_or_query_parameter() {
+        assert!(authorized(
+            "s3cret",
+            &request("/api/jobs", Some("Bearer s3cret"))
+        ));
+        assert!(authorized(
+            "s3cret",
+            &request("/api/events?jobId=a&token=s3cret", None)
+        ));
+        assert!(authorized(
+            "s3 cr+t",
+            &request("/api/jobs?token=s3%20cr%2Bt", None)
+        ));
+        assert!(!authorized("s3cret", &request("/api/jobs", None)));
+        assert!(!authorized(
+            "s3cret",
+            &request("/api/jobs", Some("Bearer s3cre"))
+        ));
+        assert!(!authorized(
+            "s3cret",
+            &request("/api/jobs?token=other", None)
+        ));
+
+        let options = ServeOptions::default();
+        assert_eq!(options.access_token().len(), 64);
+        assert_ne!(options.access_token(), options.access_token());
+    }
+
+    #[test]
+    fn refuses_requests_from_other_origins_and_hosts() {
+        let with_headers = |headers: &[(&str, &str)]| {
+            let mut builder = Request::builder().uri("/api/jobs");
+            for (name, value) in headers {
+                builder = builder.header(*name, *value);
+            }
+            builder.body(()).unwrap()
+        };
+        assert!(same_origin(
+            &with_headers(&[("host", "127.0.0.1:7420")]),
+            true
+        ));
+        assert!(same_origin(&with_headers(&[]), true));
+        assert!(same_origin(
+            &with_headers(&[
+                ("host", "localhost:7420"),
+                ("origin", "http://localhost:7420")
+            ]),
+            true
+        ));
+        assert!(same_origin(&with_headers(&[("host", "[::1]:7420")]), true));
+        assert!(!same_origin(
+            &with_headers(&[
+                ("host", "127.0.0.1:7420"),
+                ("origin", "https://evil.example")
+            ]),
+            true
+        ));
+        assert!(!same_origin(
+            &with_headers(&[("host", "127.0.0.1:7420"), ("origin", "null")]),
+            true
+        ));
+        // A DNS name rebound to 127.0.0.1
+        assert!(!same_origin(
+            &with_headers(&[
+                ("host", "evil.example:7420"),
+                ("origin", "http://evil.example:7420")
+            ]),
+            true
+        ));
+        assert!(same_origin(
+            &with_headers(&[
+                ("host", "translator.lan:7420"),
+                ("origin", "http://translator.lan:7420")
+            ]),
+            false
+        ));
+    }
+
+    #[test]
+    fn filters_events_by_job_including_per_language_jobs() {
+        let event = |job_id: &str| json!({ "type": "retry", "jobId": job_id, "attempt": 1 });
+        assert!(event_matches_job(&event("job-1"), None));
+        assert!(event_matches_job(&event("job-1"), Some("job-1")));
+        assert!(event_matches_job(&event("job-1-ko"), Some("job-1")));
+        assert!(!event_matches_job(&event("job-10"), Some("job-1")));
+        assert!(!event_matches_job(&json!({ "scanned": 3 }), Some("job-1")));
+
+        assert_eq!(
+            sse_event("translation-job-event", &json!({ "jobId": "job-1" })),
+            Bytes::from("event: translation-job-event\ndata: {\"jobId\":\"job-1\"}\n\n")
+        );
+    }
+
+    #[test]
+    fn serves_health_and_rejects_missing_tokens() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+            assert!(serve_listener(listener, " ".into()).await.is_err());
+
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+            let base = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(serve_listener(listener, "s3cret".into()));
+
+            let client = reqwest::Client::new();
+            let denied = client
+                .get(format!("{base}/api/health"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(denied.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+            let health = client
+                .get(format!("{base}/api/health"))
+                .bearer_auth("s3cret")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(health.status(), reqwest::StatusCode::OK);
+            let body: Value = health.json().await.unwrap();
+            assert_eq!(body["status"], "ok");
+
+            let missing = client
+                .get(format!("{base}/api/jobs/unknown-job"))
+                .bearer_auth("s3cret")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+            // A page in the browser posting a job without a preflight
+            let forged = client
+                .post(format!("{base}/api/jobs?token=s3cret"))
+                .header("content-type", "text/plain")
+                .body("{}")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(forged.status(), reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            let cross_site = client
+                .get(format!("{base}/api/jobs"))
+                .bearer_auth("s3cret")
+                .header("origin", "https://evil.example")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(cross_site.status(), reqwest::StatusCode::FORBIDDEN);
+        });
+    }
+}
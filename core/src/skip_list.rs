@@ -0,0 +1,127 @@
+/// "Do not translate" lists.
+///
+/// Some entries must stay exactly as the author wrote them: credits, version
+/// strings, names of people or servers. A job and a game profile can each
+/// list entry keys and regular expressions; matching lines are extracted
+/// like any other segment but never sent to the provider, and the reports
+/// record them as skipped.
+use crate::incremental::entry_key;
+use log::warn;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Entry keys and patterns to pass through untranslated
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkipList {
+    /// 그대로 둘 항목 키 (대소문자 구분)
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// 항목 키나 줄 전체에 맞춰 볼 정규식 (예: `^Credits`, `^v?\d+(\.\d+)+$`)
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl SkipList {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.patterns.is_empty()
+    }
+
+    /// This list together with `other`
+    pub fn merged(&self, other: &SkipList) -> SkipList {
+        SkipList {
+            keys: self.keys.iter().chain(&other.keys).cloned().collect(),
+            patterns: self.patterns.iter().chain(&other.patterns).cloned().collect(),
+        }
+    }
+
+    /// Compiles the patterns. Invalid ones are logged and skipped.
+    pub fn matcher(&self) -> SkipMatcher {
+        let patterns = self
+            .patterns
+            .iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(error) => {
+                    warn!("ignoring invalid skip pattern {pattern:?}: {error}");
+                    None
+                }
+            })
+            .collect();
+        SkipMatcher {
+            keys: self
+                .keys
+                .iter()
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .collect(),
+            patterns,
+        }
+    }
+}
+
+/// A compiled [`SkipList`]
+#[derive(Debug, Clone, Default)]
+pub struct SkipMatcher {
+    keys: HashSet<String>,
+    patterns: Vec<Regex>,
+}
+
+impl SkipMatcher {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Why `line` stays untranslated: the listed key or the pattern it
+    /// matches. Patterns are tried on the entry key first, then on the line.
+    pub fn reason(&self, line: &str) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let key = entry_key(line);
+        if let Some(key) = key.as_ref().filter(|key| self.keys.contains(*key)) {
+            return Some(format!("키 {key}"));
+        }
+        self.patterns
+            .iter()
+            .find(|pattern| {
+                key.as_deref().is_some_and(|key| pattern.is_match(key)) || pattern.is_match(line)
+            })
+            .map(|pattern| format!("패턴 {}", pattern.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_listed_keys_and_patterns() {
+        let job = SkipList {
+            keys: vec!["ModVersion".into()],
+            patterns: vec![r"^v?\d+(\.\d+)+$".into(), "(".into()],
+        };
+        let profile = SkipList {
+            keys: Vec::new(),
+            patterns: vec!["^Credits".into()],
+        };
+        let matcher = job.merged(&profile).matcher();
+
+        assert_eq!(
+            matcher.reason("<ModVersion>Release 2</ModVersion>").as_deref(),
+            Some("키 ModVersion")
+        );
+        assert_eq!(
+            matcher.reason("<CreditsTitle>Made by Ludeon</CreditsTitle>").as_deref(),
+            Some("패턴 ^Credits")
+        );
+        assert_eq!(
+            matcher.reason("v1.4.2").as_deref(),
+            Some(r"패턴 ^v?\d+(\.\d+)+$")
+        );
+        assert_eq!(matcher.reason("<Greeting>Hello there</Greeting>"), None);
+        assert!(SkipList::default().matcher().reason("v1.4.2").is_none());
+    }
+}
@@ -433,7 +433,7 @@ pub fn resolve_app_name(steamapps: &Path, app_id: &str) -> Option<String> {
         .filter(|name| !name.is_empty())
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub fn detect_steam_path() -> Result<SteamPathResponse, String> {
     let locator = SteamLocator::new();
     let discovered = locator.discover_path();
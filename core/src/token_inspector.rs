@@ -0,0 +1,123 @@
+/// Read-only view of what the Protector would mask in a string.
+///
+/// Powers the token highlighter in the UI: every protected span is returned
+/// with its class and position so the source can be colored without running
+/// a translation. Offsets are UTF-16 code units to match JavaScript strings.
+use crate::profiles::{list_profiles, GameProfile};
+use crate::protector::{ProtectionMode, Protector};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectedToken {
+    /// Token class code, e.g. `PRINTF` or `RPYVAR`
+    pub class: String,
+    pub value: String,
+    pub start: usize,
+    pub end: usize,
+    /// Marker sent to the model in place of the token, e.g. `⟦MT:PRINTF:0⟧`
+    pub marker: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtectionInspection {
+    pub profile_id: String,
+    pub masked_text: String,
+    pub tokens: Vec<InspectedToken>,
+    /// The whole string looks like code or an identifier and is skipped.
+    pub likely_code: bool,
+    /// Natural-language text remains once tokens are masked.
+    pub has_translatable_text: bool,
+}
+
+/// Inspects `text` with the Protector and the profile's own patterns, as a
+/// translation job would protect it.
+pub fn inspect_text(text: &str, profile: &GameProfile) -> ProtectionInspection {
+    let fragment = Protector::protect_with_patterns(
+        text,
+        ProtectionMode::Full,
+        &profile.placeholder_patterns(),
+    );
+    let tokens = fragment
+        .token_map()
+        .tokens
+        .iter()
+        .map(|token| InspectedToken {
+            class: token.kind.code().to_string(),
+            value: token.value.clone(),
+            start: utf16_offset(text, token.span.0),
+            end: utf16_offset(text, token.span.1),
+            marker: token.marker.clone(),
+        })
+        .collect();
+
+    ProtectionInspection {
+        profile_id: profile.id.clone(),
+        masked_text: fragment.masked_text().to_string(),
+        tokens,
+        likely_code: Protector::is_likely_code(text),
+        has_translatable_text: Protector::has_translatable_text(fragment.masked_text()),
+    }
+}
+
+fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+/// Tauri command: show which parts of `text` are protected from translation.
+/// `profileId` selects a game profile; the generic profile is used when omitted.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn inspect_protection(
+    text: String,
+    profileId: Option<String>,
+) -> Result<ProtectionInspection, String> {
+    let profile = match profileId.as_deref() {
+        None | Some("") => GameProfile::generic(),
+        Some(id) => list_profiles()
+            .into_iter()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| format!("알 수 없는 게임 프로필입니다: {}", id))?,
+    };
+    Ok(inspect_text(&text, &profile))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::paradox::ParadoxProfile;
+
+    #[test]
+    fn reports_protected_spans_in_utf16_offsets() {
+        let inspection = inspect_text("골드 %d개와 {0}", &GameProfile::generic());
+        let classes: Vec<&str> = inspection.tokens.iter().map(|t| t.class.as_str()).collect();
+        assert_eq!(classes, vec!["PRINTF", "DOTNET"]);
+        assert_eq!((inspection.tokens[0].start, inspection.tokens[0].end), (3, 5));
+        assert_eq!((inspection.tokens[1].start, inspection.tokens[1].end), (8, 11));
+        assert!(inspection.tokens[0].marker.starts_with("⟦MT:PRINTF:"));
+        assert!(inspection.has_translatable_text);
+        assert!(!inspection.likely_code);
+    }
+
+    #[test]
+    fn masks_profile_patterns_the_protector_does_not_cover() {
+        let inspection = inspect_text("Gain £gold£ in [3] days", &ParadoxProfile::profile());
+        assert_eq!(inspection.profile_id, "paradox");
+        assert_eq!(inspection.tokens.len(), 2);
+        assert_eq!(inspection.tokens[0].class, "PDXICON");
+        assert_eq!(inspection.tokens[1].class, "PROFILE");
+        assert_eq!(inspection.tokens[1].value, "[3]");
+        assert_eq!(inspection.tokens[1].marker, "⟦MT:PROFILE:1⟧");
+        assert!(inspection.masked_text.contains("⟦MT:PROFILE:1⟧"));
+    }
+
+    #[test]
+    fn rejects_unknown_profiles() {
+        assert!(inspect_protection("Hi".into(), Some("nope".into())).is_err());
+        assert_eq!(
+            inspect_protection("Hi".into(), None).unwrap().profile_id,
+            "generic"
+        );
+    }
+}
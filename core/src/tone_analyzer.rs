@@ -86,6 +86,118 @@ impl KoreanHonorific {
     }
 }
 
+impl KoreanHonorific {
+    /// 존댓말 (해요체, 합쇼체)
+    pub fn is_polite(&self) -> bool {
+        matches!(self, KoreanHonorific::Haeyoche | KoreanHonorific::Hapsochu)
+    }
+}
+
+/// Tone a translation job asks for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetTone {
+    /// Polite, formal speech
+    Formal,
+    /// Informal, friendly speech
+    Casual,
+    /// The formality of the source text
+    Preserve,
+}
+
+impl TargetTone {
+    /// Value of the `{{tone}}` prompt variable
+    pub fn prompt_instruction(&self, target_lang: &str) -> String {
+        let korean = is_korean(target_lang);
+        match (self, korean) {
+            (TargetTone::Formal, true) => {
+                "formal; use polite Korean speech (합니다체, or 해요체 for dialogue)".to_string()
+            }
+            (TargetTone::Casual, true) => {
+                "casual; use informal Korean speech (반말: 해체 or 해라체)".to_string()
+            }
+            (TargetTone::Formal, false) => "formal and polite".to_string(),
+            (TargetTone::Casual, false) => "casual and friendly".to_string(),
+            (TargetTone::Preserve, _) => {
+                "match the formality and politeness of the source text".to_string()
+            }
+        }
+    }
+
+    /// Whether translations should use polite speech; `Preserve` follows the
+    /// style recommended for the source
+    pub fn expects_polite(&self, source: &ToneAnalysis) -> bool {
+        match self {
+            TargetTone::Formal => true,
+            TargetTone::Casual => false,
+            TargetTone::Preserve => source.recommended_style.korean_honorific.is_polite(),
+        }
+    }
+}
+
+fn is_korean(target_lang: &str) -> bool {
+    let lang = target_lang.trim().to_lowercase();
+    lang.starts_with("ko") || lang == "한국어"
+}
+
+/// Speech level of each Korean sentence in `text`, judged from its ending.
+/// Sentences ending in a noun (common in UI labels) are skipped.
+pub fn korean_speech_levels(text: &str) -> Vec<KoreanHonorific> {
+    let is_hangul = |c: char| ('가'..='힣').contains(&c);
+    text.split(|c: char| !is_hangul(c) && !c.is_whitespace())
+        .filter_map(|chunk| {
+            let chunk = chunk.trim();
+            let words = chunk.split_whitespace().count();
+            let level = if chunk.ends_with("니다")
+                || chunk.ends_with("니까")
+                || chunk.ends_with("시오")
+            {
+                KoreanHonorific::Hapsochu
+            } else if chunk.ends_with('요') {
+                KoreanHonorific::Haeyoche
+            } else if words < 2 {
+                // A lone word ending like a casual verb is usually a noun
+                return None;
+            } else if ["다", "냐", "자", "라"].iter().any(|end| chunk.ends_with(end)) {
+                KoreanHonorific::Haerache
+            } else if ["해", "야", "어", "아", "지", "래", "게", "걸"]
+                .iter()
+                .any(|end| chunk.ends_with(end))
+            {
+                KoreanHonorific::Haechae
+            } else {
+                return None;
+            };
+            Some(level)
+        })
+        .collect()
+}
+
+/// QC warning when a translation does not use the speech level the job
+/// asked for. Only Korean translations are checked; a translation with both
+/// polite and casual sentences (e.g. quoted dialogue) is not flagged.
+pub fn tone_mismatch(expects_polite: bool, target_lang: &str, translation: &str) -> Option<String> {
+    if !is_korean(target_lang) {
+        return None;
+    }
+    let levels = korean_speech_levels(translation);
+    let polite = levels.iter().filter(|level| level.is_polite()).count();
+    let casual = levels.len() - polite;
+    if expects_polite && casual > 0 && polite == 0 {
+        Some(format!(
+            "어조 불일치: 존댓말을 요청했지만 {}로 번역되었습니다",
+            levels[0].description()
+        ))
+    } else if !expects_polite && polite > 0 && casual == 0 {
+        Some(format!(
+            "어조 불일치: 반말을 요청했지만 {}로 번역되었습니다",
+            levels[0].description()
+        ))
+    } else {
+        None
+    }
+}
+
 /// Japanese formality levels
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum JapaneseFormality {
@@ -526,6 +638,38 @@ mod tests {
         ));
     }
     
+    #[test]
+    fn test_korean_speech_levels() {
+        assert_eq!(
+            korean_speech_levels("<Start>게임을 시작합니다.</Start>"),
+            vec![KoreanHonorific::Hapsochu]
+        );
+        assert_eq!(
+            korean_speech_levels("저장할까요? 나중에 하자!"),
+            vec![KoreanHonorific::Haeyoche, KoreanHonorific::Haerache]
+        );
+        assert_eq!(korean_speech_levels("{0}개를 얻었어"), vec![KoreanHonorific::Haechae]);
+        // Nouns and single words do not count
+        assert!(korean_speech_levels("<Language>언어</Language>").is_empty());
+        assert!(korean_speech_levels("새 게임").is_empty());
+    }
+
+    #[test]
+    fn test_tone_mismatch() {
+        assert!(tone_mismatch(true, "ko", "문을 열었어.").is_some());
+        assert!(tone_mismatch(true, "ko", "문을 열었습니다.").is_none());
+        assert!(tone_mismatch(false, "ko", "문을 열었어요.").is_some());
+        assert!(tone_mismatch(false, "ko", "\"열었어요.\" 그가 말했다.").is_none());
+        assert!(tone_mismatch(true, "ja", "ドアを開けた").is_none());
+        assert!(tone_mismatch(true, "ko", "설정").is_none());
+
+        let analyzer = ToneAnalyzer::new();
+        let casual = analyzer.analyze(&["Hey, gonna grab some stuff", "Yeah, cool stuff"]);
+        assert!(!TargetTone::Preserve.expects_polite(&casual));
+        assert!(TargetTone::Formal.expects_polite(&casual));
+        assert!(TargetTone::Casual.prompt_instruction("ko-KR").contains("반말"));
+    }
+
     #[test]
     fn test_generate_prompt_hint() {
         let analyzer = ToneAnalyzer::new();
@@ -0,0 +1,524 @@
+/// Triage of the segments a job could not translate.
+///
+/// At the end of a job the rolled-back and failed segments are stored with
+/// their validation failure reports and what is needed to ask the provider
+/// again. The user can then retranslate a few of them or accept the source
+/// text without running the whole job again.
+use crate::access_mode::ensure_writable;
+use crate::ai::{
+    prompt::{PromptTemplate, PromptVars},
+    translate_document, translate_text_with_context, ModelParams, ProviderId, TranslationSettings,
+};
+use crate::backup::write_atomic;
+use crate::config::RequestOptions;
+use crate::format_validator::validate_for_format;
+use crate::formats::FileFormat;
+use crate::jobs::{build_http_client, validation_retry_note};
+use crate::placeholder_validator::{
+    PlaceholderValidator, Segment as ValidatorSegment, ValidationFailureReport, ValidatorConfig,
+};
+use crate::protector::{MarkerScheme, ProtectedFragment, ProtectionMode, Protector};
+use crate::qc_report::stored_job_file;
+use crate::quality::{validate_segment, SegmentLimits};
+use crate::review::{ReviewMetadata, SegmentReviewStatus};
+use crate::secrets::stored_api_key;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Settings shared by the failed segments of one source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageFile {
+    pub file_path: String,
+    pub output_path: PathBuf,
+    /// Prompts with the mod's overrides
+    #[serde(default)]
+    pub prompt: PromptTemplate,
+    #[serde(default)]
+    pub prompt_vars: PromptVars,
+    /// Extra placeholder patterns of the mod's game profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placeholder_patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageResolution {
+    Retranslated,
+    AcceptedOriginal,
+}
+
+/// A segment whose source text was kept in the output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedSegment {
+    /// `<job id>:<index>`, unique across jobs
+    pub segment_id: String,
+    pub file_index: usize,
+    pub file_path: String,
+    pub line_number: usize,
+    pub source: String,
+    pub prefix: String,
+    pub suffix: String,
+    pub status: SegmentReviewStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<ValidationFailureReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<TriageResolution>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+}
+
+impl FailedSegment {
+    /// The output line while the source text is kept
+    fn original_line(&self) -> String {
+        format!("{}{}{}", self.prefix, self.source, self.suffix)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageStore {
+    #[serde(flatten)]
+    pub metadata: ReviewMetadata,
+    pub provider_id: ProviderId,
+    #[serde(default)]
+    pub params: ModelParams,
+    #[serde(default)]
+    pub request_options: RequestOptions,
+    /// 작업이 요청을 보낸 API 주소 (번역기 주소를 재정의한 경우)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_base_url: Option<String>,
+    #[serde(default)]
+    pub validator_config: ValidatorConfig,
+    #[serde(default)]
+    pub marker_scheme: MarkerScheme,
+    pub files: Vec<TriageFile>,
+    pub segments: Vec<FailedSegment>,
+}
+
+impl TriageStore {
+    pub fn open_segments(&self) -> impl Iterator<Item = &FailedSegment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.resolution.is_none())
+    }
+
+    /// Marks the open segments among `segment_ids` as resolved with the
+    /// source text. Returns how many were marked.
+    pub fn accept_original(&mut self, segment_ids: &HashSet<&str>) -> usize {
+        let mut accepted = 0;
+        for segment in &mut self.segments {
+            if segment.resolution.is_none() && segment_ids.contains(segment.segment_id.as_str()) {
+                segment.resolution = Some(TriageResolution::AcceptedOriginal);
+                accepted += 1;
+            }
+        }
+        accepted
+    }
+
+    fn settings(&self, file: &TriageFile, api_key: String) -> TranslationSettings {
+        TranslationSettings {
+            provider: self.provider_id,
+            api_key,
+            model_id: self.metadata.model_id.clone(),
+            source_lang: self.metadata.source_lang.clone(),
+            target_lang: self.metadata.target_lang.clone(),
+            params: self.params.clone(),
+            prompt: file.prompt.clone(),
+            prompt_vars: file.prompt_vars.clone(),
+            rate_limit: Default::default(),
+            base_url: self.api_base_url.clone(),
+        }
+    }
+}
+
+/// Outcome of retranslating one segment
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetranslationResult {
+    pub segment_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn triage_store_path(job_id: &str) -> PathBuf {
+    stored_job_file(job_id, "failures.json")
+}
+
+pub fn read_triage_store(path: &Path) -> io::Result<TriageStore> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(io::Error::from)
+}
+
+pub fn write_triage_store(path: &Path, store: &TriageStore) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(store)?;
+    write_atomic(path, contents.as_bytes())
+}
+
+/// Stores the failed segments of a finished job, or drops the store of an
+/// earlier run when none failed this time
+pub fn save_triage_store(store: &TriageStore) {
+    let path = triage_store_path(&store.metadata.job_id);
+    let result = if store.segments.is_empty() {
+        match fs::remove_file(&path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    } else {
+        write_triage_store(&path, store)
+    };
+    if let Err(error) = result {
+        log::warn!(
+            "failed to store failed segments {}: {error}",
+            path.display()
+        );
+    }
+}
+
+fn load_triage_store(job_id: &str) -> Result<TriageStore, String> {
+    read_triage_store(&triage_store_path(job_id)).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => format!("작업 {job_id}에 실패한 세그먼트 기록이 없습니다."),
+        _ => format!("실패한 세그먼트 기록을 읽을 수 없습니다: {error}"),
+    })
+}
+
+fn store_triage(store: &TriageStore) -> Result<(), String> {
+    write_triage_store(&triage_store_path(&store.metadata.job_id), store)
+        .map_err(|error| format!("실패한 세그먼트 기록을 저장할 수 없습니다: {error}"))
+}
+
+/// Replaces the line equal to `original` closest to `line_number` (1-based)
+/// and keeps its line ending. Provenance headers shift the output lines, so
+/// the line is looked up by content rather than by position alone.
+pub fn replace_output_line(
+    contents: &str,
+    line_number: usize,
+    original: &str,
+    replacement: &str,
+) -> Option<String> {
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let target = line_number.saturating_sub(1);
+    let (index, line) = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim_end_matches(['\r', '\n']) == original)
+        .min_by_key(|(index, _)| index.abs_diff(target))?;
+    let ending = &line[original.len()..];
+    let mut updated = String::with_capacity(contents.len() + replacement.len());
+    for (current, text) in lines.iter().enumerate() {
+        if current == index {
+            updated.push_str(replacement);
+            updated.push_str(ending);
+        } else {
+            updated.push_str(text);
+        }
+    }
+    Some(updated)
+}
+
+/// Tauri command: the segments of a job that still keep their source text
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn list_validation_failures(jobId: String) -> Result<Vec<FailedSegment>, String> {
+    match read_triage_store(&triage_store_path(&jobId)) {
+        Ok(store) => Ok(store.open_segments().cloned().collect()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(format!("실패한 세그먼트 기록을 읽을 수 없습니다: {error}")),
+    }
+}
+
+/// Tauri command: keeps the source text of the given segments and removes
+/// them from the failure list. Returns the number of segments accepted.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn accept_original(segmentIds: Vec<String>) -> Result<usize, String> {
+    ensure_writable("원문 유지")?;
+    let mut by_job: Vec<(&str, HashSet<&str>)> = Vec::new();
+    for segment_id in &segmentIds {
+        let (job_id, _) = segment_id
+            .rsplit_once(':')
+            .ok_or_else(|| format!("세그먼트 ID 형식이 잘못되었습니다: {segment_id}"))?;
+        match by_job.iter_mut().find(|(id, _)| *id == job_id) {
+            Some((_, ids)) => {
+                ids.insert(segment_id.as_str());
+            }
+            None => by_job.push((job_id, HashSet::from([segment_id.as_str()]))),
+        }
+    }
+
+    let mut accepted = 0;
+    for (job_id, ids) in by_job {
+        let mut store = load_triage_store(job_id)?;
+        let count = store.accept_original(&ids);
+        if count > 0 {
+            store_triage(&store)?;
+        }
+        accepted += count;
+    }
+    Ok(accepted)
+}
+
+/// Tauri command: asks the job's provider again for the given segments,
+/// naming the tokens the earlier translation lost, and writes the
+/// translations that pass validation into the output files.
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub async fn retranslate_failed_segments(
+    jobId: String,
+    segmentIds: Vec<String>,
+) -> Result<Vec<RetranslationResult>, String> {
+    ensure_writable("재번역")?;
+    let mut store = load_triage_store(&jobId)?;
+    let api_key = stored_api_key(store.provider_id)
+        .ok_or_else(|| "API 키가 설정되지 않았습니다.".to_string())?;
+    let client = build_http_client(&store.request_options)
+        .map_err(|_| "HTTP 클라이언트를 초기화하지 못했습니다.".to_string())?;
+
+    let mut results = Vec::with_capacity(segmentIds.len());
+    for segment_id in segmentIds {
+        let Some(index) = store
+            .segments
+            .iter()
+            .position(|segment| segment.segment_id == segment_id && segment.resolution.is_none())
+        else {
+            results.push(RetranslationResult {
+                segment_id: segment_id.clone(),
+                translation: None,
+                error: Some(format!("처리할 세그먼트가 없습니다: {segment_id}")),
+            });
+            continue;
+        };
+        let outcome = retranslate_segment(&client, &store, index, api_key.trim()).await;
+        let segment = &mut store.segments[index];
+        let result = match outcome {
+            Ok(translation) => {
+                segment.resolution = Some(TriageResolution::Retranslated);
+                segment.translation = Some(translation.clone());
+                RetranslationResult {
+                    segment_id,
+                    translation: Some(translation),
+                    error: None,
+                }
+            }
+            Err(failure) => {
+                if let Some(report) = failure.report {
+                    segment.failure = Some(report);
+                }
+                segment.messages = vec![failure.message.clone()];
+                RetranslationResult {
+                    segment_id,
+                    translation: None,
+                    error: Some(failure.message),
+                }
+            }
+        };
+        results.push(result);
+        store_triage(&store)?;
+    }
+    Ok(results)
+}
+
+struct RetranslationFailure {
+    message: String,
+    report: Option<ValidationFailureReport>,
+}
+
+impl From<String> for RetranslationFailure {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            report: None,
+        }
+    }
+}
+
+async fn retranslate_segment(
+    client: &reqwest::Client,
+    store: &TriageStore,
+    index: usize,
+    api_key: &str,
+) -> Result<String, RetranslationFailure> {
+    let segment = &store.segments[index];
+    let file = store
+        .files
+        .get(segment.file_index)
+        .ok_or_else(|| format!("파일 정보를 찾을 수 없습니다: {}", segment.file_path))?;
+    let patterns: Vec<Regex> = file
+        .placeholder_patterns
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .collect();
+    let protect = |text: &str| -> ProtectedFragment {
+        Protector::protect_with_scheme(text, ProtectionMode::Full, &patterns, store.marker_scheme)
+    };
+    let fragment = protect(&segment.source);
+    let settings = store.settings(file, api_key.to_string());
+
+    let output = if settings.needs_document_mode(&segment.source) {
+        translate_document(client, &settings, &fragment, protect).await
+    } else {
+        let note = segment
+            .failure
+            .as_ref()
+            .map(|report| validation_retry_note(&fragment, report));
+        translate_text_with_context(client, &settings, &fragment, note.as_deref()).await
+    }
+    .map_err(|error| format!("번역 요청이 실패했습니다: {error}"))?;
+
+    let contents = fs::read_to_string(&file.output_path).map_err(|error| {
+        format!(
+            "번역 파일을 읽을 수 없습니다 ({}): {}",
+            file.output_path.display(),
+            error
+        )
+    })?;
+    let format = FileFormat::from_path(&file.output_path).refine_with_content(&contents);
+
+    let token_types = fragment
+        .token_map()
+        .tokens
+        .iter()
+        .map(|token| token.kind.code().to_string())
+        .collect();
+    let validator_segment = ValidatorSegment::new(
+        segment.file_path.clone(),
+        segment.line_number as u32,
+        format!("line_{}", segment.line_number.saturating_sub(1)),
+        segment.source.clone(),
+        fragment.masked_text().to_string(),
+    )
+    .with_format(format.into())
+    .with_token_types(token_types);
+    let validator = PlaceholderValidator::new(store.validator_config.clone())
+        .with_token_map(fragment.token_map());
+    let value = match validator.validate(&validator_segment, &output.text) {
+        Ok(success) => success.value,
+        Err(report) => {
+            return Err(RetranslationFailure {
+                message: format!("Placeholder validation failed: {:?}", report.code),
+                report: Some(report),
+            })
+        }
+    };
+    let quality = validate_segment(&segment.source, &value, &SegmentLimits::default());
+    if !quality.is_pass() {
+        return Err(quality.errors.join("; ").into());
+    }
+
+    let replacement = format!("{}{}{}", segment.prefix, value, segment.suffix);
+    let updated = replace_output_line(
+        &contents,
+        segment.line_number,
+        &segment.original_line(),
+        &replacement,
+    )
+    .ok_or_else(|| {
+        format!(
+            "출력 파일에서 {}행의 원문을 찾을 수 없습니다.",
+            segment.line_number
+        )
+    })?;
+    if validate_for_format(format, &contents).is_ok() {
+        if let Err(error) = validate_for_format(format, &updated) {
+            return Err(format!("번역을 넣으면 파일 형식이 깨집니다: {error}").into());
+        }
+    }
+    write_atomic(&file.output_path, updated.as_bytes()).map_err(|error| {
+        format!(
+            "번역 파일을 저장할 수 없습니다 ({}): {}",
+            file.output_path.display(),
+            error
+        )
+    })?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed_segment(index: usize) -> FailedSegment {
+        FailedSegment {
+            segment_id: format!("job-1:{index}"),
+            file_index: 0,
+            file_path: "Languages/English/Keyed/Menu.xml".into(),
+            line_number: index + 2,
+            source: "Hello {0}".into(),
+            prefix: "  <Greeting>".into(),
+            suffix: "</Greeting>".into(),
+            status: SegmentReviewStatus::RolledBack,
+            messages: Vec::new(),
+            failure: None,
+            resolution: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn output_line_is_found_near_its_source_position() {
+        let contents =
+            "<!-- provenance -->\r\n<LanguageData>\r\n  <A>Hello</A>\r\n  <B>Hello</B>\r\n";
+        let updated = replace_output_line(contents, 3, "  <B>Hello</B>", "  <B>안녕</B>").unwrap();
+        assert_eq!(
+            updated,
+            "<!-- provenance -->\r\n<LanguageData>\r\n  <A>Hello</A>\r\n  <B>안녕</B>\r\n"
+        );
+        assert_eq!(
+            replace_output_line(contents, 3, "  <C>Hello</C>", "x"),
+            None
+        );
+    }
+
+    #[test]
+    fn accepted_segments_leave_the_open_list() {
+        let mut store = TriageStore {
+            metadata: ReviewMetadata {
+                job_id: "job-1".into(),
+                source_lang: "en".into(),
+                target_lang: "ko".into(),
+                provider: "Gemini".into(),
+                model_id: "gemini-2.5-flash".into(),
+            },
+            provider_id: ProviderId::Gemini,
+            params: ModelParams::default(),
+            request_options: RequestOptions::default(),
+            api_base_url: None,
+            validator_config: ValidatorConfig::default(),
+            marker_scheme: MarkerScheme::default(),
+            files: Vec::new(),
+            segments: vec![failed_segment(0), failed_segment(1)],
+        };
+
+        let ids = HashSet::from(["job-1:1", "job-2:0"]);
+        assert_eq!(store.accept_original(&ids), 1);
+        assert_eq!(store.accept_original(&ids), 0);
+        let open: Vec<_> = store
+            .open_segments()
+            .map(|s| s.segment_id.as_str())
+            .collect();
+        assert_eq!(open, ["job-1:0"]);
+        assert_eq!(
+            store.segments[0].original_line(),
+            "  <Greeting>Hello {0}</Greeting>"
+        );
+
+        let json = serde_json::to_string(&store).unwrap();
+        let read: TriageStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            read.segments[1].resolution,
+            Some(TriageResolution::AcceptedOriginal)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/// Opt-in local usage statistics.
+///
+/// Counts which providers, file formats, games and job features are used.
+/// Nothing is ever sent anywhere: the counters live in a JSON file under the
+/// local app data dir and can be exported by the user to attach to an issue.
+use crate::paths::app_data_dir;
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const USAGE_STATS_FILE_NAME: &str = "usage-stats.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_recorded_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_recorded_at: Option<DateTime<Utc>>,
+    pub jobs_started: u64,
+    #[serde(default)]
+    pub providers: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub formats: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub games: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub features: BTreeMap<String, u64>,
+}
+
+/// What a single job contributes to the statistics.
+#[derive(Debug, Clone, Default)]
+pub struct JobUsageEvent {
+    pub provider: String,
+    /// File extensions, one entry per translated file.
+    pub formats: Vec<String>,
+    /// Game profile ids, one entry per distinct mod.
+    pub games: Vec<String>,
+    pub features: Vec<&'static str>,
+}
+
+impl UsageStats {
+    pub fn record_job(&mut self, event: &JobUsageEvent, now: DateTime<Utc>) {
+        self.first_recorded_at.get_or_insert(now);
+        self.last_recorded_at = Some(now);
+        self.jobs_started = self.jobs_started.saturating_add(1);
+        increment(&mut self.providers, &event.provider);
+        for format in &event.formats {
+            increment(&mut self.formats, format);
+        }
+        for game in &event.games {
+            increment(&mut self.games, game);
+        }
+        for feature in &event.features {
+            increment(&mut self.features, feature);
+        }
+    }
+}
+
+fn increment(counters: &mut BTreeMap<String, u64>, key: &str) {
+    let key = key.trim().to_lowercase();
+    if key.is_empty() {
+        return;
+    }
+    let counter = counters.entry(key).or_insert(0);
+    *counter = counter.saturating_add(1);
+}
+
+/// On-disk file: the opt-in flag and the counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageStatsFile {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    stats: UsageStats,
+}
+
+impl UsageStatsFile {
+    fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|error| {
+            warn!("ignoring unreadable usage stats {}: {}", path.display(), error);
+            Self::default()
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create usage stats dir: {}", e))?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize usage stats: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write usage stats: {}", e))
+    }
+}
+
+static USAGE_STATS: Lazy<Mutex<UsageStatsFile>> =
+    Lazy::new(|| Mutex::new(UsageStatsFile::load(&get_usage_stats_path())));
+
+/// Location of the usage statistics file.
+pub fn get_usage_stats_path() -> PathBuf {
+    app_data_dir().join(USAGE_STATS_FILE_NAME)
+}
+
+/// Records a job if the user opted in; does nothing otherwise. The event is
+/// built lazily so callers pay for game detection only when enabled.
+pub fn record_job_usage(build_event: impl FnOnce() -> JobUsageEvent) {
+    let Ok(mut guard) = USAGE_STATS.lock() else {
+        return;
+    };
+    if !guard.enabled {
+        return;
+    }
+    guard.stats.record_job(&build_event(), Utc::now());
+    if let Err(error) = guard.save(&get_usage_stats_path()) {
+        warn!("{}", error);
+    }
+}
+
+/// Tauri command to check whether local usage statistics are enabled
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn get_usage_stats_enabled() -> Result<bool, String> {
+    USAGE_STATS
+        .lock()
+        .map(|guard| guard.enabled)
+        .map_err(|_| "usage stats lock poisoned".to_string())
+}
+
+/// Tauri command to opt in to or out of local usage statistics
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn set_usage_stats_enabled(enabled: bool) -> Result<(), String> {
+    let mut guard = USAGE_STATS
+        .lock()
+        .map_err(|_| "usage stats lock poisoned".to_string())?;
+    guard.enabled = enabled;
+    guard.save(&get_usage_stats_path())
+}
+
+/// Tauri command to export usage statistics as JSON
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn export_usage_stats() -> Result<String, String> {
+    let guard = USAGE_STATS
+        .lock()
+        .map_err(|_| "usage stats lock poisoned".to_string())?;
+    serde_json::to_string_pretty(&guard.stats)
+        .map_err(|e| format!("Failed to serialize usage stats: {}", e))
+}
+
+/// Tauri command to clear usage statistics (keeps the opt-in choice)
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub async fn reset_usage_stats() -> Result<(), String> {
+    let mut guard = USAGE_STATS
+        .lock()
+        .map_err(|_| "usage stats lock poisoned".to_string())?;
+    guard.stats = UsageStats::default();
+    guard.save(&get_usage_stats_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_job_counts_each_dimension() {
+        let mut stats = UsageStats::default();
+        let event = JobUsageEvent {
+            provider: "Gemini".into(),
+            formats: vec!["xml".into(), "XML".into(), "json".into()],
+            games: vec!["rimworld".into()],
+            features: vec!["strict_mode"],
+        };
+        let now = Utc::now();
+        stats.record_job(&event, now);
+        stats.record_job(&event, now);
+
+        assert_eq!(stats.jobs_started, 2);
+        assert_eq!(stats.providers["gemini"], 2);
+        assert_eq!(stats.formats["xml"], 4);
+        assert_eq!(stats.formats["json"], 2);
+        assert_eq!(stats.games["rimworld"], 2);
+        assert_eq!(stats.features["strict_mode"], 2);
+        assert_eq!(stats.first_recorded_at, Some(now));
+    }
+
+    #[test]
+    fn stats_file_is_disabled_by_default_and_roundtrips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(USAGE_STATS_FILE_NAME);
+        let mut file = UsageStatsFile::load(&path);
+        assert!(!file.enabled);
+
+        file.enabled = true;
+        file.stats.record_job(
+            &JobUsageEvent {
+                provider: "Claude".into(),
+                ..JobUsageEvent::default()
+            },
+            Utc::now(),
+        );
+        file.save(&path).unwrap();
+
+        let loaded = UsageStatsFile::load(&path);
+        assert!(loaded.enabled);
+        assert_eq!(loaded.stats, file.stats);
+    }
+}
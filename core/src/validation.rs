@@ -1,4 +1,7 @@
+use crate::access_mode::is_offline;
+use crate::ai::catalog::{self, ModelInfo};
 use crate::ai::ProviderId;
+use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use reqwest::{Client, StatusCode, Url};
 use serde::Serialize;
@@ -87,35 +90,109 @@ pub struct ProviderValidationResult {
     pub validation_status: KeyValidationState,
     #[serde(default)]
     pub models: Vec<String>,
+    /// 모델별 컨텍스트 창, 가격대, 번역 권장 여부
+    #[serde(default)]
+    pub catalog: Vec<ModelInfo>,
+    /// 저장해 둔 목록을 돌려준 경우 그 목록을 받아온 시각 (오프라인 모드, 연결 실패)
+    #[serde(default)]
+    pub cached_at: Option<DateTime<Utc>>,
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub async fn validate_api_key_and_list_models(
     provider: String,
     api_key: String,
     model_hint: Option<String>,
+    force_refresh: Option<bool>,
 ) -> Result<ProviderValidationResult, String> {
     let trimmed_key = api_key.trim().to_string();
     if trimmed_key.is_empty() {
         return Ok(ProviderValidationResult {
             validation_status: KeyValidationState::Unauthorized,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         });
     }
 
     let provider_id = ProviderId::try_from(provider.as_str())
         .map_err(|_| format!("unsupported provider: {provider}"))?;
 
-    let result = match provider_id {
+    if is_offline() {
+        return Ok(
+            saved_result(provider_id, &trimmed_key).unwrap_or(ProviderValidationResult {
+                validation_status: KeyValidationState::NetworkError,
+                models: Vec::new(),
+                catalog: Vec::new(),
+                cached_at: None,
+            }),
+        );
+    }
+
+    if !force_refresh.unwrap_or(false) {
+        if let Some(result) = cached_result(provider_id, &trimmed_key, model_hint.as_deref()) {
+            return Ok(result);
+        }
+    }
+
+    let mut result = match provider_id {
         ProviderId::Gemini => validate_gemini(&trimmed_key, model_hint.as_deref()).await,
         ProviderId::Gpt => validate_openai(&trimmed_key, model_hint.as_deref()).await,
         ProviderId::Claude => validate_anthropic(&trimmed_key, model_hint.as_deref()).await,
         ProviderId::Grok => validate_grok(&trimmed_key, model_hint.as_deref()).await,
     };
 
+    match result.validation_status {
+        KeyValidationState::Valid => {
+            result.catalog = catalog::store_models(provider_id, &trimmed_key, &result.models);
+        }
+        KeyValidationState::Unauthorized | KeyValidationState::Forbidden => {
+            catalog::invalidate(provider_id);
+        }
+        KeyValidationState::NetworkError => {
+            // Keep offering the last list fetched with this key
+            if let Some(saved) = saved_result(provider_id, &trimmed_key) {
+                result.models = saved.models;
+                result.catalog = saved.catalog;
+                result.cached_at = saved.cached_at;
+            }
+        }
+    }
+
     Ok(result)
 }
 
+/// The list saved on disk for the same key, however old
+fn saved_result(provider: ProviderId, api_key: &str) -> Option<ProviderValidationResult> {
+    let (saved, fetched_at) = catalog::saved_models(provider, Some(api_key))?;
+    Some(ProviderValidationResult {
+        validation_status: KeyValidationState::Valid,
+        models: saved.iter().map(|model| model.id.clone()).collect(),
+        catalog: saved,
+        cached_at: Some(fetched_at),
+    })
+}
+
+/// A recent successful validation of the same key, unless the hinted model
+/// was not in its list and still needs to be tried
+fn cached_result(
+    provider: ProviderId,
+    api_key: &str,
+    model_hint: Option<&str>,
+) -> Option<ProviderValidationResult> {
+    let cached = catalog::cached_models(provider, Some(api_key))?;
+    let hint = model_hint.map(str::trim).filter(|hint| !hint.is_empty());
+    if hint.is_some_and(|hint| !cached.iter().any(|model| model.id == hint)) {
+        return None;
+    }
+    Some(ProviderValidationResult {
+        validation_status: KeyValidationState::Valid,
+        models: cached.iter().map(|model| model.id.clone()).collect(),
+        catalog: cached,
+        cached_at: None,
+    })
+}
+
 async fn validate_openai(api_key: &str, model_hint: Option<&str>) -> ProviderValidationResult {
     let models = match fetch_openai_models(api_key).await {
         Ok(list) => list,
@@ -123,6 +200,8 @@ async fn validate_openai(api_key: &str, model_hint: Option<&str>) -> ProviderVal
             return ProviderValidationResult {
                 validation_status: status,
                 models: Vec::new(),
+                catalog: Vec::new(),
+                cached_at: None,
             };
         }
     };
@@ -131,6 +210,8 @@ async fn validate_openai(api_key: &str, model_hint: Option<&str>) -> ProviderVal
         return ProviderValidationResult {
             validation_status: KeyValidationState::NetworkError,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         };
     };
 
@@ -143,11 +224,15 @@ async fn validate_openai(api_key: &str, model_hint: Option<&str>) -> ProviderVal
             ProviderValidationResult {
                 validation_status: KeyValidationState::Valid,
                 models: dedupe_and_sort(merged),
+                catalog: Vec::new(),
+                cached_at: None,
             }
         }
         Err(status) => ProviderValidationResult {
             validation_status: status,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         },
     }
 }
@@ -219,6 +304,8 @@ async fn validate_anthropic(api_key: &str, model_hint: Option<&str>) -> Provider
             return ProviderValidationResult {
                 validation_status: status,
                 models: Vec::new(),
+                catalog: Vec::new(),
+                cached_at: None,
             };
         }
     };
@@ -228,6 +315,8 @@ async fn validate_anthropic(api_key: &str, model_hint: Option<&str>) -> Provider
         return ProviderValidationResult {
             validation_status: KeyValidationState::NetworkError,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         };
     };
 
@@ -240,11 +329,15 @@ async fn validate_anthropic(api_key: &str, model_hint: Option<&str>) -> Provider
             ProviderValidationResult {
                 validation_status: KeyValidationState::Valid,
                 models: dedupe_and_sort(merged),
+                catalog: Vec::new(),
+                cached_at: None,
             }
         }
         Err(status) => ProviderValidationResult {
             validation_status: status,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         },
     }
 }
@@ -322,6 +415,8 @@ async fn validate_gemini(api_key: &str, model_hint: Option<&str>) -> ProviderVal
             return ProviderValidationResult {
                 validation_status: status,
                 models: Vec::new(),
+                catalog: Vec::new(),
+                cached_at: None,
             };
         }
     };
@@ -330,6 +425,8 @@ async fn validate_gemini(api_key: &str, model_hint: Option<&str>) -> ProviderVal
         return ProviderValidationResult {
             validation_status: KeyValidationState::NetworkError,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         };
     };
 
@@ -342,11 +439,15 @@ async fn validate_gemini(api_key: &str, model_hint: Option<&str>) -> ProviderVal
             ProviderValidationResult {
                 validation_status: KeyValidationState::Valid,
                 models: dedupe_and_sort(merged),
+                catalog: Vec::new(),
+                cached_at: None,
             }
         }
         Err(status) => ProviderValidationResult {
             validation_status: status,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         },
     }
 }
@@ -431,6 +532,8 @@ async fn validate_grok(api_key: &str, model_hint: Option<&str>) -> ProviderValid
             return ProviderValidationResult {
                 validation_status: status,
                 models: Vec::new(),
+                catalog: Vec::new(),
+                cached_at: None,
             };
         }
     };
@@ -439,6 +542,8 @@ async fn validate_grok(api_key: &str, model_hint: Option<&str>) -> ProviderValid
         return ProviderValidationResult {
             validation_status: KeyValidationState::NetworkError,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         };
     };
 
@@ -451,11 +556,15 @@ async fn validate_grok(api_key: &str, model_hint: Option<&str>) -> ProviderValid
             ProviderValidationResult {
                 validation_status: KeyValidationState::Valid,
                 models: dedupe_and_sort(merged),
+                catalog: Vec::new(),
+                cached_at: None,
             }
         }
         Err(status) => ProviderValidationResult {
             validation_status: status,
             models: Vec::new(),
+            catalog: Vec::new(),
+            cached_at: None,
         },
     }
 }
@@ -1,4 +1,5 @@
 /// Logging and metrics for placeholder validation
+use crate::paths::app_data_dir;
 use crate::placeholder_validator::{ValidationErrorCode, ValidationFailureReport};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -193,7 +194,15 @@ impl ValidationLogger {
         }
     }
 
-    /// Get current metrics
+    /// Log the outcome of translating a segment again after it failed
+    /// validation. Failed retries are counted through the failure report.
+    pub fn log_retry(&self, success: bool) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.record_retry(success);
+        }
+    }
+
+        /// Get current metrics
     pub fn get_metrics(&self) -> ValidationMetrics {
         self.metrics
             .lock()
@@ -238,10 +247,7 @@ pub fn init_validation_logging<P: AsRef<Path>>(path: P) -> Result<(), String> {
 
 /// Get log file path for current session
 pub fn get_validation_log_path() -> PathBuf {
-    let app_dir = dirs::data_local_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("mod-translator")
-        .join("logs");
+    let app_dir = app_data_dir().join("logs");
 
     std::fs::create_dir_all(&app_dir).ok();
 
@@ -250,26 +256,26 @@ pub fn get_validation_log_path() -> PathBuf {
 }
 
 /// Tauri command to get validation metrics
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub async fn get_validation_metrics() -> Result<ValidationMetrics, String> {
     Ok(validation_logger().get_metrics())
 }
 
 /// Tauri command to reset validation metrics
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub async fn reset_validation_metrics() -> Result<(), String> {
     validation_logger().reset_metrics();
     Ok(())
 }
 
 /// Tauri command to export metrics as JSON
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub async fn export_validation_metrics() -> Result<String, String> {
     validation_logger().export_metrics_json()
 }
 
 /// Tauri command to get validation log file path
-#[tauri::command]
+#[cfg_attr(feature = "tauri", tauri::command)]
 pub async fn get_validation_log_file_path() -> Result<String, String> {
     Ok(get_validation_log_path().to_string_lossy().to_string())
 }
@@ -320,6 +326,7 @@ mod tests {
             source_line: "".to_string(),
             preprocessed_source: "".to_string(),
             candidate_line: "".to_string(),
+            token_types: vec![],
             autofix: AutofixResult {
                 applied: true,
                 steps: vec![],
@@ -0,0 +1,490 @@
+/// Watch mode: automatic re-translation when mods update.
+///
+/// A file watcher follows selected mod directories or a whole Workshop
+/// folder. Changes are collected per mod until the mod has been quiet for
+/// [`DEBOUNCE`], then an incremental translation job for the mod's source
+/// files is queued from the job template given when watching started, and a
+/// `translation-auto-started` event is emitted. Files written by those jobs
+/// do not trigger another run unless their contents changed afterwards.
+/// Mods can be excluded one by one; the exclusions are kept across restarts.
+use crate::access_mode::ensure_writable;
+use crate::backup::write_atomic;
+use crate::install::{job_outputs, JobOutput};
+use crate::job::queue::JobPriority;
+use crate::job::sink::ProgressSink;
+use crate::jobs::{
+    get_job_status, queue_translation_job, StartTranslationJobPayload, TranslationFileInput,
+};
+use crate::library::{is_translation_source, list_mod_files_with};
+use crate::paths::app_data_dir;
+use chrono::Utc;
+use log::{info, warn};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+#[cfg(feature = "tauri")]
+use tauri::AppHandle;
+
+/// Quiet time after the last change before a mod is re-translated; Steam
+/// writes an update as many separate files
+pub const DEBOUNCE: Duration = Duration::from_secs(10);
+const TICK: Duration = Duration::from_secs(1);
+const WATCH_SETTINGS_FILE: &str = "watch.json";
+pub const AUTO_STARTED_EVENT: &str = "translation-auto-started";
+
+/// Job states in which a mod's previous automatic job still owns its files
+const ACTIVE_JOB_STATUSES: [&str; 4] = ["pending", "running", "paused", "awaiting_approval"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchRootKind {
+    /// A single mod directory
+    Mod,
+    /// A folder whose subdirectories are mods, e.g. `workshop/content/294100`
+    Workshop,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchRoot {
+    pub path: String,
+    pub kind: WatchRootKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchStatus {
+    pub active: bool,
+    pub roots: Vec<WatchRoot>,
+    pub disabled_mods: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationAutoStartedPayload {
+    pub job_id: String,
+    pub mod_path: String,
+    /// Changed source files that triggered the job, relative to the mod
+    pub changed_files: Vec<String>,
+    /// Files the job translates
+    pub file_count: usize,
+}
+
+/// Settings kept across restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchSettings {
+    #[serde(default)]
+    disabled_mods: BTreeSet<String>,
+}
+
+fn watch_settings_path() -> PathBuf {
+    app_data_dir().join(WATCH_SETTINGS_FILE)
+}
+
+impl WatchSettings {
+    fn load() -> Self {
+        fs::read_to_string(watch_settings_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let contents = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        write_atomic(&watch_settings_path(), &contents)
+            .map_err(|e| format!("감시 설정을 저장하지 못했습니다: {}", e))
+    }
+}
+
+struct WatchService {
+    /// Dropping the watcher ends the worker thread
+    _watcher: RecommendedWatcher,
+    roots: Vec<WatchRoot>,
+    template: StartTranslationJobPayload,
+    /// Automatic jobs started per mod directory
+    auto_jobs: HashMap<PathBuf, Vec<String>>,
+}
+
+static WATCH: Lazy<Mutex<Option<WatchService>>> = Lazy::new(|| Mutex::new(None));
+
+/// Mod directory a changed path belongs to
+fn mod_dir_for(roots: &[WatchRoot], path: &Path) -> Option<PathBuf> {
+    roots.iter().find_map(|root| {
+        let root_path = Path::new(&root.path);
+        let relative = path.strip_prefix(root_path).ok()?;
+        match root.kind {
+            WatchRootKind::Mod => Some(root_path.to_path_buf()),
+            WatchRootKind::Workshop => {
+                let mut components = relative.components();
+                let Some(Component::Normal(mod_name)) = components.next() else {
+                    return None;
+                };
+                // Files directly inside the Workshop folder belong to no mod
+                components.next()?;
+                Some(root_path.join(mod_name))
+            }
+        }
+    })
+}
+
+fn sha256_file(path: &Path) -> Option<String> {
+    fs::read(path)
+        .ok()
+        .map(|contents| hex::encode(Sha256::digest(contents)))
+}
+
+/// Changed files that call for re-translation, relative to the mod: source
+/// files and archives, except outputs still holding what a job wrote
+fn triggering_changes(
+    mod_dir: &Path,
+    changed: &HashSet<PathBuf>,
+    outputs: &[JobOutput],
+) -> Vec<String> {
+    let mut triggers: Vec<String> = changed
+        .iter()
+        .filter(|path| is_translation_source(mod_dir, path))
+        .filter(|path| {
+            let written_by_job = outputs.iter().any(|output| {
+                output.absolute_path == **path
+                    && output.sha256.is_some()
+                    && output.sha256 == sha256_file(path)
+            });
+            !written_by_job
+        })
+        .filter_map(|path| path.strip_prefix(mod_dir).ok())
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .collect();
+    triggers.sort();
+    triggers
+}
+
+fn is_job_pending(job_id: &str) -> bool {
+    matches!(
+        get_job_status(job_id.to_string()),
+        Ok(Some(snapshot)) if ACTIVE_JOB_STATUSES.contains(&snapshot.status.as_str())
+    )
+}
+
+enum Outcome {
+    Done,
+    /// An earlier automatic job for the mod is still queued or running
+    Retry,
+}
+
+/// Queues an incremental job for `mod_dir` if its changes need one
+fn handle_mod_changes(
+    sink: &Arc<dyn ProgressSink>,
+    mod_dir: &Path,
+    changed: &HashSet<PathBuf>,
+) -> Outcome {
+    let mod_path = mod_dir.to_string_lossy().to_string();
+    if WatchSettings::load().disabled_mods.contains(&mod_path) {
+        return Outcome::Done;
+    }
+    let (template, previous_jobs) = {
+        let Ok(guard) = WATCH.lock() else {
+            return Outcome::Done;
+        };
+        let Some(service) = guard.as_ref() else {
+            return Outcome::Done;
+        };
+        let previous_jobs = service.auto_jobs.get(mod_dir).cloned().unwrap_or_default();
+        (service.template.clone(), previous_jobs)
+    };
+    if previous_jobs.iter().any(|job_id| is_job_pending(job_id)) {
+        return Outcome::Retry;
+    }
+
+    let outputs: Vec<JobOutput> = previous_jobs
+        .iter()
+        .flat_map(|id| job_outputs(id))
+        .collect();
+    let changed_files = triggering_changes(mod_dir, changed, &outputs);
+    if changed_files.is_empty() || !mod_dir.is_dir() {
+        return Outcome::Done;
+    }
+
+    let listing = match list_mod_files_with(sink.as_ref(), mod_path.clone()) {
+        Ok(listing) => listing,
+        Err(error) => {
+            warn!("watch mode could not list {}: {}", mod_path, error);
+            return Outcome::Done;
+        }
+    };
+    let files: Vec<TranslationFileInput> = listing
+        .files
+        .into_iter()
+        .filter(|file| file.translatable && file.auto_selected)
+        .map(|file| TranslationFileInput {
+            relative_path: file
+                .archive_path
+                .clone()
+                .unwrap_or_else(|| file.path.clone()),
+            mod_install_path: file.mod_install_path,
+            archive_entry_path: file.archive_path.is_some().then_some(file.path),
+            archive_path: file.archive_path,
+        })
+        .collect();
+    if files.is_empty() {
+        return Outcome::Done;
+    }
+
+    let mod_name = mod_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mod".into());
+    let job_id = format!("auto-{}-{}", mod_name, Utc::now().format("%Y%m%d%H%M%S"));
+    let file_count = files.len();
+    let mut payload = template;
+    payload.job_id = job_id.clone();
+    payload.files = files;
+    payload.incremental = true;
+
+    if let Err(error) = queue_translation_job(sink.clone(), payload, JobPriority::Low) {
+        warn!(
+            "watch mode could not queue a job for {}: {}",
+            mod_path, error
+        );
+        return Outcome::Done;
+    }
+    info!(
+        "watch mode queued {} for {} changed files",
+        job_id,
+        changed_files.len()
+    );
+    if let Ok(mut guard) = WATCH.lock() {
+        if let Some(service) = guard.as_mut() {
+            service
+                .auto_jobs
+                .entry(mod_dir.to_path_buf())
+                .or_default()
+                .push(job_id.clone());
+        }
+    }
+    let event = TranslationAutoStartedPayload {
+        job_id,
+        mod_path,
+        changed_files,
+        file_count,
+    };
+    sink.emit(AUTO_STARTED_EVENT, event);
+    Outcome::Done
+}
+
+/// Collects watcher events per mod and handles each mod once it is quiet
+fn run_worker(
+    sink: Arc<dyn ProgressSink>,
+    roots: Vec<WatchRoot>,
+    events: mpsc::Receiver<notify::Result<Event>>,
+) {
+    let mut pending: HashMap<PathBuf, (Instant, HashSet<PathBuf>)> = HashMap::new();
+    loop {
+        match events.recv_timeout(TICK) {
+            Ok(Ok(event)) if !event.kind.is_access() => {
+                for path in event.paths {
+                    if let Some(mod_dir) = mod_dir_for(&roots, &path) {
+                        let entry = pending
+                            .entry(mod_dir)
+                            .or_insert_with(|| (Instant::now(), HashSet::new()));
+                        entry.0 = Instant::now();
+                        entry.1.insert(path);
+                    }
+                }
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => warn!("file watcher error: {}", error),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let quiet: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, (last_change, _))| last_change.elapsed() >= DEBOUNCE)
+            .map(|(mod_dir, _)| mod_dir.clone())
+            .collect();
+        for mod_dir in quiet {
+            let Some((_, changed)) = pending.remove(&mod_dir) else {
+                continue;
+            };
+            if let Outcome::Retry = handle_mod_changes(&sink, &mod_dir, &changed) {
+                pending.insert(mod_dir, (Instant::now(), changed));
+            }
+        }
+    }
+}
+
+fn current_status() -> Result<WatchStatus, String> {
+    let guard = WATCH
+        .lock()
+        .map_err(|_| "watch lock poisoned".to_string())?;
+    Ok(WatchStatus {
+        active: guard.is_some(),
+        roots: guard
+            .as_ref()
+            .map(|service| service.roots.clone())
+            .unwrap_or_default(),
+        disabled_mods: WatchSettings::load().disabled_mods.into_iter().collect(),
+    })
+}
+
+/// Tauri command to start watching `roots`. Automatic jobs copy `template`
+/// (provider, model, languages and options); its `jobId` and `files` are
+/// replaced for every job. Replaces any watch already running.
+#[cfg(feature = "tauri")]
+#[tauri::command]
+pub fn start_watch_mode(
+    app: AppHandle,
+    roots: Vec<WatchRoot>,
+    template: StartTranslationJobPayload,
+) -> Result<WatchStatus, String> {
+    start_watch_mode_with(Arc::new(app), roots, template)
+}
+
+/// [`start_watch_mode`] reporting automatic jobs to `sink`
+pub fn start_watch_mode_with(
+    sink: Arc<dyn ProgressSink>,
+    roots: Vec<WatchRoot>,
+    template: StartTranslationJobPayload,
+) -> Result<WatchStatus, String> {
+    ensure_writable("자동 번역 감시")?;
+    if roots.is_empty() {
+        return Err("감시할 폴더를 하나 이상 선택해야 합니다.".into());
+    }
+    for root in &roots {
+        if !Path::new(&root.path).is_dir() {
+            return Err(format!("감시할 폴더를 찾을 수 없습니다: {}", root.path));
+        }
+    }
+
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = sender.send(event);
+    })
+    .map_err(|e| format!("파일 감시를 시작하지 못했습니다: {}", e))?;
+    for root in &roots {
+        watcher
+            .watch(Path::new(&root.path), RecursiveMode::Recursive)
+            .map_err(|e| format!("파일 감시를 시작하지 못했습니다 ({}): {}", root.path, e))?;
+    }
+
+    let worker_roots = roots.clone();
+    std::thread::spawn(move || run_worker(sink, worker_roots, events));
+    let mut guard = WATCH
+        .lock()
+        .map_err(|_| "watch lock poisoned".to_string())?;
+    let auto_jobs = guard
+        .take()
+        .map(|previous| previous.auto_jobs)
+        .unwrap_or_default();
+    *guard = Some(WatchService {
+        _watcher: watcher,
+        roots,
+        template,
+        auto_jobs,
+    });
+    drop(guard);
+    current_status()
+}
+
+/// Tauri command to stop watching. Jobs already queued keep running.
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn stop_watch_mode() -> Result<WatchStatus, String> {
+    WATCH
+        .lock()
+        .map_err(|_| "watch lock poisoned".to_string())?
+        .take();
+    current_status()
+}
+
+/// Tauri command to include or exclude one mod from automatic re-translation
+#[cfg_attr(feature = "tauri", tauri::command)]
+#[allow(non_snake_case)]
+pub fn set_watch_mod_enabled(modPath: String, enabled: bool) -> Result<WatchStatus, String> {
+    let mut settings = WatchSettings::load();
+    if enabled {
+        settings.disabled_mods.remove(&modPath);
+    } else {
+        settings.disabled_mods.insert(modPath);
+    }
+    settings.save()?;
+    current_status()
+}
+
+#[cfg_attr(feature = "tauri", tauri::command)]
+pub fn get_watch_status() -> Result<WatchStatus, String> {
+    current_status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_map_to_their_mod_directory() {
+        let roots = vec![
+            WatchRoot {
+                path: "/steam/workshop/content/294100".into(),
+                kind: WatchRootKind::Workshop,
+            },
+            WatchRoot {
+                path: "/games/RimWorld/Mods/Local".into(),
+                kind: WatchRootKind::Mod,
+            },
+        ];
+        let workshop = Path::new("/steam/workshop/content/294100");
+
+        assert_eq!(
+            mod_dir_for(&roots, &workshop.join("123/Languages/English/Keyed/UI.xml")),
+            Some(workshop.join("123"))
+        );
+        assert_eq!(mod_dir_for(&roots, &workshop.join("stray.txt")), None);
+        assert_eq!(
+            mod_dir_for(
+                &roots,
+                Path::new("/games/RimWorld/Mods/Local/About/About.xml")
+            ),
+            Some(PathBuf::from("/games/RimWorld/Mods/Local"))
+        );
+        assert_eq!(mod_dir_for(&roots, Path::new("/elsewhere/file.xml")), None);
+    }
+
+    #[test]
+    fn job_outputs_do_not_retrigger_until_edited() {
+        let mod_dir = tempfile::tempdir().unwrap();
+        let source = mod_dir.path().join("Languages/English/Keyed/UI.xml");
+        let output = mod_dir.path().join("Languages/Korean/Keyed/UI.xml");
+        let archive = mod_dir.path().join("Mod.jar");
+        for path in [&source, &output, &archive] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "<LanguageData/>").unwrap();
+        }
+        let outputs = vec![JobOutput {
+            relative_path: "Mod.jar".into(),
+            absolute_path: archive.clone(),
+            backup_path: None,
+            created: false,
+            sha256: sha256_file(&archive),
+            archive_entries: vec!["assets/demo/lang/ko_kr.json".into()],
+        }];
+        let changed: HashSet<PathBuf> = [&source, &output, &archive].into_iter().cloned().collect();
+
+        assert_eq!(
+            triggering_changes(mod_dir.path(), &changed, &outputs),
+            vec!["Languages/English/Keyed/UI.xml"]
+        );
+
+        fs::write(&archive, "updated by Steam").unwrap();
+        assert_eq!(
+            triggering_changes(mod_dir.path(), &changed, &outputs),
+            vec!["Languages/English/Keyed/UI.xml", "Mod.jar"]
+        );
+    }
+}
@@ -180,6 +180,16 @@ fn test_e2e_format_detection() {
         ("test.csv", FileFormat::Csv),
         ("test.md", FileFormat::Markdown),
         ("test.txt", FileFormat::Txt),
+        ("Strings.resx", FileFormat::Resx),
+        ("Resources.resw", FileFormat::Resx),
+        ("events_l_english.yml", FileFormat::Paradox),
+        ("StringTable.asset", FileFormat::UnityAsset),
+        ("script.rpy", FileFormat::RenPy),
+        ("config.toml", FileFormat::Toml),
+        ("load.mcfunction", FileFormat::McFunction),
+        ("chapter_1.snbt", FileFormat::Snbt),
+        ("config/ftbquests/quests/chapters/chapter_1.snbt", FileFormat::FtbQuests),
+        ("config/betterquesting/DefaultQuests.json", FileFormat::BetterQuesting),
     ];
     
     for (filename, expected_format) in test_cases {
@@ -0,0 +1,216 @@
+//! Loadability tests for translated output
+//!
+//! Each fixture mod under `fixtures/mods` is copied to a temporary folder and
+//! translated by a real job (`run_translation_job_with`) against a mock
+//! OpenAI-compatible server. The files the job writes are then read back with
+//! third-party parsers (quick-xml, serde_json, polib) to make sure the game
+//! side can load them.
+
+use mod_translator_core::job::sink::MemorySink;
+use mod_translator_core::{run_translation_job_with, StartTranslationJobPayload};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// English phrases of the fixture mods and their mock translations. The
+/// Korean text keeps the quotes, entities and escapes of the source, so the
+/// output is only loadable if the job puts every placeholder back in place.
+const TRANSLATIONS: &[(&str, &str)] = &[
+    ("Magic Wand", r#"\"마법\" 지팡이"#),
+    ("Deals", "대상에게"),
+    ("damage to", "피해"),
+    ("mobs", "몹"),
+    ("Welcome", "환영합니다"),
+    ("plasteel sword", "플라스틸 검"),
+    ("A sword made of", "재료"),
+    ("Sharp", "날카롭고"),
+    ("light.", "가볍다."),
+    ("crafted", "제작함"),
+    ("stored it.", "보관했습니다."),
+    ("Keep", "불에서"),
+    ("away", "멀리"),
+    ("from fire!", "떨어지세요!"),
+    ("Start game", "게임 시작"),
+    ("Loaded", "불러온 모드"),
+    ("mods", "개"),
+];
+
+/// Answers OpenAI chat requests the way a model does for line-by-line jobs:
+/// known phrases are translated and everything else (keys, markup, masked
+/// placeholders) comes back as sent
+struct MockTranslator;
+
+impl Respond for MockTranslator {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let text = body["messages"]
+            .as_array()
+            .and_then(|messages| messages.last())
+            .and_then(|message| message["content"].as_str())
+            .unwrap_or_default();
+        let translation = TRANSLATIONS
+            .iter()
+            .fold(text.to_string(), |text, (source, target)| {
+                text.replace(source, target)
+            });
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{ "message": { "content": translation } }]
+        }))
+    }
+}
+
+fn fixture_mod(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/mods")
+        .join(name)
+}
+
+/// Copies `from` into `to` and returns the copied files, relative to `to`
+fn copy_mod(from: &Path, to: &Path, prefix: &str, files: &mut Vec<String>) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).expect("fixture directory") {
+        let entry = entry.expect("fixture entry");
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = format!("{prefix}{name}");
+        if entry.path().is_dir() {
+            copy_mod(
+                &entry.path(),
+                &to.join(&name),
+                &format!("{relative}/"),
+                files,
+            );
+        } else {
+            fs::copy(entry.path(), to.join(&name)).unwrap();
+            files.push(relative);
+        }
+    }
+}
+
+/// Translates every file of a fixture mod into Korean with a job and returns
+/// the copied mod folder the job wrote into
+async fn translate_mod(name: &str) -> TempDir {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(MockTranslator)
+        .mount(&server)
+        .await;
+
+    let mod_dir = TempDir::new().unwrap();
+    let mut files = Vec::new();
+    copy_mod(&fixture_mod(name), mod_dir.path(), "", &mut files);
+    files.sort();
+
+    let mod_path = mod_dir.path().to_string_lossy().to_string();
+    let payload: StartTranslationJobPayload = serde_json::from_value(serde_json::json!({
+        "jobId": format!("loadable-{name}-{}", uuid::Uuid::new_v4()),
+        "provider": "gpt",
+        "modelId": "gpt-4o-mini",
+        "targetLang": "ko",
+        "files": files
+            .iter()
+            .map(|file| serde_json::json!({ "relativePath": file, "modInstallPath": mod_path }))
+            .collect::<Vec<_>>(),
+        "apiBaseUrl": server.uri(),
+        "modelParams": { "structuredOutput": false },
+        "prompts": { "default": { "system": "", "user": "{{text}}" } },
+    }))
+    .unwrap();
+
+    let sink = MemorySink::new();
+    run_translation_job_with(&sink, payload, "test-key".into())
+        .await
+        .expect("job starts");
+    let status = sink
+        .payloads("translation-progress")
+        .last()
+        .and_then(|payload| payload["status"].as_str().map(str::to_string));
+    assert_eq!(status.as_deref(), Some("completed"), "{:#?}", sink.events());
+    mod_dir
+}
+
+/// Parses an XML file strictly and returns the text of each leaf element
+fn load_xml(path: &Path) -> HashMap<String, String> {
+    let xml = fs::read_to_string(path).unwrap();
+    let mut reader = Reader::from_str(&xml);
+    let mut open: Vec<String> = Vec::new();
+    let mut texts = HashMap::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => {
+                open.push(String::from_utf8_lossy(start.name().as_ref()).to_string())
+            }
+            Ok(Event::End(_)) => {
+                open.pop();
+            }
+            Ok(Event::Text(text)) => {
+                let value = text
+                    .unescape()
+                    .unwrap_or_else(|e| panic!("{}: bad text: {}", path.display(), e));
+                if let (Some(element), false) = (open.last(), value.trim().is_empty()) {
+                    texts.insert(element.clone(), value.to_string());
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => panic!(
+                "{} is not loadable at {}: {}",
+                path.display(),
+                reader.error_position(),
+                error
+            ),
+        }
+    }
+    assert!(open.is_empty(), "{}: unclosed elements", path.display());
+    texts
+}
+
+#[tokio::test]
+async fn rimworld_output_loads_in_an_xml_parser() {
+    let mod_dir = translate_mod("rimworld").await;
+    let korean = mod_dir.path().join("Languages/Korean");
+
+    let keyed = load_xml(&korean.join("Keyed/Misc.xml"));
+    assert_eq!(keyed["Demo_Crafted"], "{0} 제작함 {1} & 보관했습니다.");
+    assert_eq!(keyed["Demo_Warning"], "불에서 <b>멀리</b> 떨어지세요!");
+
+    let injected = load_xml(&korean.join("DefInjected/ThingDef/Weapons.xml"));
+    assert_eq!(injected["Demo_Sword.label"], "플라스틸 검");
+    assert_eq!(
+        injected["Demo_Sword.description"],
+        "재료 \"plasteel\". 날카롭고 & 가볍다."
+    );
+}
+
+#[tokio::test]
+async fn minecraft_output_loads_in_a_json_parser() {
+    let mod_dir = translate_mod("minecraft").await;
+
+    let json = fs::read_to_string(mod_dir.path().join("assets/demo/lang/ko_kr.json")).unwrap();
+    let lang: HashMap<String, String> =
+        serde_json::from_str(&json).expect("lang file is a flat string map");
+    assert_eq!(lang.len(), 3);
+    assert_eq!(lang["item.demo.wand"], "\"마법\" 지팡이");
+    assert_eq!(
+        lang["item.demo.wand.tooltip"],
+        "대상에게 %s 피해 \"undead\" 몹"
+    );
+    assert_eq!(lang["chat.demo.welcome"], "§a환영합니다, %1$s!");
+}
+
+#[tokio::test]
+async fn gettext_output_loads_in_a_po_parser() {
+    let mod_dir = translate_mod("gettext").await;
+
+    // Line-by-line jobs translate the msgid lines (PoHandler does not fill in
+    // msgstr yet); the catalog must still load
+    let catalog = polib::po_file::parse(&mod_dir.path().join("locale/messages.ko.po"))
+        .expect("PO file is loadable");
+    let msgids: Vec<&str> = catalog.messages().map(|message| message.msgid()).collect();
+    assert_eq!(msgids, vec!["게임 시작", "불러온 모드 %d 개"]);
+}